@@ -2,7 +2,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use colored::Colorize;
 
@@ -19,35 +19,47 @@ use carina_core::executor::{
 };
 use carina_core::override_aware::OverrideAwareResources;
 use carina_core::plan::Plan;
-use carina_core::provider::{self as provider_mod, Provider, ProviderNormalizer, ReadRequest};
+use carina_core::plan_changes::PlanChanges;
+use carina_core::provider::{
+    self as provider_mod, BlockedResource, Provider, ProviderNormalizer, ReadRequest,
+};
 #[cfg(test)]
 use carina_core::resource::ConcreteValue;
 use carina_core::resource::{DataSource, Resource, ResourceId, State, Value};
-use carina_core::value::format_value;
+use carina_core::value::{format_value, redact_secrets_in_plan, redact_secrets_in_state};
 use carina_state::{BackendLock, LockInfo, StateBackend, StateFile};
 use tokio_util::sync::CancellationToken;
 
 use carina_core::parser::{ProviderConfig, ProviderContext};
 
-use super::{DriftCommand, validate_and_resolve_with_config, verify_for_mutation};
+use super::{
+    DriftCommand, load_bound_configuration, validate_and_resolve_with_config, verify_for_mutation,
+};
 use crate::DetailLevel;
 use crate::commands::plan::{PlanFile, collect_delete_attributes};
+use crate::commands::shared::api_budget::{
+    print_api_budget_warnings, print_credential_expiry_warnings, record_api_call_stats,
+    save_api_call_stats,
+};
+use crate::commands::shared::apply_metrics::report_apply_metrics;
 use crate::commands::shared::effect_execution::{
     execute_import_effects, execute_state_only_effects,
 };
 use crate::commands::shared::finalize::handle_finalize_after_execute;
-use crate::commands::shared::observer::CliObserver;
-use crate::commands::shared::plan_errors::render_plan_errors_and_abort;
+use crate::commands::shared::observer::{CliObserver, JsonObserver};
+use crate::commands::shared::plan_errors::{print_blocked_resources, render_plan_errors_and_abort};
 use crate::commands::shared::progress::{
     RefreshProgress, emit_newline_on_interrupt, format_duration, refresh_multi_progress,
 };
 use crate::commands::shared::state_writeback::{
     ApplyStateSave, FinalizeApplyInput, PostApplyStates, build_state_after_apply, resolve_exports,
 };
+use crate::commands::shared::trace_export::{export_apply_trace, export_plan_trace, trace_apply};
 use crate::commands::state::map_lock_error;
 use crate::cursor::CursorReveal;
 use crate::display::print_plan;
 use crate::error::AppError;
+use crate::events::{self, ApplyEvent};
 use crate::wiring::{
     DataSourceRefreshResolution, WiringContext, build_factories_from_providers,
     create_providers_from_configs, get_provider_with_ctx, prepare_data_sources_for_plan,
@@ -61,7 +73,31 @@ pub type ApplyResult = ExecutionResult;
 type ObserverFactory<'a> = dyn Fn(&Plan) -> Box<dyn ExecutionObserver> + 'a;
 
 fn cli_observer_factory(plan: &Plan) -> Box<dyn ExecutionObserver> {
-    Box::new(CliObserver::new(plan))
+    if events::enabled() {
+        Box::new(JsonObserver)
+    } else {
+        Box::new(CliObserver::new(plan))
+    }
+}
+
+/// Open the `--json` event stream with the plan being applied, redacted
+/// the same way as a saved plan's `changes` section.
+fn emit_plan_started(
+    plan: &Plan,
+    current_states: &HashMap<ResourceId, State>,
+) -> Result<(), AppError> {
+    if !events::enabled() {
+        return Ok(());
+    }
+    let plan = redact_secrets_in_plan(plan)?;
+    let current_states = current_states
+        .iter()
+        .map(|(id, state)| Ok((id.clone(), redact_secrets_in_state(state)?)))
+        .collect::<Result<HashMap<_, _>, carina_core::value::SerializationError>>()?;
+    events::emit(&ApplyEvent::PlanStarted {
+        resource_changes: PlanChanges::from_plan(&plan, &current_states).resource_changes,
+    });
+    Ok(())
 }
 
 fn format_total_apply_line(elapsed: Duration) -> String {
@@ -368,8 +404,8 @@ pub async fn refresh_pending_states(
         return HashSet::new();
     }
 
-    println!();
-    println!("{}", "Refreshing uncertain resource states...".cyan());
+    human_println!();
+    human_println!("{}", "Refreshing uncertain resource states...".cyan());
 
     let mut refreshes: Vec<_> = pending_refreshes.iter().collect();
     refreshes.sort_by_key(|(left_id, _)| left_id.to_string());
@@ -378,11 +414,11 @@ pub async fn refresh_pending_states(
     for (id, identifier) in refreshes {
         match read_with_retry(provider, id, Some(identifier)).await {
             Ok(state) => {
-                println!("  {} Refresh {}", "✓".green(), id);
+                human_println!("  {} Refresh {}", "✓".green(), id);
                 current_states.insert(id.clone(), state);
             }
             Err(error) => {
-                println!("  {} Refresh {} - {}", "!".yellow(), id, error);
+                human_println!("  {} Refresh {} - {}", "!".yellow(), id, error);
                 failed_refreshes.insert(id.clone());
             }
         }
@@ -391,13 +427,33 @@ pub async fn refresh_pending_states(
     failed_refreshes
 }
 
+/// Set the values provenance tags carry: `carina:project` is the state
+/// lineage and `carina:state` names where `backend` keeps the state. A
+/// first apply has no state yet, so pin a fresh lineage now for
+/// `finalize_apply` to persist, keeping the tag and the saved lineage
+/// in agreement.
+fn set_provenance_values(
+    provider: &mut provider_mod::ProviderRouter,
+    state_file: &mut Option<StateFile>,
+    backend: Option<&carina_core::parser::BackendConfig>,
+) {
+    let lineage = state_file
+        .get_or_insert_with(StateFile::new)
+        .lineage
+        .clone();
+    let location = backend
+        .map(|b| carina_state::BackendConfig::from(b).state_location())
+        .unwrap_or_else(|| carina_state::LocalBackend::DEFAULT_STATE_FILE.to_string());
+    provider.set_provenance_values(lineage, location);
+}
+
 /// Save state after apply. Does NOT release the lock -- caller is responsible.
 ///
 /// When `lock` is `None` (i.e. `--lock=false`), state is written without lock
 /// validation via `save_state_unlocked`.
 pub(crate) async fn finalize_apply(input: FinalizeApplyInput<'_>) -> Result<(), AppError> {
-    println!();
-    println!("{}", "Saving state...".cyan());
+    human_println!();
+    human_println!("{}", "Saving state...".cyan());
 
     let mut state = build_state_after_apply(ApplyStateSave {
         state_file: input.state_file,
@@ -410,6 +466,7 @@ pub(crate) async fn finalize_apply(input: FinalizeApplyInput<'_>) -> Result<(),
         failed_refreshes: &input.result.failed_refreshes,
         schemas: input.schemas,
     })?;
+    state.record_operation_outcomes(&input.result.operation_outcomes, chrono::Utc::now());
 
     // `Some([])` is meaningful: the user removed the `exports {}` block,
     // so stale entries the previous apply persisted must be cleared
@@ -436,7 +493,10 @@ pub(crate) async fn finalize_apply(input: FinalizeApplyInput<'_>) -> Result<(),
     } else {
         save_state_unlocked(input.backend, &mut state).await?;
     }
-    println!("  {} State saved (serial: {})", "✓".green(), state.serial);
+    human_println!("  {} State saved (serial: {})", "✓".green(), state.serial);
+    events::emit(&ApplyEvent::StateSaved {
+        serial: state.serial,
+    });
 
     Ok(())
 }
@@ -545,8 +605,11 @@ pub(crate) async fn persist_exports_only(
     } else {
         save_state_unlocked(backend, &mut state).await?;
     }
-    println!("  {} State saved (serial: {})", "✓".green(), state.serial);
-    println!("  {} Exports updated", "✓".green());
+    human_println!("  {} State saved (serial: {})", "✓".green(), state.serial);
+    events::emit(&ApplyEvent::StateSaved {
+        serial: state.serial,
+    });
+    human_println!("  {} Exports updated", "✓".green());
     Ok(())
 }
 
@@ -654,8 +717,10 @@ pub async fn detect_drift(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run_apply(
     path: &Path,
+    var_file: Option<&Path>,
     auto_approve: bool,
     lock: bool,
     parallelism: NonZeroUsize,
@@ -665,6 +730,7 @@ pub async fn run_apply(
 ) -> Result<(), AppError> {
     run_apply_with_observer_factory(
         path,
+        var_file,
         auto_approve,
         lock,
         parallelism,
@@ -680,6 +746,7 @@ pub async fn run_apply(
 #[allow(clippy::too_many_arguments)]
 async fn run_apply_with_observer_factory(
     path: &Path,
+    var_file: Option<&Path>,
     auto_approve: bool,
     lock: bool,
     parallelism: NonZeroUsize,
@@ -688,11 +755,7 @@ async fn run_apply_with_observer_factory(
     cancel: CancellationToken,
     observer_factory: &ObserverFactory<'_>,
 ) -> Result<(), AppError> {
-    let loaded = load_configuration_with_config(
-        path,
-        provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    )?;
+    let loaded = load_bound_configuration(path, var_file, provider_context)?;
     let mut parsed = loaded.parsed;
     let mut unresolved_parsed = loaded.unresolved_parsed;
     let backend_file = loaded.backend_file;
@@ -720,7 +783,7 @@ async fn run_apply_with_observer_factory(
         let bucket_exists = backend.bucket_exists().await.map_err(AppError::Backend)?;
 
         if !bucket_exists {
-            println!(
+            human_println!(
                 "{}",
                 "State bucket not found. Running bootstrap..."
                     .yellow()
@@ -740,8 +803,8 @@ async fn run_apply_with_observer_factory(
             if let Some(bucket_resource) =
                 parsed.find_resource_by_attr(backend_resource_type, "bucket", &bucket_name)
             {
-                println!("Found state bucket resource in configuration.");
-                println!(
+                human_println!("Found state bucket resource in configuration.");
+                human_println!(
                     "Creating bucket '{}' before other resources...",
                     bucket_name.cyan()
                 );
@@ -782,17 +845,21 @@ async fn run_apply_with_observer_factory(
                             ))
                         })?;
 
+                let client_token =
+                    carina_core::executor::client_token(resolved_bucket.as_resource());
                 match bucket_provider
                     .create(
                         &bucket_resource.id,
                         carina_core::provider::CreateRequest {
                             resource: resolved_bucket,
+                            timeout: None,
+                            client_token: Some(client_token),
                         },
                     )
                     .await
                 {
                     Ok(_) => {
-                        println!("  {} Created state bucket: {}", "✓".green(), bucket_name);
+                        human_println!("  {} Created state bucket: {}", "✓".green(), bucket_name);
                     }
                     Err(e) => {
                         return Err(AppError::Config(format!(
@@ -808,9 +875,9 @@ async fn run_apply_with_observer_factory(
                     .unwrap_or(true);
 
                 if auto_create {
-                    println!("Auto-creating state bucket: {}", bucket_name.cyan());
+                    human_println!("Auto-creating state bucket: {}", bucket_name.cyan());
                     backend.create_bucket().await.map_err(AppError::Backend)?;
-                    println!("  {} Created state bucket", "✓".green());
+                    human_println!("  {} Created state bucket", "✓".green());
 
                     let backend_provider_name = backend
                         .provider_name()
@@ -835,7 +902,7 @@ async fn run_apply_with_observer_factory(
 
                     fs::write(&target_file, &content)
                         .map_err(|e| format!("Failed to write {}: {}", target_file.display(), e))?;
-                    println!(
+                    human_println!(
                         "  {} Added resource definition to {}",
                         "✓".green(),
                         target_file.display()
@@ -850,12 +917,7 @@ async fn run_apply_with_observer_factory(
                     // reuses the heavier preflight pipeline only for its
                     // resolve+anon-id steps; the schema validation already
                     // ran on the pre-injection configuration.
-                    parsed = load_configuration_with_config(
-                        path,
-                        provider_context,
-                        &carina_core::schema::SchemaRegistry::new(),
-                    )?
-                    .parsed;
+                    parsed = load_bound_configuration(path, var_file, provider_context)?.parsed;
                     validate_and_resolve_with_config(&mut parsed, base_dir, true)?;
 
                     let backend_resource_type = backend
@@ -887,7 +949,7 @@ async fn run_apply_with_observer_factory(
                         .write_state(&initial_state)
                         .await
                         .map_err(AppError::Backend)?;
-                    println!(
+                    human_println!(
                         "  {} Registered state bucket as protected resource",
                         "✓".green()
                     );
@@ -913,16 +975,16 @@ async fn run_apply_with_observer_factory(
 
     // Acquire lock (unless --lock=false)
     if lock {
-        println!("{}", "Acquiring state lock...".cyan());
+        human_println!("{}", "Acquiring state lock...".cyan());
         lock_info = Some(
             backend
                 .acquire_lock("apply")
                 .await
                 .map_err(map_lock_error)?,
         );
-        println!("  {} Lock acquired", "✓".green());
+        human_println!("  {} Lock acquired", "✓".green());
     } else {
-        println!(
+        human_println!(
             "{}",
             "Warning: State locking is disabled. This is unsafe if others might run commands against the same state."
                 .yellow()
@@ -955,17 +1017,17 @@ async fn run_apply_with_observer_factory(
         if release_result.is_ok()
             && (op_result.is_ok() || matches!(op_result, Err(AppError::Interrupted)))
         {
-            println!("  {} Lock released", "✓".green());
+            human_println!("  {} Lock released", "✓".green());
         }
 
         let timing = op_result?;
         release_result?;
         if let Some(elapsed) = timing {
-            println!("{}", format_total_apply_line(elapsed));
+            human_println!("{}", format_total_apply_line(elapsed));
         }
     } else {
         if let Some(timing) = op_result? {
-            println!("{}", format_total_apply_line(timing));
+            human_println!("{}", format_total_apply_line(timing));
         }
     }
 
@@ -987,6 +1049,7 @@ async fn run_apply_locked(
     parallelism: NonZeroUsize,
     accept_legacy_name_overrides: bool,
 ) -> Result<Option<Duration>, AppError> {
+    let plan_started = SystemTime::now();
     // Read current state from backend. carina#3315: if `check_and_migrate`
     // lifted an older on-disk schema in memory, persist the upgrade
     // under the current lock before any short-circuit path can return
@@ -1070,7 +1133,8 @@ async fn run_apply_locked(
     .map_err(|e| AppError::Config(format!("Provider attribute resolution error: {}", e)))?;
 
     // Select appropriate Provider based on configuration
-    let provider = get_provider_with_ctx(ctx, parsed, base_dir).await?;
+    let mut provider = get_provider_with_ctx(ctx, parsed, base_dir).await?;
+    set_provenance_values(&mut provider, &mut state_file, parsed.backend.as_ref());
 
     // carina#3132: `sorted_resources` is `mut` because deferred-for
     // expansion now runs post-refresh (after phase-2, below) via the
@@ -1086,9 +1150,16 @@ async fn run_apply_locked(
     // managed resources are dependency-sorted; data sources are
     // refreshed in a later phase against the populated `current_states`.
     let mut sorted_resources = sort_resources_by_dependencies(&parsed.resources)?;
-    let data_sources: Vec<carina_core::resource::DataSource> =
+    let mut data_sources: Vec<carina_core::resource::DataSource> =
         unresolved_parsed.data_sources.clone();
 
+    // Same scoping as the plan path: resources behind a provider instance
+    // that failed to initialize are left out, the rest is applied.
+    let blocked = provider.blocked_resources(&sorted_resources, &data_sources);
+    let blocked_ids: HashSet<ResourceId> = blocked.iter().map(|b| b.id.clone()).collect();
+    sorted_resources.retain(|r| !blocked_ids.contains(&r.id));
+    data_sources.retain(|d| !blocked_ids.contains(&d.id));
+
     // Build state-file-derived maps up front so anonymous → let-bound
     // rename transfer (#1685) can run between refresh phases 1 and 2.
     let mut saved_attrs = state_file
@@ -1185,8 +1256,11 @@ async fn run_apply_locked(
     if let Some(sf) = state_file.as_ref() {
         let desired_ids: HashSet<ResourceId> =
             sorted_resources.iter().map(|r| r.id.clone()).collect();
-        let orphan_states: Vec<(ResourceId, State)> =
-            sf.build_orphan_states(&desired_ids).into_iter().collect();
+        let orphan_states: Vec<(ResourceId, State)> = sf
+            .build_orphan_states(&desired_ids)
+            .into_iter()
+            .filter(|(id, _)| !crate::wiring::is_outside_run(&provider, &blocked_ids, id))
+            .collect();
         let orphan_results: Vec<Result<(ResourceId, State), AppError>> =
             stream::iter(orphan_states)
                 .map(|(id, state)| {
@@ -1216,6 +1290,7 @@ async fn run_apply_locked(
                 current_states.entry(id).or_insert(refreshed);
             }
         }
+        let desired_ids: HashSet<ResourceId> = desired_ids.union(&blocked_ids).cloned().collect();
         orphan_dependencies = sf.build_orphan_dependencies(&desired_ids);
     }
 
@@ -1409,8 +1484,14 @@ async fn run_apply_locked(
         &override_aware_resources,
         state_file.as_ref(),
     );
+    crate::last_operations::emit_last_operation_notes(state_file.as_ref());
     let unresolved_override_aware_resources = OverrideAwareResources::build(
-        unresolved_parsed.resources.clone(), // allow: direct — parser-internal, pre-expansion
+        unresolved_parsed
+            .resources // allow: direct — parser-internal, pre-expansion
+            .iter()
+            .filter(|r| !blocked_ids.contains(&r.id))
+            .cloned()
+            .collect(),
         state_file.as_ref(),
         carina_core::binding_index::PreApplyInputs {
             managed: &[],
@@ -1451,7 +1532,7 @@ async fn run_apply_locked(
             &data_sources_for_plan,
             &mut wait_bindings,
         )
-        .await;
+        .await?;
     let plan_input_states = carina_core::resource::into_plan_input_map(
         current_states.clone(),
         ctx.schemas(),
@@ -1529,8 +1610,9 @@ async fn run_apply_locked(
             crate::commands::plan::compute_export_diffs(&resolved_exports, current_exports);
 
         if export_changes.is_empty() {
-            println!("{}", "No changes needed.".green());
-            return Ok(None);
+            human_println!("{}", "No changes needed.".green());
+            print_blocked_resources(&blocked);
+            return blocked_outcome(&blocked).map(|()| None);
         }
 
         print_plan(
@@ -1544,6 +1626,7 @@ async fn run_apply_locked(
             None,
             None,
         );
+        print_blocked_resources(&blocked);
 
         let stdin = tokio::io::BufReader::new(tokio::io::stdin());
         if confirm_apply(stdin, cancel.clone(), auto_approve).await? == ApplyConfirmation::Cancelled
@@ -1551,7 +1634,7 @@ async fn run_apply_locked(
             return Ok(None);
         }
 
-        println!(
+        human_println!(
             "{}",
             format!(
                 "Persisting {} export change(s) to state.",
@@ -1579,7 +1662,7 @@ async fn run_apply_locked(
             &current_states,
         )
         .await?;
-        return Ok(None);
+        return blocked_outcome(&blocked).map(|()| None);
     }
 
     // Build delete attributes map from current states for display
@@ -1605,6 +1688,7 @@ async fn run_apply_locked(
         .unwrap_or_default();
     let export_changes =
         crate::commands::plan::compute_export_diffs(&resolved_exports, &current_exports);
+    let plan_trace = export_plan_trace(&plan, plan_started);
     print_plan(
         &plan,
         DetailLevel::Full,
@@ -1616,6 +1700,9 @@ async fn run_apply_locked(
         Some(&prev_explicit),
         None,
     );
+    print_blocked_resources(&blocked);
+    print_api_budget_warnings(&plan);
+    print_credential_expiry_warnings(&provider, &plan, parallelism).await;
 
     let stdin = tokio::io::BufReader::new(tokio::io::stdin());
     if confirm_apply(stdin, cancel.clone(), auto_approve).await? == ApplyConfirmation::Cancelled {
@@ -1623,8 +1710,10 @@ async fn run_apply_locked(
     }
 
     let apply_phase_started = Instant::now();
-    println!("{}", "Applying changes...".cyan().bold());
-    println!();
+    let apply_started_at = SystemTime::now();
+    human_println!("{}", "Applying changes...".cyan().bold());
+    human_println!();
+    emit_plan_started(&plan, &current_states)?;
 
     // Build unresolved resource map for re-resolution at apply time
     let unresolved_resources: HashMap<ResourceId, UnresolvedResource> =
@@ -1644,7 +1733,8 @@ async fn run_apply_locked(
     // `ProviderNormalizer`; the same object is passed in both positions
     // so apply re-normalizes with exactly the plan-time normalizer
     // (carina#3060). They must stay the same object.
-    let observer = observer_factory(&plan);
+    let (observer, api_call_stats) = record_api_call_stats(observer_factory(&plan));
+    let (observer, apply_trace) = trace_apply(observer, plan_trace);
     let outcome = execute_effects_with_observer(
         &plan,
         &provider,
@@ -1663,6 +1753,7 @@ async fn run_apply_locked(
     )
     .await;
     let (mut result, cancelled) = split_execution_outcome(outcome);
+    save_api_call_stats(&api_call_stats);
 
     // Execute import effects: read imported resources from the provider
     execute_import_effects(&plan, &provider, &mut result).await;
@@ -1670,6 +1761,8 @@ async fn run_apply_locked(
     // Execute remove and move effects (state-only, logged for user feedback)
     execute_state_only_effects(&plan, &mut result);
     let resources_finished = Instant::now();
+    report_apply_metrics(&result.operation_outcomes, apply_started_at);
+    export_apply_trace(apply_trace, &result.operation_outcomes);
 
     // Use override-aware resources (post-default_tags merge, post-canonicalize)
     // for state writeback so the per-resource `explicit` tree includes
@@ -1693,21 +1786,25 @@ async fn run_apply_locked(
     .await;
     handle_finalize_after_execute(finalize_result, cancelled)?;
 
-    println!();
+    human_println!();
     let exit_code = apply_exit_code_for_counts(
         result.failure_count + result.skip_count,
         result.partial_count,
     );
     if exit_code == ApplyExitCode::Success {
-        println!(
+        human_println!(
             "{}",
             format!("Apply complete! {} changes applied.", result.success_count)
                 .green()
                 .bold()
         );
+        blocked_outcome(&blocked)?;
         Ok(Some(resources_finished.duration_since(apply_phase_started)))
     } else {
         let mut parts = vec![format!("{} succeeded", result.success_count)];
+        if !blocked.is_empty() {
+            parts.push(format!("{} blocked", blocked.len()));
+        }
         if result.partial_count > 0 {
             parts.push(format!("{} partial", result.partial_count));
         }
@@ -1726,6 +1823,19 @@ async fn run_apply_locked(
     }
 }
 
+/// An apply that left resources out because their provider instance
+/// failed to initialize did not converge the configuration, so it exits
+/// as a partial success even when every planned change succeeded.
+fn blocked_outcome(blocked: &[BlockedResource]) -> Result<(), AppError> {
+    if blocked.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::PartialSuccess(format!(
+        "{} resource(s) blocked by a provider instance that failed to initialize.",
+        blocked.len()
+    )))
+}
+
 fn ensure_saved_plan_backend_matches_current(
     plan_backend_config: Option<&carina_core::parser::BackendConfig>,
     current_backend_config: Option<&carina_core::parser::BackendConfig>,
@@ -1820,7 +1930,7 @@ async fn run_apply_from_plan_with_observer_factory(
 
     let current_version = env!("CARGO_PKG_VERSION");
     if plan_file.carina_version != current_version {
-        println!(
+        human_println!(
             "{}",
             format!(
                 "Warning: plan was created with carina {} but current version is {}",
@@ -1830,7 +1940,7 @@ async fn run_apply_from_plan_with_observer_factory(
         );
     }
 
-    println!(
+    human_println!(
         "{}",
         format!(
             "Using saved plan from {} (created {})",
@@ -1864,15 +1974,15 @@ async fn run_apply_from_plan_with_observer_factory(
 
     // Acquire lock (unless --lock=false)
     let lock_info: Option<LockInfo> = if lock {
-        println!("{}", "Acquiring state lock...".cyan());
+        human_println!("{}", "Acquiring state lock...".cyan());
         let li = backend
             .acquire_lock("apply")
             .await
             .map_err(map_lock_error)?;
-        println!("  {} Lock acquired", "✓".green());
+        human_println!("  {} Lock acquired", "✓".green());
         Some(li)
     } else {
-        println!(
+        human_println!(
             "{}",
             "Warning: State locking is disabled. This is unsafe if others might run commands against the same state."
                 .yellow()
@@ -1901,17 +2011,17 @@ async fn run_apply_from_plan_with_observer_factory(
         if release_result.is_ok()
             && (op_result.is_ok() || matches!(op_result, Err(AppError::Interrupted)))
         {
-            println!("  {} Lock released", "✓".green());
+            human_println!("  {} Lock released", "✓".green());
         }
 
         let timing = op_result?;
         release_result?;
         if let Some(elapsed) = timing {
-            println!("{}", format_total_apply_line(elapsed));
+            human_println!("{}", format_total_apply_line(elapsed));
         }
     } else {
         if let Some(timing) = op_result? {
-            println!("{}", format_total_apply_line(timing));
+            human_println!("{}", format_total_apply_line(timing));
         }
     }
 
@@ -1962,7 +2072,7 @@ async fn run_apply_from_plan_locked(
         if let Some(plan_serial) = plan_file.state_serial
             && state.serial != plan_serial
         {
-            println!(
+            human_println!(
                 "{}",
                 format!(
                     "Warning: state serial has changed since plan was created ({} → {}). \
@@ -2003,26 +2113,31 @@ async fn run_apply_from_plan_locked(
         .collect();
 
     // Create provider early for drift detection
-    let (provider, ctx) =
+    let (mut provider, ctx) =
         create_providers_from_configs(&plan_file.provider_configs, base_dir).await?;
+    set_provenance_values(
+        &mut provider,
+        &mut state_file,
+        plan_file.backend_config.as_ref(),
+    );
 
     // Drift detection: re-read actual infrastructure state and compare against planned states
-    println!("{}", "Checking for infrastructure drift...".cyan());
+    human_println!("{}", "Checking for infrastructure drift...".cyan());
     let drift_result = detect_drift(sorted_resources, &planned_states, &provider).await?;
 
     if let Some(drift_messages) = drift_result {
-        println!();
-        println!("{}", "Error: Infrastructure drift detected!".red().bold());
-        println!(
+        human_println!();
+        human_println!("{}", "Error: Infrastructure drift detected!".red().bold());
+        human_println!(
             "{}",
             "The following resources have changed since the plan was created:".red()
         );
-        println!();
+        human_println!();
         for msg in &drift_messages {
-            println!("{}", msg);
+            human_println!("{}", msg);
         }
-        println!();
-        println!(
+        human_println!();
+        human_println!(
             "{}",
             "Please re-run 'carina plan' to create a new plan that reflects the current state."
                 .yellow()
@@ -2032,7 +2147,7 @@ async fn run_apply_from_plan_locked(
         ));
     }
 
-    println!("  {} No drift detected.", "✓".green());
+    human_println!("  {} No drift detected.", "✓".green());
 
     // Use the actual states (freshly read) as current_states for apply
     let mut current_states = planned_states;
@@ -2047,7 +2162,7 @@ async fn run_apply_from_plan_locked(
         // resource-apply pipeline. Mirrors the source-driven apply
         // path's gate (carina#3270 → run_apply_locked).
         // carina#3275.
-        println!("{}", "No changes needed.".green());
+        human_println!("{}", "No changes needed.".green());
         return Ok(None);
     }
 
@@ -2065,6 +2180,8 @@ async fn run_apply_from_plan_locked(
         None,
         None,
     );
+    print_api_budget_warnings(plan);
+    print_credential_expiry_warnings(&provider, plan, parallelism).await;
 
     let stdin = tokio::io::BufReader::new(tokio::io::stdin());
     if confirm_apply(stdin, cancel.clone(), auto_approve).await? == ApplyConfirmation::Cancelled {
@@ -2117,8 +2234,10 @@ async fn run_apply_from_plan_locked(
     });
 
     let apply_phase_started = Instant::now();
-    println!("{}", "Applying changes...".cyan().bold());
-    println!();
+    let apply_started_at = SystemTime::now();
+    human_println!("{}", "Applying changes...".cyan().bold());
+    human_println!();
+    emit_plan_started(plan, &current_states)?;
 
     // Build unresolved resource map for re-resolution at apply time from
     // the saved pre-resolution snapshot. `sorted_resources` has already
@@ -2136,7 +2255,8 @@ async fn run_apply_from_plan_locked(
     // Same object in both positions: `ProviderRouter` is both the
     // `Provider` and the `ProviderNormalizer`, so apply re-normalizes
     // with the plan-time normalizer (carina#3060).
-    let observer = observer_factory(plan);
+    let (observer, api_call_stats) = record_api_call_stats(observer_factory(plan));
+    let (observer, apply_trace) = trace_apply(observer, plan_file.trace_id);
     let outcome = execute_effects_with_observer(
         plan,
         &provider,
@@ -2155,6 +2275,7 @@ async fn run_apply_from_plan_locked(
     )
     .await;
     let (mut result, cancelled) = split_execution_outcome(outcome);
+    save_api_call_stats(&api_call_stats);
 
     // Execute import effects: read imported resources from the provider
     execute_import_effects(plan, &provider, &mut result).await;
@@ -2162,6 +2283,8 @@ async fn run_apply_from_plan_locked(
     // Execute remove and move effects (state-only, logged for user feedback)
     execute_state_only_effects(plan, &mut result);
     let resources_finished = Instant::now();
+    report_apply_metrics(&result.operation_outcomes, apply_started_at);
+    export_apply_trace(apply_trace, &result.operation_outcomes);
 
     // Build schemas for write-only attribute persistence
     let (factories, _) = build_factories_from_providers(&plan_file.provider_configs, base_dir);
@@ -2194,13 +2317,13 @@ async fn run_apply_from_plan_locked(
     .await;
     handle_finalize_after_execute(finalize_result, cancelled)?;
 
-    println!();
+    human_println!();
     let exit_code = apply_exit_code_for_counts(
         result.failure_count + result.skip_count,
         result.partial_count,
     );
     if exit_code == ApplyExitCode::Success {
-        println!(
+        human_println!(
             "{}",
             format!("Apply complete! {} changes applied.", result.success_count)
                 .green()
@@ -2267,11 +2390,11 @@ where
         return Ok(ApplyConfirmation::Confirmed);
     }
 
-    println!(
+    human_println!(
         "{}",
         "Do you want to perform these actions?".yellow().bold()
     );
-    println!(
+    human_println!(
         "  {}",
         "Carina will perform the actions described above. Type 'yes' to confirm.".yellow()
     );
@@ -2279,7 +2402,7 @@ where
     // so the user does not type blind, and re-hide on scope exit.
     let input = {
         let _reveal = CursorReveal::new();
-        print!("\n  Enter a value: ");
+        human_print!("\n  Enter a value: ");
         std::io::Write::flush(&mut std::io::stdout()).map_err(|e| e.to_string())?;
 
         let read_result = crate::signal::read_line_until_cancelled(reader, cancel).await;
@@ -2288,11 +2411,11 @@ where
     };
 
     if input.trim() != "yes" {
-        println!();
-        println!("{}", "Apply cancelled.".yellow());
+        human_println!();
+        human_println!("{}", "Apply cancelled.".yellow());
         Ok(ApplyConfirmation::Cancelled)
     } else {
-        println!();
+        human_println!();
         Ok(ApplyConfirmation::Confirmed)
     }
 }
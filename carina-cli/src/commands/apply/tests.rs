@@ -520,6 +520,7 @@ async fn saved_plan_apply_reconstructs_and_dispatches_deferred_data_source_read(
         name: "mock".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -564,6 +565,8 @@ async fn saved_plan_apply_reconstructs_and_dispatches_deferred_data_source_read(
         upstream_snapshot: HashMap::new(),
         upstream_sources: Vec::new(),
         wait_bindings: Vec::new(),
+        changes: None,
+        trace_id: None,
     };
 
     let observer_factory = fixture.observer_factory();
@@ -705,6 +708,7 @@ async fn run_apply_cancelled_after_partial_execution_persists_state_and_releases
     let observer_factory = fixture.observer_factory();
     let err = run_apply_with_observer_factory(
         fixture.config_path(),
+        None,
         true,
         true,
         NonZeroUsize::new(1).unwrap(),
@@ -760,6 +764,7 @@ async fn apply_cancel_token_integration_persists_completed_state_releases_lock_a
     let observer_factory = fixture.observer_factory();
     let err = run_apply_with_observer_factory(
         fixture.config_path(),
+        None,
         true,
         true,
         NonZeroUsize::new(1).unwrap(),
@@ -1048,6 +1053,7 @@ async fn post_apply_plan_refreshes_existing_resource_data_source_and_is_idempote
         &carina_core::identifier::StateBlockClaims::empty(),
         &crate::wiring::ResolvedStateBlockTargets::default(),
         base_dir,
+        crate::wiring::PlanChecks::default(),
     )
     .await
     .expect("post-apply plan should succeed");
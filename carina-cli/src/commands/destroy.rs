@@ -9,7 +9,7 @@ use colored::Colorize;
 use futures::stream::{FuturesUnordered, StreamExt};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
-use carina_core::config_loader::{get_base_dir, load_configuration_with_config};
+use carina_core::config_loader::get_base_dir;
 use carina_core::deps::get_resource_dependencies;
 use carina_core::effect::Effect;
 use carina_core::effect::deps::{
@@ -18,14 +18,16 @@ use carina_core::effect::deps::{
 };
 use carina_core::parser::WaitBinding;
 use carina_core::plan::Plan;
-use carina_core::provider::Provider;
+use carina_core::provider::{DeleteBlocker, Provider};
 use carina_core::resource::{ConcreteValue, Resource, ResourceId, State, Value};
 use carina_state::{LockInfo, StateBackend, StateFile};
 use tokio_util::sync::CancellationToken;
 
 use carina_core::parser::ProviderContext;
 
-use super::{DriftCommand, validate_and_resolve_with_config, verify_for_mutation};
+use super::{
+    DriftCommand, load_bound_configuration, validate_and_resolve_with_config, verify_for_mutation,
+};
 use crate::DetailLevel;
 use crate::commands::shared::finalize::handle_finalize_after_execute;
 use crate::commands::shared::progress::{
@@ -47,20 +49,17 @@ use crate::wiring::{
 #[allow(clippy::too_many_arguments)]
 pub async fn run_destroy(
     path: &Path,
+    var_file: Option<&Path>,
     auto_approve: bool,
     lock: bool,
     refresh: bool,
     force: bool,
+    force_dependencies: bool,
     parallelism: NonZeroUsize,
     provider_context: &ProviderContext,
     cancel: CancellationToken,
 ) -> Result<(), AppError> {
-    let mut parsed = load_configuration_with_config(
-        path,
-        provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    )?
-    .parsed;
+    let mut parsed = load_bound_configuration(path, var_file, provider_context)?.parsed;
 
     let base_dir = get_base_dir(path);
     validate_and_resolve_with_config(&mut parsed, base_dir, true)?;
@@ -109,6 +108,7 @@ pub async fn run_destroy(
         lock_info.as_ref(),
         refresh,
         force,
+        force_dependencies,
         base_dir,
         parallelism,
         cancel,
@@ -143,6 +143,7 @@ async fn run_destroy_locked(
     lock: Option<&LockInfo>,
     refresh: bool,
     force: bool,
+    force_dependencies: bool,
     base_dir: &std::path::Path,
     parallelism: NonZeroUsize,
     cancel: CancellationToken,
@@ -530,6 +531,8 @@ async fn run_destroy_locked(
         return Ok(());
     }
 
+    check_delete_blockers(&provider, &delete_effects, force_dependencies).await?;
+
     // Confirmation prompt
     if !auto_approve {
         println!(
@@ -805,6 +808,8 @@ async fn run_destroy_locked(
                             &identifier,
                             carina_core::provider::DeleteRequest {
                                 directives: directives.clone(),
+                                timeout: directives.timeouts.delete,
+                                force_dependencies,
                             },
                         )
                         .await;
@@ -1127,6 +1132,128 @@ fn observe_destroy_success_for_tests(
 ) {
 }
 
+/// Ask the providers what would keep each resource from being deleted
+/// and refuse to start while anything outside this destroy would.
+///
+/// Blockers that are themselves being destroyed are ignored, as are the
+/// removable ones under `--force-dependencies`, which the provider
+/// removes before the delete. A provider that cannot run the check does
+/// not stop the destroy; WASM providers never can, since the plugin
+/// interface does not carry `Provider::delete_blockers`.
+async fn check_delete_blockers(
+    provider: &dyn Provider,
+    delete_effects: &[Effect],
+    force_dependencies: bool,
+) -> Result<(), AppError> {
+    let mut found = Vec::new();
+    for effect in delete_effects {
+        let Effect::Delete { id, identifier, .. } = effect else {
+            continue;
+        };
+        match provider.delete_blockers(id, identifier).await {
+            Ok(blockers) if !blockers.is_empty() => found.push(((**id).clone(), blockers)),
+            Ok(_) => {}
+            Err(e) => eprintln!(
+                "{}",
+                format!(
+                    "Warning: could not check what blocks deleting {}: {e}",
+                    **id
+                )
+                .yellow()
+            ),
+        }
+    }
+    let destroyed: HashSet<&str> = delete_effects
+        .iter()
+        .filter_map(|effect| match effect {
+            Effect::Delete { identifier, .. } => Some(identifier.as_str()),
+            _ => None,
+        })
+        .collect();
+    let (removed, outstanding) = partition_delete_blockers(found, &destroyed, force_dependencies);
+
+    if !removed.is_empty() {
+        println!(
+            "{}",
+            "Removing blocking dependencies before delete (--force-dependencies):".yellow()
+        );
+        print!("{}", format_delete_blockers(&removed));
+        println!();
+    }
+    if outstanding.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "{}",
+        "Error: deleting these resources would fail on dependencies outside this destroy:"
+            .red()
+            .bold()
+    );
+    print!("{}", format_delete_blockers(&outstanding));
+    if outstanding
+        .iter()
+        .any(|(_, blockers)| blockers.iter().any(|b| b.removable))
+    {
+        println!();
+        println!(
+            "{}",
+            "Use --force-dependencies to let the provider remove the blockers marked removable."
+                .yellow()
+        );
+    }
+    println!();
+    Err(AppError::Validation(format!(
+        "{} resource(s) are blocked by dependencies outside this destroy.",
+        outstanding.len()
+    )))
+}
+
+type BlockedDeletes = Vec<(ResourceId, Vec<DeleteBlocker>)>;
+
+/// Split the blockers found into those the provider removes itself
+/// (`--force-dependencies` and removable) and those left for the user,
+/// dropping blockers that are deleted by this destroy anyway.
+fn partition_delete_blockers(
+    found: BlockedDeletes,
+    destroyed: &HashSet<&str>,
+    force_dependencies: bool,
+) -> (BlockedDeletes, BlockedDeletes) {
+    let mut removed = Vec::new();
+    let mut outstanding = Vec::new();
+    for (id, blockers) in found {
+        let (forced, left): (Vec<_>, Vec<_>) = blockers
+            .into_iter()
+            .filter(|b| !destroyed.contains(b.identifier.as_str()))
+            .partition(|b| force_dependencies && b.removable);
+        if !forced.is_empty() {
+            removed.push((id.clone(), forced));
+        }
+        if !left.is_empty() {
+            outstanding.push((id, left));
+        }
+    }
+    (removed, outstanding)
+}
+
+fn format_delete_blockers(blocked: &[(ResourceId, Vec<DeleteBlocker>)]) -> String {
+    let mut out = String::new();
+    for (id, blockers) in blocked {
+        out.push_str(&format!("  {} {}\n", "✗".red().bold(), id));
+        for blocker in blockers {
+            let removable = if blocker.removable {
+                " (removable)"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "      - {} {}: {}{}\n",
+                blocker.kind, blocker.identifier, blocker.description, removable
+            ));
+        }
+    }
+    out
+}
+
 fn build_destroy_wait_aliases(
     wait_bindings: &[WaitBinding],
     resources_to_destroy: &[&Resource],
@@ -1494,10 +1621,12 @@ mod tests {
 
         let err = run_destroy(
             fixture.config_path(),
+            None,
             true,
             true,
             false,
             false,
+            false,
             NonZeroUsize::new(1).unwrap(),
             fixture.provider_context(),
             token,
@@ -1614,6 +1743,50 @@ mod tests {
         }
     }
 
+    fn blocker(identifier: &str, removable: bool) -> DeleteBlocker {
+        DeleteBlocker {
+            kind: "network_interface".to_string(),
+            identifier: identifier.to_string(),
+            description: "in use".to_string(),
+            removable,
+        }
+    }
+
+    #[test]
+    fn delete_blockers_destroyed_in_the_same_run_are_ignored() {
+        let vpc = ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None);
+        let found = vec![(
+            vpc.clone(),
+            vec![blocker("eni-managed", false), blocker("eni-stray", false)],
+        )];
+        let destroyed = HashSet::from(["vpc-1", "eni-managed"]);
+
+        let (removed, outstanding) = partition_delete_blockers(found, &destroyed, false);
+        assert!(removed.is_empty());
+        assert_eq!(outstanding, vec![(vpc, vec![blocker("eni-stray", false)])]);
+    }
+
+    #[test]
+    fn force_dependencies_only_clears_removable_blockers() {
+        let vpc = ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None);
+        let found = vec![(
+            vpc.clone(),
+            vec![blocker("sgr-default", true), blocker("eni-stray", false)],
+        )];
+
+        let (removed, outstanding) =
+            partition_delete_blockers(found.clone(), &HashSet::new(), false);
+        assert!(removed.is_empty());
+        assert_eq!(outstanding, found);
+
+        let (removed, outstanding) = partition_delete_blockers(found, &HashSet::new(), true);
+        assert_eq!(
+            removed,
+            vec![(vpc.clone(), vec![blocker("sgr-default", true)])]
+        );
+        assert_eq!(outstanding, vec![(vpc, vec![blocker("eni-stray", false)])]);
+    }
+
     #[test]
     fn is_retryable_detects_dependency_violation() {
         let err = ProviderError::api_error(
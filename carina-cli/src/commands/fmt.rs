@@ -102,7 +102,7 @@ pub fn run_fmt(path: &Path, check: bool, show_diff: bool, recursive: bool) -> Re
     }
 }
 
-fn print_diff(file: &Path, original: &str, formatted: &str) {
+pub(crate) fn print_diff(file: &Path, original: &str, formatted: &str) {
     println!("\n{} {}:", "Diff for".cyan().bold(), file.display());
 
     let diff = TextDiff::from_lines(original, formatted);
@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use carina_core::graph::DependencyGraph;
+use carina_core::parser::ProviderContext;
+
+use super::plan::{PlanReport, run_plan};
+use crate::DetailLevel;
+use crate::error::AppError;
+use crate::wiring::{PlanChecks, PlanContext};
+
+/// Output format of `carina graph` (CLI-facing enum with clap support).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, e.g. for `dot -Tsvg`
+    Dot,
+    /// Mermaid flowchart, rendered inline by GitHub and GitLab
+    Mermaid,
+}
+
+impl GraphFormat {
+    pub fn to_core(self) -> carina_core::graph::GraphFormat {
+        match self {
+            GraphFormat::Dot => carina_core::graph::GraphFormat::Dot,
+            GraphFormat::Mermaid => carina_core::graph::GraphFormat::Mermaid,
+        }
+    }
+}
+
+/// Run the `carina graph` command: plan, then render the dependency
+/// graph with the planned changes highlighted.
+pub async fn run_graph(
+    path: &Path,
+    var_file: Option<&Path>,
+    format: GraphFormat,
+    refresh: bool,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    run_plan(
+        path,
+        var_file,
+        None,
+        DetailLevel::Full,
+        false,
+        refresh,
+        false,
+        false,
+        false,
+        false,
+        PlanChecks::default(),
+        provider_context,
+        Some(PlanReport::Graph(format)),
+    )
+    .await
+    .map(|_| ())
+}
+
+pub(crate) fn render_from_plan(ctx: &PlanContext, format: GraphFormat) -> String {
+    DependencyGraph::new(&ctx.sorted_resources, Some(&ctx.plan)).render(format.to_core())
+}
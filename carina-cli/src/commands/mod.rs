@@ -3,16 +3,22 @@ pub mod destroy;
 pub mod docs;
 pub mod export;
 pub mod fmt;
+pub mod graph;
 pub(crate) mod iam_preflight;
 pub mod init;
 pub mod lint;
 pub mod migrate_state;
 pub mod module;
+pub mod org;
+pub mod orphans;
 pub mod plan;
+pub mod providers;
 pub(crate) mod shared;
 pub mod skills;
 pub mod state;
+pub mod upgrade;
 pub mod validate;
+pub mod why;
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -53,6 +59,7 @@ pub enum DriftCommand {
     Apply,
     Destroy,
     RefreshState,
+    UpgradeState,
 }
 
 impl DriftCommand {
@@ -61,6 +68,7 @@ impl DriftCommand {
             Self::Apply => "Cannot apply",
             Self::Destroy => "Cannot destroy",
             Self::RefreshState => "Cannot refresh state",
+            Self::UpgradeState => "Cannot upgrade state",
         }
     }
 }
@@ -270,6 +278,52 @@ pub(crate) fn collapse_errors(errors: Vec<AppError>) -> AppError {
     )
 }
 
+/// Load the configuration at `path` with its root `variables { }` bound
+/// from `var_file` and disabled resources dropped. Every command that
+/// evaluates the configuration loads it through here, so none of them
+/// sees an unbound variable.
+pub fn load_bound_configuration(
+    path: &Path,
+    var_file: Option<&Path>,
+    provider_context: &ProviderContext,
+) -> Result<carina_core::config_loader::LoadedConfig, AppError> {
+    let mut loaded = carina_core::config_loader::load_configuration_with_config(
+        path,
+        provider_context,
+        &carina_core::schema::SchemaRegistry::new(),
+    )?;
+    bind_variables(&mut loaded.parsed, var_file, provider_context)?;
+    drop_disabled_resources(&mut loaded.parsed, &mut loaded.unresolved_parsed)?;
+    Ok(loaded)
+}
+
+/// Give the root `variables { }` their values from `var_file`, or their
+/// defaults when no file is passed. Runs before validation so the
+/// substituted values are what gets type-checked against the schemas.
+fn bind_variables(
+    parsed: &mut carina_core::parser::InferredFile,
+    var_file: Option<&Path>,
+    provider_context: &carina_core::parser::ProviderContext,
+) -> Result<(), AppError> {
+    let values = carina_core::config_loader::load_var_file(var_file, provider_context)?;
+    carina_core::module_resolver::bind_root_variables(parsed, values, provider_context)
+        .map_err(AppError::Validation)
+}
+
+/// Drop resources whose `directives.enabled` evaluated to `false` now
+/// that variables are bound, so the plan treats them as removed. The
+/// pre-resolution copy loses the same entries.
+fn drop_disabled_resources(
+    parsed: &mut carina_core::parser::InferredFile,
+    unresolved: &mut carina_core::parser::ParsedFile,
+) -> Result<(), AppError> {
+    let removed =
+        carina_core::enabled::drop_disabled_resources(parsed).map_err(AppError::Validation)?;
+    unresolved.resources.retain(|r| !removed.contains(&r.id));
+    unresolved.data_sources.retain(|d| !removed.contains(&d.id));
+    Ok(())
+}
+
 pub fn validate_and_resolve_with_config(
     parsed: &mut carina_core::parser::InferredFile,
     base_dir: &Path,
@@ -444,6 +498,9 @@ pub fn validate_and_resolve_errors_with_factories(
         errors.extend(validate_deferred_populate_refs_with_ctx(&ctx, parsed));
         let mut argument_names: HashSet<String> =
             parsed.arguments.iter().map(|a| a.name.clone()).collect();
+        // Variables are substituted before validation when a plan binds
+        // them; `validate` leaves them as references.
+        argument_names.extend(parsed.variable_params.iter().map(|v| v.name.clone()));
         // Upstream state bindings are resolved at plan time, skip type validation
         for us in &parsed.upstream_states {
             argument_names.insert(us.binding.clone());
@@ -759,6 +816,7 @@ mod tests {
             source: Some("badscheme://not-a-valid-source".to_string()),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             version: None,
             revision: None,
             unresolved_attributes: IndexMap::new(),
@@ -793,6 +851,7 @@ mod tests {
             source: None,
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             version: None,
             revision: None,
             unresolved_attributes: IndexMap::new(),
@@ -0,0 +1,444 @@
+//! `carina org`: a read-only index over every project in a directory tree.
+//!
+//! A project is a directory holding a `carina-backend.lock` (written by
+//! `carina init`). For each one the index records the resources and exports
+//! in its state and the `upstream_state` sources it declares, which is
+//! enough to answer "which project manages this resource" and "who consumes
+//! this project's exports" without planning anything.
+
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use carina_core::config_loader::load_configuration_with_config;
+use carina_core::parser::ProviderContext;
+use carina_core::schema::SchemaRegistry;
+use carina_state::backend_lock::LOCK_FILE;
+use carina_state::{BackendConfig as StateBackendConfig, resolve_backend_anchored};
+
+use crate::error::AppError;
+
+#[derive(clap::Subcommand)]
+pub enum OrgCommands {
+    /// List every project under a directory with its resources, exports
+    /// and upstream_state sources
+    Index {
+        /// Directory to scan for projects
+        #[arg(default_value = ".")]
+        root: PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Find the project that manages a resource, by cloud identifier,
+    /// binding name or address
+    Find {
+        /// Identifier (e.g. vpc-0abc), binding or address to look for
+        query: String,
+
+        /// Directory to scan for projects
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List the projects that read a project's exports via upstream_state
+    Consumers {
+        /// Project directory whose consumers to list
+        project: PathBuf,
+
+        /// Directory to scan for projects
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub async fn run_org_command(
+    command: OrgCommands,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    match command {
+        OrgCommands::Index { root, json } => {
+            let index = build_org_index(&root, provider_context).await?;
+            emit(&index, json, || format_index(&index))
+        }
+        OrgCommands::Find { query, root, json } => {
+            let index = build_org_index(&root, provider_context).await?;
+            let matches = index.find(&query);
+            emit(&matches, json, || format_matches(&index, &query, &matches))
+        }
+        OrgCommands::Consumers {
+            project,
+            root,
+            json,
+        } => {
+            let project = project.canonicalize().map_err(|e| {
+                AppError::Config(format!("cannot resolve '{}': {}", project.display(), e))
+            })?;
+            let index = build_org_index(&root, provider_context).await?;
+            let consumers = index.consumers(&project);
+            emit(&consumers, json, || {
+                format_consumers(&index, &project, &consumers)
+            })
+        }
+    }
+}
+
+fn emit<T: Serialize>(
+    value: &T,
+    json: bool,
+    human: impl FnOnce() -> String,
+) -> Result<(), AppError> {
+    if json {
+        let out = serde_json::to_string_pretty(value)
+            .map_err(|e| AppError::Config(format!("Failed to serialize org index: {e}")))?;
+        println!("{out}");
+    } else {
+        print!("{}", human());
+    }
+    Ok(())
+}
+
+/// Every project found under a root directory.
+#[derive(Debug, Serialize)]
+pub struct OrgIndex {
+    pub root: PathBuf,
+    pub projects: Vec<ProjectSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectSummary {
+    /// Canonical project directory.
+    pub path: PathBuf,
+    pub resources: Vec<ManagedResource>,
+    pub exports: Vec<String>,
+    pub upstreams: Vec<UpstreamEdge>,
+    /// Why the configuration or state could not be read. The project is
+    /// still listed so a broken project does not vanish from the index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManagedResource {
+    /// `provider.resource_type identity`
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binding: Option<String>,
+    /// Cloud-side identifier (e.g. `vpc-0abc`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpstreamEdge {
+    pub binding: String,
+    /// Source directory, canonicalized when it exists.
+    pub source: PathBuf,
+}
+
+/// A resource matched by [`OrgIndex::find`].
+#[derive(Debug, Serialize)]
+pub struct ResourceMatch<'a> {
+    pub project: &'a Path,
+    pub resource: &'a ManagedResource,
+}
+
+/// A consumer found by [`OrgIndex::consumers`].
+#[derive(Debug, Serialize)]
+pub struct Consumer<'a> {
+    pub project: &'a Path,
+    pub binding: &'a str,
+}
+
+impl OrgIndex {
+    /// Resources whose identifier, binding or address equals `query`.
+    pub fn find(&self, query: &str) -> Vec<ResourceMatch<'_>> {
+        self.projects
+            .iter()
+            .flat_map(|p| {
+                p.resources
+                    .iter()
+                    .filter(move |r| {
+                        r.identifier.as_deref() == Some(query)
+                            || r.binding.as_deref() == Some(query)
+                            || r.address == query
+                    })
+                    .map(move |r| ResourceMatch {
+                        project: &p.path,
+                        resource: r,
+                    })
+            })
+            .collect()
+    }
+
+    /// Projects with an `upstream_state` whose source is `project`.
+    pub fn consumers(&self, project: &Path) -> Vec<Consumer<'_>> {
+        self.projects
+            .iter()
+            .flat_map(|p| {
+                p.upstreams
+                    .iter()
+                    .filter(move |u| u.source == project)
+                    .map(move |u| Consumer {
+                        project: &p.path,
+                        binding: &u.binding,
+                    })
+            })
+            .collect()
+    }
+
+    fn display_path<'a>(&self, path: &'a Path) -> std::path::Display<'a> {
+        path.strip_prefix(&self.root)
+            .ok()
+            .filter(|rel| !rel.as_os_str().is_empty())
+            .unwrap_or(path)
+            .display()
+    }
+}
+
+/// Project directories under `root`, sorted. Hidden directories and build
+/// output (`target`, `node_modules`) are not descended into.
+pub fn discover_projects(root: &Path) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        if dir.join(LOCK_FILE).is_file() {
+            projects.push(dir.clone());
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || name == "target" || name == "node_modules" {
+                continue;
+            }
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                pending.push(entry.path());
+            }
+        }
+    }
+    projects.sort();
+    projects
+}
+
+/// Scan `root` and read every project's configuration and state.
+pub async fn build_org_index(
+    root: &Path,
+    provider_context: &ProviderContext,
+) -> Result<OrgIndex, AppError> {
+    let root = root
+        .canonicalize()
+        .map_err(|e| AppError::Config(format!("cannot resolve '{}': {}", root.display(), e)))?;
+    let mut projects = Vec::new();
+    for path in discover_projects(&root) {
+        projects.push(summarize_project(path, provider_context).await);
+    }
+    Ok(OrgIndex { root, projects })
+}
+
+async fn summarize_project(path: PathBuf, provider_context: &ProviderContext) -> ProjectSummary {
+    let mut summary = ProjectSummary {
+        path,
+        resources: Vec::new(),
+        exports: Vec::new(),
+        upstreams: Vec::new(),
+        error: None,
+    };
+    let parsed = match load_configuration_with_config(
+        &summary.path,
+        provider_context,
+        &SchemaRegistry::new(),
+    ) {
+        Ok(loaded) => loaded.parsed,
+        Err(e) => {
+            summary.error = Some(e.to_string());
+            return summary;
+        }
+    };
+    summary.upstreams = parsed
+        .upstream_states
+        .iter()
+        .map(|us| {
+            let source = summary.path.join(&us.source);
+            UpstreamEdge {
+                binding: us.binding.clone(),
+                source: source.canonicalize().unwrap_or(source),
+            }
+        })
+        .collect();
+
+    // Read-only: drop any pending-migration token (carina#3315).
+    let backend_config = parsed.backend.as_ref().map(StateBackendConfig::from);
+    let state = match resolve_backend_anchored(backend_config.as_ref(), &summary.path).await {
+        Ok(backend) => backend.read_state().await,
+        Err(e) => Err(e),
+    };
+    match state {
+        Ok(Some(loaded)) => {
+            let state = loaded.into_state();
+            summary.resources = state
+                .resources
+                .iter()
+                .map(|rs| ManagedResource {
+                    address: if rs.provider.is_empty() {
+                        format!("{} {}", rs.resource_type, rs.identity)
+                    } else {
+                        format!("{}.{} {}", rs.provider, rs.resource_type, rs.identity)
+                    },
+                    binding: rs.binding.clone(),
+                    identifier: rs.identifier.clone(),
+                })
+                .collect();
+            summary.exports = state.exports.keys().cloned().collect();
+            summary.exports.sort();
+        }
+        Ok(None) => {}
+        Err(e) => summary.error = Some(e.to_string()),
+    }
+    summary
+}
+
+/// Format the human-readable project listing.
+pub fn format_index(index: &OrgIndex) -> String {
+    if index.projects.is_empty() {
+        return format!(
+            "No projects found under {} (looked for {}).\n",
+            index.root.display(),
+            LOCK_FILE
+        );
+    }
+    let mut out = String::new();
+    for project in &index.projects {
+        out.push_str(&format!(
+            "{}\n",
+            index.display_path(&project.path).to_string().bold()
+        ));
+        if let Some(error) = &project.error {
+            out.push_str(&format!("  {} {}\n", "error:".red(), error));
+        }
+        out.push_str(&format!("  resources: {}\n", project.resources.len()));
+        if !project.exports.is_empty() {
+            out.push_str(&format!("  exports: {}\n", project.exports.join(", ")));
+        }
+        for upstream in &project.upstreams {
+            out.push_str(&format!(
+                "  upstream: {} <- {}\n",
+                upstream.binding,
+                index.display_path(&upstream.source)
+            ));
+        }
+    }
+    out
+}
+
+fn format_matches(index: &OrgIndex, query: &str, matches: &[ResourceMatch<'_>]) -> String {
+    if matches.is_empty() {
+        return format!("No project manages '{query}'.\n");
+    }
+    let mut out = String::new();
+    for m in matches {
+        out.push_str(&format!(
+            "{}: {}",
+            index.display_path(m.project),
+            m.resource.address
+        ));
+        if let Some(binding) = &m.resource.binding {
+            out.push_str(&format!(" (let {binding})"));
+        }
+        if let Some(identifier) = &m.resource.identifier {
+            out.push_str(&format!(" [{identifier}]"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn format_consumers(index: &OrgIndex, project: &Path, consumers: &[Consumer<'_>]) -> String {
+    if consumers.is_empty() {
+        return format!(
+            "No project reads {} via upstream_state.\n",
+            index.display_path(project)
+        );
+    }
+    let mut out = String::new();
+    for c in consumers {
+        out.push_str(&format!(
+            "{} (upstream_state {})\n",
+            index.display_path(c.project),
+            c.binding
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_state::{LocalBackend, ResourceState, StateFile};
+    use std::fs;
+
+    fn project(dir: &Path, crn: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("main.crn"), crn).unwrap();
+        fs::write(dir.join(LOCK_FILE), "").unwrap();
+    }
+
+    #[tokio::test]
+    async fn index_answers_ownership_and_consumer_queries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let network = tmp.path().join("network");
+        let app = tmp.path().join("apps/web");
+        project(&network, "");
+        project(
+            &app,
+            r#"let network = upstream_state { source = "../../network" }"#,
+        );
+        fs::create_dir_all(tmp.path().join(".git/network")).unwrap();
+        fs::write(tmp.path().join(".git/network").join(LOCK_FILE), "").unwrap();
+
+        let mut state = StateFile::new();
+        let mut vpc = ResourceState::new("ec2.Vpc", "main", "awscc");
+        vpc.identifier = Some("vpc-0abc".to_string());
+        vpc.binding = Some("vpc".to_string());
+        state.upsert_resource(vpc);
+        state
+            .exports
+            .insert("vpc_id".to_string(), serde_json::json!("vpc-0abc"));
+        fs::write(
+            network.join(LocalBackend::DEFAULT_STATE_FILE),
+            serde_json::to_string(&state).unwrap(),
+        )
+        .unwrap();
+
+        let index = build_org_index(tmp.path(), &ProviderContext::default())
+            .await
+            .unwrap();
+        assert_eq!(index.projects.len(), 2);
+
+        let found = index.find("vpc-0abc");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].project, network.canonicalize().unwrap());
+        assert_eq!(found[0].resource.address, "awscc.ec2.Vpc main");
+        assert!(index.find("vpc-missing").is_empty());
+
+        let consumers = index.consumers(&network.canonicalize().unwrap());
+        assert_eq!(consumers.len(), 1);
+        assert_eq!(consumers[0].project, app.canonicalize().unwrap());
+        assert_eq!(consumers[0].binding, "network");
+
+        let text = format_index(&index);
+        assert!(text.contains("  resources: 1\n  exports: vpc_id\n"));
+        assert!(text.contains("upstream: network <- network\n"));
+    }
+}
@@ -0,0 +1,310 @@
+//! `carina orphans`: reconcile state against what the providers report.
+//!
+//! Lists resources tagged with this project's `carina:project` tag that
+//! state does not track, and state entries whose resource no longer
+//! exists, each with the `import` / `removed` block that would settle it.
+//! Read-only: neither state nor infrastructure is changed.
+//!
+//! The report can be narrowed by type and tag, and the untracked
+//! resources — the provider's discovery results, which can run long —
+//! split into pages.
+//!
+//! Listing goes through `Provider::list_project_resources`, which the
+//! WASM plugin interface does not carry; WASM providers always end up
+//! under "Not checked".
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use carina_core::config_loader::get_base_dir;
+use carina_core::orphans::{ReconciliationEntry, ReconciliationReport, TrackedResource};
+use carina_core::parser::ProviderContext;
+use carina_core::provider::PROJECT_TAG;
+use carina_core::resource::ResourceId;
+use carina_state::report::{ReportFilter, ReportRow, csv_field, paginate};
+use carina_state::{StateBackend, StateFile, resolve_backend_for_read};
+
+use super::{load_bound_configuration, validate_and_resolve_with_config};
+use crate::error::AppError;
+use crate::wiring::{
+    WiringContext, build_factories_from_providers, get_provider_with_ctx, read_with_retry,
+};
+
+/// Output format for `carina orphans`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrphansFormat {
+    Text,
+    Json,
+    /// One `status,resource,identifier` row per entry.
+    Csv,
+}
+
+/// A page of the untracked resources to print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub size: NonZeroUsize,
+    /// 1-based.
+    pub page: NonZeroUsize,
+}
+
+/// Where a printed page sits among the untracked resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct PageInfo {
+    page: usize,
+    total_pages: usize,
+    total_untracked: usize,
+}
+
+pub async fn run_orphans(
+    path: &Path,
+    var_file: Option<&Path>,
+    filter: &ReportFilter,
+    page: Option<PageRequest>,
+    format: OrphansFormat,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    let loaded = load_bound_configuration(path, var_file, provider_context)?;
+    let mut parsed = loaded.parsed;
+    let base_dir = get_base_dir(path);
+    validate_and_resolve_with_config(&mut parsed, base_dir, true)?;
+
+    let backend: Box<dyn StateBackend> = resolve_backend_for_read(parsed.backend.as_ref())
+        .await
+        .map_err(AppError::Backend)?;
+    let state = backend
+        .read_state()
+        .await
+        .map_err(AppError::Backend)?
+        .map(|loaded| loaded.into_state())
+        .ok_or_else(|| {
+            AppError::Config("No state file found. Nothing to reconcile.".to_string())
+        })?;
+
+    let (factories, _) = build_factories_from_providers(&parsed.providers, base_dir);
+    let ctx = WiringContext::new(factories);
+    let provider = get_provider_with_ctx(&ctx, &parsed, base_dir).await?;
+
+    let tracked = tracked_resources(&state);
+    let mut report = ReconciliationReport::default();
+
+    for (resource, rs) in tracked.iter().zip(&state.resources) {
+        let Some(identifier) = resource.identifier.as_deref() else {
+            continue;
+        };
+        if !filter.matches(&ReportRow::from_resource_state(rs)) {
+            continue;
+        }
+        match read_with_retry(&provider, &resource.id, Some(identifier)).await {
+            Ok(read) if !read.exists => report.add_vanished(resource),
+            Ok(_) => {}
+            Err(e) => report.add_error(resource.id.human().to_string(), e.to_string()),
+        }
+    }
+
+    for listing in provider
+        .list_project_resources_by_instance(&state.lineage)
+        .await
+    {
+        match listing.result {
+            // Every tracked resource stays in the match set, so one the
+            // filter excludes is still not reported as untracked.
+            Ok(Some(states)) => report.add_listed(
+                states
+                    .into_iter()
+                    .filter(|s| filter.matches(&ReportRow::from_state(s)))
+                    .collect(),
+                &tracked,
+            ),
+            Ok(None) => report.add_unchecked(listing.instance),
+            Err(e) => report.add_error(listing.instance, e.to_string()),
+        }
+    }
+
+    let page_info = page.map(|request| {
+        let page = paginate(&report.untracked, request.size, request.page);
+        let info = PageInfo {
+            page: page.page,
+            total_pages: page.total_pages,
+            total_untracked: page.total_rows,
+        };
+        report.untracked = page.rows.to_vec();
+        info
+    });
+
+    match format {
+        OrphansFormat::Json => {
+            #[derive(Serialize)]
+            struct PagedReport<'a> {
+                #[serde(flatten)]
+                report: &'a ReconciliationReport,
+                #[serde(flatten)]
+                page: Option<PageInfo>,
+            }
+            let out = serde_json::to_string_pretty(&PagedReport {
+                report: &report,
+                page: page_info,
+            })
+            .map_err(|e| AppError::Config(format!("Failed to serialize report: {e}")))?;
+            println!("{out}");
+        }
+        OrphansFormat::Csv => print!("{}", format_csv(&report)),
+        OrphansFormat::Text => print!(
+            "{}",
+            format_report(&report, &state.lineage, page_info.as_ref())
+        ),
+    }
+    Ok(())
+}
+
+fn tracked_resources(state: &StateFile) -> Vec<TrackedResource> {
+    state
+        .resources
+        .iter()
+        .map(|rs| TrackedResource {
+            id: ResourceId::with_provider_name_compat(
+                &rs.provider,
+                &rs.resource_type,
+                &rs.identity,
+                rs.directives.provider_instance.clone(),
+            ),
+            identifier: rs.identifier.clone(),
+        })
+        .collect()
+}
+
+fn format_report(report: &ReconciliationReport, project: &str, page: Option<&PageInfo>) -> String {
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{}\n",
+        format!("Reconciling project {PROJECT_TAG}={project}").cyan()
+    ));
+
+    format_section(
+        &mut out,
+        "Tagged for this project but not in state (import to manage):",
+        &report.untracked,
+    );
+    if let Some(page) = page {
+        out.push_str(&format!(
+            "  {}\n",
+            format!(
+                "Page {} of {} ({} untracked resources)",
+                page.page, page.total_pages, page.total_untracked
+            )
+            .dimmed()
+        ));
+    }
+    format_section(
+        &mut out,
+        "In state but gone remotely (remove from state):",
+        &report.vanished,
+    );
+
+    if report.is_clean() {
+        out.push_str(&format!("\n{}\n", "State and providers agree.".green()));
+    }
+    if !report.unchecked.is_empty() {
+        out.push_str(&format!(
+            "\n{} {}\n",
+            "Not checked for untracked resources (provider cannot list them):".yellow(),
+            report.unchecked.join(", ")
+        ));
+    }
+    for error in &report.errors {
+        out.push_str(&format!(
+            "\n{} {}: {}\n",
+            "!".yellow(),
+            error.subject,
+            error.message
+        ));
+    }
+    out
+}
+
+fn format_csv(report: &ReconciliationReport) -> String {
+    let mut out = String::from("status,resource,identifier\n");
+    let rows = (report.untracked.iter().map(|e| ("untracked", e)))
+        .chain(report.vanished.iter().map(|e| ("vanished", e)));
+    for (status, entry) in rows {
+        out.push_str(&format!(
+            "{},{},{}\n",
+            status,
+            csv_field(&entry.resource),
+            csv_field(&entry.identifier)
+        ));
+    }
+    out
+}
+
+fn format_section(out: &mut String, heading: &str, entries: &[ReconciliationEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n{}\n", heading.bold()));
+    for entry in entries {
+        out.push_str(&format!("  {} ({})\n", entry.resource, entry.identifier));
+        for line in entry.suggestion.lines() {
+            out.push_str(&format!("    {line}\n"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_suggestions_and_unchecked_instances() {
+        let mut report = ReconciliationReport::default();
+        report.add_vanished(&TrackedResource {
+            id: ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None),
+            identifier: Some("vpc-0abc".to_string()),
+        });
+        report.add_unchecked("aws");
+
+        let out = format_report(&report, "lineage-1", None);
+        assert!(out.contains("carina:project=lineage-1"));
+        assert!(out.contains("  awscc.ec2.Vpc main (vpc-0abc)\n    removed {\n"));
+        assert!(out.contains("provider cannot list them)"));
+        assert!(out.trim_end().ends_with("aws"));
+        assert!(!out.contains("agree"));
+    }
+
+    #[test]
+    fn report_shows_which_page_of_untracked_resources_it_holds() {
+        let mut report = ReconciliationReport::default();
+        report.add_listed(
+            vec![
+                carina_core::resource::State::existing(
+                    ResourceId::with_provider("awscc", "ec2.Vpc", None, None),
+                    Default::default(),
+                )
+                .with_identifier("vpc-0abc"),
+            ],
+            &[],
+        );
+        let page = PageInfo {
+            page: 2,
+            total_pages: 3,
+            total_untracked: 5,
+        };
+        let out = format_report(&report, "lineage-1", Some(&page));
+        assert!(out.contains("Page 2 of 3 (5 untracked resources)"));
+    }
+
+    #[test]
+    fn csv_lists_each_entry_with_its_status() {
+        let mut report = ReconciliationReport::default();
+        report.add_vanished(&TrackedResource {
+            id: ResourceId::with_provider_identity("awscc", "s3.Bucket", "logs", None),
+            identifier: Some("logs,archive".to_string()),
+        });
+        assert_eq!(
+            format_csv(&report),
+            "status,resource,identifier\nvanished,awscc.s3.Bucket logs,\"logs,archive\"\n"
+        );
+    }
+}
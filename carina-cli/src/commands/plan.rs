@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use carina_core::config_loader::{get_base_dir, load_configuration_with_config};
 use carina_core::parser::{BackendConfig, ProviderConfig, ProviderContext, UpstreamState};
 use carina_core::plan::Plan;
+use carina_core::plan_changes::PlanChanges;
 use carina_core::resource::{ConcreteValue, DeferredValue, Resource, ResourceId, State, Value};
 use carina_core::value::{
     redact_secrets_in_plan, redact_secrets_in_resource, redact_secrets_in_state,
@@ -18,15 +19,17 @@ use carina_state::{
 };
 
 use super::{
-    BackendDriftStatus, drift_warning, inspect_backend_drift, validate_and_resolve_with_config,
+    BackendDriftStatus, drift_warning, inspect_backend_drift, load_bound_configuration,
+    validate_and_resolve_with_config,
 };
 use crate::DetailLevel;
 use crate::commands::shared::plan_errors::render_plan_errors_and_abort;
 use crate::display::{print_plan, refresh_plan_separator};
 use crate::error::AppError;
 use crate::wiring::{
-    WiringContext, build_factories_from_providers, create_plan_from_parsed_with_upstream,
-    reconcile_anonymous_identifiers_with_ctx, reconcile_prefixed_names,
+    PlanChecks, WiringContext, build_factories_from_providers,
+    create_plan_from_parsed_with_upstream, reconcile_anonymous_identifiers_with_ctx,
+    reconcile_prefixed_names,
 };
 
 /// Saved plan file for `plan --out` / `apply plan.json`
@@ -126,6 +129,17 @@ pub struct PlanFile {
     /// Empty when the configuration declares no `wait` bindings.
     #[serde(default)]
     pub wait_bindings: Vec<PlanWaitBinding>,
+    /// Stable, documented view of the plan for review and policy
+    /// tooling: one entry per resource with its action and before/after
+    /// attributes. Informational only — apply executes `plan`. `None`
+    /// in plans saved before the view existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<PlanChanges>,
+    /// Trace id of the plan's exported spans, so the apply's spans can
+    /// link to them. `None` unless `CARINA_TRACES_FILE` was set at plan
+    /// time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<carina_core::trace::TraceId>,
 }
 
 impl PlanFile {
@@ -187,12 +201,19 @@ fn build_plan_file<E>(
     backend_config: Option<BackendConfig>,
     state_file: &Option<StateFile>,
     ctx: &crate::wiring::PlanContext,
+    trace_id: Option<carina_core::trace::TraceId>,
 ) -> Result<PlanFile, carina_core::value::SerializationError> {
     let source_path = path
         .canonicalize()
         .unwrap_or_else(|_| path.to_path_buf())
         .display()
         .to_string();
+    let plan = redact_secrets_in_plan(&ctx.plan)?;
+    let current_states = ctx
+        .current_states
+        .iter()
+        .map(|(id, state)| Ok((id.clone(), redact_secrets_in_state(state)?)))
+        .collect::<Result<HashMap<_, _>, carina_core::value::SerializationError>>()?;
 
     Ok(PlanFile {
         // Phase 9: bumped 8→9 — `data_sources` now stores the exact
@@ -228,7 +249,9 @@ fn build_plan_file<E>(
         state_serial: state_file.as_ref().map(|s| s.serial),
         provider_configs: parsed.providers.clone(),
         backend_config,
-        plan: redact_secrets_in_plan(&ctx.plan)?,
+        changes: Some(PlanChanges::from_plan(&plan, &current_states)),
+        trace_id,
+        plan,
         sorted_resources: ctx
             .sorted_resources
             .iter()
@@ -249,16 +272,10 @@ fn build_plan_file<E>(
             .iter()
             .map(carina_core::value::redact_secrets_in_data_source)
             .collect::<Result<Vec<_>, _>>()?,
-        current_states: ctx
-            .current_states
-            .iter()
-            .map(|(id, state)| {
-                Ok::<_, carina_core::value::SerializationError>(CurrentStateEntry {
-                    id: id.clone(),
-                    state: redact_secrets_in_state(state)?,
-                })
-            })
-            .collect::<Result<Vec<_>, _>>()?,
+        current_states: current_states
+            .into_iter()
+            .map(|(id, state)| CurrentStateEntry { id, state })
+            .collect(),
         upstream_snapshot: ctx.upstream_snapshot.clone(),
         upstream_sources: parsed
             .upstream_states
@@ -433,9 +450,21 @@ async fn drift_detection_test_barrier() {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+/// What `run_plan` prints instead of the plan, for commands that compute
+/// a plan only to report on it.
+#[derive(Debug, Clone, Copy)]
+pub enum PlanReport<'a> {
+    /// `carina why`: explain one attribute.
+    Why(&'a crate::commands::why::WhyTarget),
+    /// `carina graph`: render the dependency graph.
+    Graph(crate::commands::graph::GraphFormat),
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn run_plan(
     path: &Path,
+    var_file: Option<&Path>,
     out: Option<&Path>,
     detail: DetailLevel,
     tui: bool,
@@ -443,13 +472,13 @@ pub async fn run_plan(
     json: bool,
     check_iam: bool,
     strict_iam: bool,
+    cost: bool,
+    checks: PlanChecks,
     provider_context: &ProviderContext,
+    report: Option<PlanReport<'_>>,
 ) -> Result<bool, AppError> {
-    let loaded = load_configuration_with_config(
-        path,
-        provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    )?;
+    let plan_started = std::time::SystemTime::now();
+    let loaded = load_bound_configuration(path, var_file, provider_context)?;
     let mut parsed = loaded.parsed;
     let mut unresolved_parsed = loaded.unresolved_parsed;
 
@@ -698,6 +727,7 @@ pub async fn run_plan(
         &state_block_claims,
         &resolved_state_block_targets,
         base_dir,
+        checks,
     )
     .await?;
     let has_changes = ctx.plan.mutation_count() > 0;
@@ -738,6 +768,28 @@ pub async fn run_plan(
     }
 
     render_plan_errors_and_abort(&ctx.plan)?;
+    let trace_id =
+        crate::commands::shared::trace_export::export_plan_trace(&ctx.plan, plan_started);
+
+    match report {
+        Some(PlanReport::Why(target)) => {
+            print!(
+                "{}",
+                crate::commands::why::explain_from_plan(
+                    target,
+                    &ctx,
+                    state_file.as_ref(),
+                    wiring.schemas()
+                )?
+            );
+            return Ok(has_changes);
+        }
+        Some(PlanReport::Graph(format)) => {
+            print!("{}", crate::commands::graph::render_from_plan(&ctx, format));
+            return Ok(has_changes);
+        }
+        None => {}
+    }
 
     let iam_preflight_result = if check_iam {
         let result =
@@ -768,6 +820,7 @@ pub async fn run_plan(
             plan_file_backend_config.clone(),
             &state_file,
             &ctx,
+            trace_id,
         )
         .map_err(|e| format_plan_save_error(&e, "--json"))?;
         let json_str = serde_json::to_string_pretty(&plan_file)
@@ -818,6 +871,19 @@ pub async fn run_plan(
             Some(&ctx.prev_explicit),
             Some(&ctx.expansion_trace),
         );
+        crate::commands::shared::plan_errors::print_blocked_resources(&ctx.blocked);
+        if cost {
+            crate::commands::shared::cost::print_cost_summary(
+                &ctx.plan,
+                &delete_attributes,
+                &ctx.provider,
+            )
+            .await;
+        }
+        print_key_move_hint(&carina_core::key_migration::suggest_key_moves(
+            &ctx.plan,
+            &ctx.current_states,
+        ));
         if let Some(note) = drift_note.as_ref() {
             println!();
             println!("{}", note.yellow());
@@ -847,6 +913,7 @@ pub async fn run_plan(
             plan_file_backend_config.clone(),
             &state_file,
             &ctx,
+            trace_id,
         )
         .map_err(|e| format_plan_save_error(&e, "--out"))?;
         let json_out = carina_core::utils::pretty_with_newline(&plan_file)
@@ -873,6 +940,29 @@ pub async fn run_plan(
     Ok(has_changes)
 }
 
+/// Point at the `moved` blocks that would keep resources a `for` loop
+/// now addresses by key instead of by index, rather than replacing them.
+fn print_key_move_hint(moves: &[carina_core::key_migration::KeyMove]) {
+    if moves.is_empty() {
+        return;
+    }
+    println!();
+    println!(
+        "{}",
+        format!(
+            "Note: {} resource(s) are deleted and re-created only because their \
+             for-expression address changed from an index to a key. Add these \
+             blocks to keep them:",
+            moves.len()
+        )
+        .yellow()
+    );
+    for key_move in moves {
+        println!();
+        println!("{}", key_move.moved_block());
+    }
+}
+
 /// Load remote state files and build binding maps for reference resolution.
 ///
 /// For each `remote_state` block, reads the referenced state file and builds a
@@ -1625,13 +1715,17 @@ exports { region: String = "ap-northeast-1" }"#,
         run_plan(
             &dir_b,
             None,
+            None,
             DetailLevel::None,
             false,
             false,
             true,
             false,
             false,
+            false,
+            PlanChecks::default(),
             &ProviderContext::default(),
+            None,
         )
         .await
         .expect("run_plan should succeed");
@@ -1688,6 +1782,7 @@ mod run_plan_out_tests {
         let plan_path = dir.join("plan.json");
         run_plan(
             dir,
+            None,
             Some(&plan_path),
             DetailLevel::None,
             false,
@@ -1695,7 +1790,10 @@ mod run_plan_out_tests {
             true,
             false,
             false,
+            false,
+            PlanChecks::default(),
             &ProviderContext::default(),
+            None,
         )
         .await
         .expect("run_plan should succeed");
@@ -1707,6 +1805,11 @@ mod run_plan_out_tests {
             "plan --out file must end with a trailing newline; got {:?}",
             bytes.last().map(|b| *b as char),
         );
+        let saved: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            saved["changes"],
+            serde_json::json!({ "format_version": 1, "resource_changes": [] }),
+        );
     }
 
     #[test]
@@ -1750,9 +1853,10 @@ mod run_plan_out_tests {
             prev_explicit: HashMap::new(),
             residual_deferred_for: Vec::new(),
             expansion_trace: carina_core::resource::ExpansionTrace::new(),
+            blocked: Vec::new(),
         };
 
-        let plan_file = build_plan_file(&path, &parsed, None, &None, &ctx)
+        let plan_file = build_plan_file(&path, &parsed, None, &None, &ctx, None)
             .expect("saved plan should serialize");
 
         let saved = plan_file.data_sources[0]
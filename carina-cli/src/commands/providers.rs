@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+
+use carina_core::config_loader::{get_base_dir, load_configuration_with_config};
+use carina_core::parser::ProviderContext;
+use carina_core::schema::{SchemaRegistry, UnknownPropertyReport};
+use carina_state::{StateBackend, StateFile, resolve_backend_for_read};
+
+use crate::error::AppError;
+use crate::wiring::{WiringContext, build_factories_from_providers};
+
+#[derive(clap::Subcommand)]
+pub enum ProvidersCommands {
+    /// List attributes recorded in state that the installed provider
+    /// schemas do not declare, grouped by resource type
+    ReportUnknowns {
+        /// Path to directory containing .crn files
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub async fn run_providers_command(
+    command: ProvidersCommands,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    match command {
+        ProvidersCommands::ReportUnknowns { path, json } => {
+            run_report_unknowns(&path, json, provider_context).await
+        }
+    }
+}
+
+async fn run_report_unknowns(
+    path: &Path,
+    json: bool,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    let parsed =
+        load_configuration_with_config(path, provider_context, &SchemaRegistry::new())?.parsed;
+    let base_dir = get_base_dir(path);
+    let (factories, _) = build_factories_from_providers(&parsed.providers, base_dir);
+    let ctx = WiringContext::new(factories);
+
+    let backend: Box<dyn StateBackend> = resolve_backend_for_read(parsed.backend.as_ref())
+        .await
+        .map_err(AppError::Backend)?;
+    let state = backend
+        .read_state()
+        .await
+        .map_err(AppError::Backend)?
+        .map(|loaded| loaded.into_state())
+        .ok_or_else(|| AppError::Config("No state file found.".to_string()))?;
+
+    let report = unknown_properties_in_state(ctx.schemas(), &state);
+    if json {
+        let out = serde_json::to_string_pretty(&report)
+            .map_err(|e| AppError::Config(format!("Failed to serialize report: {e}")))?;
+        println!("{out}");
+    } else {
+        print!("{}", format_unknowns_report(&report));
+    }
+    Ok(())
+}
+
+/// Compare every resource's recorded attributes with the current schemas.
+pub fn unknown_properties_in_state(
+    schemas: &SchemaRegistry,
+    state: &StateFile,
+) -> UnknownPropertyReport {
+    let mut report = UnknownPropertyReport::new();
+    for rs in &state.resources {
+        report.record(
+            schemas,
+            &rs.provider,
+            &rs.resource_type,
+            rs.attributes.keys().map(String::as_str),
+        );
+    }
+    report
+}
+
+/// Format the human-readable report.
+pub fn format_unknowns_report(report: &UnknownPropertyReport) -> String {
+    if report.is_empty() {
+        return "No unknown properties.\n".to_string();
+    }
+    let mut out = String::from("Properties not declared by the provider schema:\n");
+    for (resource_type, names) in report.iter() {
+        out.push_str(&format!("  {resource_type}\n"));
+        for name in names {
+            out.push_str(&format!("    {name}\n"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::schema::{AttributeSchema, AttributeType, ResourceSchema};
+    use carina_state::ResourceState;
+
+    #[test]
+    fn report_lists_state_attributes_missing_from_schema() {
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert(
+            "awscc",
+            ResourceSchema::new("ec2.Vpc")
+                .attribute(AttributeSchema::new("cidr_block", AttributeType::string())),
+        );
+        let mut state = StateFile::new();
+        state.upsert_resource(
+            ResourceState::new("ec2.Vpc", "main", "awscc")
+                .with_attribute("cidr_block", serde_json::json!("10.0.0.0/16"))
+                .with_attribute("ipv6_native", serde_json::json!(false)),
+        );
+
+        let report = unknown_properties_in_state(&schemas, &state);
+
+        assert_eq!(
+            format_unknowns_report(&report),
+            "Properties not declared by the provider schema:\n  awscc.ec2.Vpc\n    ipv6_native\n"
+        );
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({ "awscc.ec2.Vpc": ["ipv6_native"] })
+        );
+    }
+
+    #[test]
+    fn empty_report_says_so() {
+        assert_eq!(
+            format_unknowns_report(&UnknownPropertyReport::new()),
+            "No unknown properties.\n"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Pre-apply API call budget and credential lifetime warnings, and the
+//! local timing history that feeds them.
+//!
+//! Timings live in `~/.carina/cache/api-call-stats.json`, shared by every
+//! project, so applying never writes into the configuration's source
+//! tree. Loading and saving are best-effort: a missing or corrupt file
+//! falls back to default estimates, and a failed save only costs accuracy
+//! on the next run, so neither aborts an apply.
+
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use colored::Colorize;
+
+use carina_core::api_budget::{
+    ApiCallStats, ApiCallStatsRecorder, DEFAULT_SERVICE_CALL_BUDGET, estimate_api_calls,
+    estimate_apply_duration,
+};
+use carina_core::executor::ExecutionObserver;
+use carina_core::plan::Plan;
+use carina_core::provider::ProviderRouter;
+
+/// Path of the timing history relative to the user's Carina directory.
+pub(crate) const API_CALL_STATS_FILE: &str = "cache/api-call-stats.json";
+
+/// `~/.carina/cache/api-call-stats.json`, or `None` when the home
+/// directory cannot be determined.
+fn stats_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".carina").join(API_CALL_STATS_FILE))
+}
+
+fn load_stats_from(path: &Path) -> ApiCallStats {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn load_api_call_stats() -> ApiCallStats {
+    stats_path()
+        .map(|path| load_stats_from(&path))
+        .unwrap_or_default()
+}
+
+/// Warning lines for provider instances whose estimated call count to a
+/// service exceeds the budget. Empty when the plan fits.
+pub(crate) fn api_budget_warnings(plan: &Plan, stats: &ApiCallStats) -> Vec<String> {
+    let estimate = estimate_api_calls(plan, stats);
+    estimate
+        .over_budget(DEFAULT_SERVICE_CALL_BUDGET)
+        .into_iter()
+        .map(|(key, calls)| {
+            let through = if key.instance.is_empty() {
+                String::new()
+            } else {
+                format!(" through provider '{}'", key.instance)
+            };
+            format!(
+                "Warning: this plan is estimated to issue ~{calls} {} API calls{through}, over \
+                 the budget of {DEFAULT_SERVICE_CALL_BUDGET} per provider; the apply may be \
+                 throttled. Consider splitting the change across several applies.",
+                key.service
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn print_api_budget_warnings(plan: &Plan) {
+    let stats = load_api_call_stats();
+    for warning in api_budget_warnings(plan, &stats) {
+        human_println!("{}", warning.yellow());
+    }
+}
+
+/// Warning lines for provider instances whose credentials (`expiries`)
+/// run out before an apply estimated to take `estimate` from `now`
+/// would finish.
+pub(crate) fn credential_expiry_warnings(
+    expiries: &[(String, SystemTime)],
+    now: SystemTime,
+    estimate: Duration,
+) -> Vec<String> {
+    expiries
+        .iter()
+        .filter(|(_, expiry)| *expiry < now + estimate)
+        .map(|(instance, expiry)| {
+            let remaining = expiry.duration_since(now).unwrap_or_default();
+            format!(
+                "Warning: credentials for provider '{instance}' expire in {}m, but this apply \
+                 is estimated to take up to {}m; refresh them before applying.",
+                remaining.as_secs() / 60,
+                estimate.as_secs().div_ceil(60)
+            )
+        })
+        .collect()
+}
+
+/// Warn about credentials that will not last the apply. Only instances
+/// that report an expiry are checked; WASM providers never do, as the
+/// plugin interface does not carry `Provider::credential_expiry`.
+pub(crate) async fn print_credential_expiry_warnings(
+    provider: &ProviderRouter,
+    plan: &Plan,
+    parallelism: NonZeroUsize,
+) {
+    let expiries = provider.credential_expiries_by_instance().await;
+    if expiries.is_empty() {
+        return;
+    }
+    let estimate = estimate_apply_duration(plan, &load_api_call_stats(), parallelism);
+    for warning in credential_expiry_warnings(&expiries, SystemTime::now(), estimate) {
+        human_println!("{}", warning.yellow());
+    }
+}
+
+/// Wrap `observer` so the apply records per-type operation timings.
+pub(crate) fn record_api_call_stats(
+    observer: Box<dyn ExecutionObserver>,
+) -> (Box<dyn ExecutionObserver>, Arc<Mutex<ApiCallStats>>) {
+    let (recorder, recorded) = ApiCallStatsRecorder::wrap(observer);
+    (Box::new(recorder), recorded)
+}
+
+/// Merge this apply's timings into the history on disk.
+pub(crate) fn save_api_call_stats(recorded: &Mutex<ApiCallStats>) {
+    if let Some(path) = stats_path() {
+        save_stats_to(&path, recorded);
+    }
+}
+
+fn save_stats_to(path: &Path, recorded: &Mutex<ApiCallStats>) {
+    let recorded = recorded.lock().unwrap();
+    if recorded.is_empty() {
+        return;
+    }
+    let mut stats = load_stats_from(path);
+    stats.merge(&recorded);
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            let json = serde_json::to_string_pretty(&stats).map_err(std::io::Error::other)?;
+            std::fs::write(path, json)
+        });
+    if let Err(e) = result {
+        human_println!(
+            "{}",
+            format!(
+                "Warning: failed to save API call stats to {}: {e}",
+                path.display()
+            )
+            .yellow()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::effect::Effect;
+    use carina_core::resource::{ResolvedResource, Resource};
+
+    #[test]
+    fn warns_only_for_services_over_budget() {
+        let mut plan = Plan::new();
+        for i in 0..60 {
+            plan.add(Effect::Create(ResolvedResource::new(Resource::new(
+                "ec2.Subnet",
+                format!("subnet{i}"),
+            ))));
+        }
+        plan.add(Effect::Create(ResolvedResource::new(Resource::new(
+            "s3.Bucket",
+            "bucket",
+        ))));
+        let warnings = api_budget_warnings(&plan, &ApiCallStats::new());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("~240 ec2 API calls,"), "{warnings:?}");
+        assert!(
+            warnings[0].contains("budget of 200 per provider")
+                && !warnings[0].contains("parallelism"),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn budgets_each_provider_instance_separately() {
+        let mut plan = Plan::new();
+        for i in 0..60 {
+            let binding = (i % 2 == 0).then(|| "prod".to_string());
+            let mut resource = Resource::new("ec2.Subnet", format!("subnet{i}"));
+            resource.id.provider = "awscc".to_string();
+            resource.id.provider_instance = binding;
+            plan.add(Effect::Create(ResolvedResource::new(resource)));
+        }
+        // 240 calls in total, but only 120 through each instance.
+        assert!(api_budget_warnings(&plan, &ApiCallStats::new()).is_empty());
+
+        for i in 60..90 {
+            let mut resource = Resource::new("ec2.Subnet", format!("subnet{i}"));
+            resource.id.provider = "awscc".to_string();
+            plan.add(Effect::Create(ResolvedResource::new(resource)));
+        }
+        let warnings = api_budget_warnings(&plan, &ApiCallStats::new());
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains("~240 ec2 API calls through provider 'awscc',"),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn warns_when_credentials_expire_before_the_estimated_end() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let expiries = vec![
+            ("awscc".to_string(), now + Duration::from_secs(600)),
+            ("awscc.prod".to_string(), now + Duration::from_secs(3600)),
+        ];
+        let warnings = credential_expiry_warnings(&expiries, now, Duration::from_secs(1500));
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains("'awscc' expire in 10m") && warnings[0].contains("take up to 25m"),
+            "{warnings:?}"
+        );
+        assert!(credential_expiry_warnings(&expiries, now, Duration::from_secs(60)).is_empty());
+    }
+
+    #[test]
+    fn save_merges_into_existing_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(API_CALL_STATS_FILE);
+        for secs in [10, 20] {
+            let mut stats = ApiCallStats::new();
+            stats.record("s3.Bucket", "create", Duration::from_secs(secs));
+            save_stats_to(&path, &Mutex::new(stats));
+        }
+        let loaded = load_stats_from(&path);
+        let sample = loaded.sample("s3.Bucket", "create").unwrap();
+        assert_eq!(sample.runs, 2);
+        assert_eq!(sample.mean(), Some(Duration::from_secs(15)));
+    }
+}
@@ -0,0 +1,136 @@
+//! End-of-apply timing summary and the optional OTLP metrics export.
+//!
+//! The summary table groups the operations that ran by resource type,
+//! slowest first. When `CARINA_METRICS_FILE` is set, the same numbers
+//! are written there as an OTLP/JSON `ExportMetricsServiceRequest`,
+//! which an OpenTelemetry collector's `otlpjsonfile` receiver can pick
+//! up. A failed export only warns; it never fails the apply.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+
+use carina_core::executor::OperationOutcome;
+use carina_core::executor::metrics::{ResourceTypeStats, summarize, to_otlp_json};
+use carina_core::resource::ResourceId;
+
+/// Where to write OTLP/JSON apply metrics, when set.
+pub const METRICS_FILE_ENV: &str = "CARINA_METRICS_FILE";
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 60.0 {
+        format!("{}m{:02}s", d.as_secs() / 60, d.as_secs() % 60)
+    } else {
+        format!("{secs:.1}s")
+    }
+}
+
+pub(crate) fn format_apply_metrics(stats: &[ResourceTypeStats]) -> String {
+    let mut out = String::new();
+    if stats.is_empty() {
+        return out;
+    }
+    let width = stats
+        .iter()
+        .map(|s| s.resource_type.len())
+        .max()
+        .unwrap_or(0)
+        .max("Resource type".len());
+    out.push_str(&format!("\n{}\n", "Apply timing:".bold()));
+    out.push_str(&format!(
+        "  {}\n",
+        format!(
+            "{:<width$}  {:>4}  {:>8}  {:>8}  {:>5}  {:>8}  {:>5}  {:>7}",
+            "Resource type", "Ops", "Total", "Slowest", "API", "Latency", "Polls", "Retries"
+        )
+        .dimmed()
+    ));
+    for s in stats {
+        let ops = if s.failures > 0 {
+            format!("{:>4}", format!("{}!", s.operations))
+                .red()
+                .to_string()
+        } else {
+            format!("{:>4}", s.operations)
+        };
+        out.push_str(&format!(
+            "  {:<width$}  {ops}  {:>8}  {:>8}  {:>5}  {:>8}  {:>5}  {:>7}\n",
+            s.resource_type,
+            format_duration(s.total),
+            format_duration(s.slowest),
+            s.api_calls,
+            format_duration(s.api_latency),
+            s.polls,
+            s.retries,
+        ));
+    }
+    out
+}
+
+/// Print the timing summary and, when [`METRICS_FILE_ENV`] is set, export
+/// it. `started_at` is when the apply began executing effects.
+pub(crate) fn report_apply_metrics(
+    outcomes: &HashMap<ResourceId, OperationOutcome>,
+    started_at: SystemTime,
+) {
+    let stats = summarize(outcomes);
+    human_print!("{}", format_apply_metrics(&stats));
+
+    let Some(path) = std::env::var_os(METRICS_FILE_ENV) else {
+        return;
+    };
+    let nanos = |t: SystemTime| t.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let doc = to_otlp_json(&stats, nanos(started_at), nanos(SystemTime::now()));
+    let result = serde_json::to_string(&doc)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(&path, json + "\n"));
+    if let Err(e) = result {
+        human_println!(
+            "{}",
+            format!(
+                "Warning: failed to write apply metrics to {}: {e}",
+                std::path::Path::new(&path).display()
+            )
+            .yellow()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_lists_types_and_flags_failures() {
+        let stats = vec![
+            ResourceTypeStats {
+                resource_type: "awscc.ec2.NatGateway".to_string(),
+                operations: 1,
+                total: Duration::from_secs(95),
+                slowest: Duration::from_secs(95),
+                api_calls: 12,
+                api_latency: Duration::from_millis(1500),
+                polls: 10,
+                ..Default::default()
+            },
+            ResourceTypeStats {
+                resource_type: "awscc.ec2.Subnet".to_string(),
+                operations: 2,
+                failures: 1,
+                total: Duration::from_secs(4),
+                slowest: Duration::from_secs(3),
+                ..Default::default()
+            },
+        ];
+        let table = format_apply_metrics(&stats);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines[1], "Apply timing:".bold().to_string(), "{table}");
+        assert!(lines[3].starts_with("  awscc.ec2.NatGateway"), "{table}");
+        assert!(lines[3].contains("1m35s"), "{table}");
+        assert!(lines[3].contains("1.5s"), "{table}");
+        assert!(lines[4].contains("2!"), "{table}");
+        assert!(format_apply_metrics(&[]).is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+//! Cost delta summary printed after the plan.
+//!
+//! Each provider prices its own resources from its configuration (for
+//! AWS providers, the bundled list prices of their region, which the
+//! router also falls back to for WASM providers), so the
+//! figures are a rough guide to fixed hourly charges rather than a bill
+//! forecast.
+
+use std::collections::HashMap;
+
+use colored::Colorize;
+
+use carina_core::cost::{CostEstimator, CostSummary};
+use carina_core::plan::Plan;
+use carina_core::resource::{ResourceId, Value};
+
+fn format_usd(usd: f64) -> String {
+    if usd < 0.0 {
+        format!("-${:.2}", -usd)
+    } else {
+        format!("+${usd:.2}")
+    }
+}
+
+pub(crate) fn format_cost_summary(summary: &CostSummary) -> String {
+    let mut out = String::new();
+    if summary.is_empty() {
+        return out;
+    }
+    out.push_str(&format!("\n{}\n", "Estimated monthly cost change:".bold()));
+    for change in &summary.changes {
+        out.push_str(&format!(
+            "  {:>10}  {} ({})\n",
+            format_usd(change.delta_usd()),
+            change.resource,
+            change.basis
+        ));
+    }
+    out.push_str(&format!(
+        "  {:>10}  {}\n",
+        format_usd(summary.monthly_delta_usd()).bold(),
+        "total (USD list prices; usage charges excluded)".dimmed()
+    ));
+    out
+}
+
+/// Price the plan with the providers that estimate costs and print the
+/// delta. Prints nothing when no resource the plan touches is priced.
+pub(crate) async fn print_cost_summary(
+    plan: &Plan,
+    delete_attributes: &HashMap<ResourceId, HashMap<String, Value>>,
+    estimator: &dyn CostEstimator,
+) {
+    let summary = CostSummary::for_plan(plan, delete_attributes, estimator).await;
+    human_print!("{}", format_cost_summary(&summary));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::cost::MonthlyCost;
+    use carina_core::effect::Effect;
+    use carina_core::provider::BoxFuture;
+    use carina_core::resource::{ResolvedResource, Resource};
+
+    /// Prices every NAT gateway at a flat hourly rate.
+    struct NatEstimator;
+
+    impl CostEstimator for NatEstimator {
+        fn monthly_cost<'a>(
+            &'a self,
+            id: &'a ResourceId,
+            _attributes: &'a HashMap<String, Value>,
+        ) -> BoxFuture<'a, Option<MonthlyCost>> {
+            Box::pin(async move {
+                (id.resource_type == "ec2.NatGateway").then(|| MonthlyCost {
+                    usd: 32.85,
+                    basis: "730 h × $0.045/h".to_string(),
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn summary_lists_priced_creates_and_the_total() {
+        let mut plan = Plan::new();
+        for name in ["nat_a", "nat_b"] {
+            plan.add(Effect::Create(ResolvedResource::new(
+                Resource::with_provider("awscc", "ec2.NatGateway", name, None),
+            )));
+        }
+        plan.add(Effect::Create(ResolvedResource::new(
+            Resource::with_provider("awscc", "ec2.Vpc", "main", None),
+        )));
+
+        let summary = CostSummary::for_plan(&plan, &HashMap::new(), &NatEstimator).await;
+        let out = format_cost_summary(&summary);
+
+        assert!(out.contains("+$32.85  awscc.ec2.NatGateway nat_a (730 h × $0.045/h)"));
+        assert!(out.contains("+$65.70"));
+        assert!(!out.contains("ec2.Vpc"));
+    }
+
+    #[tokio::test]
+    async fn unpriced_plans_print_nothing() {
+        let summary = CostSummary::for_plan(&Plan::new(), &HashMap::new(), &NatEstimator).await;
+        assert!(format_cost_summary(&summary).is_empty());
+    }
+}
@@ -26,15 +26,25 @@ pub(crate) async fn execute_import_effects(
             // that performs this check, so any future caller is forced
             // to handle the deferred case explicitly via its `Result`
             // contract rather than rolling its own ad-hoc match.
+            if !provider.capabilities(id).supports_import {
+                human_println!(
+                    "  {} Import failed for {}: provider '{}' does not support import",
+                    "✗".red(),
+                    id,
+                    id.provider
+                );
+                result.failure_count += 1;
+                continue;
+            }
             let identifier_str = match resolve_import_identifier(identifier) {
                 Ok(s) => s.to_string(),
                 Err(e) => {
-                    println!("  {} Import failed for {}: {}", "✗".red(), id, e);
+                    human_println!("  {} Import failed for {}: {}", "✗".red(), id, e);
                     result.failure_count += 1;
                     continue;
                 }
             };
-            println!(
+            human_println!(
                 "  {} Importing {} (id: {})...",
                 "<-".cyan(),
                 id,
@@ -46,11 +56,11 @@ pub(crate) async fn execute_import_effects(
             {
                 Ok(state) => {
                     if state.exists {
-                        println!("  {} Imported {}", "✓".green(), id);
+                        human_println!("  {} Imported {}", "✓".green(), id);
                         result.applied_states.insert(id.clone().into_inner(), state);
                         result.success_count += 1;
                     } else {
-                        println!(
+                        human_println!(
                             "  {} Import failed: resource {} with id {} not found",
                             "✗".red(),
                             id,
@@ -60,7 +70,7 @@ pub(crate) async fn execute_import_effects(
                     }
                 }
                 Err(e) => {
-                    println!("  {} Import failed for {}: {}", "✗".red(), id, e);
+                    human_println!("  {} Import failed for {}: {}", "✗".red(), id, e);
                     result.failure_count += 1;
                 }
             }
@@ -92,7 +102,7 @@ fn format_state_only_effect_line(effect: &Effect) -> Option<String> {
 pub(crate) fn execute_state_only_effects(plan: &Plan, result: &mut ExecutionResult) {
     for effect in plan.effects() {
         if let Some(line) = format_state_only_effect_line(effect) {
-            println!("{}", line);
+            human_println!("{}", line);
             result.success_count += 1;
         }
     }
@@ -128,6 +138,7 @@ mod tests {
             current_states: HashMap::new(),
             bindings: carina_core::binding_index::ResolvedBindings::default(),
             failed_refreshes: HashSet::new(),
+            operation_outcomes: HashMap::new(),
         }
     }
 
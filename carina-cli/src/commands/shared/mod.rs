@@ -6,8 +6,11 @@
 //! out lets the command files focus on top-level flow and tightens the
 //! cohesion of the shared utilities.
 
+pub(crate) mod api_budget;
+pub(crate) mod apply_metrics;
 #[cfg(test)]
 pub(crate) mod cancellation_test_support;
+pub(crate) mod cost;
 pub(crate) mod effect_execution;
 pub(crate) mod finalize;
 pub(crate) mod observer;
@@ -15,3 +18,4 @@ pub(crate) mod plan_errors;
 pub(crate) mod progress;
 pub(crate) mod retry;
 pub(crate) mod state_writeback;
+pub(crate) mod trace_export;
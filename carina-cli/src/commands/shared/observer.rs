@@ -15,8 +15,10 @@ use std::io::IsTerminal;
 use std::sync::Mutex;
 use std::time::Duration;
 
-use carina_core::executor::{ExecutionEvent, ExecutionObserver};
+use carina_core::effect::Effect;
+use carina_core::executor::{ExecutionEvent, ExecutionObserver, ProgressInfo};
 use carina_core::plan::Plan;
+use carina_core::resource::ResourceId;
 use carina_core::value::format_value_user_facing;
 use carina_core::wait::WaitObservation;
 use colored::Colorize;
@@ -24,6 +26,7 @@ use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
 use crate::commands::shared::progress::{format_progress, spinner_style};
 use crate::display::format_effect;
+use crate::events::{self, ApplyEvent, FinishStatus};
 
 use super::progress::format_duration;
 
@@ -153,6 +156,7 @@ fn handle_tty(
             error,
             duration,
             progress,
+            ..
         } => {
             let key = format_effect(effect);
             let timing = format!("took {}", format_duration(*duration)).dimmed();
@@ -318,6 +322,7 @@ fn format_plain(event: &ExecutionEvent) -> Vec<String> {
             error,
             duration,
             progress,
+            ..
         } => {
             let timing = format!("took {}", format_duration(*duration));
             let counter = format_progress(progress);
@@ -367,6 +372,147 @@ fn format_plain(event: &ExecutionEvent) -> Vec<String> {
     }
 }
 
+/// Observer for `apply --json`: reports execution as NDJSON
+/// [`ApplyEvent`]s on stdout instead of drawing progress.
+pub(crate) struct JsonObserver;
+
+impl ExecutionObserver for JsonObserver {
+    fn on_event(&self, event: &ExecutionEvent) {
+        if let Some(event) = apply_event(event) {
+            events::emit(&event);
+        }
+    }
+}
+
+fn finished(
+    effect: &Effect,
+    status: FinishStatus,
+    duration: Option<Duration>,
+    progress: &ProgressInfo,
+    message: Option<String>,
+) -> ApplyEvent {
+    ApplyEvent::ResourceApplyFinished {
+        address: effect.resource_id().human().to_string(),
+        action: effect.kind(),
+        status,
+        duration_secs: duration.map(|d| d.as_secs_f64()),
+        completed: progress.completed,
+        total: progress.total,
+        message,
+    }
+}
+
+fn progress_event(id: &ResourceId, message: String) -> ApplyEvent {
+    ApplyEvent::Progress {
+        address: Some(id.human().to_string()),
+        message,
+    }
+}
+
+fn error_event(id: &ResourceId, message: String) -> ApplyEvent {
+    ApplyEvent::Error {
+        address: Some(id.human().to_string()),
+        message,
+    }
+}
+
+/// The event-stream form of an execution event; `None` for events with
+/// no counterpart (an effect still waiting on its dependencies).
+fn apply_event(event: &ExecutionEvent) -> Option<ApplyEvent> {
+    Some(match event {
+        ExecutionEvent::Waiting { .. } => return None,
+        ExecutionEvent::EffectStarted { effect } => ApplyEvent::ResourceApplyStarted {
+            address: effect.resource_id().human().to_string(),
+            action: effect.kind(),
+        },
+        ExecutionEvent::EffectSucceeded {
+            effect,
+            duration,
+            progress,
+            ..
+        } => finished(
+            effect,
+            FinishStatus::Succeeded,
+            Some(*duration),
+            progress,
+            None,
+        ),
+        ExecutionEvent::EffectPartiallySucceeded {
+            effect,
+            diagnostic,
+            duration,
+            progress,
+            ..
+        } => finished(
+            effect,
+            FinishStatus::Partial,
+            Some(*duration),
+            progress,
+            Some(format!(
+                "{}; missing attributes: {}",
+                diagnostic.reason(),
+                diagnostic.missing_attributes().join(", ")
+            )),
+        ),
+        ExecutionEvent::EffectFailed {
+            effect,
+            error,
+            duration,
+            progress,
+            ..
+        } => finished(
+            effect,
+            FinishStatus::Failed,
+            Some(*duration),
+            progress,
+            Some(error.to_string()),
+        ),
+        ExecutionEvent::EffectSkipped {
+            effect,
+            reason,
+            progress,
+        } => finished(
+            effect,
+            FinishStatus::Skipped,
+            None,
+            progress,
+            Some(reason.to_string()),
+        ),
+        ExecutionEvent::WaitPolling {
+            observation,
+            elapsed,
+        } => progress_event(
+            observation.target_id(),
+            format!(
+                "{}: waited {}, {}",
+                observation.binding(),
+                format_duration(*elapsed),
+                format_wait_observed_attr(observation)
+            ),
+        ),
+        ExecutionEvent::CascadeUpdateSucceeded { id } => {
+            progress_event(id, "cascade update succeeded".to_string())
+        }
+        ExecutionEvent::CascadeUpdateFailed { id, error } => {
+            error_event(id, format!("cascade update failed: {error}"))
+        }
+        ExecutionEvent::RenameSucceeded { id, from, to } => {
+            progress_event(id, format!("renamed \"{from}\" to \"{to}\""))
+        }
+        ExecutionEvent::RenameFailed { id, error } => {
+            error_event(id, format!("rename failed: {error}"))
+        }
+        ExecutionEvent::RefreshStarted => ApplyEvent::Progress {
+            address: None,
+            message: "refreshing uncertain resource states".to_string(),
+        },
+        ExecutionEvent::RefreshSucceeded { id } => progress_event(id, "refreshed".to_string()),
+        ExecutionEvent::RefreshFailed { id, error } => {
+            error_event(id, format!("refresh failed: {error}"))
+        }
+    })
+}
+
 fn format_wait_polling_line(observation: &WaitObservation, elapsed: Duration) -> String {
     let observed = format_wait_observed_attr(observation);
     format!(
@@ -394,10 +540,8 @@ fn format_wait_observed_attr(observation: &WaitObservation) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use carina_core::effect::Effect;
-    use carina_core::executor::ProgressInfo;
     use carina_core::resource::{
-        ConcreteValue, DeferredValue, ResolvedResource, Resource, ResourceId, UnknownReason, Value,
+        ConcreteValue, DeferredValue, ResolvedResource, Resource, UnknownReason, Value,
     };
     use carina_core::wait::predicate::{AttrPath, WaitPredicate};
     use indexmap::IndexMap;
@@ -579,6 +723,7 @@ mod tests {
         let lines = format_plain(&ExecutionEvent::EffectFailed {
             effect: &effect,
             error: "boom",
+            cause: None,
             duration: Duration::from_millis(50),
             progress: ProgressInfo {
                 completed: 2,
@@ -609,4 +754,65 @@ mod tests {
         assert_eq!(lines.len(), 1);
         assert!(lines[0].contains("dependency 'x' failed"));
     }
+
+    #[test]
+    fn json_maps_start_and_finish_and_drops_waiting() {
+        let effect = dummy_create_effect();
+        let address = effect.resource_id().human().to_string();
+        assert_eq!(
+            apply_event(&ExecutionEvent::EffectStarted { effect: &effect }),
+            Some(ApplyEvent::ResourceApplyStarted {
+                address: address.clone(),
+                action: "create",
+            })
+        );
+        assert_eq!(
+            apply_event(&ExecutionEvent::Waiting {
+                effect: &effect,
+                pending_dependencies: vec!["x".into()],
+            }),
+            None
+        );
+        assert_eq!(
+            apply_event(&ExecutionEvent::EffectSkipped {
+                effect: &effect,
+                reason: "dependency failed",
+                progress: ProgressInfo {
+                    completed: 2,
+                    total: 3,
+                },
+            }),
+            Some(ApplyEvent::ResourceApplyFinished {
+                address: address.clone(),
+                action: "create",
+                status: FinishStatus::Skipped,
+                duration_secs: None,
+                completed: 2,
+                total: 3,
+                message: Some("dependency failed".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn json_reports_wait_polling_as_progress() {
+        let target_id = ResourceId::with_identity("aws.test.Resource", "demo");
+        let predicate = equals_predicate(AttrPath::single("status"), "ready");
+        let attrs = HashMap::from([(
+            "status".to_string(),
+            Value::Concrete(ConcreteValue::String("pending".to_string())),
+        )]);
+        let observation = wait_observation(&target_id, &predicate, &attrs);
+        let Some(ApplyEvent::Progress { address, message }) =
+            apply_event(&ExecutionEvent::WaitPolling {
+                observation,
+                elapsed: Duration::from_secs(30),
+            })
+        else {
+            panic!("expected a progress event");
+        };
+        assert_eq!(address, Some(target_id.human().to_string()));
+        assert!(message.starts_with("demo_ready: waited"), "{message}");
+        assert!(message.ends_with("status=pending"), "{message}");
+    }
 }
@@ -1,6 +1,7 @@
 use colored::Colorize;
 
 use carina_core::plan::{Plan, PlanError, PlanErrorKind};
+use carina_core::provider::BlockedResource;
 
 use crate::error::AppError;
 
@@ -45,6 +46,28 @@ fn plan_error_summary(errors: &[PlanError]) -> String {
     }
 }
 
+/// Report resources left out of a plan because a provider instance failed
+/// to initialize. Printed after the plan so the blocked subset is the last
+/// thing the user sees.
+pub(crate) fn print_blocked_resources(blocked: &[BlockedResource]) {
+    if blocked.is_empty() {
+        return;
+    }
+    human_println!();
+    human_println!("{}", blocked_resources_report(blocked).yellow());
+}
+
+fn blocked_resources_report(blocked: &[BlockedResource]) -> String {
+    let mut report = format!(
+        "Blocked: {} resource(s) not planned because a provider instance failed to initialize:",
+        blocked.len()
+    );
+    for entry in blocked {
+        report.push_str(&format!("\n  {} — {}", entry.id.human(), entry.reason));
+    }
+    report
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,6 +98,25 @@ mod tests {
             .to_string()
     }
 
+    #[test]
+    fn blocked_report_lists_each_resource_with_its_reason() {
+        let blocked = vec![
+            BlockedResource {
+                id: ResourceId::with_identity("mock.test.resource", "a"),
+                reason: "provider 'us' (mock) failed to initialize: bad role".to_string(),
+            },
+            BlockedResource {
+                id: ResourceId::with_identity("mock.test.resource", "b"),
+                reason: "depends on blocked 'a'".to_string(),
+            },
+        ];
+        let report = blocked_resources_report(&blocked);
+        assert!(report.starts_with("Blocked: 2 resource(s) not planned"));
+        assert!(report.contains("bad role"));
+        assert!(report.contains("depends on blocked 'a'"));
+        assert_eq!(report.lines().count(), 3);
+    }
+
     #[test]
     fn empty_plan_error_helper_returns_ok() {
         assert!(render_plan_errors_and_abort(&Plan::new()).is_ok());
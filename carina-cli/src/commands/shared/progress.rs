@@ -48,7 +48,7 @@ pub(crate) struct RefreshProgress {
 impl RefreshProgress {
     /// Print the "Refreshing state..." header and prepare for per-resource spinners.
     pub fn start_header() {
-        println!("{}", "Refreshing state...".cyan());
+        human_println!("{}", "Refreshing state...".cyan());
     }
 
     /// Begin tracking a resource read under a shared `MultiProgress`.
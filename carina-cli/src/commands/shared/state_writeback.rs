@@ -226,7 +226,7 @@ impl ExportResolution {
     pub(crate) fn write_into(self, state: &mut StateFile) {
         let mut next = HashMap::new();
         for skipped in &self.skipped {
-            println!("{}", render_skipped(skipped));
+            human_println!("{}", render_skipped(skipped));
             if let Some(prior) = state.exports.get(&skipped.name) {
                 next.insert(skipped.name.clone(), prior.clone());
             }
@@ -474,6 +474,9 @@ pub(crate) fn dsl_value_to_json(
         Value::Concrete(ConcreteValue::Duration(d)) => {
             Ok(Some(serde_json::Value::Number((d.as_secs() as i64).into())))
         }
+        Value::Concrete(ConcreteValue::ByteSize(n)) => {
+            Ok(Some(serde_json::Value::Number((*n).into())))
+        }
         Value::Concrete(ConcreteValue::List(items)) => {
             // `Result::transpose` flips `Result<Option<T>, E>` to
             // `Option<Result<T, E>>`, so `filter_map` drops the
@@ -753,11 +756,14 @@ pub(crate) fn build_state_after_apply(save: ApplyStateSave<'_>) -> Result<StateF
     for id in &writeback.cleanups {
         state.remove_resource(&id.provider, &id.resource_type, id.identity_or_empty());
     }
+    let desired: HashSet<String> = sorted_resources.iter().map(|r| r.id.to_string()).collect();
+    state.prune_last_operations(&desired);
 
     Ok(state)
 }
 
-/// Apply destroy results to the state file: remove destroyed resources and
+/// Apply destroy results to the state file: remove destroyed resources,
+/// drop recorded failures of creates that never produced a resource, and
 /// clear any exports (since exports reference attributes of destroyed resources).
 pub(crate) fn apply_destroy_to_state(
     state: &mut carina_state::StateFile,
@@ -766,6 +772,7 @@ pub(crate) fn apply_destroy_to_state(
     for id in destroyed_ids {
         state.remove_resource(&id.provider, &id.resource_type, id.identity_or_empty());
     }
+    state.prune_last_operations(&HashSet::new());
     state.exports.clear();
 }
 
@@ -1135,6 +1142,50 @@ mod apply_state_save_tests {
         assert!(wb.cleanups.is_empty());
     }
 
+    #[test]
+    fn replace_whose_create_returns_no_identifier_does_not_inherit_the_old_one() {
+        let id = ResourceId::with_provider_identity(
+            "aws",
+            "route53.Record",
+            "validation_records[0]",
+            None,
+        );
+        let runtime_child =
+            Resource::with_provider("aws", "route53.Record", "validation_records[0]", None)
+                .with_binding("validation_records[0]");
+        let mut state_file = StateFile::new();
+        state_file.resources.push(
+            ResourceState::new("route53.Record", "validation_records[0]", "aws")
+                .with_identifier("old-record-id"),
+        );
+        let mut plan = Plan::new();
+        plan.add(deferred_replace_effect(&id));
+
+        let state = build_state_after_apply(ApplyStateSave {
+            state_file: Some(state_file),
+            sorted_resources: &[],
+            runtime_synthesized_resources: std::slice::from_ref(&runtime_child),
+            current_states: &HashMap::new(),
+            applied_states: &HashMap::from([(
+                id.clone(),
+                State::existing(id.clone(), HashMap::new()),
+            )]),
+            plan: &plan,
+            successfully_deleted: &HashSet::from([id.clone()]),
+            failed_refreshes: &HashSet::new(),
+            schemas: &carina_core::schema::SchemaRegistry::new(),
+        })
+        .expect("state writeback should succeed");
+
+        let saved = state
+            .find_resource("aws", "route53.Record", "validation_records[0]")
+            .expect("replacement should be persisted");
+        assert_eq!(
+            saved.identifier, None,
+            "the deleted resource's identifier must not carry over to its replacement"
+        );
+    }
+
     #[test]
     fn deferred_replace_delete_success_create_failure_writes_cleanup() {
         let id = ResourceId::with_provider_identity(
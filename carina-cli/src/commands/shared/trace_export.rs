@@ -0,0 +1,114 @@
+//! Optional OpenTelemetry trace export for plan and apply.
+//!
+//! When `CARINA_TRACES_FILE` is set, each plan and apply appends one
+//! OTLP/JSON `ExportTraceServiceRequest` line to that file, for an
+//! OpenTelemetry collector's `otlpjsonfile` receiver to forward. Apply
+//! spans link to the spans of the plan they carry out: the plan's trace
+//! id travels in the saved plan file, or stays in memory for a plain
+//! `carina apply`. Export failures only warn.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use colored::Colorize;
+
+use carina_core::executor::{ExecutionObserver, OperationOutcome};
+use carina_core::plan::Plan;
+use carina_core::resource::ResourceId;
+use carina_core::trace::{
+    SpanId, SpanRecord, TraceId, TraceRecorder, annotate_operation_metrics, plan_root_span_id,
+    plan_spans, to_otlp_json,
+};
+
+/// File that plan and apply append OTLP/JSON trace lines to, when set.
+pub const TRACES_FILE_ENV: &str = "CARINA_TRACES_FILE";
+
+fn append_spans(spans: &[SpanRecord]) {
+    let Some(path) = std::env::var_os(TRACES_FILE_ENV) else {
+        return;
+    };
+    let result = serde_json::to_string(&to_otlp_json(spans))
+        .map_err(std::io::Error::other)
+        .and_then(|line| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?
+                .write_all(format!("{line}\n").as_bytes())
+        });
+    if let Err(e) = result {
+        human_println!(
+            "{}",
+            format!(
+                "Warning: failed to write traces to {}: {e}",
+                std::path::Path::new(&path).display()
+            )
+            .yellow()
+        );
+    }
+}
+
+/// Export the spans of `plan`, computed since `started`. Returns the
+/// plan's trace id for the apply to link to, or `None` when tracing is
+/// off.
+pub(crate) fn export_plan_trace(plan: &Plan, started: SystemTime) -> Option<TraceId> {
+    std::env::var_os(TRACES_FILE_ENV)?;
+    let trace = TraceId::random();
+    append_spans(&plan_spans(plan, trace, started, SystemTime::now()));
+    Some(trace)
+}
+
+/// An apply trace in progress; finished by [`export_apply_trace`].
+pub(crate) struct ApplyTrace {
+    trace: TraceId,
+    root: SpanId,
+    started: SystemTime,
+    plan_trace: Option<TraceId>,
+    spans: Arc<Mutex<Vec<SpanRecord>>>,
+}
+
+/// Wrap `observer` so the apply records a span per effect when tracing
+/// is on, linked to the spans of `plan_trace`.
+pub(crate) fn trace_apply(
+    observer: Box<dyn ExecutionObserver>,
+    plan_trace: Option<TraceId>,
+) -> (Box<dyn ExecutionObserver>, Option<ApplyTrace>) {
+    if std::env::var_os(TRACES_FILE_ENV).is_none() {
+        return (observer, None);
+    }
+    let trace = TraceId::random();
+    let root = SpanId::random();
+    let (recorder, spans) = TraceRecorder::wrap(observer, trace, root, plan_trace);
+    (
+        Box::new(recorder),
+        Some(ApplyTrace {
+            trace,
+            root,
+            started: SystemTime::now(),
+            plan_trace,
+            spans,
+        }),
+    )
+}
+
+/// Close the apply's root span, add the provider-reported counters from
+/// `outcomes`, and export the trace.
+pub(crate) fn export_apply_trace(
+    trace: Option<ApplyTrace>,
+    outcomes: &HashMap<ResourceId, OperationOutcome>,
+) {
+    let Some(trace) = trace else {
+        return;
+    };
+    let mut root = SpanRecord::new(trace.trace, trace.root, "carina apply");
+    root.start = trace.started;
+    if let Some(plan_trace) = trace.plan_trace {
+        root.links.push((plan_trace, plan_root_span_id(plan_trace)));
+    }
+    let mut spans = vec![root];
+    spans.append(&mut trace.spans.lock().unwrap());
+    annotate_operation_metrics(&mut spans, outcomes);
+    append_spans(&spans);
+}
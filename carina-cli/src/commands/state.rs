@@ -4,7 +4,6 @@ use std::path::{Path, PathBuf};
 
 use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
 use colored::Colorize;
-use futures::stream::{FuturesUnordered, StreamExt};
 use tokio_util::sync::CancellationToken;
 
 use carina_core::config_loader::{get_base_dir, load_configuration_with_config};
@@ -14,10 +13,13 @@ use carina_core::executor::UnresolvedDataSourceInput;
 use carina_core::parser::ProviderContext;
 use carina_core::plan::Plan;
 use carina_core::provider::{self as provider_mod, Provider, ProviderNormalizer};
+use carina_core::refresh::RefreshError;
 use carina_core::resource::{
     ConcreteValue, ResolvedDataSource, Resource, ResourceId, State, Value,
 };
 use carina_core::value::{format_value, json_to_dsl_value};
+use carina_state::cfn_import::{CfnTypeMapping, build_import_entries, to_import_json};
+use carina_state::report::{rows_from_state, to_csv, to_json};
 use carina_state::{
     BackendConfig as StateBackendConfig, BackendError, LockInfo, ResourceState, StateBackend,
     StateFile, StateUrl, create_backend, load_state_from_url, resolve_backend_anchored,
@@ -25,8 +27,8 @@ use carina_state::{
 };
 
 use super::{
-    BackendDriftStatus, DriftCommand, inspect_backend_drift, validate_and_resolve_with_config,
-    verify_for_mutation,
+    BackendDriftStatus, DriftCommand, inspect_backend_drift, load_bound_configuration,
+    validate_and_resolve_with_config, verify_for_mutation,
 };
 use crate::commands::shared::state_writeback::apply_name_overrides;
 use crate::error::AppError;
@@ -80,13 +82,18 @@ fn format_deferred_state_refresh_warning(
 
 /// Read local state file for shell completion.
 ///
-/// Tries `carina.state.json` in the current directory. Returns `None` if the
-/// file does not exist or cannot be parsed (completion simply produces no
+/// Tries `carina.state.json` in the current directory, then the state
+/// replica the language server keeps in `.carina/` (which also covers
+/// remote backends). Never reads the backend itself, so completion stays
+/// fast and cannot contend with a running `apply`. Returns `None` if
+/// neither file exists or parses (completion simply produces no
 /// candidates in that case).
 fn read_local_state_for_completion() -> Option<StateFile> {
     let path = std::path::Path::new("carina.state.json");
-    let contents = std::fs::read_to_string(path).ok()?;
-    serde_json::from_str(&contents).ok()
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .or_else(|| carina_state::StateReplica::new(std::path::Path::new(".")).load())
 }
 
 /// Shell completion function for `state lookup` queries.
@@ -193,6 +200,11 @@ pub enum StateCommands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
         /// Enable/disable state locking (default: true)
         #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
         lock: bool,
@@ -208,6 +220,17 @@ pub enum StateCommands {
         /// local path. Mutually exclusive with [PATH].
         #[arg(long, conflicts_with = "path")]
         state_url: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: ListFormat,
+
+        /// JSON file adding or correcting the CloudFormation type name and
+        /// identifier property per resource type, e.g.
+        /// `{"ec2.Eip": {"type": "AWS::EC2::EIP", "identifier": "AllocationId"}}`
+        /// (only with `--format cfn-import`)
+        #[arg(long, value_name = "FILE")]
+        cfn_mapping: Option<PathBuf>,
     },
     /// Look up resource attributes from the state file
     Lookup {
@@ -251,6 +274,19 @@ pub enum StateCommands {
     },
 }
 
+/// Output format for `state list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ListFormat {
+    /// One `provider.type name` line per resource
+    Text,
+    /// CSV with a header row, tags flattened to `key=value;...`
+    Csv,
+    /// JSON array of the rows
+    Json,
+    /// CloudFormation resource import file (`--resources-to-import`)
+    CfnImport,
+}
+
 /// Run state subcommands
 pub async fn run_state_command(
     command: StateCommands,
@@ -263,11 +299,25 @@ pub async fn run_state_command(
             force,
             path,
         } => run_state_bucket_delete(&bucket_name, force, &path, provider_context).await,
-        StateCommands::Refresh { path, lock } => {
-            run_state_refresh(&path, lock, provider_context, cancel).await
-        }
-        StateCommands::List { path, state_url } => {
-            run_state_list(path.as_deref(), state_url.as_deref(), provider_context).await
+        StateCommands::Refresh {
+            path,
+            var_file,
+            lock,
+        } => run_state_refresh(&path, var_file.as_deref(), lock, provider_context, cancel).await,
+        StateCommands::List {
+            path,
+            state_url,
+            format,
+            cfn_mapping,
+        } => {
+            run_state_list(
+                path.as_deref(),
+                state_url.as_deref(),
+                format,
+                cfn_mapping.as_deref(),
+                provider_context,
+            )
+            .await
         }
         StateCommands::Lookup {
             query,
@@ -405,13 +455,9 @@ fn find_resource_by_query<'a>(state: &'a StateFile, name: &str) -> Option<&'a Re
 
 /// Format state list output. Returns each line as a string.
 fn format_state_list(state: &StateFile) -> Vec<String> {
-    state
-        .resources
+    rows_from_state(state)
         .iter()
-        .map(|rs| {
-            let display_name = rs.binding.as_deref().unwrap_or(&rs.identity);
-            format!("{}.{} {}", rs.provider, rs.resource_type, display_name)
-        })
+        .map(|row| format!("{} {}", row.qualified_type(), row.name))
         .collect()
 }
 
@@ -419,22 +465,60 @@ fn format_state_list(state: &StateFile) -> Vec<String> {
 async fn run_state_list(
     path: Option<&Path>,
     state_url: Option<&str>,
+    format: ListFormat,
+    cfn_mapping: Option<&Path>,
     provider_context: &ProviderContext,
 ) -> Result<(), AppError> {
-    let state = load_state_file(path, state_url, provider_context).await?;
-
-    if state.resources.is_empty() {
-        println!("No resources in state.");
-        return Ok(());
+    if cfn_mapping.is_some() && !matches!(format, ListFormat::CfnImport) {
+        return Err(AppError::Config(
+            "--cfn-mapping only applies to --format cfn-import".to_string(),
+        ));
     }
+    let state = load_state_file(path, state_url, provider_context).await?;
 
-    for line in format_state_list(&state) {
-        println!("{}", line);
+    match format {
+        ListFormat::Csv => print!("{}", to_csv(&rows_from_state(&state))),
+        ListFormat::Json => println!("{}", to_json(&rows_from_state(&state))),
+        ListFormat::CfnImport => {
+            let mapping = load_cfn_mapping(cfn_mapping)?;
+            let (entries, skipped) = build_import_entries(&rows_from_state(&state), &mapping);
+            for skip in &skipped {
+                eprintln!(
+                    "{}",
+                    format!("Skipping {}: {}", skip.name, skip.reason).yellow()
+                );
+            }
+            println!("{}", to_import_json(&entries));
+        }
+        ListFormat::Text => {
+            if state.resources.is_empty() {
+                println!("No resources in state.");
+                return Ok(());
+            }
+            for line in format_state_list(&state) {
+                println!("{}", line);
+            }
+        }
     }
 
     Ok(())
 }
 
+fn load_cfn_mapping(path: Option<&Path>) -> Result<CfnTypeMapping, AppError> {
+    let Some(path) = path else {
+        return Ok(CfnTypeMapping::new());
+    };
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| AppError::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_str(&content).map_err(|e| {
+        AppError::Config(format!(
+            "Invalid CloudFormation mapping {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
 /// Format lookup output for a query against a state file.
 ///
 /// Three address shapes are accepted (in resolution order):
@@ -658,6 +742,15 @@ fn format_state_show(state: &StateFile) -> String {
             "# {}.{} ({})\n",
             rs.provider, rs.resource_type, display_name
         ));
+        let id = ResourceId::with_provider_name_compat(
+            &rs.provider,
+            &rs.resource_type,
+            &rs.identity,
+            None,
+        );
+        if let Some(last) = state.last_operation(&id) {
+            output.push_str(&format!("  # last operation: {}\n", last));
+        }
 
         // Sort attributes for deterministic output
         let mut keys: Vec<&String> = rs.attributes.keys().collect();
@@ -669,6 +762,31 @@ fn format_state_show(state: &StateFile) -> String {
             }
         }
     }
+
+    // A failed create leaves no resource row; show its failure on its own.
+    let addresses: HashSet<String> = state
+        .resources
+        .iter()
+        .map(|rs| {
+            ResourceId::with_provider_name_compat(
+                &rs.provider,
+                &rs.resource_type,
+                &rs.identity,
+                None,
+            )
+            .to_string()
+        })
+        .collect();
+    for (address, last) in &state.last_operations {
+        if addresses.contains(address) {
+            continue;
+        }
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&format!("# {} (not in state)\n", address));
+        output.push_str(&format!("  # last operation: {}\n", last));
+    }
     output
 }
 
@@ -689,7 +807,7 @@ async fn run_state_show(
         return Ok(());
     }
 
-    if state.resources.is_empty() {
+    if state.resources.is_empty() && state.last_operations.is_empty() {
         println!("No resources in state.");
         return Ok(());
     }
@@ -848,15 +966,12 @@ async fn run_state_bucket_delete(
 /// Run state refresh command
 pub async fn run_state_refresh(
     path: &Path,
+    var_file: Option<&Path>,
     lock: bool,
     provider_context: &ProviderContext,
     cancel: CancellationToken,
 ) -> Result<(), AppError> {
-    let loaded = load_configuration_with_config(
-        path,
-        provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    )?;
+    let loaded = load_bound_configuration(path, var_file, provider_context)?;
     let mut parsed = loaded.parsed;
 
     let base_dir = get_base_dir(path);
@@ -1187,8 +1302,7 @@ pub(crate) async fn run_state_refresh_locked(
 
     println!();
 
-    let mut updated_count = 0u32;
-    let mut unchanged_count = 0u32;
+    let mut summary = RefreshSummary::default();
 
     for resource in &sorted_resources {
         let fresh_state = match current_states.get(&resource.id) {
@@ -1201,8 +1315,7 @@ pub(crate) async fn run_state_refresh_locked(
             &mut state,
             Some(resource),
             "",
-            &mut updated_count,
-            &mut unchanged_count,
+            &mut summary,
         )?;
     }
 
@@ -1218,8 +1331,7 @@ pub(crate) async fn run_state_refresh_locked(
             &mut state,
             None,
             " (orphan)",
-            &mut updated_count,
-            &mut unchanged_count,
+            &mut summary,
         )?;
     }
 
@@ -1258,84 +1370,50 @@ pub(crate) async fn run_state_refresh_locked(
         crate::commands::apply::save_state_unlocked(backend, &mut state).await?;
     }
 
-    // Summary
-    println!(
-        "State refreshed: {} resource{} updated, {} resource{} unchanged.",
-        updated_count,
-        if updated_count == 1 { "" } else { "s" },
-        unchanged_count,
-        if unchanged_count == 1 { "" } else { "s" },
-    );
+    println!("{}", summary.line());
     println!("  {} State saved (serial: {})", "✓".green(), state.serial);
 
     Ok(())
 }
 
+/// Read `reads` through [`carina_core::refresh::read_tracked`], returning
+/// the states along with the set of ids read.
 async fn refresh_existing_resources_until_cancelled(
     provider: &dyn Provider,
     reads: Vec<(ResourceId, String)>,
     cancel: &CancellationToken,
 ) -> Result<(HashMap<ResourceId, State>, HashSet<ResourceId>), AppError> {
-    let mut current_states = HashMap::new();
-    let mut refreshed = HashSet::new();
-    let mut read_iter = reads.into_iter();
-    let mut in_flight = FuturesUnordered::new();
-    let mut refresh_cancelled = cancel.is_cancelled();
-
-    loop {
-        while !refresh_cancelled && in_flight.len() < 5 {
-            let Some((id, identifier)) = read_iter.next() else {
-                break;
-            };
-            in_flight.push(async move {
-                let fresh_state = provider
-                    .read(
-                        &id,
-                        Some(identifier.as_str()),
-                        carina_core::provider::ReadRequest,
-                    )
-                    .await
-                    .map_err(AppError::Provider)?;
-                Ok((id, fresh_state))
-            });
-        }
-
-        if in_flight.is_empty() {
-            break;
-        }
+    let states = carina_core::refresh::read_tracked(provider, reads, cancel)
+        .await
+        .map_err(|e| match e {
+            RefreshError::Provider(e) => AppError::Provider(e),
+            RefreshError::Cancelled => AppError::Interrupted,
+        })?;
+    let refreshed = states.keys().cloned().collect();
+    Ok((states, refreshed))
+}
 
-        let result: Result<(ResourceId, State), AppError> = if refresh_cancelled {
-            in_flight.next().await.unwrap()
-        } else {
-            tokio::select! {
-                biased;
-                _ = cancel.cancelled() => {
-                    refresh_cancelled = true;
-                    continue;
-                }
-                result = in_flight.next() => {
-                    result.unwrap()
-                }
-            }
-        };
+/// What a refresh changed in state, counted per resource.
+#[derive(Debug, Default)]
+struct RefreshSummary {
+    updated: u32,
+    /// Read back as not found and dropped from state.
+    gone: u32,
+    unchanged: u32,
+}
 
-        if refresh_cancelled {
-            continue;
+impl RefreshSummary {
+    fn line(&self) -> String {
+        fn resources(n: u32) -> String {
+            format!("{n} resource{}", if n == 1 { "" } else { "s" })
         }
-
-        let (id, state) = result?;
-        refreshed.insert(id.clone());
-        current_states.insert(id, state);
-    }
-
-    drop(in_flight);
-    drop(read_iter);
-
-    if refresh_cancelled {
-        return Err(AppError::Interrupted);
+        let mut line = format!("State refreshed: {} updated", resources(self.updated));
+        if self.gone > 0 {
+            line.push_str(&format!(", {} no longer found", resources(self.gone)));
+        }
+        line.push_str(&format!(", {} unchanged.", resources(self.unchanged)));
+        line
     }
-
-    Ok((current_states, refreshed))
 }
 
 /// Compare old state with fresh provider state for a single resource,
@@ -1352,8 +1430,7 @@ fn diff_display_update_resource(
     state: &mut carina_state::StateFile,
     resource: Option<&Resource>,
     label_suffix: &str,
-    updated_count: &mut u32,
-    unchanged_count: &mut u32,
+    summary: &mut RefreshSummary,
 ) -> Result<(), AppError> {
     let existing = state.find_resource(&id.provider, &id.resource_type, id.identity_or_empty());
     let existing_rs = match existing {
@@ -1422,8 +1499,14 @@ fn diff_display_update_resource(
         }
     }
 
+    if !fresh_state.exists {
+        summary.gone += 1;
+    } else if has_changes {
+        summary.updated += 1;
+    } else {
+        summary.unchanged += 1;
+    }
     if has_changes {
-        *updated_count += 1;
         println!(
             "  {} \"{}\"{}:",
             id.display_type().cyan(),
@@ -1434,8 +1517,6 @@ fn diff_display_update_resource(
             println!("{}", change);
         }
         println!();
-    } else {
-        *unchanged_count += 1;
     }
 
     // Update state with refreshed data
@@ -1529,6 +1610,24 @@ mod tests {
 
     // --- format_raw_value tests ---
 
+    #[test]
+    fn refresh_summary_mentions_vanished_resources_only_when_present() {
+        let mut summary = RefreshSummary {
+            updated: 1,
+            gone: 0,
+            unchanged: 3,
+        };
+        assert_eq!(
+            summary.line(),
+            "State refreshed: 1 resource updated, 3 resources unchanged."
+        );
+        summary.gone = 2;
+        assert_eq!(
+            summary.line(),
+            "State refreshed: 1 resource updated, 2 resources no longer found, 3 resources unchanged."
+        );
+    }
+
     #[test]
     fn format_raw_value_string() {
         assert_eq!(format_raw_value(&json!("hello")), "hello");
@@ -1727,6 +1826,24 @@ mod tests {
         insta::assert_snapshot!(output);
     }
 
+    #[tokio::test]
+    async fn cfn_mapping_is_rejected_with_other_formats() {
+        let result = run_state_list(
+            None,
+            None,
+            ListFormat::Json,
+            Some(Path::new("cfn-mapping.json")),
+            &ProviderContext::default(),
+        )
+        .await;
+        match result {
+            Err(AppError::Config(msg)) => {
+                assert_eq!(msg, "--cfn-mapping only applies to --format cfn-import")
+            }
+            other => panic!("expected a config error, got {other:?}"),
+        }
+    }
+
     // --- run_force_unlock tests ---
 
     #[tokio::test]
@@ -0,0 +1,236 @@
+//! `carina upgrade`: apply a release's schema upgrade rules to a project.
+//!
+//! The rules shipped with this binary (or a file passed with `--rules`)
+//! rewrite renamed attributes and changed enum values in the project's
+//! `.crn` files, then move renamed attribute keys in the state so the
+//! next plan does not see a spurious diff.
+
+use std::fs;
+use std::path::Path;
+
+use colored::Colorize;
+
+use carina_core::config_loader::{
+    find_crn_files_in_dir, get_base_dir, load_configuration_with_config,
+};
+use carina_core::upgrade::UpgradeRules;
+use carina_state::{LockInfo, StateBackend, StateFile};
+
+use super::state::map_lock_error;
+use super::{BackendDriftStatus, DriftCommand, inspect_backend_drift, verify_for_mutation};
+use crate::commands::fmt::print_diff;
+use crate::error::AppError;
+use carina_core::parser::ProviderContext;
+
+/// Upgrade rules for the schema changes in this release.
+const RELEASE_RULES: &str = include_str!("../../upgrade-rules.json");
+
+pub async fn run_upgrade(
+    path: &Path,
+    rules_path: Option<&Path>,
+    dry_run: bool,
+    lock: bool,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    let rules = load_rules(rules_path)?;
+
+    if path.is_file() {
+        return Err(AppError::Config(format!(
+            "expected directory, got file: {}",
+            path.display()
+        )));
+    }
+
+    let files = find_crn_files_in_dir(path)?;
+    let mut rewritten = 0;
+    for file in &files {
+        let content = fs::read_to_string(file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        let upgraded = rules.rewrite_source(&content);
+        if upgraded == content {
+            continue;
+        }
+        rewritten += 1;
+        if dry_run {
+            print_diff(file, &content, &upgraded);
+        } else {
+            fs::write(file, &upgraded)
+                .map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+            println!("{} {}", "Upgraded:".green(), file.display());
+        }
+    }
+
+    let state_renames =
+        upgrade_project_state(path, &rules, dry_run, lock, provider_context).await?;
+
+    let verb = if dry_run { "Would upgrade" } else { "Upgraded" };
+    if rewritten == 0 && state_renames == 0 {
+        println!("{}", "Nothing to upgrade.".green());
+    } else {
+        println!(
+            "{}",
+            format!(
+                "{} {} file(s) and {} state attribute(s).",
+                verb, rewritten, state_renames
+            )
+            .green()
+            .bold()
+        );
+    }
+    Ok(())
+}
+
+fn load_rules(rules_path: Option<&Path>) -> Result<UpgradeRules, AppError> {
+    let text = match rules_path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+        None => RELEASE_RULES.to_string(),
+    };
+    UpgradeRules::from_json(&text).map_err(AppError::Config)
+}
+
+/// Rename state attribute keys for the project at `path`. Returns the
+/// number of renamed attributes. A project that was never initialized has
+/// no state to upgrade.
+async fn upgrade_project_state(
+    path: &Path,
+    rules: &UpgradeRules,
+    dry_run: bool,
+    lock: bool,
+    provider_context: &ProviderContext,
+) -> Result<usize, AppError> {
+    let loaded = load_configuration_with_config(
+        path,
+        provider_context,
+        &carina_core::schema::SchemaRegistry::new(),
+    )?;
+    let parsed = loaded.parsed;
+    let base_dir = get_base_dir(path);
+
+    if matches!(
+        inspect_backend_drift(base_dir, parsed.backend.as_ref())?,
+        BackendDriftStatus::Fresh
+    ) {
+        return Ok(0);
+    }
+    let verified_backend = verify_for_mutation(
+        base_dir,
+        parsed.backend.as_ref(),
+        DriftCommand::UpgradeState,
+    )?;
+    let backend: Box<dyn StateBackend> = verified_backend
+        .resolve()
+        .await
+        .map_err(AppError::Backend)?;
+
+    if dry_run {
+        let Some(mut state) = backend
+            .read_state()
+            .await
+            .map_err(AppError::Backend)?
+            .map(|s| s.into_state())
+        else {
+            return Ok(0);
+        };
+        return Ok(rename_state_attributes(&mut state, rules));
+    }
+
+    let lock_info: Option<LockInfo> = if lock {
+        Some(
+            backend
+                .acquire_lock("upgrade")
+                .await
+                .map_err(map_lock_error)?,
+        )
+    } else {
+        None
+    };
+
+    let op_result = upgrade_state_locked(backend.as_ref(), lock_info.as_ref(), rules).await;
+
+    if let Some(ref li) = lock_info {
+        let release_result = backend.release_lock(li).await.map_err(AppError::Backend);
+        let renamed = op_result?;
+        release_result?;
+        Ok(renamed)
+    } else {
+        op_result
+    }
+}
+
+async fn upgrade_state_locked(
+    backend: &dyn StateBackend,
+    lock: Option<&LockInfo>,
+    rules: &UpgradeRules,
+) -> Result<usize, AppError> {
+    let Some(mut state) =
+        crate::commands::apply::load_state_persist_if_migrated(backend, lock).await?
+    else {
+        return Ok(0);
+    };
+    let renamed = rename_state_attributes(&mut state, rules);
+    if renamed > 0 {
+        if let Some(lk) = lock {
+            crate::commands::apply::save_state_locked(backend, lk, &mut state).await?;
+        } else {
+            crate::commands::apply::save_state_unlocked(backend, &mut state).await?;
+        }
+    }
+    Ok(renamed)
+}
+
+/// Apply the rules' attribute renames to every resource in `state`,
+/// printing one line per rename. Returns the number of renames.
+fn rename_state_attributes(state: &mut StateFile, rules: &UpgradeRules) -> usize {
+    let mut renamed = 0;
+    for resource in &mut state.resources {
+        let resource_type = format!("{}.{}", resource.provider, resource.resource_type);
+        for (old, new) in rules.attribute_renames(&resource_type) {
+            if resource.rename_attribute(old, new) {
+                println!(
+                    "  {} {}: {} → {}",
+                    resource_type.cyan(),
+                    resource.identity,
+                    old,
+                    new
+                );
+                renamed += 1;
+            }
+        }
+    }
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_state::ResourceState;
+
+    #[test]
+    fn release_rules_parse() {
+        load_rules(None).expect("shipped upgrade rules must parse");
+    }
+
+    #[test]
+    fn rename_state_attributes_matches_provider_qualified_type() {
+        let rules = UpgradeRules::from_json(
+            r#"{"rules": [{"version": "0.9.0", "resource_type": "awscc.ec2.Vpc",
+                "rename_attributes": {"cidr": "cidr_block"}}]}"#,
+        )
+        .unwrap();
+        let mut state = StateFile::new();
+        let mut vpc = ResourceState::new("ec2.Vpc", "main", "awscc");
+        vpc.attributes
+            .insert("cidr".to_string(), serde_json::json!("10.0.0.0/16"));
+        state.resources.push(vpc);
+        let mut other = ResourceState::new("ec2.Vpc", "legacy", "aws");
+        other
+            .attributes
+            .insert("cidr".to_string(), serde_json::json!("10.1.0.0/16"));
+        state.resources.push(other);
+
+        assert_eq!(rename_state_attributes(&mut state, &rules), 1);
+        assert!(state.resources[0].attributes.contains_key("cidr_block"));
+        assert!(state.resources[1].attributes.contains_key("cidr"));
+    }
+}
@@ -4,14 +4,12 @@ use std::path::{Path, PathBuf};
 use colored::Colorize;
 use serde::Serialize;
 
-use carina_core::config_loader::{
-    find_crn_files_in_dir, get_base_dir, load_configuration_with_config,
-};
+use carina_core::config_loader::{find_crn_files_in_dir, get_base_dir};
 use carina_core::lint::find_duplicate_attrs;
 use carina_core::parser::{File, ProviderContext, ResourceRef, UpstreamState};
 use carina_core::resource::ResourceId;
 
-use super::validate_and_resolve_errors;
+use super::{load_bound_configuration, validate_and_resolve_errors};
 use crate::error::AppError;
 use crate::wiring::check_unused_bindings;
 
@@ -54,11 +52,7 @@ pub fn validate_with_factories(
     factories: Vec<Box<dyn carina_core::provider::ProviderFactory>>,
 ) -> Vec<String> {
     let provider_context = ProviderContext::default();
-    let loaded = match load_configuration_with_config(
-        path,
-        &provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    ) {
+    let loaded = match load_bound_configuration(path, None, &provider_context) {
         Ok(l) => l,
         Err(e) => return vec![e.to_string()],
     };
@@ -107,11 +101,7 @@ pub fn validated_resource_ids_with_factories(
     factories: Vec<Box<dyn carina_core::provider::ProviderFactory>>,
 ) -> Vec<String> {
     let provider_context = ProviderContext::default();
-    let loaded = match load_configuration_with_config(
-        path,
-        &provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    ) {
+    let loaded = match load_bound_configuration(path, None, &provider_context) {
         Ok(l) => l,
         Err(e) => panic!("fixture failed to load: {e}"),
     };
@@ -247,14 +237,11 @@ fn validated_entries<E>(parsed: &File<E>) -> Vec<ValidatedEntry<'_>> {
 
 pub fn run_validate(
     path: &Path,
+    var_file: Option<&Path>,
     json: bool,
     provider_context: &ProviderContext,
 ) -> Result<(), AppError> {
-    let loaded = load_configuration_with_config(
-        path,
-        provider_context,
-        &carina_core::schema::SchemaRegistry::new(),
-    )?;
+    let loaded = load_bound_configuration(path, var_file, provider_context)?;
     let mut parsed = loaded.parsed;
 
     let base_dir = get_base_dir(path);
@@ -529,6 +516,39 @@ mod tests {
         assert_eq!(rendered, "aws.s3.Bucket.<pending>");
     }
 
+    #[test]
+    fn variables_without_a_value_are_reported_outside_plan() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("main.crn"),
+            "variables {\n  env: String\n}\n",
+        )
+        .unwrap();
+        let err = run_validate(dir.path(), None, true, &ProviderContext::default()).unwrap_err();
+        let AppError::Validation(message) = err else {
+            panic!("expected a validation error, got {err:?}");
+        };
+        assert_eq!(
+            message,
+            "variable 'env' has no default; set it in the var file"
+        );
+
+        assert_eq!(
+            validate_with_factories(dir.path(), Vec::new()),
+            vec!["variable 'env' has no default; set it in the var file"]
+        );
+
+        let var_file = dir.path().join("dev.crnvars");
+        fs::write(&var_file, "env = \"dev\"\n").unwrap();
+        run_validate(
+            dir.path(),
+            Some(&var_file),
+            true,
+            &ProviderContext::default(),
+        )
+        .unwrap();
+    }
+
     fn upstream(binding: &str, source: &str) -> UpstreamState {
         UpstreamState {
             binding: binding.to_string(),
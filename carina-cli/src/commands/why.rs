@@ -0,0 +1,246 @@
+use std::fmt::Write as _;
+use std::path::Path;
+use std::str::FromStr;
+
+use colored::Colorize;
+
+use carina_core::differ::{AttributeExplanation, ChangeSource, explain_attribute};
+use carina_core::parser::ProviderContext;
+use carina_core::resource::Value;
+use carina_core::schema::{DiffSuppressor, SchemaRegistry};
+use carina_core::value::format_value;
+use carina_state::StateFile;
+
+use super::plan::{PlanReport, run_plan};
+use crate::DetailLevel;
+use crate::error::AppError;
+use crate::wiring::{PlanChecks, PlanContext};
+
+/// `<binding>.<attribute>` as given to `carina why`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhyTarget {
+    pub binding: String,
+    pub attribute: String,
+}
+
+impl FromStr for WhyTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once('.') {
+            Some((binding, attribute)) if !binding.is_empty() && !attribute.is_empty() => {
+                Ok(Self {
+                    binding: binding.to_string(),
+                    attribute: attribute.to_string(),
+                })
+            }
+            _ => Err(format!(
+                "expected <binding>.<attribute> (e.g. `bucket.versioning`), got '{s}'"
+            )),
+        }
+    }
+}
+
+/// Run the `carina why` command: plan, then explain one attribute.
+pub async fn run_why(
+    target: &WhyTarget,
+    path: &Path,
+    var_file: Option<&Path>,
+    refresh: bool,
+    provider_context: &ProviderContext,
+) -> Result<(), AppError> {
+    run_plan(
+        path,
+        var_file,
+        None,
+        DetailLevel::Full,
+        false,
+        refresh,
+        false,
+        false,
+        false,
+        false,
+        PlanChecks::default(),
+        provider_context,
+        Some(PlanReport::Why(target)),
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Look the target up in a computed plan and render its explanation.
+pub(crate) fn explain_from_plan(
+    target: &WhyTarget,
+    ctx: &PlanContext,
+    state_file: Option<&StateFile>,
+    schemas: &SchemaRegistry,
+) -> Result<String, AppError> {
+    let resource = ctx
+        .sorted_resources
+        .iter()
+        .find(|r| r.binding.as_deref() == Some(target.binding.as_str()))
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "No resource is bound to '{}' in this configuration",
+                target.binding
+            ))
+        })?;
+    let current = ctx
+        .current_states
+        .get(&resource.id)
+        .cloned()
+        .unwrap_or_else(|| carina_core::resource::State::not_found(resource.id.clone()));
+    let saved = state_file.map(|sf| sf.build_saved_attrs());
+    let explanation = explain_attribute(
+        resource,
+        &current,
+        saved.as_ref().and_then(|s| s.get(&resource.id)),
+        ctx.prev_explicit.get(&resource.id),
+        schemas.get_for(resource),
+        &target.attribute,
+    );
+    Ok(render_explanation(
+        &format!("{}.{}", target.binding, target.attribute),
+        &resource.id.human().to_string(),
+        &explanation,
+    ))
+}
+
+fn show(value: Option<&Value>) -> String {
+    value.map_or_else(|| "(not set)".to_string(), format_value)
+}
+
+fn suppressor_label(suppressor: &DiffSuppressor) -> String {
+    match suppressor {
+        DiffSuppressor::JsonDocument => "JSON documents compare by parsed value".to_string(),
+        DiffSuppressor::IgnoreOrder => "list order is ignored".to_string(),
+        DiffSuppressor::IgnoreCase => "case is ignored".to_string(),
+        DiffSuppressor::IgnoreFields { fields } => {
+            format!("fields not compared: {}", fields.join(", "))
+        }
+        DiffSuppressor::Ignore => "changes are ignored once set".to_string(),
+        DiffSuppressor::Unknown => "unknown suppressor (suppresses nothing)".to_string(),
+    }
+}
+
+pub(crate) fn render_explanation(
+    target: &str,
+    resource: &str,
+    why: &AttributeExplanation,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{} ({})", target.bold(), resource);
+    let _ = writeln!(out, "  desired:      {}", show(why.desired.as_ref()));
+    let _ = writeln!(out, "  observed:     {}", show(why.observed.as_ref()));
+    let _ = writeln!(out, "  last applied: {}", show(why.last_applied.as_ref()));
+    let _ = writeln!(out);
+
+    if why.changed {
+        let side = match why.source {
+            Some(ChangeSource::Desired) => "the configuration changed since the last apply",
+            Some(ChangeSource::Observed) => {
+                "the resource drifted; the configuration still matches the last apply"
+            }
+            Some(ChangeSource::Both) => "both the configuration and the resource changed",
+            _ => "no last-applied value is recorded, so the changed side is unknown",
+        };
+        let _ = writeln!(out, "{} {}", "Changed:".yellow().bold(), side);
+    } else {
+        let _ = writeln!(out, "{} no change planned", "Unchanged:".green().bold());
+    }
+
+    let _ = writeln!(out, "Normalization:");
+    let mut notes = Vec::new();
+    if why.server_default {
+        notes.push(
+            "observed value is a server default you never wrote; it is not compared".to_string(),
+        );
+    }
+    if why.write_only {
+        notes.push("write-only; the provider never returns it".to_string());
+    }
+    if why.read_only {
+        notes.push("read-only; set by the provider".to_string());
+    }
+    for rule in &why.suppressors {
+        notes.push(format!(
+            "diff suppressor at `{}`: {}",
+            rule.path,
+            suppressor_label(&rule.suppressor)
+        ));
+    }
+    if why.suppressed {
+        notes.push("the raw values differ, but the suppressors treat them as equal".to_string());
+    }
+    if notes.is_empty() {
+        notes.push("none".to_string());
+    }
+    for note in notes {
+        let _ = writeln!(out, "  - {note}");
+    }
+    let _ = writeln!(
+        out,
+        "  - carina has no ignore_changes directive; only schema suppressors apply"
+    );
+
+    if why.requires_replacement() {
+        let _ = writeln!(
+            out,
+            "{} create-only attribute; the resource will be replaced",
+            "Replacement:".red().bold()
+        );
+    } else if why.create_only {
+        let _ = writeln!(
+            out,
+            "Replacement: create-only attribute; changing it would replace the resource"
+        );
+    } else {
+        let _ = writeln!(out, "Replacement: no");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::resource::ConcreteValue;
+    use carina_core::schema::DiffSuppressorRule;
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    #[test]
+    fn target_splits_on_the_last_dot() {
+        let target: WhyTarget = "web.sg.group_name".parse().unwrap();
+        assert_eq!(target.binding, "web.sg");
+        assert_eq!(target.attribute, "group_name");
+        assert!("bucket".parse::<WhyTarget>().is_err());
+        assert!("bucket.".parse::<WhyTarget>().is_err());
+    }
+
+    #[test]
+    fn render_reports_side_suppressors_and_replacement() {
+        let why = AttributeExplanation {
+            attribute: "policy".to_string(),
+            desired: Some(string("new")),
+            observed: Some(string("old")),
+            last_applied: Some(string("old")),
+            changed: true,
+            source: Some(ChangeSource::Desired),
+            server_default: false,
+            suppressors: vec![DiffSuppressorRule {
+                path: "policy".to_string(),
+                suppressor: DiffSuppressor::JsonDocument,
+            }],
+            suppressed: false,
+            create_only: true,
+            read_only: false,
+            write_only: false,
+        };
+        let text = render_explanation("role.policy", "iam.Role.role", &why);
+        assert!(text.contains("the configuration changed since the last apply"));
+        assert!(text.contains("diff suppressor at `policy`: JSON documents compare"));
+        assert!(text.contains("create-only attribute; the resource will be replaced"));
+    }
+}
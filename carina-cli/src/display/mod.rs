@@ -320,7 +320,7 @@ pub fn print_plan(
     prev_explicit: Option<&HashMap<ResourceId, carina_core::explicit::ExplicitFields>>,
     expansion_trace: Option<&carina_core::resource::ExpansionTrace>,
 ) {
-    print!(
+    human_print!(
         "{}",
         format_plan(
             plan,
@@ -2058,6 +2058,7 @@ fn render_list_of_maps_diff(
                 f,
                 ListOfMapsDiffField::NestedMapChanged { .. }
                     | ListOfMapsDiffField::StringListChanged { .. }
+                    | ListOfMapsDiffField::NestedListOfMapsChanged { .. }
             )
         });
         if has_block_field {
@@ -2097,6 +2098,18 @@ fn render_list_of_maps_diff(
                             &nested_prefix,
                         );
                     }
+                    ListOfMapsDiffField::NestedListOfMapsChanged { key, block } => {
+                        writeln!(out, "{}      {}:", attr_prefix, key).unwrap();
+                        let nested_prefix = format!("{}      ", attr_prefix);
+                        render_list_of_maps_diff(
+                            out,
+                            block.unchanged(),
+                            block.modified(),
+                            block.added(),
+                            block.removed(),
+                            &nested_prefix,
+                        );
+                    }
                 }
             }
             // #2881: surface the number of unchanged sibling fields
@@ -2213,11 +2226,12 @@ fn render_modified_fields(fields: &[ListOfMapsDiffField]) -> String {
             ListOfMapsDiffField::NestedMapChanged { key, .. } => {
                 result_parts.push(format!("{}: (nested changes)", key));
             }
-            ListOfMapsDiffField::StringListChanged { .. } => {
+            ListOfMapsDiffField::StringListChanged { .. }
+            | ListOfMapsDiffField::NestedListOfMapsChanged { .. } => {
                 // `has_block_field` in `render_list_of_maps_diff` keeps
-                // `StringListChanged` out of the inline summary path.
+                // multi-line fields out of the inline summary path.
                 unreachable!(
-                    "StringListChanged should be handled by the block layout, not the inline summary"
+                    "multi-line list fields should be handled by the block layout, not the inline summary"
                 );
             }
         }
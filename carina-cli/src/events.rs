@@ -0,0 +1,159 @@
+//! Machine-readable apply event stream (`carina apply --json`).
+//!
+//! While the stream is enabled, stdout carries only NDJSON: one
+//! [`ApplyEvent`] per line, each with a `timestamp` and an `event` tag.
+//! Human-oriented output printed through `human_println!` /
+//! `human_print!` moves to stderr so CI wrappers can parse stdout
+//! without scraping spinner output.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+use carina_core::plan_changes::ResourceChange;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Route stdout to the event stream for the rest of the process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// `println!` for human-oriented text: stdout normally, stderr while the
+/// event stream owns stdout.
+macro_rules! human_println {
+    ($($arg:tt)*) => {
+        if $crate::events::enabled() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// `print!` counterpart of `human_println!`.
+macro_rules! human_print {
+    ($($arg:tt)*) => {
+        if $crate::events::enabled() {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FinishStatus {
+    Succeeded,
+    /// Applied, but the read-back missed some attributes.
+    Partial,
+    Failed,
+    /// Not attempted, typically because a dependency failed.
+    Skipped,
+}
+
+/// One line of the event stream.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ApplyEvent {
+    /// The plan about to be applied, in the saved-plan `changes` format.
+    PlanStarted {
+        resource_changes: Vec<ResourceChange>,
+    },
+    ResourceApplyStarted {
+        address: String,
+        action: &'static str,
+    },
+    /// Activity that is not a resource starting or finishing: wait
+    /// heartbeats, cascade updates, renames, post-apply refreshes.
+    Progress {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        address: Option<String>,
+        message: String,
+    },
+    ResourceApplyFinished {
+        address: String,
+        action: &'static str,
+        status: FinishStatus,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        duration_secs: Option<f64>,
+        completed: usize,
+        total: usize,
+        /// Failure or skip reason, or what a partial read-back missed.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        address: Option<String>,
+        message: String,
+    },
+    StateSaved {
+        serial: u64,
+    },
+}
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    timestamp: String,
+    #[serde(flatten)]
+    event: &'a ApplyEvent,
+}
+
+fn event_line(event: &ApplyEvent) -> String {
+    serde_json::to_string(&EventLine {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        event,
+    })
+    .expect("apply events serialize to JSON")
+}
+
+/// Write `event` to stdout if the stream is enabled; a no-op otherwise.
+pub fn emit(event: &ApplyEvent) {
+    if !enabled() {
+        return;
+    }
+    let mut out = std::io::stdout().lock();
+    let _ = writeln!(out, "{}", event_line(event));
+    let _ = out.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_tagged_single_line_json() {
+        let line = event_line(&ApplyEvent::ResourceApplyFinished {
+            address: "awscc.ec2.Vpc main".to_string(),
+            action: "create",
+            status: FinishStatus::Succeeded,
+            duration_secs: Some(1.5),
+            completed: 1,
+            total: 3,
+            message: None,
+        });
+        assert!(!line.contains('\n'));
+        let mut json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert!(json["timestamp"].is_string());
+        json.as_object_mut().unwrap().remove("timestamp");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "event": "resource_apply_finished",
+                "address": "awscc.ec2.Vpc main",
+                "action": "create",
+                "status": "succeeded",
+                "duration_secs": 1.5,
+                "completed": 1,
+                "total": 3,
+            })
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::{self, Write};
+
+use carina_state::StateFile;
+
+/// Print the failures and skips the previous apply recorded in state,
+/// ahead of the plan that is about to retry them.
+pub(crate) fn emit_last_operation_notes(state_file: Option<&StateFile>) {
+    let mut stderr = io::stderr().lock();
+    let _ = write_last_operation_notes(state_file, &mut stderr);
+}
+
+pub(crate) fn write_last_operation_notes<W: Write>(
+    state_file: Option<&StateFile>,
+    writer: &mut W,
+) -> io::Result<()> {
+    let Some(state_file) = state_file else {
+        return Ok(());
+    };
+    if state_file.last_operations.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "note: the previous apply did not complete for {} resource(s):",
+        state_file.last_operations.len()
+    )?;
+    for (address, last) in &state_file.last_operations {
+        writeln!(writer, "  {address}: {last}")?;
+    }
+    writeln!(writer, "  Run `carina state show` for details.")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::executor::{OperationOutcome, OperationStatus};
+    use carina_core::resource::ResourceId;
+    use std::collections::HashMap;
+
+    #[test]
+    fn notes_list_each_recorded_failure() {
+        let mut state = StateFile::new();
+        let mut out = Vec::new();
+        write_last_operation_notes(Some(&state), &mut out).unwrap();
+        assert!(out.is_empty());
+
+        state.record_operation_outcomes(
+            &HashMap::from([(
+                ResourceId::with_provider_identity("aws", "s3.Bucket", "logs", None),
+                OperationOutcome {
+                    status: OperationStatus::Skipped,
+                    operation: "create",
+                    error_kind: None,
+                    message: Some("dependency 'vpc' failed".to_string()),
+                    request_id: None,
+                    duration: None,
+                    metrics: None,
+                },
+            )]),
+            chrono::DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        );
+        write_last_operation_notes(Some(&state), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "note: the previous apply did not complete for 1 resource(s):\n  \
+             aws.s3.Bucket.logs: create skipped at 2026-01-02T03:04:05Z: dependency 'vpc' failed\n  \
+             Run `carina state show` for details.\n"
+        );
+    }
+}
@@ -1,3 +1,7 @@
+// First, so its output macros are in scope for the modules below.
+#[macro_use]
+pub mod events;
+
 pub mod commands;
 pub mod cursor;
 pub mod display;
@@ -7,6 +11,7 @@ pub mod kms;
 pub mod signal;
 pub mod wiring;
 
+pub(crate) mod last_operations;
 pub(crate) mod legacy_name_overrides;
 
 #[cfg(test)]
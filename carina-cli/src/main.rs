@@ -12,12 +12,18 @@ use carina_cli::commands::docs;
 use carina_cli::commands::fmt::run_fmt;
 use carina_cli::commands::lint::run_lint;
 use carina_cli::commands::module::{ModuleCommands, run_module_command};
+use carina_cli::commands::org::{OrgCommands, run_org_command};
+use carina_cli::commands::orphans::{OrphansFormat, PageRequest};
 use carina_cli::commands::plan::run_plan;
+use carina_cli::commands::providers::{ProvidersCommands, run_providers_command};
 use carina_cli::commands::skills;
 use carina_cli::commands::state::{StateCommands, run_force_unlock, run_state_command};
+use carina_cli::commands::upgrade::run_upgrade;
 use carina_cli::commands::validate::run_validate;
 use carina_cli::error;
+use carina_cli::wiring::PlanChecks;
 use carina_cli::{DEFAULT_PARALLELISM, DetailLevel};
+use carina_state::report::{ReportFilter, TagFilter};
 
 /// Version string assembled at build time by `build.rs`. Formatted as
 /// `<pkg> (<git-hash>[-dirty] <build-date>)`, or just `<pkg>` when the
@@ -42,6 +48,11 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
         /// Output results as JSON
         #[arg(long)]
         json: bool,
@@ -52,6 +63,11 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
         /// Save plan to a file for later apply
         #[arg(long = "out")]
         out: Option<PathBuf>,
@@ -83,6 +99,18 @@ enum Commands {
         /// With --check-iam, fail (exit 1) instead of warning when permissions are missing. Requires --check-iam.
         #[arg(long, requires = "check_iam")]
         strict_iam: bool,
+
+        /// Print the estimated monthly cost change after the plan (default: true)
+        #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+        cost: bool,
+
+        /// Pass each resource to its provider's dry run, which checks it against the cloud's resource schema and may call the cloud API. WASM providers have no dry run; their resources are skipped with a warning
+        #[arg(long)]
+        simulate: bool,
+
+        /// Fail when a provider returns properties its schema does not declare, instead of ignoring them
+        #[arg(long)]
+        strict_schema: bool,
     },
     /// Apply changes to reach the desired state
     Apply {
@@ -90,6 +118,11 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
         /// Skip confirmation prompt (auto-approve)
         #[arg(long)]
         auto_approve: bool,
@@ -109,6 +142,11 @@ enum Commands {
         /// Run `carina plan` first to inspect, then re-run with this flag.
         #[arg(long)]
         accept_legacy_name_overrides: bool,
+
+        /// Write a machine-readable NDJSON event stream to stdout; human
+        /// output moves to stderr. Requires --auto-approve.
+        #[arg(long, requires = "auto_approve")]
+        json: bool,
     },
     /// Destroy all resources defined in the configuration file
     Destroy {
@@ -116,6 +154,11 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
         /// Skip confirmation prompt (auto-approve)
         #[arg(long)]
         auto_approve: bool,
@@ -132,10 +175,115 @@ enum Commands {
         #[arg(long)]
         force: bool,
 
+        /// Let providers remove dependencies that block a delete when they
+        /// know them to be safe to remove (e.g. default security group rules).
+        /// WASM providers do not report blockers, so it has no effect on them
+        #[arg(long)]
+        force_dependencies: bool,
+
         /// Maximum concurrent provider operations
         #[arg(long, default_value_t = DEFAULT_PARALLELISM)]
         parallelism: NonZeroUsize,
     },
+    /// Report resources tagged for this project but missing from state,
+    /// and state entries whose resource no longer exists
+    ///
+    /// Only process providers can list their resources. WASM providers,
+    /// including aws and awscc, are reported as not checked.
+    Orphans {
+        /// Path to directory containing .crn files
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Output as CSV, one `status,resource,identifier` row per entry
+        #[arg(long, conflicts_with = "json")]
+        csv: bool,
+
+        /// Only report resources whose type matches this glob, e.g. `ec2.*`
+        /// or `aws.s3.*`. Repeatable; a resource matching any pattern is kept.
+        #[arg(long = "type", value_name = "PATTERN")]
+        types: Vec<String>,
+
+        /// Only report resources carrying this tag (`KEY` or `KEY=VALUE`).
+        /// Repeatable; a resource must carry every tag given.
+        #[arg(long = "tag", value_name = "KEY[=VALUE]")]
+        tags: Vec<TagFilter>,
+
+        /// Only report resources with no tags
+        #[arg(long)]
+        untagged_only: bool,
+
+        /// Split the untracked resources into pages of this many
+        #[arg(long, value_name = "N")]
+        page_size: Option<NonZeroUsize>,
+
+        /// 1-based page of untracked resources to print (requires --page-size)
+        #[arg(long, default_value = "1", requires = "page_size")]
+        page: NonZeroUsize,
+    },
+    /// Explain why an attribute shows up (or not) in the plan
+    Why {
+        /// Attribute to explain, as <binding>.<attribute>
+        #[arg()]
+        target: commands::why::WhyTarget,
+
+        /// Path to directory containing .crn files
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
+        /// Refresh state from provider before planning (default: true)
+        #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+        refresh: bool,
+    },
+    /// Render the resource dependency graph, highlighting planned changes
+    Graph {
+        /// Path to directory containing .crn files
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "dot")]
+        format: commands::graph::GraphFormat,
+
+        /// Refresh state from provider before planning (default: true)
+        #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+        refresh: bool,
+    },
+    /// Resync state with the cloud without planning changes
+    /// (same as `carina state refresh`)
+    Refresh {
+        /// Path to directory containing .crn files
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Variable file (`.crnvars`) giving values to the configuration's
+        /// `variables { }` block. Variables not set take their defaults.
+        #[arg(long = "var-file")]
+        var_file: Option<PathBuf>,
+
+        /// Enable/disable state locking (default: true)
+        #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+        lock: bool,
+    },
     /// Show export values from the state
     Export {
         /// Name of a specific export to display
@@ -210,12 +358,38 @@ enum Commands {
         #[arg(long, requires = "migrate_state")]
         force: bool,
     },
+    /// Provider schema maintenance commands
+    Providers {
+        #[command(subcommand)]
+        command: ProvidersCommands,
+    },
+    /// Search resources and upstream_state references across many projects
+    Org {
+        #[command(subcommand)]
+        command: OrgCommands,
+    },
     /// Lint .crn files for style issues
     Lint {
         /// Path to directory containing .crn files
         #[arg(default_value = ".")]
         path: PathBuf,
     },
+    /// Rewrite .crn files and state for schema changes in this release
+    Upgrade {
+        /// Path to directory containing .crn files
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Upgrade rules file to apply instead of the rules shipped with
+        /// this release
+        #[arg(long)]
+        rules: Option<PathBuf>,
+        /// Show the changes without writing files or state
+        #[arg(long)]
+        dry_run: bool,
+        /// Enable/disable state locking (default: true)
+        #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
+        lock: bool,
+    },
     /// Generate shell completions
     Completions {
         /// Shell to generate completions for
@@ -329,6 +503,7 @@ async fn main() {
     // Handle Plan separately since it returns Result<bool, String>
     if let Commands::Plan {
         path,
+        var_file,
         out,
         detailed_exitcode,
         detail,
@@ -337,10 +512,14 @@ async fn main() {
         json,
         check_iam,
         strict_iam,
+        cost,
+        simulate,
+        strict_schema,
     } = cli.command
     {
         match run_plan(
             &path,
+            var_file.as_deref(),
             out.as_deref(),
             detail,
             tui,
@@ -348,7 +527,13 @@ async fn main() {
             json,
             check_iam,
             strict_iam,
+            cost,
+            PlanChecks {
+                simulate: simulate.then_some(DEFAULT_PARALLELISM),
+                strict_schema,
+            },
             &provider_context,
+            None,
         )
         .await
         {
@@ -368,16 +553,31 @@ async fn main() {
     }
 
     let result = match cli.command {
-        Commands::Validate { path, json } => run_validate(&path, json, &provider_context),
+        Commands::Validate {
+            path,
+            var_file,
+            json,
+        } => run_validate(&path, var_file.as_deref(), json, &provider_context),
         Commands::Plan { .. } => unreachable!(),
         Commands::Apply {
             path,
+            var_file,
             auto_approve,
             lock,
             parallelism,
             accept_legacy_name_overrides,
+            json,
         } => {
-            if path.extension().is_some_and(|ext| ext == "json") {
+            if json {
+                carina_cli::events::enable();
+            }
+            if path.extension().is_some_and(|ext| ext == "json") && var_file.is_some() {
+                // A saved plan already has its variables substituted.
+                Err(error::AppError::Config(
+                    "--var-file cannot be used with a saved plan; pass it to `carina plan --out` instead"
+                        .to_string(),
+                ))
+            } else if path.extension().is_some_and(|ext| ext == "json") {
                 run_apply_from_plan(
                     &path,
                     auto_approve,
@@ -391,6 +591,7 @@ async fn main() {
             } else {
                 run_apply(
                     &path,
+                    var_file.as_deref(),
                     auto_approve,
                     lock,
                     parallelism,
@@ -403,24 +604,106 @@ async fn main() {
         }
         Commands::Destroy {
             path,
+            var_file,
             auto_approve,
             lock,
             refresh,
             force,
+            force_dependencies,
             parallelism,
         } => {
             run_destroy(
                 &path,
+                var_file.as_deref(),
                 auto_approve,
                 lock,
                 refresh,
                 force,
+                force_dependencies,
                 parallelism,
                 &provider_context,
                 cancel_token.clone(),
             )
             .await
         }
+        Commands::Why {
+            target,
+            path,
+            var_file,
+            refresh,
+        } => {
+            commands::why::run_why(
+                &target,
+                &path,
+                var_file.as_deref(),
+                refresh,
+                &provider_context,
+            )
+            .await
+        }
+        Commands::Graph {
+            path,
+            var_file,
+            format,
+            refresh,
+        } => {
+            commands::graph::run_graph(
+                &path,
+                var_file.as_deref(),
+                format,
+                refresh,
+                &provider_context,
+            )
+            .await
+        }
+        Commands::Refresh {
+            path,
+            var_file,
+            lock,
+        } => {
+            commands::state::run_state_refresh(
+                &path,
+                var_file.as_deref(),
+                lock,
+                &provider_context,
+                cancel_token.clone(),
+            )
+            .await
+        }
+        Commands::Orphans {
+            path,
+            var_file,
+            json,
+            csv,
+            types,
+            tags,
+            untagged_only,
+            page_size,
+            page,
+        } => {
+            let filter = ReportFilter {
+                types,
+                tags,
+                untagged_only,
+            };
+            let page = page_size.map(|size| PageRequest { size, page });
+            let format = if json {
+                OrphansFormat::Json
+            } else if csv {
+                OrphansFormat::Csv
+            } else {
+                OrphansFormat::Text
+            };
+            commands::orphans::run_orphans(
+                &path,
+                var_file.as_deref(),
+                &filter,
+                page,
+                format,
+                &provider_context,
+            )
+            .await
+        }
         Commands::Export { name, json, raw } => {
             let format = if raw {
                 commands::export::OutputFormat::Raw
@@ -463,7 +746,15 @@ async fn main() {
             }
             Ok(())
         }
+        Commands::Providers { command } => run_providers_command(command, &provider_context).await,
+        Commands::Org { command } => run_org_command(command, &provider_context).await,
         Commands::Lint { path } => run_lint(&path, &provider_context),
+        Commands::Upgrade {
+            path,
+            rules,
+            dry_run,
+            lock,
+        } => run_upgrade(&path, rules.as_deref(), dry_run, lock, &provider_context).await,
         Commands::Completions { shell } => {
             generate(shell, &mut Cli::command(), "carina", &mut std::io::stdout());
             Ok(())
@@ -556,6 +847,10 @@ fn handle_app_error(e: error::AppError) -> ! {
     // would never restore the cursor on the error path — restore it here
     // first (#3158). Claim-once, so this is harmless if a guard also ran.
     carina_cli::cursor::restore_cursor();
+    carina_cli::events::emit(&carina_cli::events::ApplyEvent::Error {
+        address: None,
+        message: e.to_string(),
+    });
     let rendering = render_app_error(&e);
     if !rendering.stderr.is_empty() {
         eprint!("{}", rendering.stderr);
@@ -730,6 +1025,44 @@ mod error_format_tests {
         }
     }
 
+    #[test]
+    fn plan_cost_summary_can_be_turned_off() {
+        let cli = Cli::try_parse_from(["carina", "plan", "--cost", "false"]).unwrap();
+        assert!(matches!(cli.command, Commands::Plan { cost: false, .. }));
+        let cli = Cli::try_parse_from(["carina", "plan"]).unwrap();
+        assert!(matches!(cli.command, Commands::Plan { cost: true, .. }));
+    }
+
+    #[test]
+    fn plan_simulate_flag_parses() {
+        let cli = Cli::try_parse_from(["carina", "plan", "--simulate"]).unwrap();
+        assert!(matches!(cli.command, Commands::Plan { simulate: true, .. }));
+    }
+
+    #[test]
+    fn plan_strict_schema_flag_parses() {
+        let cli = Cli::try_parse_from(["carina", "plan", "--strict-schema"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Plan {
+                strict_schema: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn orphans_rejects_page_zero() {
+        assert!(
+            Cli::try_parse_from(["carina", "orphans", "--page-size", "10", "--page", "0"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["carina", "orphans", "--page-size", "0"]).is_err());
+        let cli =
+            Cli::try_parse_from(["carina", "orphans", "--type", "ec2.*", "--page-size", "10"])
+                .unwrap();
+        assert!(matches!(cli.command, Commands::Orphans { .. }));
+    }
+
     #[test]
     fn plan_strict_iam_requires_check_iam() {
         assert!(Cli::try_parse_from(["carina", "plan", "--strict-iam"]).is_err());
@@ -739,4 +1072,9 @@ mod error_format_tests {
     fn plan_check_iam_with_strict_iam_parses() {
         assert!(Cli::try_parse_from(["carina", "plan", "--check-iam", "--strict-iam"]).is_ok());
     }
+
+    #[test]
+    fn providers_report_unknowns_parses() {
+        assert!(Cli::try_parse_from(["carina", "providers", "report-unknowns", "--json"]).is_ok());
+    }
 }
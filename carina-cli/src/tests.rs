@@ -340,6 +340,7 @@ fn plan_file_serde_round_trip() {
                 )),
             )]),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             source: None,
             version: None,
             revision: None,
@@ -369,6 +370,8 @@ fn plan_file_serde_round_trip() {
         upstream_snapshot: HashMap::new(),
         upstream_sources: Vec::new(),
         wait_bindings: vec![],
+        changes: None,
+        trace_id: None,
     };
 
     let json = serde_json::to_string_pretty(&plan_file).unwrap();
@@ -632,6 +635,7 @@ fn make_awscc_provider(region_dsl: &str) -> ProviderConfig {
         name: "awscc".to_string(),
         attributes: attrs,
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -751,6 +755,7 @@ fn test_find_state_bucket_resource_matching_type() {
     let parsed = ParsedFile {
         providers: vec![],
         backend: None,
+        naming: None,
         resources: vec![
             Resource::with_provider("aws", "s3.Bucket", "my-bucket", None).with_attribute(
                 "bucket",
@@ -764,6 +769,7 @@ fn test_find_state_bucket_resource_matching_type() {
         module_calls: vec![],
         arguments: vec![],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         state_blocks: vec![],
         user_functions: HashMap::new(),
@@ -1488,6 +1494,7 @@ async fn lock_released_on_write_state_failure() {
         current_states: HashMap::new(),
         bindings: carina_core::binding_index::ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     };
 
     // This mirrors the pattern used in run_apply_locked / run_apply_from_plan_locked:
@@ -1630,6 +1637,7 @@ async fn finalize_apply_uses_write_state_locked() {
         current_states: HashMap::new(),
         bindings: carina_core::binding_index::ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     };
 
     let op_result = finalize_apply(FinalizeApplyInput {
@@ -1825,6 +1833,7 @@ async fn finalize_apply_without_lock_uses_write_state() {
         current_states: HashMap::new(),
         bindings: carina_core::binding_index::ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     };
 
     let op_result = finalize_apply(FinalizeApplyInput {
@@ -2538,6 +2547,8 @@ fn plan_file_serialization_redacts_secrets() {
         upstream_snapshot: HashMap::new(),
         upstream_sources: Vec::new(),
         wait_bindings: vec![],
+        changes: None,
+        trace_id: None,
     };
 
     let json = serde_json::to_string_pretty(&plan_file).unwrap();
@@ -2736,6 +2747,7 @@ async fn finalize_apply_clears_state_exports_when_params_empty() {
         current_states: HashMap::new(),
         bindings: carina_core::binding_index::ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     };
 
     let op_result = finalize_apply(FinalizeApplyInput {
@@ -2798,6 +2810,7 @@ async fn finalize_apply_preserves_state_exports_when_params_none() {
         current_states: HashMap::new(),
         bindings: carina_core::binding_index::ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     };
 
     let op_result = finalize_apply(FinalizeApplyInput {
@@ -2866,6 +2879,7 @@ async fn finalize_apply_persists_successful_state_when_one_export_is_unresolved(
         current_states: HashMap::from([(a_id, a_state)]),
         bindings: carina_core::binding_index::ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     };
 
     let export_params = vec![
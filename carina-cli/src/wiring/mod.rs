@@ -2,6 +2,7 @@ use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::io::IsTerminal;
+use std::num::NonZeroUsize;
 use std::path::Path;
 
 #[cfg(test)]
@@ -13,13 +14,15 @@ use colored::Colorize;
 use futures::stream::{self, StreamExt};
 
 use carina_core::binding_index::{PreApplyInputs, ResolvedBindings, WaitAliasSpec};
+use carina_core::cost::AwsListPrices;
 use carina_core::deps::sort_resources_by_dependencies;
 use carina_core::differ::binding_matches_deferred_template;
 use carina_core::differ::create_plan_with_cascades;
 use carina_core::effect::{DeferredReplaceDelete, DeferredReplacePayload, Effect, NonEmptyDeletes};
 use carina_core::executor::normalized::{
-    is_value_fully_concrete_for_expansion, restore_stripped_attributes,
-    run_desired_normalization_stages, states_contain_unknown, strip_provider_boundary_attributes,
+    is_value_fully_concrete_for_expansion, materialize_schema_defaults,
+    restore_stripped_attributes, run_desired_normalization_stages, states_contain_unknown,
+    strip_provider_boundary_attributes,
 };
 use carina_core::executor::{UnresolvedDataSourceInput, unresolved_data_source_inputs};
 use carina_core::identifier::{
@@ -29,15 +32,17 @@ use carina_core::module_resolver;
 use carina_core::override_aware::OverrideAwareResources;
 use carina_core::parser::{ProviderConfig, StateBlock, StateBlockAddress, WarningKind};
 use carina_core::plan::Plan;
+use carina_core::policy_document::compile_policy_documents;
 use carina_core::provider::{
-    self as provider_mod, Provider, ProviderError, ProviderFactory, ProviderNormalizer,
-    ProviderRouter,
+    self as provider_mod, BlockedResource, Provider, ProviderError, ProviderFactory,
+    ProviderNormalizer, ProviderRouter, ResourceDiagnostic,
 };
 use carina_core::resource::{
     ConcreteValue, DataSource, DeferredValue, Resource, ResourceId, State, Value,
 };
 use carina_core::schema::{
-    AttributeSchema, AttributeType, ResourceSchema, SchemaRegistry, resolve_block_names,
+    AttributeSchema, AttributeType, ResourceSchema, SchemaRegistry, UnknownPropertyReport,
+    resolve_block_names,
 };
 use carina_core::validation;
 use carina_provider_mock::MockProvider;
@@ -82,6 +87,9 @@ pub struct PlanContext {
     /// that produced them (#3306, #3307). Forwarded to the display
     /// layer so the rendered tree folds leaves under composition rows.
     pub expansion_trace: carina_core::resource::ExpansionTrace,
+    /// Resources left out of the plan because the provider instance they
+    /// route to (directly or through a dependency) failed to initialize.
+    pub blocked: Vec<BlockedResource>,
 }
 
 /// State-block targets resolved while constructing [`StateBlockClaims`].
@@ -202,30 +210,13 @@ pub fn build_factories_from_providers(
             continue;
         };
 
-        if !carina_provider_resolver::is_wasm_provider(&binary_path) {
-            let reason = format!(
-                "Provider '{}': native binaries are no longer supported. Use a .wasm component instead.",
-                config.name
-            );
-            eprintln!("{}", reason.red());
-            load_errors.insert(config.name.clone(), reason);
-            continue;
-        }
-
-        let factory_result: Result<Box<dyn ProviderFactory>, String> = {
-            tokio::task::block_in_place(|| {
-                tokio::runtime::Handle::current().block_on(
-                    carina_plugin_host::WasmProviderFactory::new(binary_path.clone()),
-                )
-            })
-            .and_then(|f| {
-                if let Some(constraint) = &config.version {
-                    f.verify_version(&constraint.raw)?;
-                }
-                Ok(Box::new(f) as Box<dyn ProviderFactory>)
-            })
-            .map_err(|e| format!("Failed to load WASM provider: {e}"))
-        };
+        let factory_result = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(load_verified_factory(
+                config,
+                base_dir,
+                &binary_path,
+            ))
+        });
 
         match factory_result {
             Ok(factory) => {
@@ -242,6 +233,72 @@ pub fn build_factories_from_providers(
     (factories, load_errors)
 }
 
+/// Load the provider binary at `binary_path` — as a WASM component, or
+/// out of process over JSON-RPC for a native binary — and check it
+/// against `config`'s version constraint and the schema digest recorded
+/// in `carina-providers.lock`. Every loader goes through here so the two
+/// kinds of provider can't end up with different checks.
+async fn load_verified_factory(
+    config: &ProviderConfig,
+    base_dir: &Path,
+    binary_path: &Path,
+) -> Result<Box<dyn ProviderFactory>, String> {
+    if carina_provider_resolver::is_wasm_provider(binary_path) {
+        carina_plugin_host::WasmProviderFactory::new(binary_path.to_path_buf())
+            .await
+            .and_then(|f| {
+                verify_loaded_provider(
+                    config,
+                    base_dir,
+                    binary_path,
+                    f.version(),
+                    f.schemas_json(),
+                    |constraint| f.verify_version(constraint),
+                )?;
+                Ok(Box::new(f) as Box<dyn ProviderFactory>)
+            })
+            .map_err(|e| format!("Failed to load WASM provider: {e}"))
+    } else {
+        carina_plugin_host::ProcessProviderFactory::new(binary_path.to_path_buf())
+            .await
+            .and_then(|f| {
+                verify_loaded_provider(
+                    config,
+                    base_dir,
+                    binary_path,
+                    f.version(),
+                    f.schemas_json(),
+                    |constraint| f.verify_version(constraint),
+                )?;
+                Ok(Box::new(f) as Box<dyn ProviderFactory>)
+            })
+            .map_err(|e| format!("Failed to load process provider: {e}"))
+    }
+}
+
+/// The checks [`load_verified_factory`] runs on a freshly loaded
+/// provider, whatever its kind.
+fn verify_loaded_provider(
+    config: &ProviderConfig,
+    base_dir: &Path,
+    binary_path: &Path,
+    version: &str,
+    schemas_json: &str,
+    verify_version: impl FnOnce(&str) -> Result<(), String>,
+) -> Result<(), String> {
+    if let Some(constraint) = &config.version {
+        verify_version(&constraint.raw)?;
+    }
+    carina_provider_resolver::verify_provider_schemas(
+        base_dir,
+        config,
+        binary_path,
+        version,
+        schemas_json,
+    )?;
+    Ok(())
+}
+
 /// Lift a core-validation `Result<(), String>` into per-finding
 /// `AppError::Validation` entries. The underlying `validation::*`
 /// helpers join every finding with `\n`, so splitting here lets
@@ -322,6 +379,94 @@ pub fn validate_deferred_populate_refs_with_ctx<E>(
     .collect()
 }
 
+/// Run the provider's plan-time [`Provider::validate_resource`] hook over
+/// the normalized desired resources. Only in-process providers implement
+/// the hook; plugins contribute nothing here. Warnings go to `warn`;
+/// errors are joined into one `AppError::Validation` so every finding is
+/// reported in a single plan.
+pub fn validate_resources_with_provider(
+    provider: &dyn Provider,
+    resources: &[Resource],
+    warn: &mut dyn FnMut(String),
+) -> Result<(), AppError> {
+    report_resource_diagnostics(
+        resources
+            .iter()
+            .map(|resource| (resource, provider.validate_resource(resource))),
+        warn,
+    )
+}
+
+/// Opt-in plan-time checks run by [`PlanPreprocessor::prepare`] after the
+/// provider's `validate_resource` hook. Everything is off by default;
+/// `carina plan` turns checks on from its flags.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlanChecks {
+    /// Pass each desired resource to the provider's [`Provider::simulate`]
+    /// dry run, with at most this many dry runs in flight. A dry run may
+    /// call the cloud API to check referenced identifiers.
+    pub simulate: Option<NonZeroUsize>,
+    /// Fail when a refreshed state carries properties the provider schema
+    /// does not declare, instead of ignoring them.
+    pub strict_schema: bool,
+}
+
+/// Run the provider's [`Provider::simulate`] dry run over the desired
+/// resources, at most `parallelism` at a time, and report its findings
+/// like `validate_resource`'s. A resource the provider cannot simulate is
+/// skipped with a warning: simulation is best-effort and must not block a
+/// plan on its own.
+pub async fn simulate_resources_with_provider(
+    provider: &dyn Provider,
+    resources: &[Resource],
+    parallelism: NonZeroUsize,
+    warn: &mut dyn FnMut(String),
+) -> Result<(), AppError> {
+    let results: Vec<_> = stream::iter(resources)
+        .map(|resource| async move { (resource, provider.simulate(resource).await) })
+        .buffered(parallelism.get())
+        .collect()
+        .await;
+    let mut findings = Vec::new();
+    for (resource, result) in results {
+        match result {
+            Ok(diagnostics) => findings.push((resource, diagnostics)),
+            Err(e) => warn(format!(
+                "warning: {}: simulation skipped: {}",
+                resource.id, e
+            )),
+        }
+    }
+    report_resource_diagnostics(findings, warn)
+}
+
+/// Send warnings to `warn` and join errors into one
+/// `AppError::Validation`.
+fn report_resource_diagnostics<'a>(
+    findings: impl IntoIterator<Item = (&'a Resource, Vec<ResourceDiagnostic>)>,
+    warn: &mut dyn FnMut(String),
+) -> Result<(), AppError> {
+    use carina_core::provider::DiagnosticSeverity;
+    let mut errors = Vec::new();
+    for (resource, diagnostics) in findings {
+        for diag in diagnostics {
+            let message = match &diag.attribute {
+                Some(attr) => format!("{}: {}: {}", resource.id, attr, diag.message),
+                None => format!("{}: {}", resource.id, diag.message),
+            };
+            match diag.severity {
+                DiagnosticSeverity::Error => errors.push(message),
+                DiagnosticSeverity::Warning => warn(format!("warning: {}", message)),
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors.join("\n")))
+    }
+}
+
 pub fn validate_resource_ref_types_with_ctx<E>(
     ctx: &WiringContext,
     parsed: &carina_core::parser::File<E>,
@@ -430,6 +575,10 @@ fn value_contains_empty_interpolation(value: &Value) -> bool {
 /// Resolve block name aliases and attribute prefixes in one step.
 pub fn resolve_names_with_ctx(ctx: &WiringContext, resources: &mut [Resource]) -> Vec<AppError> {
     let mut errors = lift_validation_result(resolve_block_names(resources, ctx.schemas()));
+    errors.extend(lift_validation_result(compile_policy_documents(
+        resources,
+        ctx.schemas(),
+    )));
     errors.extend(resolve_attr_prefixes_with_ctx(ctx, resources));
     errors
 }
@@ -815,14 +964,27 @@ pub fn compute_anonymous_identifiers_with_ctx(
 /// 2. `normalize_state` — convert raw API values to match DSL format
 /// 3. `merge_default_tags` — add provider-level default tags (must run after normalize_desired)
 /// 4. `resolve_enum_aliases` — convert to canonical AWS values in both resources and states
+/// 5. `validate_resource` — provider plan-time checks on the normalized desired resources
+/// 6. `simulate` — provider dry run, when [`PlanChecks::simulate`] is set
 pub struct PlanPreprocessor<'a> {
-    normalizer: &'a dyn ProviderNormalizer,
+    provider: &'a ProviderRouter,
     ctx: &'a WiringContext,
+    checks: PlanChecks,
 }
 
 impl<'a> PlanPreprocessor<'a> {
-    pub fn new(normalizer: &'a dyn ProviderNormalizer, ctx: &'a WiringContext) -> Self {
-        Self { normalizer, ctx }
+    pub fn new(provider: &'a ProviderRouter, ctx: &'a WiringContext) -> Self {
+        Self {
+            provider,
+            ctx,
+            checks: PlanChecks::default(),
+        }
+    }
+
+    /// Turn on the opt-in [`PlanChecks`].
+    pub fn with_checks(mut self, checks: PlanChecks) -> Self {
+        self.checks = checks;
+        self
     }
 
     /// Run the full normalization pipeline on desired resources, current
@@ -836,6 +998,10 @@ impl<'a> PlanPreprocessor<'a> {
     /// cannot canonicalize resources/states while silently skipping
     /// waits. `wait_bindings` is mutated in place; pass the same slice
     /// on to `create_plan`.
+    ///
+    /// Fails when the provider's `validate_resource` hook reports an
+    /// error for any desired resource, or — in strict schema mode — when
+    /// a refreshed state carries attributes the schema does not declare.
     pub async fn prepare(
         &self,
         resources: &mut [Resource],
@@ -843,9 +1009,13 @@ impl<'a> PlanPreprocessor<'a> {
         provider_configs: &[ProviderConfig],
         data_sources: &[carina_core::resource::DataSource],
         wait_bindings: &mut [carina_core::parser::WaitBinding],
-    ) {
+    ) -> Result<(), AppError> {
         let schemas = self.ctx.schemas();
+        if self.checks.strict_schema {
+            check_unknown_read_back_properties(schemas, current_states)?;
+        }
         carina_core::value::canonicalize_resources_with_schemas(resources, schemas);
+        materialize_schema_defaults(resources, schemas);
 
         // RFC #2371 stage 2 + #2387: strip every attribute the WASM
         // provider boundary refuses to serialize — `Value::Deferred(DeferredValue::Unknown)`
@@ -867,12 +1037,12 @@ impl<'a> PlanPreprocessor<'a> {
         run_desired_normalization_stages(
             resources,
             provider_configs,
-            self.normalizer,
+            self.provider,
             self.ctx.factories(),
             schemas,
         )
         .await;
-        self.normalizer.normalize_state(current_states).await;
+        self.provider.normalize_state(current_states).await;
         resolve_enum_aliases_in_states(self.ctx, current_states);
         // carina#3358: the `until` predicate RHS is the third enum-alias
         // axis. Resolve it here, beside the resource/state passes, so the
@@ -881,7 +1051,31 @@ impl<'a> PlanPreprocessor<'a> {
         // so target lookup is valid at this point.
         resolve_enum_aliases_in_wait_bindings(self.ctx, wait_bindings, resources, data_sources);
         restore_stripped_attributes(resources, stripped);
+        let mut warn = |warning: String| eprintln!("{}", warning.yellow());
+        validate_resources_with_provider(self.provider, resources, &mut warn)?;
+        if let Some(parallelism) = self.checks.simulate {
+            simulate_resources_with_provider(self.provider, resources, parallelism, &mut warn)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Strict schema mode ([`PlanChecks::strict_schema`]): reject refreshed
+/// states that carry attributes the provider schema does not declare.
+/// Outside strict mode the differ ignores them; `carina providers
+/// report-unknowns` lists the ones that reached the state file.
+pub fn check_unknown_read_back_properties(
+    schemas: &SchemaRegistry,
+    current_states: &HashMap<ResourceId, State>,
+) -> Result<(), AppError> {
+    let report = UnknownPropertyReport::from_states(schemas, current_states);
+    if report.is_empty() {
+        return Ok(());
     }
+    Err(AppError::Validation(format!(
+        "provider returned properties not declared by its schema (--strict-schema is set):\n{report}"
+    )))
 }
 
 /// Normalize enum values in current states to match DSL format.
@@ -1087,6 +1281,7 @@ pub async fn get_provider_with_ctx<E>(
     base_dir: &Path,
 ) -> Result<ProviderRouter, AppError> {
     let mut router = ProviderRouter::new();
+    let mut init_failures = Vec::new();
 
     // Two-pass build so named instances can reuse the kind's factory.
     // Pass 1 handles every default instance (top-level `provider <kind>`
@@ -1095,8 +1290,16 @@ pub async fn get_provider_with_ctx<E>(
     // instance (`let <name> = provider <kind> { ... }`), reusing the
     // factory the default instance already brought in.
     for provider_config in parsed.providers.iter().filter(|p| p.is_default) {
-        instantiate_provider_into_router(ctx, &mut router, provider_config, base_dir, None, None)
-            .await?;
+        instantiate_provider_into_router(
+            ctx,
+            &mut router,
+            &mut init_failures,
+            provider_config,
+            base_dir,
+            None,
+            None,
+        )
+        .await?;
     }
 
     for provider_config in parsed.providers.iter().filter(|p| !p.is_default) {
@@ -1108,6 +1311,7 @@ pub async fn get_provider_with_ctx<E>(
         instantiate_provider_into_router(
             ctx,
             &mut router,
+            &mut init_failures,
             provider_config,
             base_dir,
             Some(binding),
@@ -1115,12 +1319,21 @@ pub async fn get_provider_with_ctx<E>(
         )
         .await?;
     }
+    report_provider_init_failures(&router, init_failures)?;
+    if let Some(provenance) =
+        provider_mod::ProvenanceTagging::from_configs(&parsed.providers, ctx.schemas())
+    {
+        router.set_provenance(provenance);
+    }
+    if let Some(list_prices) = AwsListPrices::from_configs(&parsed.providers) {
+        router.set_list_prices(list_prices);
+    }
 
     if router.is_empty() {
         // Use mock provider for other cases.
         // Register the kind's default instance with empty kind to match
         // resources without a provider prefix.
-        println!("{}", "Using mock provider".cyan());
+        human_println!("{}", "Using mock provider".cyan());
         router.add_provider(String::new(), Box::new(MockProvider::new()));
     }
 
@@ -1175,6 +1388,34 @@ fn format_provider_using_line(
     }
 }
 
+/// Decide what a run does about provider instances that failed to
+/// initialize. When every instance failed there is nothing to plan, so
+/// the first error aborts the run as before. Otherwise each failure is
+/// reported once and the run continues; resources routed to a failed
+/// instance are blocked (see `ProviderRouter::blocked_resources`).
+fn report_provider_init_failures(
+    router: &ProviderRouter,
+    mut failures: Vec<ProviderError>,
+) -> Result<(), AppError> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+    if router.is_empty() {
+        return Err(AppError::Provider(failures.remove(0)));
+    }
+    for failure in &failures {
+        eprintln!(
+            "{}",
+            format!(
+                "Warning: {}\n  Resources that use this provider instance are skipped.",
+                failure
+            )
+            .yellow()
+        );
+    }
+    Ok(())
+}
+
 /// Register a single provider instance into `router`. `binding = None`
 /// is the kind's default instance; `binding = Some(name)` is a named
 /// instance and routes resources tagged `directives { provider = name }`.
@@ -1182,9 +1423,14 @@ fn format_provider_using_line(
 /// Source-loading (`provider <kind> { source = ... }`) is only invoked
 /// when this is the kind's default instance — named instances reuse the
 /// factory the default instance already loaded.
+///
+/// A provider that rejects its configuration (bad role ARN, account
+/// mismatch) is registered as unavailable and its error pushed onto
+/// `init_failures` instead of aborting the run.
 async fn instantiate_provider_into_router(
     ctx: &WiringContext,
     router: &mut ProviderRouter,
+    init_failures: &mut Vec<ProviderError>,
     provider_config: &ProviderConfig,
     base_dir: &Path,
     binding: Option<String>,
@@ -1197,7 +1443,7 @@ async fn instantiate_provider_into_router(
     if binding.is_none()
         && let Some(ref source) = provider_config.source
     {
-        try_add_source_provider(router, source, provider_config, base_dir).await?;
+        try_add_source_provider(router, init_failures, source, provider_config, base_dir).await;
         return Ok(());
     }
 
@@ -1208,7 +1454,7 @@ async fn instantiate_provider_into_router(
         // and the parser rejects `source` on named instances. So the only
         // candidate for the log line is the kind default's source, threaded
         // in by the caller as `inherited_source`.
-        println!(
+        human_println!(
             "{}",
             format_provider_using_line(
                 factory.name(),
@@ -1218,10 +1464,21 @@ async fn instantiate_provider_into_router(
             )
             .cyan()
         );
-        let provider = factory
+        let provider = match factory
             .create_provider(binding.as_deref(), &provider_config.attributes)
             .await
-            .map_err(|e| e.for_provider(provider_config.name.clone()))?;
+        {
+            Ok(provider) => provider,
+            Err(e) => {
+                router.add_unavailable_instance(
+                    provider_config.name.clone(),
+                    binding.clone(),
+                    e.message().to_string(),
+                );
+                init_failures.push(e.for_provider(provider_config.name.clone()));
+                return Ok(());
+            }
+        };
         router.add_normalizer(
             factory
                 .create_normalizer(binding.as_deref(), &provider_config.attributes)
@@ -1229,7 +1486,7 @@ async fn instantiate_provider_into_router(
         );
         router.add_provider_instance(provider_config.name.clone(), binding, provider);
     } else if provider_config.name == "mock" {
-        println!("{}", "Using mock provider".cyan());
+        human_println!("{}", "Using mock provider".cyan());
         router.add_provider_instance(
             provider_config.name.clone(),
             binding,
@@ -1257,36 +1514,36 @@ async fn instantiate_provider_into_router(
 
 async fn try_add_source_provider(
     router: &mut ProviderRouter,
+    init_failures: &mut Vec<ProviderError>,
     source: &str,
     config: &ProviderConfig,
     base_dir: &Path,
-) -> Result<(), AppError> {
+) {
     match load_source_provider(source, config, base_dir).await {
         Ok((factory, provider, name)) => {
             let region = factory.extract_region(&config.attributes);
-            println!(
+            human_println!(
                 "{}",
                 format_provider_using_line(&name, &region, None, Some(source)).cyan()
             );
             router.add_provider(name, provider);
             router.add_normalizer(factory.create_normalizer(None, &config.attributes).await);
-            Ok(())
         }
         Err(LoadSourceError::Provider(e)) => {
             // Provider init failure (e.g. allowed_account_ids mismatch).
-            // Propagate verbatim so the CLI boundary can render it
+            // Kept verbatim so the CLI boundary can render it
             // structurally without leaking implementation-detail
             // wrappers like "Failed to load provider '...': ...".
             // Attach provider name so the renderer can label the
             // structured block with the right provider.
-            Err(AppError::Provider(e.for_provider(config.name.clone())))
+            router.add_unavailable_instance(config.name.clone(), None, e.message().to_string());
+            init_failures.push(e.for_provider(config.name.clone()));
         }
         Err(LoadSourceError::Other(msg)) => {
             eprintln!(
                 "{}",
                 format!("Failed to load provider '{}': {}", config.name, msg).red()
             );
-            Ok(())
         }
     }
 }
@@ -1319,18 +1576,9 @@ async fn load_source_provider(
         )));
     };
 
-    if !carina_provider_resolver::is_wasm_provider(&binary_path) {
-        return Err(LoadSourceError::Other(format!(
-            "Provider '{}': native binaries are no longer supported. Use a .wasm component instead.",
-            config.name
-        )));
-    }
-
-    let factory: Box<dyn ProviderFactory> = Box::new(
-        carina_plugin_host::WasmProviderFactory::new(binary_path.clone())
-            .await
-            .map_err(|e| LoadSourceError::Other(format!("Failed to load WASM provider: {e}")))?,
-    );
+    let factory = load_verified_factory(config, base_dir, &binary_path)
+        .await
+        .map_err(LoadSourceError::Other)?;
     let name = factory.name().to_string();
 
     factory
@@ -1356,12 +1604,22 @@ pub async fn create_providers_from_configs(
     let (factories, _) = build_factories_from_providers(configs, base_dir);
     let ctx = WiringContext::new(factories);
     let mut router = ProviderRouter::new();
+    let mut init_failures = Vec::new();
 
     // Same two-pass shape as `get_provider_with_ctx`: default instances
     // first (they may load the WASM plugin), then named instances reuse
     // the factory that was just loaded.
     for config in configs.iter().filter(|p| p.is_default) {
-        instantiate_provider_into_router(&ctx, &mut router, config, base_dir, None, None).await?;
+        instantiate_provider_into_router(
+            &ctx,
+            &mut router,
+            &mut init_failures,
+            config,
+            base_dir,
+            None,
+            None,
+        )
+        .await?;
     }
     for config in configs.iter().filter(|p| !p.is_default) {
         let binding = config
@@ -1372,6 +1630,7 @@ pub async fn create_providers_from_configs(
         instantiate_provider_into_router(
             &ctx,
             &mut router,
+            &mut init_failures,
             config,
             base_dir,
             Some(binding),
@@ -1379,9 +1638,17 @@ pub async fn create_providers_from_configs(
         )
         .await?;
     }
+    report_provider_init_failures(&router, init_failures)?;
+    if let Some(provenance) = provider_mod::ProvenanceTagging::from_configs(configs, ctx.schemas())
+    {
+        router.set_provenance(provenance);
+    }
+    if let Some(list_prices) = AwsListPrices::from_configs(configs) {
+        router.set_list_prices(list_prices);
+    }
 
     if router.is_empty() {
-        println!("{}", "Using mock provider".cyan());
+        human_println!("{}", "Using mock provider".cyan());
         router.add_provider(String::new(), Box::new(MockProvider::new()));
     }
 
@@ -1837,6 +2104,7 @@ pub async fn create_plan_from_parsed<E: Clone>(
         state_block_claims,
         resolved_state_block_targets,
         base_dir,
+        PlanChecks::default(),
     )
     .await
 }
@@ -1852,6 +2120,7 @@ pub async fn create_plan_from_parsed_with_upstream<E: Clone>(
     state_block_claims: &StateBlockClaims,
     resolved_state_block_targets: &ResolvedStateBlockTargets,
     base_dir: &Path,
+    checks: PlanChecks,
 ) -> Result<PlanContext, AppError> {
     let (factories, _) = build_factories_from_providers(&parsed.providers, base_dir);
     let ctx = WiringContext::new(factories);
@@ -1866,6 +2135,7 @@ pub async fn create_plan_from_parsed_with_upstream<E: Clone>(
         state_block_claims,
         resolved_state_block_targets,
         base_dir,
+        checks,
     )
     .await
 }
@@ -1882,6 +2152,7 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
     state_block_claims: &StateBlockClaims,
     resolved_state_block_targets: &ResolvedStateBlockTargets,
     base_dir: &Path,
+    checks: PlanChecks,
 ) -> Result<PlanContext, AppError> {
     // Mutable: a same-config deferred-for loop is expanded into concrete
     // resources *after* refresh (carina#3132) and the augmented set is
@@ -1896,11 +2167,24 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
     // `current_states`.
     let mut sorted_resources =
         sort_resources_by_dependencies(&parsed.resources).map_err(AppError::Validation)?;
-    let data_sources: Vec<DataSource> = unresolved_data_sources.to_vec();
+    let mut data_sources: Vec<DataSource> = unresolved_data_sources.to_vec();
 
     // Select appropriate Provider based on configuration
     let provider = get_provider_with_ctx(ctx, parsed, base_dir).await?;
 
+    // Resources routed to a provider instance that failed to initialize
+    // (and everything depending on them) are left out of this run; the
+    // rest of the configuration is planned normally.
+    let blocked = provider.blocked_resources(&sorted_resources, &data_sources);
+    let blocked_ids: HashSet<ResourceId> = blocked.iter().map(|b| b.id.clone()).collect();
+    sorted_resources.retain(|r| !blocked_ids.contains(&r.id));
+    data_sources.retain(|d| !blocked_ids.contains(&d.id));
+    let unresolved_resources: Vec<Resource> = unresolved_resources
+        .iter()
+        .filter(|r| !blocked_ids.contains(&r.id))
+        .cloned()
+        .collect();
+
     let mut current_states: HashMap<ResourceId, State> = HashMap::new();
 
     // Build state-file-derived maps up front so anonymous → let-bound
@@ -2008,8 +2292,11 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
         if let Some(sf) = state_file.as_ref() {
             let desired_ids: HashSet<ResourceId> =
                 sorted_resources.iter().map(|r| r.id.clone()).collect();
-            let orphan_states: Vec<(ResourceId, State)> =
-                sf.build_orphan_states(&desired_ids).into_iter().collect();
+            let orphan_states: Vec<(ResourceId, State)> = sf
+                .build_orphan_states(&desired_ids)
+                .into_iter()
+                .filter(|(id, _)| !is_outside_run(&provider, &blocked_ids, id))
+                .collect();
             refresh_printed_bars |= !orphan_states.is_empty();
             let orphan_results: Vec<Result<(ResourceId, State), AppError>> =
                 stream::iter(orphan_states)
@@ -2143,6 +2430,9 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
             let desired_ids: HashSet<ResourceId> =
                 sorted_resources.iter().map(|r| r.id.clone()).collect();
             for (id, state) in sf.build_orphan_states(&desired_ids) {
+                if is_outside_run(&provider, &blocked_ids, &id) {
+                    continue;
+                }
                 current_states.entry(id).or_insert(state);
             }
 
@@ -2316,6 +2606,46 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
         &override_aware_resources,
         state_file.as_ref(),
     );
+    crate::last_operations::emit_last_operation_notes(state_file.as_ref());
+    // Schema constraints spanning two resources (a subnet's CIDR inside
+    // its VPC's) need the bindings view, so they run here rather than in
+    // `validate_resources`. They only warn: the referenced resource may
+    // have ranges the check cannot see, such as secondary VPC CIDR blocks.
+    for warning in carina_core::validation::reference_constraints::check_reference_constraints(
+        override_aware_resources.unresolved_resources(),
+        override_aware_resources.bindings(),
+        ctx.schemas(),
+    ) {
+        eprintln!("{}", format!("warning: {warning}").yellow());
+    }
+    // The project naming policy checks resolved names, so a name built
+    // from `let` bindings or another resource's known attribute is
+    // checked by its final value. Renaming a resource that already
+    // exists means replacing it, so those violations only warn; the
+    // policy is enforced on resources this plan creates.
+    if let Some(policy) = &parsed.naming {
+        let (existing, created): (Vec<_>, Vec<_>) = carina_core::validation::naming::check_naming(
+            override_aware_resources.resources(),
+            ctx.schemas(),
+            policy,
+        )
+        .into_iter()
+        .partition(|v| {
+            is_existing_resource(&v.resource, state_file.as_ref(), &parsed.state_blocks)
+        });
+        for violation in &existing {
+            eprintln!("{}", format!("warning: {violation}").yellow());
+        }
+        if !created.is_empty() {
+            return Err(AppError::Validation(
+                created
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ));
+        }
+    }
     let unresolved_override_aware_resources = OverrideAwareResources::build_for_plan(
         unresolved_resources.to_vec(),
         state_file.as_ref(),
@@ -2344,7 +2674,7 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
     // merge_default_tags → resolve_enum_aliases (resources, states, and
     // wait `until` predicates — carina#3358). Order matters.
     let mut wait_bindings = parsed.wait_bindings.clone();
-    let preprocessor = PlanPreprocessor::new(&provider, ctx);
+    let preprocessor = PlanPreprocessor::new(&provider, ctx).with_checks(checks);
     preprocessor
         .prepare(
             override_aware_resources.resources_mut(),
@@ -2353,7 +2683,7 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
             &data_sources_for_plan,
             &mut wait_bindings,
         )
-        .await;
+        .await?;
 
     // Anonymous resources whose identity attributes include ResourceRefs can
     // only be named and matched after plan-time refs and provider normalization
@@ -2408,6 +2738,7 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
             .resources()
             .iter()
             .map(|r| r.id.clone())
+            .chain(blocked_ids.iter().cloned())
             .collect();
         sf.build_orphan_dependencies(&desired_ids)
     } else {
@@ -2475,9 +2806,22 @@ pub(crate) async fn create_plan_from_parsed_with_upstream_with_ctx<E: Clone>(
         prev_explicit,
         residual_deferred_for,
         expansion_trace: parsed.expansion_trace.clone(),
+        blocked,
     })
 }
 
+/// Whether a state row must be left alone by the orphan pass because
+/// some provider instance failed to initialize: blocked resources are
+/// still declared, and rows routed to an unavailable instance can be
+/// neither read nor deleted this run.
+pub(crate) fn is_outside_run(
+    provider: &ProviderRouter,
+    blocked_ids: &HashSet<ResourceId>,
+    id: &ResourceId,
+) -> bool {
+    blocked_ids.contains(id) || provider.unavailable_reason(id).is_some()
+}
+
 /// Pre-process moved blocks by transferring state, `prev_explicit`, and
 /// `saved_attrs` from the old resource name to the new name.
 ///
@@ -2506,6 +2850,28 @@ pub fn materialize_moved_states(
     )
 }
 
+/// Whether `id` is already managed: recorded in state, or the target of
+/// an `import` or `moved` block.
+fn is_existing_resource(
+    id: &ResourceId,
+    state_file: Option<&StateFile>,
+    state_blocks: &[StateBlock],
+) -> bool {
+    let in_state = state_file.is_some_and(|sf| {
+        sf.find_resource(&id.provider, &id.resource_type, id.identity_or_empty())
+            .is_some()
+    });
+    in_state
+        || state_blocks.iter().any(|block| match block {
+            StateBlock::Import { to, .. } | StateBlock::Moved { to, .. } => {
+                to.provider == id.provider
+                    && to.resource_type == id.resource_type
+                    && to.name_str() == id.identity_or_empty()
+            }
+            StateBlock::Removed { .. } => false,
+        })
+}
+
 fn materialize_moved_states_with_warning_sink(
     current_states: &mut HashMap<ResourceId, State>,
     prev_explicit: &mut HashMap<ResourceId, carina_core::explicit::ExplicitFields>,
@@ -3214,7 +3580,7 @@ pub(crate) async fn refresh_resource_set<'a>(
 /// bar line is on the device we are about to print the plan to".
 pub(crate) fn finish_refresh_bar_region(started_bar: bool) {
     if started_bar && std::io::stdout().is_terminal() {
-        println!();
+        human_println!();
     }
 }
 
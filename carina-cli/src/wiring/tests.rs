@@ -851,6 +851,37 @@ fn bucket_state_file(names: &[&str]) -> carina_state::state::StateFile {
     state_file
 }
 
+#[test]
+fn naming_policy_treats_state_imports_and_moves_as_existing() {
+    let state_file = bucket_state_file(&["managed"]);
+    let state_blocks = vec![
+        StateBlock::Import {
+            to: StateBlockAddress::new("awscc", "s3.Bucket", "imported"),
+            id: Value::Concrete(ConcreteValue::String("imported-bucket".to_string())),
+        },
+        StateBlock::Moved {
+            from: StateBlockAddress::new("awscc", "s3.Bucket", "managed"),
+            to: StateBlockAddress::new("awscc", "s3.Bucket", "renamed"),
+        },
+    ];
+    for name in ["managed", "imported", "renamed"] {
+        assert!(
+            is_existing_resource(&bucket_resource(name).id, Some(&state_file), &state_blocks),
+            "{name} should be treated as existing"
+        );
+    }
+    assert!(!is_existing_resource(
+        &bucket_resource("fresh").id,
+        Some(&state_file),
+        &state_blocks
+    ));
+    assert!(!is_existing_resource(
+        &bucket_resource("managed").id,
+        None,
+        &[]
+    ));
+}
+
 fn assert_collision_contains(result: Result<(), AppError>, needle: &str) {
     let err = result.expect_err("expected collision error");
     assert!(
@@ -901,6 +932,7 @@ moved {
         &state_block_claims,
         &resolved_state_block_targets,
         tmp.path(),
+        PlanChecks::default(),
     )
     .await
     {
@@ -988,6 +1020,7 @@ removed {
         &state_block_claims,
         &resolved_state_block_targets,
         tmp.path(),
+        PlanChecks::default(),
     )
     .await
     {
@@ -1620,6 +1653,7 @@ moved {{
         &state_block_claims,
         &resolved_state_block_targets,
         tmp.path(),
+        PlanChecks::default(),
     )
     .await
     .expect("plan fixture");
@@ -2131,6 +2165,146 @@ async fn literal_input_data_source_refresh_reads_provider_once() {
     );
 }
 
+/// Records how many `simulate` dry runs are in flight at once.
+struct SimulateProvider {
+    in_flight: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Provider for SimulateProvider {
+    fn name(&self) -> &str {
+        "test"
+    }
+
+    fn read(
+        &self,
+        _id: &ResourceId,
+        _identifier: Option<&str>,
+        _request: ReadRequest,
+    ) -> BoxFuture<'_, ProviderResult<State>> {
+        unimplemented!("plan-time simulation does not read")
+    }
+
+    fn read_data_source(
+        &self,
+        _resource: &carina_core::resource::DataSource,
+    ) -> BoxFuture<'_, ProviderResult<State>> {
+        unimplemented!("plan-time simulation does not read")
+    }
+
+    fn create(
+        &self,
+        _id: &ResourceId,
+        _request: carina_core::provider::CreateRequest,
+    ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+        unimplemented!("plan-time simulation does not create")
+    }
+
+    fn update(
+        &self,
+        _id: &ResourceId,
+        _identifier: &str,
+        _request: UpdateRequest,
+    ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+        unimplemented!("plan-time simulation does not update")
+    }
+
+    fn delete(
+        &self,
+        _id: &ResourceId,
+        _identifier: &str,
+        _request: DeleteRequest,
+    ) -> BoxFuture<'_, ProviderResult<()>> {
+        unimplemented!("plan-time simulation does not delete")
+    }
+
+    fn required_permissions(&self, _id: &ResourceId, _op: PlanOp) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn simulate(
+        &self,
+        resource: &Resource,
+    ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+        let name = resource.id.identity_or_empty().to_string();
+        Box::pin(async move {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            if name == "bad" {
+                return Err(ProviderError::api_error("no dry run"));
+            }
+            Ok(Vec::new())
+        })
+    }
+}
+
+#[tokio::test]
+async fn simulation_runs_at_most_parallelism_dry_runs_at_once() {
+    let provider = SimulateProvider {
+        in_flight: AtomicUsize::new(0),
+        peak: AtomicUsize::new(0),
+    };
+    let resources: Vec<Resource> = (0..10)
+        .map(|i| Resource::new("s3.Bucket", format!("b{i}")))
+        .chain([Resource::new("s3.Bucket", "bad")])
+        .collect();
+    let mut warnings = Vec::new();
+
+    simulate_resources_with_provider(
+        &provider,
+        &resources,
+        NonZeroUsize::new(3).unwrap(),
+        &mut |w| warnings.push(w),
+    )
+    .await
+    .unwrap();
+
+    let peak = provider.peak.load(Ordering::SeqCst);
+    assert!((2..=3).contains(&peak), "peak in-flight dry runs: {peak}");
+    assert_eq!(warnings.len(), 1, "got {warnings:?}");
+    assert!(
+        warnings[0].contains("simulation skipped"),
+        "got {warnings:?}"
+    );
+}
+
+#[test]
+fn strict_schema_rejects_read_back_properties_the_schema_does_not_declare() {
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert(
+        "awscc",
+        ResourceSchema::new("ec2.Vpc")
+            .attribute(AttributeSchema::new("cidr_block", AttributeType::string())),
+    );
+    let id = ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None);
+    let mut attrs = HashMap::new();
+    attrs.insert(
+        "cidr_block".to_string(),
+        Value::Concrete(ConcreteValue::String("10.0.0.0/16".to_string())),
+    );
+    let mut states = HashMap::new();
+    states.insert(id.clone(), State::existing(id.clone(), attrs.clone()));
+
+    assert!(check_unknown_read_back_properties(&schemas, &states).is_ok());
+
+    attrs.insert(
+        "ipv6_native".to_string(),
+        Value::Concrete(ConcreteValue::String("false".to_string())),
+    );
+    states.insert(id.clone(), State::existing(id, attrs));
+    let err = check_unknown_read_back_properties(&schemas, &states).unwrap_err();
+    let AppError::Validation(message) = err else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert!(message.contains("--strict-schema"), "got {message}");
+    assert!(
+        message.contains("awscc.ec2.Vpc: ipv6_native"),
+        "got {message}"
+    );
+}
+
 // Two resources with unknown types must surface as two distinct
 // `AppError::Validation` entries instead of one joined string, so
 // the driver can accumulate diagnostics across validators.
@@ -2270,6 +2444,7 @@ fn region_provider_config(raw_region: &str) -> ProviderConfig {
             "region".to_string() => Value::Concrete(ConcreteValue::enum_identifier(raw_region)),
         },
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2541,6 +2716,7 @@ fn validate_rejects_empty_interpolation_in_export_value() {
         Value::Deferred(DeferredValue::Unknown(UnknownReason::EmptyInterpolation)),
     )]));
     let parsed = ParsedFile {
+        variable_params: vec![],
         export_params: vec![ParsedExportParam {
             name: "url".to_string(),
             type_expr: None,
@@ -3895,6 +4071,7 @@ mod kind_default_source_tests {
             name: name.to_string(),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             source: source.map(str::to_string),
             version: None,
             revision: None,
@@ -4281,3 +4458,30 @@ mod wait_until_enum_alias {
         );
     }
 }
+
+#[test]
+fn provider_diagnostic_warnings_go_to_the_sink_and_errors_fail() {
+    use carina_core::provider::ResourceDiagnostic;
+    let resource = Resource::new("s3.Bucket", "logs");
+    let mut warnings = Vec::new();
+    let result = report_resource_diagnostics(
+        [(
+            &resource,
+            vec![
+                ResourceDiagnostic::warning("bucket name is not DNS-compatible"),
+                ResourceDiagnostic::error("versioning requires object lock"),
+            ],
+        )],
+        &mut |w| warnings.push(w),
+    );
+    assert_eq!(
+        warnings,
+        vec![format!(
+            "warning: {}: bucket name is not DNS-compatible",
+            resource.id
+        )]
+    );
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("versioning requires object lock"), "{err}");
+    assert!(!err.contains("DNS-compatible"), "{err}");
+}
@@ -262,6 +262,7 @@ fn deferred_replace_consumer_ordering_plan_file(project: &Path, state: &StateFil
             name: "mock".to_string(),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             source: None,
             version: None,
             revision: None,
@@ -285,6 +286,8 @@ fn deferred_replace_consumer_ordering_plan_file(project: &Path, state: &StateFil
         upstream_snapshot: HashMap::new(),
         upstream_sources: vec![],
         wait_bindings: vec![],
+        changes: None,
+        trace_id: None,
     }
 }
 
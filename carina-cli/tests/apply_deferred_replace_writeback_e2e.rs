@@ -214,6 +214,7 @@ fn deferred_replace_plan_file(project: &Path, state: &StateFile) -> PlanFile {
             name: "mock".to_string(),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             source: None,
             version: None,
             revision: None,
@@ -237,6 +238,8 @@ fn deferred_replace_plan_file(project: &Path, state: &StateFile) -> PlanFile {
         upstream_snapshot: HashMap::new(),
         upstream_sources: vec![],
         wait_bindings: vec![],
+        changes: None,
+        trace_id: None,
     }
 }
 
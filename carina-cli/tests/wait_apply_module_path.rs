@@ -434,7 +434,8 @@ async fn run_apply_chain(cert_publishes_arn: bool) -> (usize, usize, Vec<String>
 
     let provider = NoopProvider { cert_publishes_arn };
     let mut wait_bindings = parsed.wait_bindings.clone();
-    let preprocessor = PlanPreprocessor::new(&NoopNormalizer, &ctx);
+    let router = carina_core::provider::ProviderRouter::new();
+    let preprocessor = PlanPreprocessor::new(&router, &ctx);
     preprocessor
         .prepare(
             &mut resources_for_plan,
@@ -443,7 +444,8 @@ async fn run_apply_chain(cert_publishes_arn: bool) -> (usize, usize, Vec<String>
             &parsed.data_sources,
             &mut wait_bindings,
         )
-        .await;
+        .await
+        .expect("prepare");
 
     let plan = create_plan(
         &resources_for_plan,
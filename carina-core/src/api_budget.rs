@@ -0,0 +1,381 @@
+//! API call budget estimation for plans.
+//!
+//! Before apply, [`estimate_api_calls`] predicts how many cloud API calls
+//! each service of each provider instance will receive — the mutating
+//! call itself, the read-back after it, and the status polls in between —
+//! so the CLI can warn when a plan is likely to run into provider
+//! throttling. Throttling quotas belong to the account and region behind
+//! an instance, so two instances of the same provider are budgeted
+//! separately.
+//!
+//! Polling is invisible to the host (it happens inside the provider), so
+//! the poll count is derived from how long an operation on the same
+//! resource type took in earlier applies. Those durations are collected
+//! by [`ApiCallStatsRecorder`] and persisted as [`ApiCallStats`]. The
+//! same history feeds [`estimate_apply_duration`], which the CLI compares
+//! against the remaining lifetime of the provider's credentials.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::effect::Effect;
+use crate::executor::{ExecutionEvent, ExecutionObserver};
+use crate::plan::Plan;
+use crate::resource::ResourceId;
+
+/// Assumed interval between provider status polls.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default call count for one service of one provider instance above
+/// which a plan is flagged as likely to be throttled.
+pub const DEFAULT_SERVICE_CALL_BUDGET: u64 = 200;
+
+/// Polls assumed for a mutating operation on a type with no history.
+const DEFAULT_POLLS: u64 = 2;
+
+/// Duration assumed for a mutating operation on a type with no history.
+/// Deliberately generous: underestimating is what lets credentials
+/// expire mid-apply.
+pub const DEFAULT_OPERATION_DURATION: Duration = Duration::from_secs(120);
+
+/// Observed durations of one operation kind on one resource type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct OperationSample {
+    pub runs: u64,
+    pub total_secs: f64,
+}
+
+impl OperationSample {
+    pub fn mean(&self) -> Option<Duration> {
+        (self.runs > 0).then(|| Duration::from_secs_f64(self.total_secs / self.runs as f64))
+    }
+}
+
+/// Per-resource-type, per-operation timing history from previous applies.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiCallStats {
+    samples: BTreeMap<String, BTreeMap<String, OperationSample>>,
+}
+
+impl ApiCallStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one completed operation of `kind` (an [`Effect::kind`]) on
+    /// `resource_type`.
+    pub fn record(&mut self, resource_type: &str, kind: &str, duration: Duration) {
+        let sample = self
+            .samples
+            .entry(resource_type.to_string())
+            .or_default()
+            .entry(kind.to_string())
+            .or_default();
+        sample.runs += 1;
+        sample.total_secs += duration.as_secs_f64();
+    }
+
+    pub fn sample(&self, resource_type: &str, kind: &str) -> Option<&OperationSample> {
+        self.samples.get(resource_type)?.get(kind)
+    }
+
+    /// Fold `other`'s samples into `self`.
+    pub fn merge(&mut self, other: &ApiCallStats) {
+        for (resource_type, kinds) in &other.samples {
+            let entry = self.samples.entry(resource_type.clone()).or_default();
+            for (kind, sample) in kinds {
+                let merged = entry.entry(kind.clone()).or_default();
+                merged.runs += sample.runs;
+                merged.total_secs += sample.total_secs;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Polls expected for `kind` on `resource_type`: the mean recorded
+    /// duration divided by [`POLL_INTERVAL`], or [`DEFAULT_POLLS`] when
+    /// there is no history.
+    fn expected_polls(&self, resource_type: &str, kind: &str) -> u64 {
+        match self
+            .sample(resource_type, kind)
+            .and_then(OperationSample::mean)
+        {
+            Some(mean) => mean.as_secs().div_ceil(POLL_INTERVAL.as_secs()),
+            None => DEFAULT_POLLS,
+        }
+    }
+}
+
+/// A service of one provider instance, the unit a budget applies to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BudgetKey {
+    /// Provider instance name (`awscc`, or `awscc.prod` for a named
+    /// binding). Empty when the resource carries no provider.
+    pub instance: String,
+    pub service: String,
+}
+
+/// Estimated API calls per provider instance and service for a plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiCallEstimate {
+    pub per_service: BTreeMap<BudgetKey, u64>,
+}
+
+impl ApiCallEstimate {
+    pub fn total(&self) -> u64 {
+        self.per_service.values().sum()
+    }
+
+    /// Calls estimated for `service` of `instance`, zero when none.
+    pub fn calls(&self, instance: &str, service: &str) -> u64 {
+        self.per_service
+            .get(&BudgetKey {
+                instance: instance.to_string(),
+                service: service.to_string(),
+            })
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Services whose estimate exceeds `budget`, with their estimate.
+    pub fn over_budget(&self, budget: u64) -> Vec<(&BudgetKey, u64)> {
+        self.per_service
+            .iter()
+            .filter(|(_, calls)| **calls > budget)
+            .map(|(key, calls)| (key, *calls))
+            .collect()
+    }
+}
+
+/// Service a resource type belongs to: the segment before the first dot
+/// (`ec2.Vpc` → `ec2`).
+pub fn service_of(resource_type: &str) -> &str {
+    resource_type
+        .split_once('.')
+        .map_or(resource_type, |(service, _)| service)
+}
+
+/// Provider instance a resource is routed to, named the way
+/// [`ProviderRouter`](crate::provider::ProviderRouter) reports instances:
+/// the provider, followed by `.binding` for a named instance.
+pub fn instance_of(id: &ResourceId) -> String {
+    match &id.provider_instance {
+        Some(binding) => format!("{}.{binding}", id.provider),
+        None => id.provider.clone(),
+    }
+}
+
+/// Estimate the API calls `plan` will issue, grouped by provider
+/// instance and service.
+///
+/// Create and update cost the mutating call, a read-back and the
+/// expected polls; delete costs the call and its polls; data source
+/// reads and imports cost one read. State-only and scheduler effects are
+/// free. Replacements are costed through the create and delete effects
+/// they expand into.
+pub fn estimate_api_calls(plan: &Plan, stats: &ApiCallStats) -> ApiCallEstimate {
+    let mut estimate = ApiCallEstimate::default();
+    for effect in plan.effects() {
+        let id = effect.resource_id();
+        let resource_type = &id.resource_type;
+        let calls = match effect {
+            Effect::Create(_) | Effect::Update { .. } => {
+                2 + stats.expected_polls(resource_type, effect.kind())
+            }
+            Effect::Delete { .. } => 1 + stats.expected_polls(resource_type, effect.kind()),
+            Effect::Read { .. } | Effect::Import { .. } => 1,
+            Effect::Remove { .. }
+            | Effect::Move { .. }
+            | Effect::Wait { .. }
+            | Effect::DeferredCreate { .. }
+            | Effect::DeferredReplace(_) => 0,
+        };
+        if calls > 0 {
+            let key = BudgetKey {
+                instance: instance_of(id),
+                service: service_of(resource_type).to_string(),
+            };
+            *estimate.per_service.entry(key).or_default() += calls;
+        }
+    }
+    estimate
+}
+
+/// Conservative estimate of how long applying `plan` takes.
+///
+/// Each create, update and delete takes its type's mean recorded duration
+/// ([`DEFAULT_OPERATION_DURATION`] without history), and at most
+/// `parallelism` run at once; the result is never shorter than the
+/// slowest single operation. Reads, imports and state-only effects are
+/// treated as instant, and waits are not counted since their length
+/// depends on what they wait for.
+pub fn estimate_apply_duration(
+    plan: &Plan,
+    stats: &ApiCallStats,
+    parallelism: NonZeroUsize,
+) -> Duration {
+    let mut total = Duration::ZERO;
+    let mut longest = Duration::ZERO;
+    for effect in plan.effects() {
+        if !matches!(
+            effect,
+            Effect::Create(_) | Effect::Update { .. } | Effect::Delete { .. }
+        ) {
+            continue;
+        }
+        let duration = stats
+            .sample(&effect.resource_id().resource_type, effect.kind())
+            .and_then(OperationSample::mean)
+            .unwrap_or(DEFAULT_OPERATION_DURATION);
+        total += duration;
+        longest = longest.max(duration);
+    }
+    let spread = total / u32::try_from(parallelism.get()).unwrap_or(u32::MAX);
+    spread.max(longest)
+}
+
+/// Observer wrapper that records successful operation durations into a
+/// shared [`ApiCallStats`] while forwarding every event to `inner`.
+pub struct ApiCallStatsRecorder {
+    inner: Box<dyn ExecutionObserver>,
+    stats: Arc<Mutex<ApiCallStats>>,
+}
+
+impl ApiCallStatsRecorder {
+    /// Wrap `inner`, returning the recorder and a handle to the stats it
+    /// collects.
+    pub fn wrap(inner: Box<dyn ExecutionObserver>) -> (Self, Arc<Mutex<ApiCallStats>>) {
+        let stats = Arc::new(Mutex::new(ApiCallStats::new()));
+        (
+            Self {
+                inner,
+                stats: Arc::clone(&stats),
+            },
+            stats,
+        )
+    }
+}
+
+impl ExecutionObserver for ApiCallStatsRecorder {
+    fn on_event(&self, event: &ExecutionEvent) {
+        if let ExecutionEvent::EffectSucceeded {
+            effect, duration, ..
+        } = event
+            && matches!(
+                effect,
+                Effect::Create(_) | Effect::Update { .. } | Effect::Delete { .. }
+            )
+        {
+            self.stats.lock().unwrap().record(
+                &effect.resource_id().resource_type,
+                effect.kind(),
+                *duration,
+            );
+        }
+        self.inner.on_event(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{ResolvedResource, Resource};
+
+    fn create(resource_type: &str, name: &str) -> Effect {
+        Effect::Create(ResolvedResource::new(Resource::new(resource_type, name)))
+    }
+
+    #[test]
+    fn service_of_takes_first_segment() {
+        assert_eq!(service_of("ec2.Vpc"), "ec2");
+        assert_eq!(service_of("s3.Bucket"), "s3");
+        assert_eq!(service_of("bare"), "bare");
+    }
+
+    #[test]
+    fn estimate_uses_default_polls_without_history() {
+        let mut plan = Plan::new();
+        plan.add(create("ec2.Vpc", "a"));
+        plan.add(create("ec2.Subnet", "b"));
+        let estimate = estimate_api_calls(&plan, &ApiCallStats::new());
+        assert_eq!(estimate.calls("", "ec2"), 2 * (2 + DEFAULT_POLLS));
+    }
+
+    #[test]
+    fn estimate_scales_polls_with_recorded_duration() {
+        let mut stats = ApiCallStats::new();
+        stats.record("rds.DbInstance", "create", Duration::from_secs(600));
+        stats.record("rds.DbInstance", "create", Duration::from_secs(400));
+        let mut plan = Plan::new();
+        plan.add(create("rds.DbInstance", "db"));
+        let estimate = estimate_api_calls(&plan, &stats);
+        // mean 500s / 5s interval = 100 polls, plus create and read-back.
+        assert_eq!(estimate.calls("", "rds"), 102);
+        let over = estimate.over_budget(100);
+        assert_eq!(over.len(), 1);
+        assert_eq!((over[0].0.service.as_str(), over[0].1), ("rds", 102));
+        assert!(estimate.over_budget(200).is_empty());
+    }
+
+    #[test]
+    fn estimate_budgets_each_provider_instance_separately() {
+        let mut plan = Plan::new();
+        for (name, binding) in [("a", None), ("b", None), ("c", Some("prod"))] {
+            let mut resource = Resource::new("ec2.Vpc", name);
+            resource.id.provider = "awscc".to_string();
+            resource.id.provider_instance = binding.map(str::to_string);
+            plan.add(Effect::Create(ResolvedResource::new(resource)));
+        }
+        let estimate = estimate_api_calls(&plan, &ApiCallStats::new());
+        assert_eq!(estimate.calls("awscc", "ec2"), 2 * (2 + DEFAULT_POLLS));
+        assert_eq!(estimate.calls("awscc.prod", "ec2"), 2 + DEFAULT_POLLS);
+        assert_eq!(estimate.total(), 3 * (2 + DEFAULT_POLLS));
+    }
+
+    #[test]
+    fn apply_duration_spreads_operations_over_parallelism() {
+        let mut stats = ApiCallStats::new();
+        stats.record("rds.DbInstance", "create", Duration::from_secs(600));
+        let mut plan = Plan::new();
+        plan.add(create("rds.DbInstance", "db"));
+        for name in ["a", "b", "c", "d"] {
+            plan.add(create("ec2.Vpc", name));
+        }
+        let two = NonZeroUsize::new(2).unwrap();
+        // The database alone outlasts the VPCs spread over two workers.
+        assert_eq!(
+            estimate_apply_duration(&plan, &stats, two),
+            Duration::from_secs(600)
+        );
+        stats.record("rds.DbInstance", "create", Duration::from_secs(0));
+        // (300s + 4 × 120s) / 2 workers.
+        assert_eq!(
+            estimate_apply_duration(&plan, &stats, two),
+            Duration::from_secs(390)
+        );
+        assert_eq!(
+            estimate_apply_duration(&Plan::new(), &stats, two),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn stats_merge_accumulates_samples() {
+        let mut a = ApiCallStats::new();
+        a.record("s3.Bucket", "create", Duration::from_secs(2));
+        let mut b = ApiCallStats::new();
+        b.record("s3.Bucket", "create", Duration::from_secs(4));
+        a.merge(&b);
+        let sample = a.sample("s3.Bucket", "create").unwrap();
+        assert_eq!(sample.runs, 2);
+        assert_eq!(sample.mean(), Some(Duration::from_secs(3)));
+    }
+}
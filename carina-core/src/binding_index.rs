@@ -301,7 +301,7 @@ impl BindingNameSet {
                 .entry(upstream.binding.clone())
                 .or_insert(BindingNameKind::UpstreamState);
         }
-        for arg in &parsed.arguments {
+        for arg in parsed.arguments.iter().chain(&parsed.variable_params) {
             by_name
                 .entry(arg.name.clone())
                 .or_insert(BindingNameKind::Argument);
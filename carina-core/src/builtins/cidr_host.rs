@@ -0,0 +1,110 @@
+//! `cidr_host(prefix, hostnum)` built-in function
+
+use std::net::Ipv4Addr;
+
+use crate::resource::{ConcreteValue, Value};
+
+use super::cidr_subnet::parse_cidr;
+use super::value_type_name;
+
+/// `cidr_host(prefix, hostnum)` - Calculate a host address within a network prefix.
+///
+/// - `prefix`: base CIDR string (e.g., "10.0.1.0/24")
+/// - `hostnum`: host number within the prefix (Int); negative numbers
+///   count back from the end of the range
+/// - Returns: IP address string without a prefix length (e.g., "10.0.1.5")
+///
+/// Examples:
+/// ```text
+/// cidr_host("10.0.1.0/24", 5)   // => "10.0.1.5"
+/// cidr_host("10.0.1.0/24", -2)  // => "10.0.1.254"
+/// ```
+pub(crate) fn builtin_cidr_host(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "cidr_host() expects 2 arguments (prefix, hostnum), got {}",
+            args.len()
+        ));
+    }
+
+    let prefix = match &args[0] {
+        Value::Concrete(ConcreteValue::String(s)) => s,
+        other => {
+            return Err(format!(
+                "cidr_host() first argument (prefix) must be a string, got {}",
+                value_type_name(other)
+            ));
+        }
+    };
+
+    let hostnum = match &args[1] {
+        Value::Concrete(ConcreteValue::Int(n)) => *n,
+        other => {
+            return Err(format!(
+                "cidr_host() second argument (hostnum) must be an integer, got {}",
+                value_type_name(other)
+            ));
+        }
+    };
+
+    let (addr, prefix_len) = parse_cidr("cidr_host", prefix)?;
+    let size: i64 = 1i64 << (32 - prefix_len);
+    let offset = if hostnum < 0 { size + hostnum } else { hostnum };
+    if !(0..size).contains(&offset) {
+        return Err(format!(
+            "cidr_host() hostnum {} is out of range for prefix {} ({} addresses)",
+            hostnum, prefix, size
+        ));
+    }
+
+    let base_mask = if prefix_len == 0 {
+        0u32
+    } else {
+        !0u32 << (32 - prefix_len)
+    };
+    let host = (u32::from(addr) & base_mask) | offset as u32;
+    Ok(Value::Concrete(ConcreteValue::String(
+        Ipv4Addr::from(host).to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builtins::evaluate_builtin_to_value as evaluate_builtin;
+    use crate::resource::{ConcreteValue, Value};
+
+    fn cidr_host(prefix: &str, hostnum: i64) -> Result<Value, String> {
+        let args = vec![
+            Value::Concrete(ConcreteValue::String(prefix.to_string())),
+            Value::Concrete(ConcreteValue::Int(hostnum)),
+        ];
+        evaluate_builtin("cidr_host", &args)
+    }
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    #[test]
+    fn host_numbers_count_from_the_network_address() {
+        assert_eq!(cidr_host("10.0.1.0/24", 5).unwrap(), s("10.0.1.5"));
+        assert_eq!(cidr_host("10.0.1.77/24", 0).unwrap(), s("10.0.1.0"));
+        assert_eq!(cidr_host("10.0.0.0/16", 258).unwrap(), s("10.0.1.2"));
+    }
+
+    #[test]
+    fn negative_host_numbers_count_from_the_end() {
+        assert_eq!(cidr_host("10.0.1.0/24", -1).unwrap(), s("10.0.1.255"));
+        assert_eq!(cidr_host("10.0.1.0/24", -2).unwrap(), s("10.0.1.254"));
+    }
+
+    #[test]
+    fn out_of_range_host_numbers_are_rejected() {
+        let err = cidr_host("10.0.1.0/24", 256).unwrap_err();
+        assert!(err.contains("out of range"), "{err}");
+        let err = cidr_host("10.0.1.0/24", -257).unwrap_err();
+        assert!(err.contains("out of range"), "{err}");
+        let err = cidr_host("10.0.1.0", 1).unwrap_err();
+        assert!(err.contains("cidr_host() invalid CIDR format"), "{err}");
+    }
+}
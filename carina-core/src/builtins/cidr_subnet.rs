@@ -61,28 +61,29 @@ pub(crate) fn builtin_cidr_subnet(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Concrete(ConcreteValue::String(result)))
 }
 
-/// Parse a CIDR string into (Ipv4Addr, prefix_length).
-fn parse_cidr(cidr: &str) -> Result<(Ipv4Addr, u32), String> {
+/// Parse a CIDR string into (Ipv4Addr, prefix_length). `func` names the
+/// calling built-in in error messages.
+pub(super) fn parse_cidr(func: &str, cidr: &str) -> Result<(Ipv4Addr, u32), String> {
     let parts: Vec<&str> = cidr.split('/').collect();
     if parts.len() != 2 {
         return Err(format!(
-            "cidr_subnet() invalid CIDR format: '{}' (expected 'address/prefix')",
-            cidr
+            "{}() invalid CIDR format: '{}' (expected 'address/prefix')",
+            func, cidr
         ));
     }
 
     let addr: Ipv4Addr = parts[0]
         .parse()
-        .map_err(|e| format!("cidr_subnet() invalid IP address '{}': {}", parts[0], e))?;
+        .map_err(|e| format!("{}() invalid IP address '{}': {}", func, parts[0], e))?;
 
     let prefix_len: u32 = parts[1]
         .parse()
-        .map_err(|e| format!("cidr_subnet() invalid prefix length '{}': {}", parts[1], e))?;
+        .map_err(|e| format!("{}() invalid prefix length '{}': {}", func, parts[1], e))?;
 
     if prefix_len > 32 {
         return Err(format!(
-            "cidr_subnet() prefix length {} exceeds maximum of 32",
-            prefix_len
+            "{}() prefix length {} exceeds maximum of 32",
+            func, prefix_len
         ));
     }
 
@@ -104,7 +105,7 @@ fn calculate_cidr_subnet(prefix: &str, newbits: i64, netnum: i64) -> Result<Stri
         ));
     }
 
-    let (addr, prefix_len) = parse_cidr(prefix)?;
+    let (addr, prefix_len) = parse_cidr("cidr_subnet", prefix)?;
     let newbits = newbits as u32;
     let netnum = netnum as u64;
 
@@ -0,0 +1,83 @@
+//! `coalesce(values)` built-in function
+
+use crate::resource::{ConcreteValue, Value};
+
+use super::value_type_name;
+
+/// `coalesce(values)` - Return the first value in a list that is not empty.
+///
+/// - Single argument: a List
+/// - Returns: the first element that is not an empty string, list or map
+///
+/// Examples:
+/// ```text
+/// coalesce(["", "fallback"])  // => "fallback"
+/// coalesce([override, "default"])
+/// ```
+pub(crate) fn builtin_coalesce(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("coalesce() expects 1 argument, got {}", args.len()));
+    }
+
+    match &args[0] {
+        Value::Concrete(ConcreteValue::List(items)) => items
+            .iter()
+            .find(|item| !is_empty(item))
+            .cloned()
+            .ok_or_else(|| "coalesce() every value is empty".to_string()),
+        other => Err(format!(
+            "coalesce() argument must be a List, got {}",
+            value_type_name(other)
+        )),
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Concrete(ConcreteValue::String(s)) => s.is_empty(),
+        Value::Concrete(ConcreteValue::List(items)) => items.is_empty(),
+        Value::Concrete(ConcreteValue::Map(map)) => map.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builtins::evaluate_builtin_to_value as evaluate_builtin;
+    use crate::resource::{ConcreteValue, Value};
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    fn list(items: Vec<Value>) -> Value {
+        Value::Concrete(ConcreteValue::List(items))
+    }
+
+    #[test]
+    fn first_non_empty_value_wins() {
+        assert_eq!(
+            evaluate_builtin("coalesce", &[list(vec![s(""), s("a"), s("b")])]).unwrap(),
+            s("a")
+        );
+        assert_eq!(
+            evaluate_builtin(
+                "coalesce",
+                &[list(vec![
+                    list(vec![]),
+                    Value::Concrete(ConcreteValue::Int(0))
+                ])]
+            )
+            .unwrap(),
+            Value::Concrete(ConcreteValue::Int(0))
+        );
+    }
+
+    #[test]
+    fn all_empty_is_an_error() {
+        let err = evaluate_builtin("coalesce", &[list(vec![s("")])]).unwrap_err();
+        assert!(err.contains("every value is empty"), "{err}");
+        let err = evaluate_builtin("coalesce", &[s("x")]).unwrap_err();
+        assert!(err.contains("must be a List"), "{err}");
+    }
+}
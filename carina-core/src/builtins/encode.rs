@@ -0,0 +1,123 @@
+//! `base64_encode(string)` and `json_encode(value)` built-in functions
+
+use base64::Engine;
+
+use crate::resource::{ConcreteValue, Value};
+
+use super::value_type_name;
+
+/// `base64_encode(string)` - Encode a string's UTF-8 bytes as standard base64.
+///
+/// - Single argument: a String
+/// - Returns: String
+///
+/// Examples:
+/// ```text
+/// base64_encode("hello")  // => "aGVsbG8="
+/// ```
+pub(crate) fn builtin_base64_encode(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "base64_encode() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    match &args[0] {
+        Value::Concrete(ConcreteValue::String(s)) => Ok(Value::Concrete(ConcreteValue::String(
+            base64::engine::general_purpose::STANDARD.encode(s.as_bytes()),
+        ))),
+        other => Err(format!(
+            "base64_encode() argument must be a String, got {}",
+            value_type_name(other)
+        )),
+    }
+}
+
+/// `json_encode(value)` - Render a value as compact JSON.
+///
+/// - Single argument: any fully known value
+/// - Returns: String (map keys sorted)
+///
+/// A secret anywhere in the value makes the result a secret.
+///
+/// Examples:
+/// ```text
+/// json_encode({b: 1, a: ["x"]})  // => "{\"a\":[\"x\"],\"b\":1}"
+/// ```
+pub(crate) fn builtin_json_encode(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "json_encode() expects 1 argument, got {}",
+            args.len()
+        ));
+    }
+
+    if let Some(deferred) = find_deferred(&args[0]) {
+        return Err(format!(
+            "json_encode() argument must be fully known, found {}",
+            value_type_name(deferred)
+        ));
+    }
+    let json = crate::value::value_to_json(&args[0]).map_err(|e| format!("json_encode(): {e}"))?;
+    Ok(Value::Concrete(ConcreteValue::String(json.to_string())))
+}
+
+fn find_deferred(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Deferred(_) => Some(value),
+        Value::Concrete(ConcreteValue::List(items)) => items.iter().find_map(find_deferred),
+        Value::Concrete(ConcreteValue::Map(map)) => map.values().find_map(find_deferred),
+        Value::Concrete(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use crate::builtins::evaluate_builtin_to_value as evaluate_builtin;
+    use crate::resource::{ConcreteValue, DeferredValue, Value};
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    #[test]
+    fn base64_encode_basic() {
+        assert_eq!(
+            evaluate_builtin("base64_encode", &[s("hello")]).unwrap(),
+            s("aGVsbG8=")
+        );
+        assert_eq!(evaluate_builtin("base64_encode", &[s("")]).unwrap(), s(""));
+        let err = evaluate_builtin("base64_encode", &[Value::Concrete(ConcreteValue::Int(1))])
+            .unwrap_err();
+        assert!(err.contains("must be a String"), "{err}");
+    }
+
+    #[test]
+    fn json_encode_sorts_map_keys() {
+        let value = Value::Concrete(ConcreteValue::Map(IndexMap::from([
+            ("b".to_string(), Value::Concrete(ConcreteValue::Int(1))),
+            (
+                "a".to_string(),
+                Value::Concrete(ConcreteValue::List(vec![s("x")])),
+            ),
+        ])));
+        assert_eq!(
+            evaluate_builtin("json_encode", &[value]).unwrap(),
+            s(r#"{"a":["x"],"b":1}"#)
+        );
+    }
+
+    #[test]
+    fn json_encode_of_a_secret_is_secret() {
+        let value = Value::Concrete(ConcreteValue::List(vec![Value::Deferred(
+            DeferredValue::Secret(Box::new(s("hunter2"))),
+        )]));
+        assert_eq!(
+            evaluate_builtin("json_encode", &[value]).unwrap(),
+            Value::Deferred(DeferredValue::Secret(Box::new(s(r#"["hunter2"]"#))))
+        );
+    }
+}
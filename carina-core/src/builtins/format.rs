@@ -0,0 +1,158 @@
+//! `format(template, values)` built-in function
+
+use crate::resource::{ConcreteValue, Value};
+
+use super::value_type_name;
+
+/// `format(template, values)` - Substitute values into a template string.
+///
+/// - First argument: template (String). `%s` takes the next value as
+///   text, `%d` takes the next value and requires an Int, `%%` is a
+///   literal `%`.
+/// - Second argument: list of values, consumed left to right
+/// - Returns: String
+///
+/// The number of placeholders must match the number of values.
+///
+/// Examples:
+/// ```text
+/// format("%s-%d", ["web", 3])  // => "web-3"
+/// ["web", 3] |> format("%s-%d")  // => "web-3" (pipe form)
+/// ```
+pub(crate) fn builtin_format(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!(
+            "format() expects 2 arguments (template, values), got {}",
+            args.len()
+        ));
+    }
+
+    let template = match &args[0] {
+        Value::Concrete(ConcreteValue::String(s)) => s,
+        other => {
+            return Err(format!(
+                "format() first argument must be a string, got {}",
+                value_type_name(other)
+            ));
+        }
+    };
+
+    let values = match &args[1] {
+        Value::Concrete(ConcreteValue::List(items)) => items,
+        other => {
+            return Err(format!(
+                "format() second argument must be a list, got {}",
+                value_type_name(other)
+            ));
+        }
+    };
+
+    let mut out = String::with_capacity(template.len());
+    let mut next = values.iter();
+    let mut used = 0;
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let verb = chars.next();
+        if verb == Some('%') {
+            out.push('%');
+            continue;
+        }
+        let value = next.next().ok_or_else(|| {
+            format!(
+                "format() template has more placeholders than the {} value(s) given",
+                values.len()
+            )
+        })?;
+        used += 1;
+        match (verb, value) {
+            (Some('s'), v) => out.push_str(&display(v)?),
+            (Some('d'), Value::Concrete(ConcreteValue::Int(n))) => out.push_str(&n.to_string()),
+            (Some('d'), other) => {
+                return Err(format!(
+                    "format() value {} for %d must be an Int, got {}",
+                    used,
+                    value_type_name(other)
+                ));
+            }
+            (Some(other), _) => {
+                return Err(format!(
+                    "format() unknown placeholder '%{}'; use %s, %d or %%",
+                    other
+                ));
+            }
+            (None, _) => return Err("format() template ends with a lone '%'".to_string()),
+        }
+    }
+    if used != values.len() {
+        return Err(format!(
+            "format() template has {} placeholder(s) but {} value(s) were given",
+            used,
+            values.len()
+        ));
+    }
+
+    Ok(Value::Concrete(ConcreteValue::String(out)))
+}
+
+fn display(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Concrete(ConcreteValue::String(s)) => Ok(s.clone()),
+        Value::Concrete(ConcreteValue::Int(n)) => Ok(n.to_string()),
+        Value::Concrete(ConcreteValue::Float(f)) => Ok(f.to_string()),
+        Value::Concrete(ConcreteValue::Bool(b)) => Ok(b.to_string()),
+        Value::Concrete(ConcreteValue::Duration(d)) => Ok(crate::value::render_duration(*d)),
+        other => Err(format!(
+            "format() %s value must be a String, number or Bool, got {}",
+            value_type_name(other)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builtins::evaluate_builtin_to_value as evaluate_builtin;
+    use crate::resource::{ConcreteValue, Value};
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    fn format(template: &str, values: Vec<Value>) -> Result<Value, String> {
+        evaluate_builtin(
+            "format",
+            &[s(template), Value::Concrete(ConcreteValue::List(values))],
+        )
+    }
+
+    #[test]
+    fn placeholders_are_filled_in_order() {
+        assert_eq!(
+            format(
+                "%s-%d (100%%)",
+                vec![s("web"), Value::Concrete(ConcreteValue::Int(3))]
+            )
+            .unwrap(),
+            s("web-3 (100%)")
+        );
+    }
+
+    #[test]
+    fn placeholder_count_must_match() {
+        let err = format("%s-%s", vec![s("web")]).unwrap_err();
+        assert!(err.contains("more placeholders"), "{err}");
+        let err = format("%s", vec![s("a"), s("b")]).unwrap_err();
+        assert!(err.contains("1 placeholder(s) but 2 value(s)"), "{err}");
+    }
+
+    #[test]
+    fn d_requires_an_int() {
+        let err = format("%d", vec![s("3")]).unwrap_err();
+        assert!(err.contains("must be an Int, got String"), "{err}");
+        let err = format("%x", vec![s("3")]).unwrap_err();
+        assert!(err.contains("unknown placeholder '%x'"), "{err}");
+    }
+}
@@ -0,0 +1,169 @@
+//! `ids(resources)` and `attrs(resources, name)` built-in functions
+
+use crate::resource::{ConcreteValue, DeferredValue, UnknownReason, Value};
+
+use super::value_type_name;
+
+/// `attrs(resources, name)` - Reference one attribute of every resource in
+/// a `for`-expression group.
+///
+/// - First argument: the binding of a `for` expression that produced
+///   resources or module calls
+/// - Second argument: the attribute name
+/// - Returns: List of attribute references, one per group member, in
+///   iteration order (list index order, or sorted key order for maps)
+///
+/// Each element is an ordinary reference, so values not known until the
+/// member is applied show as known after apply, and the resource using
+/// the list depends on every member. A `for` expression whose iterable is
+/// not available yet has no members: the call stays deferred until the
+/// expression expands, and evaluates to an unknown value if it never does.
+///
+/// Examples:
+/// ```text
+/// attrs(subnets, 'subnet_id')  // => [subnets[0].subnet_id, subnets[1].subnet_id]
+/// ```
+pub(crate) fn builtin_attrs(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 2 {
+        return Err(format!("attrs() expects 2 arguments, got {}", args.len()));
+    }
+    let name = match &args[1] {
+        Value::Concrete(ConcreteValue::String(s)) if !s.is_empty() => s,
+        other => {
+            return Err(format!(
+                "attrs() second argument must be an attribute name, got {}",
+                value_type_name(other)
+            ));
+        }
+    };
+    member_refs("attrs", &args[0], name)
+}
+
+/// `ids(resources)` - Shorthand for `attrs(resources, 'id')`.
+///
+/// Examples:
+/// ```text
+/// ids(subnets)  // => [subnets[0].id, subnets[1].id]
+/// ```
+pub(crate) fn builtin_ids(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("ids() expects 1 argument, got {}", args.len()));
+    }
+    member_refs("ids", &args[0], "id")
+}
+
+/// The binding of the deferred for-expression `value` stands for, if any.
+pub(crate) fn deferred_for_group(value: &Value) -> Option<&str> {
+    match value {
+        Value::Deferred(DeferredValue::Unknown(UnknownReason::ForGroup { binding })) => {
+            Some(binding)
+        }
+        _ => None,
+    }
+}
+
+fn member_refs(func: &str, group: &Value, attribute: &str) -> Result<Value, String> {
+    if deferred_for_group(group).is_some() {
+        return Ok(group.clone());
+    }
+    let Value::Concrete(ConcreteValue::List(members)) = group else {
+        return Err(format!(
+            "{func}() first argument must be a for-expression binding, got {}",
+            value_type_name(group)
+        ));
+    };
+    members
+        .iter()
+        .map(|member| match member {
+            Value::Deferred(DeferredValue::BindingRef { binding }) => {
+                Ok(Value::resource_ref(binding.clone(), attribute, vec![]))
+            }
+            other => Err(format!(
+                "{func}() expects a for-expression binding, got a list containing {}",
+                value_type_name(other)
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|refs| Value::Concrete(ConcreteValue::List(refs)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builtins::evaluate_builtin_to_value as evaluate_builtin;
+    use crate::resource::{ConcreteValue, DeferredValue, UnknownReason, Value};
+
+    fn group(members: &[&str]) -> Value {
+        Value::Concrete(ConcreteValue::List(
+            members
+                .iter()
+                .map(|m| {
+                    Value::Deferred(DeferredValue::BindingRef {
+                        binding: m.to_string(),
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn attrs_references_each_member_in_order() {
+        let args = vec![
+            group(&["subnets[0]", "subnets[1]"]),
+            Value::Concrete(ConcreteValue::String("subnet_id".to_string())),
+        ];
+        assert_eq!(
+            evaluate_builtin("attrs", &args).unwrap(),
+            Value::Concrete(ConcreteValue::List(vec![
+                Value::resource_ref("subnets[0]", "subnet_id", vec![]),
+                Value::resource_ref("subnets[1]", "subnet_id", vec![]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn ids_is_attrs_of_id() {
+        assert_eq!(
+            evaluate_builtin("ids", &[group(&["vpcs.dev"])]).unwrap(),
+            Value::Concrete(ConcreteValue::List(vec![Value::resource_ref(
+                "vpcs.dev",
+                "id",
+                vec![]
+            )]))
+        );
+    }
+
+    #[test]
+    fn deferred_group_stays_unknown() {
+        let group = Value::Deferred(DeferredValue::Unknown(UnknownReason::ForGroup {
+            binding: "subnets".to_string(),
+        }));
+        let result = evaluate_builtin("ids", std::slice::from_ref(&group)).unwrap();
+        assert!(matches!(
+            result,
+            Value::Deferred(DeferredValue::Unknown(UnknownReason::ForGroup { ref binding }))
+                if binding == "subnets"
+        ));
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_resource_groups() {
+        let err = evaluate_builtin(
+            "ids",
+            &[Value::Concrete(ConcreteValue::List(vec![Value::Concrete(
+                ConcreteValue::String("subnet-1".to_string()),
+            )]))],
+        )
+        .unwrap_err();
+        assert!(err.contains("list containing String"), "{err}");
+
+        let err = evaluate_builtin(
+            "attrs",
+            &[
+                Value::Concrete(ConcreteValue::String("subnets".to_string())),
+                Value::Concrete(ConcreteValue::String("subnet_id".to_string())),
+            ],
+        )
+        .unwrap_err();
+        assert!(err.contains("must be a for-expression binding"), "{err}");
+    }
+}
@@ -1,5 +1,7 @@
 //! `keys(map)` and `values(map)` built-in functions
 
+use indexmap::IndexMap;
+
 use crate::resource::{ConcreteValue, Value};
 
 use super::value_type_name;
@@ -66,6 +68,48 @@ pub(crate) fn builtin_values(args: &[Value]) -> Result<Value, String> {
     }
 }
 
+/// `keyed(list)` - Turn a list of strings into a map keyed by each element.
+///
+/// - Single argument: a List of String
+/// - Returns: Map whose keys and values are the list elements
+///
+/// Iterating the result with `for k, v in ...` addresses each instance by
+/// its key instead of its position, so adding or removing an element
+/// leaves the other instances' addresses untouched.
+///
+/// Examples:
+/// ```text
+/// keyed(["a", "b"])  // => {a: "a", b: "b"}
+/// keyed([])          // => {}
+/// ```
+pub(crate) fn builtin_keyed(args: &[Value]) -> Result<Value, String> {
+    if args.len() != 1 {
+        return Err(format!("keyed() expects 1 argument, got {}", args.len()));
+    }
+
+    match &args[0] {
+        Value::Concrete(ConcreteValue::List(items)) => {
+            let mut map = IndexMap::new();
+            for item in items {
+                let Value::Concrete(ConcreteValue::String(key)) = item else {
+                    return Err(format!(
+                        "keyed() list elements must be Strings, got {}",
+                        value_type_name(item)
+                    ));
+                };
+                if map.insert(key.clone(), item.clone()).is_some() {
+                    return Err(format!("keyed() list contains duplicate element '{}'", key));
+                }
+            }
+            Ok(Value::Concrete(ConcreteValue::Map(map)))
+        }
+        other => Err(format!(
+            "keyed() argument must be a List, got {}",
+            value_type_name(other)
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use indexmap::IndexMap;
@@ -214,4 +258,41 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("must be a Map"));
     }
+
+    #[test]
+    fn keyed_uses_each_element_as_its_key() {
+        let list = |items: &[&str]| {
+            Value::Concrete(ConcreteValue::List(
+                items
+                    .iter()
+                    .map(|s| Value::Concrete(ConcreteValue::String(s.to_string())))
+                    .collect(),
+            ))
+        };
+        let result = evaluate_builtin("keyed", &[list(&["b", "a"])]).unwrap();
+        assert_eq!(
+            result,
+            Value::Concrete(ConcreteValue::Map(IndexMap::from([
+                (
+                    "b".to_string(),
+                    Value::Concrete(ConcreteValue::String("b".to_string()))
+                ),
+                (
+                    "a".to_string(),
+                    Value::Concrete(ConcreteValue::String("a".to_string()))
+                ),
+            ])))
+        );
+
+        let err = evaluate_builtin("keyed", &[list(&["a", "a"])]).unwrap_err();
+        assert!(err.contains("duplicate element 'a'"), "{err}");
+        let err = evaluate_builtin(
+            "keyed",
+            &[Value::Concrete(ConcreteValue::List(vec![Value::Concrete(
+                ConcreteValue::Int(1),
+            )]))],
+        )
+        .unwrap_err();
+        assert!(err.contains("must be Strings"), "{err}");
+    }
 }
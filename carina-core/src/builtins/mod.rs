@@ -3,11 +3,16 @@
 //! Provides a registry of built-in functions that can be called from DSL expressions.
 //! Functions take `&[Value]` arguments and return `Result<Value, String>`.
 
+mod cidr_host;
 mod cidr_subnet;
+mod coalesce;
 mod concat;
 pub mod decrypt;
+mod encode;
 mod env;
 mod flatten;
+mod format;
+mod ids_attrs;
 mod join;
 mod keys_values;
 mod length;
@@ -144,7 +149,12 @@ macro_rules! register_builtins {
                                 })
                             })
                             .collect();
-                        $handler(&lowered?).map(EvalValue::from_value)
+                        // Secret arguments taint the result: the handler
+                        // sees plain values and its output is re-wrapped.
+                        let (lowered, tainted) = secret::reveal_args(lowered?);
+                        $handler(&lowered)
+                            .map(|result| if tainted { secret::taint(result) } else { result })
+                            .map(EvalValue::from_value)
                     }
                 )*
                 _ => Err(format!("Unknown built-in function: {name}")),
@@ -172,11 +182,31 @@ macro_rules! register_builtins {
 }
 
 register_builtins! {
+    attrs(ids_attrs::builtin_attrs, arity: 2) {
+        signature: "attrs(resources: for-binding, name: String) -> list",
+        description: "References one attribute of every resource in a for-expression binding, in iteration order.",
+        return_type: BuiltinReturnType::List,
+    },
+    base64_encode(encode::builtin_base64_encode, arity: 1) {
+        signature: "base64_encode(string: String) -> String",
+        description: "Encodes a string's UTF-8 bytes as standard base64.",
+        return_type: BuiltinReturnType::String,
+    },
+    cidr_host(cidr_host::builtin_cidr_host, arity: 2) {
+        signature: "cidr_host(prefix: String, hostnum: Int) -> String",
+        description: "Calculates a host IP address within a network prefix. Negative host numbers count back from the end of the range.",
+        return_type: BuiltinReturnType::String,
+    },
     cidr_subnet(cidr_subnet::builtin_cidr_subnet, arity: 3) {
         signature: "cidr_subnet(prefix: String, newbits: Int, netnum: Int) -> String",
         description: "Calculates a subnet CIDR block within a given IP network address prefix.",
         return_type: BuiltinReturnType::String,
     },
+    coalesce(coalesce::builtin_coalesce, arity: 1) {
+        signature: "coalesce(values: list) -> Any",
+        description: "Returns the first value in the list that is not an empty string, list or map.",
+        return_type: BuiltinReturnType::Any,
+    },
     concat(concat::builtin_concat, arity: 2) {
         signature: "concat(items: list, base_list: list) -> list",
         description: "Appends items to a list. Data-last: base_list |> concat(items).",
@@ -197,11 +227,31 @@ register_builtins! {
         description: "Flattens nested lists by one level.",
         return_type: BuiltinReturnType::List,
     },
+    format(format::builtin_format, arity: 2) {
+        signature: "format(template: String, values: list) -> String",
+        description: "Substitutes values into a template: %s inserts the next value, %d the next Int, %% a literal %. Data-last: values |> format(template).",
+        return_type: BuiltinReturnType::String,
+    },
+    ids(ids_attrs::builtin_ids, arity: 1) {
+        signature: "ids(resources: for-binding) -> list",
+        description: "References the `id` attribute of every resource in a for-expression binding. Shorthand for attrs(resources, 'id').",
+        return_type: BuiltinReturnType::List,
+    },
     join(join::builtin_join, arity: 2) {
         signature: "join(separator: String, list: list) -> String",
         description: "Joins list elements into a string using the separator.",
         return_type: BuiltinReturnType::String,
     },
+    json_encode(encode::builtin_json_encode, arity: 1) {
+        signature: "json_encode(value: Any) -> String",
+        description: "Renders a value as compact JSON with sorted map keys.",
+        return_type: BuiltinReturnType::String,
+    },
+    keyed(keys_values::builtin_keyed, arity: 1) {
+        signature: "keyed(list: list) -> map",
+        description: "Turns a list of strings into a map keyed by each element, so `for k, v in keyed(list)` addresses instances by value instead of position.",
+        return_type: BuiltinReturnType::Map,
+    },
     keys(keys_values::builtin_keys, arity: 1) {
         signature: "keys(map: map) -> list",
         description: "Returns the keys of a map as a sorted list.",
@@ -373,6 +423,16 @@ pub(crate) fn evaluate_builtin_with_config(
     }
 }
 
+/// Built-ins that only turn a `for`-expression binding into attribute
+/// references. The parser evaluates them eagerly even though their
+/// arguments are references, so the references they return take part in
+/// dependency ordering and resolution like hand-written ones.
+pub(crate) fn expands_at_parse_time(name: &str) -> bool {
+    matches!(name, "attrs" | "ids")
+}
+
+pub(crate) use ids_attrs::deferred_for_group;
+
 /// Check if a function name is a known built-in function.
 pub fn is_known_builtin(name: &str) -> bool {
     builtin_functions().iter().any(|f| f.name == name)
@@ -419,6 +479,7 @@ fn value_type_name(value: &Value) -> &'static str {
         Value::Concrete(ConcreteValue::Float(_)) => "Float",
         Value::Concrete(ConcreteValue::Bool(_)) => "Bool",
         Value::Concrete(ConcreteValue::Duration(_)) => "Duration",
+        Value::Concrete(ConcreteValue::ByteSize(_)) => "ByteSize",
         Value::Concrete(ConcreteValue::List(_)) => "List",
         Value::Concrete(ConcreteValue::StringList(_)) => "StringList",
         Value::Concrete(ConcreteValue::Map(_)) => "Map",
@@ -1,6 +1,6 @@
 //! `secret(value)` built-in function
 
-use crate::resource::{DeferredValue, Value};
+use crate::resource::{ConcreteValue, DeferredValue, Value};
 
 /// `secret(value)` - Mark a value as secret.
 ///
@@ -22,6 +22,45 @@ pub(crate) fn builtin_secret(args: &[Value]) -> Result<Value, String> {
     ))))
 }
 
+/// Strip every `Secret` wrapper from `args`, reporting whether any was
+/// found. Built-ins then compute on plain values and the caller wraps
+/// the result with [`taint`], so a secret never leaks into an ordinary
+/// value through a function call.
+pub(crate) fn reveal_args(args: Vec<Value>) -> (Vec<Value>, bool) {
+    let mut tainted = false;
+    let args = args
+        .into_iter()
+        .map(|arg| reveal(arg, &mut tainted))
+        .collect();
+    (args, tainted)
+}
+
+fn reveal(value: Value, tainted: &mut bool) -> Value {
+    match value {
+        Value::Deferred(DeferredValue::Secret(inner)) => {
+            *tainted = true;
+            reveal(*inner, tainted)
+        }
+        Value::Concrete(ConcreteValue::List(items)) => Value::Concrete(ConcreteValue::List(
+            items.into_iter().map(|v| reveal(v, tainted)).collect(),
+        )),
+        Value::Concrete(ConcreteValue::Map(map)) => Value::Concrete(ConcreteValue::Map(
+            map.into_iter()
+                .map(|(k, v)| (k, reveal(v, tainted)))
+                .collect(),
+        )),
+        other => other,
+    }
+}
+
+/// Mark a result computed from secret inputs as secret.
+pub(crate) fn taint(value: Value) -> Value {
+    match value {
+        Value::Deferred(DeferredValue::Secret(_)) => value,
+        other => Value::Deferred(DeferredValue::Secret(Box::new(other))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::builtins::evaluate_builtin_to_value as evaluate_builtin;
@@ -71,4 +110,43 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("expects 1 argument"));
     }
+
+    #[test]
+    fn secret_arguments_taint_the_result() {
+        let password = Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+            ConcreteValue::String("hunter2".to_string()),
+        ))));
+        let parts = Value::Concrete(ConcreteValue::List(vec![
+            Value::Concrete(ConcreteValue::String("admin".to_string())),
+            password.clone(),
+        ]));
+        let joined = evaluate_builtin(
+            "join",
+            &[
+                Value::Concrete(ConcreteValue::String(":".to_string())),
+                parts,
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            joined,
+            Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+                ConcreteValue::String("admin:hunter2".to_string())
+            ))))
+        );
+
+        let upper = evaluate_builtin("upper", std::slice::from_ref(&password)).unwrap();
+        assert_eq!(
+            upper,
+            Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+                ConcreteValue::String("HUNTER2".to_string())
+            ))))
+        );
+
+        // Re-wrapping an already secret value does not nest.
+        assert_eq!(
+            evaluate_builtin("secret", std::slice::from_ref(&password)).unwrap(),
+            password
+        );
+    }
 }
@@ -7,7 +7,7 @@ use std::path::{Path, PathBuf};
 use indexmap::IndexMap;
 
 use crate::parser::{self, File, InferredFile, ParsedFile, ProviderContext};
-use crate::resource::Value;
+use crate::resource::{ConcreteValue, DeferredValue, InterpolationPart, Value};
 use crate::schema::SchemaRegistry;
 use crate::validation::inference::InferenceError;
 
@@ -145,11 +145,24 @@ pub fn load_configuration_with_config(
             // unresolved copy only ever stored a single backend, so we
             // strip it before merging to avoid accidentally overwriting
             // `unresolved_merged.backend`.
+            // `naming` is handled the same way.
             unresolved.backend = None;
+            unresolved.naming = None;
             merge_parsed_file(&mut unresolved_merged, unresolved);
 
             let backend = parsed.backend.take();
+            let naming = parsed.naming.take();
             merge_parsed_file(&mut merged, parsed);
+            if let Some(naming) = naming {
+                if merged.naming.is_some() {
+                    parse_errors.push(format!(
+                        "{}: multiple naming blocks defined",
+                        file.display()
+                    ));
+                } else {
+                    merged.naming = Some(naming);
+                }
+            }
             if let Some(backend) = backend {
                 if merged.backend.is_some() {
                     parse_errors.push(format!(
@@ -359,8 +372,10 @@ pub(crate) fn merge_parsed_file<E>(target: &mut File<E>, source: File<E>) {
         module_calls,
         arguments,
         attribute_params,
+        variable_params,
         export_params,
         backend,
+        naming,
         state_blocks,
         user_functions,
         upstream_states,
@@ -381,6 +396,7 @@ pub(crate) fn merge_parsed_file<E>(target: &mut File<E>, source: File<E>) {
     target.module_calls.extend(module_calls);
     target.arguments.extend(arguments);
     target.attribute_params.extend(attribute_params);
+    target.variable_params.extend(variable_params);
     target.export_params.extend(export_params);
     target.state_blocks.extend(state_blocks);
     target.user_functions.extend(user_functions);
@@ -399,10 +415,14 @@ pub(crate) fn merge_parsed_file<E>(target: &mut File<E>, source: File<E>) {
     for (leaf, call_sites) in expansion_trace.leaf_to_call_sites {
         target.expansion_trace.record(leaf, call_sites);
     }
-    // `backend` is config, not accumulated content: last file wins.
+    // `backend` and `naming` are config, not accumulated content: last
+    // file wins.
     if let Some(backend) = backend {
         target.backend = Some(backend);
     }
+    if let Some(naming) = naming {
+        target.naming = Some(naming);
+    }
 }
 
 /// Parse every `(path, source)` pair as part of a single directory unit.
@@ -462,7 +482,7 @@ pub fn parse_directory_files(
             parse_local_variable_union(files, config, &seeds, &pass_1a_local_variables)?;
         parser::reject_cyclic_let_bindings_in_variables(&variables_next, &structural)?;
 
-        if variables_next == variables_current {
+        if same_let_values(&variables_next, &variables_current) {
             converged = true;
             break;
         }
@@ -495,6 +515,55 @@ pub fn parse_directory_files(
     Ok(out)
 }
 
+/// Fixed-point check for the let-value loop. `Value`'s `PartialEq` never
+/// equates two unknowns, but a let that stays unknown between rounds (a
+/// deferred for-expression group) has settled all the same, so unknowns
+/// compare by reason here.
+fn same_let_values(a: &IndexMap<String, Value>, b: &IndexMap<String, Value>) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((ka, va), (kb, vb))| ka == kb && same_let_value(va, vb))
+}
+
+fn same_let_value(a: &Value, b: &Value) -> bool {
+    let same_all = |a: &[Value], b: &[Value]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| same_let_value(x, y))
+    };
+    match (a, b) {
+        (
+            Value::Deferred(DeferredValue::Unknown(ra)),
+            Value::Deferred(DeferredValue::Unknown(rb)),
+        ) => ra == rb,
+        (Value::Concrete(ConcreteValue::List(xs)), Value::Concrete(ConcreteValue::List(ys))) => {
+            same_all(xs, ys)
+        }
+        (Value::Concrete(ConcreteValue::Map(xs)), Value::Concrete(ConcreteValue::Map(ys))) => {
+            same_let_values(xs, ys)
+        }
+        (
+            Value::Deferred(DeferredValue::FunctionCall { name: na, args: xs }),
+            Value::Deferred(DeferredValue::FunctionCall { name: nb, args: ys }),
+        ) => na == nb && same_all(xs, ys),
+        (Value::Deferred(DeferredValue::Secret(x)), Value::Deferred(DeferredValue::Secret(y))) => {
+            same_let_value(x, y)
+        }
+        (
+            Value::Deferred(DeferredValue::Interpolation(xs)),
+            Value::Deferred(DeferredValue::Interpolation(ys)),
+        ) => {
+            xs.len() == ys.len()
+                && xs.iter().zip(ys).all(|pair| match pair {
+                    (InterpolationPart::Expr(x), InterpolationPart::Expr(y)) => {
+                        same_let_value(x, y)
+                    }
+                    (x, y) => x == y,
+                })
+        }
+        _ => a == b,
+    }
+}
+
 fn local_variable_names(parsed: &ParsedFile) -> HashSet<String> {
     parsed.variables.keys().cloned().collect()
 }
@@ -587,6 +656,21 @@ pub fn find_crn_files_in_dir(dir: &Path) -> Result<Vec<PathBuf>, String> {
     Ok(files)
 }
 
+/// Read the variable values in a `--var-file` (`*.crnvars`), ready for
+/// [`bind_root_variables`](crate::module_resolver::bind_root_variables).
+/// No file gives no values, so every variable takes its default.
+pub fn load_var_file(
+    path: Option<&Path>,
+    config: &ProviderContext,
+) -> Result<IndexMap<String, Value>, String> {
+    let Some(path) = path else {
+        return Ok(IndexMap::new());
+    };
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parser::parse_var_file(&content, config).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
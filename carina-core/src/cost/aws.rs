@@ -0,0 +1,162 @@
+//! Bundled AWS list prices, the fallback for providers that do not price
+//! their own resources.
+//!
+//! WASM providers cannot answer [`Provider::monthly_cost`]: the plugin
+//! interface has no export for it. [`AwsListPrices`] prices the `aws` and
+//! `awscc` resources routed to such a provider from the snapshot in
+//! [`carina_provider_protocol::pricing`], for the region configured on
+//! the provider instance each resource routes to.
+//!
+//! [`Provider::monthly_cost`]: crate::provider::Provider::monthly_cost
+
+use std::collections::HashMap;
+
+use carina_provider_protocol::pricing::aws_list_price;
+
+use super::MonthlyCost;
+use crate::parser::ProviderConfig;
+use crate::resource::{ConcreteValueRef, ResourceId, Value};
+
+/// Provider kinds whose resource types follow the CloudFormation-derived
+/// naming the snapshot is keyed on.
+const AWS_PROVIDERS: [&str; 2] = ["aws", "awscc"];
+
+/// The region of every `aws` / `awscc` provider instance.
+#[derive(Debug, Clone, Default)]
+pub struct AwsListPrices {
+    /// Region keyed by `(kind, binding)`, as the router keys instances.
+    regions: HashMap<(String, Option<String>), String>,
+}
+
+impl AwsListPrices {
+    /// List prices for every AWS instance in `providers` that sets a
+    /// `region`; `None` when none does.
+    pub fn from_configs(providers: &[ProviderConfig]) -> Option<Self> {
+        let regions: HashMap<_, _> = providers
+            .iter()
+            .filter(|p| AWS_PROVIDERS.contains(&p.name.as_str()))
+            .filter_map(|p| {
+                let region = region_name(p.attributes.get("region")?)?;
+                Some(((p.name.clone(), p.binding.clone()), region))
+            })
+            .collect();
+        (!regions.is_empty()).then_some(Self { regions })
+    }
+
+    /// List price of resource `id` with `attributes` in the region of
+    /// its provider instance, or `None` when the instance has no region
+    /// or the type is not priced there.
+    pub fn monthly_cost(
+        &self,
+        id: &ResourceId,
+        attributes: &HashMap<String, Value>,
+    ) -> Option<MonthlyCost> {
+        let region = self
+            .regions
+            .get(&(id.provider.clone(), id.provider_instance.clone()))?;
+        let cost = aws_list_price(
+            region,
+            &id.resource_type,
+            enum_text(attributes.get("vpc_endpoint_type")),
+            subnet_count(attributes.get("subnet_ids")),
+        )?;
+        Some(MonthlyCost {
+            usd: cost.usd,
+            basis: cost.basis,
+        })
+    }
+}
+
+/// The region code of a provider's `region` attribute, whether written
+/// as `"ap-northeast-1"` or as `aws.Region.ap_northeast_1`.
+fn region_name(value: &Value) -> Option<String> {
+    match value.as_concrete()? {
+        ConcreteValueRef::CanonicalEnum(e) => Some(e.api_value().to_string()),
+        other => {
+            let text = other.as_string_like()?;
+            Some(text.rsplit('.').next().unwrap_or(text).replace('_', "-"))
+        }
+    }
+}
+
+/// The variant name of an enum-typed attribute, whether written as a
+/// string, a namespaced identifier or a schema-resolved enum.
+fn enum_text(value: Option<&Value>) -> Option<&str> {
+    match value?.as_concrete()? {
+        ConcreteValueRef::CanonicalEnum(e) => Some(e.api_value()),
+        other => other
+            .as_string_like()
+            .map(|s| s.rsplit('.').next().unwrap_or(s)),
+    }
+}
+
+/// Endpoints are billed per subnet (one per AZ). A list not yet known at
+/// plan time counts as one.
+fn subnet_count(value: Option<&Value>) -> usize {
+    match value.and_then(Value::as_concrete) {
+        Some(ConcreteValueRef::List(items)) => items.len(),
+        Some(ConcreteValueRef::StringList(items)) => items.len(),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::ConcreteValue;
+    use indexmap::IndexMap;
+
+    fn config(name: &str, binding: Option<&str>, region: Option<Value>) -> ProviderConfig {
+        ProviderConfig {
+            name: name.to_string(),
+            attributes: region
+                .map(|region| IndexMap::from([("region".to_string(), region)]))
+                .unwrap_or_default(),
+            default_tags: IndexMap::new(),
+            provenance_tags: false,
+            source: None,
+            version: None,
+            revision: None,
+            unresolved_attributes: IndexMap::new(),
+            binding: binding.map(str::to_string),
+            is_default: binding.is_none(),
+        }
+    }
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    #[test]
+    fn each_instance_is_priced_for_its_own_region() {
+        let prices = AwsListPrices::from_configs(&[
+            config("awscc", None, Some(string("us-east-1"))),
+            config(
+                "awscc",
+                Some("tokyo"),
+                Some(Value::Concrete(ConcreteValue::enum_identifier(
+                    "awscc.Region.ap_northeast_1",
+                ))),
+            ),
+        ])
+        .unwrap();
+        let default = ResourceId::with_provider_identity("awscc", "ec2.NatGateway", "nat", None);
+        let mut tokyo = default.clone();
+        tokyo.provider_instance = Some("tokyo".to_string());
+
+        let virginia = prices.monthly_cost(&default, &HashMap::new()).unwrap();
+        assert!((virginia.usd - 32.85).abs() < 1e-9);
+        let tokyo = prices.monthly_cost(&tokyo, &HashMap::new()).unwrap();
+        assert!((tokyo.usd - 45.26).abs() < 1e-9);
+        assert!(tokyo.basis.contains("ap-northeast-1"));
+    }
+
+    #[test]
+    fn other_providers_and_unset_regions_are_not_priced() {
+        assert!(
+            AwsListPrices::from_configs(&[config("mock", None, Some(string("us-east-1")))])
+                .is_none()
+        );
+        assert!(AwsListPrices::from_configs(&[config("aws", None, None)]).is_none());
+    }
+}
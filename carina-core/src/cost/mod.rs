@@ -0,0 +1,273 @@
+//! Monthly cost estimation for plans.
+//!
+//! Once a plan is computed, [`CostSummary::for_plan`] runs it past a
+//! [`CostEstimator`] and records, for every priced resource the plan
+//! touches, the estimated monthly cost before and after. Prices come
+//! from the providers through [`Provider::monthly_cost`], so each is
+//! priced for its provider's own configuration (region, partition).
+//! Resources whose provider returns `None` fall back to the bundled
+//! [`AwsListPrices`] when the router has them. Only the fixed recurring
+//! charges a provider models are counted.
+//!
+//! [`Provider::monthly_cost`]: crate::provider::Provider::monthly_cost
+
+mod aws;
+
+pub use aws::AwsListPrices;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::effect::Effect;
+use crate::plan::Plan;
+use crate::provider::{BoxFuture, Provider, ProviderRouter};
+use crate::resource::{ResourceId, Value};
+
+/// A resource's estimated recurring cost.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonthlyCost {
+    pub usd: f64,
+    /// How the figure was derived, e.g. `730 h × $0.045/h`.
+    pub basis: String,
+}
+
+/// Prices resources from their planned attributes.
+pub trait CostEstimator: Send + Sync {
+    /// Monthly cost of resource `id` with `attributes`, or `None` when
+    /// this estimator does not price the type.
+    fn monthly_cost<'a>(
+        &'a self,
+        id: &'a ResourceId,
+        attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>>;
+}
+
+/// Each resource is priced by the provider instance it routes to, or
+/// from the router's list prices when that provider does not price it.
+impl CostEstimator for ProviderRouter {
+    fn monthly_cost<'a>(
+        &'a self,
+        id: &'a ResourceId,
+        attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>> {
+        Box::pin(async move {
+            match Provider::monthly_cost(self, id, attributes).await {
+                Some(cost) => Some(cost),
+                None => self.list_prices()?.monthly_cost(id, attributes),
+            }
+        })
+    }
+}
+
+/// Estimated monthly cost of one resource before and after the plan.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostChange {
+    pub resource: String,
+    pub before_usd: f64,
+    pub after_usd: f64,
+    pub basis: String,
+}
+
+impl CostChange {
+    pub fn delta_usd(&self) -> f64 {
+        self.after_usd - self.before_usd
+    }
+}
+
+/// Cost delta of a plan, one entry per priced resource whose cost moves.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CostSummary {
+    pub changes: Vec<CostChange>,
+}
+
+impl CostSummary {
+    /// Estimate the cost delta of `plan`.
+    ///
+    /// Creates add their cost, deletes subtract it (priced from the
+    /// attributes in `delete_attributes`, keyed like
+    /// [`Effect::deleted_resource_attributes_ids`]) and updates count the
+    /// difference between the old and new attributes. A replacement's
+    /// delete and create fold into one entry. Resources materialized only
+    /// at apply time (deferred creates and replacements) are not priced.
+    pub async fn for_plan(
+        plan: &Plan,
+        delete_attributes: &HashMap<ResourceId, HashMap<String, Value>>,
+        estimator: &dyn CostEstimator,
+    ) -> Self {
+        let no_attributes = HashMap::new();
+        let mut changes: BTreeMap<String, CostChange> = BTreeMap::new();
+        let mut record =
+            |id: &ResourceId, before: Option<MonthlyCost>, after: Option<MonthlyCost>| {
+                if before.is_none() && after.is_none() {
+                    return;
+                }
+                let entry = changes
+                    .entry(id.human().to_string())
+                    .or_insert_with(|| CostChange {
+                        resource: id.human().to_string(),
+                        before_usd: 0.0,
+                        after_usd: 0.0,
+                        basis: String::new(),
+                    });
+                if let Some(before) = before {
+                    entry.before_usd += before.usd;
+                    if entry.basis.is_empty() {
+                        entry.basis = before.basis;
+                    }
+                }
+                if let Some(after) = after {
+                    entry.after_usd += after.usd;
+                    entry.basis = after.basis;
+                }
+            };
+
+        for effect in plan.effects() {
+            match effect {
+                Effect::Create(resource) => {
+                    let after = estimator
+                        .monthly_cost(&resource.id, &resource.resolved_attributes())
+                        .await;
+                    record(&resource.id, None, after);
+                }
+                Effect::Update { from, to, .. } => {
+                    let before = estimator.monthly_cost(&from.id, &from.attributes).await;
+                    let after = estimator
+                        .monthly_cost(&to.id, &to.resolved_attributes())
+                        .await;
+                    record(&to.id, before, after);
+                }
+                Effect::Delete { id, .. } => {
+                    let attributes = delete_attributes.get(&**id).unwrap_or(&no_attributes);
+                    record(id, estimator.monthly_cost(id, attributes).await, None);
+                }
+                Effect::Read { .. }
+                | Effect::Import { .. }
+                | Effect::Remove { .. }
+                | Effect::Move { .. }
+                | Effect::Wait { .. }
+                | Effect::DeferredCreate { .. }
+                | Effect::DeferredReplace(_) => {}
+            }
+        }
+
+        Self {
+            changes: changes
+                .into_values()
+                .filter(|change| change.delta_usd().abs() >= 0.005)
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Net change in estimated monthly cost.
+    pub fn monthly_delta_usd(&self) -> f64 {
+        self.changes.iter().map(CostChange::delta_usd).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{
+        ConcreteValue, Directives, ResolvedResource, ResolvedResourceId, Resource, State,
+    };
+    use std::collections::HashSet;
+
+    /// Prices every `test.Priced` resource at its `size` attribute.
+    struct SizeEstimator;
+
+    impl CostEstimator for SizeEstimator {
+        fn monthly_cost<'a>(
+            &'a self,
+            id: &'a ResourceId,
+            attributes: &'a HashMap<String, Value>,
+        ) -> BoxFuture<'a, Option<MonthlyCost>> {
+            Box::pin(async move {
+                if id.resource_type != "Priced" {
+                    return None;
+                }
+                let usd = match attributes.get("size") {
+                    Some(Value::Concrete(ConcreteValue::Int(n))) => *n as f64,
+                    _ => 1.0,
+                };
+                Some(MonthlyCost {
+                    usd,
+                    basis: format!("size {usd}"),
+                })
+            })
+        }
+    }
+
+    fn priced(name: &str, size: i64) -> Resource {
+        Resource::with_provider("test", "Priced", name, None)
+            .with_attribute("size", Value::Concrete(ConcreteValue::Int(size)))
+    }
+
+    fn delete(id: &ResourceId) -> Effect {
+        Effect::Delete {
+            id: ResolvedResourceId::new(id.clone()),
+            identifier: "id-1".to_string(),
+            directives: Directives::default(),
+            binding: None,
+            dependencies: HashSet::new(),
+            explicit_dependencies: HashSet::new(),
+            blocked_by_updates: HashSet::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn creates_deletes_and_updates_are_priced() {
+        let old = priced("old", 4);
+        let resized = priced("resized", 10);
+        let mut plan = Plan::new();
+        plan.add(Effect::Create(ResolvedResource::new(priced("new", 3))));
+        plan.add(Effect::Create(ResolvedResource::new(Resource::new(
+            "test.Free",
+            "free",
+        ))));
+        plan.add(delete(&old.id));
+        plan.add(Effect::Update {
+            from: Box::new(State::existing(
+                resized.id.clone(),
+                priced("resized", 2).resolved_attributes(),
+            )),
+            to: ResolvedResource::new(resized),
+            changed_attributes: vec!["size".to_string()],
+        });
+        let delete_attributes = HashMap::from([(old.id.clone(), old.resolved_attributes())]);
+
+        let summary = CostSummary::for_plan(&plan, &delete_attributes, &SizeEstimator).await;
+
+        let deltas: Vec<(&str, f64)> = summary
+            .changes
+            .iter()
+            .map(|c| (c.resource.as_str(), c.delta_usd()))
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![
+                ("test.Priced new", 3.0),
+                ("test.Priced old", -4.0),
+                ("test.Priced resized", 8.0),
+            ]
+        );
+        assert_eq!(summary.monthly_delta_usd(), 7.0);
+    }
+
+    #[tokio::test]
+    async fn replacement_at_the_same_price_nets_out() {
+        let resource = priced("nat", 5);
+        let mut plan = Plan::new();
+        plan.add(delete(&resource.id));
+        plan.add(Effect::Create(ResolvedResource::new(resource.clone())));
+        let delete_attributes =
+            HashMap::from([(resource.id.clone(), resource.resolved_attributes())]);
+
+        let summary = CostSummary::for_plan(&plan, &delete_attributes, &SizeEstimator).await;
+        assert!(summary.is_empty());
+    }
+}
@@ -150,9 +150,13 @@ fn topological_sort(resources: &[Resource]) -> Result<Vec<Resource>, String> {
         visiting: &mut Vec<String>,
         sorted: &mut Vec<Resource>,
     ) -> Result<(), String> {
+        // Unbound resources are keyed by provider too, so same-named
+        // resources of one type in two providers (`aws` / `awscc`) stay
+        // distinct.
         let binding_name = resource.binding.clone().unwrap_or_else(|| {
             format!(
-                "{}:{}",
+                "{}.{}:{}",
+                resource.id.provider,
                 resource.id.resource_type,
                 resource.id.identity_or_empty()
             )
@@ -250,6 +254,26 @@ mod tests {
         assert_eq!(deps.len(), 2);
     }
 
+    #[test]
+    fn sort_orders_references_across_providers() {
+        let mut vpc = Resource::with_provider("awscc", "ec2.Vpc", "main", None);
+        vpc.binding = Some("vpc".to_string());
+        let mut subnet = Resource::with_provider("aws", "ec2.Subnet", "web", None);
+        subnet.set_attr("vpc_id", Value::resource_ref("vpc", "vpc_id", vec![]));
+        // Same type and name as `subnet`, but in the other provider.
+        let twin = Resource::with_provider("awscc", "ec2.Subnet", "web", None);
+
+        let sorted = sort_resources_by_dependencies(&[subnet, twin, vpc]).unwrap();
+        let order: Vec<String> = sorted
+            .iter()
+            .map(|r| format!("{}.{}", r.id.provider, r.id.resource_type))
+            .collect();
+        assert_eq!(
+            order,
+            ["awscc.ec2.Vpc", "aws.ec2.Subnet", "awscc.ec2.Subnet"]
+        );
+    }
+
     #[test]
     fn test_get_composition_dependencies_collects_attrs_and_deps() {
         use crate::resource::{Composition, Signature};
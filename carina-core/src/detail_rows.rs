@@ -302,6 +302,14 @@ pub enum ListOfMapsDiffField {
         added: Vec<String>,
         removed: Vec<String>,
     },
+    /// Field value is itself a list of maps that changed (S3 lifecycle
+    /// `rules[].transitions`) — show element-level diffs of the inner
+    /// list instead of both whole lists on one line. Unchanged inner
+    /// elements are dropped, as in `MapDiffEntryIR::NestedListOfMapsDiff`.
+    NestedListOfMapsChanged {
+        key: String,
+        block: NonEmptyListOfMapsBlock,
+    },
 }
 
 /// Resolve the subtype for map entry `key`: a `Map`'s `value` type, a
@@ -488,11 +496,18 @@ fn build_create_rows(
                 }
                 _ => None,
             };
+            // Unit-annotated integers show the API unit, since the
+            // value is the converted integer rather than the literal
+            // the user wrote (`12h` renders as `43200  # seconds`).
+            let annotation = schema
+                .and_then(|s| s.attributes.get(key.as_str()))
+                .and_then(|attr| attr.unit)
+                .map(|unit| format!("# {unit}"));
             rows.push(DetailRow::Attribute {
                 key: key.to_string(),
                 value: format_value_with_key(value, Some(key)),
                 ref_binding,
-                annotation: None,
+                annotation,
             });
         }
     }
@@ -803,10 +818,8 @@ fn build_attribute_diff_rows(
         } else {
             ctx.from_attrs_projected.get(key)
         };
-        let attr_type = ctx
-            .schema
-            .and_then(|s| s.attributes.get(key.as_str()))
-            .map(|a| &a.attr_type);
+        let attr_schema = ctx.schema.and_then(|s| s.attributes.get(key.as_str()));
+        let attr_type = attr_schema.map(|a| &a.attr_type);
         // Sites 1/2 (carina#3073): schema-aware top-level equality.
         // When the only diffs inside this attribute are enum-equal
         // leaves, `type_aware_equal` recurses Struct/List/Map
@@ -841,6 +854,7 @@ fn build_attribute_diff_rows(
                 old_value,
                 new_value,
                 attr_type,
+                attr_schema.and_then(|a| a.list_key.as_deref()),
                 ctx.defs,
                 ctx.detail,
                 forces_replacement,
@@ -1061,8 +1075,14 @@ fn build_replace_removed_row(
 
     if is_list_of_maps(old_value) {
         let empty_list = Value::Concrete(ConcreteValue::List(Vec::new()));
-        let (unchanged, modified, added, removed) =
-            compute_list_of_maps_diff_parts(Some(old_value), &empty_list, attr_type, defs, detail);
+        let (unchanged, modified, added, removed) = compute_list_of_maps_diff_parts(
+            Some(old_value),
+            &empty_list,
+            attr_type,
+            None,
+            defs,
+            detail,
+        );
         if let Some(block) =
             NonEmptyListOfMapsBlock::from_parts(unchanged, modified, added, removed)
         {
@@ -1092,8 +1112,14 @@ fn build_removed_attr_row(
 
     if is_list_of_maps(old_value) {
         let empty_list = Value::Concrete(ConcreteValue::List(Vec::new()));
-        let (unchanged, modified, added, removed) =
-            compute_list_of_maps_diff_parts(Some(old_value), &empty_list, attr_type, defs, detail);
+        let (unchanged, modified, added, removed) = compute_list_of_maps_diff_parts(
+            Some(old_value),
+            &empty_list,
+            attr_type,
+            None,
+            defs,
+            detail,
+        );
         if let Some(block) =
             NonEmptyListOfMapsBlock::from_parts(unchanged, modified, added, removed)
         {
@@ -1285,6 +1311,7 @@ fn compute_map_diff_entries(
                         Some(&e.old_value),
                         &e.new_value,
                         entry_type,
+                        None,
                         defs,
                         detail,
                     );
@@ -1356,17 +1383,19 @@ fn compute_map_diff_entries(
 /// dropped (#2886) — semantically the attribute has no diff to show, so
 /// the caller should treat it as effectively unchanged and let it roll
 /// into the top-level `# (n unchanged attributes hidden)` summary.
+#[allow(clippy::too_many_arguments)]
 fn build_list_of_maps_diff_row(
     key: &str,
     old_value: Option<&Value>,
     new_value: &Value,
     attr_type: Option<&AttributeType>,
+    list_key: Option<&str>,
     defs: &std::collections::BTreeMap<String, AttributeType>,
     detail: DetailLevel,
     forces_replacement: bool,
 ) -> Option<DetailRow> {
     let (unchanged, modified, added, removed) =
-        compute_list_of_maps_diff_parts(old_value, new_value, attr_type, defs, detail);
+        compute_list_of_maps_diff_parts(old_value, new_value, attr_type, list_key, defs, detail);
     let block = NonEmptyListOfMapsBlock::from_parts(unchanged, modified, added, removed)?;
     Some(list_of_maps_diff_row(
         key.to_string(),
@@ -1379,6 +1408,7 @@ fn compute_list_of_maps_diff_parts(
     old_value: Option<&Value>,
     new_value: &Value,
     attr_type: Option<&AttributeType>,
+    list_key: Option<&str>,
     defs: &std::collections::BTreeMap<String, AttributeType>,
     detail: DetailLevel,
 ) -> (
@@ -1445,19 +1475,28 @@ fn compute_list_of_maps_diff_parts(
         .map(|(i, _)| i)
         .collect();
 
-    // Phase 2: Pair unmatched items by similarity
+    // Phase 2: Pair unmatched items by their list key when the schema
+    // declares one, otherwise by similarity. Keyed items never pair
+    // across different keys, so an inserted element shows as added
+    // rather than as a change to whichever neighbour it resembles.
     let mut paired: Vec<(usize, usize)> = Vec::new();
     let mut paired_old = vec![false; unmatched_old.len()];
     let mut paired_new = vec![false; unmatched_new.len()];
 
     for (ui_new, &ni) in unmatched_new.iter().enumerate() {
+        let new_key = crate::schema::list_element_key(list_key, &new_items[ni]);
         let mut best_oi_idx = None;
         let mut best_sim = 0usize;
         for (ui_old, &oi) in unmatched_old.iter().enumerate() {
             if paired_old[ui_old] {
                 continue;
             }
-            let sim = map_similarity(&old_items[oi], &new_items[ni]);
+            let old_key = crate::schema::list_element_key(list_key, &old_items[oi]);
+            let sim = match (new_key, old_key) {
+                (Some(nk), Some(ok)) if nk.semantically_equal(ok) => usize::MAX,
+                (None, None) => map_similarity(&old_items[oi], &new_items[ni]),
+                _ => continue,
+            };
             if sim > best_sim {
                 best_sim = sim;
                 best_oi_idx = Some(ui_old);
@@ -1551,6 +1590,27 @@ fn compute_list_of_maps_diff_parts(
                         key: k.to_string(),
                         entries: nested,
                     });
+                } else if is_list_of_maps(&new_map[k]) && old_val.is_some_and(is_list_of_maps) {
+                    let (_, modified, added, removed) = compute_list_of_maps_diff_parts(
+                        old_val,
+                        &new_map[k],
+                        field_type,
+                        None,
+                        defs,
+                        detail,
+                    );
+                    // Every inner element matched or folded away: the
+                    // field is unchanged for display purposes.
+                    let Some(block) =
+                        NonEmptyListOfMapsBlock::from_parts(Vec::new(), modified, added, removed)
+                    else {
+                        unchanged_count += 1;
+                        continue;
+                    };
+                    fields.push(ListOfMapsDiffField::NestedListOfMapsChanged {
+                        key: k.to_string(),
+                        block,
+                    });
                 } else if let Some(diff) =
                     compute_string_list_change(old_val, &new_map[k], field_type)
                 {
@@ -3562,6 +3622,7 @@ mod tests {
             Some(&old_value),
             &new_value,
             None,
+            None,
             crate::schema::empty_defs_for_schema_walks(),
             DetailLevel::Full,
         );
@@ -3794,6 +3855,7 @@ mod tests {
             Some(&old_value),
             &new_value,
             None,
+            None,
             crate::schema::empty_defs_for_schema_walks(),
             DetailLevel::Full,
         );
@@ -3812,6 +3874,155 @@ mod tests {
         }
     }
 
+    /// A list-of-maps field inside a modified list-of-maps element
+    /// (S3 lifecycle `rules[].transitions`) diffs per inner element
+    /// instead of printing both whole lists on one line.
+    #[test]
+    fn list_of_maps_modified_diffs_nested_list_of_maps_field() {
+        let s = |v: &str| Value::Concrete(ConcreteValue::String(v.to_string()));
+        let transition = |days: i64, class: &str| {
+            Value::Concrete(ConcreteValue::Map(indexmap::IndexMap::from([
+                (
+                    "transition_in_days".to_string(),
+                    Value::Concrete(ConcreteValue::Int(days)),
+                ),
+                ("storage_class".to_string(), s(class)),
+            ])))
+        };
+        let rule = |transitions: Vec<Value>| {
+            Value::Concrete(ConcreteValue::List(vec![Value::Concrete(
+                ConcreteValue::Map(indexmap::IndexMap::from([
+                    ("id".to_string(), s("archive")),
+                    ("status".to_string(), s("Enabled")),
+                    (
+                        "transitions".to_string(),
+                        Value::Concrete(ConcreteValue::List(transitions)),
+                    ),
+                ])),
+            )]))
+        };
+        let old_value = rule(vec![
+            transition(30, "STANDARD_IA"),
+            transition(90, "GLACIER"),
+        ]);
+        let new_value = rule(vec![
+            transition(30, "STANDARD_IA"),
+            transition(180, "GLACIER"),
+            transition(365, "DEEP_ARCHIVE"),
+        ]);
+
+        let (_unchanged, modified, added, removed) = compute_list_of_maps_diff_parts(
+            Some(&old_value),
+            &new_value,
+            None,
+            None,
+            crate::schema::empty_defs_for_schema_walks(),
+            DetailLevel::Full,
+        );
+
+        assert!(added.is_empty() && removed.is_empty());
+        assert_eq!(modified.len(), 1);
+        assert_eq!(modified[0].hidden_unchanged_count, 2);
+        let [ListOfMapsDiffField::NestedListOfMapsChanged { key, block }] =
+            modified[0].fields.as_slice()
+        else {
+            panic!(
+                "expected one nested list-of-maps field, got {:?}",
+                modified[0].fields
+            );
+        };
+        assert_eq!(key, "transitions");
+        assert!(block.unchanged().is_empty());
+        assert_eq!(block.modified().len(), 1);
+        assert_eq!(
+            block.modified()[0].fields.as_slice(),
+            &[ListOfMapsDiffField::Changed {
+                key: "transition_in_days".to_string(),
+                old: "90".to_string(),
+                new: "180".to_string(),
+            }]
+        );
+        assert_eq!(block.added().len(), 1);
+        assert!(block.removed().is_empty());
+    }
+
+    /// With a schema-declared list key, an inserted rule shows as added
+    /// and the edited rule is paired with its own previous version, not
+    /// with whichever old rule it most resembles.
+    #[test]
+    fn list_of_maps_pairs_elements_by_schema_list_key() {
+        let rule = |id: &str, port: i64| {
+            Value::Concrete(ConcreteValue::Map(indexmap::IndexMap::from([
+                (
+                    "id".to_string(),
+                    Value::Concrete(ConcreteValue::String(id.to_string())),
+                ),
+                (
+                    "protocol".to_string(),
+                    Value::Concrete(ConcreteValue::String("tcp".to_string())),
+                ),
+                (
+                    "port".to_string(),
+                    Value::Concrete(ConcreteValue::Int(port)),
+                ),
+            ])))
+        };
+        let rules = |items: Vec<Value>| Value::Concrete(ConcreteValue::List(items));
+        let from = State::existing(
+            ResourceId::with_identity("ec2.SecurityGroup", "sg"),
+            [(
+                "rules".to_string(),
+                rules(vec![rule("web", 80), rule("ssh", 22)]),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let to = Resource::new("ec2.SecurityGroup", "sg").with_attribute(
+            "rules",
+            rules(vec![rule("web", 80), rule("https", 443), rule("ssh", 2222)]),
+        );
+        let effect = Effect::Update {
+            from: Box::new(from),
+            to: resolved(to),
+            changed_attributes: vec!["rules".to_string()],
+        };
+        let rule_type = AttributeType::list(AttributeType::struct_(
+            "Rule".to_string(),
+            vec![
+                crate::schema::StructField::new("id", AttributeType::string()),
+                crate::schema::StructField::new("protocol", AttributeType::string()),
+                crate::schema::StructField::new("port", AttributeType::int()),
+            ],
+        ));
+        let mut registry = SchemaRegistry::new();
+        registry.insert(
+            "",
+            ResourceSchema::new("ec2.SecurityGroup").attribute(
+                crate::schema::AttributeSchema::new("rules", rule_type).with_list_key("id"),
+            ),
+        );
+
+        let rows = build_detail_rows(&effect, Some(&registry), DetailLevel::Explicit, None, None);
+        let [DetailRow::ListOfMapsDiff { key, block }] = rows.as_slice() else {
+            panic!("expected one list-of-maps row, got {rows:?}");
+        };
+        assert_eq!(key, "rules");
+        assert_eq!(
+            block.modified()[0].fields.as_slice(),
+            &[ListOfMapsDiffField::Changed {
+                key: "port".to_string(),
+                old: "22".to_string(),
+                new: "2222".to_string(),
+            }]
+        );
+        assert_eq!(block.added().len(), 1);
+        assert!(block.added()[0].fields.contains(&(
+            "id".to_string(),
+            Value::Concrete(ConcreteValue::String("https".to_string()))
+        )));
+        assert!(block.removed().is_empty());
+    }
+
     /// carina#3258 (negative — secret bypass at map-diff site):
     /// mirrors `top_level_changed_keeps_secret_rotation_visible` at
     /// `compute_map_diff_entries`. A nested `Secret(A) → Secret(B)`
@@ -3900,6 +4111,7 @@ mod tests {
             Some(&old_value),
             &new_value,
             None,
+            None,
             crate::schema::empty_defs_for_schema_walks(),
             DetailLevel::Full,
         );
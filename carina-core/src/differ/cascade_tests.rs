@@ -34,6 +34,10 @@ fn base_schemas(subnet_vpc_id_create_only: bool) -> SchemaRegistry {
             .attribute(AttributeSchema::new("name", AttributeType::string()).create_only())
             .attribute(AttributeSchema::new("cidr_block", AttributeType::string()).create_only())
             .attribute(AttributeSchema::new("vpc_id", AttributeType::string()).read_only())
+            .attribute(AttributeSchema::new(
+                "instance_tenancy",
+                AttributeType::string(),
+            ))
             .with_unique_name_attribute("name"),
     );
 
@@ -227,6 +231,74 @@ fn cascade_dependent_updates_adds_update_for_dependent() {
     assert!(delete_blocked_by(&plan, &vpc_id).contains(&ResourceIdentity::new("my-subnet")));
 }
 
+#[test]
+fn cascade_skips_dependent_referencing_configured_attribute() {
+    // `cidr_block` is configured on the VPC, so its post-replace value is
+    // already known at plan time; only computed attributes such as
+    // `vpc_id` need to wait for the replacement to be applied.
+    let schemas = base_schemas(false);
+    let (managed_vpc, unresolved_vpc) = vpc_resources(true);
+    let (mut managed_subnet, mut unresolved_subnet) = subnet_resources(false, false);
+    managed_subnet.set_attr("tags", string("10.1.0.0/16"));
+    unresolved_subnet.set_attr("tags", ref_value("vpc", "cidr_block"));
+    unresolved_subnet.set_attr("vpc_id", string("vpc-old"));
+    let vpc_id = managed_vpc.id.clone();
+    let subnet_id = managed_subnet.id.clone();
+    let mut states = standard_states();
+    states
+        .get_mut(&subnet_id)
+        .unwrap()
+        .attributes
+        .insert("tags".to_string(), string("10.1.0.0/16"));
+
+    let plan = plan_for(
+        vec![managed_vpc, managed_subnet],
+        vec![unresolved_vpc, unresolved_subnet],
+        states,
+        &schemas,
+    );
+
+    assert!(update_for(&plan, &subnet_id).is_none());
+    assert!(delete_blocked_by(&plan, &vpc_id).is_empty());
+}
+
+#[test]
+fn cascade_follows_dependent_referencing_unset_optional_attribute() {
+    // `instance_tenancy` is not read-only, but the VPC does not set it:
+    // the provider fills it in, so the value read from the old VPC says
+    // nothing about the replacement.
+    let schemas = base_schemas(false);
+    let (managed_vpc, unresolved_vpc) = vpc_resources(true);
+    let (mut managed_subnet, mut unresolved_subnet) = subnet_resources(false, false);
+    managed_subnet.set_attr("tags", string("default"));
+    unresolved_subnet.set_attr("tags", ref_value("vpc", "instance_tenancy"));
+    let vpc_id = managed_vpc.id.clone();
+    let subnet_id = managed_subnet.id.clone();
+    let mut states = standard_states();
+    states
+        .get_mut(&subnet_id)
+        .unwrap()
+        .attributes
+        .insert("tags".to_string(), string("default"));
+
+    let plan = plan_for(
+        vec![managed_vpc, managed_subnet],
+        vec![unresolved_vpc, unresolved_subnet],
+        states,
+        &schemas,
+    );
+
+    let update = update_for(&plan, &subnet_id).expect("subnet update should be added");
+    let Effect::Update { to, .. } = update else {
+        panic!("expected subnet update, got {update:?}");
+    };
+    assert!(matches!(
+        to.get_attr("tags"),
+        Some(Value::Deferred(DeferredValue::ResourceRef { .. }))
+    ));
+    assert!(delete_blocked_by(&plan, &vpc_id).contains(&ResourceIdentity::new("my-subnet")));
+}
+
 #[test]
 fn cascade_reuses_existing_update_for_dependent() {
     let schemas = base_schemas(false);
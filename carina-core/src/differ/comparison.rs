@@ -5,6 +5,7 @@ use std::collections::{BTreeMap, HashMap};
 
 use indexmap::IndexMap;
 
+use super::suppress::{suppressed_type, suppressed_view};
 use crate::explicit::{self, ExplicitFields};
 use crate::resource::{ConcreteValue, DeferredValue, ResourceId, Value, merge_with_saved};
 use crate::schema::{AttributeType, ResourceSchema, empty_defs_for_schema_walks};
@@ -543,7 +544,32 @@ pub(crate) fn key_should_enter_patch(
     {
         return false;
     }
-    should_patch_attr(cmp)
+    let Some(schema) = schema else {
+        return should_patch_attr(cmp);
+    };
+    let attr_type = cmp
+        .type_info
+        .as_ref()
+        .and_then(|info| suppressed_type(schema, key, info.attr_type, info.defs));
+    let view = |v: &Value| suppressed_view(schema, key, v);
+    let (to, from, saved) = (
+        view(cmp.to),
+        cmp.from.and_then(view),
+        cmp.saved.and_then(view),
+    );
+    should_patch_attr(AttrComparison {
+        from: from.as_ref().or(cmp.from),
+        to: to.as_ref().unwrap_or(cmp.to),
+        saved: saved.as_ref().or(cmp.saved),
+        type_info: match (&attr_type, cmp.type_info) {
+            (Some(attr_type), Some(info)) => Some(TypedAttr {
+                attr_type,
+                defs: info.defs,
+            }),
+            (_, info) => info,
+        },
+        secret_ctx: cmp.secret_ctx,
+    })
 }
 
 /// Build a comparison-only view that grafts `Secret` wrappers from source.
@@ -752,6 +778,78 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn iam_policy_document_compares_canonical_forms() {
+        let attr_type = crate::schema::types::iam_policy_document();
+        let string = |s: &str| Value::Concrete(ConcreteValue::String(s.to_string()));
+        let desired = string(
+            r#"{"Version": "2012-10-17", "Statement": [{"Effect": "Allow", "Action": ["sts:AssumeRole"], "Principal": {"Service": ["ec2.amazonaws.com"]}}]}"#,
+        );
+        let returned = string(
+            r#"{"Statement":{"Principal":{"Service":"ec2.amazonaws.com"},"Action":"sts:AssumeRole","Effect":"Allow"},"Version":"2012-10-17"}"#,
+        );
+        let changed = string(
+            r#"{"Statement":{"Principal":{"Service":"lambda.amazonaws.com"},"Action":"sts:AssumeRole","Effect":"Allow"},"Version":"2012-10-17"}"#,
+        );
+        let equal = |a: &Value, b: &Value| {
+            type_aware_equal(a, b, Some(&attr_type), empty_defs_for_schema_walks(), None)
+        };
+        assert!(equal(&returned, &desired));
+        assert!(!equal(&changed, &desired));
+    }
+
+    #[test]
+    fn schema_diff_suppressor_hides_reformatted_policy() {
+        let schema = ResourceSchema::new("iam.Role")
+            .attribute(crate::schema::AttributeSchema::new(
+                "policy",
+                AttributeType::string(),
+            ))
+            .with_diff_suppressor("policy", crate::schema::DiffSuppressor::JsonDocument);
+        let desired = HashMap::from([(
+            "policy".to_string(),
+            Value::Concrete(ConcreteValue::String(
+                r#"{"Version": "2012-10-17", "Statement": []}"#.to_string(),
+            )),
+        )]);
+        let current = HashMap::from([(
+            "policy".to_string(),
+            Value::Concrete(ConcreteValue::String(
+                r#"{"Statement":[],"Version":"2012-10-17"}"#.to_string(),
+            )),
+        )]);
+        assert!(
+            find_changed_attributes(&desired, &current, None, None, Some(&schema), None).is_empty()
+        );
+        assert_eq!(
+            find_changed_attributes(&desired, &current, None, None, None, None),
+            vec!["policy".to_string()]
+        );
+    }
+
+    #[test]
+    fn ipv6_cidr_read_back_in_canonical_form_is_no_change() {
+        let attr_type = crate::schema::types::ipv6_cidr();
+        let state = Value::Concrete(ConcreteValue::String("2001:db8::/56".to_string()));
+        let desired = Value::Concrete(ConcreteValue::String("2001:DB8:0:0::/56".to_string()));
+
+        assert!(type_aware_equal(
+            &state,
+            &desired,
+            Some(&attr_type),
+            empty_defs_for_schema_walks(),
+            None,
+        ));
+        let other = Value::Concrete(ConcreteValue::String("2001:db8:0:100::/56".to_string()));
+        assert!(!type_aware_equal(
+            &state,
+            &other,
+            Some(&attr_type),
+            empty_defs_for_schema_walks(),
+            None,
+        ));
+    }
+
     #[test]
     fn canonical_enum_cross_identity_same_api_value_is_no_change() {
         let attr_type = AttributeType::enum_(
@@ -0,0 +1,253 @@
+//! Why a single attribute does (or does not) show up in a plan.
+//!
+//! Re-runs the differ's own comparison for one attribute and records the
+//! inputs that decided it: the desired, observed and last-applied values,
+//! whether the observed value is a server default the user never wrote,
+//! which schema diff suppressors applied, and whether a change forces
+//! replacement. `carina why` renders the result.
+
+use std::collections::HashMap;
+
+use super::comparison::{find_changed_attributes, type_aware_equal};
+use crate::explicit::ExplicitFields;
+use crate::resource::{Resource, State, Value};
+use crate::schema::{DiffSuppressorRule, ResourceSchema, empty_defs_for_schema_walks};
+
+/// Which side of the comparison moved away from the last-applied value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeSource {
+    /// The configuration changed; the resource still matches the last apply.
+    Desired,
+    /// The resource drifted; the configuration still matches the last apply.
+    Observed,
+    /// Both moved.
+    Both,
+    /// No last-applied value is recorded, so the sides cannot be told apart.
+    Unknown,
+}
+
+/// The differ's verdict on one attribute, with the inputs behind it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeExplanation {
+    pub attribute: String,
+    /// Value from the configuration, after reference resolution.
+    pub desired: Option<Value>,
+    /// Value read back from the provider. `None` when the resource does
+    /// not exist yet.
+    pub observed: Option<Value>,
+    /// Value recorded in state by the last apply.
+    pub last_applied: Option<Value>,
+    /// Whether the plan changes this attribute.
+    pub changed: bool,
+    /// Set when `changed`.
+    pub source: Option<ChangeSource>,
+    /// The provider returns a value the user never wrote; it is left out
+    /// of the comparison.
+    pub server_default: bool,
+    /// Schema diff suppressors declared for this attribute.
+    pub suppressors: Vec<DiffSuppressorRule>,
+    /// The raw values differ but the suppressors consider them equal.
+    pub suppressed: bool,
+    pub create_only: bool,
+    pub read_only: bool,
+    pub write_only: bool,
+}
+
+impl AttributeExplanation {
+    /// A change to a create-only attribute cannot be applied in place.
+    pub fn requires_replacement(&self) -> bool {
+        self.changed && self.create_only
+    }
+}
+
+/// Explain the diff of `attribute` between `desired` and `current`, with
+/// the same inputs [`diff`](super::diff) takes.
+pub fn explain_attribute(
+    desired: &Resource,
+    current: &State,
+    saved: Option<&HashMap<String, Value>>,
+    prev_explicit: Option<&ExplicitFields>,
+    schema: Option<&ResourceSchema>,
+    attribute: &str,
+) -> AttributeExplanation {
+    let desired_attrs = desired.resolved_attributes();
+    let desired_value = desired_attrs.get(attribute).cloned();
+    let observed = current
+        .exists
+        .then(|| current.attributes.get(attribute).cloned())
+        .flatten();
+    let last_applied = saved.and_then(|s| s.get(attribute)).cloned();
+    let attr_schema = schema.and_then(|s| s.attributes.get(attribute));
+
+    let changed = if current.exists {
+        find_changed_attributes(
+            &desired_attrs,
+            &current.attributes,
+            saved,
+            prev_explicit,
+            schema,
+            Some(&desired.id),
+        )
+        .iter()
+        .any(|key| key == attribute)
+    } else {
+        desired_value.is_some()
+    };
+
+    let defs = schema
+        .map(|s| &s.defs)
+        .unwrap_or(empty_defs_for_schema_walks());
+    let same = |a: Option<&Value>, b: Option<&Value>| match (a, b) {
+        (Some(a), Some(b)) => type_aware_equal(a, b, attr_schema.map(|s| &s.attr_type), defs, None),
+        (None, None) => true,
+        _ => false,
+    };
+
+    let source = changed.then(|| match last_applied.as_ref() {
+        None => ChangeSource::Unknown,
+        Some(saved) => {
+            let desired_moved = !same(desired_value.as_ref(), Some(saved));
+            let observed_moved = current.exists && !same(observed.as_ref(), Some(saved));
+            match (desired_moved, observed_moved) {
+                (true, false) => ChangeSource::Desired,
+                (false, true) => ChangeSource::Observed,
+                (true, true) => ChangeSource::Both,
+                (false, false) => ChangeSource::Unknown,
+            }
+        }
+    });
+
+    let server_default = desired_value.is_none()
+        && observed.is_some()
+        && matches!(
+            prev_explicit,
+            Some(ExplicitFields::Struct { children }) if !children.contains_key(attribute)
+        );
+
+    let suppressors: Vec<DiffSuppressorRule> = schema
+        .map(|s| s.diff_suppressors_for(attribute).cloned().collect())
+        .unwrap_or_default();
+    let suppressed = !changed
+        && !suppressors.is_empty()
+        && desired_value.is_some()
+        && observed.is_some()
+        && !same(desired_value.as_ref(), observed.as_ref());
+
+    AttributeExplanation {
+        attribute: attribute.to_string(),
+        desired: desired_value,
+        observed,
+        last_applied,
+        changed,
+        source,
+        server_default,
+        suppressors,
+        suppressed,
+        create_only: attr_schema.is_some_and(|a| a.create_only),
+        read_only: attr_schema.is_some_and(|a| a.read_only),
+        write_only: attr_schema.is_some_and(|a| a.write_only),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{ConcreteValue, ResourceId};
+    use crate::schema::{AttributeSchema, AttributeType, DiffSuppressor};
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    fn bucket(attr: &str, value: &str) -> Resource {
+        Resource::new("s3.Bucket", "logs").with_attribute(attr, string(value))
+    }
+
+    fn observed(attr: &str, value: &str) -> State {
+        State::existing(
+            ResourceId::with_identity("s3.Bucket", "logs"),
+            HashMap::from([(attr.to_string(), string(value))]),
+        )
+    }
+
+    fn schema() -> ResourceSchema {
+        ResourceSchema::new("s3.Bucket")
+            .attribute(AttributeSchema::new("bucket_name", AttributeType::string()).create_only())
+            .attribute(AttributeSchema::new("policy", AttributeType::string()))
+            .attribute(AttributeSchema::new("region", AttributeType::string()))
+            .with_diff_suppressor("policy", DiffSuppressor::JsonDocument)
+    }
+
+    #[test]
+    fn config_change_is_attributed_to_desired_and_forces_replacement() {
+        let saved = HashMap::from([("bucket_name".to_string(), string("old"))]);
+        let why = explain_attribute(
+            &bucket("bucket_name", "new"),
+            &observed("bucket_name", "old"),
+            Some(&saved),
+            None,
+            Some(&schema()),
+            "bucket_name",
+        );
+        assert!(why.changed);
+        assert_eq!(why.source, Some(ChangeSource::Desired));
+        assert!(why.requires_replacement());
+    }
+
+    #[test]
+    fn drift_is_attributed_to_observed() {
+        let saved = HashMap::from([("region".to_string(), string("a"))]);
+        let why = explain_attribute(
+            &bucket("region", "a"),
+            &observed("region", "b"),
+            Some(&saved),
+            None,
+            Some(&schema()),
+            "region",
+        );
+        assert_eq!(why.source, Some(ChangeSource::Observed));
+        assert!(!why.requires_replacement());
+
+        let why = explain_attribute(
+            &bucket("region", "a"),
+            &observed("region", "b"),
+            None,
+            None,
+            Some(&schema()),
+            "region",
+        );
+        assert_eq!(why.source, Some(ChangeSource::Unknown));
+    }
+
+    #[test]
+    fn suppressed_difference_is_reported_without_a_change() {
+        let why = explain_attribute(
+            &bucket("policy", r#"{"a":1,"b":2}"#),
+            &observed("policy", r#"{ "b": 2, "a": 1 }"#),
+            None,
+            None,
+            Some(&schema()),
+            "policy",
+        );
+        assert!(!why.changed);
+        assert!(why.suppressed);
+        assert_eq!(why.suppressors.len(), 1);
+    }
+
+    #[test]
+    fn unwritten_attribute_is_a_server_default() {
+        let explicit = ExplicitFields::Struct {
+            children: HashMap::from([("bucket_name".to_string(), ExplicitFields::Leaf)]),
+        };
+        let why = explain_attribute(
+            &bucket("bucket_name", "logs"),
+            &observed("region", "us-east-1"),
+            None,
+            Some(&explicit),
+            Some(&schema()),
+            "region",
+        );
+        assert!(why.server_default);
+        assert!(!why.changed);
+    }
+}
@@ -4,13 +4,16 @@
 //! from the Provider, and generates a list of required Effects (Plan).
 
 mod comparison;
+mod explain;
 mod plan;
+mod suppress;
 
 use std::collections::HashMap;
 
 use crate::resource::{Resource, ResourceId, State, Value};
 use crate::schema::ResourceSchema;
 
+pub use explain::{AttributeExplanation, ChangeSource, explain_attribute};
 pub use plan::{create_plan, create_plan_with_cascades};
 
 // Imports used by test submodules (accessible via `use super::*;`)
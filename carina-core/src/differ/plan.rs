@@ -62,14 +62,17 @@ fn find_changed_create_only(
         .collect()
 }
 
+/// Attributes of `attrs` whose value references `target_binding`
+/// through an attribute for which `deferred` holds.
 fn cascade_ref_attrs(
     attrs: &indexmap::IndexMap<String, Value>,
     target_binding: &str,
+    deferred: &dyn Fn(&str) -> bool,
 ) -> Vec<RefAttr> {
     attrs
         .iter()
         .filter_map(|(attribute, value)| {
-            cascade_ref_hint(value, target_binding).map(|hint| RefAttr {
+            cascade_ref_hint(value, target_binding, deferred).map(|hint| RefAttr {
                 attribute: attribute.clone(),
                 hint,
             })
@@ -77,12 +80,16 @@ fn cascade_ref_attrs(
         .collect()
 }
 
-fn cascade_ref_hint(value: &Value, target_binding: &str) -> Option<String> {
+fn cascade_ref_hint(
+    value: &Value,
+    target_binding: &str,
+    deferred: &dyn Fn(&str) -> bool,
+) -> Option<String> {
     let mut hit: Option<String> = None;
 
     // Multi-ref attributes report the first target-binding ref in list/IndexMap/source order.
     value.visit_resource_refs(&mut |path| {
-        if hit.is_none() && path.binding() == target_binding {
+        if hit.is_none() && path.binding() == target_binding && deferred(path.attribute()) {
             hit = Some(format!("{}.{}", path.binding(), path.attribute()));
         }
     });
@@ -415,13 +422,21 @@ fn create_plan_parts(
                     continue;
                 }
 
-                // Check if any changed attributes are create-only
-                let changed_create_only = find_changed_create_only(
-                    &resource.id.provider,
-                    &resource.id.resource_type,
-                    &changed_attributes,
-                    registry,
-                );
+                // Check if any changed attributes are create-only. A type
+                // its provider cannot update in place makes them all so.
+                let changed_create_only = if provider
+                    .capabilities(&resource.id)
+                    .supports_update_in_place(&resource.id.resource_type)
+                {
+                    find_changed_create_only(
+                        &resource.id.provider,
+                        &resource.id.resource_type,
+                        &changed_attributes,
+                        registry,
+                    )
+                } else {
+                    changed_attributes.clone()
+                };
 
                 if let Some(changed_create_only) = ChangedCreateOnly::new(changed_create_only) {
                     // Replace involves destroying the old resource
@@ -953,7 +968,12 @@ fn promote_pending_replaces_for_dependents(
                 continue;
             }
 
-            let ref_attrs = cascade_ref_attrs(&resource.attributes, dep);
+            let ref_attrs = {
+                let pending = &pending_replaces[&replaced_identity];
+                cascade_ref_attrs(&resource.attributes, dep, &|attribute| {
+                    replacement_attr_deferred(pending, attribute, registry)
+                })
+            };
             if ref_attrs.is_empty() {
                 continue;
             }
@@ -1017,6 +1037,32 @@ fn promote_pending_replaces_for_dependents(
     promoted
 }
 
+/// Whether a consumer's reference to `attribute` on a pending
+/// replacement only resolves once the replacement is applied.
+///
+/// Only an attribute the user set explicitly has a value known from
+/// the DSL, already substituted at plan time, so the consumer's own diff
+/// covers it. Everything else (read-only and `deferred_populate`
+/// attributes, but also optional attributes the provider fills in)
+/// belongs to the new cloud resource: the value resolved from the old
+/// state is stale and the consumer must be cascaded. The unique-name
+/// attribute also stays deferred, since it may take a temporary name
+/// under create-before-destroy.
+fn replacement_attr_deferred(
+    pending: &PendingReplace,
+    attribute: &str,
+    registry: &SchemaRegistry,
+) -> bool {
+    let id = &pending.create.id;
+    let Some(schema) = registry.get(&id.provider, &id.resource_type, SchemaKind::Resource) else {
+        return true;
+    };
+    if matches!(&schema.unique_name, UniqueNameSpec::Attribute(name) if name == attribute) {
+        return true;
+    }
+    pending.create.get_attr(attribute).is_none()
+}
+
 fn pending_reference_targets(
     pending_replaces: &HashMap<ResourceIdentity, PendingReplace>,
     create_before_destroy_only: bool,
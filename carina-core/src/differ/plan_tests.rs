@@ -21,8 +21,10 @@ fn explicit_top_level(keys: &[&str]) -> ExplicitFields {
     }
 }
 
+#[derive(Default)]
 struct HintProvider {
     hints: Vec<crate::wait::BindingPattern>,
+    capabilities: crate::provider::ProviderCapabilities,
 }
 
 impl crate::provider::Provider for HintProvider {
@@ -89,6 +91,10 @@ impl crate::provider::Provider for HintProvider {
     ) -> Vec<crate::wait::BindingPattern> {
         self.hints.clone()
     }
+
+    fn capabilities(&self, _id: &ResourceId) -> crate::provider::ProviderCapabilities {
+        self.capabilities.clone()
+    }
 }
 
 fn replacement_metadata<'a>(
@@ -1614,6 +1620,7 @@ fn wait_provider_satisfier_hint_augments_explicit_dependencies() {
     .expect("parsed file should be valid");
     let provider = HintProvider {
         hints: vec![BindingPattern::Exact("validation_records".to_string())],
+        ..Default::default()
     };
 
     let plan = create_plan(
@@ -1673,6 +1680,7 @@ fn wait_user_depends_on_conflict_with_provider_hint_deduplicates() {
     };
     let provider = HintProvider {
         hints: vec![BindingPattern::Exact("something".to_string())],
+        ..Default::default()
     };
 
     let plan = create_plan(
@@ -2160,3 +2168,52 @@ fn wait_emitted_when_known_target_has_pending_update_even_if_cached_state_satisf
         plan.effects()
     );
 }
+
+#[test]
+fn provider_without_in_place_update_turns_update_into_replace() {
+    let resources = vec![Resource::new("ec2.Vpc", "my-vpc").with_attribute(
+        "enable_dns_support",
+        Value::Concrete(ConcreteValue::Bool(true)),
+    )];
+    let id = ResourceId::with_identity("ec2.Vpc", "my-vpc");
+    let mut attrs = HashMap::new();
+    attrs.insert(
+        "enable_dns_support".to_string(),
+        Value::Concrete(ConcreteValue::Bool(false)),
+    );
+    let current_states = crate::resource::into_plan_input_map(
+        HashMap::from([(id.clone(), State::existing(id.clone(), attrs))]),
+        &SchemaRegistry::new(),
+        &[],
+    );
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("", crate::schema::ResourceSchema::new("ec2.Vpc"));
+    let plan_with = |provider: &HintProvider| {
+        create_plan(
+            &resources,
+            &[],
+            provider,
+            &current_states,
+            &HashMap::new(),
+            &schemas,
+            &HashMap::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            &[],
+        )
+    };
+
+    let plan = plan_with(&HintProvider::default());
+    assert!(matches!(plan.effects()[..], [Effect::Update { .. }]));
+
+    let mut provider = HintProvider::default();
+    provider
+        .capabilities
+        .replace_only_types
+        .insert("ec2.Vpc".to_string());
+    let plan = plan_with(&provider);
+    assert_eq!(
+        &replacement_metadata(&plan, &id).changed_create_only[..],
+        &["enable_dns_support".to_string()]
+    );
+}
@@ -0,0 +1,351 @@
+//! Schema-declared diff suppressors.
+//!
+//! Providers attach [`DiffSuppressorRule`]s to a resource schema for
+//! differences the cloud API introduces on its own — policy JSON
+//! reformatted, rule lists reordered, server-assigned IDs filled in —
+//! and for values that change outside Carina by design, such as an
+//! auto-scaled desired count.
+//!
+//! Suppressors that have a counterpart in the attribute type are folded
+//! into it, so the ordinary type-aware comparison does the work:
+//! `IgnoreOrder` marks the list unordered, and `JsonDocument` and
+//! `IgnoreCase` set the string's [`DslTransform`]. The rest have no type
+//! counterpart and rewrite both values instead: `IgnoreFields` drops the
+//! fields and `Ignore` collapses the value to a constant. A path the
+//! attribute type does not reach has nothing to fold into, so only the
+//! value-rewriting suppressors take effect there.
+//!
+//! [`DiffSuppressorRule`]: crate::schema::DiffSuppressorRule
+
+use std::collections::BTreeMap;
+
+use crate::resource::{ConcreteValue, Value};
+use crate::schema::{AttrTypeKind, AttributeType, DiffSuppressor, DslTransform, ResourceSchema};
+
+/// `attr_type` for top-level attribute `key` with every suppressor that
+/// has a type counterpart folded in, or `None` when `schema` declares
+/// none under `key`.
+pub(crate) fn suppressed_type(
+    schema: &ResourceSchema,
+    key: &str,
+    attr_type: &AttributeType,
+    defs: &BTreeMap<String, AttributeType>,
+) -> Option<AttributeType> {
+    let mut rules = schema
+        .diff_suppressors_for(key)
+        .filter(|rule| !rewrites_value(&rule.suppressor))
+        .peekable();
+    rules.peek()?;
+
+    let mut out = attr_type.clone();
+    for rule in rules {
+        let rest: Vec<&str> = rule.path.split('.').skip(1).collect();
+        fold_at(&mut out, &rest, &rule.suppressor, defs);
+    }
+    Some(out)
+}
+
+/// `value` for top-level attribute `key` with every suppressor that
+/// rewrites values applied, or `None` when `schema` declares none under
+/// `key`.
+pub(crate) fn suppressed_view(schema: &ResourceSchema, key: &str, value: &Value) -> Option<Value> {
+    let mut rules = schema
+        .diff_suppressors_for(key)
+        .filter(|rule| rewrites_value(&rule.suppressor))
+        .peekable();
+    rules.peek()?;
+
+    let mut out = value.clone();
+    for rule in rules {
+        let rest: Vec<&str> = rule.path.split('.').skip(1).collect();
+        apply_at(&mut out, &rest, &rule.suppressor);
+    }
+    Some(out)
+}
+
+fn rewrites_value(suppressor: &DiffSuppressor) -> bool {
+    matches!(
+        suppressor,
+        DiffSuppressor::IgnoreFields { .. } | DiffSuppressor::Ignore
+    )
+}
+
+fn fold_at(
+    attr_type: &mut AttributeType,
+    path: &[&str],
+    suppressor: &DiffSuppressor,
+    defs: &BTreeMap<String, AttributeType>,
+) {
+    // Inline the definition so the change stays local to this path
+    // instead of reaching every other use of the definition.
+    while let AttrTypeKind::Ref(name) = &attr_type.kind {
+        let Some(def) = defs.get(name) else {
+            return;
+        };
+        *attr_type = def.clone();
+    }
+    match (&mut attr_type.kind, path) {
+        (AttrTypeKind::List { ordered, .. }, [])
+            if matches!(suppressor, DiffSuppressor::IgnoreOrder) =>
+        {
+            *ordered = false;
+        }
+        // Lists are transparent: the path continues into the elements.
+        (AttrTypeKind::List { element_type, .. }, _) => {
+            fold_at(element_type, path, suppressor, defs);
+        }
+        (AttrTypeKind::Struct { fields, .. }, [field, rest @ ..]) => {
+            if let Some(field) = fields.iter_mut().find(|f| f.name == *field) {
+                fold_at(&mut field.field_type, rest, suppressor, defs);
+            }
+        }
+        (AttrTypeKind::Map { value, .. }, [_, rest @ ..]) => {
+            fold_at(value, rest, suppressor, defs);
+        }
+        (AttrTypeKind::Union(members), _) => {
+            for member in members {
+                fold_at(member, path, suppressor, defs);
+            }
+        }
+        (AttrTypeKind::String { to_dsl, .. }, []) => match suppressor {
+            DiffSuppressor::JsonDocument => *to_dsl = Some(DslTransform::CanonicalJsonDocument),
+            DiffSuppressor::IgnoreCase => *to_dsl = Some(DslTransform::Lowercase),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn apply_at(value: &mut Value, path: &[&str], suppressor: &DiffSuppressor) {
+    match (value, path) {
+        (value, []) if matches!(suppressor, DiffSuppressor::Ignore) => apply(value, suppressor),
+        // Lists are transparent: the path continues into every element.
+        (Value::Concrete(ConcreteValue::List(items)), _) => {
+            for item in items {
+                apply_at(item, path, suppressor);
+            }
+        }
+        (Value::Concrete(ConcreteValue::Map(map)), [field, rest @ ..]) => {
+            if let Some(inner) = map.get_mut(*field) {
+                apply_at(inner, rest, suppressor);
+            }
+        }
+        (value, []) => apply(value, suppressor),
+        _ => {}
+    }
+}
+
+fn apply(value: &mut Value, suppressor: &DiffSuppressor) {
+    match (suppressor, value) {
+        (DiffSuppressor::IgnoreFields { fields }, Value::Concrete(ConcreteValue::Map(map))) => {
+            map.retain(|k, _| !fields.contains(k));
+        }
+        // Both sides collapse to the same constant; which one is irrelevant.
+        (DiffSuppressor::Ignore, value) => {
+            *value = Value::Concrete(ConcreteValue::Bool(true));
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differ::comparison::{AttrComparison, TypedAttr, key_should_enter_patch};
+    use crate::schema::{AttributeSchema, StructField};
+    use indexmap::IndexMap;
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    fn map(entries: &[(&str, Value)]) -> Value {
+        Value::Concrete(ConcreteValue::Map(
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect::<IndexMap<_, _>>(),
+        ))
+    }
+
+    fn list(items: Vec<Value>) -> Value {
+        Value::Concrete(ConcreteValue::List(items))
+    }
+
+    /// Whether `a` and `b` compare equal for attribute `key` of `schema`.
+    fn same(schema: &ResourceSchema, key: &str, a: &Value, b: &Value) -> bool {
+        !key_should_enter_patch(
+            key,
+            Some(schema),
+            AttrComparison {
+                from: Some(a),
+                to: b,
+                saved: None,
+                type_info: schema.attributes.get(key).map(|attr| TypedAttr {
+                    attr_type: &attr.attr_type,
+                    defs: &schema.defs,
+                }),
+                secret_ctx: None,
+            },
+        )
+    }
+
+    #[test]
+    fn json_document_ignores_whitespace_and_key_order() {
+        let schema = ResourceSchema::new("iam.Role")
+            .attribute(AttributeSchema::new("policy", AttributeType::string()))
+            .attribute(AttributeSchema::new("name", AttributeType::string()))
+            .with_diff_suppressor("policy", DiffSuppressor::JsonDocument);
+        let (a, b) = (
+            string(r#"{"b": 1, "a": [2]}"#),
+            string(r#"{"a":[2],"b":1}"#),
+        );
+        assert!(same(&schema, "policy", &a, &b));
+        assert!(!same(&schema, "policy", &a, &string(r#"{"a":[3],"b":1}"#)));
+        assert!(!same(&schema, "name", &a, &b));
+    }
+
+    #[test]
+    fn ignore_case_compares_lowercase_spellings() {
+        let schema = ResourceSchema::new("route53.Record")
+            .attribute(AttributeSchema::new("name", AttributeType::string()))
+            .with_diff_suppressor("name", DiffSuppressor::IgnoreCase);
+        assert!(same(
+            &schema,
+            "name",
+            &string("WWW.Example.com"),
+            &string("www.example.com")
+        ));
+        assert!(!same(
+            &schema,
+            "name",
+            &string("www.example.com"),
+            &string("api.example.com")
+        ));
+    }
+
+    #[test]
+    fn ignore_fields_then_order_through_nested_lists() {
+        let rule_type = AttributeType::struct_(
+            "Rule",
+            vec![
+                StructField::new("id", AttributeType::string()),
+                StructField::new("prefix", AttributeType::string()),
+            ],
+        );
+        let lifecycle_type = AttributeType::struct_(
+            "Lifecycle",
+            vec![StructField::new("rules", AttributeType::list(rule_type))],
+        );
+        let schema = ResourceSchema::new("s3.Bucket")
+            .attribute(AttributeSchema::new("lifecycle", lifecycle_type))
+            .with_diff_suppressor("lifecycle.rules", DiffSuppressor::IgnoreOrder)
+            .with_diff_suppressor(
+                "lifecycle.rules",
+                DiffSuppressor::IgnoreFields {
+                    fields: vec!["id".to_string()],
+                },
+            );
+        let rule = |id: &str, prefix: &str| map(&[("id", string(id)), ("prefix", string(prefix))]);
+        let desired = map(&[(
+            "rules",
+            list(vec![
+                map(&[("prefix", string("logs/"))]),
+                map(&[("prefix", string("tmp/"))]),
+            ]),
+        )]);
+        let actual = map(&[(
+            "rules",
+            list(vec![rule("r-2", "tmp/"), rule("r-1", "logs/")]),
+        )]);
+        assert!(same(&schema, "lifecycle", &desired, &actual));
+        let changed = map(&[(
+            "rules",
+            list(vec![rule("r-2", "tmp/"), rule("r-1", "cache/")]),
+        )]);
+        assert!(!same(&schema, "lifecycle", &desired, &changed));
+    }
+
+    #[test]
+    fn ignore_order_matches_elements_by_value_not_spelling() {
+        let schema = ResourceSchema::new("x.Y")
+            .attribute(AttributeSchema::new(
+                "weights",
+                AttributeType::list(AttributeType::float()),
+            ))
+            .with_diff_suppressor("weights", DiffSuppressor::IgnoreOrder);
+        let int = |n| Value::Concrete(ConcreteValue::Int(n));
+        let float = |f| Value::Concrete(ConcreteValue::Float(f));
+        assert!(same(
+            &schema,
+            "weights",
+            &list(vec![int(1), float(2.0)]),
+            &list(vec![int(2), float(1.0)])
+        ));
+        assert!(!same(
+            &schema,
+            "weights",
+            &list(vec![int(1), float(2.0)]),
+            &list(vec![int(2), float(3.0)])
+        ));
+    }
+
+    #[test]
+    fn suppressor_under_a_definition_does_not_leak_into_other_uses() {
+        let schema = ResourceSchema::new("x.Y")
+            .attribute(AttributeSchema::new("a", AttributeType::ref_("Names")))
+            .attribute(AttributeSchema::new("b", AttributeType::ref_("Names")))
+            .with_def("Names", AttributeType::list(AttributeType::string()))
+            .with_diff_suppressor("a", DiffSuppressor::IgnoreOrder);
+        let (x, y) = (
+            list(vec![string("1"), string("2")]),
+            list(vec![string("2"), string("1")]),
+        );
+        assert!(same(&schema, "a", &x, &y));
+        assert!(!same(&schema, "b", &x, &y));
+    }
+
+    #[test]
+    fn ignore_hides_any_change_to_the_value() {
+        let deployment_type = AttributeType::struct_(
+            "Deployment",
+            vec![
+                StructField::new("last_modified", AttributeType::string()),
+                StructField::new("strategy", AttributeType::string()),
+            ],
+        );
+        let schema = ResourceSchema::new("ecs.Service")
+            .attribute(AttributeSchema::new("desired_count", AttributeType::int()))
+            .attribute(AttributeSchema::new("deployment", deployment_type))
+            .with_diff_suppressor("desired_count", DiffSuppressor::Ignore)
+            .with_diff_suppressor("deployment.last_modified", DiffSuppressor::Ignore);
+        let count = |n| Value::Concrete(ConcreteValue::Int(n));
+        assert!(same(&schema, "desired_count", &count(2), &count(7)));
+        let deployment = |modified: &str, strategy: &str| {
+            map(&[
+                ("last_modified", string(modified)),
+                ("strategy", string(strategy)),
+            ])
+        };
+        assert!(same(
+            &schema,
+            "deployment",
+            &deployment("monday", "rolling"),
+            &deployment("friday", "rolling")
+        ));
+        assert!(!same(
+            &schema,
+            "deployment",
+            &deployment("monday", "rolling"),
+            &deployment("monday", "blue_green")
+        ));
+    }
+
+    #[test]
+    fn unknown_suppressor_changes_nothing() {
+        let schema = ResourceSchema::new("x.Y")
+            .attribute(AttributeSchema::new("a", AttributeType::string()))
+            .with_diff_suppressor("a", DiffSuppressor::Unknown);
+        assert!(!same(&schema, "a", &string("Mixed"), &string("mixed")));
+    }
+}
@@ -0,0 +1,245 @@
+//! Conditional resources: `directives { enabled = <Bool> }`.
+//!
+//! The parser keeps the `enabled` expression in [`Directives::enabled`]
+//! instead of evaluating it, because it usually depends on a root
+//! variable or module argument that is only bound later.
+//! [`drop_disabled_resources`] runs once those are substituted: for the
+//! root file after variables are bound, and for each module instance at
+//! the end of its expansion, once the call's arguments are in place. It
+//! removes every resource and data source whose flag is `false`, so the
+//! planner sees them as absent from the configuration and plans the
+//! deletion of anything state still holds for them.
+//!
+//! A disabled resource has no attributes to offer, so any remaining
+//! reference to its binding is a plan-time error rather than an empty
+//! value. `depends_on` edges to it are dropped: ordering against a
+//! resource that will not exist is trivially satisfied.
+
+use std::collections::{BTreeSet, HashSet};
+
+use crate::deps::collect_dependencies;
+use crate::parser::{ExportParamLike, File};
+use crate::resource::{ConcreteValue, Directives, ResourceId, Value};
+
+/// Remove resources and data sources whose `directives.enabled` is
+/// `false`, returning the ids of what was removed.
+///
+/// Fails when a flag is not a `Bool` by now, or when something that
+/// stays in the configuration references a disabled binding. All
+/// problems are reported at once, newline-separated.
+pub fn drop_disabled_resources<E: ExportParamLike>(
+    parsed: &mut File<E>,
+) -> Result<Vec<ResourceId>, String> {
+    let mut errors = Vec::new();
+    let mut removed = Vec::new();
+    let mut disabled = BTreeSet::new();
+
+    parsed.resources.retain_mut(|resource| {
+        let enabled = take_flag(
+            &mut resource.directives,
+            &resource.id.to_string(),
+            &mut errors,
+        );
+        if !enabled {
+            removed.push(resource.id.clone());
+            disabled.extend(resource.binding.clone());
+        }
+        enabled
+    });
+    parsed.data_sources.retain_mut(|data_source| {
+        let enabled = take_flag(
+            &mut data_source.directives,
+            &data_source.id.to_string(),
+            &mut errors,
+        );
+        if !enabled {
+            removed.push(data_source.id.clone());
+            disabled.extend(data_source.binding.clone());
+        }
+        enabled
+    });
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    if disabled.is_empty() {
+        return Ok(removed);
+    }
+
+    let mut check = |consumer: String, refs: HashSet<String>| {
+        let mut hits: Vec<&String> = refs.iter().filter(|r| disabled.contains(*r)).collect();
+        hits.sort();
+        for binding in hits {
+            errors.push(format!(
+                "{} references '{}', which is disabled (directives.enabled = false)",
+                consumer, binding
+            ));
+        }
+    };
+    for resource in &mut parsed.resources {
+        resource
+            .directives
+            .depends_on
+            .retain(|d| !disabled.contains(d));
+        check(
+            resource.id.to_string(),
+            references(resource.attributes.values(), &resource.dependency_bindings),
+        );
+    }
+    for data_source in &mut parsed.data_sources {
+        data_source
+            .directives
+            .depends_on
+            .retain(|d| !disabled.contains(d));
+        check(
+            data_source.id.to_string(),
+            references(
+                data_source.attributes.values(),
+                &data_source.dependency_bindings,
+            ),
+        );
+    }
+    for composition in &parsed.compositions {
+        let values: Vec<Value> = composition
+            .signature
+            .attributes
+            .values()
+            .map(|a| a.to_value())
+            .collect();
+        check(
+            format!("module '{}' attributes", composition.instance),
+            references(&values, &composition.dependency_bindings),
+        );
+    }
+    for call in &parsed.module_calls {
+        let consumer = match &call.binding_name {
+            Some(binding) => format!("module call '{}'", binding),
+            None => format!("module call '{}'", call.module_name),
+        };
+        check(
+            consumer,
+            references(call.arguments.values(), &BTreeSet::new()),
+        );
+    }
+    for export in &parsed.export_params {
+        check(
+            format!("export '{}'", export.name()),
+            references(export.value(), &BTreeSet::new()),
+        );
+    }
+
+    if errors.is_empty() {
+        Ok(removed)
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Take and evaluate the `enabled` flag; a missing flag means enabled.
+fn take_flag(directives: &mut Directives, subject: &str, errors: &mut Vec<String>) -> bool {
+    match directives.enabled.take() {
+        None | Some(Value::Concrete(ConcreteValue::Bool(true))) => true,
+        Some(Value::Concrete(ConcreteValue::Bool(false))) => false,
+        Some(other) => {
+            errors.push(format!(
+                "{}: directives.enabled must be a Bool known at plan time, got {:?}",
+                subject, other
+            ));
+            true
+        }
+    }
+}
+
+fn references<'a>(
+    values: impl IntoIterator<Item = &'a Value>,
+    dependency_bindings: &BTreeSet<String>,
+) -> HashSet<String> {
+    let mut refs: HashSet<String> = dependency_bindings.iter().cloned().collect();
+    for value in values {
+        collect_dependencies(value, &mut refs);
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ProviderContext, parse, resolve_resource_refs};
+
+    fn parsed(src: &str) -> crate::parser::ParsedFile {
+        let mut parsed = parse(src, &ProviderContext::default()).unwrap();
+        resolve_resource_refs(&mut parsed).unwrap();
+        parsed
+    }
+
+    #[test]
+    fn disabled_resources_are_dropped() {
+        let mut file = parsed(
+            r#"
+            let create_nat = false
+
+            let nat = awscc.ec2.NatGateway {
+                subnet_id = 'subnet-1'
+                directives {
+                    enabled = create_nat
+                }
+            }
+
+            let vpc = awscc.ec2.Vpc {
+                cidr_block = '10.0.0.0/16'
+                directives {
+                    enabled = true
+                    depends_on = [nat]
+                }
+            }
+            "#,
+        );
+        let removed = drop_disabled_resources(&mut file).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].identity_str(), Some("nat"));
+        assert_eq!(file.resources.len(), 1);
+        let vpc = &file.resources[0];
+        assert!(vpc.directives.depends_on.is_empty());
+        assert!(vpc.directives.enabled.is_none());
+    }
+
+    #[test]
+    fn references_to_disabled_resources_are_rejected() {
+        let mut file = parsed(
+            r#"
+            let nat = awscc.ec2.NatGateway {
+                subnet_id = 'subnet-1'
+                directives {
+                    enabled = false
+                }
+            }
+
+            awscc.ec2.Route {
+                nat_gateway_id = nat.nat_gateway_id
+            }
+
+            exports {
+                nat_id = nat.nat_gateway_id
+            }
+            "#,
+        );
+        let err = drop_disabled_resources(&mut file).unwrap_err();
+        assert!(err.contains("references 'nat', which is disabled"), "{err}");
+        assert!(err.contains("export 'nat_id' references 'nat'"), "{err}");
+    }
+
+    #[test]
+    fn non_bool_flags_are_rejected() {
+        let mut file = parsed(
+            r#"
+            awscc.ec2.Vpc {
+                cidr_block = '10.0.0.0/16'
+                directives {
+                    enabled = 'yes'
+                }
+            }
+            "#,
+        );
+        let err = drop_disabled_resources(&mut file).unwrap_err();
+        assert!(err.contains("directives.enabled must be a Bool"), "{err}");
+    }
+}
@@ -3,7 +3,7 @@
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Instant;
+use tokio::time::Instant;
 
 use crate::binding_index::ResolvedBindings;
 use crate::differ::{
@@ -21,8 +21,12 @@ use crate::resolver::resolve_ref_value;
 use crate::resource::{
     ConcreteValue, DeferredValue, ResolvedResource, Resource, ResourceId, State, Value,
 };
-use crate::value::{SecretHashContext, SerializationContext, SerializationError};
+use crate::value::{
+    SecretHashContext, SerializationContext, SerializationError, redact_secret_text,
+};
 
+use super::idempotency::{client_token, ensure_not_created};
+use super::read_after_write::verify_created;
 use super::wait::AppliedStates;
 use super::{ExecutionEvent, ExecutionObserver, ProgressInfo};
 
@@ -51,6 +55,9 @@ pub(super) enum BasicEffectResult {
     },
     Failure {
         refresh: Option<(ResourceId, String)>,
+        /// The provider rejected the run's credentials; see
+        /// [`ProviderError::is_fatal`](crate::provider::ProviderError::is_fatal).
+        fatal: bool,
     },
     Deleted {
         resource_id: ResourceId,
@@ -293,6 +300,7 @@ fn assert_fully_resolved(
         | Value::Concrete(ConcreteValue::Float(_))
         | Value::Concrete(ConcreteValue::Bool(_))
         | Value::Concrete(ConcreteValue::Duration(_))
+        | Value::Concrete(ConcreteValue::ByteSize(_))
         | Value::Concrete(ConcreteValue::StringList(_)) => Ok(()),
     }
 }
@@ -369,6 +377,7 @@ fn collect_unresolved_bindings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
         | Value::Concrete(ConcreteValue::Float(_))
         | Value::Concrete(ConcreteValue::Bool(_))
         | Value::Concrete(ConcreteValue::Duration(_))
+        | Value::Concrete(ConcreteValue::ByteSize(_))
         | Value::Concrete(ConcreteValue::StringList(_)) => {}
     }
 }
@@ -428,7 +437,7 @@ pub(super) fn process_basic_result(result: BasicEffectResult, exec: &mut Executi
                 exec.applied_states.insert(resource_id, *s);
             }
         }
-        BasicEffectResult::Failure { refresh } => {
+        BasicEffectResult::Failure { refresh, .. } => {
             *exec.failure_count += 1;
             exec.failed_indices.insert(exec.idx);
             if let Some((id, identifier)) = &refresh {
@@ -520,18 +529,46 @@ pub(super) async fn execute_basic_effect<'a>(
                     observer.on_event(&ExecutionEvent::EffectFailed {
                         effect,
                         error: &e,
+                        cause: None,
                         duration: started.elapsed(),
                         progress,
                     });
-                    return BasicEffectResult::Failure { refresh: None };
+                    return BasicEffectResult::Failure {
+                        refresh: None,
+                        fatal: false,
+                    };
                 }
             };
             let resolved_attrs = resolved.as_resource().resolved_attributes();
+            let timeout = resolved.as_resource().directives.timeouts.create;
+            let token = client_token(resolved.as_resource());
+            if let Err(e) = ensure_not_created(provider, &resource.id, &resolved).await {
+                let error_str = redact_secret_text(&e.to_string(), resource.attributes.values());
+                observer.on_event(&ExecutionEvent::EffectFailed {
+                    effect,
+                    error: &error_str,
+                    cause: Some(&e),
+                    duration: started.elapsed(),
+                    progress,
+                });
+                return BasicEffectResult::Failure {
+                    refresh: None,
+                    fatal: e.is_fatal(),
+                };
+            }
             match provider
-                .create(&resource.id, CreateRequest { resource: resolved })
+                .create(
+                    &resource.id,
+                    CreateRequest {
+                        resource: resolved,
+                        timeout,
+                        client_token: Some(token),
+                    },
+                )
                 .await
             {
                 Ok(outcome) => {
+                    let outcome = verify_created(provider, &resource.id, outcome).await;
                     let diagnostic = outcome.diagnostic().cloned();
                     let state = outcome.into_state_for_writeback();
                     if let Some(diagnostic) = diagnostic {
@@ -565,14 +602,19 @@ pub(super) async fn execute_basic_effect<'a>(
                     }
                 }
                 Err(e) => {
-                    let error_str = e.to_string();
+                    let error_str =
+                        redact_secret_text(&e.to_string(), resource.attributes.values());
                     observer.on_event(&ExecutionEvent::EffectFailed {
                         effect,
                         error: &error_str,
+                        cause: Some(&e),
                         duration: started.elapsed(),
                         progress,
                     });
-                    BasicEffectResult::Failure { refresh: None }
+                    BasicEffectResult::Failure {
+                        refresh: None,
+                        fatal: e.is_fatal(),
+                    }
                 }
             }
         }
@@ -593,10 +635,14 @@ pub(super) async fn execute_basic_effect<'a>(
                         observer.on_event(&ExecutionEvent::EffectFailed {
                             effect,
                             error: &e,
+                            cause: None,
                             duration: started.elapsed(),
                             progress,
                         });
-                        return BasicEffectResult::Failure { refresh: None };
+                        return BasicEffectResult::Failure {
+                            refresh: None,
+                            fatal: false,
+                        };
                     }
                 };
             let identifier = from.identifier.as_deref().unwrap_or("");
@@ -647,10 +693,11 @@ pub(super) async fn execute_basic_effect<'a>(
                     effective_changed.push(key.clone());
                 }
             }
-            let patch = build_update_patch(&effective_changed, &resolved_to, from);
+            let patch = build_update_patch(&effective_changed, &resolved_to, from, schema);
             let request = UpdateRequest {
                 from: from.clone(),
                 patch,
+                timeout: resolved_to.as_resource().directives.timeouts.update,
             };
             match provider.update(id, identifier, request).await {
                 Ok(outcome) => {
@@ -692,15 +739,18 @@ pub(super) async fn execute_basic_effect<'a>(
                     }
                 }
                 Err(e) => {
-                    let error_str = e.to_string();
+                    let error_str =
+                        redact_secret_text(&e.to_string(), resolve_source.attributes.values());
                     observer.on_event(&ExecutionEvent::EffectFailed {
                         effect,
                         error: &error_str,
+                        cause: Some(&e),
                         duration: started.elapsed(),
                         progress,
                     });
                     BasicEffectResult::Failure {
                         refresh: Some((id.clone(), identifier.to_string())),
+                        fatal: e.is_fatal(),
                     }
                 }
             }
@@ -716,6 +766,8 @@ pub(super) async fn execute_basic_effect<'a>(
                 identifier,
                 DeleteRequest {
                     directives: directives.clone(),
+                    timeout: directives.timeouts.delete,
+                    force_dependencies: false,
                 },
             )
             .await
@@ -736,11 +788,13 @@ pub(super) async fn execute_basic_effect<'a>(
                 observer.on_event(&ExecutionEvent::EffectFailed {
                     effect,
                     error: &error_str,
+                    cause: Some(&e),
                     duration: started.elapsed(),
                     progress,
                 });
                 BasicEffectResult::Failure {
                     refresh: Some((id.clone(), identifier.to_string())),
+                    fatal: e.is_fatal(),
                 }
             }
         },
@@ -130,6 +130,7 @@ pub(super) fn dispatch_deferred_create(
             ctx.observer.on_event(&ExecutionEvent::EffectFailed {
                 effect,
                 error: &message,
+                cause: None,
                 duration: Duration::ZERO,
                 progress: ProgressInfo {
                     completed: ctx.completed.load(Ordering::Relaxed),
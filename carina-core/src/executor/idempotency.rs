@@ -0,0 +1,107 @@
+//! Duplicate-create protection.
+//!
+//! An apply that times out after the cloud accepted a create leaves a
+//! resource behind that state never recorded; running the apply again
+//! would create a second copy (two VPCs with the same `Name` tag). Each
+//! create therefore carries a client token that is stable across retries
+//! of the same configuration, and is preceded by a lookup of
+//! name-identified resources that turns a would-be duplicate into an
+//! [`ProviderError::AlreadyExists`] error.
+
+use std::collections::BTreeMap;
+
+use crate::provider::{Provider, ProviderError, ProviderResult};
+use crate::resource::{ResolvedResource, Resource, ResourceId};
+
+/// Idempotency token for creating `resource`.
+///
+/// Derived from the resource address and a hash of its configuration,
+/// so a retried apply of the same configuration sends the same token
+/// while a changed configuration sends a new one. The hash is computed
+/// here rather than with `std`'s hasher, whose output may change between
+/// releases. Tokens are `carina-` followed by 32 hex digits, inside
+/// Cloud Control's `ClientToken` alphabet and length limit.
+pub fn client_token(resource: &Resource) -> String {
+    let address = resource.id.human().to_string();
+    // Sorted so the token does not depend on attribute order.
+    let attributes: BTreeMap<&String, _> = resource.attributes.iter().collect();
+    let config = serde_json::to_vec(&attributes).unwrap_or_default();
+    format!(
+        "carina-{:016x}{:016x}",
+        fnv1a(address.as_bytes()),
+        fnv1a(&config)
+    )
+}
+
+/// Fail with [`ProviderError::AlreadyExists`] when the provider finds a
+/// remote resource that creating `resource` would duplicate.
+pub(super) async fn ensure_not_created(
+    provider: &dyn Provider,
+    id: &ResourceId,
+    resource: &ResolvedResource,
+) -> ProviderResult<()> {
+    match provider.find_existing(id, resource).await? {
+        Some(identifier) => Err(ProviderError::already_exists(format!(
+            "{} already exists as {identifier}; add an `import` block to adopt it, \
+             or give the resource a different name",
+            id.human()
+        ))
+        .for_resource(id.clone())),
+        None => Ok(()),
+    }
+}
+
+/// 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{ConcreteValue, Value};
+
+    fn vpc(cidr: &str) -> Resource {
+        Resource::with_provider("awscc", "ec2.Vpc", "main", None)
+            .with_attribute(
+                "cidr_block",
+                Value::Concrete(ConcreteValue::String(cidr.to_string())),
+            )
+            .with_attribute(
+                "enable_dns_support",
+                Value::Concrete(ConcreteValue::Bool(true)),
+            )
+    }
+
+    #[test]
+    fn client_token_is_stable_for_the_same_configuration() {
+        let token = client_token(&vpc("10.0.0.0/16"));
+        assert_eq!(token, client_token(&vpc("10.0.0.0/16")));
+        assert_eq!(token.len(), "carina-".len() + 32);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+
+        let mut reordered = Resource::with_provider("awscc", "ec2.Vpc", "main", None);
+        for (key, value) in vpc("10.0.0.0/16").attributes.into_iter().rev() {
+            reordered = reordered.with_attribute(key, value);
+        }
+        assert_eq!(token, client_token(&reordered));
+    }
+
+    #[test]
+    fn client_token_changes_with_address_or_configuration() {
+        let token = client_token(&vpc("10.0.0.0/16"));
+        assert_ne!(token, client_token(&vpc("10.1.0.0/16")));
+
+        let mut renamed = vpc("10.0.0.0/16");
+        renamed.id = ResourceId::with_provider_identity("awscc", "ec2.Vpc", "other", None);
+        assert_ne!(token, client_token(&renamed));
+    }
+
+    #[test]
+    fn fnv1a_matches_reference_vectors() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
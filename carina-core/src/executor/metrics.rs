@@ -0,0 +1,231 @@
+//! Apply statistics per resource type, built from the
+//! [`OperationOutcome`]s an apply recorded, so users can see which
+//! resource types dominate apply time. Also exported as OpenTelemetry
+//! metrics in the OTLP/JSON encoding.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use serde_json::{Value as Json, json};
+
+use super::{OperationOutcome, OperationStatus};
+use crate::resource::ResourceId;
+
+/// Totals for every create, update and delete of one resource type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceTypeStats {
+    /// Provider-qualified type, e.g. `awscc.ec2.Vpc`.
+    pub resource_type: String,
+    pub operations: u32,
+    pub failures: u32,
+    /// Summed wall-clock time of the operations, including waits.
+    pub total: Duration,
+    /// Longest single operation.
+    pub slowest: Duration,
+    /// Provider-reported totals; zero when the provider reports none.
+    pub api_calls: u32,
+    pub api_latency: Duration,
+    pub polls: u32,
+    pub retries: u32,
+}
+
+/// Per-type statistics for the operations that ran, slowest type first.
+/// Skipped operations are left out.
+pub fn summarize(outcomes: &HashMap<ResourceId, OperationOutcome>) -> Vec<ResourceTypeStats> {
+    let mut by_type: BTreeMap<String, ResourceTypeStats> = BTreeMap::new();
+    for (id, outcome) in outcomes {
+        let Some(duration) = outcome.duration else {
+            continue;
+        };
+        let resource_type = id.display_type();
+        let stats = by_type
+            .entry(resource_type.clone())
+            .or_insert_with(|| ResourceTypeStats {
+                resource_type,
+                ..Default::default()
+            });
+        stats.operations += 1;
+        if outcome.status == OperationStatus::Failed {
+            stats.failures += 1;
+        }
+        stats.total += duration;
+        stats.slowest = stats.slowest.max(duration);
+        if let Some(metrics) = outcome.metrics {
+            stats.api_calls += metrics.api_calls;
+            stats.api_latency += metrics.api_latency;
+            stats.polls += metrics.polls;
+            stats.retries += metrics.retries;
+        }
+    }
+    let mut stats: Vec<_> = by_type.into_values().collect();
+    // Stable sort keeps the name order among equal totals.
+    stats.sort_by_key(|s| std::cmp::Reverse(s.total));
+    stats
+}
+
+/// The statistics as an OTLP `ExportMetricsServiceRequest` in its JSON
+/// encoding, one data point per resource type, covering the apply from
+/// `start_unix_nanos` to `end_unix_nanos`. Durations are in seconds;
+/// counts are delta sums.
+pub fn to_otlp_json(
+    stats: &[ResourceTypeStats],
+    start_unix_nanos: u128,
+    end_unix_nanos: u128,
+) -> Json {
+    let point = |s: &ResourceTypeStats, value: Json| {
+        let mut point = json!({
+            "attributes": [
+                { "key": "carina.resource_type", "value": { "stringValue": s.resource_type } }
+            ],
+            "startTimeUnixNano": start_unix_nanos.to_string(),
+            "timeUnixNano": end_unix_nanos.to_string(),
+        });
+        let key = if value.is_f64() { "asDouble" } else { "asInt" };
+        // OTLP/JSON encodes 64-bit integers as strings.
+        let value = match value.as_u64() {
+            Some(n) => Json::String(n.to_string()),
+            None => value,
+        };
+        point[key] = value;
+        point
+    };
+    let metric = |name: &str, unit: &str, value: &dyn Fn(&ResourceTypeStats) -> Json| {
+        json!({
+            "name": name,
+            "unit": unit,
+            "sum": {
+                "aggregationTemporality": 1,
+                "isMonotonic": true,
+                "dataPoints": stats.iter().map(|s| point(s, value(s))).collect::<Vec<_>>(),
+            },
+        })
+    };
+    let secs = |d: Duration| json!(d.as_secs_f64());
+    let metrics = vec![
+        metric("carina.apply.operations", "{operation}", &|s| {
+            json!(s.operations)
+        }),
+        metric("carina.apply.failures", "{operation}", &|s| {
+            json!(s.failures)
+        }),
+        metric("carina.apply.duration", "s", &|s| secs(s.total)),
+        metric("carina.apply.api.calls", "{request}", &|s| {
+            json!(s.api_calls)
+        }),
+        metric("carina.apply.api.latency", "s", &|s| secs(s.api_latency)),
+        metric("carina.apply.polls", "{poll}", &|s| json!(s.polls)),
+        metric("carina.apply.retries", "{retry}", &|s| json!(s.retries)),
+    ];
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "carina" } }
+                ]
+            },
+            "scopeMetrics": [{
+                "scope": { "name": "carina", "version": env!("CARGO_PKG_VERSION") },
+                "metrics": metrics,
+            }],
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::OperationMetrics;
+
+    fn outcome(
+        status: OperationStatus,
+        secs: u64,
+        metrics: Option<OperationMetrics>,
+    ) -> OperationOutcome {
+        OperationOutcome {
+            status,
+            operation: "create",
+            error_kind: None,
+            message: None,
+            request_id: None,
+            duration: (status != OperationStatus::Skipped).then(|| Duration::from_secs(secs)),
+            metrics,
+        }
+    }
+
+    fn id(resource_type: &str, name: &str) -> ResourceId {
+        ResourceId::with_provider_identity("awscc", resource_type, name, None)
+    }
+
+    #[test]
+    fn summarize_groups_by_type_slowest_first() {
+        let polled = OperationMetrics {
+            api_calls: 4,
+            api_latency: Duration::from_millis(800),
+            polls: 3,
+            retries: 1,
+        };
+        let outcomes = HashMap::from([
+            (
+                id("ec2.Subnet", "a"),
+                outcome(OperationStatus::Succeeded, 5, None),
+            ),
+            (
+                id("ec2.NatGateway", "nat"),
+                outcome(OperationStatus::Succeeded, 90, Some(polled)),
+            ),
+            (
+                id("ec2.Subnet", "b"),
+                outcome(OperationStatus::Failed, 7, Some(polled)),
+            ),
+            (
+                id("ec2.Subnet", "c"),
+                outcome(OperationStatus::Skipped, 0, None),
+            ),
+        ]);
+
+        let stats = summarize(&outcomes);
+        assert_eq!(
+            stats
+                .iter()
+                .map(|s| s.resource_type.as_str())
+                .collect::<Vec<_>>(),
+            ["awscc.ec2.NatGateway", "awscc.ec2.Subnet"]
+        );
+        let subnet = &stats[1];
+        assert_eq!(subnet.operations, 2);
+        assert_eq!(subnet.failures, 1);
+        assert_eq!(subnet.total, Duration::from_secs(12));
+        assert_eq!(subnet.slowest, Duration::from_secs(7));
+        assert_eq!((subnet.api_calls, subnet.polls, subnet.retries), (4, 3, 1));
+    }
+
+    #[test]
+    fn otlp_json_has_one_point_per_type() {
+        let stats = vec![ResourceTypeStats {
+            resource_type: "awscc.ec2.Vpc".to_string(),
+            operations: 2,
+            total: Duration::from_millis(2500),
+            ..Default::default()
+        }];
+        let doc = to_otlp_json(&stats, 1_000, 2_000);
+        let metrics = &doc["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        let by_name = |name: &str| {
+            metrics
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|m| m["name"] == name)
+                .unwrap()["sum"]["dataPoints"][0]
+                .clone()
+        };
+
+        let operations = by_name("carina.apply.operations");
+        assert_eq!(operations["asInt"], "2");
+        assert_eq!(
+            operations["attributes"][0]["value"]["stringValue"],
+            "awscc.ec2.Vpc"
+        );
+        assert_eq!(operations["startTimeUnixNano"], "1000");
+        assert_eq!(by_name("carina.apply.duration")["asDouble"], 2.5);
+    }
+}
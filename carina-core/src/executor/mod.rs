@@ -10,18 +10,28 @@
 //! - `parallel`: Dependency computation and fine-grained parallel scheduling
 //! - `deferred_dispatch`: DeferredCreate and DeferredReplace materialization/dispatch
 //! - `replace`: Shared update patch helpers
+//! - `metrics`: Per-resource-type apply statistics and their OTLP/JSON export
+//! - `sim` (test-only): Scripted fake provider for virtual-clock executor tests
 
 pub(crate) mod basic;
 mod deferred_dispatch;
+mod idempotency;
+pub mod metrics;
 pub mod normalized;
 #[cfg(test)]
 mod normalized_tests;
 mod parallel;
+mod read_after_write;
 mod replace;
 pub(super) mod scheduler;
+#[cfg(test)]
+pub(crate) mod sim;
+#[cfg(test)]
+mod sim_tests;
 pub(crate) mod wait;
 
 pub use crate::effect::deps::UnresolvedResource;
+pub use idempotency::client_token;
 pub use replace::compute_full_diff_patch;
 
 use std::collections::{HashMap, HashSet};
@@ -31,7 +41,9 @@ use std::time::Duration;
 use crate::binding_index::ResolvedBindings;
 use crate::effect::Effect;
 use crate::parser::ProviderConfig;
-use crate::provider::{PartialReadDiagnostic, Provider, ProviderError, ProviderNormalizer};
+use crate::provider::{
+    OperationMetrics, PartialReadDiagnostic, Provider, ProviderError, ProviderNormalizer,
+};
 use crate::resource::{
     AccessPath, ConcreteValue, DataSource, DeferredValue, InterpolationPart, ResolvedResource,
     Resource, ResourceId, State, UnknownReason, Value,
@@ -224,6 +236,114 @@ pub struct ExecutionResult {
     pub current_states: HashMap<ResourceId, State>,
     pub bindings: ResolvedBindings,
     pub failed_refreshes: HashSet<ResourceId>,
+    /// Outcome of the last create/update/delete dispatched for each
+    /// resource in this run.
+    pub operation_outcomes: HashMap<ResourceId, OperationOutcome>,
+}
+
+/// Whether a resource's last operation succeeded, failed, or never ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    Succeeded,
+    Failed,
+    /// Not attempted because a dependency failed or the run was cancelled.
+    Skipped,
+}
+
+/// Outcome of one create/update/delete effect, as recorded by
+/// [`execute_plan`] for persistence in state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationOutcome {
+    pub status: OperationStatus,
+    /// Effect kind (`create`, `update`, `delete`).
+    pub operation: &'static str,
+    /// Provider error kind (`api_error`, `timeout`, ...). `None` when the
+    /// failure happened outside a provider call, e.g. while resolving
+    /// references.
+    pub error_kind: Option<&'static str>,
+    /// Error message for failures, skip reason for skips.
+    pub message: Option<String>,
+    /// Cloud-API request id reported with the failure.
+    pub request_id: Option<String>,
+    /// Wall-clock time of the effect, including waits. `None` for skips.
+    pub duration: Option<Duration>,
+    /// Timing the provider collected for the operation, if it reports any.
+    pub metrics: Option<OperationMetrics>,
+}
+
+/// Observer that forwards every event and records per-resource
+/// [`OperationOutcome`]s along the way.
+struct OutcomeRecorder<'a> {
+    inner: &'a dyn ExecutionObserver,
+    provider: &'a dyn Provider,
+    outcomes: std::sync::Mutex<HashMap<ResourceId, OperationOutcome>>,
+}
+
+impl ExecutionObserver for OutcomeRecorder<'_> {
+    fn on_event(&self, event: &ExecutionEvent) {
+        self.inner.on_event(event);
+        let (effect, status, error_kind, message, request_id, duration) = match event {
+            ExecutionEvent::EffectSucceeded {
+                effect, duration, ..
+            }
+            | ExecutionEvent::EffectPartiallySucceeded {
+                effect, duration, ..
+            } => (
+                *effect,
+                OperationStatus::Succeeded,
+                None,
+                None,
+                None,
+                Some(*duration),
+            ),
+            ExecutionEvent::EffectFailed {
+                effect,
+                error,
+                cause,
+                duration,
+                ..
+            } => (
+                *effect,
+                OperationStatus::Failed,
+                cause.map(ProviderError::variant_name),
+                Some(error.to_string()),
+                cause.and_then(|c| c.detail().request_id.clone()),
+                Some(*duration),
+            ),
+            ExecutionEvent::EffectSkipped { effect, reason, .. } => (
+                *effect,
+                OperationStatus::Skipped,
+                None,
+                Some(reason.to_string()),
+                None,
+                None,
+            ),
+            _ => return,
+        };
+        if !matches!(
+            effect,
+            Effect::Create(_) | Effect::Update { .. } | Effect::Delete { .. }
+        ) {
+            return;
+        }
+        self.outcomes
+            .lock()
+            .expect("outcome recorder poisoned")
+            .insert(
+                effect.resource_id().clone(),
+                OperationOutcome {
+                    status,
+                    operation: effect.kind(),
+                    error_kind,
+                    message,
+                    request_id,
+                    duration,
+                    metrics: duration
+                        .and_then(|_| self.provider.operation_metrics(effect.resource_id())),
+                },
+            );
+    }
 }
 
 /// Outcome of executing a plan: either it ran to completion, or a
@@ -294,6 +414,9 @@ pub enum ExecutionEvent<'a> {
     EffectFailed {
         effect: &'a Effect,
         error: &'a str,
+        /// The provider error behind `error`, when the failure came from
+        /// a provider call rather than from resolution or a wait.
+        cause: Option<&'a ProviderError>,
         duration: Duration,
         progress: ProgressInfo,
     },
@@ -358,8 +481,17 @@ pub async fn execute_plan(
     observer: &dyn ExecutionObserver,
     cancel: CancellationToken,
 ) -> ExecutionOutcome {
-    let (result, was_cancelled) =
-        execute_effects_sequential(provider, &mut input, observer, &cancel).await;
+    let recorder = OutcomeRecorder {
+        inner: observer,
+        provider,
+        outcomes: std::sync::Mutex::new(HashMap::new()),
+    };
+    let (mut result, was_cancelled) =
+        execute_effects_sequential(provider, &mut input, &recorder, &cancel).await;
+    result.operation_outcomes = recorder
+        .outcomes
+        .into_inner()
+        .expect("outcome recorder poisoned");
     if was_cancelled {
         ExecutionOutcome::Cancelled(result)
     } else {
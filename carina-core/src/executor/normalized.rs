@@ -5,7 +5,7 @@
 //! use carina_core::resource::{Resource, State};
 //! let r: Resource = unimplemented!();
 //! let s: State = unimplemented!();
-//! build_update_patch(&[], &r, &s);   // must not compile
+//! build_update_patch(&[], &r, &s, None);   // must not compile
 //! ```
 //!
 //! ```compile_fail
@@ -71,8 +71,9 @@ pub async fn apply_desired_normalization(
 }
 
 /// Apply the full desired-side pipeline to a resource slice in place:
-/// canonicalize, strip provider-boundary-deferred attributes, run desired
-/// normalization stages, then restore stripped attributes.
+/// canonicalize, materialize schema defaults, strip provider-boundary-deferred
+/// attributes, run desired normalization stages, then restore stripped
+/// attributes.
 ///
 /// Use this from callers that own a slice and do not need to interleave
 /// plan-only state-side passes. `PlanPreprocessor::prepare` interleaves those
@@ -85,12 +86,46 @@ pub async fn apply_desired_normalization_slice(
     schemas: &SchemaRegistry,
 ) {
     crate::value::canonicalize_resources_with_schemas(resources, schemas);
+    materialize_schema_defaults(resources, schemas);
     let stripped = strip_provider_boundary_attributes(resources);
     run_desired_normalization_stages(resources, provider_configs, normalizer, factories, schemas)
         .await;
     restore_stripped_attributes(resources, stripped);
 }
 
+/// Insert each schema attribute's `default` into resources that leave it
+/// unset, so the desired side matches what the provider fills in and a
+/// resource whose user never wrote `instance_tenancy` does not diff
+/// against the read-back `"default"`.
+///
+/// Must run after `canonicalize_resources_with_schemas` and before
+/// [`strip_provider_boundary_attributes`]: presence is key-based, and an
+/// attribute bound to a not-yet-known value is still user-specified.
+/// Read-only attributes and attributes excluded by a schema constraint
+/// (any member of an `exactly_one_of` group, or a `required_with` trigger
+/// whose partners are unset) are skipped.
+pub fn materialize_schema_defaults(resources: &mut [Resource], schemas: &SchemaRegistry) {
+    for resource in resources.iter_mut() {
+        let Some(schema) = schemas.get_for(resource) else {
+            continue;
+        };
+        let attributes = &resource.attributes;
+        let defaults: Vec<(String, Value)> = schema
+            .missing_defaults(&|name| attributes.contains_key(name))
+            .into_iter()
+            .map(|(name, value)| {
+                let attr_type = &schema.attributes[name].attr_type;
+                let value =
+                    crate::value::canonicalize_with_type(value.clone(), attr_type, &schema.defs);
+                (name.to_string(), value)
+            })
+            .collect();
+        for (name, value) in defaults {
+            resource.attributes.insert(name, value);
+        }
+    }
+}
+
 /// Run the desired-side normalization stages on a slice that has already been
 /// canonicalized and whose unserializable attributes have been stripped.
 ///
@@ -277,6 +312,7 @@ pub fn is_value_fully_concrete_for_expansion(value: &Value) -> bool {
             ConcreteValue::Float(_) => true,
             ConcreteValue::Bool(_) => true,
             ConcreteValue::Duration(_) => true,
+            ConcreteValue::ByteSize(_) => true,
             ConcreteValue::List(items) => items.iter().all(is_value_fully_concrete_for_expansion),
             ConcreteValue::StringList(_) => true,
             ConcreteValue::Map(map) => map.values().all(is_value_fully_concrete_for_expansion),
@@ -4,8 +4,8 @@ use std::sync::{Arc, Mutex};
 use indexmap::IndexMap;
 
 use crate::executor::normalized::{
-    apply_desired_normalization, apply_desired_normalization_slice, restore_stripped_attributes,
-    run_desired_normalization_stages, strip_attributes_matching,
+    apply_desired_normalization, apply_desired_normalization_slice, materialize_schema_defaults,
+    restore_stripped_attributes, run_desired_normalization_stages, strip_attributes_matching,
     strip_provider_boundary_attributes,
 };
 use crate::parser::ProviderConfig;
@@ -80,6 +80,7 @@ fn provider_config(default_tags: IndexMap<String, Value>) -> ProviderConfig {
         name: "test".to_string(),
         attributes: IndexMap::new(),
         default_tags,
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -866,3 +867,126 @@ async fn apply_desired_normalization_is_idempotent() {
 
     assert_eq!(first.as_resource(), second.as_resource());
 }
+
+fn vpc_defaults_schema() -> SchemaRegistry {
+    use crate::schema::{AttributeSchema, AttributeType, ResourceSchema};
+
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert(
+        "test",
+        ResourceSchema::new("vpc")
+            .attribute(
+                AttributeSchema::new("instance_tenancy", AttributeType::string())
+                    .with_default(string_value("default")),
+            )
+            .attribute(AttributeSchema::new("cidr_block", AttributeType::string()))
+            .attribute(
+                AttributeSchema::new("ipv4_ipam_pool_id", AttributeType::string())
+                    .with_default(string_value("pool-default")),
+            )
+            .attribute(
+                AttributeSchema::new("state", AttributeType::string())
+                    .with_default(string_value("available"))
+                    .read_only(),
+            )
+            .exactly_one_of(&["cidr_block", "ipv4_ipam_pool_id"]),
+    );
+    schemas
+}
+
+#[test]
+fn materialize_schema_defaults_fills_unset_attributes_only() {
+    let schemas = vpc_defaults_schema();
+    let mut unset = Resource::with_provider("test", "vpc", "a", None);
+    unset.set_attr("cidr_block", string_value("10.0.0.0/16"));
+    let mut set = Resource::with_provider("test", "vpc", "b", None);
+    set.set_attr("cidr_block", string_value("10.1.0.0/16"));
+    set.set_attr("instance_tenancy", string_value("dedicated"));
+    let mut resources = vec![unset, set];
+
+    materialize_schema_defaults(&mut resources, &schemas);
+
+    assert_eq!(
+        resources[0].get_attr("instance_tenancy"),
+        Some(&string_value("default"))
+    );
+    assert_eq!(
+        resources[1].get_attr("instance_tenancy"),
+        Some(&string_value("dedicated"))
+    );
+    for resource in &resources {
+        // Read-only defaults describe provider output, not desired input.
+        assert!(resource.get_attr("state").is_none());
+        // `cidr_block` already satisfies the exactly-one-of group.
+        assert!(resource.get_attr("ipv4_ipam_pool_id").is_none());
+    }
+}
+
+#[test]
+fn materialize_schema_defaults_keeps_deferred_user_value() {
+    let schemas = vpc_defaults_schema();
+    let mut resource = Resource::with_provider("test", "vpc", "a", None);
+    resource.set_attr(
+        "instance_tenancy",
+        Value::resource_ref("settings".to_string(), "tenancy".to_string(), vec![]),
+    );
+    let mut resources = vec![resource];
+
+    materialize_schema_defaults(&mut resources, &schemas);
+
+    assert!(contains_resource_ref(
+        resources[0].get_attr("instance_tenancy").unwrap()
+    ));
+}
+
+#[test]
+fn materialize_schema_defaults_leaves_an_empty_exactly_one_of_group_unset() {
+    let schemas = vpc_defaults_schema();
+    let mut resources = vec![Resource::with_provider("test", "vpc", "a", None)];
+
+    materialize_schema_defaults(&mut resources, &schemas);
+
+    // The user chose neither member; a default must not pick
+    // `ipv4_ipam_pool_id` for them and hide the validation error.
+    assert!(resources[0].get_attr("ipv4_ipam_pool_id").is_none());
+    let schema = schemas.get_for(&resources[0]).unwrap();
+    assert!(
+        schema
+            .validate(&resources[0].resolved_attributes())
+            .is_err()
+    );
+}
+
+#[test]
+fn materialize_schema_defaults_does_not_trigger_required_with_from_a_default() {
+    use crate::schema::{AttributeSchema, AttributeType, ResourceSchema};
+
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert(
+        "test",
+        ResourceSchema::new("listener")
+            .attribute(
+                AttributeSchema::new("protocol", AttributeType::string())
+                    .with_default(string_value("HTTPS")),
+            )
+            .attribute(AttributeSchema::new("ssl_policy", AttributeType::string()))
+            .required_with("protocol", &["ssl_policy"]),
+    );
+    let bare = Resource::with_provider("test", "listener", "a", None);
+    let mut with_policy = Resource::with_provider("test", "listener", "b", None);
+    with_policy.set_attr("ssl_policy", string_value("tls13"));
+    let mut resources = vec![bare, with_policy];
+
+    materialize_schema_defaults(&mut resources, &schemas);
+
+    // The user wrote neither attribute; the default must not make them
+    // answer for an `ssl_policy` they were never asked for.
+    assert!(resources[0].get_attr("protocol").is_none());
+    let schema = schemas.get_for(&resources[0]).unwrap();
+    assert!(schema.validate(&resources[0].resolved_attributes()).is_ok());
+    // With its partner present the trigger's default still applies.
+    assert_eq!(
+        resources[1].get_attr("protocol"),
+        Some(&string_value("HTTPS"))
+    );
+}
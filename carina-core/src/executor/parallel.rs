@@ -2,7 +2,8 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Instant;
+use std::time::Duration;
+use tokio::time::Instant;
 
 use tokio_util::sync::CancellationToken;
 
@@ -13,26 +14,32 @@ use crate::provider::Provider;
 use crate::resource::{Resource, ResourceId, Value};
 
 use super::basic::{
-    BasicEffectCtx, ExecutionState, RenormalizePipeline, count_actionable_effects,
-    execute_basic_effect, process_basic_result, refresh_pending_states,
+    BasicEffectCtx, BasicEffectResult, ExecutionState, RenormalizePipeline,
+    count_actionable_effects, execute_basic_effect, process_basic_result, refresh_pending_states,
 };
 use super::deferred_dispatch::PureMetaCtx;
 use super::replace::SingleEffectResult;
 use super::scheduler::{
     FailureView, PureMetaOutcome, build_scheduler_deps, dependency_failed_reason,
-    emit_cancelled_skips_with_progress, failure_binding_name, try_dispatch_pure_meta,
+    emit_undispatched_skips_with_progress, failure_binding_name, try_dispatch_pure_meta,
     wait_dependency_failed_reason,
 };
 use super::wait::{
-    AppliedStates, SKIP_REASON_CANCELLED, WaitAwareInFlight, WaitOutcome, WaitSignal,
-    count_effectively_undispatched, resolve_wait_identifier, unsatisfiable_reason_message,
-    wait_failure_message,
+    AppliedStates, SKIP_REASON_CANCELLED, SKIP_REASON_FATAL, WaitAwareInFlight, WaitOutcome,
+    WaitSignal, count_effectively_undispatched, resolve_wait_identifier,
+    unsatisfiable_reason_message, wait_failure_message,
 };
 use super::{
     DeferredDataSourceReads, ExecutionEvent, ExecutionInput, ExecutionObserver, ExecutionResult,
     ProgressInfo, unresolved_data_source_inputs,
 };
 
+/// How long in-flight operations may keep running after a fatal provider
+/// error before the executor abandons them. Cloud Control operations that
+/// are already accepted usually finish well within this; anything slower is
+/// reported as failed so the run can still exit and save state.
+pub(super) const FATAL_DRAIN_TIMEOUT: Duration = Duration::from_secs(300);
+
 pub(super) struct ExpandedEffects {
     pub(super) effects: Vec<Effect>,
     pub(super) deferred_replace_delete_deps: Vec<(usize, usize)>,
@@ -127,6 +134,50 @@ fn is_deferred_read(effect: &Effect, deferred_reads: &DeferredDataSourceReads) -
     matches!(effect, Effect::Read { resource } if deferred_reads.contains(&resource.id))
 }
 
+/// Keep the effects of `ready` whose provider is below its
+/// [`ProviderCapabilities::max_parallel_ops`], counting the `in_flight`
+/// effects against the same provider instance. Waits and scheduler-meta
+/// effects neither count nor wait: a wait holding a provider's only slot
+/// would starve the create it is waiting for.
+///
+/// [`ProviderCapabilities::max_parallel_ops`]: crate::provider::ProviderCapabilities::max_parallel_ops
+fn within_provider_limits(
+    provider: &dyn Provider,
+    effects: &[Effect],
+    ready: Vec<usize>,
+    in_flight: &[usize],
+) -> Vec<usize> {
+    let throttled = |effect: &Effect| !effect.is_wait() && !effect.is_scheduler_meta();
+    let instance = |effect: &Effect| {
+        let id = effect.resource_id();
+        (id.provider.clone(), id.provider_instance.clone())
+    };
+    let mut running: HashMap<(String, Option<String>), usize> = HashMap::new();
+    for &idx in in_flight {
+        if throttled(&effects[idx]) {
+            *running.entry(instance(&effects[idx])).or_default() += 1;
+        }
+    }
+    ready
+        .into_iter()
+        .filter(|&idx| {
+            let effect = &effects[idx];
+            if !throttled(effect) {
+                return true;
+            }
+            let Some(limit) = provider.capabilities(effect.resource_id()).max_parallel_ops else {
+                return true;
+            };
+            let running = running.entry(instance(effect)).or_default();
+            if *running >= limit.get() {
+                return false;
+            }
+            *running += 1;
+            true
+        })
+        .collect()
+}
+
 fn format_unresolved_read_inputs(unresolved: &[super::UnresolvedDataSourceInput]) -> String {
     unresolved
         .iter()
@@ -276,6 +327,10 @@ pub(super) async fn execute_effects_sequential(
 
     let mut in_flight: WaitAwareInFlight<'_, SingleEffectResult> = WaitAwareInFlight::new();
     let mut cancelled = false;
+    // Set once an effect fails with a fatal provider error: nothing new is
+    // dispatched, and in-flight effects get until this deadline to finish.
+    let mut drain_deadline: Option<Instant> = None;
+    let mut dispatched_at: HashMap<usize, Instant> = HashMap::new();
 
     loop {
         let undispatched_at_loop_start = actionable_indices
@@ -292,7 +347,7 @@ pub(super) async fn execute_effects_sequential(
 
         // Find newly ready effects: all deps completed and not yet dispatched
         let mut newly_ready: Vec<usize> = Vec::new();
-        if !cancelled {
+        if !cancelled && drain_deadline.is_none() {
             for &idx in &actionable_indices {
                 if dispatched.contains(&idx) {
                     continue;
@@ -307,7 +362,7 @@ pub(super) async fn execute_effects_sequential(
         }
 
         // Emit Waiting events for effects that have unmet dependencies
-        if !cancelled {
+        if !cancelled && drain_deadline.is_none() {
             for &idx in &actionable_indices {
                 if dispatched.contains(&idx) || newly_ready.contains(&idx) {
                     continue;
@@ -328,6 +383,12 @@ pub(super) async fn execute_effects_sequential(
             }
         }
 
+        let mut newly_ready = within_provider_limits(
+            provider,
+            &effects,
+            newly_ready,
+            &in_flight.in_flight_indices(),
+        );
         let available = input.parallelism.get().saturating_sub(in_flight.len());
         newly_ready.truncate(available);
 
@@ -560,6 +621,7 @@ pub(super) async fn execute_effects_sequential(
                 (idx, result)
             };
 
+            dispatched_at.insert(idx, Instant::now());
             if effect.is_wait() {
                 in_flight.push_wait(idx, |cancel_rx| Box::pin(make_future(Some(cancel_rx))));
             } else {
@@ -587,13 +649,18 @@ pub(super) async fn execute_effects_sequential(
                 .iter()
                 .filter(|idx| !dispatched.contains(idx))
                 .count();
-            if cancelled {
+            if cancelled || drain_deadline.is_some() {
                 let mut progress_for = |_| ProgressInfo {
                     completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
                     total,
                 };
-                emit_cancelled_skips_with_progress(
+                emit_undispatched_skips_with_progress(
                     &effects,
+                    if cancelled {
+                        SKIP_REASON_CANCELLED
+                    } else {
+                        SKIP_REASON_FATAL
+                    },
                     &actionable_indices,
                     &mut dispatched,
                     &mut completed_indices,
@@ -615,7 +682,58 @@ pub(super) async fn execute_effects_sequential(
         }
 
         // Wait for the next effect to complete
-        let (finished_idx, result) = if cancelled {
+        let (finished_idx, result) = if let Some(deadline) = drain_deadline {
+            let next = tokio::time::timeout_at(
+                deadline,
+                in_flight
+                    .check_terminal(count_undispatched(&dispatched, &failed_indices))
+                    .cancel_if_terminal()
+                    .next_completed(),
+            )
+            .await;
+            match next {
+                Ok(Some(finished)) => finished,
+                Ok(None) => break,
+                Err(_) => {
+                    let error = format!(
+                        "abandoned: still running {}s after a fatal provider error",
+                        FATAL_DRAIN_TIMEOUT.as_secs()
+                    );
+                    for idx in in_flight.in_flight_indices() {
+                        observer.on_event(&ExecutionEvent::EffectFailed {
+                            effect: &effects[idx],
+                            error: &error,
+                            cause: None,
+                            duration: dispatched_at
+                                .get(&idx)
+                                .map_or(FATAL_DRAIN_TIMEOUT, Instant::elapsed),
+                            progress: ProgressInfo {
+                                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                                total,
+                            },
+                        });
+                        failure_count += 1;
+                        failed_indices.insert(idx);
+                        completed_indices.insert(idx);
+                    }
+                    let mut progress_for = |_| ProgressInfo {
+                        completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                        total,
+                    };
+                    emit_undispatched_skips_with_progress(
+                        &effects,
+                        SKIP_REASON_FATAL,
+                        &actionable_indices,
+                        &mut dispatched,
+                        &mut completed_indices,
+                        &mut skip_count,
+                        observer,
+                        &mut progress_for,
+                    );
+                    break;
+                }
+            }
+        } else if cancelled {
             let Some(finished) = in_flight
                 .check_terminal(count_undispatched(&dispatched, &failed_indices))
                 .cancel_if_terminal()
@@ -649,6 +767,12 @@ pub(super) async fn execute_effects_sequential(
         // Process the result and update shared state immediately
         match result {
             SingleEffectResult::Basic(basic) => {
+                if matches!(basic, BasicEffectResult::Failure { fatal: true, .. })
+                    && drain_deadline.is_none()
+                {
+                    drain_deadline = Some(Instant::now() + FATAL_DRAIN_TIMEOUT);
+                    in_flight.signal_in_flight_waits();
+                }
                 process_basic_result(
                     basic,
                     &mut ExecutionState {
@@ -695,6 +819,7 @@ pub(super) async fn execute_effects_sequential(
                     observer.on_event(&ExecutionEvent::EffectFailed {
                         effect: &effects[finished_idx],
                         error: &error,
+                        cause: None,
                         duration,
                         progress,
                     });
@@ -760,6 +885,7 @@ pub(super) async fn execute_effects_sequential(
                     observer.on_event(&ExecutionEvent::EffectFailed {
                         effect: &effects[finished_idx],
                         error: &error,
+                        cause: None,
                         duration,
                         progress,
                     });
@@ -795,6 +921,7 @@ pub(super) async fn execute_effects_sequential(
         current_states: input.current_states.clone(),
         bindings: input.bindings.clone(),
         failed_refreshes,
+        operation_outcomes: HashMap::new(),
     };
     (result, cancelled)
 }
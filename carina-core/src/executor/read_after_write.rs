@@ -0,0 +1,276 @@
+//! Read-after-write verification for creates.
+//!
+//! Cloud APIs are often eventually consistent: the read a provider makes
+//! right after creating a resource can miss it entirely, or return it
+//! before every attribute is populated (EC2 is the usual offender). A
+//! create whose outcome shows either symptom is re-read on a bounded
+//! backoff until the resource is visible and the attributes the provider
+//! reported missing are filled in, so the state written back is not a
+//! stale snapshot.
+
+use std::time::Duration;
+
+use crate::provider::{CreateOutcome, Provider, ProviderError, ReadRequest};
+use crate::resource::{ResourceId, State};
+
+/// Delays before each verification read, 15 seconds in total.
+const VERIFY_DELAYS: [Duration; 4] = [
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+];
+
+/// Re-read a just-created resource until it is visible with the
+/// attributes its create outcome was missing.
+///
+/// A complete outcome is returned untouched, as is one without an
+/// identifier to read by. Each visible read is merged over the created
+/// state, so attributes only the create response carried survive. When
+/// the delays run out, what has been observed so far is returned, still
+/// partial if attributes are missing; a read error other than not-found
+/// ends verification early the same way, since the create itself
+/// succeeded.
+pub(super) async fn verify_created(
+    provider: &dyn Provider,
+    id: &ResourceId,
+    outcome: CreateOutcome,
+) -> CreateOutcome {
+    let (mut state, reason, mut missing) = match outcome {
+        CreateOutcome::Success { state } if state.exists => {
+            return CreateOutcome::Success { state };
+        }
+        CreateOutcome::Success { state } => (state, None, Vec::new()),
+        CreateOutcome::PartialSuccess { state, diagnostic } => (
+            state,
+            Some(diagnostic.reason().to_string()),
+            diagnostic.missing_attributes().to_vec(),
+        ),
+    };
+    let Some(identifier) = state.identifier.clone() else {
+        return finish(state, reason, missing);
+    };
+
+    for delay in VERIFY_DELAYS {
+        tokio::time::sleep(delay).await;
+        match provider.read(id, Some(&identifier), ReadRequest).await {
+            Ok(read) if read.exists => {
+                merge_read(&mut state, read);
+                missing.retain(|attr| !state.attributes.contains_key(attr));
+                if missing.is_empty() {
+                    return CreateOutcome::Success { state };
+                }
+            }
+            Ok(_) | Err(ProviderError::NotFound(_)) => {}
+            Err(_) => break,
+        }
+    }
+    finish(state, reason, missing)
+}
+
+fn merge_read(state: &mut State, read: State) {
+    state.exists = true;
+    state.attributes.extend(read.attributes);
+}
+
+fn finish(state: State, reason: Option<String>, missing: Vec<String>) -> CreateOutcome {
+    match reason {
+        Some(reason) => CreateOutcome::partial_success(state, reason, missing),
+        None => CreateOutcome::Success { state },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use crate::effect::PlanOp;
+    use crate::provider::{
+        BoxFuture, CreateRequest, DeleteRequest, ProviderResult, UpdateOutcome, UpdateRequest,
+    };
+    use crate::resource::{ConcreteValue, DataSource, Value};
+
+    #[derive(Clone)]
+    enum Reply {
+        State(Box<State>),
+        NotFound,
+        ApiError,
+    }
+
+    impl Reply {
+        fn state(state: State) -> Self {
+            Reply::State(Box::new(state))
+        }
+    }
+
+    /// Answers reads from a script, repeating the last answer.
+    struct ScriptedReads {
+        responses: Mutex<Vec<Reply>>,
+        reads: Mutex<usize>,
+    }
+
+    impl ScriptedReads {
+        fn new(responses: Vec<Reply>) -> Self {
+            Self {
+                responses: Mutex::new(responses),
+                reads: Mutex::new(0),
+            }
+        }
+
+        fn read_count(&self) -> usize {
+            *self.reads.lock().unwrap()
+        }
+    }
+
+    impl Provider for ScriptedReads {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn read(
+            &self,
+            _id: &ResourceId,
+            _identifier: Option<&str>,
+            _request: ReadRequest,
+        ) -> BoxFuture<'_, ProviderResult<State>> {
+            Box::pin(async move {
+                *self.reads.lock().unwrap() += 1;
+                let mut responses = self.responses.lock().unwrap();
+                let reply = if responses.len() > 1 {
+                    responses.remove(0)
+                } else {
+                    responses[0].clone()
+                };
+                match reply {
+                    Reply::State(state) => Ok(*state),
+                    Reply::NotFound => Err(ProviderError::not_found("no such VPC")),
+                    Reply::ApiError => Err(ProviderError::api_error("AccessDenied")),
+                }
+            })
+        }
+
+        fn read_data_source(&self, _resource: &DataSource) -> BoxFuture<'_, ProviderResult<State>> {
+            unreachable!()
+        }
+
+        fn create(
+            &self,
+            _id: &ResourceId,
+            _request: CreateRequest,
+        ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+            unreachable!()
+        }
+
+        fn update(
+            &self,
+            _id: &ResourceId,
+            _identifier: &str,
+            _request: UpdateRequest,
+        ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+            unreachable!()
+        }
+
+        fn delete(
+            &self,
+            _id: &ResourceId,
+            _identifier: &str,
+            _request: DeleteRequest,
+        ) -> BoxFuture<'_, ProviderResult<()>> {
+            unreachable!()
+        }
+
+        fn required_permissions(&self, _id: &ResourceId, _op: PlanOp) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    fn id() -> ResourceId {
+        ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None)
+    }
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    fn created(attributes: &[(&str, &str)]) -> State {
+        State::existing(
+            id(),
+            attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), string(v)))
+                .collect(),
+        )
+        .with_identifier("vpc-1")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn complete_creates_are_not_re_read() {
+        let provider = ScriptedReads::new(vec![Reply::ApiError]);
+        let outcome = CreateOutcome::Success {
+            state: created(&[("cidr_block", "10.0.0.0/16")]),
+        };
+        assert_eq!(
+            verify_created(&provider, &id(), outcome.clone()).await,
+            outcome
+        );
+        assert_eq!(provider.read_count(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn partial_create_is_re_read_until_missing_attributes_appear() {
+        let provider = ScriptedReads::new(vec![
+            Reply::state(State::not_found(id())),
+            Reply::state(created(&[("cidr_block", "10.0.0.0/16")])),
+            Reply::state(created(&[
+                ("cidr_block", "10.0.0.0/16"),
+                ("vpc_id", "vpc-1"),
+            ])),
+        ]);
+        let outcome = CreateOutcome::partial_success(
+            created(&[("tags", "t")]),
+            "read returned before the VPC was visible".to_string(),
+            vec!["cidr_block".to_string(), "vpc_id".to_string()],
+        );
+
+        let CreateOutcome::Success { state } = verify_created(&provider, &id(), outcome).await
+        else {
+            panic!("expected the verified create to be complete");
+        };
+        assert_eq!(provider.read_count(), 3);
+        let mut keys: Vec<&str> = state.attributes.keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(keys, ["cidr_block", "tags", "vpc_id"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn invisible_create_stays_as_reported_once_retries_run_out() {
+        let provider = ScriptedReads::new(vec![Reply::NotFound]);
+        let mut state = created(&[]);
+        state.exists = false;
+        let outcome = CreateOutcome::Success {
+            state: state.clone(),
+        };
+
+        assert_eq!(
+            verify_created(&provider, &id(), outcome).await,
+            CreateOutcome::Success { state }
+        );
+        assert_eq!(provider.read_count(), VERIFY_DELAYS.len());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_errors_end_verification_with_the_partial_outcome() {
+        let provider = ScriptedReads::new(vec![Reply::ApiError]);
+        let outcome = CreateOutcome::partial_success(
+            created(&[]),
+            "stale read".to_string(),
+            vec!["vpc_id".to_string()],
+        );
+        assert_eq!(
+            verify_created(&provider, &id(), outcome.clone()).await,
+            outcome
+        );
+        assert_eq!(provider.read_count(), 1);
+    }
+}
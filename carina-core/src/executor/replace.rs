@@ -68,7 +68,7 @@ pub fn compute_full_diff_patch(
             None => (!key.starts_with('_')).then(|| key.to_string()),
         })
         .collect();
-    build_update_patch(&changed, to, from)
+    build_update_patch(&changed, to, from, schema)
 }
 
 /// Build a single-attribute [`UpdatePatch`] when exactly one
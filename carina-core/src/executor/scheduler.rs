@@ -8,7 +8,6 @@ use crate::resource::ResourceId;
 use super::UnresolvedResource;
 use super::deferred_dispatch::{DeferredDispatchResult, PureMetaCtx, dispatch_deferred_create};
 use super::parallel::apply_deferred_replace_delete_deps;
-use super::wait::SKIP_REASON_CANCELLED;
 use super::{ExecutionEvent, ExecutionObserver, ProgressInfo};
 use crate::effect::deps::{
     ScheduleInputs, build_effect_dependency_analysis, relax_update_update_edges,
@@ -203,8 +202,10 @@ impl<'a> FailureView<'a> {
     }
 }
 
-pub(super) fn emit_cancelled_skips_with_progress(
+#[allow(clippy::too_many_arguments)]
+pub(super) fn emit_undispatched_skips_with_progress(
     effects: &[Effect],
+    reason: &str,
     indices: &[usize],
     dispatched: &mut HashSet<usize>,
     completed_indices: &mut HashSet<usize>,
@@ -220,7 +221,7 @@ pub(super) fn emit_cancelled_skips_with_progress(
         completed_indices.insert(idx);
         observer.on_event(&ExecutionEvent::EffectSkipped {
             effect: &effects[idx],
-            reason: SKIP_REASON_CANCELLED,
+            reason,
             progress: progress_for(idx),
         });
         *skip_count += 1;
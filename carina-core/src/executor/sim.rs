@@ -0,0 +1,291 @@
+//! Deterministic simulation harness for the executor.
+//!
+//! [`SimProvider`] is a fake provider whose operations follow a script:
+//! each call sleeps for a scripted latency on the tokio clock and then
+//! returns a scripted outcome (success, failure, or throttle).
+//! Tests run under `#[tokio::test(start_paused = true)]`, so the tokio
+//! clock is virtual and auto-advances whenever every task is idle — a
+//! ten-minute wait timeout or a 4s throttling backoff completes
+//! instantly and the timeline recorded in [`SimProvider::calls`] is
+//! exact and reproducible.
+//!
+//! The executor measures durations with `tokio::time::Instant`, so the
+//! `duration` fields on [`ExecutionEvent`](super::ExecutionEvent)s are
+//! virtual time too.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::binding_index::ResolvedBindings;
+use crate::plan::Plan;
+use crate::provider::{
+    BoxFuture, CreateOutcome, CreateRequest, DeleteRequest, NoopNormalizer, Provider,
+    ProviderError, ProviderResult, ReadRequest, UpdateOutcome, UpdateRequest,
+};
+use crate::resource::{ConcreteValue, DataSource, ResourceId, State, Value};
+use crate::schema::SchemaRegistry;
+
+use super::{DeferredDataSourceReads, ExecutionInput};
+
+static SIM_SCHEMAS: LazyLock<SchemaRegistry> = LazyLock::new(SchemaRegistry::new);
+
+/// Provider operation a script entry applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum SimOp {
+    Create,
+    Update,
+    Delete,
+    Read,
+    ReadDataSource,
+}
+
+/// Injected failure returned by a scripted call instead of success.
+#[derive(Debug, Clone)]
+pub(crate) enum Fault {
+    /// Non-retryable API error with the given message.
+    Fail(String),
+    /// `ThrottlingException`, which the executor's retry paths back off on.
+    Throttle,
+    /// A credentials rejection (`ExpiredToken`), which the executor
+    /// treats as fatal to the run.
+    ExpiredToken,
+}
+
+impl Fault {
+    fn into_error(self, id: &ResourceId) -> ProviderError {
+        match self {
+            Fault::Fail(message) => ProviderError::api_error(message),
+            Fault::Throttle => ProviderError::api_error("ThrottlingException: Rate exceeded"),
+            Fault::ExpiredToken => {
+                ProviderError::credentials("The security token included in the request is expired")
+                    .with_code("ExpiredToken")
+            }
+        }
+        .for_resource(id.clone())
+    }
+}
+
+/// One scripted call: how long it takes and what it returns.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Step {
+    pub latency: Duration,
+    pub fault: Option<Fault>,
+    /// Attributes returned by a successful call, merged over the default
+    /// `id` attribute. Drives wait predicates through `Read`.
+    pub attrs: HashMap<String, Value>,
+}
+
+impl Step {
+    pub fn ok(latency: Duration) -> Self {
+        Self {
+            latency,
+            ..Self::default()
+        }
+    }
+
+    pub fn fault(latency: Duration, fault: Fault) -> Self {
+        Self {
+            latency,
+            fault: Some(fault),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_attr(mut self, key: &str, value: &str) -> Self {
+        self.attrs.insert(
+            key.to_string(),
+            Value::Concrete(ConcreteValue::String(value.to_string())),
+        );
+        self
+    }
+}
+
+/// A finished provider call, timestamped on the virtual clock relative
+/// to the provider's construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SimCall {
+    pub op: SimOp,
+    pub name: String,
+    pub started: Duration,
+    pub finished: Duration,
+    pub ok: bool,
+}
+
+/// Scripted fake provider. Calls are keyed by `(op, identity)`; each call
+/// pops the next [`Step`] for its key, and once a script is exhausted its
+/// last step repeats. Unscripted keys succeed after `default_latency`.
+pub(crate) struct SimProvider {
+    origin: Instant,
+    default_latency: Duration,
+    scripts: Mutex<HashMap<(SimOp, String), VecDeque<Step>>>,
+    calls: Arc<Mutex<Vec<SimCall>>>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl SimProvider {
+    pub fn new(default_latency: Duration) -> Self {
+        Self {
+            origin: Instant::now(),
+            default_latency,
+            scripts: Mutex::new(HashMap::new()),
+            calls: Arc::new(Mutex::new(Vec::new())),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Append `steps` to the script for `op` on `name`.
+    pub fn script(self, op: SimOp, name: &str, steps: impl IntoIterator<Item = Step>) -> Self {
+        self.scripts
+            .lock()
+            .unwrap()
+            .entry((op, name.to_string()))
+            .or_default()
+            .extend(steps);
+        self
+    }
+
+    pub fn latency(self, op: SimOp, name: &str, latency: Duration) -> Self {
+        self.script(op, name, [Step::ok(latency)])
+    }
+
+    pub fn fault(self, op: SimOp, name: &str, fault: Fault) -> Self {
+        let latency = self.default_latency;
+        self.script(op, name, [Step::fault(latency, fault)])
+    }
+
+    /// Every completed call, in completion order.
+    pub fn calls(&self) -> Vec<SimCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Highest number of provider calls observed in flight at once.
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Virtual time elapsed since the provider was constructed.
+    pub fn elapsed(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn next_step(&self, op: SimOp, name: &str) -> Step {
+        let mut scripts = self.scripts.lock().unwrap();
+        match scripts.get_mut(&(op, name.to_string())) {
+            Some(steps) if steps.len() > 1 => steps.pop_front().unwrap(),
+            Some(steps) if !steps.is_empty() => steps[0].clone(),
+            _ => Step::ok(self.default_latency),
+        }
+    }
+
+    fn run(&self, op: SimOp, id: &ResourceId) -> BoxFuture<'_, ProviderResult<State>> {
+        let id = id.clone();
+        let name = id.identity_or_empty().to_string();
+        let step = self.next_step(op, &name);
+        Box::pin(async move {
+            let now_in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight
+                .fetch_max(now_in_flight, Ordering::SeqCst);
+            let started = self.origin.elapsed();
+            tokio::time::sleep(step.latency).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            let result = match step.fault {
+                Some(fault) => Err(fault.into_error(&id)),
+                None => {
+                    let mut attrs = HashMap::new();
+                    attrs.insert(
+                        "id".to_string(),
+                        Value::Concrete(ConcreteValue::String(format!("{name}-id"))),
+                    );
+                    attrs.extend(step.attrs);
+                    Ok(State::existing(id.clone(), attrs).with_identifier(format!("{name}-id")))
+                }
+            };
+            self.calls.lock().unwrap().push(SimCall {
+                op,
+                name,
+                started,
+                finished: self.origin.elapsed(),
+                ok: result.is_ok(),
+            });
+            result
+        })
+    }
+}
+
+impl Provider for SimProvider {
+    fn name(&self) -> &str {
+        "sim"
+    }
+
+    fn read(
+        &self,
+        id: &ResourceId,
+        _identifier: Option<&str>,
+        _request: ReadRequest,
+    ) -> BoxFuture<'_, ProviderResult<State>> {
+        self.run(SimOp::Read, id)
+    }
+
+    fn read_data_source(&self, resource: &DataSource) -> BoxFuture<'_, ProviderResult<State>> {
+        self.run(SimOp::ReadDataSource, &resource.id)
+    }
+
+    fn create(
+        &self,
+        id: &ResourceId,
+        _request: CreateRequest,
+    ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+        let fut = self.run(SimOp::Create, id);
+        Box::pin(async move { fut.await.map(|state| CreateOutcome::Success { state }) })
+    }
+
+    fn update(
+        &self,
+        id: &ResourceId,
+        _identifier: &str,
+        _request: UpdateRequest,
+    ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+        let fut = self.run(SimOp::Update, id);
+        Box::pin(async move { fut.await.map(|state| UpdateOutcome::Success { state }) })
+    }
+
+    fn delete(
+        &self,
+        id: &ResourceId,
+        _identifier: &str,
+        _request: DeleteRequest,
+    ) -> BoxFuture<'_, ProviderResult<()>> {
+        let fut = self.run(SimOp::Delete, id);
+        Box::pin(async move { fut.await.map(|_| ()) })
+    }
+
+    fn required_permissions(&self, _id: &ResourceId, _op: crate::effect::PlanOp) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// `ExecutionInput` for a plan with no prior state, bindings, or schemas.
+pub(crate) fn sim_input(plan: &Plan, parallelism: usize) -> ExecutionInput<'_> {
+    ExecutionInput {
+        plan,
+        unresolved_resources: &EMPTY_UNRESOLVED,
+        compositions: &[],
+        bindings: ResolvedBindings::default(),
+        current_states: HashMap::new(),
+        deferred_data_source_reads: DeferredDataSourceReads::none(),
+        normalizer: &NoopNormalizer,
+        provider_configs: &[],
+        factories: &[],
+        schemas: &SIM_SCHEMAS,
+        parallelism: std::num::NonZeroUsize::new(parallelism).expect("parallelism must be > 0"),
+    }
+}
+
+static EMPTY_UNRESOLVED: LazyLock<HashMap<ResourceId, super::UnresolvedResource>> =
+    LazyLock::new(HashMap::new);
@@ -0,0 +1,303 @@
+//! Executor scenarios on the virtual clock. See [`super::sim`].
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use super::sim::{Fault, SimOp, SimProvider, Step, sim_input};
+use super::{ExecutionEvent, ExecutionObserver, ExecutionOutcome, ExecutionResult, execute_plan};
+use crate::effect::Effect;
+use crate::plan::Plan;
+use crate::resource::{
+    ConcreteValue, DataSource, ResolvedResource, ResolvedResourceId, Resource, ResourceIdentity,
+    Value,
+};
+use crate::wait::predicate::{AttrPath, WaitPredicate};
+
+const SECS_10: Duration = Duration::from_secs(10);
+
+struct NullObserver;
+
+impl ExecutionObserver for NullObserver {
+    fn on_event(&self, _event: &ExecutionEvent) {}
+}
+
+fn create(binding: &str, deps: &[&str]) -> Effect {
+    let mut r = Resource::new("test", binding);
+    r.binding = Some(binding.to_string());
+    for dep in deps {
+        r.set_attr(
+            format!("ref_{dep}"),
+            Value::resource_ref(dep.to_string(), "id".to_string(), vec![]),
+        );
+        r.dependency_bindings.insert(dep.to_string());
+    }
+    Effect::Create(ResolvedResource::new(r))
+}
+
+fn completed(outcome: ExecutionOutcome) -> ExecutionResult {
+    match outcome {
+        ExecutionOutcome::Completed(result) => result,
+        ExecutionOutcome::Cancelled(_) => panic!("uncancelled run returned Cancelled"),
+    }
+}
+
+fn applied_names(result: &ExecutionResult) -> HashSet<String> {
+    result
+        .applied_states
+        .keys()
+        .map(|id| id.identity_or_empty().to_string())
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn parallelism_cap_bounds_in_flight_calls_on_virtual_clock() {
+    let provider = SimProvider::new(SECS_10);
+    let mut plan = Plan::new();
+    for name in ["r1", "r2", "r3", "r4", "r5", "r6"] {
+        plan.add(create(name, &[]));
+    }
+
+    let result = completed(
+        execute_plan(
+            &provider,
+            sim_input(&plan, 2),
+            &NullObserver,
+            CancellationToken::new(),
+        )
+        .await,
+    );
+
+    assert_eq!(result.success_count, 6);
+    assert_eq!(provider.max_in_flight(), 2);
+    assert_eq!(provider.elapsed(), Duration::from_secs(30));
+}
+
+#[tokio::test(start_paused = true)]
+async fn dependent_starts_only_after_slow_dependency_finishes() {
+    let provider = SimProvider::new(Duration::from_secs(1)).latency(
+        SimOp::Create,
+        "vpc",
+        Duration::from_secs(45),
+    );
+    let mut plan = Plan::new();
+    plan.add(create("vpc", &[]));
+    plan.add(create("subnet", &["vpc"]));
+    plan.add(create("bucket", &[]));
+
+    let result = completed(
+        execute_plan(
+            &provider,
+            sim_input(&plan, 8),
+            &NullObserver,
+            CancellationToken::new(),
+        )
+        .await,
+    );
+
+    assert_eq!(result.success_count, 3);
+    let calls = provider.calls();
+    let started = |name: &str| calls.iter().find(|c| c.name == name).unwrap().started;
+    assert_eq!(started("bucket"), Duration::ZERO);
+    assert_eq!(started("subnet"), Duration::from_secs(45));
+}
+
+#[tokio::test(start_paused = true)]
+async fn injected_failure_skips_dependents_and_keeps_completed_states() {
+    let provider = SimProvider::new(SECS_10).fault(
+        SimOp::Create,
+        "vpc",
+        Fault::Fail("InvalidParameterValue".to_string()),
+    );
+    let mut plan = Plan::new();
+    plan.add(create("vpc", &[]));
+    plan.add(create("subnet", &["vpc"]));
+    plan.add(create("bucket", &[]));
+
+    let result = completed(
+        execute_plan(
+            &provider,
+            sim_input(&plan, 8),
+            &NullObserver,
+            CancellationToken::new(),
+        )
+        .await,
+    );
+
+    assert_eq!(result.failure_count, 1);
+    assert_eq!(result.skip_count, 1);
+    // The state checkpoint persisted after a failed apply holds exactly
+    // the effects that completed.
+    assert_eq!(
+        applied_names(&result),
+        HashSet::from(["bucket".to_string()])
+    );
+    assert!(provider.calls().iter().all(|c| c.name != "subnet"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancel_mid_run_lets_in_flight_call_finish_and_drops_the_rest() {
+    let provider = SimProvider::new(SECS_10);
+    let mut plan = Plan::new();
+    for name in ["r1", "r2", "r3", "r4"] {
+        plan.add(create(name, &[]));
+    }
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(15)).await;
+        trigger.cancel();
+    });
+
+    let outcome = execute_plan(&provider, sim_input(&plan, 1), &NullObserver, cancel).await;
+
+    let result = match outcome {
+        ExecutionOutcome::Cancelled(result) => result,
+        ExecutionOutcome::Completed(_) => panic!("cancelled run returned Completed"),
+    };
+    assert_eq!(
+        applied_names(&result),
+        HashSet::from(["r1".to_string(), "r2".to_string()])
+    );
+    assert_eq!(provider.calls().len(), 2);
+    assert_eq!(provider.elapsed(), Duration::from_secs(20));
+}
+
+#[tokio::test(start_paused = true)]
+async fn fatal_error_drains_in_flight_calls_and_dispatches_nothing_new() {
+    let provider = SimProvider::new(SECS_10)
+        .fault(SimOp::Create, "r1", Fault::ExpiredToken)
+        .latency(SimOp::Create, "r2", Duration::from_secs(60));
+    let mut plan = Plan::new();
+    for name in ["r1", "r2", "r3"] {
+        plan.add(create(name, &[]));
+    }
+
+    let result = completed(
+        execute_plan(
+            &provider,
+            sim_input(&plan, 2),
+            &NullObserver,
+            CancellationToken::new(),
+        )
+        .await,
+    );
+
+    assert_eq!(result.failure_count, 1);
+    assert_eq!(result.skip_count, 1);
+    // The slow sibling was allowed to finish, so its state is checkpointed.
+    assert_eq!(applied_names(&result), HashSet::from(["r2".to_string()]));
+    assert!(provider.calls().iter().all(|c| c.name != "r3"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn fatal_drain_abandons_calls_still_running_at_the_timeout() {
+    let provider = SimProvider::new(SECS_10)
+        .fault(SimOp::Create, "r1", Fault::ExpiredToken)
+        .latency(SimOp::Create, "r2", Duration::from_secs(3600));
+    let mut plan = Plan::new();
+    plan.add(create("r1", &[]));
+    plan.add(create("r2", &[]));
+    let started = tokio::time::Instant::now();
+
+    let result = completed(
+        execute_plan(
+            &provider,
+            sim_input(&plan, 2),
+            &NullObserver,
+            CancellationToken::new(),
+        )
+        .await,
+    );
+
+    assert_eq!(result.failure_count, 2);
+    assert!(result.applied_states.is_empty());
+    assert_eq!(
+        started.elapsed(),
+        SECS_10 + super::parallel::FATAL_DRAIN_TIMEOUT
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn wait_times_out_on_virtual_clock_without_real_sleep() {
+    // A slow unrelated create keeps a mutator in flight, so the wait runs
+    // to its deadline instead of being cut short as unsatisfiable.
+    let provider = SimProvider::new(Duration::from_secs(1))
+        .script(
+            SimOp::Read,
+            "cert",
+            [Step::ok(Duration::from_secs(1)).with_attr("status", "PENDING")],
+        )
+        .latency(SimOp::Create, "cluster", Duration::from_secs(900));
+    let mut plan = Plan::new();
+    plan.add(create("cluster", &[]));
+    let cert = create("cert", &[]);
+    let cert_id = cert.resource_id().clone();
+    plan.add(cert);
+    plan.add(Effect::Wait {
+        identity: ResourceIdentity::new("cert_ready"),
+        target_id: ResolvedResourceId::new(cert_id),
+        until: WaitPredicate::Equals {
+            attr: AttrPath::single("status"),
+            value: Value::Concrete(ConcreteValue::String("ISSUED".to_string())),
+        },
+        until_surface: "cert.status == ISSUED".to_string(),
+        timeout: Duration::from_secs(600),
+        interval: Duration::from_secs(30),
+        explicit_dependencies: HashSet::new(),
+    });
+
+    let real_start = std::time::Instant::now();
+    let result = completed(
+        execute_plan(
+            &provider,
+            sim_input(&plan, 3),
+            &NullObserver,
+            CancellationToken::new(),
+        )
+        .await,
+    );
+
+    assert_eq!(result.success_count, 2);
+    assert_eq!(result.failure_count, 1);
+    assert_eq!(provider.elapsed(), Duration::from_secs(900));
+    let reads = provider
+        .calls()
+        .iter()
+        .filter(|c| c.op == SimOp::Read)
+        .count();
+    // The wait starts at t=1s and reads every 31s (1s read latency + 30s
+    // interval); the 21st read lands at 621s, past the 600s deadline.
+    assert_eq!(reads, 21);
+    assert!(real_start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test(start_paused = true)]
+async fn data_source_read_backs_off_on_scripted_throttles() {
+    let provider = SimProvider::new(Duration::ZERO).script(
+        SimOp::ReadDataSource,
+        "roles",
+        [
+            Step::fault(Duration::ZERO, Fault::Throttle),
+            Step::fault(Duration::ZERO, Fault::Throttle),
+            Step::ok(Duration::ZERO),
+        ],
+    );
+    let ds = DataSource::new("test", "roles");
+
+    let state = super::read_data_source_with_retry(&provider, &ds)
+        .await
+        .expect("third attempt succeeds");
+
+    assert!(state.exists);
+    let starts: Vec<_> = provider.calls().iter().map(|c| c.started).collect();
+    assert_eq!(
+        starts,
+        vec![
+            Duration::ZERO,
+            Duration::from_secs(1),
+            Duration::from_secs(3)
+        ]
+    );
+}
@@ -90,6 +90,10 @@ struct MockProvider {
     update_requests: Arc<Mutex<Vec<UpdateRequest>>>,
     /// Data sources passed to `read_data_source()` in call order.
     data_source_reads: Arc<Mutex<Vec<DataSource>>>,
+    /// `CreateRequest::client_token`s passed to `create()` in call order.
+    create_tokens: Arc<Mutex<Vec<Option<String>>>>,
+    /// Identifiers `find_existing()` reports, keyed by resource id.
+    existing: Mutex<HashMap<String, String>>,
 }
 
 impl MockProvider {
@@ -103,6 +107,8 @@ impl MockProvider {
             create_resources: Arc::new(Mutex::new(Vec::new())),
             update_requests: Arc::new(Mutex::new(Vec::new())),
             data_source_reads: Arc::new(Mutex::new(Vec::new())),
+            create_tokens: Arc::new(Mutex::new(Vec::new())),
+            existing: Mutex::new(HashMap::new()),
         }
     }
 
@@ -147,6 +153,17 @@ impl MockProvider {
     fn captured_data_source_reads(&self) -> Vec<DataSource> {
         self.data_source_reads.lock().unwrap().clone()
     }
+
+    fn captured_create_tokens(&self) -> Vec<Option<String>> {
+        self.create_tokens.lock().unwrap().clone()
+    }
+
+    fn set_existing(&self, id: &ResourceId, identifier: &str) {
+        self.existing
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), identifier.to_string());
+    }
 }
 
 impl Provider for MockProvider {
@@ -191,6 +208,10 @@ impl Provider for MockProvider {
             .lock()
             .unwrap()
             .push(request.resource.as_resource().clone());
+        self.create_tokens
+            .lock()
+            .unwrap()
+            .push(request.client_token);
         let result = self.create_results.lock().unwrap().remove(0);
         Box::pin(async move { result })
     }
@@ -229,6 +250,15 @@ impl Provider for MockProvider {
     fn required_permissions(&self, _id: &ResourceId, _op: crate::effect::PlanOp) -> Vec<String> {
         Vec::new()
     }
+
+    fn find_existing(
+        &self,
+        id: &ResourceId,
+        _resource: &ResolvedResource,
+    ) -> BoxFuture<'_, ProviderResult<Option<String>>> {
+        let existing = self.existing.lock().unwrap().get(&id.to_string()).cloned();
+        Box::pin(async move { Ok(existing) })
+    }
 }
 
 fn call_position(calls: &[(String, String)], op: &str, id: &str) -> usize {
@@ -742,6 +772,7 @@ fn empty_execution_result() -> ExecutionResult {
         current_states: HashMap::new(),
         bindings: ResolvedBindings::default(),
         failed_refreshes: HashSet::new(),
+        operation_outcomes: HashMap::new(),
     }
 }
 
@@ -752,6 +783,7 @@ fn provider_config_with_default_tags(
         name: "test".to_string(),
         attributes: indexmap::IndexMap::new(),
         default_tags: tags,
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -775,6 +807,9 @@ struct DelayedCountingProvider {
     cancel_after_create: Option<String>,
     cancel: Option<CancellationToken>,
     started: Arc<Mutex<Vec<String>>>,
+    max_parallel_ops: Option<NonZeroUsize>,
+    active_creates: Arc<AtomicUsize>,
+    peak_creates: Arc<AtomicUsize>,
 }
 
 impl DelayedCountingProvider {
@@ -785,6 +820,9 @@ impl DelayedCountingProvider {
             cancel_after_create: None,
             cancel: None,
             started: Arc::new(Mutex::new(Vec::new())),
+            max_parallel_ops: None,
+            active_creates: Arc::new(AtomicUsize::new(0)),
+            peak_creates: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -833,12 +871,16 @@ impl Provider for DelayedCountingProvider {
         let started = self.started.clone();
         let cancel_after_create = self.cancel_after_create.clone();
         let cancel = self.cancel.clone();
+        let active = self.active_creates.clone();
+        let peak = self.peak_creates.clone();
         Box::pin(async move {
             started
                 .lock()
                 .unwrap()
                 .push(id.identity_or_empty().to_string());
+            peak.fetch_max(active.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
             tokio::time::sleep(delay).await;
+            active.fetch_sub(1, Ordering::SeqCst);
             if cancel_after_create.as_deref() == Some(id.identity_or_empty())
                 && let Some(cancel) = cancel
             {
@@ -894,6 +936,13 @@ impl Provider for DelayedCountingProvider {
     fn required_permissions(&self, _id: &ResourceId, _op: crate::effect::PlanOp) -> Vec<String> {
         Vec::new()
     }
+
+    fn capabilities(&self, _id: &ResourceId) -> crate::provider::ProviderCapabilities {
+        crate::provider::ProviderCapabilities {
+            max_parallel_ops: self.max_parallel_ops,
+            ..Default::default()
+        }
+    }
 }
 
 struct PendingWaitProvider {
@@ -1269,6 +1318,40 @@ async fn execute_plan_cancelled_after_three_completed_keeps_in_flight_and_drops_
     assert_eq!(provider.started_names(), vec!["r1", "r2", "r3"]);
 }
 
+#[tokio::test]
+async fn execute_plan_keeps_provider_within_its_max_parallel_ops() {
+    let mut provider = DelayedCountingProvider::new(std::time::Duration::from_millis(5));
+    provider.max_parallel_ops = NonZeroUsize::new(2);
+    let plan = create_independent_create_plan(["r1", "r2", "r3", "r4", "r5"]);
+    let input = ExecutionInput {
+        plan: &plan,
+        unresolved_resources: &HashMap::new(),
+        compositions: &[],
+        bindings: ResolvedBindings::default(),
+        current_states: HashMap::new(),
+        deferred_data_source_reads: DeferredDataSourceReads::none(),
+        normalizer: &NoopNormalizer,
+        provider_configs: &[],
+        factories: &[],
+        schemas: &TEST_SCHEMAS,
+        parallelism: crate::executor::TEST_UNCAPPED,
+    };
+
+    let outcome = execute_plan(
+        &provider,
+        input,
+        &MockObserver::new(),
+        CancellationToken::new(),
+    )
+    .await;
+
+    let ExecutionOutcome::Completed(result) = outcome else {
+        panic!("uncancelled execution returned Cancelled");
+    };
+    assert_eq!(result.success_count, 5);
+    assert_eq!(provider.peak_creates.load(Ordering::SeqCst), 2);
+}
+
 #[tokio::test]
 async fn execute_plan_cancels_in_flight_wait_effect_promptly() {
     use crate::wait::predicate::{AttrPath, WaitPredicate};
@@ -1496,7 +1579,7 @@ async fn test_simple_create() {
     );
 }
 
-#[tokio::test]
+#[tokio::test(start_paused = true)]
 async fn partial_create_records_state_and_diagnostic() {
     let provider = MockProvider::new();
     let resource = make_resource("a", &[]);
@@ -1515,6 +1598,9 @@ async fn partial_create_records_state_and_diagnostic() {
         "mock partial create".to_string(),
         vec!["computed".to_string()],
     )));
+    // The read-after-write verification read fails, so the create stays
+    // partial.
+    provider.push_read(Err(ProviderError::api_error("read failed")));
 
     let input = ExecutionInput {
         plan: &plan,
@@ -1542,6 +1628,136 @@ async fn partial_create_records_state_and_diagnostic() {
     assert!(observer.events().iter().any(|e| e.starts_with("partial:")));
 }
 
+#[tokio::test(start_paused = true)]
+async fn partial_create_completed_by_read_after_write_verification_succeeds() {
+    let provider = MockProvider::new();
+    let resource = make_resource("a", &[]);
+    let rid = resource.id.clone();
+
+    let mut plan = Plan::new();
+    plan.add(create_effect(resource));
+
+    provider.push_create_outcome(Ok(crate::provider::CreateOutcome::partial_success(
+        ok_state(&rid),
+        "read before the resource was consistent".to_string(),
+        vec!["computed".to_string()],
+    )));
+    provider.push_read(Ok(State::not_found(rid.clone())));
+    let mut complete = ok_state(&rid);
+    complete.attributes.insert(
+        "computed".to_string(),
+        Value::Concrete(ConcreteValue::String("late".to_string())),
+    );
+    provider.push_read(Ok(complete));
+
+    let input = ExecutionInput {
+        plan: &plan,
+        unresolved_resources: &HashMap::new(),
+        compositions: &[],
+        bindings: ResolvedBindings::default(),
+        current_states: HashMap::new(),
+        deferred_data_source_reads: DeferredDataSourceReads::none(),
+        normalizer: &NoopNormalizer,
+        provider_configs: &[],
+        factories: &[],
+        schemas: &TEST_SCHEMAS,
+        parallelism: crate::executor::TEST_UNCAPPED,
+    };
+
+    let observer = MockObserver::new();
+    let result =
+        completed_result(execute_plan(&provider, input, &observer, CancellationToken::new()).await);
+
+    assert_eq!(result.success_count, 1);
+    assert_eq!(result.partial_count, 0);
+    let state = &result.applied_states[&rid];
+    assert!(state.partial_read.is_none());
+    assert!(state.attributes.contains_key("computed"));
+}
+
+#[tokio::test]
+async fn create_sends_the_same_client_token_on_retry() {
+    let provider = MockProvider::new();
+    let resource = make_resource("a", &[]);
+    let rid = resource.id.clone();
+    let mut plan = Plan::new();
+    plan.add(create_effect(resource));
+
+    for _ in 0..2 {
+        provider.push_create(Ok(ok_state(&rid)));
+        let input = ExecutionInput {
+            plan: &plan,
+            unresolved_resources: &HashMap::new(),
+            compositions: &[],
+            bindings: ResolvedBindings::default(),
+            current_states: HashMap::new(),
+            deferred_data_source_reads: DeferredDataSourceReads::none(),
+            normalizer: &NoopNormalizer,
+            provider_configs: &[],
+            factories: &[],
+            schemas: &TEST_SCHEMAS,
+            parallelism: crate::executor::TEST_UNCAPPED,
+        };
+        let result = completed_result(
+            execute_plan(
+                &provider,
+                input,
+                &MockObserver::new(),
+                CancellationToken::new(),
+            )
+            .await,
+        );
+        assert_eq!(result.success_count, 1);
+    }
+
+    let tokens = provider.captured_create_tokens();
+    assert_eq!(tokens.len(), 2);
+    assert!(
+        tokens[0]
+            .as_deref()
+            .is_some_and(|t| t.starts_with("carina-"))
+    );
+    assert_eq!(tokens[0], tokens[1]);
+}
+
+#[tokio::test]
+async fn create_of_an_existing_name_fails_with_already_exists() {
+    let provider = MockProvider::new();
+    let resource = make_resource("a", &[]);
+    let rid = resource.id.clone();
+    provider.set_existing(&rid, "vpc-0abc");
+    let mut plan = Plan::new();
+    plan.add(create_effect(resource));
+
+    let input = ExecutionInput {
+        plan: &plan,
+        unresolved_resources: &HashMap::new(),
+        compositions: &[],
+        bindings: ResolvedBindings::default(),
+        current_states: HashMap::new(),
+        deferred_data_source_reads: DeferredDataSourceReads::none(),
+        normalizer: &NoopNormalizer,
+        provider_configs: &[],
+        factories: &[],
+        schemas: &TEST_SCHEMAS,
+        parallelism: crate::executor::TEST_UNCAPPED,
+    };
+    let observer = MockObserver::new();
+    let result =
+        completed_result(execute_plan(&provider, input, &observer, CancellationToken::new()).await);
+
+    assert_eq!(result.failure_count, 1);
+    assert!(provider.calls().is_empty(), "{:?}", provider.calls());
+    assert!(
+        observer
+            .events()
+            .iter()
+            .any(|e| e.contains("already exists as vpc-0abc")),
+        "{:?}",
+        observer.events()
+    );
+}
+
 /// carina#3060: the apply execution path must re-apply the provider
 /// normalizer after reference re-resolution, before building the
 /// provider request. Plan-time normalization is undone when the
@@ -1725,6 +1941,51 @@ async fn test_apply_reapplies_enum_alias_stage_update_path() {
     );
 }
 
+/// A resource's `timeouts { update = ... }` reaches the provider on the
+/// update request.
+#[tokio::test]
+async fn test_apply_passes_update_timeout_to_provider() {
+    let provider = MockProvider::new();
+    let mut to_resource = Resource::with_provider("test", "nat", "a", None);
+    to_resource.set_attr(
+        "name",
+        Value::Concrete(ConcreteValue::String("b".to_string())),
+    );
+    to_resource.directives.timeouts.update = Some(std::time::Duration::from_secs(1800));
+    let rid = to_resource.id.clone();
+    let from_state = State::existing(rid.clone(), HashMap::new()).with_identifier("id-123");
+
+    let mut plan = Plan::new();
+    plan.add(Effect::Update {
+        from: Box::new(from_state),
+        to: resolved(to_resource),
+        changed_attributes: vec!["name".to_string()],
+    });
+    provider.push_update(Ok(ok_state(&rid)));
+
+    let input = ExecutionInput {
+        plan: &plan,
+        unresolved_resources: &HashMap::new(),
+        compositions: &[],
+        bindings: ResolvedBindings::default(),
+        current_states: HashMap::new(),
+        deferred_data_source_reads: DeferredDataSourceReads::none(),
+        normalizer: &NoopNormalizer,
+        provider_configs: &[],
+        factories: &[],
+        schemas: &TEST_SCHEMAS,
+        parallelism: crate::executor::TEST_UNCAPPED,
+    };
+
+    let observer = MockObserver::new();
+    let result =
+        completed_result(execute_plan(&provider, input, &observer, CancellationToken::new()).await);
+    assert_eq!(result.success_count, 1);
+
+    let reqs = provider.captured_update_requests();
+    assert_eq!(reqs[0].timeout, Some(std::time::Duration::from_secs(1800)));
+}
+
 /// carina#3063, canonicalize stage (plan pipeline stage 1): the apply
 /// path must also re-apply `canonicalize_resources_with_schemas`. With
 /// a schema declaring `subject` as `Union[String, list(String)]`, a
@@ -2489,7 +2750,8 @@ async fn test_failed_effect_propagates_to_dependent() {
     let provider = MockProvider::new();
     let ra = make_resource("a", &[]);
     let rb = make_resource("b", &["a"]);
-    let _rid_a = ra.id.clone();
+    let rid_a = ra.id.clone();
+    let rid_b = rb.id.clone();
 
     let mut plan = Plan::new();
     plan.add(create_effect(ra));
@@ -2525,6 +2787,15 @@ async fn test_failed_effect_propagates_to_dependent() {
             .iter()
             .any(|e| e.contains("skipped:") && e.contains("dependency 'a' failed"))
     );
+
+    let failed = &result.operation_outcomes[&rid_a];
+    assert_eq!(failed.status, OperationStatus::Failed);
+    assert_eq!(failed.operation, "create");
+    assert_eq!(failed.error_kind, Some("api_error"));
+    assert!(failed.message.as_deref().unwrap().contains("create failed"));
+    let skipped = &result.operation_outcomes[&rid_b];
+    assert_eq!(skipped.status, OperationStatus::Skipped);
+    assert_eq!(skipped.error_kind, None);
 }
 
 #[tokio::test]
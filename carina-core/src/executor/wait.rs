@@ -11,9 +11,10 @@ use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::time::Instant;
 
 use crate::executor::scheduler::FailureView;
 use crate::executor::{ExecutionEvent, ExecutionObserver};
@@ -180,6 +181,10 @@ fn format_wait_last_attrs(last_attrs: &HashMap<String, Value>) -> String {
 /// string to render cancelled effects distinctly from cascade failures.
 pub(super) const SKIP_REASON_CANCELLED: &str = "cancelled";
 
+/// Skip reason emitted for effects left undispatched after a provider error
+/// that makes the rest of the run pointless (expired or rejected credentials).
+pub(super) const SKIP_REASON_FATAL: &str = "aborted after a fatal provider error";
+
 /// Signal all in-flight Wait effect cancellers, causing their polling to
 /// abort early. Used when cancel observation needs to drain in-flight Wait
 /// effects without waiting for their natural timeout.
@@ -263,6 +268,13 @@ impl<'fut, R> WaitAwareInFlight<'fut, R> {
         self.inner.len()
     }
 
+    /// Indices of the futures currently in flight, in ascending order.
+    pub(super) fn in_flight_indices(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = self.kinds.keys().copied().collect();
+        indices.sort_unstable();
+        indices
+    }
+
     /// Signal every in-flight wait to stop because external cancellation was observed.
     pub(super) fn signal_in_flight_waits(&self) {
         signal_in_flight_waits(&self.cancellers);
@@ -60,8 +60,10 @@ pub enum NodeKind {
     File,
     UseExpr,
     BackendBlock,
+    NamingBlock,
     ProviderBlock,
     ArgumentsBlock,
+    VariablesBlock,
     AttributesBlock,
     ExportsBlock,
     ArgumentsParam,
@@ -70,12 +70,18 @@ impl<'a> CstBuilder<'a> {
             Rule::backend_block => Some(CstChild::Node(
                 self.build_node(NodeKind::BackendBlock, pair),
             )),
+            Rule::naming_block => {
+                Some(CstChild::Node(self.build_node(NodeKind::NamingBlock, pair)))
+            }
             Rule::provider_block => Some(CstChild::Node(
                 self.build_node(NodeKind::ProviderBlock, pair),
             )),
             Rule::arguments_block => Some(CstChild::Node(
                 self.build_node(NodeKind::ArgumentsBlock, pair),
             )),
+            Rule::variables_block => Some(CstChild::Node(
+                self.build_node(NodeKind::VariablesBlock, pair),
+            )),
             Rule::attributes_block => Some(CstChild::Node(
                 self.build_node(NodeKind::AttributesBlock, pair),
             )),
@@ -275,6 +281,10 @@ impl<'a> CstBuilder<'a> {
                 Some(CstChild::Token(Token::new(pair.as_str().to_string(), span)))
             }
             Rule::duration_unit => None,
+            Rule::byte_size_literal => {
+                Some(CstChild::Token(Token::new(pair.as_str().to_string(), span)))
+            }
+            Rule::byte_size_unit => None,
             Rule::boolean => Some(CstChild::Token(Token::new(pair.as_str().to_string(), span))),
             Rule::inner_string
             | Rule::char
@@ -300,9 +310,11 @@ impl<'a> CstBuilder<'a> {
             Rule::kw_import => Some(CstChild::Token(Token::new("import".to_string(), span))),
             Rule::kw_use => Some(CstChild::Token(Token::new("use".to_string(), span))),
             Rule::kw_backend => Some(CstChild::Token(Token::new("backend".to_string(), span))),
+            Rule::kw_naming => Some(CstChild::Token(Token::new("naming".to_string(), span))),
             Rule::kw_provider => Some(CstChild::Token(Token::new("provider".to_string(), span))),
             Rule::kw_let => Some(CstChild::Token(Token::new("let".to_string(), span))),
             Rule::kw_arguments => Some(CstChild::Token(Token::new("arguments".to_string(), span))),
+            Rule::kw_variables => Some(CstChild::Token(Token::new("variables".to_string(), span))),
             Rule::kw_attributes => {
                 Some(CstChild::Token(Token::new("attributes".to_string(), span)))
             }
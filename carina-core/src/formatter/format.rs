@@ -23,7 +23,7 @@ pub fn format(source: &str, config: &FormatConfig) -> Result<String, FormatParse
     let preprocess_result =
         crate::heredoc::preprocess_heredocs(source).map_err(|e| FormatParseError {
             message: e.to_string(),
-            line: 0,
+            line: e.line(),
             column: 0,
         })?;
     let pairs = parser::parse(&preprocess_result.source)?;
@@ -47,7 +47,7 @@ pub fn format_with_block_names(
     let preprocess_result =
         crate::heredoc::preprocess_heredocs(source).map_err(|e| FormatParseError {
             message: e.to_string(),
-            line: 0,
+            line: e.line(),
             column: 0,
         })?;
     let pairs = parser::parse(&preprocess_result.source)?;
@@ -175,8 +175,11 @@ impl Formatter {
         match node.kind {
             NodeKind::UseExpr => self.format_use_expr(node),
             NodeKind::BackendBlock => self.format_backend_block(node),
+            NodeKind::NamingBlock => self.format_naming_block(node),
             NodeKind::ProviderBlock => self.format_provider_block(node),
-            NodeKind::ArgumentsBlock => self.format_arguments_block(node),
+            NodeKind::ArgumentsBlock | NodeKind::VariablesBlock => {
+                self.format_arguments_block(node)
+            }
             NodeKind::AttributesBlock => self.format_attributes_block(node),
             NodeKind::ExportsBlock => self.format_attributes_block(node), // same format as attributes
             NodeKind::LetBinding => self.format_let_binding(node),
@@ -323,6 +326,18 @@ mod tests {
         assert!(result.contains("  region = aws.Region.ap_northeast_1"));
     }
 
+    #[test]
+    fn test_format_naming_block() {
+        let input = "naming {\ntemplate='{env}-{name}'\n    env   = 'prd'\n}";
+        let config = FormatConfig::default();
+        let result = format(input, &config).unwrap();
+
+        assert_eq!(
+            result,
+            "naming {\n  template = '{env}-{name}'\n  env      = 'prd'\n}\n"
+        );
+    }
+
     #[test]
     fn test_format_preserves_comments() {
         let input = "# Header comment\nprovider aws {}\n";
@@ -1332,6 +1347,13 @@ mod tests {
         assert_eq!(first, second, "Formatting should be idempotent");
     }
 
+    #[test]
+    fn format_variables_block_keeps_its_keyword() {
+        let input = "variables {\n  env:String=\"dev\"\n}\n";
+        let result = format(input, &FormatConfig::default()).unwrap();
+        assert_eq!(result, "variables {\n  env: String = 'dev'\n}\n");
+    }
+
     #[test]
     fn format_arguments_mixed_with_alignment() {
         let input = r#"arguments {
@@ -6,8 +6,12 @@ use super::super::format::Formatter;
 
 impl Formatter {
     pub(in crate::formatter) fn format_arguments_block(&mut self, node: &CstNode) {
+        let keyword = match node.kind {
+            NodeKind::VariablesBlock => "variables",
+            _ => "arguments",
+        };
         self.write_indent();
-        self.write("arguments {");
+        self.write(&format!("{keyword} {{"));
         self.write_newline();
         self.current_indent += 1;
 
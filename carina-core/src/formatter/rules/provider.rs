@@ -1,4 +1,4 @@
-//! Formatter methods for `provider` and `backend` blocks.
+//! Formatter methods for `provider`, `backend`, and `naming` blocks.
 
 use super::super::cst::{CstChild, CstNode};
 use super::super::format::Formatter;
@@ -31,6 +31,20 @@ impl Formatter {
         self.write_newline();
     }
 
+    pub(in crate::formatter) fn format_naming_block(&mut self, node: &CstNode) {
+        self.write_indent();
+        self.write("naming {");
+        self.write_newline();
+        self.current_indent += 1;
+
+        self.format_block_attributes(node);
+
+        self.current_indent -= 1;
+        self.write_indent();
+        self.write("}");
+        self.write_newline();
+    }
+
     pub(in crate::formatter) fn format_provider_block(&mut self, node: &CstNode) {
         self.write_indent();
         self.write("provider ");
@@ -0,0 +1,297 @@
+//! Resource dependency graph rendered as Graphviz DOT or Mermaid.
+//!
+//! Nodes are the managed resources of a configuration, filled with one
+//! colour per provider. Edges point from a resource to the resources
+//! that reference it, so everything reachable from a node is what a
+//! change to it can ripple into. When built with a plan, the resources
+//! the plan acts on are outlined in the colour of their action, and
+//! resources the plan deletes that are no longer in the configuration
+//! are added as unconnected nodes.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+
+use crate::deps::get_resource_dependencies;
+use crate::plan::Plan;
+use crate::plan_changes::{ChangeAction, PlanChanges};
+use crate::resource::Resource;
+
+/// Fill colours assigned to providers in name order, wrapping around.
+const PROVIDER_FILLS: [&str; 6] = [
+    "#dbeafe", "#fef3c7", "#dcfce7", "#fce7f3", "#ede9fe", "#e0f2fe",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    /// `provider.Type name`, as printed by `carina plan`.
+    pub address: String,
+    pub provider: String,
+    pub resource_type: String,
+    /// What the plan does to this resource; `None` when it is unchanged
+    /// or the graph was built without a plan.
+    pub change: Option<ChangeAction>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DependencyGraph {
+    /// Sorted by address.
+    pub nodes: Vec<GraphNode>,
+    /// `(dependency, dependent)` pairs of indices into `nodes`, sorted.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl DependencyGraph {
+    /// Build the graph of `resources`, highlighting what `plan` changes.
+    ///
+    /// Dependencies are the bindings a resource references or lists in
+    /// `depends_on`; references to anything that is not a managed
+    /// resource (data sources, modules, upstream state) are dropped.
+    pub fn new(resources: &[Resource], plan: Option<&Plan>) -> Self {
+        let changes: HashMap<String, ChangeAction> = plan
+            .map(|plan| {
+                PlanChanges::from_plan(plan, &HashMap::new())
+                    .resource_changes
+                    .into_iter()
+                    .filter(|change| change.action != ChangeAction::Read)
+                    .map(|change| (change.address, change.action))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut nodes: Vec<GraphNode> = resources
+            .iter()
+            .map(|resource| {
+                let address = resource.id.human().to_string();
+                GraphNode {
+                    change: changes.get(&address).copied(),
+                    address,
+                    provider: resource.id.provider.clone(),
+                    resource_type: resource.id.resource_type.clone(),
+                }
+            })
+            .collect();
+        // Resources the plan acts on that the configuration no longer
+        // declares (deletes of removed blocks, state-only removals).
+        if let Some(plan) = plan {
+            for effect in plan.effects() {
+                let id = effect.resource_id();
+                let address = id.human().to_string();
+                if let Some(&action) = changes.get(&address)
+                    && !nodes.iter().any(|n| n.address == address)
+                {
+                    nodes.push(GraphNode {
+                        address,
+                        provider: id.provider.clone(),
+                        resource_type: id.resource_type.clone(),
+                        change: Some(action),
+                    });
+                }
+            }
+        }
+        nodes.sort_by(|a, b| a.address.cmp(&b.address));
+
+        let index: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.address.as_str(), i))
+            .collect();
+        let by_binding: HashMap<&str, usize> = resources
+            .iter()
+            .filter_map(|resource| {
+                let binding = resource.binding.as_deref()?;
+                let address = resource.id.human().to_string();
+                Some((binding, index[address.as_str()]))
+            })
+            .collect();
+        let mut edges = BTreeSet::new();
+        for resource in resources {
+            let dependent = index[resource.id.human().to_string().as_str()];
+            for binding in get_resource_dependencies(resource) {
+                if let Some(&dependency) = by_binding.get(binding.as_str())
+                    && dependency != dependent
+                {
+                    edges.insert((dependency, dependent));
+                }
+            }
+        }
+
+        Self {
+            nodes,
+            edges: edges.into_iter().collect(),
+        }
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let fills = self.provider_fills();
+        let mut out = String::from("digraph carina {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut label = format!("{}\\n{}", node.resource_type, node.address);
+            let mut attrs = format!("fillcolor=\"{}\"", fills[node.provider.as_str()]);
+            if let Some(action) = node.change {
+                let _ = write!(label, "\\n({})", action_label(action));
+                let _ = write!(attrs, ", color=\"{}\", penwidth=3", action_color(action));
+            }
+            let _ = writeln!(out, "  n{i} [label=\"{}\", {attrs}];", dot_escape(&label));
+        }
+        for (from, to) in &self.edges {
+            let _ = writeln!(out, "  n{from} -> n{to};");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    pub fn to_mermaid(&self) -> String {
+        let fills = self.provider_fills();
+        let mut out = String::from("flowchart LR\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut label = format!("{}<br/>{}", node.resource_type, node.address);
+            if let Some(action) = node.change {
+                let _ = write!(label, "<br/>({})", action_label(action));
+            }
+            let _ = writeln!(out, "  n{i}[\"{}\"]", label.replace('"', "#quot;"));
+        }
+        for (from, to) in &self.edges {
+            let _ = writeln!(out, "  n{from} --> n{to}");
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut style = format!("fill:{}", fills[node.provider.as_str()]);
+            if let Some(action) = node.change {
+                let _ = write!(style, ",stroke:{},stroke-width:3px", action_color(action));
+            }
+            let _ = writeln!(out, "  style n{i} {style}");
+        }
+        out
+    }
+
+    fn provider_fills(&self) -> HashMap<&str, &'static str> {
+        let providers: BTreeSet<&str> = self.nodes.iter().map(|n| n.provider.as_str()).collect();
+        providers
+            .into_iter()
+            .enumerate()
+            .map(|(i, provider)| (provider, PROVIDER_FILLS[i % PROVIDER_FILLS.len()]))
+            .collect()
+    }
+}
+
+fn action_label(action: ChangeAction) -> &'static str {
+    match action {
+        ChangeAction::Create => "create",
+        ChangeAction::Update => "update",
+        ChangeAction::Delete => "delete",
+        ChangeAction::Replace => "replace",
+        ChangeAction::Read => "read",
+        ChangeAction::Import => "import",
+        ChangeAction::Remove => "remove",
+        ChangeAction::Move => "move",
+        ChangeAction::DeferredCreate => "create at apply",
+        ChangeAction::DeferredReplace => "replace at apply",
+    }
+}
+
+/// Outline colour of a changed node, matching `carina plan`'s symbols:
+/// green creates, yellow in-place changes, red deletes, magenta
+/// replacements.
+fn action_color(action: ChangeAction) -> &'static str {
+    match action {
+        ChangeAction::Create | ChangeAction::DeferredCreate | ChangeAction::Import => "#16a34a",
+        ChangeAction::Update | ChangeAction::Move | ChangeAction::Read => "#ca8a04",
+        ChangeAction::Delete | ChangeAction::Remove => "#dc2626",
+        ChangeAction::Replace | ChangeAction::DeferredReplace => "#c026d3",
+    }
+}
+
+fn dot_escape(label: &str) -> String {
+    label.replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::Effect;
+    use crate::resource::{ResolvedResource, ResourceId, Value};
+
+    fn resource(resource_type: &str, binding: &str) -> Resource {
+        let mut resource = Resource::with_provider("awscc", resource_type, binding, None);
+        resource.binding = Some(binding.to_string());
+        resource
+    }
+
+    fn vpc_and_subnet() -> Vec<Resource> {
+        let vpc = resource("ec2.Vpc", "vpc");
+        let subnet = resource("ec2.Subnet", "subnet")
+            .with_attribute("vpc_id", Value::resource_ref("vpc", "vpc_id", vec![]));
+        vec![vpc, subnet]
+    }
+
+    #[test]
+    fn edges_point_from_dependency_to_dependent() {
+        let graph = DependencyGraph::new(&vpc_and_subnet(), None);
+        let addresses: Vec<&str> = graph.nodes.iter().map(|n| n.address.as_str()).collect();
+        assert_eq!(addresses, ["awscc.ec2.Subnet subnet", "awscc.ec2.Vpc vpc"]);
+        assert_eq!(graph.edges, [(1, 0)]);
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("  n1 -> n0;\n"), "{dot}");
+        assert!(!dot.contains("penwidth"), "{dot}");
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"), "{mermaid}");
+        assert!(mermaid.contains("  n1 --> n0\n"), "{mermaid}");
+    }
+
+    #[test]
+    fn plan_changes_are_highlighted_and_removed_resources_added() {
+        let resources = vpc_and_subnet();
+        let mut plan = Plan::new();
+        plan.add(Effect::Create(ResolvedResource::new(resources[1].clone())));
+        let gone = ResourceId::with_provider_identity("awscc", "ec2.Eip", "eip", None);
+        plan.add(Effect::Delete {
+            id: crate::resource::ResolvedResourceId::new(gone),
+            identifier: "eipalloc-1".to_string(),
+            directives: Default::default(),
+            binding: None,
+            dependencies: Default::default(),
+            explicit_dependencies: Default::default(),
+            blocked_by_updates: Default::default(),
+        });
+
+        let graph = DependencyGraph::new(&resources, Some(&plan));
+        let changes: Vec<(&str, Option<ChangeAction>)> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.address.as_str(), n.change))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("awscc.ec2.Eip eip", Some(ChangeAction::Delete)),
+                ("awscc.ec2.Subnet subnet", Some(ChangeAction::Create)),
+                ("awscc.ec2.Vpc vpc", None),
+            ]
+        );
+        let dot = graph.to_dot();
+        assert!(
+            dot.contains("(create)\", fillcolor=\"#dbeafe\", color=\"#16a34a\", penwidth=3"),
+            "{dot}"
+        );
+        let mermaid = graph.to_mermaid();
+        assert!(
+            mermaid.contains("style n0 fill:#dbeafe,stroke:#dc2626,stroke-width:3px"),
+            "{mermaid}"
+        );
+    }
+}
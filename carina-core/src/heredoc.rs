@@ -8,6 +8,10 @@
 //! - `<<-MARKER` — interpolating + indented (common leading whitespace stripped)
 //! - `<<'MARKER'` — literal heredoc (`${...}` is NOT expanded)
 //! - `<<-'MARKER'` — literal + indented
+//!
+//! Backslashes in a heredoc body are literal. In the interpolating
+//! modes, `$${` produces a literal `${` (e.g. `$${HOME}` in a shell
+//! script).
 
 /// Result of preprocessing: the transformed source and a list of heredoc
 /// replacements (for the formatter to restore them in the output).
@@ -53,6 +57,7 @@ pub fn preprocess_heredocs(input: &str) -> Result<PreprocessResult, HeredocError
             original.push('\n');
 
             // Collect body lines until we find the closing marker
+            let start_line = i + 1;
             let mut body_lines: Vec<&str> = Vec::new();
             i += 1;
             let mut found_end = false;
@@ -72,7 +77,7 @@ pub fn preprocess_heredocs(input: &str) -> Result<PreprocessResult, HeredocError
 
             if !found_end {
                 return Err(HeredocError::Unterminated {
-                    line: i,
+                    line: start_line,
                     marker: marker.to_string(),
                 });
             }
@@ -247,12 +252,14 @@ fn escape_for_double_quote(s: &str) -> String {
 }
 
 /// Escape for double quotes but preserve `${...}` for interpolation.
+/// `$${` is the heredoc escape for a literal `${`.
 fn escape_for_double_quote_interpolating(s: &str) -> String {
     s.replace('\\', "\\\\")
         .replace('"', "\\\"")
         .replace('\n', "\\n")
         .replace('\r', "\\r")
         .replace('\t', "\\t")
+        .replace("$${", "\\${")
 }
 
 /// Check if a line contains a heredoc start (`<<MARKER` or `<<-MARKER`),
@@ -267,10 +274,20 @@ pub fn find_heredoc_marker_with_quoted(line: &str) -> Option<(String, bool)> {
 /// Error during heredoc preprocessing.
 #[derive(Debug, thiserror::Error)]
 pub enum HeredocError {
+    /// `line` is the 1-indexed line holding the `<<MARKER` opener.
     #[error("Unterminated heredoc at line {line}: closing marker '{marker}' not found")]
     Unterminated { line: usize, marker: String },
 }
 
+impl HeredocError {
+    /// 1-indexed source line the error points at.
+    pub fn line(&self) -> usize {
+        match self {
+            HeredocError::Unterminated { line, .. } => *line,
+        }
+    }
+}
+
 struct HeredocStart<'a> {
     /// Byte offset where the heredoc operator starts
     prefix_end: usize,
@@ -416,6 +433,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_unterminated_heredoc_points_at_the_opener() {
+        let input = "a = 1\n\nx = <<EOT\nhello\nworld\n";
+        let err = preprocess_heredocs(input).unwrap_err();
+        assert_eq!(err.line(), 3);
+    }
+
+    #[test]
+    fn test_double_dollar_escapes_interpolation() {
+        let input = "x = <<EOT\necho $${HOME} ${dir}\nEOT\n";
+        let result = preprocess_heredocs(input).unwrap();
+        assert_eq!(result.source, "x = \"echo \\${HOME} ${dir}\"\n");
+
+        // Literal heredocs keep `$${` verbatim.
+        let input = "x = <<'EOT'\n$${HOME}\nEOT\n";
+        let result = preprocess_heredocs(input).unwrap();
+        assert_eq!(result.source, "x = \"$\\${HOME}\"\n");
+    }
+
     #[test]
     fn test_unquoted_heredoc_allows_interpolation() {
         // <<EOT (unquoted) should NOT escape ${...} — allows interpolation
@@ -217,6 +217,7 @@ fn deterministic_value_string(value: &Value) -> String {
         Value::Concrete(ConcreteValue::Float(f)) => format!("Float({})", f),
         Value::Concrete(ConcreteValue::Bool(b)) => format!("Bool({})", b),
         Value::Concrete(ConcreteValue::Duration(d)) => format!("Duration({})", d.as_secs()),
+        Value::Concrete(ConcreteValue::ByteSize(n)) => format!("ByteSize({n})"),
         Value::Concrete(ConcreteValue::List(items)) => {
             let parts: Vec<String> = items.iter().map(deterministic_value_string).collect();
             format!("List([{}])", parts.join(", "))
@@ -285,6 +286,7 @@ fn deterministic_value_string(value: &Value) -> String {
                 UnknownReason::PostCreateReadIncomplete { detail } => {
                     format!("Unknown(PostCreateReadIncomplete({detail}))")
                 }
+                UnknownReason::ForGroup { binding } => format!("Unknown(ForGroup({binding}))"),
             }
         }
     }
@@ -303,6 +305,7 @@ fn canonical_create_only_value_string(value: &Value) -> Option<String> {
         // State JSON stores durations as integer seconds, so the comparison
         // channel must use the same form as the state-side Int round-trip.
         Value::Concrete(ConcreteValue::Duration(d)) => Some(format!("Int({})", d.as_secs())),
+        Value::Concrete(ConcreteValue::ByteSize(n)) => Some(format!("Int({n})")),
         Value::Concrete(ConcreteValue::List(items)) => {
             let parts: Vec<String> = items
                 .iter()
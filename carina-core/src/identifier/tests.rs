@@ -57,6 +57,7 @@ fn provider_config(name: &str, attrs: Vec<(&str, Value)>) -> ProviderConfig {
             .map(|(key, value)| (key.to_string(), value))
             .collect(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -860,6 +861,7 @@ fn test_reconcile_anonymous_id_partial_create_only_match() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1065,6 +1067,7 @@ fn test_anonymous_resource_inside_module_keeps_instance_prefix() {
         .into_iter()
         .collect(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1121,6 +1124,7 @@ fn test_anonymous_resource_no_create_only_properties() {
         .into_iter()
         .collect(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1167,6 +1171,7 @@ fn test_anonymous_resource_no_create_only_deterministic() {
         .into_iter()
         .collect(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1209,6 +1214,7 @@ fn test_anonymous_resource_no_create_only_collision() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1259,6 +1265,7 @@ fn test_identity_attribute_prevents_collision() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2084,6 +2091,7 @@ fn test_reconcile_anonymous_id_no_create_only_hamming_match() {
         .into_iter()
         .collect(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2300,6 +2308,7 @@ fn test_reconcile_anonymous_id_create_only_exists_but_none_set() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2497,6 +2506,7 @@ fn test_reconcile_no_create_only_picks_closest_among_multiple_state_entries() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2632,6 +2642,7 @@ fn test_reconcile_no_create_only_same_id_in_state_no_change() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2707,6 +2718,7 @@ fn test_compute_anonymous_id_uses_simhash_for_no_create_only() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2770,6 +2782,7 @@ fn test_compute_anonymous_id_simhash_vs_create_only_hash_independent() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2898,6 +2911,7 @@ fn test_compute_anonymous_id_stable_with_prefixed_create_only_attribute() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2943,6 +2957,7 @@ fn test_compute_anonymous_id_different_prefix_produces_different_id() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -3140,6 +3155,7 @@ fn test_reconcile_eip_tag_update_with_unset_create_only_props() {
         .into_iter()
         .collect(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -3930,6 +3946,7 @@ fn anonymous_identifier_includes_provider_prefix() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -3969,6 +3986,7 @@ fn anonymous_identifier_provider_prefix_for_aws_provider() {
         name: "aws".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -4008,6 +4026,7 @@ fn reconcile_simhash_match_keeps_new_format_identifier_and_emits_rename() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
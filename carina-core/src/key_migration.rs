@@ -0,0 +1,234 @@
+//! State key migration for `for` expressions that switch from list
+//! iteration to map iteration.
+//!
+//! Iterating a list addresses instances by position (`subnets[0]`);
+//! iterating a map addresses them by key (`subnets.a`). Rewriting a loop
+//! from one to the other therefore plans every instance as a delete plus
+//! a create. [`suggest_key_moves`] pairs those deletes and creates back
+//! up so the plan can offer the `moved` blocks that keep the existing
+//! resources instead.
+//!
+//! A deleted `binding[N]` is paired with a created `binding.key` (or
+//! `binding['key']`) of the same provider and resource type when every
+//! concrete attribute the new instance declares matches what state
+//! recorded for the old one, and no other created instance matches too.
+
+use std::collections::HashMap;
+
+use crate::effect::Effect;
+use crate::plan::Plan;
+use crate::resource::{ResourceId, State, Value};
+
+/// One suggested index → key move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMove {
+    pub from: ResourceId,
+    pub to: ResourceId,
+}
+
+impl KeyMove {
+    /// The `moved` block that carries the state across.
+    pub fn moved_block(&self) -> String {
+        format!(
+            "moved {{\n  from = {} {}\n  to   = {} {}\n}}",
+            self.from.display_type(),
+            quote_address(self.from.identity_or_empty()),
+            self.to.display_type(),
+            quote_address(self.to.identity_or_empty())
+        )
+    }
+}
+
+/// Single-quote an address unless it already holds a single-quoted key
+/// (`subnets['us-east-1a']`), which needs double quotes around it.
+fn quote_address(address: &str) -> String {
+    if address.contains('\'') {
+        format!("\"{}\"", address)
+    } else {
+        format!("'{}'", address)
+    }
+}
+
+/// Pair index-addressed deletes in `plan` with key-addressed creates of
+/// the same loop binding. `current_states` supplies the attributes of
+/// the deleted instances.
+pub fn suggest_key_moves(plan: &Plan, current_states: &HashMap<ResourceId, State>) -> Vec<KeyMove> {
+    let creates: Vec<_> = plan
+        .effects()
+        .iter()
+        .filter_map(|effect| match effect {
+            Effect::Create(resource) => Some(resource),
+            _ => None,
+        })
+        .filter(|resource| {
+            resource
+                .id
+                .identity_str()
+                .and_then(key_address_binding)
+                .is_some()
+        })
+        .collect();
+    let mut taken = vec![false; creates.len()];
+    let mut moves = Vec::new();
+
+    for effect in plan.effects() {
+        let Effect::Delete { id, .. } = effect else {
+            continue;
+        };
+        let Some(binding) = index_address_binding(id.identity_str()) else {
+            continue;
+        };
+        let Some(state) = current_states.get(id.as_inner()) else {
+            continue;
+        };
+        let candidates: Vec<usize> = creates
+            .iter()
+            .enumerate()
+            .filter(|(i, create)| {
+                !taken[*i]
+                    && create.id.provider == id.provider
+                    && create.id.resource_type == id.resource_type
+                    && create.id.identity_str().and_then(key_address_binding) == Some(binding)
+                    && attributes_match(&create.attributes, &state.attributes)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if let [only] = candidates[..] {
+            taken[only] = true;
+            moves.push(KeyMove {
+                from: id.as_inner().clone(),
+                to: creates[only].id.clone(),
+            });
+        }
+    }
+    moves
+}
+
+/// `subnets[0]` → `subnets`.
+fn index_address_binding(address: &str) -> Option<&str> {
+    let (binding, rest) = address.split_once('[')?;
+    let index = rest.strip_suffix(']')?;
+    (!binding.is_empty() && !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+        .then_some(binding)
+}
+
+/// `subnets.a` / `subnets['a-1']` → `subnets`.
+fn key_address_binding(address: &str) -> Option<&str> {
+    if let Some((binding, rest)) = address.split_once("['") {
+        return (!binding.is_empty() && rest.ends_with("']")).then_some(binding);
+    }
+    let (binding, key) = address.split_once('.')?;
+    (!binding.is_empty() && !key.is_empty()).then_some(binding)
+}
+
+/// Every concrete attribute the desired resource declares has the same
+/// value in state. Unresolved values cannot be compared and are skipped;
+/// at least one attribute must have been compared.
+fn attributes_match(
+    desired: &indexmap::IndexMap<String, Value>,
+    recorded: &HashMap<String, Value>,
+) -> bool {
+    let mut compared = 0;
+    for (name, value) in desired {
+        if name.starts_with('_') || !matches!(value, Value::Concrete(_)) {
+            continue;
+        }
+        if recorded.get(name) != Some(value) {
+            return false;
+        }
+        compared += 1;
+    }
+    compared > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::{ConcreteValue, ResolvedResource, ResolvedResourceId, Resource};
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    fn subnet(name: &str, az: &str) -> Resource {
+        Resource::with_provider("awscc", "ec2.Subnet", name, None)
+            .with_attribute("availability_zone", s(az))
+    }
+
+    fn plan_with(deletes: &[&str], creates: &[Resource]) -> Plan {
+        let mut plan = Plan::new();
+        for create in creates {
+            plan.add(Effect::Create(ResolvedResource::new(create.clone())));
+        }
+        for name in deletes {
+            plan.add(Effect::Delete {
+                id: ResolvedResourceId::new(ResourceId::with_provider_identity(
+                    "awscc",
+                    "ec2.Subnet",
+                    *name,
+                    None,
+                )),
+                identifier: format!("subnet-{name}"),
+                directives: Default::default(),
+                binding: None,
+                dependencies: Default::default(),
+                explicit_dependencies: Default::default(),
+                blocked_by_updates: Default::default(),
+            });
+        }
+        plan
+    }
+
+    fn states(entries: &[(&str, &str)]) -> HashMap<ResourceId, State> {
+        entries
+            .iter()
+            .map(|(name, az)| {
+                let id = ResourceId::with_provider_identity("awscc", "ec2.Subnet", *name, None);
+                let attrs = HashMap::from([("availability_zone".to_string(), s(az))]);
+                (id.clone(), State::existing(id, attrs))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn index_addresses_pair_with_matching_key_addresses() {
+        let plan = plan_with(
+            &["subnets[0]", "subnets[1]"],
+            &[
+                subnet("subnets.a", "us-east-1a"),
+                subnet("subnets['b-1']", "us-east-1b"),
+            ],
+        );
+        let current = states(&[("subnets[0]", "us-east-1a"), ("subnets[1]", "us-east-1b")]);
+
+        let moves = suggest_key_moves(&plan, &current);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(
+            moves[0].moved_block(),
+            "moved {\n  from = awscc.ec2.Subnet 'subnets[0]'\n  to   = awscc.ec2.Subnet 'subnets.a'\n}"
+        );
+        assert_eq!(
+            moves[1].moved_block(),
+            "moved {\n  from = awscc.ec2.Subnet 'subnets[1]'\n  to   = awscc.ec2.Subnet \"subnets['b-1']\"\n}"
+        );
+    }
+
+    #[test]
+    fn mismatched_or_ambiguous_instances_are_left_alone() {
+        let plan = plan_with(&["subnets[0]"], &[subnet("subnets.a", "us-east-1c")]);
+        let current = states(&[("subnets[0]", "us-east-1a")]);
+        assert!(suggest_key_moves(&plan, &current).is_empty());
+
+        let plan = plan_with(
+            &["subnets[0]"],
+            &[
+                subnet("subnets.a", "us-east-1a"),
+                subnet("subnets.b", "us-east-1a"),
+            ],
+        );
+        assert!(suggest_key_moves(&plan, &current).is_empty());
+
+        let plan = plan_with(&["subnets[0]"], &[subnet("other.a", "us-east-1a")]);
+        assert!(suggest_key_moves(&plan, &current).is_empty());
+    }
+}
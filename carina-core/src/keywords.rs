@@ -11,8 +11,8 @@ pub enum KeywordKind {
     /// Binding / function introducer: `let`, `fn`.
     Storage,
     /// Top-level / structural block declarations: `provider`, `backend`,
-    /// `upstream_state`, `exports`, `attributes`, `arguments`, `validation`,
-    /// `moved`, `removed`.
+    /// `naming`, `upstream_state`, `exports`, `attributes`, `arguments`,
+    /// `variables`, `validation`, `moved`, `removed`.
     Declaration,
     /// Control flow: `for`, `in`, `if`, `else`.
     Control,
@@ -31,10 +31,12 @@ pub const KEYWORDS: &[(&str, KeywordKind)] = &[
     ("backend", KeywordKind::Declaration),
     ("exports", KeywordKind::Declaration),
     ("moved", KeywordKind::Declaration),
+    ("naming", KeywordKind::Declaration),
     ("provider", KeywordKind::Declaration),
     ("removed", KeywordKind::Declaration),
     ("upstream_state", KeywordKind::Declaration),
     ("validation", KeywordKind::Declaration),
+    ("variables", KeywordKind::Declaration),
     ("else", KeywordKind::Control),
     ("for", KeywordKind::Control),
     ("if", KeywordKind::Control),
@@ -2,38 +2,49 @@
 //!
 //! Core library for an infrastructure management tool that treats side effects as values
 
+pub mod api_budget;
 pub mod binding_index;
 #[cfg(test)]
 mod binding_index_split_tests;
 pub mod builtins;
 pub mod config_loader;
+pub mod cost;
 pub mod deps;
 pub mod detail_rows;
 pub mod diff_helpers;
 pub mod differ;
 pub mod effect;
+pub mod enabled;
 pub(crate) mod eval_value;
 pub mod executor;
 pub mod explicit;
 pub mod formatter;
+pub mod graph;
 pub mod heredoc;
 pub mod identifier;
+pub mod key_migration;
 pub mod keywords;
 pub mod lint;
 pub mod module;
 pub mod module_resolver;
 pub mod name_override;
 pub(crate) mod non_empty;
+pub mod orphans;
 pub mod override_aware;
 pub mod parser;
 pub mod plan;
+pub mod plan_changes;
 pub mod plan_tree;
+pub mod policy_document;
 pub mod provider;
+pub mod refresh;
 pub mod resolver;
 #[cfg(test)]
 mod resolver_split_tests;
 pub mod resource;
 pub mod schema;
+pub mod trace;
+pub mod upgrade;
 pub mod upstream_exports;
 pub mod utils;
 pub mod validation;
@@ -147,6 +147,7 @@ fn format_value(value: &Value) -> String {
         }
         Value::Concrete(ConcreteValue::Bool(b)) => b.to_string(),
         Value::Concrete(ConcreteValue::Duration(d)) => crate::value::render_duration(*d),
+        Value::Concrete(ConcreteValue::ByteSize(n)) => crate::value::render_byte_size(*n),
         Value::Concrete(ConcreteValue::List(items)) => {
             if items.is_empty() {
                 "[]".to_string()
@@ -514,6 +515,7 @@ impl RootConfigSignature {
                 | ConcreteValue::Float(_)
                 | ConcreteValue::Bool(_)
                 | ConcreteValue::Duration(_)
+                | ConcreteValue::ByteSize(_)
                 | ConcreteValue::EnumIdentifier(_)
                 | ConcreteValue::CanonicalEnum(_)
                 | ConcreteValue::StringList(_),
@@ -1046,6 +1048,7 @@ impl ModuleSignature {
                 | ConcreteValue::Float(_)
                 | ConcreteValue::Bool(_)
                 | ConcreteValue::Duration(_)
+                | ConcreteValue::ByteSize(_)
                 | ConcreteValue::EnumIdentifier(_)
                 | ConcreteValue::CanonicalEnum(_)
                 | ConcreteValue::StringList(_),
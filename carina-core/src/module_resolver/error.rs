@@ -60,6 +60,9 @@ pub enum ModuleError {
     #[error("Require constraint failed in module '{module}': {message}")]
     RequireConstraintFailed { module: String, message: String },
 
+    #[error("Conditional resource error in module '{module}': {message}")]
+    DisabledResource { module: String, message: String },
+
     #[error(
         "Module path '{path}' must be a directory. Single-file modules are not supported; put the module's .crn files in a directory and import the directory."
     )]
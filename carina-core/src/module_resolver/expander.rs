@@ -7,7 +7,8 @@ use indexmap::IndexMap;
 
 use crate::identifier::SimHash;
 use crate::parser::{
-    ArgumentParameter, BindingName, DeferredForExpression, ModuleCall, ParsedFile, WaitBinding,
+    ArgumentParameter, BindingName, DeferredForExpression, ModuleCall, ParsedFile, RequireBlock,
+    WaitBinding,
 };
 use crate::resource::{
     Composition, ConcreteValue, DataSource, DeferredValue, Resource, ResourceId, ResourceIdentity,
@@ -17,9 +18,41 @@ use crate::resource::{
 use super::error::ModuleError;
 use super::resolver::ModuleResolver;
 use super::typecheck::check_module_arg_type;
-use super::validation::{evaluate_require_expr, evaluate_validate_expr, format_value_for_error};
+use super::validation::{
+    evaluate_require_expr, evaluate_validate_expr, format_value_for_error, uses_instance_count,
+};
 
 impl ModuleResolver<'_> {
+    /// Check `require instance_count() ...` quotas for every module
+    /// called in `module_calls`, one scope (the root configuration or
+    /// one module body) at a time.
+    ///
+    /// Runs after the calls are expanded, so a quota sees every instance
+    /// including those produced by `for` expressions. Quota requires are
+    /// evaluated without call arguments — they constrain the module as a
+    /// whole, not any one instance.
+    pub fn check_instance_quotas(&self, module_calls: &[ModuleCall]) -> Result<(), ModuleError> {
+        let mut instance_counts: IndexMap<&str, usize> = IndexMap::new();
+        for call in module_calls {
+            *instance_counts
+                .entry(call.module_name.as_str())
+                .or_default() += 1;
+        }
+        for (module_name, count) in instance_counts {
+            let Some(module) = self.imported_modules.get(module_name) else {
+                continue;
+            };
+            for require in module
+                .requires
+                .iter()
+                .filter(|r| uses_instance_count(&r.condition))
+            {
+                check_require(require, module_name, &HashMap::new(), Some(count))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Expand a module call into resources.
     ///
     /// If the module defines `attributes` and the call has a `binding_name`,
@@ -180,23 +213,15 @@ impl ModuleResolver<'_> {
             }
         }
 
-        // Evaluate require blocks (cross-argument constraints)
-        for require in &module.requires {
-            match evaluate_require_expr(&require.condition, &argument_values) {
-                Ok(true) => {} // Constraint satisfied
-                Ok(false) => {
-                    return Err(ModuleError::RequireConstraintFailed {
-                        module: call.module_name.clone(),
-                        message: require.error_message.clone(),
-                    });
-                }
-                Err(e) => {
-                    return Err(ModuleError::RequireConstraintFailed {
-                        module: call.module_name.clone(),
-                        message: format!("error evaluating require expression: {}", e),
-                    });
-                }
-            }
+        // Evaluate require blocks (cross-argument constraints). Instance
+        // quotas are checked by `check_instance_quotas` once every call of
+        // the module has been expanded.
+        for require in module
+            .requires
+            .iter()
+            .filter(|r| !uses_instance_count(&r.condition))
+        {
+            check_require(require, &call.module_name, &argument_values, None)?;
         }
 
         // Collect intra-module binding names so we can rewrite
@@ -354,32 +379,13 @@ impl ModuleResolver<'_> {
         // `module.compositions` plus the `_virtual` attribute
         // resource built above.
 
-        // #3306: build the expansion trace from this call's leaves +
-        // nested-expansion traces inherited from `module`, *before*
-        // the struct literal moves `resources` / `data_sources`.
-        // carina#3322: stamp the call site with the DSL `use` path
-        // so plan rendering can label the group with a DSL-visible
-        // name (`module "<binding>" (<source_path>)`). `None` falls
-        // back to a path-less header — only relevant in test harnesses
-        // that bypass `process_imports`; real expansions always have
-        // a recorded path.
-        let source_path: Option<&str> =
-            self.module_paths.get(&call.module_name).map(String::as_str);
-        let expansion_trace = build_expansion_trace(
-            instance_prefix,
-            source_path,
-            &module.expansion_trace,
-            &resources,
-            &data_sources,
-        );
-
         // The contribution is a full `ParsedFile` built with an
         // **exhaustive struct literal** (no `..Default::default()`):
         // adding a `File<E>` field breaks this until someone decides
         // whether a module instance contributes it. Fields a module
         // does *not* propagate are explicitly empty *here*, with the
         // reason — never silently absent (the carina#3126 fix).
-        Ok(ParsedFile {
+        let mut contribution = ParsedFile {
             // Populated from the module, instance-prefixed:
             resources,
             data_sources,
@@ -400,8 +406,8 @@ impl ModuleResolver<'_> {
             //     the call's args are bound here; outputs reach the
             //     caller through the `_virtual` attribute resource.
             //   - `requires`: evaluated against this call's args here.
-            //   - `state_blocks` / `backend`: a module does not own
-            //     caller state/backend config.
+            //   - `state_blocks` / `backend` / `naming`: a module does
+            //     not own caller state, backend, or naming config.
             //   - `structural_bindings`: scoped to the module's own
             //     parse, not merged upward.
             providers: Vec::new(),
@@ -410,8 +416,10 @@ impl ModuleResolver<'_> {
             module_calls: Vec::new(),
             arguments: Vec::new(),
             attribute_params: Vec::new(),
+            variable_params: Vec::new(),
             export_params: Vec::new(),
             backend: None,
+            naming: None,
             state_blocks: Vec::new(),
             user_functions: HashMap::new(),
             upstream_states: Vec::new(),
@@ -419,10 +427,60 @@ impl ModuleResolver<'_> {
             structural_bindings: HashSet::new(),
             warnings: module.warnings.clone(),
 
-            // ExpansionTrace (#3306): pre-built above against the
-            // call's leaves before the struct literal moved them.
-            expansion_trace,
-        })
+            // ExpansionTrace (#3306): built below, once disabled
+            // resources are gone, against the call's remaining leaves.
+            expansion_trace: crate::resource::ExpansionTrace::new(),
+        };
+
+        // `directives { enabled = <argument> }` is only decidable now
+        // that this call's arguments are substituted; a disabled module
+        // resource must not reach the caller at all.
+        crate::enabled::drop_disabled_resources(&mut contribution).map_err(|message| {
+            ModuleError::DisabledResource {
+                module: call.module_name.clone(),
+                message,
+            }
+        })?;
+
+        // #3306: build the expansion trace from this call's leaves +
+        // nested-expansion traces inherited from `module`.
+        // carina#3322: stamp the call site with the DSL `use` path
+        // so plan rendering can label the group with a DSL-visible
+        // name (`module "<binding>" (<source_path>)`). `None` falls
+        // back to a path-less header — only relevant in test harnesses
+        // that bypass `process_imports`; real expansions always have
+        // a recorded path.
+        let source_path: Option<&str> =
+            self.module_paths.get(&call.module_name).map(String::as_str);
+        contribution.expansion_trace = build_expansion_trace(
+            instance_prefix,
+            source_path,
+            &module.expansion_trace,
+            &contribution.resources,
+            &contribution.data_sources,
+        );
+
+        Ok(contribution)
+    }
+}
+
+/// Evaluate one `require` against a module call's arguments.
+fn check_require(
+    require: &RequireBlock,
+    module_name: &str,
+    argument_values: &HashMap<String, Value>,
+    instance_count: Option<usize>,
+) -> Result<(), ModuleError> {
+    match evaluate_require_expr(&require.condition, argument_values, instance_count) {
+        Ok(true) => Ok(()), // Constraint satisfied
+        Ok(false) => Err(ModuleError::RequireConstraintFailed {
+            module: module_name.to_string(),
+            message: require.error_message.clone(),
+        }),
+        Err(e) => Err(ModuleError::RequireConstraintFailed {
+            module: module_name.to_string(),
+            message: format!("error evaluating require expression: {}", e),
+        }),
     }
 }
 
@@ -619,6 +677,14 @@ fn prefix_module_resource(
         );
     }
     new_resource.attributes = substituted_attrs;
+    new_resource.directives.enabled = new_resource.directives.enabled.as_ref().map(|expr| {
+        prefix_attr_value(
+            expr,
+            instance_prefix,
+            intra_module_bindings,
+            argument_values,
+        )
+    });
 
     new_resource
 }
@@ -665,6 +731,14 @@ fn prefix_module_data_source(
         );
     }
     new_data_source.attributes = substituted_attrs;
+    new_data_source.directives.enabled = new_data_source.directives.enabled.as_ref().map(|expr| {
+        prefix_attr_value(
+            expr,
+            instance_prefix,
+            intra_module_bindings,
+            argument_values,
+        )
+    });
 
     new_data_source
 }
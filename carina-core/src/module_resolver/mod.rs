@@ -20,6 +20,8 @@
 //! - `resolver`: the `ModuleResolver` struct/impl driver and the
 //!   `resolve_modules*` top-level entry points.
 //! - `validation`: expression evaluator for `validate` and `require` blocks.
+//! - `variables`: binds root-configuration `variables { }` to var-file
+//!   values, reusing the argument type check and substitution.
 
 mod error;
 mod expander;
@@ -27,6 +29,7 @@ mod loader;
 mod resolver;
 mod typecheck;
 mod validation;
+mod variables;
 
 pub use error::ModuleError;
 pub use expander::{instance_prefix_for_call, reconcile_anonymous_module_instances};
@@ -35,6 +38,7 @@ pub use loader::{
     load_module_from_directory,
 };
 pub use resolver::{ModuleResolver, resolve_modules, resolve_modules_with_config};
+pub use variables::{bind_root_variable_defaults, bind_root_variables};
 
 // Bring `pub(super)` helpers into mod.rs scope so the `tests` submodule (which
 // uses `super::*`) can call them by their bare names. Production code never
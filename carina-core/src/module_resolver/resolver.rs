@@ -246,12 +246,14 @@ impl<'cfg> ModuleResolver<'cfg> {
             }
         }
 
+        let quotas = self.check_instance_quotas(&module_calls);
+
         // Restore original state
         self.base_dir = original_base_dir;
         self.imported_modules = original_imported;
         self.module_paths = original_paths;
 
-        Ok(())
+        quotas
     }
 
     /// Get an imported module by alias
@@ -297,6 +299,7 @@ pub fn resolve_modules_with_config<E>(
         let expanded = crate::config_loader::relabel_export_phase(expanded);
         crate::config_loader::merge_parsed_file(parsed, expanded);
     }
+    resolver.check_instance_quotas(&module_calls)?;
 
     Ok(())
 }
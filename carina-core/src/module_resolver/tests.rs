@@ -79,8 +79,10 @@ fn create_test_module() -> ParsedFile {
             },
         ],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -172,8 +174,10 @@ fn create_test_module_with_anonymous_resource() -> ParsedFile {
         module_calls: vec![],
         arguments: vec![],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -321,8 +325,10 @@ fn create_module_with_named_provider_instance() -> ParsedFile {
         module_calls: vec![],
         arguments: vec![],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -490,8 +496,10 @@ fn create_module_with_intra_refs() -> ParsedFile {
             validations: Vec::new(),
         }],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -636,8 +644,10 @@ fn create_module_with_attributes() -> ParsedFile {
                 vec![],
             )),
         }],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -940,8 +950,10 @@ fn test_expand_module_call_preserves_arguments_on_composition_signature() {
                 "fixed-endpoint".to_string(),
             ))),
         }],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -1171,6 +1183,168 @@ fn test_named_module_call_uses_binding_name() {
     assert_eq!(instance_prefix_for_call(&call), "prod");
 }
 
+fn resolve_quota_fixture(root_body: &str) -> Result<ParsedFile, ModuleError> {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let module_dir = tmp.path().join("modules/thing");
+    fs::create_dir_all(&module_dir).unwrap();
+    fs::write(
+        module_dir.join("main.crn"),
+        r#"
+arguments {
+  name: String
+}
+
+require instance_count() <= 2, 'at most 2 things per configuration'
+
+let role = awscc.iam.Role {
+  role_name = name
+  assume_role_policy_document = {}
+}
+"#,
+    )
+    .unwrap();
+    let root_dir = tmp.path().join("root");
+    fs::create_dir_all(&root_dir).unwrap();
+    let mut parsed = crate::parser::parse(root_body, &ProviderContext::default()).unwrap();
+    resolve_modules(&mut parsed, &root_dir).map(|()| parsed)
+}
+
+#[test]
+fn test_require_instance_count_quota() {
+    let parsed = resolve_quota_fixture(
+        r#"
+let thing = use { source = '../modules/thing' }
+
+thing { name = 'alpha' }
+thing { name = 'beta' }
+"#,
+    )
+    .expect("2 instances is within quota");
+    assert_eq!(role_names(&parsed).len(), 2);
+
+    let err = resolve_quota_fixture(
+        r#"
+let thing = use { source = '../modules/thing' }
+
+let things = for n in ['a', 'b', 'c'] {
+  thing { name = n }
+}
+"#,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("at most 2 things per configuration"),
+        "got: {err}"
+    );
+}
+
+fn resolve_toggle_fixture(module_extra: &str, root_body: &str) -> Result<ParsedFile, ModuleError> {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let module_dir = tmp.path().join("modules/toggle");
+    fs::create_dir_all(&module_dir).unwrap();
+    fs::write(
+        module_dir.join("main.crn"),
+        r#"
+arguments {
+  name: String
+  create_nat: Bool = true
+}
+
+let role = awscc.iam.Role {
+  role_name = name
+  assume_role_policy_document = {}
+}
+
+let nat = awscc.ec2.NatGateway {
+  subnet_id = 'subnet-1'
+  directives {
+    enabled = create_nat
+  }
+}
+
+let always_off = awscc.iam.Role {
+  role_name = "${name}-off"
+  assume_role_policy_document = {}
+  directives {
+    enabled = false
+  }
+}
+"#
+        .to_string()
+            + module_extra,
+    )
+    .unwrap();
+    let root_dir = tmp.path().join("root");
+    fs::create_dir_all(&root_dir).unwrap();
+    let mut parsed = crate::parser::parse(root_body, &ProviderContext::default()).unwrap();
+    resolve_modules(&mut parsed, &root_dir).map(|()| parsed)
+}
+
+#[test]
+fn test_disabled_resources_inside_module_are_dropped_at_expansion() {
+    let parsed = resolve_toggle_fixture(
+        "",
+        r#"
+let toggle = use { source = '../modules/toggle' }
+
+let on = toggle { name = 'on' }
+let off = toggle {
+  name = 'off'
+  create_nat = false
+}
+"#,
+    )
+    .unwrap();
+
+    let nats: Vec<String> = parsed
+        .resources
+        .iter()
+        .filter(|r| r.id.resource_type == "ec2.NatGateway")
+        .map(|r| r.binding.clone().unwrap())
+        .collect();
+    assert_eq!(nats, vec!["on.nat".to_string()]);
+    assert_eq!(
+        role_names(&parsed),
+        HashSet::from(["on".to_string(), "off".to_string()])
+    );
+    assert!(
+        parsed
+            .resources
+            .iter()
+            .all(|r| r.directives.enabled.is_none()),
+        "surviving resources must not carry an unevaluated flag"
+    );
+}
+
+#[test]
+fn test_module_attribute_referencing_disabled_resource_is_rejected() {
+    let err = resolve_toggle_fixture(
+        r#"
+attributes {
+  nat_id = nat.nat_gateway_id
+}
+"#,
+        r#"
+let toggle = use { source = '../modules/toggle' }
+
+let off = toggle {
+  name = 'off'
+  create_nat = false
+}
+"#,
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, ModuleError::DisabledResource { .. }),
+        "got: {err}"
+    );
+    assert!(
+        err.to_string().contains("references 'off.nat'"),
+        "got: {err}"
+    );
+}
+
 #[test]
 fn test_anonymous_module_calls_expand_into_distinct_instances() {
     let parsed = resolve_thing_fixture(
@@ -2029,8 +2203,10 @@ fn create_module_with_interpolation() -> ParsedFile {
             },
         ],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -2413,8 +2589,10 @@ fn create_module_with_port_validation() -> ParsedFile {
             }],
         }],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -2592,8 +2770,10 @@ fn test_argument_validation_no_message_uses_default() {
             }],
         }],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -2661,8 +2841,10 @@ fn test_argument_validation_len_with_list() {
             }],
         }],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -2750,8 +2932,10 @@ fn test_require_block_passes() {
             },
         ],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -2832,8 +3016,10 @@ fn test_require_block_fails_with_not_expr() {
             },
         ],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -2906,8 +3092,10 @@ fn test_require_block_len_function() {
             validations: Vec::new(),
         }],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -3007,8 +3195,10 @@ fn test_require_block_multiple_constraints() {
             },
         ],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -4308,8 +4498,10 @@ fn create_module_with_environment_union() -> ParsedFile {
             validations: Vec::new(),
         }],
         attribute_params: vec![],
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: vec![],
         user_functions: HashMap::new(),
         upstream_states: vec![],
@@ -51,6 +51,7 @@ fn describe_value_shape(value: &Value) -> &'static str {
         Value::Concrete(ConcreteValue::Float(_)) => "float",
         Value::Concrete(ConcreteValue::Bool(_)) => "bool",
         Value::Concrete(ConcreteValue::Duration(_)) => "duration",
+        Value::Concrete(ConcreteValue::ByteSize(_)) => "byte size",
         Value::Concrete(ConcreteValue::List(_)) | Value::Concrete(ConcreteValue::StringList(_)) => {
             "list"
         }
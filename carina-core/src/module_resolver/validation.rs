@@ -260,11 +260,17 @@ fn eval_validate_function(
 
 /// Evaluate a require expression with access to all argument values.
 /// Returns Ok(true) if the constraint is satisfied, Ok(false) if it fails.
+///
+/// `instance_count` is the number of times the module is called in the
+/// configuration, exposed as `instance_count()`. It is `None` while
+/// individual calls are being expanded; callers defer requires that
+/// [`uses_instance_count`] until every call is known.
 pub(super) fn evaluate_require_expr(
     expr: &ValidateExpr,
     args: &HashMap<String, Value>,
+    instance_count: Option<usize>,
 ) -> Result<bool, String> {
-    let result = eval_require(expr, args)?;
+    let result = eval_require(expr, args, instance_count)?;
     match result {
         RequireValue::Bool(b) => Ok(b),
         other => Err(format!(
@@ -274,6 +280,30 @@ pub(super) fn evaluate_require_expr(
     }
 }
 
+/// Whether `expr` calls `instance_count()` and so can only be evaluated
+/// once every call of the module is known.
+pub(super) fn uses_instance_count(expr: &ValidateExpr) -> bool {
+    match expr {
+        ValidateExpr::FunctionCall { name, args } => {
+            name == "instance_count" || args.iter().any(uses_instance_count)
+        }
+        ValidateExpr::Compare { lhs, rhs, .. } => {
+            uses_instance_count(lhs) || uses_instance_count(rhs)
+        }
+        ValidateExpr::And(lhs, rhs) | ValidateExpr::Or(lhs, rhs) => {
+            uses_instance_count(lhs) || uses_instance_count(rhs)
+        }
+        ValidateExpr::Not(inner) => uses_instance_count(inner),
+        ValidateExpr::Bool(_)
+        | ValidateExpr::Int(_)
+        | ValidateExpr::Float(_)
+        | ValidateExpr::Duration(_)
+        | ValidateExpr::String(_)
+        | ValidateExpr::Null
+        | ValidateExpr::Var(_) => false,
+    }
+}
+
 /// Internal value type for require expression evaluation
 #[derive(Debug, Clone)]
 enum RequireValue {
@@ -289,6 +319,7 @@ enum RequireValue {
 fn eval_require(
     expr: &ValidateExpr,
     args: &HashMap<String, Value>,
+    instance_count: Option<usize>,
 ) -> Result<RequireValue, String> {
     match expr {
         ValidateExpr::Bool(b) => Ok(RequireValue::Bool(*b)),
@@ -317,17 +348,17 @@ fn eval_require(
             }
         }
         ValidateExpr::Compare { lhs, op, rhs } => {
-            let left = eval_require(lhs, args)?;
-            let right = eval_require(rhs, args)?;
+            let left = eval_require(lhs, args, instance_count)?;
+            let right = eval_require(rhs, args, instance_count)?;
             let result = compare_require_values(&left, op, &right)?;
             Ok(RequireValue::Bool(result))
         }
         ValidateExpr::And(lhs, rhs) => {
-            let left = eval_require(lhs, args)?;
+            let left = eval_require(lhs, args, instance_count)?;
             match left {
                 RequireValue::Bool(false) => Ok(RequireValue::Bool(false)),
                 RequireValue::Bool(true) => {
-                    let right = eval_require(rhs, args)?;
+                    let right = eval_require(rhs, args, instance_count)?;
                     match right {
                         RequireValue::Bool(b) => Ok(RequireValue::Bool(b)),
                         _ => Err("right operand of && must be boolean".to_string()),
@@ -337,11 +368,11 @@ fn eval_require(
             }
         }
         ValidateExpr::Or(lhs, rhs) => {
-            let left = eval_require(lhs, args)?;
+            let left = eval_require(lhs, args, instance_count)?;
             match left {
                 RequireValue::Bool(true) => Ok(RequireValue::Bool(true)),
                 RequireValue::Bool(false) => {
-                    let right = eval_require(rhs, args)?;
+                    let right = eval_require(rhs, args, instance_count)?;
                     match right {
                         RequireValue::Bool(b) => Ok(RequireValue::Bool(b)),
                         _ => Err("right operand of || must be boolean".to_string()),
@@ -351,7 +382,7 @@ fn eval_require(
             }
         }
         ValidateExpr::Not(inner) => {
-            let val = eval_require(inner, args)?;
+            let val = eval_require(inner, args, instance_count)?;
             match val {
                 RequireValue::Bool(b) => Ok(RequireValue::Bool(!b)),
                 _ => Err("operand of ! must be boolean".to_string()),
@@ -360,7 +391,7 @@ fn eval_require(
         ValidateExpr::FunctionCall {
             name,
             args: fn_args,
-        } => eval_require_function(name, fn_args, args),
+        } => eval_require_function(name, fn_args, args, instance_count),
     }
 }
 
@@ -449,8 +480,22 @@ fn eval_require_function(
     name: &str,
     fn_args: &[ValidateExpr],
     args: &HashMap<String, Value>,
+    instance_count: Option<usize>,
 ) -> Result<RequireValue, String> {
     match name {
+        "instance_count" => {
+            if !fn_args.is_empty() {
+                return Err(format!(
+                    "instance_count() expects 0 arguments, got {}",
+                    fn_args.len()
+                ));
+            }
+            instance_count
+                .map(|n| RequireValue::Int(n as i64))
+                .ok_or_else(|| {
+                    "instance_count() is only known after every module call is expanded".to_string()
+                })
+        }
         "len" | "length" => {
             if fn_args.len() != 1 {
                 return Err(format!(
@@ -481,7 +526,7 @@ fn eval_require_function(
                 };
             }
             // For non-Var expressions, evaluate normally
-            let val = eval_require(&fn_args[0], args)?;
+            let val = eval_require(&fn_args[0], args, instance_count)?;
             match val {
                 RequireValue::String(s) => Ok(RequireValue::Int(s.len() as i64)),
                 _ => Err(format!(
@@ -0,0 +1,350 @@
+//! Root-configuration variables: binds the values of a `variables { }`
+//! block (from a `--var-file`, falling back to the declared defaults)
+//! and substitutes them into the root file.
+//!
+//! The root is treated as an implicit module call whose arguments come
+//! from the var file, so values go through the same fix-point default
+//! resolution, type check and `validation` blocks as module arguments.
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::parser::{ExportParamLike, File, ProviderContext, StateBlock};
+use crate::resource::Value;
+
+use super::expander::substitute_arguments;
+use super::typecheck::{TypeCheckResult, check_type_match};
+use super::validation::{
+    evaluate_require_expr, evaluate_validate_expr, format_value_for_error, uses_instance_count,
+};
+
+/// Bind `values` to the variables `parsed` declares, check the root
+/// `require` blocks against them and substitute them into resources,
+/// data sources, module call arguments, provider blocks, exports,
+/// `import` ids and `wait` conditions.
+///
+/// Fails on a value for an undeclared variable, a variable with neither
+/// a value nor a default, a type mismatch, a failed `validation` block
+/// or a failed `require`. All problems are reported at once,
+/// newline-separated.
+pub fn bind_root_variables<E: ExportParamLike>(
+    parsed: &mut File<E>,
+    values: IndexMap<String, Value>,
+    config: &ProviderContext,
+) -> Result<(), String> {
+    if parsed.variable_params.is_empty() && values.is_empty() {
+        return Ok(());
+    }
+    let mut errors = Vec::new();
+
+    for name in values.keys() {
+        if !parsed.variable_params.iter().any(|v| &v.name == name) {
+            errors.push(format!("variable '{}' is not declared", name));
+        }
+    }
+
+    let mut bound: HashMap<String, Value> = HashMap::new();
+    for var in &parsed.variable_params {
+        match values.get(&var.name).or(var.default.as_ref()) {
+            Some(value) => {
+                bound.insert(var.name.clone(), value.clone());
+            }
+            None => errors.push(format!(
+                "variable '{}' has no default; set it in the var file",
+                var.name
+            )),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+
+    resolve_fix_point(&mut bound, parsed.variable_params.len());
+
+    for var in &parsed.variable_params {
+        let value = &bound[&var.name];
+        match check_type_match(&var.type_expr, value, config, None) {
+            TypeCheckResult::Ok => {}
+            TypeCheckResult::Mismatch => errors.push(format!(
+                "variable '{}': expected {}, got {}",
+                var.name,
+                var.type_expr,
+                format_value_for_error(value)
+            )),
+            TypeCheckResult::ValidationError(e) => errors.push(format!(
+                "variable '{}': expected {} ({}), got {}",
+                var.name,
+                var.type_expr,
+                e,
+                format_value_for_error(value)
+            )),
+        }
+        for block in &var.validations {
+            match evaluate_validate_expr(&block.condition, &var.name, value) {
+                Ok(true) => {}
+                Ok(false) => errors.push(format!(
+                    "variable '{}': {} (got {})",
+                    var.name,
+                    block
+                        .error_message
+                        .as_deref()
+                        .unwrap_or("validation failed"),
+                    format_value_for_error(value)
+                )),
+                Err(e) => errors.push(format!(
+                    "variable '{}': error evaluating validate expression: {}",
+                    var.name, e
+                )),
+            }
+        }
+    }
+    // `instance_count()` only means something for a module's calls.
+    for require in parsed
+        .requires
+        .iter()
+        .filter(|r| !uses_instance_count(&r.condition))
+    {
+        match evaluate_require_expr(&require.condition, &bound, None) {
+            Ok(true) => {}
+            Ok(false) => errors.push(require.error_message.clone()),
+            Err(e) => errors.push(format!("error evaluating require expression: {}", e)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+
+    substitute_variables(parsed, &bound);
+    Ok(())
+}
+
+/// Substitute the declared defaults of the root variables, leaving those
+/// without a default unbound. For callers with no var file to read, such
+/// as the LSP, so values that come from defaults are checked like any
+/// other literal. Values are not type-checked here; `carina validate`
+/// reports those errors through [`bind_root_variables`].
+pub fn bind_root_variable_defaults<E: ExportParamLike>(parsed: &mut File<E>) {
+    let mut bound: HashMap<String, Value> = parsed
+        .variable_params
+        .iter()
+        .filter_map(|var| Some((var.name.clone(), var.default.clone()?)))
+        .collect();
+    if bound.is_empty() {
+        return;
+    }
+    resolve_fix_point(&mut bound, parsed.variable_params.len());
+    substitute_variables(parsed, &bound);
+}
+
+/// Defaults may reference other variables; iterate to a fix point the
+/// same way `expand_module_call` does for module arguments.
+fn resolve_fix_point(bound: &mut HashMap<String, Value>, rounds: usize) {
+    for _ in 0..=rounds {
+        let snapshot = bound.clone();
+        let mut changed = false;
+        for value in bound.values_mut() {
+            let mut next = substitute_arguments(value, &snapshot);
+            next.canonicalize_in_place();
+            if &next != value {
+                *value = next;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+fn substitute_variables<E: ExportParamLike>(parsed: &mut File<E>, bound: &HashMap<String, Value>) {
+    let substitute = |value: &mut Value| {
+        let mut next = substitute_arguments(value, bound);
+        next.canonicalize_in_place();
+        *value = next;
+    };
+    for value in parsed.variables.values_mut() {
+        substitute(value);
+    }
+    for resource in &mut parsed.resources {
+        resource.attributes.values_mut().for_each(substitute);
+        resource.directives.enabled.iter_mut().for_each(substitute);
+    }
+    for data_source in &mut parsed.data_sources {
+        data_source.attributes.values_mut().for_each(substitute);
+        data_source
+            .directives
+            .enabled
+            .iter_mut()
+            .for_each(substitute);
+    }
+    for call in &mut parsed.module_calls {
+        call.arguments.values_mut().for_each(substitute);
+    }
+    for provider in &mut parsed.providers {
+        provider.attributes.values_mut().for_each(substitute);
+        provider.default_tags.values_mut().for_each(substitute);
+        provider
+            .unresolved_attributes
+            .values_mut()
+            .for_each(substitute);
+    }
+    for export in &mut parsed.export_params {
+        export.value_mut().into_iter().for_each(substitute);
+    }
+    for block in &mut parsed.state_blocks {
+        if let StateBlock::Import { id, .. } = block {
+            substitute(id);
+        }
+    }
+    for wait in &mut parsed.wait_bindings {
+        substitute(&mut wait.until_predicate.rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::ConcreteValue;
+
+    fn parse(src: &str) -> crate::parser::ParsedFile {
+        let mut parsed = crate::parser::parse(src, &ProviderContext::default()).unwrap();
+        crate::parser::resolve_resource_refs(&mut parsed).unwrap();
+        parsed
+    }
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    const SRC: &str = r#"
+        variables {
+            env: String = "dev"
+            replicas: Int {
+                default = 1
+                validation {
+                    condition = replicas >= 1
+                    error_message = "replicas must be at least 1"
+                }
+            }
+        }
+
+        awscc.s3.Bucket {
+            bucket_name = "app-${env}"
+            tags = { Replicas = replicas }
+        }
+    "#;
+
+    #[test]
+    fn var_file_values_replace_defaults() {
+        let mut parsed = parse(SRC);
+        let mut values = IndexMap::new();
+        values.insert("env".to_string(), s("prod"));
+        bind_root_variables(&mut parsed, values, &ProviderContext::default()).unwrap();
+        assert_eq!(
+            parsed.resources[0].get_attr("bucket_name"),
+            Some(&s("app-prod"))
+        );
+    }
+
+    #[test]
+    fn defaults_apply_without_a_var_file() {
+        let mut parsed = parse(SRC);
+        bind_root_variables(&mut parsed, IndexMap::new(), &ProviderContext::default()).unwrap();
+        assert_eq!(
+            parsed.resources[0].get_attr("bucket_name"),
+            Some(&s("app-dev"))
+        );
+    }
+
+    #[test]
+    fn bad_values_are_rejected() {
+        let mut parsed = parse(SRC);
+        let mut values = IndexMap::new();
+        values.insert("replicas".to_string(), s("three"));
+        values.insert("region".to_string(), s("us-east-1"));
+        let err =
+            bind_root_variables(&mut parsed, values, &ProviderContext::default()).unwrap_err();
+        assert!(err.contains("variable 'region' is not declared"), "{err}");
+
+        let mut parsed = parse(SRC);
+        let mut values = IndexMap::new();
+        values.insert("replicas".to_string(), s("three"));
+        let err =
+            bind_root_variables(&mut parsed, values, &ProviderContext::default()).unwrap_err();
+        assert!(err.contains("variable 'replicas': expected Int"), "{err}");
+
+        let mut parsed = parse(SRC);
+        let mut values = IndexMap::new();
+        values.insert(
+            "replicas".to_string(),
+            Value::Concrete(ConcreteValue::Int(0)),
+        );
+        let err =
+            bind_root_variables(&mut parsed, values, &ProviderContext::default()).unwrap_err();
+        assert!(err.contains("replicas must be at least 1"), "{err}");
+    }
+
+    #[test]
+    fn defaults_alone_leave_required_variables_unbound() {
+        let mut parsed = parse(
+            "variables {\n  env: String\n  prefix: String = \"app\"\n}\n\nawscc.s3.Bucket {\n  bucket_name = \"${prefix}-${env}\"\n}\n",
+        );
+        bind_root_variable_defaults(&mut parsed);
+        let rendered = format!("{:?}", parsed.resources[0].get_attr("bucket_name"));
+        assert!(rendered.contains("app"), "{rendered}");
+        assert!(rendered.contains("env"), "{rendered}");
+        assert!(!rendered.contains("prefix"), "{rendered}");
+    }
+
+    #[test]
+    fn exports_and_import_ids_see_bound_values() {
+        let mut parsed = parse(
+            r#"
+            variables {
+                env: String = "dev"
+                vpc_id: String = "vpc-0abc"
+            }
+
+            import {
+                to = awscc.ec2.Vpc "main"
+                id = "${vpc_id}"
+            }
+
+            exports {
+                environment = env
+            }
+            "#,
+        );
+        let mut values = IndexMap::new();
+        values.insert("env".to_string(), s("prod"));
+        bind_root_variables(&mut parsed, values, &ProviderContext::default()).unwrap();
+        assert_eq!(parsed.export_params[0].value, Some(s("prod")));
+        match &parsed.state_blocks[0] {
+            StateBlock::Import { id, .. } => assert_eq!(id, &s("vpc-0abc")),
+            other => panic!("expected import, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn root_requires_are_checked_against_bound_values() {
+        let src = "variables {\n  min: Int = 1\n  max: Int = 3\n}\n\nrequire min <= max, 'min must not exceed max'\n";
+        let mut parsed = parse(src);
+        bind_root_variables(&mut parsed, IndexMap::new(), &ProviderContext::default()).unwrap();
+
+        let mut parsed = parse(src);
+        let mut values = IndexMap::new();
+        values.insert("min".to_string(), Value::Concrete(ConcreteValue::Int(5)));
+        let err =
+            bind_root_variables(&mut parsed, values, &ProviderContext::default()).unwrap_err();
+        assert!(err.contains("min must not exceed max"), "{err}");
+    }
+
+    #[test]
+    fn required_variables_need_a_value() {
+        let mut parsed = parse("variables {\n  env: String\n}\n");
+        let err = bind_root_variables(&mut parsed, IndexMap::new(), &ProviderContext::default())
+            .unwrap_err();
+        assert!(err.contains("variable 'env' has no default"), "{err}");
+    }
+}
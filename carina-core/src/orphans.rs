@@ -0,0 +1,225 @@
+//! Reconciliation between state and what providers report remotely, for
+//! `carina orphans`.
+//!
+//! Two kinds of drift are reported, each with the state block that would
+//! settle it:
+//!
+//! - *untracked*: a resource tagged with the project's
+//!   [`PROJECT_TAG`](crate::provider::PROJECT_TAG) that state does not
+//!   know about — typically left behind by an apply that failed before
+//!   saving state. Suggested action: an `import` block.
+//! - *vanished*: a resource in state that the provider reads back as
+//!   not found. Suggested action: a `removed` block.
+//!
+//! Resources are matched on provider, resource type and cloud-side
+//! identifier; the DSL name plays no part since a listed resource may
+//! not carry one.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::resource::{ResourceId, State};
+
+/// A resource in state, as seen by the reconciliation.
+#[derive(Debug, Clone)]
+pub struct TrackedResource {
+    pub id: ResourceId,
+    /// Cloud-side identifier; `None` for a state entry that never
+    /// finished creating.
+    pub identifier: Option<String>,
+}
+
+/// Outcome of reconciling one project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReconciliationReport {
+    /// Tagged remotely but absent from state.
+    pub untracked: Vec<ReconciliationEntry>,
+    /// In state but no longer present remotely.
+    pub vanished: Vec<ReconciliationEntry>,
+    /// Provider instances that cannot list tagged resources, so
+    /// untracked resources behind them go unnoticed.
+    pub unchecked: Vec<String>,
+    /// Provider instances or resources whose lookup failed, with the error.
+    pub errors: Vec<ReconciliationError>,
+}
+
+/// One resource in a [`ReconciliationReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconciliationEntry {
+    /// `provider.Type name`, or just `provider.Type` when the name is unknown.
+    pub resource: String,
+    pub identifier: String,
+    /// State block that settles the entry.
+    pub suggestion: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconciliationError {
+    pub subject: String,
+    pub message: String,
+}
+
+impl ReconciliationReport {
+    /// True when state and the checked providers agree.
+    pub fn is_clean(&self) -> bool {
+        self.untracked.is_empty() && self.vanished.is_empty()
+    }
+
+    /// Record the resources a provider instance listed under the project
+    /// tag, keeping those that `tracked` does not account for.
+    pub fn add_listed(&mut self, listed: Vec<State>, tracked: &[TrackedResource]) {
+        let known: HashSet<(&str, &str, &str)> = tracked
+            .iter()
+            .filter_map(|t| {
+                let identifier = t.identifier.as_deref()?;
+                Some((
+                    t.id.provider.as_str(),
+                    t.id.resource_type.as_str(),
+                    identifier,
+                ))
+            })
+            .collect();
+        for state in listed {
+            let Some(identifier) = state.identifier.as_deref() else {
+                continue;
+            };
+            let key = (
+                state.id.provider.as_str(),
+                state.id.resource_type.as_str(),
+                identifier,
+            );
+            if known.contains(&key) {
+                continue;
+            }
+            let name = state
+                .id
+                .identity_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| import_name(identifier));
+            self.untracked.push(ReconciliationEntry {
+                resource: state.id.human().to_string(),
+                identifier: identifier.to_string(),
+                suggestion: format!(
+                    "import {{\n  to = {} '{}'\n  id = '{}'\n}}",
+                    state.id.display_type(),
+                    name,
+                    identifier
+                ),
+            });
+        }
+        self.untracked
+            .sort_by(|a, b| (&a.resource, &a.identifier).cmp(&(&b.resource, &b.identifier)));
+    }
+
+    /// Record a state resource that the provider read back as not found.
+    pub fn add_vanished(&mut self, resource: &TrackedResource) {
+        self.vanished.push(ReconciliationEntry {
+            resource: resource.id.human().to_string(),
+            identifier: resource.identifier.clone().unwrap_or_default(),
+            suggestion: format!(
+                "removed {{\n  from = {} '{}'\n}}",
+                resource.id.display_type(),
+                resource.id.identity_or_empty()
+            ),
+        });
+    }
+
+    pub fn add_unchecked(&mut self, instance: impl Into<String>) {
+        self.unchecked.push(instance.into());
+    }
+
+    pub fn add_error(&mut self, subject: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(ReconciliationError {
+            subject: subject.into(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Derive a DSL name for an import from a cloud identifier, e.g.
+/// `arn:aws:s3:::logs` → `arn_aws_s3_logs`.
+fn import_name(identifier: &str) -> String {
+    let mut name = String::with_capacity(identifier.len());
+    for c in identifier.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_matches('_');
+    if name.is_empty() {
+        "imported".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn tracked(resource_type: &str, name: &str, identifier: &str) -> TrackedResource {
+        TrackedResource {
+            id: ResourceId::with_provider_identity("awscc", resource_type, name, None),
+            identifier: Some(identifier.to_string()),
+        }
+    }
+
+    fn listed(resource_type: &str, identifier: &str) -> State {
+        State::existing(
+            ResourceId::with_provider("awscc", resource_type, None, None),
+            HashMap::new(),
+        )
+        .with_identifier(identifier)
+    }
+
+    #[test]
+    fn listed_resources_missing_from_state_get_an_import_suggestion() {
+        let mut report = ReconciliationReport::default();
+        report.add_listed(
+            vec![
+                listed("ec2.Vpc", "vpc-0abc"),
+                listed("ec2.NatGateway", "nat-0123"),
+            ],
+            &[tracked("ec2.Vpc", "main", "vpc-0abc")],
+        );
+
+        assert_eq!(report.untracked.len(), 1);
+        let entry = &report.untracked[0];
+        assert_eq!(entry.identifier, "nat-0123");
+        assert_eq!(
+            entry.suggestion,
+            "import {\n  to = awscc.ec2.NatGateway 'nat_0123'\n  id = 'nat-0123'\n}"
+        );
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn same_identifier_under_another_type_is_not_a_match() {
+        let mut report = ReconciliationReport::default();
+        report.add_listed(
+            vec![listed("ec2.Subnet", "shared-id")],
+            &[tracked("ec2.Vpc", "main", "shared-id")],
+        );
+        assert_eq!(report.untracked.len(), 1);
+    }
+
+    #[test]
+    fn vanished_resources_get_a_removed_suggestion() {
+        let mut report = ReconciliationReport::default();
+        report.add_vanished(&tracked("ec2.Vpc", "main", "vpc-0abc"));
+        assert_eq!(
+            report.vanished[0].suggestion,
+            "removed {\n  from = awscc.ec2.Vpc 'main'\n}"
+        );
+    }
+
+    #[test]
+    fn import_name_is_a_dsl_friendly_slug() {
+        assert_eq!(import_name("arn:aws:s3:::Logs"), "arn_aws_s3_logs");
+        assert_eq!(import_name("::"), "imported");
+    }
+}
@@ -7,9 +7,10 @@ use super::expressions::for_expr::ForBinding;
 use super::expressions::validate_expr::CompareOp;
 use super::util::snake_to_pascal;
 use crate::binding_index::IterableBindings;
+use crate::eval_value::EvalValue;
 use crate::resource::{
-    Composition, ConcreteValue, DataSource, DeferredValue, Directives, GraphNode, LeafNode,
-    Resource, ResourceId, ResourceIdentity, UnknownReason, Value,
+    Composition, ConcreteValue, DataSource, DeferredValue, Directives, GraphNode,
+    InterpolationPart, LeafNode, Resource, ResourceId, ResourceIdentity, UnknownReason, Value,
 };
 use crate::version_constraint::VersionConstraint;
 use indexmap::IndexMap;
@@ -509,6 +510,7 @@ pub type ExportParameter = ParsedExportParam;
 pub trait ExportParamLike {
     fn name(&self) -> &str;
     fn value(&self) -> Option<&Value>;
+    fn value_mut(&mut self) -> Option<&mut Value>;
     /// The export's declared type, when one is available.
     ///
     /// The parser phase carries `Option<TypeExpr>` (the user may have
@@ -528,6 +530,9 @@ impl ExportParamLike for ParsedExportParam {
     fn value(&self) -> Option<&Value> {
         self.value.as_ref()
     }
+    fn value_mut(&mut self) -> Option<&mut Value> {
+        self.value.as_mut()
+    }
     fn type_expr_opt(&self) -> Option<&TypeExpr> {
         self.type_expr.as_ref()
     }
@@ -751,6 +756,11 @@ pub struct ProviderConfig {
     /// Extracted from `default_tags = { ... }` in the provider block.
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub default_tags: IndexMap<String, Value>,
+    /// Whether the provider's taggable resources get `carina:*`
+    /// provenance tags on create and update. Extracted from
+    /// `provenance_tags = true` in the provider block.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub provenance_tags: bool,
     /// Provider source (e.g., "github.com/carina-rs/carina-provider-awscc" or "file:///path/to/binary").
     /// Extracted from the provider block and not passed to the provider.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -798,6 +808,19 @@ pub struct BackendConfig {
     pub attributes: HashMap<String, Value>,
 }
 
+/// Project naming policy declared by a top-level `naming { ... }` block.
+///
+/// Checked at plan time against every resource's unique-name attribute
+/// and its `Name` tag; see [`crate::validation::naming`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NamingPolicy {
+    /// `pattern = "<regex>"`: names must match the regular expression.
+    Pattern(crate::validation::naming::NamingPattern),
+    /// `template = "{env}-{app}-{name}"` plus literal values for some of
+    /// its placeholders (`env = "prd"`).
+    Template(crate::validation::naming::NamingTemplate),
+}
+
 /// Upstream state reference: `let <binding> = upstream_state { source = "<dir>" }`.
 ///
 /// Declares a read-only reference to another Carina configuration's state.
@@ -989,6 +1012,9 @@ impl ExportParamLike for InferredExportParam {
     fn value(&self) -> Option<&Value> {
         self.value.as_ref()
     }
+    fn value_mut(&mut self) -> Option<&mut Value> {
+        self.value.as_mut()
+    }
     fn type_expr_opt(&self) -> Option<&TypeExpr> {
         Some(&self.type_expr)
     }
@@ -1023,11 +1049,16 @@ pub struct File<E> {
     pub arguments: Vec<ArgumentParameter>,
     /// Top-level attribute parameters (directory-based module style)
     pub attribute_params: Vec<AttributeParameter>,
+    /// Root-configuration inputs (`variables { ... }`), given values by
+    /// `--var-file`. Same shape as `arguments`, which belong to modules.
+    pub variable_params: Vec<ArgumentParameter>,
     /// Top-level export parameters (published to upstream_state consumers).
     /// Element type varies by phase — see [`ParsedExportParam`] / [`InferredExportParam`].
     pub export_params: Vec<E>,
     /// Backend configuration for state storage
     pub backend: Option<BackendConfig>,
+    /// Project naming policy (`naming { ... }`)
+    pub naming: Option<NamingPolicy>,
     /// State manipulation blocks (import, removed, moved)
     pub state_blocks: Vec<StateBlock>,
     /// User-defined pure functions
@@ -1078,8 +1109,10 @@ impl<E> Default for File<E> {
             module_calls: Vec::new(),
             arguments: Vec::new(),
             attribute_params: Vec::new(),
+            variable_params: Vec::new(),
             export_params: Vec::new(),
             backend: None,
+            naming: None,
             state_blocks: Vec::new(),
             user_functions: HashMap::new(),
             upstream_states: Vec::new(),
@@ -1118,8 +1151,10 @@ impl<E> File<E> {
             module_calls,
             arguments,
             attribute_params,
+            variable_params,
             export_params,
             backend,
+            naming,
             state_blocks,
             user_functions,
             upstream_states,
@@ -1141,8 +1176,10 @@ impl<E> File<E> {
             module_calls,
             arguments,
             attribute_params,
+            variable_params,
             export_params: f(export_params),
             backend,
+            naming,
             state_blocks,
             user_functions,
             upstream_states,
@@ -1214,6 +1251,7 @@ impl<E> File<E> {
         let mut names = HashSet::new();
         names.extend(self.iter_top_level_resources().filter_map(|r| r.binding()));
         names.extend(self.arguments.iter().map(|a| a.name.as_str()));
+        names.extend(self.variable_params.iter().map(|v| v.name.as_str()));
         names.extend(
             self.module_calls
                 .iter()
@@ -1341,6 +1379,9 @@ impl<E> File<E> {
         // warning collected in `new_warnings`.
         let mut mismatched_indices: Vec<usize> = Vec::new();
         let mut new_warnings: Vec<ParseWarning> = Vec::new();
+        // Member bindings of each expanded for-expression, for the
+        // `attrs()` / `ids()` calls that were waiting on it.
+        let mut groups: HashMap<String, Value> = HashMap::new();
 
         for (idx, deferred) in self.deferred_for_expressions.iter().enumerate() {
             // Look up the iterable value in the merged binding view
@@ -1352,6 +1393,7 @@ impl<E> File<E> {
                 continue;
             };
 
+            let expanded_before = expanded_resources.len();
             match (&deferred.binding, iterable_value) {
                 // Simple binding: only the value var is bound
                 (ForBinding::Simple(_), Value::Concrete(ConcreteValue::List(_))) => {
@@ -1412,6 +1454,17 @@ impl<E> File<E> {
                     // Iterable is not a list or map — leave deferred
                 }
             }
+            if resolved_indices.last() == Some(&idx) {
+                let members = expanded_resources[expanded_before..]
+                    .iter()
+                    .filter_map(|r: &Resource| r.binding.clone())
+                    .map(|binding| Value::Deferred(DeferredValue::BindingRef { binding }))
+                    .collect();
+                groups.insert(
+                    deferred.binding_name.clone(),
+                    Value::Concrete(ConcreteValue::List(members)),
+                );
+            }
         }
 
         // Remove parse-time warnings for mismatched entries (the new
@@ -1435,6 +1488,65 @@ impl<E> File<E> {
         // composition for-body never reaches the deferred path.)
         self.resources.extend(expanded_resources);
         self.warnings.extend(new_warnings);
+
+        if !groups.is_empty() {
+            for resource in &mut self.resources {
+                for value in resource.attributes.values_mut() {
+                    expand_group_calls(value, &groups);
+                }
+            }
+        }
+    }
+}
+
+/// Evaluate the `attrs()` / `ids()` calls in `value` whose deferred
+/// for-expression has expanded into the members listed in `groups`. A
+/// call that fails is left in place, so its error surfaces when the
+/// value is resolved.
+fn expand_group_calls(value: &mut Value, groups: &HashMap<String, Value>) {
+    match value {
+        Value::Deferred(DeferredValue::FunctionCall { name, args }) => {
+            let group = args
+                .first()
+                .and_then(crate::builtins::deferred_for_group)
+                .and_then(|binding| groups.get(binding));
+            if let Some(group) = group
+                && crate::builtins::expands_at_parse_time(name)
+            {
+                let eval_args: Vec<EvalValue> = std::iter::once(group.clone())
+                    .chain(args[1..].iter().cloned())
+                    .map(EvalValue::from_value)
+                    .collect();
+                if let Ok(Ok(expanded)) =
+                    crate::builtins::evaluate_builtin(name, &eval_args).map(EvalValue::into_value)
+                {
+                    *value = expanded;
+                }
+                return;
+            }
+            for arg in args {
+                expand_group_calls(arg, groups);
+            }
+        }
+        Value::Concrete(ConcreteValue::List(items)) => {
+            for item in items {
+                expand_group_calls(item, groups);
+            }
+        }
+        Value::Concrete(ConcreteValue::Map(map)) => {
+            for item in map.values_mut() {
+                expand_group_calls(item, groups);
+            }
+        }
+        Value::Deferred(DeferredValue::Secret(inner)) => expand_group_calls(inner, groups),
+        Value::Deferred(DeferredValue::Interpolation(parts)) => {
+            for part in parts {
+                if let InterpolationPart::Expr(expr) = part {
+                    expand_group_calls(expr, groups);
+                }
+            }
+        }
+        _ => {}
     }
 }
 
@@ -1475,6 +1587,9 @@ pub fn expand_deferred_children(
             Value::Concrete(ConcreteValue::Duration(_)) => {
                 Err(ShapeMismatch::new("list", "duration"))
             }
+            Value::Concrete(ConcreteValue::ByteSize(_)) => {
+                Err(ShapeMismatch::new("list", "byte size"))
+            }
             Value::Concrete(ConcreteValue::StringList(_)) => {
                 Err(ShapeMismatch::new("list", "string list"))
             }
@@ -1518,6 +1633,9 @@ pub fn expand_deferred_children(
             Value::Concrete(ConcreteValue::Duration(_)) => {
                 Err(ShapeMismatch::new("list", "duration"))
             }
+            Value::Concrete(ConcreteValue::ByteSize(_)) => {
+                Err(ShapeMismatch::new("list", "byte size"))
+            }
             Value::Concrete(ConcreteValue::StringList(_)) => {
                 Err(ShapeMismatch::new("list", "string list"))
             }
@@ -1565,6 +1683,9 @@ pub fn expand_deferred_children(
             Value::Concrete(ConcreteValue::Duration(_)) => {
                 Err(ShapeMismatch::new("map", "duration"))
             }
+            Value::Concrete(ConcreteValue::ByteSize(_)) => {
+                Err(ShapeMismatch::new("map", "byte size"))
+            }
             Value::Concrete(ConcreteValue::StringList(_)) => {
                 Err(ShapeMismatch::new("map", "string list"))
             }
@@ -1672,7 +1793,8 @@ pub(super) fn substitute_placeholder(
             UnknownReason::UpstreamRef { .. }
             | UnknownReason::UpstreamBareRef { .. }
             | UnknownReason::EmptyInterpolation
-            | UnknownReason::PostCreateReadIncomplete { .. } => {}
+            | UnknownReason::PostCreateReadIncomplete { .. }
+            | UnknownReason::ForGroup { .. } => {}
             // Function placeholders are substituted by user-function evaluation, not for expansion.
             UnknownReason::FnParam { .. } | UnknownReason::FnLocal { .. } => {}
         },
@@ -1785,6 +1907,7 @@ mod substitute_placeholder_tests {
             name: "mock".to_string(),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             source: None,
             version: None,
             revision: None,
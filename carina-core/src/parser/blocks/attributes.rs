@@ -1,5 +1,5 @@
 //! `arguments { }`, `attributes { }`, `exports { }` block parsers and the
-//! `directives { }` meta-argument extractor.
+//! `directives { }` / `timeouts { }` meta-argument extractors.
 //!
 //! Extracted from `parser/mod.rs` per #2263 (part 2/2).
 
@@ -12,7 +12,9 @@ use crate::parser::expressions::string_literal::parse_string_value;
 use crate::parser::expressions::validate_expr::parse_validate_expr;
 use crate::parser::parse_expression;
 use crate::parser::types::parse_type_expr;
-use crate::resource::{ConcreteValue, DeferredValue, Directives, Resource, Value};
+use crate::resource::{
+    ConcreteValue, DeferredValue, Directives, OperationTimeouts, Resource, UnknownReason, Value,
+};
 use indexmap::IndexMap;
 
 /// Parse arguments block. See `register_argument_binding` for the
@@ -292,12 +294,60 @@ pub(in crate::parser) fn extract_directives(
                 prevent_destroy,
                 depends_on,
                 provider_instance,
+                timeouts: OperationTimeouts::default(),
+                enabled: map.get("enabled").cloned(),
             });
         }
     }
     Ok(Directives::default())
 }
 
+/// Extract the `timeouts { create = 20m, ... }` block from a resource's
+/// attributes. Like `directives`, it is a meta-argument: it configures
+/// how long the provider may wait, and never reaches the resource.
+/// `line` is where the block was written, for error messages.
+pub(in crate::parser) fn extract_timeouts(
+    attributes: &mut IndexMap<String, Value>,
+    line: usize,
+) -> Result<OperationTimeouts, ParseError> {
+    let mut timeouts = OperationTimeouts::default();
+    let Some(Value::Concrete(ConcreteValue::List(blocks))) = attributes.shift_remove("timeouts")
+    else {
+        return Ok(timeouts);
+    };
+    let Some(Value::Concrete(ConcreteValue::Map(map))) = blocks.into_iter().next() else {
+        return Ok(timeouts);
+    };
+    for (key, value) in map {
+        let slot = match key.as_str() {
+            "create" => &mut timeouts.create,
+            "update" => &mut timeouts.update,
+            "delete" => &mut timeouts.delete,
+            _ => {
+                return Err(ParseError::InvalidExpression {
+                    line,
+                    message: format!(
+                        "timeouts.{key}: unknown operation; expected create, update, or delete"
+                    ),
+                });
+            }
+        };
+        match value {
+            Value::Concrete(ConcreteValue::Duration(d)) if !d.is_zero() => *slot = Some(d),
+            other => {
+                return Err(ParseError::InvalidExpression {
+                    line,
+                    message: format!(
+                        "timeouts.{key}: must be a non-zero duration like `20m`, got {:?}",
+                        other
+                    ),
+                });
+            }
+        }
+    }
+    Ok(timeouts)
+}
+
 /// Interpret a `Value` as a bare binding-name reference. Used by every
 /// `directives { ... }` slot whose value must be `<binding>` (currently
 /// `depends_on`'s list elements and `provider`).
@@ -313,7 +363,10 @@ pub(in crate::parser) fn extract_directives(
 /// indistinguishable.
 fn value_as_binding_name(value: &Value, context: &str) -> Result<String, ParseError> {
     match value {
-        Value::Deferred(DeferredValue::BindingRef { binding }) => Ok(binding.clone()),
+        Value::Deferred(DeferredValue::BindingRef { binding })
+        | Value::Deferred(DeferredValue::Unknown(UnknownReason::ForGroup { binding })) => {
+            Ok(binding.clone())
+        }
         Value::Concrete(ConcreteValue::EnumIdentifier(name)) => Ok(name.to_string()),
         Value::Concrete(ConcreteValue::String(name)) => {
             let bare = name
@@ -5,6 +5,7 @@
 pub(super) mod attributes;
 pub(super) mod backend;
 pub(super) mod module_call;
+pub(super) mod naming;
 pub(super) mod provider;
 pub(super) mod resource;
 pub(super) mod state;
@@ -0,0 +1,119 @@
+//! `naming { ... }` parser.
+
+use std::collections::HashMap;
+
+use crate::parser::Rule;
+use crate::parser::ast::{ModuleCall, NamingPolicy};
+use crate::parser::context::{ParseContext, next_pair};
+use crate::parser::error::ParseError;
+use crate::parser::parse_expression;
+use crate::parser::util::extract_string_from_pair;
+use crate::validation::naming::{NamingPattern, NamingTemplate};
+use indexmap::IndexMap;
+
+/// Parse a `naming { pattern = "..." }` or
+/// `naming { template = "...", <placeholder> = "..." }` block.
+///
+/// Every attribute must be a string literal: the policy is evaluated
+/// before any binding exists. The policy is compiled here, once, so a
+/// bad regex or malformed template fails at parse time with the block's
+/// line.
+pub(in crate::parser) fn parse_naming_block(
+    pair: pest::iterators::Pair<Rule>,
+) -> Result<NamingPolicy, ParseError> {
+    let (line, _) = pair.as_span().start_pos().line_col();
+    let invalid = |message: String| ParseError::InvalidExpression { line, message };
+
+    let mut pattern = None;
+    let mut template = None;
+    let mut values = IndexMap::new();
+    for attr_pair in pair.into_inner() {
+        if attr_pair.as_rule() != Rule::attribute {
+            continue;
+        }
+        let mut attr_inner = attr_pair.into_inner();
+        let key = next_pair(&mut attr_inner, "attribute name", "naming block")?
+            .as_str()
+            .to_string();
+        let value = extract_string_from_pair(next_pair(
+            &mut attr_inner,
+            "attribute value",
+            "naming block",
+        )?)
+        .map_err(|_| invalid(format!("naming block: '{key}' must be a string literal")))?;
+        match key.as_str() {
+            "pattern" => pattern = Some(value),
+            "template" => template = Some(value),
+            _ => {
+                values.insert(key, value);
+            }
+        }
+    }
+
+    let compiled = |e: String| invalid(format!("naming block: {e}"));
+    match (pattern, template) {
+        (Some(pattern), None) if values.is_empty() => NamingPattern::new(&pattern)
+            .map(NamingPolicy::Pattern)
+            .map_err(compiled),
+        (Some(_), None) => Err(invalid(
+            "naming block: placeholder values require a 'template'".to_string(),
+        )),
+        (None, Some(template)) => NamingTemplate::new(&template, values)
+            .map(NamingPolicy::Template)
+            .map_err(compiled),
+        _ => Err(invalid(
+            "naming block requires exactly one of 'pattern' or 'template'".to_string(),
+        )),
+    }
+}
+
+/// Whether the file binds `naming` at the top level, e.g. as a module
+/// alias (`let naming = use { ... }`).
+pub(in crate::parser) fn binds_naming(pairs: pest::iterators::Pairs<Rule>) -> bool {
+    pairs
+        .flat_map(|file| file.into_inner())
+        .filter(|statement| statement.as_rule() == Rule::statement)
+        .flat_map(|statement| statement.into_inner())
+        .filter(|stmt| stmt.as_rule() == Rule::let_binding)
+        .any(|stmt| {
+            stmt.into_inner()
+                .next()
+                .is_some_and(|name| name.as_str() == "naming")
+        })
+}
+
+/// Parse `naming { arg = value, ... }` as a call to the module bound to
+/// `naming`, for files where [`binds_naming`] holds.
+pub(in crate::parser) fn parse_naming_module_call(
+    pair: pest::iterators::Pair<Rule>,
+    ctx: &ParseContext,
+) -> Result<ModuleCall, ParseError> {
+    let mut arguments = HashMap::new();
+    for attr_pair in pair.into_inner() {
+        if attr_pair.as_rule() != Rule::attribute {
+            continue;
+        }
+        let (line, _) = attr_pair.as_span().start_pos().line_col();
+        let mut attr_inner = attr_pair.into_inner();
+        let key = next_pair(&mut attr_inner, "argument name", "module call argument")?;
+        if key.as_rule() != Rule::identifier {
+            return Err(ParseError::InvalidExpression {
+                line,
+                message: format!(
+                    "module argument name {} must be an identifier",
+                    key.as_str()
+                ),
+            });
+        }
+        let value = parse_expression(
+            next_pair(&mut attr_inner, "argument value", "module call argument")?,
+            ctx,
+        )?;
+        arguments.insert(key.as_str().to_string(), value);
+    }
+    Ok(ModuleCall {
+        module_name: "naming".to_string(),
+        binding_name: None,
+        arguments,
+    })
+}
@@ -17,6 +17,7 @@ pub(in crate::parser) fn parse_provider_block(
     pair: pest::iterators::Pair<Rule>,
     ctx: &ParseContext,
 ) -> Result<ProviderConfig, ParseError> {
+    let line = pair.as_span().start_pos().line_col().0;
     let mut inner = pair.into_inner();
     let name = next_pair(&mut inner, "provider name", "provider block")?
         .as_str()
@@ -52,6 +53,20 @@ pub(in crate::parser) fn parse_provider_block(
         None => IndexMap::new(),
     };
 
+    let provenance_tags = match attributes.shift_remove("provenance_tags") {
+        Some(Value::Concrete(ConcreteValue::Bool(enabled))) => enabled,
+        Some(_) => {
+            return Err(ParseError::InvalidExpression {
+                line,
+                message: format!(
+                    "Provider '{}': 'provenance_tags' must be true or false",
+                    name
+                ),
+            });
+        }
+        None => false,
+    };
+
     // Extract source from attributes if present
     let source = if let Some(Value::Concrete(ConcreteValue::String(s))) =
         attributes.shift_remove("source")
@@ -101,6 +116,7 @@ pub(in crate::parser) fn parse_provider_block(
         name,
         attributes,
         default_tags,
+        provenance_tags,
         source,
         version,
         revision,
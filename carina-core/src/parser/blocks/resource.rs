@@ -6,7 +6,7 @@
 //! Extracted from `parser/mod.rs` per #2263 (part 2/2).
 
 use crate::parser::Rule;
-use crate::parser::blocks::attributes::extract_directives;
+use crate::parser::blocks::attributes::{extract_directives, extract_timeouts};
 use crate::parser::context::{ParseContext, extract_key_string, first_inner, next_pair};
 use crate::parser::error::ParseError;
 use crate::parser::parse_expression;
@@ -19,9 +19,11 @@ pub(in crate::parser) fn parse_anonymous_resource(
     pair: pest::iterators::Pair<Rule>,
     ctx: &ParseContext,
 ) -> Result<Resource, ParseError> {
+    let line = pair.line_col().0;
     let inner = pair.into_inner();
 
     let mut iter = inner;
+    let timeouts_line = nested_block_line(&iter, "timeouts").unwrap_or(line);
     let namespaced_type = next_pair(&mut iter, "resource type", "anonymous resource")?
         .as_str()
         .to_string();
@@ -46,7 +48,8 @@ pub(in crate::parser) fn parse_anonymous_resource(
     );
 
     // Extract directives block from attributes (it's a meta-argument, not a real attribute)
-    let directives = extract_directives(&mut attributes)?;
+    let mut directives = extract_directives(&mut attributes)?;
+    directives.timeouts = extract_timeouts(&mut attributes, timeouts_line)?;
 
     let id = ResourceId::with_provider(
         provider,
@@ -67,6 +70,23 @@ pub(in crate::parser) fn parse_anonymous_resource(
     })
 }
 
+/// Line of the first nested `name { ... }` block among a resource's
+/// contents.
+fn nested_block_line(pairs: &pest::iterators::Pairs<Rule>, name: &str) -> Option<usize> {
+    pairs
+        .clone()
+        .filter(|pair| pair.as_rule() == Rule::block_content)
+        .filter_map(|pair| pair.into_inner().next())
+        .filter(|pair| pair.as_rule() == Rule::nested_block)
+        .find(|pair| {
+            pair.clone()
+                .into_inner()
+                .next()
+                .is_some_and(|block_name| block_name.as_str() == name)
+        })
+        .map(|pair| pair.line_col().0)
+}
+
 /// Parse block contents (attributes, nested blocks, and local let bindings)
 /// Nested blocks with the same name are collected into a list.
 /// Local let bindings are resolved within the block scope and NOT included in
@@ -188,7 +208,9 @@ pub(crate) fn parse_resource_expr(
     ctx: &ParseContext,
     binding_name: &str,
 ) -> Result<Resource, ParseError> {
+    let line = pair.line_col().0;
     let mut inner = pair.into_inner();
+    let timeouts_line = nested_block_line(&inner, "timeouts").unwrap_or(line);
 
     let namespaced_type = next_pair(&mut inner, "resource type", "resource expression")?
         .as_str()
@@ -212,7 +234,8 @@ pub(crate) fn parse_resource_expr(
     let resource_name = binding_name.to_string();
 
     // Extract directives block from attributes (it's a meta-argument, not a real attribute)
-    let directives = extract_directives(&mut attributes)?;
+    let mut directives = extract_directives(&mut attributes)?;
+    directives.timeouts = extract_timeouts(&mut attributes, timeouts_line)?;
 
     attributes.insert(
         "_type".to_string(),
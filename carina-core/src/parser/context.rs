@@ -53,6 +53,11 @@ pub(crate) struct ParseContext<'cfg> {
     /// collide with the seed installed because the same name is
     /// declared in a sibling `.crn`. See #2817.
     pub(super) seeded_bindings: HashSet<String>,
+    /// Set for the directory loader's provisional passes, whose output
+    /// only feeds binding-name and seed collection. Expressions that
+    /// depend on a sibling `let` not seeded yet degrade to a placeholder
+    /// there instead of failing; the final pass still reports them.
+    pub(super) provisional: bool,
 }
 
 impl<'cfg> ParseContext<'cfg> {
@@ -71,6 +76,7 @@ impl<'cfg> ParseContext<'cfg> {
             warnings: Vec::new(),
             deferred_for_expressions: Vec::new(),
             seeded_bindings: HashSet::new(),
+            provisional: false,
         }
     }
 
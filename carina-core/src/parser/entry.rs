@@ -12,10 +12,11 @@ use super::blocks::attributes::{
 };
 use super::blocks::backend::parse_backend_block;
 use super::blocks::module_call::parse_module_call;
+use super::blocks::naming::{binds_naming, parse_naming_block, parse_naming_module_call};
 use super::blocks::provider::{parse_provider_block, parse_require_statement};
 use super::blocks::resource::parse_anonymous_resource;
 use super::blocks::state::{parse_import_state_block, parse_moved_block, parse_removed_block};
-use super::context::{ParseContext, first_inner};
+use super::context::{ParseContext, first_inner, next_pair};
 use super::error::{
     ParseError, ParseWarning, ParseWarningSpan, SINGLE_QUOTED_INTERPOLATION_WARNING_MESSAGE,
     WarningKind,
@@ -137,7 +138,7 @@ fn parse_with_seeded_bindings_inner(
 ) -> Result<ParsedFile, ParseError> {
     let preprocess_result =
         crate::heredoc::preprocess_heredocs(input).map_err(|e| ParseError::InvalidExpression {
-            line: 0,
+            line: e.line(),
             message: e.to_string(),
         })?;
     let pairs = CarinaParser::parse(Rule::file, &preprocess_result.source)
@@ -149,8 +150,15 @@ fn parse_with_seeded_bindings_inner(
     };
 
     let mut ctx = ParseContext::new(config);
+    // Literal warnings are skipped exactly for the directory loader's
+    // provisional passes; the final pass collects them.
+    ctx.provisional = !collect_literal_warnings;
     ctx.warnings.extend(single_quote_warnings);
     seed_bindings(&mut ctx, seeds);
+    // `naming` became a keyword after configurations could already use
+    // it as a module alias; where it is bound, `naming { ... }` still
+    // calls the module.
+    let naming_is_binding = ctx.is_seeded_binding("naming") || binds_naming(pairs.clone());
     let mut providers = Vec::new();
     let mut resources = Vec::new();
     let mut data_sources: Vec<DataSource> = Vec::new();
@@ -158,8 +166,10 @@ fn parse_with_seeded_bindings_inner(
     let mut module_calls = Vec::new();
     let mut arguments = Vec::new();
     let mut attribute_params = Vec::new();
+    let mut variable_params = Vec::new();
     let mut export_params = Vec::new();
     let mut backend = None;
+    let mut naming = None;
     let mut state_blocks = Vec::new();
     let mut upstream_states: Vec<UpstreamState> = Vec::new();
     let mut wait_bindings: Vec<WaitBinding> = Vec::new();
@@ -176,6 +186,18 @@ fn parse_with_seeded_bindings_inner(
                             Rule::backend_block => {
                                 backend = Some(parse_backend_block(stmt, &ctx)?);
                             }
+                            Rule::naming_block if naming_is_binding => {
+                                module_calls.push(parse_naming_module_call(stmt, &ctx)?);
+                            }
+                            Rule::naming_block => {
+                                if naming.is_some() {
+                                    return Err(ParseError::InvalidExpression {
+                                        line: stmt.as_span().start_pos().line_col().0,
+                                        message: "multiple naming blocks defined".to_string(),
+                                    });
+                                }
+                                naming = Some(parse_naming_block(stmt)?);
+                            }
                             Rule::provider_block => {
                                 let provider = parse_provider_block(stmt, &ctx)?;
                                 providers.push(provider);
@@ -199,6 +221,23 @@ fn parse_with_seeded_bindings_inner(
                                 };
                                 arguments.extend(parsed_arguments);
                             }
+                            Rule::variables_block => {
+                                // Same parameter grammar and incremental
+                                // scoping as `arguments`; only the source of
+                                // the values differs (`--var-file`).
+                                let parsed_variables = {
+                                    let mut warnings = std::mem::take(&mut ctx.warnings);
+                                    let result = parse_arguments_block(
+                                        stmt,
+                                        config,
+                                        &mut ctx,
+                                        &mut warnings,
+                                    );
+                                    ctx.warnings = warnings;
+                                    result?
+                                };
+                                variable_params.extend(parsed_variables);
+                            }
                             Rule::attributes_block => {
                                 let parsed_attribute_params = {
                                     let warnings = std::mem::take(&mut ctx.warnings);
@@ -462,8 +501,10 @@ fn parse_with_seeded_bindings_inner(
         module_calls,
         arguments,
         attribute_params,
+        variable_params,
         export_params,
         backend,
+        naming,
         state_blocks,
         user_functions: ctx.user_functions,
         upstream_states,
@@ -657,6 +698,33 @@ pub(crate) fn parse_expression_eval(
     parse_coalesce_expr(inner, ctx)
 }
 
+/// Parse a variable file (`*.crnvars`): one `name = <expression>` per
+/// assignment. Values are parsed with no bindings in scope, so they must
+/// be literals; a name assigned twice is a [`ParseError::DuplicateBinding`].
+pub fn parse_var_file(
+    input: &str,
+    config: &ProviderContext,
+) -> Result<IndexMap<String, Value>, ParseError> {
+    let pairs = CarinaParser::parse(Rule::var_file, input)?;
+    let ctx = ParseContext::new(config);
+    let mut values = IndexMap::new();
+    for pair in pairs.flatten() {
+        if pair.as_rule() != Rule::var_assignment {
+            continue;
+        }
+        let line = pair.line_col().0;
+        let mut inner = pair.into_inner();
+        let name = next_pair(&mut inner, "variable name", "var_assignment")?
+            .as_str()
+            .to_string();
+        let value = parse_expression(next_pair(&mut inner, "expression", "var_assignment")?, &ctx)?;
+        if values.insert(name.clone(), value).is_some() {
+            return Err(ParseError::DuplicateBinding { name, line });
+        }
+    }
+    Ok(values)
+}
+
 /// Parse a .crn file and resolve resource references.
 ///
 /// `finalize_provider_configs` is called at the end so deferred
@@ -63,6 +63,7 @@ fn is_scalar_value(bound: &Value) -> bool {
                 | ConcreteValue::Float(_)
                 | ConcreteValue::Bool(_)
                 | ConcreteValue::Duration(_)
+                | ConcreteValue::ByteSize(_)
                 | ConcreteValue::EnumIdentifier(_)
         )
     )
@@ -92,8 +93,9 @@ pub(crate) fn parse_duration_secs(src: &str, line: usize) -> Result<u64, ParseEr
         "s" | "sec" | "second" | "seconds" => 1,
         "m" | "min" | "minute" | "minutes" => 60,
         "h" | "hr" | "hour" | "hours" => 3600,
+        "d" | "day" | "days" => 86_400,
         // Reachable only if a future grammar change adds a unit family
-        // (e.g. `day` / `week`) without updating this match. Surface as
+        // (e.g. `week`) without updating this match. Surface as
         // a typed parse error rather than a panic so the binary stays
         // up while the developer fills in the multiplier.
         other => {
@@ -117,6 +119,37 @@ pub(crate) fn parse_duration_literal(src: &str, line: usize) -> Result<Value, Pa
     )))
 }
 
+/// Decode a byte-size literal (`512MiB`, `5GB`) into bytes. Mirrors
+/// [`parse_duration_secs`], including the typed overflow error.
+pub(crate) fn parse_byte_size_literal(src: &str, line: usize) -> Result<Value, ParseError> {
+    let unit_start = src
+        .find(|c: char| !c.is_ascii_digit())
+        .expect("grammar guarantees a non-digit unit suffix");
+    let n: u64 = src[..unit_start]
+        .parse()
+        .map_err(|e| ParseError::InvalidExpression {
+            line,
+            message: format!("invalid byte size integer in {src:?}: {e}"),
+        })?;
+    let unit = &src[unit_start..];
+    let Some((_, factor)) = crate::schema::BYTE_SIZE_UNITS
+        .into_iter()
+        .find(|(name, _)| *name == unit)
+    else {
+        return Err(ParseError::InvalidExpression {
+            line,
+            message: format!("byte size unit {unit:?} is not supported by this build"),
+        });
+    };
+    let bytes = n
+        .checked_mul(factor)
+        .ok_or_else(|| ParseError::InvalidExpression {
+            line,
+            message: format!("byte size {src} overflows u64 bytes"),
+        })?;
+    Ok(Value::Concrete(ConcreteValue::ByteSize(bytes)))
+}
+
 /// Convert an index-expression value into a `Subscript`. Only
 /// non-negative integer and string keys are legal subscripts; anything
 /// else is a parse error. Negative integers are rejected here rather
@@ -141,6 +174,56 @@ fn subscript_from_value(value: Value) -> Result<Subscript, ParseError> {
     }
 }
 
+/// Read `subscripts` out of the `let`-bound list or map `bound`.
+///
+/// `Ok(None)` when `bound` is not a concrete collection (a deferred
+/// value or a secret), so the caller keeps the reference as written;
+/// `Err` when a key or index is missing from a concrete one.
+fn index_bound_value(
+    name: &str,
+    bound: &Value,
+    subscripts: &[Subscript],
+) -> Result<Option<Value>, String> {
+    let mut current = bound;
+    let mut address = name.to_string();
+    for subscript in subscripts {
+        current = match (current, subscript) {
+            (Value::Concrete(ConcreteValue::List(items)), Subscript::Int { index }) => {
+                let element = usize::try_from(*index).ok().and_then(|i| items.get(i));
+                element.ok_or_else(|| {
+                    format!(
+                        "index {} is out of range for '{}' (length {})",
+                        index,
+                        address,
+                        items.len()
+                    )
+                })?
+            }
+            (Value::Concrete(ConcreteValue::Map(map)), Subscript::Str { key }) => map
+                .get(key)
+                .ok_or_else(|| format!("'{}' has no key '{}'", address, key))?,
+            (Value::Concrete(ConcreteValue::List(_)), Subscript::Str { key }) => {
+                return Err(format!(
+                    "'{}' is a list and cannot be indexed by '{}'",
+                    address, key
+                ));
+            }
+            (Value::Concrete(ConcreteValue::Map(_)), Subscript::Int { index }) => {
+                return Err(format!(
+                    "'{}' is a map and cannot be indexed by {}",
+                    address, index
+                ));
+            }
+            _ => return Ok(None),
+        };
+        address = match subscript {
+            Subscript::Int { index } => format!("{}[{}]", address, index),
+            Subscript::Str { key } => crate::utils::map_key_address(&address, key),
+        };
+    }
+    Ok(Some(current.clone()))
+}
+
 /// Lower a `namespaced_id` pair to its `EvalValue`, attaching any
 /// trailing path segments collected by the caller (the `subscripted_id`
 /// arm — a mix of `.field` and `[index]` continuations). String-form
@@ -386,6 +469,11 @@ pub(crate) fn parse_primary_eval(
             let value = parse_duration_literal(inner.as_str(), line)?;
             Ok(EvalValue::from_value(value))
         }
+        Rule::byte_size_literal => {
+            let line = inner.line_col().0;
+            let value = parse_byte_size_literal(inner.as_str(), line)?;
+            Ok(EvalValue::from_value(value))
+        }
         Rule::string => parse_string_value(inner, ctx).map(EvalValue::from_value),
         Rule::function_call => {
             let mut fc_inner = inner.into_inner();
@@ -441,6 +529,24 @@ pub(crate) fn parse_primary_eval(
                 });
             }
 
+            // A deferred for-expression has no members to expand yet; the
+            // call is kept and expanded along with the for-expression.
+            if crate::builtins::expands_at_parse_time(&func_name)
+                && args
+                    .first()
+                    .is_none_or(|arg| crate::builtins::deferred_for_group(arg).is_none())
+            {
+                let eval_args: Vec<EvalValue> =
+                    args.iter().cloned().map(EvalValue::from_value).collect();
+                return crate::builtins::evaluate_builtin_with_config(
+                    &func_name, &eval_args, ctx.config,
+                )
+                .map_err(|e| ParseError::InvalidExpression {
+                    line: 0,
+                    message: format!("{}(): {}", func_name, e),
+                });
+            }
+
             Ok(EvalValue::from_value(Value::Deferred(
                 DeferredValue::FunctionCall {
                     name: func_name,
@@ -500,6 +606,7 @@ pub(crate) fn parse_primary_eval(
                 let mut binding_name = first_ident.to_string();
                 let mut field_names: Vec<String> = Vec::new();
                 let mut subscripts: Vec<Subscript> = Vec::new();
+                let mut pre_field_subscripts: Vec<Subscript> = Vec::new();
                 let mut in_field_phase = false;
 
                 for step in access_steps {
@@ -508,10 +615,27 @@ pub(crate) fn parse_primary_eval(
                             let index_expr_pair =
                                 first_inner(step, "index expression", "index access")?;
                             let index_value = parse_expression(index_expr_pair, ctx)?;
+                            // A key naming a sibling `let` that is not
+                            // seeded yet (`cidrs[region]`) cannot be
+                            // evaluated on a provisional pass; stand in
+                            // a reference to the head until it is.
+                            if ctx.provisional
+                                && matches!(
+                                    index_value,
+                                    Value::Concrete(ConcreteValue::EnumIdentifier(_))
+                                )
+                            {
+                                return Ok(EvalValue::from_value(Value::Deferred(
+                                    DeferredValue::BindingRef {
+                                        binding: first_ident.to_string(),
+                                    },
+                                )));
+                            }
                             let subscript = subscript_from_value(index_value)?;
                             if in_field_phase {
                                 subscripts.push(subscript);
                             } else {
+                                pre_field_subscripts.push(subscript.clone());
                                 // Pre-field index — fold into the
                                 // binding name string per the legacy
                                 // convention so `for`-iteration
@@ -554,14 +678,32 @@ pub(crate) fn parse_primary_eval(
                     // was the source of the empty-field diagnostic in
                     // #2847; the new variant makes that representation
                     // unrepresentable.
-                    match ctx.get_variable(&binding_name) {
-                        Some(val) => Ok(val.clone()),
-                        None => Ok(EvalValue::from_value(Value::Deferred(
-                            DeferredValue::BindingRef {
-                                binding: binding_name,
-                            },
-                        ))),
+                    if let Some(val) = ctx.get_variable(&binding_name) {
+                        return Ok(val.clone());
+                    }
+                    // `cidrs['prod']` / `azs[0]` on a `let`-bound map or
+                    // list reads the element directly.
+                    if !ctx.is_resource_binding(first_ident)
+                        && let Some(crate::eval_value::EvalValue::User(bound)) =
+                            ctx.get_variable(first_ident)
+                        && let Some(element) =
+                            index_bound_value(first_ident, bound, &pre_field_subscripts).or_else(
+                                |message| {
+                                    if ctx.provisional {
+                                        Ok(None)
+                                    } else {
+                                        Err(ParseError::InvalidExpression { line: 0, message })
+                                    }
+                                },
+                            )?
+                    {
+                        return Ok(EvalValue::from_value(element));
                     }
+                    Ok(EvalValue::from_value(Value::Deferred(
+                        DeferredValue::BindingRef {
+                            binding: binding_name,
+                        },
+                    )))
                 } else {
                     let attribute_name = field_names.remove(0);
                     let path = AccessPath::with_fields_and_subscripts(
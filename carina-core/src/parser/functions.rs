@@ -11,9 +11,9 @@ use super::{ProviderContext, Rule, parse_expression};
 use crate::eval_value::EvalValue;
 use crate::resource::{ConcreteValue, DeferredValue, UnknownReason, Value};
 use crate::schema::{
-    TypeIdentity, validate_http_response_status_code, validate_ipv4_address, validate_ipv4_cidr,
-    validate_ipv6_address, validate_ipv6_cidr, validate_redirect_host, validate_redirect_path,
-    validate_redirect_port, validate_redirect_protocol, validate_redirect_query,
+    TypeIdentity, check_http_response_status_code, check_ipv4_address, check_ipv4_cidr,
+    check_ipv6_address, check_ipv6_cidr, check_redirect_host, check_redirect_path,
+    check_redirect_port, check_redirect_protocol, check_redirect_query,
 };
 use indexmap::IndexMap;
 use std::collections::HashMap;
@@ -236,34 +236,34 @@ pub fn validate_custom_type(
     }
     match (builtin.then_some(identity.kind.as_str()), value) {
         (Some("Ipv4Cidr"), value) if text(value).is_some() => {
-            validate_ipv4_cidr(text(value).unwrap())
+            check_ipv4_cidr(text(value).unwrap()).map_err(String::from)
         }
         (Some("Ipv4Address"), value) if text(value).is_some() => {
-            validate_ipv4_address(text(value).unwrap())
+            check_ipv4_address(text(value).unwrap()).map_err(String::from)
         }
         (Some("Ipv6Cidr"), value) if text(value).is_some() => {
-            validate_ipv6_cidr(text(value).unwrap())
+            check_ipv6_cidr(text(value).unwrap()).map_err(String::from)
         }
         (Some("Ipv6Address"), value) if text(value).is_some() => {
-            validate_ipv6_address(text(value).unwrap())
+            check_ipv6_address(text(value).unwrap()).map_err(String::from)
         }
         (Some("HttpResponseStatusCode"), value) if text(value).is_some() => {
-            validate_http_response_status_code(text(value).unwrap())
+            check_http_response_status_code(text(value).unwrap()).map_err(String::from)
         }
         (Some("RedirectHost"), value) if text(value).is_some() => {
-            validate_redirect_host(text(value).unwrap())
+            check_redirect_host(text(value).unwrap()).map_err(String::from)
         }
         (Some("RedirectPath"), value) if text(value).is_some() => {
-            validate_redirect_path(text(value).unwrap())
+            check_redirect_path(text(value).unwrap()).map_err(String::from)
         }
         (Some("RedirectPort"), value) if text(value).is_some() => {
-            validate_redirect_port(text(value).unwrap())
+            check_redirect_port(text(value).unwrap()).map_err(String::from)
         }
         (Some("RedirectProtocol"), value) if text(value).is_some() => {
-            validate_redirect_protocol(text(value).unwrap())
+            check_redirect_protocol(text(value).unwrap()).map_err(String::from)
         }
         (Some("RedirectQuery"), value) if text(value).is_some() => {
-            validate_redirect_query(text(value).unwrap())
+            check_redirect_query(text(value).unwrap()).map_err(String::from)
         }
         (_, Value::Deferred(DeferredValue::ResourceRef { .. })) => Ok(()), // will be resolved later
         (_, Value::Deferred(DeferredValue::FunctionCall { .. })) => Ok(()), // will be resolved later
@@ -22,7 +22,7 @@ use super::parse_expression;
 use super::static_eval::is_static_value;
 use super::util::eval_type_name;
 use crate::eval_value::EvalValue;
-use crate::resource::{ConcreteValue, DataSource, DeferredValue, Resource, Value};
+use crate::resource::{ConcreteValue, DataSource, DeferredValue, Resource, UnknownReason, Value};
 
 /// Tuple returned by the let-binding parser. The RHS is `EvalValue`
 /// rather than `Value` so partial applications (closures) can survive
@@ -410,9 +410,16 @@ fn parse_primary_with_resource_or_module(
             ))
         }
         Rule::for_expr => {
+            let deferred_before = ctx.deferred_for_expressions.len();
             let (resources, data_sources, module_calls) = parse_for_expr(inner, ctx, binding_name)?;
-            let ref_value =
-                Value::Concrete(ConcreteValue::String(format!("${{for:{}}}", binding_name)));
+            let ref_value = if ctx.deferred_for_expressions.len() > deferred_before {
+                // Members are only known once the iterable resolves.
+                Value::Deferred(DeferredValue::Unknown(UnknownReason::ForGroup {
+                    binding: binding_name.to_string(),
+                }))
+            } else {
+                for_group_value(&resources, &data_sources, &module_calls)
+            };
             Ok((
                 EvalValue::from_value(ref_value),
                 resources,
@@ -450,3 +457,21 @@ fn parse_primary_with_resource_or_module(
         }
     }
 }
+
+/// Value of a `let` bound to an expanded `for` expression: the member
+/// bindings (`subnets[0]`, `vpcs.prod`, ...) in iteration order. This is
+/// what `attrs()` / `ids()` turn into per-member attribute references.
+fn for_group_value(
+    resources: &[Resource],
+    data_sources: &[DataSource],
+    module_calls: &[ModuleCall],
+) -> Value {
+    let members = resources
+        .iter()
+        .filter_map(|r| r.binding.clone())
+        .chain(data_sources.iter().filter_map(|d| d.binding.clone()))
+        .chain(module_calls.iter().filter_map(|c| c.binding_name.clone()))
+        .map(|binding| Value::Deferred(DeferredValue::BindingRef { binding }))
+        .collect();
+    Value::Concrete(ConcreteValue::List(members))
+}
@@ -19,16 +19,16 @@ mod util;
 pub use ast::{
     ArgumentParameter, AttributeParameter, BackendConfig, BindingName, DeferredForExpression,
     ExportParamLike, ExportParameter, File, FnParam, InferredExportParam, InferredFile, ModuleCall,
-    ParsedExportParam, ParsedFile, ProviderConfig, RequireBlock, ResourceContext, ResourceRef,
-    ResourceTypePath, ShapeMismatch, StateBlock, StateBlockAddress, TypeExpr, UntilPredicateAst,
-    UpstreamState, UseStatement, UserFunction, UserFunctionBody, ValidateExpr, ValidationBlock,
-    WaitBinding, expand_deferred_children,
+    NamingPolicy, ParsedExportParam, ParsedFile, ProviderConfig, RequireBlock, ResourceContext,
+    ResourceRef, ResourceTypePath, ShapeMismatch, StateBlock, StateBlockAddress, TypeExpr,
+    UntilPredicateAst, UpstreamState, UseStatement, UserFunction, UserFunctionBody, ValidateExpr,
+    ValidationBlock, WaitBinding, expand_deferred_children,
 };
 pub use config::{DecryptorFn, ProviderContext, ValidatorFn};
 pub(crate) use entry::{
     BindingSeed, parse_with_seeded_bindings, parse_with_seeded_bindings_without_literal_warnings,
 };
-pub use entry::{parse, parse_and_resolve};
+pub use entry::{parse, parse_and_resolve, parse_var_file};
 pub(crate) use entry::{parse_expression, parse_expression_eval};
 pub use error::{
     ParseError, ParseWarning, ParseWarningSpan, SINGLE_QUOTED_INTERPOLATION_WARNING_MESSAGE,
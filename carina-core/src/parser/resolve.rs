@@ -134,8 +134,9 @@ pub fn resolve_resource_refs_with_config(
         }
     }
 
-    // Register argument parameters so they're recognized as valid bindings
-    for arg in &parsed.arguments {
+    // Register argument and variable parameters so they're recognized as
+    // valid bindings
+    for arg in parsed.arguments.iter().chain(&parsed.variable_params) {
         binding_map.entry(arg.name.clone()).or_default();
     }
 
@@ -404,6 +405,7 @@ fn collect_reference_roots(
             | ConcreteValue::Float(_)
             | ConcreteValue::Bool(_)
             | ConcreteValue::Duration(_)
+            | ConcreteValue::ByteSize(_)
             | ConcreteValue::String(_)
             | ConcreteValue::CanonicalEnum(_)
             | ConcreteValue::StringList(_),
@@ -812,7 +814,7 @@ pub fn resolve_provider_unresolved_attributes<E>(
             binding_map.insert(binding_name.to_string(), rref.resolved_attributes());
         }
     }
-    for arg in &parsed.arguments {
+    for arg in parsed.arguments.iter().chain(&parsed.variable_params) {
         binding_map.entry(arg.name.clone()).or_default();
     }
     for call in &parsed.module_calls {
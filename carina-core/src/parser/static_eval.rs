@@ -19,6 +19,7 @@ pub(crate) fn is_static_value(value: &Value) -> bool {
         | Value::Concrete(ConcreteValue::Float(_))
         | Value::Concrete(ConcreteValue::Bool(_))
         | Value::Concrete(ConcreteValue::Duration(_))
+        | Value::Concrete(ConcreteValue::ByteSize(_))
         | Value::Concrete(ConcreteValue::StringList(_)) => true,
         Value::Concrete(ConcreteValue::List(items)) => items.iter().all(is_static_value),
         Value::Concrete(ConcreteValue::Map(map)) => map.values().all(is_static_value),
@@ -1,8 +1,11 @@
 use super::*;
 use crate::binding_index::{IterableBindings, PreApplyInputs, ResolvedBindings};
 use crate::resolver::resolve_refs_for_plan;
-use crate::resource::{ConcreteValue, DeferredValue, InterpolationPart, Resource, Value};
+use crate::resource::{
+    ConcreteValue, DeferredValue, InterpolationPart, Resource, UnknownReason, Value,
+};
 use crate::schema::TypeIdentity;
+use crate::validation::naming::{NamingPattern, NamingTemplate};
 use indexmap::IndexMap;
 use std::collections::{HashMap, HashSet};
 
@@ -1263,6 +1266,90 @@ fn parse_backend_block() {
     assert_eq!(result.providers[0].name, "aws");
 }
 
+#[test]
+fn parse_naming_block() {
+    let input = r#"
+        naming {
+            template = "{env}-{type}-{name}"
+            env      = "prd"
+        }
+    "#;
+    let result = parse(input, &ProviderContext::default()).unwrap();
+    assert_eq!(
+        result.naming,
+        Some(NamingPolicy::Template(
+            NamingTemplate::new(
+                "{env}-{type}-{name}",
+                IndexMap::from([("env".to_string(), "prd".to_string())]),
+            )
+            .unwrap()
+        ))
+    );
+
+    let result = parse(
+        r#"naming { pattern = "^[a-z0-9-]+$" }"#,
+        &ProviderContext::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        result.naming,
+        Some(NamingPolicy::Pattern(
+            NamingPattern::new("^[a-z0-9-]+$").unwrap()
+        ))
+    );
+}
+
+#[test]
+fn parse_naming_block_rejects_invalid_policies() {
+    for (input, expected) in [
+        ("naming {}", "exactly one of 'pattern' or 'template'"),
+        (
+            r#"naming {
+                pattern  = "^a"
+                template = "{name}"
+            }"#,
+            "exactly one of 'pattern' or 'template'",
+        ),
+        (r#"naming { pattern = "(" }"#, "invalid pattern"),
+        (
+            r#"naming {
+                pattern = "^a"
+                env     = "prd"
+            }"#,
+            "require a 'template'",
+        ),
+        (r#"naming { template = "{env" }"#, "unclosed"),
+        (r#"naming { template = 42 }"#, "must be a string literal"),
+        (
+            r#"naming { pattern = "^a" }
+            naming { pattern = "^b" }"#,
+            "multiple naming blocks defined",
+        ),
+    ] {
+        let err = parse(input, &ProviderContext::default()).unwrap_err();
+        assert!(err.to_string().contains(expected), "{input}: {err}");
+    }
+}
+
+#[test]
+fn naming_block_calls_a_module_bound_to_naming() {
+    let input = r#"
+        naming {
+            env = "prd"
+        }
+
+        let naming = use { source = "./modules/naming" }
+    "#;
+    let result = parse(input, &ProviderContext::default()).unwrap();
+    assert_eq!(result.naming, None);
+    assert_eq!(result.module_calls.len(), 1);
+    assert_eq!(result.module_calls[0].module_name, "naming");
+    assert_eq!(
+        result.module_calls[0].arguments.get("env"),
+        Some(&Value::Concrete(ConcreteValue::String("prd".to_string())))
+    );
+}
+
 #[test]
 fn parse_backend_block_with_resources() {
     let input = r#"
@@ -1435,6 +1522,54 @@ fn parse_directives_anonymous_resource() {
     assert!(!result.resources[0].attributes.contains_key("directives"));
 }
 
+#[test]
+fn parse_timeouts_block() {
+    let input = r#"
+        let nat = awscc.ec2.NatGateway {
+            subnet_id = "subnet-123"
+            timeouts {
+                create = 20m
+                delete = 40m
+            }
+        }
+    "#;
+
+    let result = parse(input, &ProviderContext::default()).unwrap();
+    let resource = &result.resources[0];
+    let timeouts = resource.directives.timeouts;
+    assert_eq!(
+        timeouts.create,
+        Some(std::time::Duration::from_secs(20 * 60))
+    );
+    assert_eq!(timeouts.update, None);
+    assert_eq!(
+        timeouts.delete,
+        Some(std::time::Duration::from_secs(40 * 60))
+    );
+    assert!(!resource.attributes.contains_key("timeouts"));
+}
+
+#[test]
+fn parse_timeouts_rejects_unknown_operation_and_non_duration() {
+    for body in ["read = 5m", "create = \"20m\"", "create = 0s"] {
+        let input = format!(
+            r#"
+            let nat = awscc.ec2.NatGateway {{
+                timeouts {{
+                    {body}
+                }}
+            }}
+            "#
+        );
+        let err = parse(&input, &ProviderContext::default()).unwrap_err();
+        assert!(err.to_string().contains("timeouts."), "{body}: {err}");
+        assert!(
+            matches!(err, ParseError::InvalidExpression { line: 3, .. }),
+            "{body}: {err:?}"
+        );
+    }
+}
+
 /// Regression test for issue #146: anonymous AWSCC resources should not have
 /// a spurious "name" attribute injected into the attributes map.
 #[test]
@@ -2794,6 +2929,7 @@ fn provider_config_carries_unresolved_attributes_field() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -2856,6 +2992,32 @@ fn parse_provider_block_without_default_tags() {
     assert!(result.providers[0].default_tags.is_empty());
 }
 
+#[test]
+fn parse_provider_block_with_provenance_tags() {
+    let input = r#"
+        provider awscc {
+            region = awscc.Region.ap_northeast_1
+            provenance_tags = true
+        }
+    "#;
+
+    let result = parse(input, &ProviderContext::default()).unwrap();
+    let provider = &result.providers[0];
+    assert!(provider.provenance_tags);
+    assert!(!provider.attributes.contains_key("provenance_tags"));
+
+    let input = r#"
+        provider awscc {
+            provenance_tags = "yes"
+        }
+    "#;
+    let err = parse(input, &ProviderContext::default()).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("'provenance_tags' must be true or false")
+    );
+}
+
 #[test]
 fn parse_provider_block_with_source_and_version() {
     let input = r#"
@@ -3814,6 +3976,151 @@ fn parse_index_access_with_integer() {
     }
 }
 
+#[test]
+fn attrs_and_ids_expand_for_binding_into_member_refs() {
+    let input = r#"
+        let subnets = for az in ["ap-northeast-1a", "ap-northeast-1c"] {
+            awscc.ec2.Subnet {
+                availability_zone = az
+            }
+        }
+
+        let cidrs = {
+            stg = "10.1.0.0/16"
+            dev = "10.0.0.0/16"
+        }
+
+        let vpcs = for name, cidr in cidrs {
+            awscc.ec2.Vpc {
+                cidr_block = cidr
+            }
+        }
+
+        awscc.rds.DbSubnetGroup {
+            subnet_ids = attrs(subnets, "subnet_id")
+            vpc_ids    = ids(vpcs)
+        }
+    "#;
+
+    let result = parse(input, &ProviderContext::default()).unwrap();
+    let group = result.resources.last().unwrap();
+    assert_eq!(
+        group.get_attr("subnet_ids"),
+        Some(&Value::Concrete(ConcreteValue::List(vec![
+            Value::resource_ref("subnets[0]", "subnet_id", vec![]),
+            Value::resource_ref("subnets[1]", "subnet_id", vec![]),
+        ])))
+    );
+    // Map iteration order is sorted by key.
+    assert_eq!(
+        group.get_attr("vpc_ids"),
+        Some(&Value::Concrete(ConcreteValue::List(vec![
+            Value::resource_ref("vpcs.dev", "id", vec![]),
+            Value::resource_ref("vpcs.stg", "id", vec![]),
+        ])))
+    );
+}
+
+#[test]
+fn attrs_over_deferred_for_binding_expands_with_the_for_expression() {
+    let input = r#"
+        let orgs = upstream_state {
+            source = "../organizations"
+        }
+
+        let assignments = for account_id in orgs.accounts {
+            awscc.sso.Assignment {
+                target_id = account_id
+            }
+        }
+
+        awscc.sso.PermissionSet {
+            name             = "admins"
+            assignment_ids   = ids(assignments)
+            assignment_names = attrs(assignments, "target_id")
+        }
+    "#;
+
+    let mut parsed = parse(input, &ProviderContext::default()).unwrap();
+    let set = parsed.resources.last().unwrap(); // allow: direct — fixture test inspection
+    assert!(
+        matches!(
+            set.get_attr("assignment_ids"),
+            Some(Value::Deferred(DeferredValue::FunctionCall { name, .. })) if name == "ids"
+        ),
+        "call waits for the for-expression, got {:?}",
+        set.get_attr("assignment_ids")
+    );
+
+    let remote_bindings = HashMap::from([(
+        "orgs".to_string(),
+        HashMap::from([(
+            "accounts".to_string(),
+            Value::Concrete(ConcreteValue::List(vec![
+                Value::Concrete(ConcreteValue::String("111111111111".to_string())),
+                Value::Concrete(ConcreteValue::String("222222222222".to_string())),
+            ])),
+        )]),
+    )]);
+    parsed.expand_deferred_for_expressions(&IterableBindings::from_upstream_only(remote_bindings));
+
+    let set = parsed
+        .resources // allow: direct — fixture test inspection
+        .iter()
+        .find(|r| r.id.resource_type == "sso.PermissionSet")
+        .unwrap();
+    assert_eq!(
+        set.get_attr("assignment_ids"),
+        Some(&Value::Concrete(ConcreteValue::List(vec![
+            Value::resource_ref("assignments[0]", "id", vec![]),
+            Value::resource_ref("assignments[1]", "id", vec![]),
+        ])))
+    );
+    assert_eq!(
+        set.get_attr("assignment_names"),
+        Some(&Value::Concrete(ConcreteValue::List(vec![
+            Value::resource_ref("assignments[0]", "target_id", vec![]),
+            Value::resource_ref("assignments[1]", "target_id", vec![]),
+        ])))
+    );
+}
+
+#[test]
+fn attrs_over_unexpanded_deferred_for_binding_resolves_to_unknown() {
+    let input = r#"
+        let orgs = upstream_state {
+            source = "../organizations"
+        }
+
+        let assignments = for account_id in orgs.accounts {
+            awscc.sso.Assignment {
+                target_id = account_id
+            }
+        }
+
+        awscc.sso.PermissionSet {
+            name           = "admins"
+            assignment_ids = ids(assignments)
+        }
+    "#;
+
+    let parsed = parse(input, &ProviderContext::default()).unwrap();
+    let set = parsed.resources.last().unwrap(); // allow: direct — fixture test inspection
+    let resolved = crate::resolver::resolve_ref_value(
+        set.get_attr("assignment_ids").unwrap(),
+        &Default::default(),
+    )
+    .unwrap();
+    assert!(
+        matches!(
+            resolved,
+            Value::Deferred(DeferredValue::Unknown(UnknownReason::ForGroup { ref binding }))
+                if binding == "assignments"
+        ),
+        "got {resolved:?}"
+    );
+}
+
 #[test]
 fn parse_index_access_with_string_key() {
     // `networks["prod"].vpc_id` parses as a ResourceRef whose binding
@@ -4303,6 +4610,29 @@ fn for_expression_over_map_uses_canonical_dot_form() {
     assert_eq!(names, vec!["resources.dev", "resources.prod"]);
 }
 
+#[test]
+fn for_expression_over_keyed_list_addresses_by_element() {
+    let input = r#"
+        let azs = ["us-east-1a", "us-east-1b"]
+
+        let subnets = for az, _ in keyed(azs) {
+            awscc.ec2.Subnet {
+                availability_zone = az
+            }
+        }
+    "#;
+    let result = parse(input, &ProviderContext::default()).unwrap();
+    let names: Vec<&str> = result
+        .resources
+        .iter()
+        .map(|r| r.id.identity_or_empty())
+        .collect();
+    assert_eq!(
+        names,
+        vec!["subnets['us-east-1a']", "subnets['us-east-1b']"]
+    );
+}
+
 #[test]
 fn parse_for_expression_with_keys_function_call() {
     let input = r#"
@@ -9222,6 +9552,7 @@ fn inferred_file_holds_inferred_export_param() {
         value: None,
     };
     let f: InferredFile = InferredFile {
+        variable_params: vec![],
         export_params: vec![one],
         ..Default::default()
     };
@@ -10428,6 +10759,9 @@ fn parse_duration_literal_all_units() {
         ("1hr", Duration::from_secs(3600)),
         ("1hour", Duration::from_secs(3600)),
         ("1hours", Duration::from_secs(3600)),
+        ("7d", Duration::from_secs(7 * 86400)),
+        ("1day", Duration::from_secs(86400)),
+        ("30days", Duration::from_secs(30 * 86400)),
     ];
     for (src, expected) in cases {
         let input = format!("let t = {}", src);
@@ -10443,6 +10777,29 @@ fn parse_duration_literal_all_units() {
     }
 }
 
+#[test]
+fn parse_byte_size_literal_units() {
+    let cases: &[(&str, u64)] = &[
+        ("512B", 512),
+        ("4KB", 4_000),
+        ("4KiB", 4 << 10),
+        ("5GB", 5_000_000_000),
+        ("512MiB", 512 << 20),
+        ("1TiB", 1 << 40),
+    ];
+    for (src, expected) in cases {
+        let input = format!("let t = {}", src);
+        let parsed =
+            parse_and_resolve(&input).unwrap_or_else(|e| panic!("parse failed for {src}: {e}"));
+        match parsed.variables.get("t").expect("t binding present") {
+            Value::Concrete(ConcreteValue::ByteSize(n)) => assert_eq!(n, expected, "case {src}"),
+            other => panic!("case {src}: expected ByteSize, got {other:?}"),
+        }
+    }
+    // `5m` stays a duration; `5mb` is not a unit.
+    assert!(parse_and_resolve("let t = 5mb").is_err());
+}
+
 #[test]
 fn parse_bare_number_still_parses_as_int() {
     // Regression: an integer literal without a unit suffix must still
@@ -10955,6 +11312,7 @@ fn provider_config_named_instance_serde_round_trip() {
             m
         },
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -10976,6 +11334,7 @@ fn provider_config_default_instance_serde_round_trip() {
         name: "aws".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: Some("github.com/x/y".to_string()),
         version: None,
         revision: None,
@@ -11817,3 +12176,147 @@ mod loop_var_field_access_matrix {
         );
     }
 }
+
+#[test]
+fn parse_var_file_reads_assignments() {
+    let values = parse_var_file(
+        "# dev environment\nenv = \"dev\"\nreplicas = 2\nzones = [\"a\", \"b\"]\n",
+        &ProviderContext::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        values.get("env"),
+        Some(&Value::Concrete(ConcreteValue::String("dev".to_string())))
+    );
+    assert_eq!(
+        values.get("replicas"),
+        Some(&Value::Concrete(ConcreteValue::Int(2)))
+    );
+    assert_eq!(
+        values.keys().collect::<Vec<_>>(),
+        ["env", "replicas", "zones"]
+    );
+}
+
+#[test]
+fn parse_var_file_rejects_duplicate_names() {
+    let err = parse_var_file(
+        "env = \"dev\"\nenv = \"prod\"\n",
+        &ProviderContext::default(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ParseError::DuplicateBinding { ref name, line: 2 } if name == "env"));
+}
+
+#[test]
+fn parse_variables_block() {
+    let result = parse(
+        "variables {\n  env: String = \"dev\"\n}\n\nawscc.s3.Bucket {\n  bucket_name = \"app-${env}\"\n}\n",
+        &ProviderContext::default(),
+    )
+    .unwrap();
+    assert_eq!(result.variable_params.len(), 1);
+    assert_eq!(result.variable_params[0].name, "env");
+    assert!(result.arguments.is_empty());
+}
+
+#[test]
+fn parse_directory_files_resolves_index_access_into_sibling_locals() {
+    let files = vec![
+        (
+            std::path::PathBuf::from("locals.crn"),
+            "let cidrs = { prod = '10.0.0.0/16', dev = '10.1.0.0/16' }\nlet azs = ['ap-northeast-1a', 'ap-northeast-1c']".to_string(),
+        ),
+        (
+            std::path::PathBuf::from("main.crn"),
+            r#"
+            let vpc_cidr = cidrs[env]
+            let env = 'prod'
+
+            awscc.ec2.Subnet {
+                cidr_block        = cidrs['dev']
+                availability_zone = azs[1]
+            }
+            "#
+            .to_string(),
+        ),
+    ];
+
+    let parsed = crate::config_loader::parse_directory_files(&files, &ProviderContext::default())
+        .expect("index access into sibling locals must parse");
+    let main = parsed[1].1.parsed();
+
+    assert_eq!(
+        main.variables.get("vpc_cidr"),
+        Some(&Value::Concrete(ConcreteValue::String(
+            "10.0.0.0/16".to_string()
+        )))
+    );
+    let subnet = &main.resources[0];
+    assert_eq!(
+        subnet.get_attr("cidr_block"),
+        Some(&Value::Concrete(ConcreteValue::String(
+            "10.1.0.0/16".to_string()
+        )))
+    );
+    assert_eq!(
+        subnet.get_attr("availability_zone"),
+        Some(&Value::Concrete(ConcreteValue::String(
+            "ap-northeast-1c".to_string()
+        )))
+    );
+}
+
+#[test]
+fn index_access_into_let_collection_rejects_missing_key() {
+    let err = parse(
+        "let cidrs = { prod = '10.0.0.0/16' }\nlet c = cidrs['stg']",
+        &ProviderContext::default(),
+    )
+    .expect_err("missing map key must fail");
+    assert!(
+        err.to_string().contains("'cidrs' has no key 'stg'"),
+        "got: {err}"
+    );
+
+    let err = parse(
+        "let azs = ['ap-northeast-1a']\nlet az = azs[2]",
+        &ProviderContext::default(),
+    )
+    .expect_err("out-of-range index must fail");
+    assert!(
+        err.to_string()
+            .contains("index 2 is out of range for 'azs'"),
+        "got: {err}"
+    );
+}
+
+#[test]
+fn secret_taint_follows_interpolation_and_function_calls() {
+    let input = r#"
+        let password = secret('hunter2')
+
+        awscc.rds.DbInstance {
+            master_user_password = "pw-${password}"
+            connection           = join(':', ['admin', password])
+            port                 = 5432
+        }
+    "#;
+    let mut parsed = parse(input, &ProviderContext::default()).unwrap();
+    resolve_resource_refs(&mut parsed).unwrap();
+    let db = &parsed.resources[0];
+    let secret = |s: &str| {
+        Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+            ConcreteValue::String(s.to_string()),
+        ))))
+    };
+    assert_eq!(
+        db.get_attr("master_user_password"),
+        Some(&secret("pw-hunter2"))
+    );
+    assert_eq!(db.get_attr("connection"), Some(&secret("admin:hunter2")));
+    assert_eq!(
+        db.get_attr("port"),
+        Some(&Value::Concrete(ConcreteValue::Int(5432)))
+    );
+}
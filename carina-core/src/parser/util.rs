@@ -56,6 +56,7 @@ pub(crate) fn value_type_name(value: &Value) -> &'static str {
         Value::Concrete(ConcreteValue::Float(_)) => "float",
         Value::Concrete(ConcreteValue::Bool(_)) => "bool",
         Value::Concrete(ConcreteValue::Duration(_)) => "duration",
+        Value::Concrete(ConcreteValue::ByteSize(_)) => "byte size",
         Value::Concrete(ConcreteValue::List(_)) => "list",
         Value::Concrete(ConcreteValue::StringList(_)) => "list",
         Value::Concrete(ConcreteValue::Map(_)) => "map",
@@ -0,0 +1,363 @@
+//! Stable, review-oriented JSON view of a plan.
+//!
+//! The serialized [`Plan`] mirrors executor internals and changes shape
+//! whenever they do. [`PlanChanges`] is the documented contract for
+//! tools that read saved plans (CI review, policy checks, cost
+//! reports): one [`ResourceChange`] per resource, with its address, the
+//! action, attribute values before and after, and why a replacement was
+//! needed. It is versioned independently by
+//! [`PLAN_CHANGES_FORMAT_VERSION`]; new fields may be added within a
+//! version, but existing ones keep their meaning.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::effect::Effect;
+use crate::plan::{Plan, ReplaceDisplayInfo};
+use crate::resource::{ResourceId, State, Value};
+use crate::value::value_to_json;
+
+/// Version of the [`PlanChanges`] format.
+pub const PLAN_CHANGES_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanChanges {
+    pub format_version: u32,
+    pub resource_changes: Vec<ResourceChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Create,
+    Update,
+    Delete,
+    /// Destroy and recreate; see [`ResourceChange::replacement_reasons`].
+    Replace,
+    Read,
+    Import,
+    /// Forget the resource without destroying it.
+    Remove,
+    /// Rename in state; see [`ResourceChange::previous_address`].
+    Move,
+    /// Resources created at apply time from a `for` over a value that
+    /// is only known then.
+    DeferredCreate,
+    /// A resource destroyed and recreated at apply time from a `for`
+    /// over a value that is only known then.
+    DeferredReplace,
+}
+
+/// One resource the plan acts on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceChange {
+    /// `provider.Type name`, as printed by `carina plan`.
+    pub address: String,
+    pub provider: String,
+    pub resource_type: String,
+    /// DSL name; `None` for a resource without one.
+    pub name: Option<String>,
+    pub action: ChangeAction,
+    /// Attributes before the change; `None` when the resource does not
+    /// exist yet.
+    pub before: Option<BTreeMap<String, serde_json::Value>>,
+    /// Attributes after the change; `None` when the resource goes away
+    /// or is not changed by Carina.
+    pub after: Option<BTreeMap<String, serde_json::Value>>,
+    /// Attributes whose value is only known at apply time, absent from
+    /// `after`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after_unknown: Vec<String>,
+    /// Attributes an update changes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_attributes: Vec<String>,
+    /// Create-only attributes whose change forces a replacement.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replacement_reasons: Vec<String>,
+    /// Replacement creates the new resource before destroying the old.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub create_before_destroy: bool,
+    /// Address before a `moved` rename.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_address: Option<String>,
+}
+
+impl ResourceChange {
+    fn new(id: &ResourceId, action: ChangeAction) -> Self {
+        Self {
+            address: id.human().to_string(),
+            provider: id.provider.clone(),
+            resource_type: id.resource_type.clone(),
+            name: id.identity_str().map(str::to_string),
+            action,
+            before: None,
+            after: None,
+            after_unknown: Vec::new(),
+            changed_attributes: Vec::new(),
+            replacement_reasons: Vec::new(),
+            create_before_destroy: false,
+            previous_address: None,
+        }
+    }
+
+    fn with_before(mut self, attributes: Option<&HashMap<String, Value>>) -> Self {
+        self.before = attributes.map(|attributes| attributes_to_json(attributes).0);
+        self
+    }
+
+    fn with_after<'a>(
+        mut self,
+        attributes: impl IntoIterator<Item = (&'a String, &'a Value)>,
+    ) -> Self {
+        let (after, unknown) = attributes_to_json(attributes);
+        self.after = Some(after);
+        self.after_unknown = unknown;
+        self
+    }
+}
+
+impl PlanChanges {
+    /// Project `plan` onto the stable format. `current_states` supplies
+    /// the `before` side of deletes, removals and deferred replacements.
+    /// Waits are not resource changes and are left out.
+    pub fn from_plan(plan: &Plan, current_states: &HashMap<ResourceId, State>) -> Self {
+        let replacements: HashMap<usize, ReplaceDisplayInfo<'_>> = plan
+            .replace_display_info()
+            .map(|info| (info.create_idx, info))
+            .collect();
+        let replaced_deletes: Vec<usize> = replacements.values().map(|i| i.delete_idx).collect();
+        let before_of = |id: &ResourceId| current_states.get(id).map(|s| &s.attributes);
+
+        let mut resource_changes = Vec::new();
+        for (idx, effect) in plan.effects().iter().enumerate() {
+            if replaced_deletes.contains(&idx) {
+                continue;
+            }
+            match effect {
+                Effect::Create(resource) => {
+                    let attributes = resource.resolved_attributes();
+                    let change = match replacements.get(&idx) {
+                        Some(info) => {
+                            let mut change =
+                                ResourceChange::new(&resource.id, ChangeAction::Replace)
+                                    .with_before(Some(info.previous_attributes))
+                                    .with_after(&attributes);
+                            change.replacement_reasons = info.changed_create_only.to_vec();
+                            change.create_before_destroy = info.create_before_destroy;
+                            change
+                        }
+                        None => ResourceChange::new(&resource.id, ChangeAction::Create)
+                            .with_after(&attributes),
+                    };
+                    resource_changes.push(change);
+                }
+                Effect::Update {
+                    from,
+                    to,
+                    changed_attributes,
+                } => {
+                    let mut change = ResourceChange::new(&to.id, ChangeAction::Update)
+                        .with_before(Some(&from.attributes))
+                        .with_after(&to.resolved_attributes());
+                    change.changed_attributes = changed_attributes.clone();
+                    resource_changes.push(change);
+                }
+                Effect::Delete { id, .. } => resource_changes
+                    .push(ResourceChange::new(id, ChangeAction::Delete).with_before(before_of(id))),
+                Effect::Read { resource } => resource_changes.push(
+                    ResourceChange::new(&resource.id, ChangeAction::Read)
+                        .with_after(&resource.attributes),
+                ),
+                Effect::Import { id, .. } => {
+                    resource_changes.push(ResourceChange::new(id, ChangeAction::Import))
+                }
+                Effect::Remove { id } => resource_changes
+                    .push(ResourceChange::new(id, ChangeAction::Remove).with_before(before_of(id))),
+                Effect::Move { from, to } => {
+                    let mut change = ResourceChange::new(to, ChangeAction::Move);
+                    change.previous_address = Some(from.human().to_string());
+                    resource_changes.push(change);
+                }
+                Effect::DeferredCreate { id, .. } => {
+                    resource_changes.push(ResourceChange::new(id, ChangeAction::DeferredCreate))
+                }
+                Effect::DeferredReplace(payload) => {
+                    for delete in payload.deletes.iter() {
+                        resource_changes.push(
+                            ResourceChange::new(&delete.id, ChangeAction::DeferredReplace)
+                                .with_before(before_of(&delete.id)),
+                        );
+                    }
+                }
+                Effect::Wait { .. } => {}
+            }
+        }
+
+        Self {
+            format_version: PLAN_CHANGES_FORMAT_VERSION,
+            resource_changes,
+        }
+    }
+}
+
+/// Attributes as JSON, plus the names of those not representable until
+/// apply time.
+fn attributes_to_json<'a>(
+    attributes: impl IntoIterator<Item = (&'a String, &'a Value)>,
+) -> (BTreeMap<String, serde_json::Value>, Vec<String>) {
+    let mut json = BTreeMap::new();
+    let mut unknown = Vec::new();
+    for (key, value) in attributes {
+        match value_to_json(value) {
+            Ok(v) => {
+                json.insert(key.clone(), v);
+            }
+            Err(_) => unknown.push(key.clone()),
+        }
+    }
+    unknown.sort();
+    (json, unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect::ChangedCreateOnly;
+    use crate::plan::{ReplacementDelete, ReplacementGroup};
+    use crate::resource::{
+        ConcreteValue, DeferredValue, Directives, ResolvedResource, ResolvedResourceId, Resource,
+    };
+    use std::collections::HashSet;
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    #[test]
+    fn creates_list_known_and_unknown_attributes() {
+        let vpc = Resource::with_provider("awscc", "ec2.Vpc", "main", None)
+            .with_attribute("cidr_block", string("10.0.0.0/16"))
+            .with_attribute(
+                "ipv4_ipam_pool_id",
+                Value::Deferred(DeferredValue::BindingRef {
+                    binding: "pool".to_string(),
+                }),
+            );
+        let mut plan = Plan::new();
+        plan.add(Effect::Create(ResolvedResource::new(vpc)));
+
+        let changes = PlanChanges::from_plan(&plan, &HashMap::new());
+
+        assert_eq!(changes.format_version, PLAN_CHANGES_FORMAT_VERSION);
+        let change = &changes.resource_changes[0];
+        assert_eq!(change.address, "awscc.ec2.Vpc main");
+        assert_eq!(change.name.as_deref(), Some("main"));
+        assert_eq!(change.action, ChangeAction::Create);
+        assert_eq!(change.before, None);
+        assert_eq!(
+            change.after.as_ref().unwrap()["cidr_block"],
+            serde_json::json!("10.0.0.0/16")
+        );
+        assert_eq!(change.after_unknown, vec!["ipv4_ipam_pool_id"]);
+    }
+
+    #[test]
+    fn deletes_take_before_from_current_state() {
+        let id = ResourceId::with_provider_identity("awscc", "s3.Bucket", "logs", None);
+        let mut plan = Plan::new();
+        plan.add(Effect::Delete {
+            id: ResolvedResourceId::new(id.clone()),
+            identifier: "logs-bucket".to_string(),
+            directives: Directives::default(),
+            binding: None,
+            dependencies: HashSet::new(),
+            explicit_dependencies: HashSet::new(),
+            blocked_by_updates: HashSet::new(),
+        });
+        let current_states = HashMap::from([(
+            id.clone(),
+            State::existing(
+                id,
+                HashMap::from([("bucket_name".to_string(), string("logs-bucket"))]),
+            ),
+        )]);
+
+        let changes = PlanChanges::from_plan(&plan, &current_states);
+
+        let change = &changes.resource_changes[0];
+        assert_eq!(change.action, ChangeAction::Delete);
+        assert_eq!(change.after, None);
+        assert_eq!(
+            change.before.as_ref().unwrap()["bucket_name"],
+            serde_json::json!("logs-bucket")
+        );
+    }
+
+    #[test]
+    fn replacements_fold_into_one_change_with_reasons() {
+        let id = ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None);
+        let mut plan = Plan::new();
+        plan.add_replacement(ReplacementGroup {
+            create: ResolvedResource::new(
+                Resource::with_provider("awscc", "ec2.Vpc", "main", None)
+                    .with_attribute("cidr_block", string("10.1.0.0/16")),
+            ),
+            delete: ReplacementDelete {
+                id: ResolvedResourceId::new(id),
+                identifier: "vpc-123".to_string(),
+                directives: Directives::default(),
+                binding: None,
+                dependencies: HashSet::new(),
+                explicit_dependencies: HashSet::new(),
+            },
+            create_before_destroy: true,
+            changed_create_only: ChangedCreateOnly::new(vec!["cidr_block".to_string()]).unwrap(),
+            cascade_ref_hints: Vec::new(),
+            temporary_name: None,
+            permanent_name_override: None,
+            consumer_updates: HashSet::new(),
+            previous_attributes: HashMap::from([("cidr_block".to_string(), string("10.0.0.0/16"))]),
+        });
+
+        let changes = PlanChanges::from_plan(&plan, &HashMap::new());
+
+        assert_eq!(changes.resource_changes.len(), 1);
+        let change = &changes.resource_changes[0];
+        assert_eq!(change.action, ChangeAction::Replace);
+        assert_eq!(change.replacement_reasons, vec!["cidr_block"]);
+        assert!(change.create_before_destroy);
+        assert_eq!(
+            change.before.as_ref().unwrap()["cidr_block"],
+            serde_json::json!("10.0.0.0/16")
+        );
+        assert_eq!(
+            change.after.as_ref().unwrap()["cidr_block"],
+            serde_json::json!("10.1.0.0/16")
+        );
+    }
+
+    #[test]
+    fn serialized_form_omits_empty_optional_fields() {
+        let mut plan = Plan::new();
+        plan.add(Effect::Create(ResolvedResource::new(
+            Resource::with_provider("awscc", "ec2.Vpc", "main", None),
+        )));
+        let json = serde_json::to_value(PlanChanges::from_plan(&plan, &HashMap::new())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "format_version": 1,
+                "resource_changes": [{
+                    "address": "awscc.ec2.Vpc main",
+                    "provider": "awscc",
+                    "resource_type": "ec2.Vpc",
+                    "name": "main",
+                    "action": "create",
+                    "before": null,
+                    "after": {},
+                }],
+            })
+        );
+    }
+}
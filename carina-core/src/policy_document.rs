@@ -0,0 +1,691 @@
+//! Policy document blocks: IAM policies written as DSL blocks instead of
+//! embedded JSON strings.
+//!
+//! Any attribute typed [`types::iam_policy_document`](crate::schema::types::iam_policy_document)
+//! accepts either a JSON string or a block:
+//!
+//! ```text
+//! policy_document {
+//!   statement {
+//!     effect    = "Allow"
+//!     actions   = ["s3:GetObject"]
+//!     resources = ["${bucket.arn}/*"]
+//!     principals {
+//!       type        = "Service"
+//!       identifiers = ["cloudfront.amazonaws.com"]
+//!     }
+//!     condition {
+//!       test     = "StringEquals"
+//!       variable = "AWS:SourceArn"
+//!       values   = [distribution.arn]
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! [`compile_policy_documents`] runs right after block-name resolution
+//! and rewrites each block into the JSON document the provider expects,
+//! so everything downstream (validation, diffing, the provider) only
+//! ever sees a string. Grammar mistakes are plan-time errors. A document
+//! whose strings reference other resources compiles to a `json_encode`
+//! call and is finished when those references resolve.
+
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+
+use crate::resource::{ConcreteValue, DeferredValue, InterpolationPart, Resource, Value};
+use crate::schema::{DslTransform, ResourceSchema, SchemaRegistry, Shape};
+
+/// Policy language version written when the block does not set one.
+pub const DEFAULT_POLICY_VERSION: &str = "2012-10-17";
+
+const DOCUMENT_KEYS: &[&str] = &["version", "id", "statement"];
+const STATEMENT_KEYS: &[&str] = &[
+    "sid",
+    "effect",
+    "actions",
+    "not_actions",
+    "resources",
+    "not_resources",
+    "principals",
+    "not_principals",
+    "condition",
+];
+const PRINCIPAL_KEYS: &[&str] = &["type", "identifiers"];
+const PRINCIPAL_TYPES: &[&str] = &["AWS", "Service", "Federated", "CanonicalUser", "*"];
+const CONDITION_KEYS: &[&str] = &["test", "variable", "values"];
+
+/// Compile every policy document block in `resources` to its JSON form.
+///
+/// Attributes already holding a string (a JSON literal, a heredoc, a
+/// reference) are left alone. Errors from every resource are collected
+/// and returned newline-separated, like [`resolve_block_names`](crate::schema::resolve_block_names).
+pub fn compile_policy_documents(
+    resources: &mut [Resource],
+    registry: &SchemaRegistry,
+) -> Result<(), String> {
+    let mut all_errors = Vec::new();
+
+    for resource in resources.iter_mut() {
+        let Some(schema) = registry.get_for(resource) else {
+            continue;
+        };
+        let resource_id = resource.id.to_string();
+        for (attr_name, compiled) in compile_attribute_documents(&resource.attributes, schema) {
+            match compiled {
+                Ok(value) => {
+                    resource.attributes.insert(attr_name, value);
+                }
+                Err(errors) => all_errors.extend(
+                    errors
+                        .into_iter()
+                        .map(|e| format!("{}: {}: {}", resource_id, attr_name, e)),
+                ),
+            }
+        }
+    }
+
+    if all_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(all_errors.join("\n"))
+    }
+}
+
+/// Compile the policy document blocks among one resource's `attributes`
+/// without modifying them: one entry per block, holding either its
+/// compiled document or its grammar errors. The editor diagnostics use
+/// this to check the same documents `carina validate` does without
+/// owning the resources.
+pub fn compile_attribute_documents(
+    attributes: &IndexMap<String, Value>,
+    schema: &ResourceSchema,
+) -> Vec<(String, Result<Value, Vec<String>>)> {
+    let mut compiled = Vec::new();
+    for (attr_name, attr_schema) in &schema.attributes {
+        if !matches!(
+            schema.shape_of(&attr_schema.attr_type),
+            Shape::String {
+                to_dsl: Some(DslTransform::CanonicalPolicyDocument),
+                ..
+            }
+        ) {
+            continue;
+        }
+        let Some(block) = attributes.get(attr_name).and_then(policy_block) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        let document = compile_document(block, &mut errors);
+        let result = if errors.is_empty() {
+            Ok(document.into_value())
+        } else {
+            Err(errors)
+        };
+        compiled.push((attr_name.clone(), result));
+    }
+    compiled
+}
+
+/// The block map when `value` was written as `name { ... }` (a
+/// one-element list) or `name = { ... }` (a map); `None` for anything
+/// else, which the regular type check then handles.
+fn policy_block(value: &Value) -> Option<&IndexMap<String, Value>> {
+    match value {
+        Value::Concrete(ConcreteValue::Map(map)) => Some(map),
+        Value::Concrete(ConcreteValue::List(items)) => match items.as_slice() {
+            [Value::Concrete(ConcreteValue::Map(map))] => Some(map),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// JSON tree whose string leaves may still be unresolved DSL values.
+enum Json {
+    Object(BTreeMap<String, Json>),
+    Array(Vec<Json>),
+    Str(Value),
+}
+
+impl Json {
+    fn str(s: &str) -> Self {
+        Json::Str(Value::Concrete(ConcreteValue::String(s.to_string())))
+    }
+
+    /// The document as a JSON string. When a string leaf still holds a
+    /// reference, the document becomes a `json_encode(...)` call instead,
+    /// so the resolved values are escaped by the same encoder and a list
+    /// landing where the policy needs a string is an error rather than
+    /// being spliced in as text.
+    fn into_value(self) -> Value {
+        if !self.has_deferred() {
+            return Value::Concrete(ConcreteValue::String(self.into_json().to_string()));
+        }
+        Value::Deferred(DeferredValue::FunctionCall {
+            name: "json_encode".to_string(),
+            args: vec![self.into_tree()],
+        })
+    }
+
+    fn has_deferred(&self) -> bool {
+        match self {
+            Json::Object(fields) => fields.values().any(Json::has_deferred),
+            Json::Array(items) => items.iter().any(Json::has_deferred),
+            Json::Str(value) => !matches!(value, Value::Concrete(ConcreteValue::String(_))),
+        }
+    }
+
+    fn into_json(self) -> serde_json::Value {
+        match self {
+            Json::Object(fields) => serde_json::Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_json()))
+                    .collect(),
+            ),
+            Json::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(Json::into_json).collect())
+            }
+            Json::Str(Value::Concrete(ConcreteValue::String(s))) => serde_json::Value::String(s),
+            Json::Str(other) => unreachable!("deferred leaf {other:?} in a literal document"),
+        }
+    }
+
+    /// The document as a DSL map/list tree for `json_encode`.
+    fn into_tree(self) -> Value {
+        match self {
+            Json::Object(fields) => Value::Concrete(ConcreteValue::Map(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_tree()))
+                    .collect(),
+            )),
+            Json::Array(items) => Value::Concrete(ConcreteValue::List(
+                items.into_iter().map(Json::into_tree).collect(),
+            )),
+            Json::Str(value) => string_expr(value),
+        }
+    }
+}
+
+/// A string leaf as a value that resolves to a string. References go
+/// through `format`, whose `%s` only accepts scalars.
+fn string_expr(value: Value) -> Value {
+    let (template, values) = match value {
+        literal @ Value::Concrete(ConcreteValue::String(_)) => return literal,
+        Value::Deferred(DeferredValue::Interpolation(parts)) => {
+            let mut template = String::new();
+            let mut values = Vec::new();
+            for part in parts {
+                match part {
+                    InterpolationPart::Literal(s) => template.push_str(&s.replace('%', "%%")),
+                    InterpolationPart::Expr(expr) => {
+                        template.push_str("%s");
+                        values.push(expr);
+                    }
+                }
+            }
+            (template, values)
+        }
+        other => ("%s".to_string(), vec![other]),
+    };
+    Value::Deferred(DeferredValue::FunctionCall {
+        name: "format".to_string(),
+        args: vec![
+            Value::Concrete(ConcreteValue::String(template)),
+            Value::Concrete(ConcreteValue::List(values)),
+        ],
+    })
+}
+
+fn compile_document(block: &IndexMap<String, Value>, errors: &mut Vec<String>) -> Json {
+    unknown_keys(block, DOCUMENT_KEYS, "policy document", errors);
+    let mut doc = BTreeMap::new();
+    let version = match block.get("version") {
+        Some(v) => string_leaf(v, "version", errors),
+        None => Json::str(DEFAULT_POLICY_VERSION),
+    };
+    doc.insert("Version".to_string(), version);
+    if let Some(id) = block.get("id") {
+        doc.insert("Id".to_string(), string_leaf(id, "id", errors));
+    }
+    let statements = match block.get("statement") {
+        Some(v) => blocks(v, "statement", errors),
+        None => Vec::new(),
+    };
+    if statements.is_empty() {
+        errors.push("policy document needs at least one statement block".to_string());
+    }
+    let statements = statements
+        .iter()
+        .enumerate()
+        .map(|(i, s)| compile_statement(s, &format!("statement {}", i + 1), errors))
+        .collect();
+    doc.insert("Statement".to_string(), Json::Array(statements));
+    Json::Object(doc)
+}
+
+fn compile_statement(block: &IndexMap<String, Value>, ctx: &str, errors: &mut Vec<String>) -> Json {
+    unknown_keys(block, STATEMENT_KEYS, ctx, errors);
+    let mut statement = BTreeMap::new();
+    if let Some(sid) = block.get("sid") {
+        statement.insert(
+            "Sid".to_string(),
+            string_leaf(sid, &format!("{ctx}: sid"), errors),
+        );
+    }
+    let effect = match block.get("effect") {
+        None => "Allow",
+        Some(Value::Concrete(ConcreteValue::String(s))) if s == "Allow" || s == "Deny" => s,
+        Some(_) => {
+            errors.push(format!("{ctx}: effect must be \"Allow\" or \"Deny\""));
+            "Allow"
+        }
+    };
+    statement.insert("Effect".to_string(), Json::str(effect));
+
+    for (positive, negative, key, not_key, required) in [
+        ("actions", "not_actions", "Action", "NotAction", true),
+        (
+            "resources",
+            "not_resources",
+            "Resource",
+            "NotResource",
+            false,
+        ),
+    ] {
+        match (block.get(positive), block.get(negative)) {
+            (Some(_), Some(_)) => errors.push(format!(
+                "{ctx}: set either {positive} or {negative}, not both"
+            )),
+            (None, None) if required => errors.push(format!(
+                "{ctx}: one of {positive} or {negative} is required"
+            )),
+            (None, None) => {}
+            (Some(v), None) => {
+                statement.insert(
+                    key.to_string(),
+                    string_list(v, &format!("{ctx}: {positive}"), errors),
+                );
+            }
+            (None, Some(v)) => {
+                statement.insert(
+                    not_key.to_string(),
+                    string_list(v, &format!("{ctx}: {negative}"), errors),
+                );
+            }
+        }
+    }
+
+    match (block.get("principals"), block.get("not_principals")) {
+        (Some(_), Some(_)) => errors.push(format!(
+            "{ctx}: set either principals or not_principals, not both"
+        )),
+        (Some(v), None) => {
+            statement.insert(
+                "Principal".to_string(),
+                compile_principals(v, &format!("{ctx}: principals"), errors),
+            );
+        }
+        (None, Some(v)) => {
+            statement.insert(
+                "NotPrincipal".to_string(),
+                compile_principals(v, &format!("{ctx}: not_principals"), errors),
+            );
+        }
+        (None, None) => {}
+    }
+
+    if let Some(v) = block.get("condition") {
+        statement.insert(
+            "Condition".to_string(),
+            compile_conditions(v, &format!("{ctx}: condition"), errors),
+        );
+    }
+    Json::Object(statement)
+}
+
+/// `principals` blocks, merged by type. A `type = "*"` block is the
+/// anonymous principal and compiles to `"*"`.
+fn compile_principals(value: &Value, ctx: &str, errors: &mut Vec<String>) -> Json {
+    let mut by_type: BTreeMap<String, Vec<Json>> = BTreeMap::new();
+    for block in blocks(value, ctx, errors) {
+        unknown_keys(block, PRINCIPAL_KEYS, ctx, errors);
+        let kind = match block.get("type") {
+            Some(Value::Concrete(ConcreteValue::String(s)))
+                if PRINCIPAL_TYPES.contains(&s.as_str()) =>
+            {
+                s.clone()
+            }
+            Some(_) => {
+                errors.push(format!(
+                    "{ctx}: type must be one of {}",
+                    PRINCIPAL_TYPES.join(", ")
+                ));
+                continue;
+            }
+            None => {
+                errors.push(format!("{ctx}: type is required"));
+                continue;
+            }
+        };
+        let Some(identifiers) = block.get("identifiers") else {
+            errors.push(format!("{ctx}: identifiers is required"));
+            continue;
+        };
+        let identifiers = match string_list(identifiers, &format!("{ctx}: identifiers"), errors) {
+            Json::Array(items) => items,
+            other => vec![other],
+        };
+        by_type.entry(kind).or_default().extend(identifiers);
+    }
+    if by_type.contains_key("*") {
+        if by_type.len() > 1 {
+            errors.push(format!(
+                "{ctx}: type \"*\" cannot be combined with other types"
+            ));
+        }
+        return Json::str("*");
+    }
+    Json::Object(
+        by_type
+            .into_iter()
+            .map(|(kind, ids)| (kind, Json::Array(ids)))
+            .collect(),
+    )
+}
+
+/// `condition` blocks, grouped as `{test: {variable: values}}`.
+fn compile_conditions(value: &Value, ctx: &str, errors: &mut Vec<String>) -> Json {
+    let mut by_test: BTreeMap<String, BTreeMap<String, Json>> = BTreeMap::new();
+    for block in blocks(value, ctx, errors) {
+        unknown_keys(block, CONDITION_KEYS, ctx, errors);
+        let (Some(test), Some(variable), Some(values)) = (
+            literal(block.get("test"), &format!("{ctx}: test"), errors),
+            literal(block.get("variable"), &format!("{ctx}: variable"), errors),
+            block.get("values"),
+        ) else {
+            if !block.contains_key("values") {
+                errors.push(format!("{ctx}: values is required"));
+            }
+            continue;
+        };
+        let values = string_list(values, &format!("{ctx}: values"), errors);
+        if by_test
+            .entry(test.clone())
+            .or_default()
+            .insert(variable.clone(), values)
+            .is_some()
+        {
+            errors.push(format!("{ctx}: {test} on {variable} is set twice"));
+        }
+    }
+    Json::Object(
+        by_test
+            .into_iter()
+            .map(|(test, vars)| (test, Json::Object(vars)))
+            .collect(),
+    )
+}
+
+/// The maps of a repeated block (`statement { } statement { }`) or of a
+/// list of maps written with `=`.
+fn blocks<'a>(
+    value: &'a Value,
+    ctx: &str,
+    errors: &mut Vec<String>,
+) -> Vec<&'a IndexMap<String, Value>> {
+    let items: Vec<&Value> = match value {
+        Value::Concrete(ConcreteValue::List(items)) => items.iter().collect(),
+        single => vec![single],
+    };
+    let mut maps = Vec::new();
+    for item in items {
+        match item {
+            Value::Concrete(ConcreteValue::Map(map)) => maps.push(map),
+            _ => errors.push(format!("{ctx} must be written as a block")),
+        }
+    }
+    maps
+}
+
+/// A key that is not part of the grammar is an error rather than being
+/// dropped, so a typo (`action` for `actions`) fails the plan instead of
+/// producing a policy that silently grants less.
+fn unknown_keys(
+    block: &IndexMap<String, Value>,
+    allowed: &[&str],
+    ctx: &str,
+    errors: &mut Vec<String>,
+) {
+    for key in block.keys() {
+        if !allowed.contains(&key.as_str()) {
+            errors.push(format!(
+                "{ctx}: unknown key '{key}' (expected one of {})",
+                allowed.join(", ")
+            ));
+        }
+    }
+}
+
+/// A plain string needed at compile time (condition operators and keys
+/// become JSON object keys, so they cannot be references).
+fn literal(value: Option<&Value>, ctx: &str, errors: &mut Vec<String>) -> Option<String> {
+    match value {
+        Some(Value::Concrete(ConcreteValue::String(s))) => Some(s.clone()),
+        Some(_) => {
+            errors.push(format!("{ctx} must be a string literal"));
+            None
+        }
+        None => {
+            errors.push(format!("{ctx} is required"));
+            None
+        }
+    }
+}
+
+fn string_leaf(value: &Value, ctx: &str, errors: &mut Vec<String>) -> Json {
+    match value {
+        Value::Concrete(ConcreteValue::String(_)) | Value::Deferred(_) => Json::Str(value.clone()),
+        Value::Concrete(ConcreteValue::Int(n)) => Json::str(&n.to_string()),
+        Value::Concrete(ConcreteValue::Bool(b)) => Json::str(&b.to_string()),
+        _ => {
+            errors.push(format!("{ctx} must be a string"));
+            Json::str("")
+        }
+    }
+}
+
+fn string_list(value: &Value, ctx: &str, errors: &mut Vec<String>) -> Json {
+    match value {
+        Value::Concrete(ConcreteValue::List(items)) => {
+            Json::Array(items.iter().map(|v| string_leaf(v, ctx, errors)).collect())
+        }
+        Value::Concrete(ConcreteValue::StringList(items)) => {
+            Json::Array(items.iter().map(|s| Json::str(s)).collect())
+        }
+        single => Json::Array(vec![string_leaf(single, ctx, errors)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::AccessPath;
+    use crate::schema::{AttributeSchema, ResourceSchema, types};
+
+    fn s(v: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(v.to_string()))
+    }
+
+    fn list(items: Vec<Value>) -> Value {
+        Value::Concrete(ConcreteValue::List(items))
+    }
+
+    fn block(fields: Vec<(&str, Value)>) -> Value {
+        Value::Concrete(ConcreteValue::Map(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        ))
+    }
+
+    fn compile(statements: Vec<Value>) -> Result<Value, String> {
+        let mut resource = Resource::new("iam.role_policy", "p");
+        resource.set_attr(
+            "policy_document",
+            list(vec![block(vec![("statement", list(statements))])]),
+        );
+        let mut resources = vec![resource];
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert(
+            "",
+            ResourceSchema::new("iam.role_policy").attribute(AttributeSchema::new(
+                "policy_document",
+                types::iam_policy_document(),
+            )),
+        );
+        compile_policy_documents(&mut resources, &schemas)?;
+        Ok(resources[0].get_attr("policy_document").unwrap().clone())
+    }
+
+    #[test]
+    fn statement_blocks_compile_to_json() {
+        let value = compile(vec![block(vec![
+            ("actions", list(vec![s("s3:GetObject")])),
+            ("resources", list(vec![s("arn:aws:s3:::b/*")])),
+            (
+                "principals",
+                list(vec![block(vec![
+                    ("type", s("Service")),
+                    ("identifiers", list(vec![s("cloudfront.amazonaws.com")])),
+                ])]),
+            ),
+            (
+                "condition",
+                list(vec![block(vec![
+                    ("test", s("Bool")),
+                    ("variable", s("aws:SecureTransport")),
+                    ("values", list(vec![s("true")])),
+                ])]),
+            ),
+        ])])
+        .unwrap();
+        let Value::Concrete(ConcreteValue::String(json)) = value else {
+            panic!("expected a string, got {value:?}");
+        };
+        assert_eq!(
+            json,
+            r#"{"Statement":[{"Action":["s3:GetObject"],"Condition":{"Bool":{"aws:SecureTransport":["true"]}},"Effect":"Allow","Principal":{"Service":["cloudfront.amazonaws.com"]},"Resource":["arn:aws:s3:::b/*"]}],"Version":"2012-10-17"}"#
+        );
+    }
+
+    /// Resolve `value` with `bucket.arn` bound to `arn`.
+    fn resolve_with_arn(value: &Value, arn: Value) -> Result<Value, String> {
+        let mut bucket = Resource::new("s3.Bucket", "b");
+        bucket.binding = Some("bucket".to_string());
+        bucket.set_attr("arn", arn);
+        let bindings = crate::binding_index::ResolvedBindings::pre_apply(
+            crate::binding_index::PreApplyInputs {
+                managed: &[bucket],
+                compositions: &[],
+                data_sources: &[],
+                current_states: &std::collections::HashMap::new(),
+                remote_bindings: &std::collections::HashMap::new(),
+                wait_aliases: &[],
+            },
+        );
+        crate::resolver::resolve_ref_value(value, &bindings)
+    }
+
+    fn statement_with_resource(resource: Value) -> Result<Value, String> {
+        compile(vec![block(vec![
+            ("actions", list(vec![s("s3:GetObject")])),
+            ("resources", list(vec![resource])),
+        ])])
+    }
+
+    fn arn_ref() -> Value {
+        Value::Deferred(DeferredValue::ResourceRef {
+            path: AccessPath::new("bucket", "arn"),
+        })
+    }
+
+    #[test]
+    fn references_are_filled_in_when_they_resolve() {
+        let value = statement_with_resource(Value::Deferred(DeferredValue::Interpolation(vec![
+            InterpolationPart::Expr(arn_ref()),
+            InterpolationPart::Literal("/*".to_string()),
+        ])))
+        .unwrap();
+        assert!(
+            matches!(&value, Value::Deferred(DeferredValue::FunctionCall { name, .. }) if name == "json_encode"),
+            "{value:?}"
+        );
+        assert_eq!(
+            resolve_with_arn(&value, s("arn:aws:s3:::b")).unwrap(),
+            s(
+                r#"{"Statement":[{"Action":["s3:GetObject"],"Effect":"Allow","Resource":["arn:aws:s3:::b/*"]}],"Version":"2012-10-17"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn resolved_values_are_escaped() {
+        let value = statement_with_resource(arn_ref()).unwrap();
+        assert_eq!(
+            resolve_with_arn(&value, s(r#"a"b\c"#)).unwrap(),
+            s(
+                r#"{"Statement":[{"Action":["s3:GetObject"],"Effect":"Allow","Resource":["a\"b\\c"]}],"Version":"2012-10-17"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn a_list_where_a_string_belongs_is_an_error() {
+        let value = statement_with_resource(arn_ref()).unwrap();
+        let err = resolve_with_arn(&value, list(vec![s("a"), s("b")])).unwrap_err();
+        assert!(err.contains("must be a String"), "{err}");
+    }
+
+    #[test]
+    fn grammar_mistakes_are_errors() {
+        let err = compile(vec![block(vec![
+            ("effect", s("Permit")),
+            ("action", list(vec![s("s3:GetObject")])),
+        ])])
+        .unwrap_err();
+        assert!(
+            err.contains("effect must be \"Allow\" or \"Deny\""),
+            "{err}"
+        );
+        assert!(err.contains("unknown key 'action'"), "{err}");
+        assert!(
+            err.contains("one of actions or not_actions is required"),
+            "{err}"
+        );
+
+        let err = compile(vec![]).unwrap_err();
+        assert!(err.contains("at least one statement"), "{err}");
+    }
+
+    #[test]
+    fn json_strings_are_left_alone() {
+        let mut resource = Resource::new("iam.role_policy", "p");
+        resource.set_attr("policy_document", s("{}"));
+        let mut resources = vec![resource];
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert(
+            "",
+            ResourceSchema::new("iam.role_policy").attribute(AttributeSchema::new(
+                "policy_document",
+                types::iam_policy_document(),
+            )),
+        );
+        compile_policy_documents(&mut resources, &schemas).unwrap();
+        assert_eq!(resources[0].get_attr("policy_document"), Some(&s("{}")));
+    }
+}
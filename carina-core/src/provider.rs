@@ -3,19 +3,22 @@
 //! A Provider defines operations for a specific infrastructure (AWS, GCP, etc.).
 //! It is responsible for converting Effects into actual API calls.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::num::NonZeroUsize;
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::future::Future;
 use std::pin::Pin;
+use std::time::{Duration, SystemTime};
 
+use crate::cost::{AwsListPrices, MonthlyCost};
 use crate::effect::PlanOp;
 use crate::resource::{
     ConcreteValue, DataSource, Directives, PartialReadMarker, ResolvedResource, Resource,
     ResourceId, State, Value,
 };
-use crate::schema::{SchemaRegistry, TypeIdentity};
+use crate::schema::{ResourceSchema, SchemaKind, SchemaRegistry, Shape, TypeIdentity};
 use crate::wait::BindingPattern;
 use crate::wait::predicate::AttrPath;
 
@@ -60,6 +63,10 @@ pub struct ErrorDetail {
     /// `x-ms-request-id`, …). Operators paste this into support
     /// tickets so the provider can look up server-side logs.
     pub request_id: Option<String>,
+    /// Identifiers of every remote resource an
+    /// [`ProviderError::AmbiguousMatch`] lookup matched, sorted. Empty
+    /// for other errors.
+    pub candidates: Vec<String>,
 }
 
 /// Structured error returned by every provider operation.
@@ -91,6 +98,19 @@ pub enum ProviderError {
     NotFound(Box<ErrorDetail>),
     /// Operation timed out before completing. Retriable with backoff.
     Timeout(Box<ErrorDetail>),
+    /// A resource with the same unique name already exists, so creating
+    /// it again would fail or duplicate it. Not retriable; the existing
+    /// resource has to be imported or the name changed.
+    AlreadyExists(Box<ErrorDetail>),
+    /// A lookup by name (e.g. a VPC by its `Name` tag) matched several
+    /// remote resources. [`ErrorDetail::candidates`] lists them all;
+    /// the configuration has to name one by its identifier. Not
+    /// retriable.
+    AmbiguousMatch(Box<ErrorDetail>),
+    /// The cloud API rejected the credentials themselves (expired
+    /// session token, unknown access key). Every later call in the same
+    /// run fails the same way; see [`ProviderError::is_fatal`].
+    Credentials(Box<ErrorDetail>),
     /// Provider-internal failure (panic, unexpected state, missing
     /// schema entry, etc.). Should be escalated as a bug rather than
     /// retried.
@@ -215,6 +235,9 @@ impl ProviderError {
             | ProviderError::ApiError(d)
             | ProviderError::NotFound(d)
             | ProviderError::Timeout(d)
+            | ProviderError::AlreadyExists(d)
+            | ProviderError::AmbiguousMatch(d)
+            | ProviderError::Credentials(d)
             | ProviderError::Internal(d) => d,
         }
     }
@@ -226,6 +249,9 @@ impl ProviderError {
             | ProviderError::ApiError(d)
             | ProviderError::NotFound(d)
             | ProviderError::Timeout(d)
+            | ProviderError::AlreadyExists(d)
+            | ProviderError::AmbiguousMatch(d)
+            | ProviderError::Credentials(d)
             | ProviderError::Internal(d) => d,
         }
     }
@@ -237,10 +263,21 @@ impl ProviderError {
             ProviderError::ApiError(_) => "api_error",
             ProviderError::NotFound(_) => "not_found",
             ProviderError::Timeout(_) => "timeout",
+            ProviderError::AlreadyExists(_) => "already_exists",
+            ProviderError::AmbiguousMatch(_) => "ambiguous_match",
+            ProviderError::Credentials(_) => "credentials",
             ProviderError::Internal(_) => "internal",
         }
     }
 
+    /// Whether every later call in the same run will fail the same way,
+    /// which providers signal with [`ProviderError::Credentials`]. The
+    /// executor stops dispatching new operations and drains the
+    /// in-flight ones on a fatal error.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, ProviderError::Credentials(_))
+    }
+
     /// Convenience accessor for the human-readable message.
     pub fn message(&self) -> &str {
         &self.detail().message
@@ -266,6 +303,26 @@ impl ProviderError {
         ProviderError::Timeout(Box::new(ErrorDetail::new(message)))
     }
 
+    /// A resource with the same unique name already exists.
+    pub fn already_exists(message: impl Into<String>) -> Self {
+        ProviderError::AlreadyExists(Box::new(ErrorDetail::new(message)))
+    }
+
+    /// The cloud API rejected the credentials themselves.
+    pub fn credentials(message: impl Into<String>) -> Self {
+        ProviderError::Credentials(Box::new(ErrorDetail::new(message)))
+    }
+
+    /// A lookup by name matched every one of `candidates`.
+    pub fn ambiguous_match(message: impl Into<String>, mut candidates: Vec<String>) -> Self {
+        candidates.sort();
+        candidates.dedup();
+        ProviderError::AmbiguousMatch(Box::new(ErrorDetail {
+            candidates,
+            ..ErrorDetail::new(message)
+        }))
+    }
+
     /// Provider-internal failure / unexpected state.
     pub fn internal(message: impl Into<String>) -> Self {
         ProviderError::Internal(Box::new(ErrorDetail::new(message)))
@@ -354,6 +411,22 @@ impl ErrorDetail {
 
 pub type ProviderResult<T> = Result<T, ProviderError>;
 
+/// Provenance tag marking a resource as owned by a Carina project. The
+/// value is the project's state lineage; `carina orphans` lists
+/// resources by it to find ones missing from state.
+pub const PROJECT_TAG: &str = "carina:project";
+
+/// Provenance tag holding the resource's Carina address.
+pub const RESOURCE_ADDRESS_TAG: &str = "carina:resource_address";
+
+/// Provenance tag holding where the project's state is stored.
+pub const STATE_TAG: &str = "carina:state";
+
+/// Tags Carina owns. [`ProviderRouter`] writes them into the `tags` of
+/// instances with `provenance_tags = true` and strips them from those
+/// instances' read-back state, so they never show up as drift.
+pub const PROVENANCE_TAGS: [&str; 3] = [PROJECT_TAG, RESOURCE_ADDRESS_TAG, STATE_TAG];
+
 /// Per-operation request record for [`Provider::create`].
 ///
 /// Mirrors `create-request` in `wit/types.wit`.
@@ -361,6 +434,25 @@ pub type ProviderResult<T> = Result<T, ProviderError>;
 pub struct CreateRequest {
     /// Full desired state for the new resource.
     pub resource: ResolvedResource,
+    /// User-configured `timeouts.create`; `None` keeps the provider's
+    /// own wait window.
+    pub timeout: Option<Duration>,
+    /// Idempotency token for the create call, derived by
+    /// [`crate::executor::client_token`] from the resource address and
+    /// its configuration. A retried apply sends the same token, so
+    /// providers whose API accepts one (Cloud Control's `ClientToken`)
+    /// return the resource the first attempt created instead of making
+    /// a second one. `None` sends no token. The WASM plugin interface's
+    /// `create-request` has no token field, so WASM providers never see
+    /// it; only native and process providers do.
+    pub client_token: Option<String>,
+}
+
+/// Request record for [`Provider::list_project_resources`].
+#[derive(Debug, Clone)]
+pub struct ListRequest {
+    /// [`PROJECT_TAG`] value to match.
+    pub project: String,
 }
 
 /// Per-operation request record for [`Provider::read`].
@@ -389,6 +481,9 @@ pub struct UpdateRequest {
     pub from: State,
     /// Structured description of the user's intended change.
     pub patch: UpdatePatch,
+    /// User-configured `timeouts.update`; `None` keeps the provider's
+    /// own wait window.
+    pub timeout: Option<Duration>,
 }
 
 /// Per-operation request record for [`Provider::delete`].
@@ -398,6 +493,82 @@ pub struct UpdateRequest {
 pub struct DeleteRequest {
     /// Carina-side directives for the resource.
     pub directives: Directives,
+    /// User-configured `timeouts.delete`; `None` keeps the provider's
+    /// own wait window.
+    pub timeout: Option<Duration>,
+    /// Remove the [`DeleteBlocker::removable`] blockers reported by
+    /// [`Provider::delete_blockers`] before deleting
+    /// (`carina destroy --force-dependencies`).
+    pub force_dependencies: bool,
+}
+
+/// A remote object, typically one Carina does not manage, that keeps a
+/// resource from being deleted (an ENI still attached to a subnet, a
+/// VPC endpoint left in a VPC). Reported by [`Provider::delete_blockers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteBlocker {
+    /// Provider-side kind, e.g. `network_interface`.
+    pub kind: String,
+    /// Cloud identifier of the blocking object, e.g. `eni-0abc`.
+    pub identifier: String,
+    /// What the object is and why it blocks the delete.
+    pub description: String,
+    /// The provider knows the object is safe to remove on the user's
+    /// behalf (e.g. the rules of a default security group) and does so
+    /// when [`DeleteRequest::force_dependencies`] is set.
+    pub removable: bool,
+}
+
+/// Timing a provider collected while performing one create, update or
+/// delete, reported by [`Provider::operation_metrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationMetrics {
+    /// Cloud API requests made.
+    pub api_calls: u32,
+    /// Time spent waiting on those requests.
+    pub api_latency: Duration,
+    /// Status polls while waiting for an asynchronous operation to settle
+    /// (e.g. Cloud Control `GetResourceRequestStatus`).
+    pub polls: u32,
+    /// Requests retried after throttling or a transient error.
+    pub retries: u32,
+}
+
+/// What a provider can and cannot do, reported by
+/// [`Provider::capabilities`] so the planner and executor do not assume
+/// every provider behaves like AWS Cloud Control.
+///
+/// The default describes a provider with no restrictions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// Resource types with no in-place update; any change to one is
+    /// planned as a replacement.
+    pub replace_only_types: BTreeSet<String>,
+    /// `import` blocks can adopt existing resources.
+    pub supports_import: bool,
+    /// Created resources accept Carina's provenance tags.
+    pub supports_tagging: bool,
+    /// Operations against this provider kept in flight at once during
+    /// apply, within the global `--parallelism`. `None` means no cap of
+    /// its own.
+    pub max_parallel_ops: Option<NonZeroUsize>,
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            replace_only_types: BTreeSet::new(),
+            supports_import: true,
+            supports_tagging: true,
+            max_parallel_ops: None,
+        }
+    }
+}
+
+impl ProviderCapabilities {
+    pub fn supports_update_in_place(&self, resource_type: &str) -> bool {
+        !self.replace_only_types.contains(resource_type)
+    }
 }
 
 /// A structured description of the user's intended change to a resource.
@@ -449,11 +620,15 @@ pub enum PatchOpKind {
 /// - missing in `to` but present in `from` → [`PatchOpKind::Remove`]
 ///
 /// `Remove` ops carry `value: None`; others carry a clone of the
-/// value from `to`.
+/// value from `to`. An unordered list whose schema declares a
+/// [`list_key`](crate::schema::AttributeSchema::list_key) is sent in the
+/// element order of `from`, with new elements last, so a provider that
+/// applies lists positionally only touches the elements that changed.
 pub fn build_update_patch(
     changed_attributes: &[String],
     to: &ResolvedResource,
     from: &State,
+    schema: Option<&ResourceSchema>,
 ) -> UpdatePatch {
     let to = to.as_resource();
     let ops = changed_attributes
@@ -473,7 +648,9 @@ pub fn build_update_patch(
             let value = if matches!(kind, PatchOpKind::Remove) {
                 None
             } else {
-                to.attributes.get(key).cloned()
+                to.attributes
+                    .get(key)
+                    .map(|desired| align_keyed_list(key, desired, from, schema))
             };
             PatchOp {
                 kind,
@@ -485,6 +662,48 @@ pub fn build_update_patch(
     UpdatePatch { ops }
 }
 
+/// Reorder an unordered keyed list to follow the element order in
+/// `from`. Values that are not such a list are returned unchanged.
+fn align_keyed_list(
+    key: &str,
+    desired: &Value,
+    from: &State,
+    schema: Option<&ResourceSchema>,
+) -> Value {
+    let Some((schema, attr)) = schema.and_then(|s| s.attributes.get(key).map(|attr| (s, attr)))
+    else {
+        return desired.clone();
+    };
+    let (
+        Some(_),
+        Shape::List { ordered: false, .. },
+        Value::Concrete(ConcreteValue::List(desired_items)),
+        Some(Value::Concrete(ConcreteValue::List(current_items))),
+    ) = (
+        attr.list_key.as_ref(),
+        attr.attr_type.shape_with_defs(&schema.defs),
+        desired,
+        from.attributes.get(key),
+    )
+    else {
+        return desired.clone();
+    };
+
+    let mut remaining: Vec<Option<&Value>> = desired_items.iter().map(Some).collect();
+    let mut aligned = Vec::with_capacity(desired_items.len());
+    for current_key in current_items.iter().filter_map(|c| attr.element_key(c)) {
+        let slot = remaining.iter_mut().find(|slot| {
+            slot.and_then(|d| attr.element_key(d))
+                .is_some_and(|k| k.semantically_equal(current_key))
+        });
+        if let Some(item) = slot.and_then(Option::take) {
+            aligned.push(item.clone());
+        }
+    }
+    aligned.extend(remaining.into_iter().flatten().cloned());
+    Value::Concrete(ConcreteValue::List(aligned))
+}
+
 /// Return type for async operations
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -637,6 +856,56 @@ impl PartialReadDiagnostic {
     }
 }
 
+/// Severity of a [`ResourceDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticSeverity {
+    /// Fails the plan: the resource would be rejected by the cloud API.
+    Error,
+    /// Surfaced to the user but does not fail the plan.
+    Warning,
+}
+
+/// A plan-time finding returned by [`Provider::validate_resource`].
+///
+/// Covers cross-attribute rules the schema cannot express (e.g. a VPC
+/// needs either `cidr_block` or `ipv4_ipam_pool_id` + `ipv4_netmask_length`,
+/// but not both), so the mistake surfaces at plan instead of as an API
+/// error mid-apply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Attribute the finding pertains to, if a single one can be named.
+    pub attribute: Option<String>,
+}
+
+impl ResourceDiagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: DiagnosticSeverity::Error,
+            message: message.into(),
+            attribute: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: DiagnosticSeverity::Warning,
+            message: message.into(),
+            attribute: None,
+        }
+    }
+
+    pub fn with_attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.attribute = Some(attribute.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.severity, DiagnosticSeverity::Error)
+    }
+}
+
 /// Runtime CRUD operations for a provider.
 ///
 /// Each infrastructure provider (AWS, GCP, etc.) implements this trait
@@ -663,6 +932,12 @@ pub trait Provider: Send + Sync {
     /// implementation, replacing the earlier `&str` shape that used
     /// `""` as a sentinel and produced carina-rs/carina#2594.
     ///
+    /// A `Some` identifier is the primary identifier recorded in state
+    /// and must be read by directly: re-resolving the resource by name
+    /// (e.g. a `Name` tag) is slow, and wrong once names are reused.
+    /// Name lookups are for `import`, where no identifier is known yet.
+    /// A returned state without an identifier keeps the one passed in.
+    ///
     /// `request` carries no operationally meaningful fields today; it
     /// exists so future fields (e.g. a freshness hint or an attribute
     /// projection) can be added without breaking the signature.
@@ -757,6 +1032,139 @@ pub trait Provider: Send + Sync {
     ) -> Vec<BindingPattern> {
         Vec::new()
     }
+
+    /// Plan-time validation of a desired resource.
+    ///
+    /// Called once per managed resource after desired-state
+    /// normalization, so enum values are in DSL form and references
+    /// resolvable at plan time are concrete. Attributes not known until
+    /// apply (e.g. an ID produced by an upstream Create) are still
+    /// `Value::Deferred` and must not be treated as missing.
+    ///
+    /// Any [`DiagnosticSeverity::Error`] fails the plan.
+    /// Empty vec means the provider has no additional checks for this resource.
+    /// Only in-process providers (linked into the binary) reach this hook:
+    /// neither the WASM plugin interface nor the process protocol carries
+    /// it, so loaded plugins always answer with the empty default. Plugins
+    /// check resources at plan time through `simulate` instead.
+    fn validate_resource(&self, _resource: &Resource) -> Vec<ResourceDiagnostic> {
+        Vec::new()
+    }
+
+    /// Best-effort dry run of applying `resource`, for providers whose
+    /// API has none (e.g. Cloud Control). The provider checks the desired
+    /// document against its own resource schema (required properties,
+    /// enums, patterns) and confirms that identifiers it references
+    /// exist, so failures that would otherwise only appear mid-apply are
+    /// reported at plan time.
+    ///
+    /// Only called when simulation is requested, since it may cost an
+    /// API call per resource. `resource` follows the same contract as in
+    /// [`Provider::validate_resource`]. Empty (the default) means no
+    /// findings; an `Err` means the simulation itself could not run.
+    /// Only in-process and process providers simulate: the WASM plugin
+    /// interface does not carry this hook.
+    fn simulate(
+        &self,
+        _resource: &Resource,
+    ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// List every remote resource carrying the [`PROJECT_TAG`] tag with
+    /// value `request.project`, whether or not it is in state.
+    ///
+    /// Each returned [`State`] carries the resource type and the
+    /// cloud-side `identifier`; the identity may be empty when the
+    /// provider cannot recover the DSL name. `Ok(None)` means this
+    /// provider cannot enumerate its resources, so `carina orphans`
+    /// reports it as unchecked instead of "no orphans". Not carried by
+    /// the WASM plugin interface.
+    fn list_project_resources(
+        &self,
+        _request: ListRequest,
+    ) -> BoxFuture<'_, ProviderResult<Option<Vec<State>>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Estimated monthly cost of resource `id` with `attributes`, for the
+    /// cost summary after `carina plan`. Providers price from their own
+    /// configuration, e.g. the list prices of their region. `None` (the
+    /// default) when the provider does not price the type; the router then
+    /// falls back to its [`AwsListPrices`]. Not carried by the WASM plugin
+    /// interface.
+    fn monthly_cost<'a>(
+        &'a self,
+        _id: &'a ResourceId,
+        _attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>> {
+        Box::pin(async { None })
+    }
+
+    /// Enumerate what would make deleting resource `id` (cloud-side
+    /// `identifier`) fail, e.g. with a `DependencyViolation`.
+    ///
+    /// `carina destroy` calls this before deleting anything, so blockers
+    /// are reported up front instead of after the delete retries run
+    /// out. Empty (the default) means none are known. Not carried by the
+    /// WASM plugin interface.
+    fn delete_blockers(
+        &self,
+        _id: &ResourceId,
+        _identifier: &str,
+    ) -> BoxFuture<'_, ProviderResult<Vec<DeleteBlocker>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// Look up a remote resource that creating `resource` would
+    /// duplicate, by the unique name its configuration gives it, and
+    /// return its cloud identifier.
+    ///
+    /// The executor calls this before each create and fails the effect
+    /// with [`ProviderError::AlreadyExists`] on a match, so an apply
+    /// retried after a timeout does not create a second copy. `None`
+    /// (the default) means no match, or that the resource type is not
+    /// identified by name. Not carried by the WASM plugin interface, so
+    /// creates through a WASM provider are not checked.
+    fn find_existing(
+        &self,
+        _id: &ResourceId,
+        _resource: &ResolvedResource,
+    ) -> BoxFuture<'_, ProviderResult<Option<String>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Metrics of the most recent create, update or delete of `id`, read
+    /// by the executor once the operation returns and shown in the apply
+    /// summary. `None` (the default) means the provider does not collect
+    /// them. Only in-process and process providers report metrics: the
+    /// WASM plugin interface does not carry this hook.
+    fn operation_metrics(&self, _id: &ResourceId) -> Option<OperationMetrics> {
+        None
+    }
+
+    /// When the credentials the provider currently holds expire.
+    ///
+    /// Checked before apply so a run that will outlast its credentials
+    /// is flagged up front instead of failing halfway with an expired
+    /// token. `None` (the default) means the credentials do not expire
+    /// or the provider refreshes them itself. Not carried by the WASM
+    /// plugin interface, so the check never fires for WASM providers.
+    fn credential_expiry(&self) -> BoxFuture<'_, Option<SystemTime>> {
+        Box::pin(async { None })
+    }
+
+    /// Capabilities of the provider serving `id`.
+    ///
+    /// Takes the resource so a router can answer for the provider
+    /// instance behind it; a single provider answers the same for every
+    /// resource. Consulted at plan time (update versus replace, import
+    /// support) and apply time (provenance tags, concurrency). Not carried
+    /// by the WASM plugin interface; WASM providers claim import support
+    /// but not tagging.
+    fn capabilities(&self, _id: &ResourceId) -> ProviderCapabilities {
+        ProviderCapabilities::default()
+    }
 }
 
 /// Convenience for a `ProviderNormalizer` method that does nothing.
@@ -956,7 +1364,84 @@ pub fn merge_default_tags_for_provider(
 ///   `directives { provider = <name> }`.
 pub struct ProviderRouter {
     providers: HashMap<(String, Option<String>), Box<dyn Provider>>,
+    /// Instances whose `create_provider` failed, with the error text.
+    /// Resources routed to one are blocked rather than failing the run.
+    unavailable: HashMap<(String, Option<String>), String>,
     normalizers: Vec<Box<dyn ProviderNormalizer>>,
+    provenance: Option<ProvenanceTagging>,
+    /// Fallback prices for resources whose provider does not price them.
+    list_prices: Option<AwsListPrices>,
+}
+
+/// Which resources carry [`PROVENANCE_TAGS`]: those of an instance with
+/// `provenance_tags = true` whose schema declares a `tags` attribute.
+#[derive(Debug, Clone, Default)]
+pub struct ProvenanceTagging {
+    /// `(kind, binding)` of every instance with `provenance_tags = true`.
+    pub instances: HashSet<(String, Option<String>)>,
+    /// `(provider, resource_type)` of every type whose schema has `tags`.
+    pub taggable: HashSet<(String, String)>,
+    /// [`PROJECT_TAG`] and [`STATE_TAG`] values. `None` until an apply
+    /// sets them; until then the tags are only stripped, not written.
+    pub values: Option<(String, String)>,
+}
+
+impl ProvenanceTagging {
+    /// Tagging for every instance in `providers` that sets
+    /// `provenance_tags = true`; `None` when none does.
+    pub fn from_configs(
+        providers: &[crate::parser::ProviderConfig],
+        schemas: &SchemaRegistry,
+    ) -> Option<Self> {
+        let instances: HashSet<(String, Option<String>)> = providers
+            .iter()
+            .filter(|p| p.provenance_tags)
+            .map(|p| (p.name.clone(), p.binding.clone()))
+            .collect();
+        if instances.is_empty() {
+            return None;
+        }
+        let taggable = schemas
+            .iter()
+            .filter(|(_, _, kind, schema)| {
+                *kind == SchemaKind::Resource && schema.attributes.contains_key("tags")
+            })
+            .map(|(provider, resource_type, _, _)| {
+                (provider.to_string(), resource_type.to_string())
+            })
+            .collect();
+        Some(Self {
+            instances,
+            taggable,
+            values: None,
+        })
+    }
+
+    /// Whether `id`'s instance opted in and its type has `tags`.
+    fn covers(&self, id: &ResourceId) -> bool {
+        self.instances
+            .contains(&(id.provider.clone(), id.provider_instance.clone()))
+            && self
+                .taggable
+                .contains(&(id.provider.clone(), id.resource_type.clone()))
+    }
+}
+
+/// One provider instance's answer to [`Provider::list_project_resources`],
+/// as gathered by [`ProviderRouter::list_project_resources_by_instance`].
+#[derive(Debug)]
+pub struct InstanceListing {
+    /// Provider kind, plus `.binding` for a named instance.
+    pub instance: String,
+    pub result: ProviderResult<Option<Vec<State>>>,
+}
+
+/// A resource left out of a run because the provider instance it needs
+/// could not be initialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedResource {
+    pub id: ResourceId,
+    pub reason: String,
 }
 
 impl Default for ProviderRouter {
@@ -969,8 +1454,124 @@ impl ProviderRouter {
     pub fn new() -> Self {
         Self {
             providers: HashMap::new(),
+            unavailable: HashMap::new(),
             normalizers: Vec::new(),
+            provenance: None,
+            list_prices: None,
+        }
+    }
+
+    /// Enable provenance tagging for the instances and types in `provenance`.
+    pub fn set_provenance(&mut self, provenance: ProvenanceTagging) {
+        self.provenance = Some(provenance);
+    }
+
+    /// Price resources whose provider answers [`Provider::monthly_cost`]
+    /// with `None` from the bundled AWS list prices.
+    pub fn set_list_prices(&mut self, list_prices: AwsListPrices) {
+        self.list_prices = Some(list_prices);
+    }
+
+    pub(crate) fn list_prices(&self) -> Option<&AwsListPrices> {
+        self.list_prices.as_ref()
+    }
+
+    /// Set the [`PROJECT_TAG`] and [`STATE_TAG`] values creates and
+    /// updates write. A no-op when no instance opted in.
+    pub fn set_provenance_values(&mut self, project: impl Into<String>, state: impl Into<String>) {
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.values = Some((project.into(), state.into()));
+        }
+    }
+
+    /// Provenance tags for `id`, or `None` when its instance did not opt
+    /// in, its type has no `tags` attribute, its provider does not
+    /// support tagging, or no values are set.
+    fn provenance_tags_for(&self, id: &ResourceId) -> Option<IndexMap<String, Value>> {
+        let provenance = self.provenance.as_ref()?;
+        let (project, state) = provenance.values.as_ref()?;
+        if !provenance.covers(id) || !self.capabilities(id).supports_tagging {
+            return None;
+        }
+        let string = |s: &str| Value::Concrete(ConcreteValue::String(s.to_string()));
+        let mut tags = IndexMap::new();
+        tags.insert(PROJECT_TAG.to_string(), string(project));
+        tags.insert(RESOURCE_ADDRESS_TAG.to_string(), string(&id.to_string()));
+        tags.insert(STATE_TAG.to_string(), string(state));
+        Some(tags)
+    }
+
+    /// Drop [`PROVENANCE_TAGS`] from the read-back `tags` of resources
+    /// whose instance opted in, and the `tags` attribute itself when
+    /// nothing else is left in it. Other instances keep `carina:*` tags
+    /// someone else set, so they still show up as drift.
+    fn strip_provenance_tags(&self, current_states: &mut HashMap<ResourceId, State>) {
+        let Some(provenance) = self.provenance.as_ref() else {
+            return;
+        };
+        for (id, state) in current_states.iter_mut() {
+            if !provenance.covers(id) {
+                continue;
+            }
+            let Some(Value::Concrete(ConcreteValue::Map(tags))) = state.attributes.get_mut("tags")
+            else {
+                continue;
+            };
+            let before = tags.len();
+            tags.retain(|key, _| !PROVENANCE_TAGS.contains(&key.as_str()));
+            if tags.is_empty() && before > 0 {
+                state.attributes.remove("tags");
+            }
+        }
+    }
+
+    /// Credential expiry of every instance that reports one, sorted by
+    /// instance name.
+    pub async fn credential_expiries_by_instance(&self) -> Vec<(String, SystemTime)> {
+        let mut keys: Vec<&(String, Option<String>)> = self.providers.keys().collect();
+        keys.sort();
+        let mut expiries = Vec::new();
+        for key in keys {
+            let (kind, binding) = key;
+            if let Some(expiry) = self.providers[key].credential_expiry().await {
+                let instance = match binding {
+                    Some(binding) => format!("{kind}.{binding}"),
+                    None => kind.clone(),
+                };
+                expiries.push((instance, expiry));
+            }
+        }
+        expiries
+    }
+
+    /// Ask every available instance for the resources tagged with
+    /// `project`, sorted by instance name. Ids returned by a named
+    /// instance are routed back to it.
+    pub async fn list_project_resources_by_instance(&self, project: &str) -> Vec<InstanceListing> {
+        let mut keys: Vec<&(String, Option<String>)> = self.providers.keys().collect();
+        keys.sort();
+        let mut listings = Vec::with_capacity(keys.len());
+        for key in keys {
+            let (kind, binding) = key;
+            let mut result = self.providers[key]
+                .list_project_resources(ListRequest {
+                    project: project.to_string(),
+                })
+                .await;
+            if let Ok(Some(states)) = result.as_mut() {
+                for state in states.iter_mut() {
+                    state.id.provider_instance = binding.clone();
+                }
+            }
+            listings.push(InstanceListing {
+                instance: match binding {
+                    Some(binding) => format!("{kind}.{binding}"),
+                    None => kind.clone(),
+                },
+                result,
+            });
         }
+        listings
     }
 
     /// Register the kind's default instance (resources with
@@ -995,12 +1596,129 @@ impl ProviderRouter {
         self.normalizers.push(ext);
     }
 
+    /// Record that a provider instance failed to initialize. Operations on
+    /// resources routed to it fail with `reason` instead of an
+    /// unknown-provider error, and [`blocked_resources`](Self::blocked_resources)
+    /// reports them.
+    pub fn add_unavailable_instance(
+        &mut self,
+        kind: String,
+        binding: Option<String>,
+        reason: impl Into<String>,
+    ) {
+        self.unavailable.insert((kind, binding), reason.into());
+    }
+
+    /// Why the instance `id` routes to is unavailable, if it is.
+    pub fn unavailable_reason(&self, id: &ResourceId) -> Option<&str> {
+        self.unavailable
+            .get(&(id.provider.clone(), id.provider_instance.clone()))
+            .map(String::as_str)
+    }
+
+    pub fn has_unavailable_instances(&self) -> bool {
+        !self.unavailable.is_empty()
+    }
+
+    /// Resources and data sources that cannot be planned: those routed to
+    /// an unavailable instance, and, transitively, those that depend on a
+    /// blocked binding. Returned in input order, resources first.
+    pub fn blocked_resources(
+        &self,
+        resources: &[Resource],
+        data_sources: &[DataSource],
+    ) -> Vec<BlockedResource> {
+        if self.unavailable.is_empty() {
+            return Vec::new();
+        }
+        let nodes: Vec<(
+            &ResourceId,
+            Option<&String>,
+            std::collections::HashSet<String>,
+        )> = resources
+            .iter()
+            .map(|r| {
+                (
+                    &r.id,
+                    r.binding.as_ref(),
+                    crate::deps::get_resource_dependencies(r),
+                )
+            })
+            .chain(data_sources.iter().map(|d| {
+                (
+                    &d.id,
+                    d.binding.as_ref(),
+                    crate::deps::get_data_source_dependencies(d),
+                )
+            }))
+            .collect();
+
+        let mut reasons: Vec<Option<String>> = nodes
+            .iter()
+            .map(|(id, _, _)| {
+                self.unavailable_reason(id).map(|reason| {
+                    let instance = match &id.provider_instance {
+                        Some(binding) => format!("'{}' ({})", binding, id.provider),
+                        None => format!("'{}'", id.provider),
+                    };
+                    format!("provider {} failed to initialize: {}", instance, reason)
+                })
+            })
+            .collect();
+        let mut blocked_bindings: std::collections::HashSet<&String> = nodes
+            .iter()
+            .zip(&reasons)
+            .filter(|(_, reason)| reason.is_some())
+            .filter_map(|((_, binding, _), _)| *binding)
+            .collect();
+        loop {
+            let mut changed = false;
+            for (i, (_, binding, deps)) in nodes.iter().enumerate() {
+                if reasons[i].is_some() {
+                    continue;
+                }
+                let mut blocked_deps: Vec<&String> = deps
+                    .iter()
+                    .filter(|d| blocked_bindings.contains(d))
+                    .collect();
+                if blocked_deps.is_empty() {
+                    continue;
+                }
+                blocked_deps.sort();
+                reasons[i] = Some(format!("depends on blocked '{}'", blocked_deps[0]));
+                if let Some(binding) = binding {
+                    blocked_bindings.insert(binding);
+                }
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        nodes
+            .iter()
+            .zip(reasons)
+            .filter_map(|((id, _, _), reason)| {
+                reason.map(|reason| BlockedResource {
+                    id: (*id).clone(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.providers.is_empty()
     }
 
     fn get_provider_or_error(&self, id: &ResourceId) -> ProviderResult<&dyn Provider> {
         let key = (id.provider.clone(), id.provider_instance.clone());
+        if let Some(reason) = self.unavailable.get(&key) {
+            return Err(ProviderError::invalid_input(reason.clone())
+                .for_provider(id.provider.clone())
+                .for_resource(id.clone()));
+        }
         self.providers.get(&key).map(|p| p.as_ref()).ok_or_else(|| {
             ProviderError::internal(match &id.provider_instance {
                 Some(binding) => format!(
@@ -1013,6 +1731,16 @@ impl ProviderRouter {
     }
 }
 
+/// Give an existing `state` the primary `identifier` it was addressed
+/// by when the provider did not report one, so state keeps addressing
+/// the resource by its identifier instead of falling back to a lookup
+/// by name on the next operation.
+fn keep_identifier(state: &mut State, identifier: Option<&str>) {
+    if state.exists && state.identifier.is_none() {
+        state.identifier = identifier.map(str::to_string);
+    }
+}
+
 impl Provider for ProviderRouter {
     fn name(&self) -> &str {
         "router"
@@ -1025,7 +1753,15 @@ impl Provider for ProviderRouter {
         request: ReadRequest,
     ) -> BoxFuture<'_, ProviderResult<State>> {
         match self.get_provider_or_error(id) {
-            Ok(provider) => provider.read(id, identifier, request),
+            Ok(provider) => {
+                let read = provider.read(id, identifier, request);
+                let identifier = identifier.map(str::to_string);
+                Box::pin(async move {
+                    let mut state = read.await?;
+                    keep_identifier(&mut state, identifier.as_deref());
+                    Ok(state)
+                })
+            }
             Err(e) => Box::pin(async move { Err(e) }),
         }
     }
@@ -1040,8 +1776,18 @@ impl Provider for ProviderRouter {
     fn create(
         &self,
         id: &ResourceId,
-        request: CreateRequest,
+        mut request: CreateRequest,
     ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+        if let Some(tags) = self.provenance_tags_for(id) {
+            let mut resource = request.resource.into_inner();
+            match resource.get_attr_mut("tags") {
+                Some(Value::Concrete(ConcreteValue::Map(existing))) => existing.extend(tags),
+                None => resource.set_attr("tags", Value::Concrete(ConcreteValue::Map(tags))),
+                // A non-map `tags` is provider-specific; leave it alone.
+                Some(_) => {}
+            }
+            request.resource = ResolvedResource::new(resource);
+        }
         match self.get_provider_or_error(id) {
             Ok(provider) => provider.create(id, request),
             Err(e) => Box::pin(async move { Err(e) }),
@@ -1052,10 +1798,23 @@ impl Provider for ProviderRouter {
         &self,
         id: &ResourceId,
         identifier: &str,
-        request: UpdateRequest,
+        mut request: UpdateRequest,
     ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+        if let Some(tags) = self.provenance_tags_for(id) {
+            add_provenance_to_patch(&mut request, tags);
+        }
         match self.get_provider_or_error(id) {
-            Ok(provider) => provider.update(id, identifier, request),
+            Ok(provider) => {
+                let update = provider.update(id, identifier, request);
+                let identifier = identifier.to_string();
+                Box::pin(async move {
+                    let mut outcome = update.await?;
+                    let (UpdateOutcome::Success { state }
+                    | UpdateOutcome::PartialSuccess { state, .. }) = &mut outcome;
+                    keep_identifier(state, Some(&identifier));
+                    Ok(outcome)
+                })
+            }
             Err(e) => Box::pin(async move { Err(e) }),
         }
     }
@@ -1085,6 +1844,100 @@ impl Provider for ProviderRouter {
             Err(_) => Vec::new(),
         }
     }
+
+    fn validate_resource(&self, resource: &Resource) -> Vec<ResourceDiagnostic> {
+        match self.get_provider_or_error(&resource.id) {
+            Ok(provider) => provider.validate_resource(resource),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn simulate(
+        &self,
+        resource: &Resource,
+    ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+        match self.get_provider_or_error(&resource.id) {
+            Ok(provider) => provider.simulate(resource),
+            Err(_) => Box::pin(async { Ok(Vec::new()) }),
+        }
+    }
+
+    /// Merged listing across instances; `None` only when no instance can
+    /// enumerate. Use [`ProviderRouter::list_project_resources_by_instance`]
+    /// to tell which instances were checked.
+    fn list_project_resources(
+        &self,
+        request: ListRequest,
+    ) -> BoxFuture<'_, ProviderResult<Option<Vec<State>>>> {
+        Box::pin(async move {
+            let mut merged: Option<Vec<State>> = None;
+            for listing in self
+                .list_project_resources_by_instance(&request.project)
+                .await
+            {
+                if let Some(states) = listing.result? {
+                    merged.get_or_insert_with(Vec::new).extend(states);
+                }
+            }
+            Ok(merged)
+        })
+    }
+
+    fn monthly_cost<'a>(
+        &'a self,
+        id: &'a ResourceId,
+        attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>> {
+        match self.get_provider_or_error(id) {
+            Ok(provider) => provider.monthly_cost(id, attributes),
+            Err(_) => Box::pin(async { None }),
+        }
+    }
+
+    fn delete_blockers(
+        &self,
+        id: &ResourceId,
+        identifier: &str,
+    ) -> BoxFuture<'_, ProviderResult<Vec<DeleteBlocker>>> {
+        match self.get_provider_or_error(id) {
+            Ok(provider) => provider.delete_blockers(id, identifier),
+            Err(e) => Box::pin(async move { Err(e) }),
+        }
+    }
+
+    fn find_existing(
+        &self,
+        id: &ResourceId,
+        resource: &ResolvedResource,
+    ) -> BoxFuture<'_, ProviderResult<Option<String>>> {
+        match self.get_provider_or_error(id) {
+            Ok(provider) => provider.find_existing(id, resource),
+            Err(e) => Box::pin(async move { Err(e) }),
+        }
+    }
+
+    fn operation_metrics(&self, id: &ResourceId) -> Option<OperationMetrics> {
+        self.get_provider_or_error(id)
+            .ok()
+            .and_then(|provider| provider.operation_metrics(id))
+    }
+
+    /// Earliest expiry across instances.
+    fn credential_expiry(&self) -> BoxFuture<'_, Option<SystemTime>> {
+        Box::pin(async move {
+            self.credential_expiries_by_instance()
+                .await
+                .into_iter()
+                .map(|(_, expiry)| expiry)
+                .min()
+        })
+    }
+
+    fn capabilities(&self, id: &ResourceId) -> ProviderCapabilities {
+        self.get_provider_or_error(id)
+            .map(|provider| provider.capabilities(id))
+            .unwrap_or_default()
+    }
 }
 
 impl ProviderNormalizer for ProviderRouter {
@@ -1107,6 +1960,7 @@ impl ProviderNormalizer for ProviderRouter {
             for ext in &self.normalizers {
                 ext.normalize_state(current_states).await;
             }
+            self.strip_provenance_tags(current_states);
         })
     }
 
@@ -1137,6 +1991,37 @@ impl ProviderNormalizer for ProviderRouter {
     }
 }
 
+/// Make `request.patch` write `tags` with `provenance` merged in: into
+/// the user's own tags op when the update already carries one, otherwise
+/// as a new op over the current tags.
+fn add_provenance_to_patch(request: &mut UpdateRequest, provenance: IndexMap<String, Value>) {
+    let ops = &mut request.patch.ops;
+    if let Some(op) = ops.iter_mut().find(|op| op.key == "tags") {
+        match (&op.kind, op.value.as_mut()) {
+            (_, Some(Value::Concrete(ConcreteValue::Map(tags)))) => tags.extend(provenance),
+            (PatchOpKind::Remove, _) => {
+                op.kind = PatchOpKind::Replace;
+                op.value = Some(Value::Concrete(ConcreteValue::Map(provenance)));
+            }
+            _ => {}
+        }
+        return;
+    }
+    let (kind, mut tags) = match request.from.attributes.get("tags") {
+        Some(Value::Concrete(ConcreteValue::Map(current))) => {
+            (PatchOpKind::Replace, current.clone())
+        }
+        None => (PatchOpKind::Add, IndexMap::new()),
+        Some(_) => return,
+    };
+    tags.extend(provenance);
+    ops.push(PatchOp {
+        kind,
+        key: "tags".to_string(),
+        value: Some(Value::Concrete(ConcreteValue::Map(tags))),
+    });
+}
+
 /// Factory for creating and configuring a Provider.
 ///
 /// Each provider crate implements this trait to encapsulate provider-specific
@@ -1149,6 +2034,21 @@ pub trait ProviderFactory: Send + Sync {
     /// Display name for user-facing messages (e.g., "AWS provider", "AWS Cloud Control provider")
     fn display_name(&self) -> &str;
 
+    /// Provider family this provider belongs to, when it shares a cloud
+    /// with other providers (e.g. `"aws"` for both `aws` and `awscc`).
+    /// Identified custom types from providers of one family are
+    /// assignable across them, so a reference can cross namespaces.
+    /// `None` keeps the provider in a family of its own.
+    fn type_family(&self) -> Option<&str> {
+        None
+    }
+
+    /// Version of the provider build, recorded alongside its schemas so
+    /// consumers can tell which provider revision a schema came from.
+    fn version(&self) -> Option<&str> {
+        None
+    }
+
     /// Return the types of the provider block's configuration attributes
     /// (e.g., `region`).
     ///
@@ -1239,6 +2139,10 @@ pub trait ProviderFactory: Send + Sync {
     /// `Some("-1")` because `"all"` is a DSL alias for the AWS value `"-1"`.
     ///
     /// Returns `None` if no alias mapping exists (the value is already canonical).
+    ///
+    /// Enum types that carry `dsl_aliases` are resolved from the schema
+    /// first (see [`crate::schema::ResourceSchema::enum_alias_reverse`]);
+    /// this hook is the fallback for aliases the schema does not declare.
     fn get_enum_alias_reverse(
         &self,
         _resource_type: &str,
@@ -1264,10 +2168,17 @@ pub fn find_factory<'a>(
 ///
 /// Each schema is inserted under the factory's `name()` plus `schema.kind`,
 /// so a given `(provider, resource_type)` pair may have both a `Managed`
-/// and a `DataSource` entry registered side by side.
+/// and a `DataSource` entry registered side by side. Declared
+/// [`ProviderFactory::type_family`] values are recorded alongside.
 pub fn collect_schemas(factories: &[Box<dyn ProviderFactory>]) -> SchemaRegistry {
     let mut registry = SchemaRegistry::new();
     for factory in factories {
+        if let Some(family) = factory.type_family() {
+            registry.set_provider_family(factory.name(), family);
+        }
+        if let Some(version) = factory.version() {
+            registry.set_provider_version(factory.name(), version);
+        }
         for schema in factory.schemas() {
             registry.insert(factory.name(), schema);
         }
@@ -1499,18 +2410,72 @@ impl Provider for Box<dyn Provider> {
     fn satisfier_hint(&self, target_id: &ResourceId, attr_path: &AttrPath) -> Vec<BindingPattern> {
         (**self).satisfier_hint(target_id, attr_path)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn validate_resource(&self, resource: &Resource) -> Vec<ResourceDiagnostic> {
+        (**self).validate_resource(resource)
+    }
 
-    fn resolved_for_test(resource: Resource) -> ResolvedResource {
-        let normalized =
-            futures::executor::block_on(crate::executor::normalized::apply_desired_normalization(
-                resource,
-                &[],
-                &NoopNormalizer,
+    fn simulate(
+        &self,
+        resource: &Resource,
+    ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+        (**self).simulate(resource)
+    }
+
+    fn list_project_resources(
+        &self,
+        request: ListRequest,
+    ) -> BoxFuture<'_, ProviderResult<Option<Vec<State>>>> {
+        (**self).list_project_resources(request)
+    }
+
+    fn monthly_cost<'a>(
+        &'a self,
+        id: &'a ResourceId,
+        attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>> {
+        (**self).monthly_cost(id, attributes)
+    }
+
+    fn delete_blockers(
+        &self,
+        id: &ResourceId,
+        identifier: &str,
+    ) -> BoxFuture<'_, ProviderResult<Vec<DeleteBlocker>>> {
+        (**self).delete_blockers(id, identifier)
+    }
+
+    fn find_existing(
+        &self,
+        id: &ResourceId,
+        resource: &ResolvedResource,
+    ) -> BoxFuture<'_, ProviderResult<Option<String>>> {
+        (**self).find_existing(id, resource)
+    }
+
+    fn operation_metrics(&self, id: &ResourceId) -> Option<OperationMetrics> {
+        (**self).operation_metrics(id)
+    }
+
+    fn credential_expiry(&self) -> BoxFuture<'_, Option<SystemTime>> {
+        (**self).credential_expiry()
+    }
+
+    fn capabilities(&self, id: &ResourceId) -> ProviderCapabilities {
+        (**self).capabilities(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved_for_test(resource: Resource) -> ResolvedResource {
+        let normalized =
+            futures::executor::block_on(crate::executor::normalized::apply_desired_normalization(
+                resource,
+                &[],
+                &NoopNormalizer,
                 &[],
                 &crate::schema::SchemaRegistry::new(),
             ));
@@ -1606,6 +2571,22 @@ mod tests {
         assert!(!state.exists);
     }
 
+    #[test]
+    fn only_credential_rejections_are_fatal() {
+        assert!(
+            ProviderError::credentials("The security token included in the request is expired")
+                .with_code("ExpiredToken")
+                .is_fatal()
+        );
+        assert!(
+            !ProviderError::api_error("The security token included in the request is expired")
+                .with_code("ExpiredToken")
+                .is_fatal()
+        );
+        assert!(!ProviderError::internal("provider panicked").is_fatal());
+        assert!(!ProviderError::timeout("ExpiredToken").is_fatal());
+    }
+
     #[test]
     fn create_outcome_exposes_state_and_diagnostic() {
         let id = ResourceId::with_identity("test", "example");
@@ -1857,6 +2838,8 @@ mod tests {
                 &id,
                 CreateRequest {
                     resource: resolved_for_test(resource),
+                    timeout: None,
+                    client_token: None,
                 },
             )
             .await
@@ -1888,6 +2871,8 @@ mod tests {
                 &id,
                 CreateRequest {
                     resource: resolved_for_test(resource),
+                    timeout: None,
+                    client_token: None,
                 },
             )
             .await
@@ -1897,6 +2882,277 @@ mod tests {
         assert_eq!(state.identifier, Some("mock-id-123".to_string()));
     }
 
+    /// Echoes a create's attributes, or an update's patched values, into
+    /// state and lists one tagged resource.
+    struct ProjectTaggingProvider;
+
+    impl Provider for ProjectTaggingProvider {
+        fn name(&self) -> &str {
+            "tagging"
+        }
+
+        fn read(
+            &self,
+            id: &ResourceId,
+            _identifier: Option<&str>,
+            _request: ReadRequest,
+        ) -> BoxFuture<'_, ProviderResult<State>> {
+            let id = id.clone();
+            Box::pin(async move { Ok(State::not_found(id)) })
+        }
+
+        fn read_data_source(&self, resource: &DataSource) -> BoxFuture<'_, ProviderResult<State>> {
+            let id = resource.id.clone();
+            Box::pin(async move { Ok(State::not_found(id)) })
+        }
+
+        fn create(
+            &self,
+            id: &ResourceId,
+            request: CreateRequest,
+        ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+            let id = id.clone();
+            Box::pin(async move {
+                let attributes = request.resource.as_resource().resolved_attributes();
+                let state = State::existing(id, attributes);
+                Ok(CreateOutcome::Success { state })
+            })
+        }
+
+        fn update(
+            &self,
+            id: &ResourceId,
+            _identifier: &str,
+            request: UpdateRequest,
+        ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+            let attributes = request
+                .patch
+                .ops
+                .into_iter()
+                .filter_map(|op| Some((op.key, op.value?)))
+                .collect();
+            let state = State::existing(id.clone(), attributes);
+            Box::pin(async move { Ok(UpdateOutcome::Success { state }) })
+        }
+
+        fn delete(
+            &self,
+            _id: &ResourceId,
+            _identifier: &str,
+            _request: DeleteRequest,
+        ) -> BoxFuture<'_, ProviderResult<()>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn required_permissions(&self, _id: &ResourceId, _op: PlanOp) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn list_project_resources(
+            &self,
+            request: ListRequest,
+        ) -> BoxFuture<'_, ProviderResult<Option<Vec<State>>>> {
+            Box::pin(async move {
+                let id = ResourceId::with_provider("tagging", "ec2.Vpc", None, None);
+                Ok(Some(vec![
+                    State::existing(id, HashMap::new()).with_identifier(request.project),
+                ]))
+            })
+        }
+    }
+
+    fn provenance_router() -> ProviderRouter {
+        let mut router = ProviderRouter::new();
+        router.add_provider("tagging".to_string(), Box::new(ProjectTaggingProvider));
+        router.add_provider_instance(
+            "tagging".to_string(),
+            Some("east".to_string()),
+            Box::new(ProjectTaggingProvider),
+        );
+        router.set_provenance(ProvenanceTagging {
+            instances: [("tagging".to_string(), None)].into_iter().collect(),
+            taggable: [("tagging".to_string(), "ec2.Vpc".to_string())]
+                .into_iter()
+                .collect(),
+            values: None,
+        });
+        router.set_provenance_values("lineage-1", "s3://bucket/carina.state.json");
+        router
+    }
+
+    fn string_map(pairs: &[(&str, &str)]) -> Value {
+        Value::Concrete(ConcreteValue::Map(
+            pairs
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.to_string(),
+                        Value::Concrete(ConcreteValue::String(v.to_string())),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn provider_router_adds_provenance_tags_on_create() {
+        let router = provenance_router();
+        let mut resource = Resource::with_provider("tagging", "ec2.Vpc", "main", None);
+        resource.set_attr("tags", string_map(&[("Name", "main")]));
+        let id = resource.id.clone();
+        let state = router
+            .create(
+                &id,
+                CreateRequest {
+                    resource: resolved_for_test(resource),
+                    timeout: None,
+                    client_token: None,
+                },
+            )
+            .await
+            .unwrap()
+            .into_state_for_writeback();
+        assert_eq!(
+            state.attributes.get("tags"),
+            Some(&string_map(&[
+                ("Name", "main"),
+                ("carina:project", "lineage-1"),
+                ("carina:resource_address", "tagging.ec2.Vpc.main"),
+                ("carina:state", "s3://bucket/carina.state.json"),
+            ]))
+        );
+
+        // Read-back normalization hides them again.
+        let mut states = HashMap::from([(id.clone(), state)]);
+        router.normalize_state(&mut states).await;
+        assert_eq!(
+            states[&id].attributes.get("tags"),
+            Some(&string_map(&[("Name", "main")]))
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_router_strips_provenance_tags_only_for_opted_in_instances() {
+        let mut router = provenance_router();
+        if let Some(provenance) = router.provenance.as_mut() {
+            // A plan sets no values: nothing is written, but read-back
+            // tags are still stripped.
+            provenance.values = None;
+        }
+        let tagged = || {
+            HashMap::from([(
+                "tags".to_string(),
+                string_map(&[("Name", "main"), ("carina:project", "lineage-1")]),
+            )])
+        };
+        let opted_in = ResourceId::with_provider_identity("tagging", "ec2.Vpc", "main", None);
+        let mut other = ResourceId::with_provider_identity("tagging", "ec2.Vpc", "main", None);
+        other.provider_instance = Some("east".to_string());
+        let mut states = HashMap::from([
+            (
+                opted_in.clone(),
+                State::existing(opted_in.clone(), tagged()),
+            ),
+            (other.clone(), State::existing(other.clone(), tagged())),
+        ]);
+        router.normalize_state(&mut states).await;
+        assert_eq!(
+            states[&opted_in].attributes.get("tags"),
+            Some(&string_map(&[("Name", "main")]))
+        );
+        assert_eq!(states[&other].attributes, tagged());
+
+        let resource = Resource::with_provider("tagging", "ec2.Vpc", "main", None);
+        let state = router
+            .create(
+                &opted_in,
+                CreateRequest {
+                    resource: resolved_for_test(resource),
+                    timeout: None,
+                    client_token: None,
+                },
+            )
+            .await
+            .unwrap()
+            .into_state_for_writeback();
+        assert!(!state.attributes.contains_key("tags"));
+    }
+
+    #[tokio::test]
+    async fn provider_router_adds_provenance_tags_to_updates_without_a_tags_op() {
+        let router = provenance_router();
+        let id = ResourceId::with_provider_identity("tagging", "ec2.Vpc", "main", None);
+        let from = State::existing(
+            id.clone(),
+            HashMap::from([("tags".to_string(), string_map(&[("Name", "main")]))]),
+        );
+        let state = router
+            .update(
+                &id,
+                "vpc-1",
+                UpdateRequest {
+                    from,
+                    patch: UpdatePatch::default(),
+                    timeout: None,
+                },
+            )
+            .await
+            .unwrap()
+            .into_state_for_writeback();
+        let Some(Value::Concrete(ConcreteValue::Map(tags))) = state.attributes.get("tags") else {
+            panic!("update should carry a tags op");
+        };
+        assert_eq!(tags.len(), 4);
+        assert!(tags.contains_key("Name"));
+    }
+
+    #[tokio::test]
+    async fn provider_router_skips_provenance_for_untaggable_types() {
+        let router = provenance_router();
+        let resource = Resource::with_provider("tagging", "iam.Policy", "p", None);
+        let id = resource.id.clone();
+        let state = router
+            .create(
+                &id,
+                CreateRequest {
+                    resource: resolved_for_test(resource),
+                    timeout: None,
+                    client_token: None,
+                },
+            )
+            .await
+            .unwrap()
+            .into_state_for_writeback();
+        assert!(!state.attributes.contains_key("tags"));
+    }
+
+    #[tokio::test]
+    async fn provider_router_lists_project_resources_per_instance() {
+        let mut router = ProviderRouter::new();
+        router.add_provider("mock".to_string(), Box::new(MockProvider));
+        router.add_provider_instance(
+            "tagging".to_string(),
+            Some("east".to_string()),
+            Box::new(ProjectTaggingProvider),
+        );
+
+        let listings = router.list_project_resources_by_instance("p").await;
+        let instances: Vec<&str> = listings.iter().map(|l| l.instance.as_str()).collect();
+        assert_eq!(instances, vec!["mock", "tagging.east"]);
+        assert!(matches!(listings[0].result, Ok(None)));
+        let states = listings[1].result.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(states[0].id.provider_instance.as_deref(), Some("east"));
+
+        let merged = router
+            .list_project_resources(ListRequest {
+                project: "p".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.len(), 1);
+    }
+
     #[test]
     fn provider_error_source_returns_cause() {
         use std::error::Error;
@@ -2282,6 +3538,20 @@ mod tests {
         let to = ProviderError::timeout("slow");
         assert!(matches!(to, ProviderError::Timeout(_)));
 
+        let dup = ProviderError::already_exists("taken");
+        assert!(matches!(dup, ProviderError::AlreadyExists(_)));
+
+        let amb = ProviderError::ambiguous_match(
+            "2 VPCs are named main",
+            vec![
+                "vpc-2".to_string(),
+                "vpc-1".to_string(),
+                "vpc-2".to_string(),
+            ],
+        );
+        assert!(matches!(amb, ProviderError::AmbiguousMatch(_)));
+        assert_eq!(amb.detail().candidates, ["vpc-1", "vpc-2"]);
+
         let intl = ProviderError::internal("bug");
         assert!(matches!(intl, ProviderError::Internal(_)));
     }
@@ -2312,7 +3582,7 @@ mod tests {
 
         let to = resolved_for_test(to);
         let changed = vec!["a".to_string(), "b".to_string(), "c".to_string()];
-        let patch = build_update_patch(&changed, &to, &from);
+        let patch = build_update_patch(&changed, &to, &from, None);
         assert_eq!(patch.ops.len(), 3);
 
         let by_key: HashMap<&str, &PatchOp> =
@@ -2334,6 +3604,65 @@ mod tests {
         assert_eq!(c.value, None);
     }
 
+    #[test]
+    fn build_update_patch_keeps_current_order_of_unordered_keyed_list() {
+        use crate::schema::{AttributeSchema, AttributeType, ResourceSchema, StructField};
+
+        let rule = |id: &str| {
+            Value::Concrete(ConcreteValue::Map(IndexMap::from([(
+                "id".to_string(),
+                Value::Concrete(ConcreteValue::String(id.to_string())),
+            )])))
+        };
+        let id = ResourceId::with_identity("test", "example");
+        let from = State::existing(
+            id,
+            HashMap::from([(
+                "rules".to_string(),
+                Value::Concrete(ConcreteValue::List(vec![rule("a"), rule("b"), rule("c")])),
+            )]),
+        );
+        let mut to = Resource::new("test", "example");
+        to.set_attr(
+            "rules".to_string(),
+            Value::Concrete(ConcreteValue::List(vec![rule("new"), rule("c"), rule("a")])),
+        );
+        let to = resolved_for_test(to);
+        let rule_type = AttributeType::struct_(
+            "Rule".to_string(),
+            vec![StructField::new("id", AttributeType::string())],
+        );
+        let keyed = ResourceSchema::new("test").attribute(
+            AttributeSchema::new("rules", AttributeType::unordered_list(rule_type.clone()))
+                .with_list_key("id"),
+        );
+        let ordered = ResourceSchema::new("test").attribute(
+            AttributeSchema::new("rules", AttributeType::list(rule_type)).with_list_key("id"),
+        );
+        let changed = vec!["rules".to_string()];
+
+        let patch = build_update_patch(&changed, &to, &from, Some(&keyed));
+        assert_eq!(
+            patch.ops[0].value,
+            Some(Value::Concrete(ConcreteValue::List(vec![
+                rule("a"),
+                rule("c"),
+                rule("new")
+            ])))
+        );
+
+        // Order is meaningful for an ordered list, so it is sent as written.
+        let patch = build_update_patch(&changed, &to, &from, Some(&ordered));
+        assert_eq!(
+            patch.ops[0].value,
+            Some(Value::Concrete(ConcreteValue::List(vec![
+                rule("new"),
+                rule("c"),
+                rule("a")
+            ])))
+        );
+    }
+
     #[tokio::test]
     async fn provider_router_dispatches_update_by_provider_name() {
         let mut router = ProviderRouter::new();
@@ -2344,6 +3673,7 @@ mod tests {
         let request = UpdateRequest {
             from,
             patch: UpdatePatch::default(),
+            timeout: None,
         };
         let state = router
             .update(&id, "mock-id-123", request)
@@ -2351,6 +3681,23 @@ mod tests {
             .unwrap()
             .into_state_for_writeback();
         assert!(state.exists);
+        // The mock reports no identifier; the one the update addressed
+        // is kept so state does not lose it.
+        assert_eq!(state.identifier.as_deref(), Some("mock-id-123"));
+    }
+
+    #[tokio::test]
+    async fn provider_router_read_does_not_attach_identifier_to_missing_resources() {
+        let mut router = ProviderRouter::new();
+        router.add_provider("mock".to_string(), Box::new(MockProvider));
+
+        let id = ResourceId::with_provider_identity("mock", "test", "example", None);
+        let state = router
+            .read(&id, Some("mock-id-123"), ReadRequest)
+            .await
+            .unwrap();
+        assert!(!state.exists);
+        assert_eq!(state.identifier, None);
     }
 
     #[tokio::test]
@@ -2375,6 +3722,62 @@ mod tests {
         assert!(err.message().contains("Unknown provider: nonexistent"));
     }
 
+    #[tokio::test]
+    async fn provider_router_reports_init_error_for_unavailable_instance() {
+        let mut router = ProviderRouter::new();
+        router.add_provider("mock".to_string(), Box::new(MockProvider));
+        router.add_unavailable_instance(
+            "mock".to_string(),
+            Some("us".to_string()),
+            "AssumeRole failed: invalid role ARN",
+        );
+
+        let id = ResourceId::with_provider_identity("mock", "test", "a", Some("us".to_string()));
+        let err = router.read(&id, None, ReadRequest).await.unwrap_err();
+        assert!(matches!(err, ProviderError::InvalidInput(_)));
+        assert_eq!(err.message(), "AssumeRole failed: invalid role ARN");
+
+        let default_id = ResourceId::with_provider_identity("mock", "test", "b", None);
+        assert!(router.read(&default_id, None, ReadRequest).await.is_ok());
+    }
+
+    #[test]
+    fn blocked_resources_follow_dependencies_on_unavailable_instances() {
+        let mut router = ProviderRouter::new();
+        router.add_provider("mock".to_string(), Box::new(MockProvider));
+        router.add_unavailable_instance("mock".to_string(), Some("us".to_string()), "bad role");
+
+        let mut vpc = Resource::with_provider("mock", "test", "vpc", Some("us".to_string()));
+        vpc.binding = Some("vpc".to_string());
+        let mut subnet = Resource::with_provider("mock", "test", "subnet", None);
+        subnet.binding = Some("subnet".to_string());
+        subnet.dependency_bindings.insert("vpc".to_string());
+        let mut instance = Resource::with_provider("mock", "test", "instance", None);
+        instance.dependency_bindings.insert("subnet".to_string());
+        let bucket = Resource::with_provider("mock", "test", "bucket", None);
+
+        let blocked = router.blocked_resources(
+            &[instance.clone(), subnet.clone(), vpc.clone(), bucket],
+            &[],
+        );
+        let ids: Vec<&ResourceId> = blocked.iter().map(|b| &b.id).collect();
+        assert_eq!(ids, vec![&instance.id, &subnet.id, &vpc.id]);
+        assert_eq!(
+            blocked[2].reason,
+            "provider 'us' (mock) failed to initialize: bad role"
+        );
+        assert_eq!(blocked[1].reason, "depends on blocked 'vpc'");
+        assert_eq!(blocked[0].reason, "depends on blocked 'subnet'");
+    }
+
+    #[test]
+    fn blocked_resources_is_empty_when_every_instance_initialized() {
+        let mut router = ProviderRouter::new();
+        router.add_provider("mock".to_string(), Box::new(MockProvider));
+        let resource = Resource::with_provider("mock", "test", "a", None);
+        assert!(router.blocked_resources(&[resource], &[]).is_empty());
+    }
+
     /// A provider that records its identity in the returned state
     /// so a routing test can tell which instance handled a call.
     struct TaggedProvider {
@@ -2428,6 +3831,64 @@ mod tests {
         fn required_permissions(&self, _id: &ResourceId, _op: PlanOp) -> Vec<String> {
             Vec::new()
         }
+
+        fn validate_resource(&self, _resource: &Resource) -> Vec<ResourceDiagnostic> {
+            vec![ResourceDiagnostic::warning(self.tag)]
+        }
+
+        fn simulate(
+            &self,
+            resource: &Resource,
+        ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+            let diagnostic =
+                ResourceDiagnostic::error(format!("{} not found", resource.id.identity_or_empty()))
+                    .with_attribute(self.tag);
+            Box::pin(async move { Ok(vec![diagnostic]) })
+        }
+    }
+
+    #[test]
+    fn provider_router_forwards_validate_resource_to_instance() {
+        let mut router = ProviderRouter::new();
+        router.add_provider_instance(
+            "mock".to_string(),
+            None,
+            Box::new(TaggedProvider { tag: "default" }),
+        );
+        router.add_provider_instance(
+            "mock".to_string(),
+            Some("us".to_string()),
+            Box::new(TaggedProvider { tag: "us" }),
+        );
+
+        let resource = Resource::with_provider("mock", "test", "b", Some("us".to_string()));
+        let diagnostics = router.validate_resource(&resource);
+        assert_eq!(diagnostics, vec![ResourceDiagnostic::warning("us")]);
+
+        // An unroutable resource yields no diagnostics: the missing
+        // provider is reported by the CRUD path, not by validation.
+        let unknown = Resource::with_provider("other", "test", "c", None);
+        assert!(router.validate_resource(&unknown).is_empty());
+    }
+
+    #[tokio::test]
+    async fn provider_router_forwards_simulate_to_instance() {
+        let mut router = ProviderRouter::new();
+        router.add_provider_instance(
+            "mock".to_string(),
+            Some("us".to_string()),
+            Box::new(TaggedProvider { tag: "us" }),
+        );
+
+        let resource = Resource::with_provider("mock", "test", "b", Some("us".to_string()));
+        let diagnostics = router.simulate(&resource).await.unwrap();
+        assert_eq!(
+            diagnostics,
+            vec![ResourceDiagnostic::error("b not found").with_attribute("us")]
+        );
+
+        let unknown = Resource::with_provider("other", "test", "c", None);
+        assert!(router.simulate(&unknown).await.unwrap().is_empty());
     }
 
     #[tokio::test]
@@ -2845,4 +4306,33 @@ mod tests {
             )))
         );
     }
+
+    #[tokio::test]
+    async fn unpriced_resources_fall_back_to_the_router_list_prices() {
+        use crate::cost::CostEstimator;
+
+        let parsed = crate::parser::parse(
+            r#"
+provider awscc {
+  region = "ap-northeast-1"
+}
+"#,
+            &crate::parser::ProviderContext::default(),
+        )
+        .unwrap();
+        let mut router = ProviderRouter::new();
+        router.add_provider("awscc".to_string(), Box::new(MockProvider));
+        let nat = ResourceId::with_provider_identity("awscc", "ec2.NatGateway", "nat", None);
+        assert!(
+            CostEstimator::monthly_cost(&router, &nat, &HashMap::new())
+                .await
+                .is_none()
+        );
+
+        router.set_list_prices(AwsListPrices::from_configs(&parsed.providers).unwrap());
+        let cost = CostEstimator::monthly_cost(&router, &nat, &HashMap::new())
+            .await
+            .unwrap();
+        assert!((cost.usd - 45.26).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,223 @@
+//! Refresh-only reads for `carina refresh`: every resource tracked in
+//! state is read back from its provider by the identifier state records,
+//! without planning any change. The caller writes the results back, so
+//! state catches up with changes made outside Carina and with computed
+//! attributes (DNS names, ARNs) that moved since the last apply.
+
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
+use tokio_util::sync::CancellationToken;
+
+use crate::provider::{Provider, ProviderError, ReadRequest};
+use crate::resource::{ResourceId, State};
+
+/// Reads kept in flight at once.
+pub const REFRESH_CONCURRENCY: usize = 5;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RefreshError {
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    #[error("refresh cancelled")]
+    Cancelled,
+}
+
+/// Read every `(id, identifier)` pair, [`REFRESH_CONCURRENCY`] at a time.
+///
+/// The first read error fails the refresh. Cancelling stops new reads
+/// from being dispatched but waits for those in flight, so no provider
+/// call is dropped mid-request, then returns
+/// [`RefreshError::Cancelled`].
+pub async fn read_tracked(
+    provider: &dyn Provider,
+    reads: Vec<(ResourceId, String)>,
+    cancel: &CancellationToken,
+) -> Result<HashMap<ResourceId, State>, RefreshError> {
+    let mut states = HashMap::new();
+    let mut pending = reads.into_iter();
+    let mut in_flight = FuturesUnordered::new();
+    let mut cancelled = cancel.is_cancelled();
+
+    loop {
+        while !cancelled && in_flight.len() < REFRESH_CONCURRENCY {
+            let Some((id, identifier)) = pending.next() else {
+                break;
+            };
+            in_flight.push(async move {
+                let state = provider
+                    .read(&id, Some(identifier.as_str()), ReadRequest)
+                    .await?;
+                Ok::<_, ProviderError>((id, state))
+            });
+        }
+        if in_flight.is_empty() {
+            break;
+        }
+
+        let result = if cancelled {
+            in_flight.next().await
+        } else {
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    cancelled = true;
+                    continue;
+                }
+                result = in_flight.next() => result,
+            }
+        };
+        if cancelled {
+            continue;
+        }
+        if let Some(result) = result {
+            let (id, state) = result?;
+            states.insert(id, state);
+        }
+    }
+
+    if cancelled {
+        return Err(RefreshError::Cancelled);
+    }
+    Ok(states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::effect::PlanOp;
+    use crate::provider::{
+        BoxFuture, CreateOutcome, CreateRequest, DeleteRequest, ProviderResult, UpdateOutcome,
+        UpdateRequest,
+    };
+    use crate::resource::DataSource;
+
+    /// Reads back every resource as existing, tracking peak concurrency.
+    #[derive(Default)]
+    struct CountingReads {
+        active: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl Provider for CountingReads {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn read(
+            &self,
+            id: &ResourceId,
+            identifier: Option<&str>,
+            _request: ReadRequest,
+        ) -> BoxFuture<'_, ProviderResult<State>> {
+            let id = id.clone();
+            let identifier = identifier.map(str::to_string);
+            Box::pin(async move {
+                let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(active, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                self.active.fetch_sub(1, Ordering::SeqCst);
+                if identifier.as_deref() == Some("broken") {
+                    return Err(ProviderError::api_error("AccessDenied"));
+                }
+                let state = State::existing(id, HashMap::new());
+                Ok(match identifier {
+                    Some(identifier) => state.with_identifier(identifier),
+                    None => state,
+                })
+            })
+        }
+
+        fn read_data_source(&self, _resource: &DataSource) -> BoxFuture<'_, ProviderResult<State>> {
+            unreachable!()
+        }
+
+        fn create(
+            &self,
+            _id: &ResourceId,
+            _request: CreateRequest,
+        ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+            unreachable!()
+        }
+
+        fn update(
+            &self,
+            _id: &ResourceId,
+            _identifier: &str,
+            _request: UpdateRequest,
+        ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+            unreachable!()
+        }
+
+        fn delete(
+            &self,
+            _id: &ResourceId,
+            _identifier: &str,
+            _request: DeleteRequest,
+        ) -> BoxFuture<'_, ProviderResult<()>> {
+            unreachable!()
+        }
+
+        fn required_permissions(&self, _id: &ResourceId, _op: PlanOp) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    fn reads(identifiers: &[&str]) -> Vec<(ResourceId, String)> {
+        identifiers
+            .iter()
+            .enumerate()
+            .map(|(i, identifier)| {
+                let id = ResourceId::with_provider_identity(
+                    "counting",
+                    "ec2.Vpc",
+                    format!("vpc{i}"),
+                    None,
+                );
+                (id, identifier.to_string())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reads_every_tracked_resource_by_its_identifier() {
+        let provider = CountingReads::default();
+        let identifiers: Vec<String> = (0..12).map(|i| format!("vpc-{i}")).collect();
+        let identifiers: Vec<&str> = identifiers.iter().map(String::as_str).collect();
+        let reads = reads(&identifiers);
+
+        let states = read_tracked(&provider, reads.clone(), &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(states.len(), 12);
+        for (id, identifier) in &reads {
+            assert_eq!(states[id].identifier.as_deref(), Some(identifier.as_str()));
+        }
+        let peak = provider.peak.load(Ordering::SeqCst);
+        assert!((1..=REFRESH_CONCURRENCY).contains(&peak), "peak {peak}");
+    }
+
+    #[tokio::test]
+    async fn read_errors_and_cancellation_fail_the_refresh() {
+        let provider = CountingReads::default();
+        let err = read_tracked(
+            &provider,
+            reads(&["vpc-1", "broken"]),
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, RefreshError::Provider(_)), "{err:?}");
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = read_tracked(&provider, reads(&["vpc-1"]), &cancel)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RefreshError::Cancelled));
+    }
+}
@@ -868,6 +868,12 @@ pub enum ConcreteValue {
     /// contract).
     #[serde(with = "duration_secs")]
     Duration(std::time::Duration),
+    /// Data size in bytes.
+    ///
+    /// Constructed from a `<integer><unit>` literal in DSL source
+    /// `512MiB`, `5GB`, `1TiB`. Serialises to JSON as integer bytes,
+    /// mirroring `Duration`.
+    ByteSize(u64),
     List(Vec<Value>),
     /// Canonical form for fields whose schema type is
     /// `Union(vec![String, list(String)])` — the IAM-style
@@ -925,6 +931,7 @@ pub enum ConcreteValueRef<'a> {
     Float(f64),
     Bool(bool),
     Duration(std::time::Duration),
+    ByteSize(u64),
     List(&'a [Value]),
     StringList(&'a [String]),
     Map(&'a IndexMap<String, Value>),
@@ -997,6 +1004,7 @@ impl Value {
                 ConcreteValue::Float(f) => ConcreteValueRef::Float(*f),
                 ConcreteValue::Bool(b) => ConcreteValueRef::Bool(*b),
                 ConcreteValue::Duration(d) => ConcreteValueRef::Duration(*d),
+                ConcreteValue::ByteSize(n) => ConcreteValueRef::ByteSize(*n),
                 ConcreteValue::List(items) => ConcreteValueRef::List(items),
                 ConcreteValue::StringList(items) => ConcreteValueRef::StringList(items),
                 ConcreteValue::Map(map) => ConcreteValueRef::Map(map),
@@ -1085,6 +1093,9 @@ pub enum UnknownReason {
     EmptyInterpolation,
     /// A create completed, but the provider could not read all attributes.
     PostCreateReadIncomplete { detail: String },
+    /// The members of a deferred for-expression bound to `binding`, as
+    /// `attrs()` / `ids()` see them before the iterable resolves.
+    ForGroup { binding: String },
 }
 
 /// A part of a string interpolation expression
@@ -1154,6 +1165,10 @@ impl PartialEq for Value {
                 Value::Concrete(ConcreteValue::Duration(a)),
                 Value::Concrete(ConcreteValue::Duration(b)),
             ) => a == b,
+            (
+                Value::Concrete(ConcreteValue::ByteSize(a)),
+                Value::Concrete(ConcreteValue::ByteSize(b)),
+            ) => a == b,
             (Value::Concrete(ConcreteValue::List(a)), Value::Concrete(ConcreteValue::List(b))) => {
                 a == b
             }
@@ -1216,6 +1231,11 @@ fn canonicalize_interpolation(parts: Vec<InterpolationPart>) -> Value {
     if merged.is_empty() {
         return Value::Concrete(ConcreteValue::String(String::new()));
     }
+    if let Some(joined) = fold_secret_interpolation(&merged) {
+        return Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+            ConcreteValue::String(joined),
+        ))));
+    }
     if merged.len() == 1 {
         // Pop the sole element. If it is a Literal, we collapse to
         // `Value::String`; otherwise it is a non-foldable Expr and we
@@ -1228,6 +1248,26 @@ fn canonicalize_interpolation(parts: Vec<InterpolationPart>) -> Value {
     Value::Deferred(DeferredValue::Interpolation(merged))
 }
 
+/// When every `Expr` part is a `Secret` wrapping a string-shaped scalar,
+/// the full text of the interpolation. The string is itself secret: it
+/// is re-wrapped by the caller so `"user:${password}"` is redacted as a
+/// whole rather than left as an interpolation no provider can accept.
+fn fold_secret_interpolation(parts: &[InterpolationPart]) -> Option<String> {
+    let mut joined = String::new();
+    let mut saw_secret = false;
+    for part in parts {
+        match part {
+            InterpolationPart::Literal(s) => joined.push_str(s),
+            InterpolationPart::Expr(Value::Deferred(DeferredValue::Secret(inner))) => {
+                joined.push_str(&value_into_literal((**inner).clone()).ok()?);
+                saw_secret = true;
+            }
+            InterpolationPart::Expr(_) => return None,
+        }
+    }
+    saw_secret.then_some(joined)
+}
+
 /// If `v` is a string-shaped scalar, return its string form (consuming
 /// `v`); otherwise return `v` unchanged so the caller can keep wrapping
 /// it as an `Expr`.
@@ -1385,6 +1425,7 @@ impl Value {
             | Value::Concrete(ConcreteValue::Float(_))
             | Value::Concrete(ConcreteValue::Bool(_))
             | Value::Concrete(ConcreteValue::Duration(_))
+            | Value::Concrete(ConcreteValue::ByteSize(_))
             | Value::Concrete(ConcreteValue::StringList(_))
             | Value::Deferred(DeferredValue::BindingRef { .. }) => {}
             // `Value::Unknown` is what a previously-unresolved
@@ -1434,6 +1475,7 @@ impl Value {
             | Value::Concrete(ConcreteValue::Float(_))
             | Value::Concrete(ConcreteValue::Bool(_))
             | Value::Concrete(ConcreteValue::Duration(_))
+            | Value::Concrete(ConcreteValue::ByteSize(_))
             | Value::Concrete(ConcreteValue::StringList(_))
             | Value::Deferred(DeferredValue::ResourceRef { .. }) => {}
             // `Value::Unknown` is what a previously-unresolved reference was
@@ -1508,6 +1550,7 @@ impl Value {
             }
             Value::Concrete(ConcreteValue::Bool(b)) => b.hash(hasher),
             Value::Concrete(ConcreteValue::Duration(d)) => d.as_secs().hash(hasher),
+            Value::Concrete(ConcreteValue::ByteSize(n)) => n.hash(hasher),
             Value::Concrete(ConcreteValue::List(items)) => {
                 // For list hashing, use an order-independent combination (wrapping sum)
                 // so that lists with same elements in different order hash the same.
@@ -1602,6 +1645,7 @@ impl Value {
                     // distinct hashes.
                     UnknownReason::ForValuePath { path } => path.hash(hasher),
                     UnknownReason::PostCreateReadIncomplete { detail } => detail.hash(hasher),
+                    UnknownReason::ForGroup { binding } => binding.hash(hasher),
                     // `For{Key,Index,Value}` and `EmptyInterpolation`
                     // carry no payload; the discriminant alone already
                     // distinguishes them.
@@ -1886,6 +1930,36 @@ pub struct Directives {
     /// routing land in Phase 3.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub provider_instance: Option<String>,
+    /// Per-operation timeouts from the resource's `timeouts { ... }`
+    /// block. Passed to the provider on each create / update / delete.
+    #[serde(default, skip_serializing_if = "OperationTimeouts::is_empty")]
+    pub timeouts: OperationTimeouts,
+    /// The unevaluated `enabled` expression. It usually depends on a
+    /// variable or module argument bound after parsing, so
+    /// [`crate::enabled::drop_disabled_resources`] evaluates it once those
+    /// are substituted and clears it. `None` means enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<Value>,
+}
+
+/// How long the provider may wait for each kind of operation on a
+/// resource, e.g. `timeouts { create = 20m, delete = 40m }`.
+///
+/// `None` leaves the provider's built-in wait window in place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct OperationTimeouts {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create: Option<std::time::Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update: Option<std::time::Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete: Option<std::time::Duration>,
+}
+
+impl OperationTimeouts {
+    pub fn is_empty(&self) -> bool {
+        self.create.is_none() && self.update.is_none() && self.delete.is_none()
+    }
 }
 
 /// Source of a resource (root or from a module)
@@ -2548,6 +2622,7 @@ pub(crate) fn assert_value_fully_resolved(
         | Value::Concrete(ConcreteValue::Float(_))
         | Value::Concrete(ConcreteValue::Bool(_))
         | Value::Concrete(ConcreteValue::Duration(_))
+        | Value::Concrete(ConcreteValue::ByteSize(_))
         | Value::Concrete(ConcreteValue::StringList(_)) => Ok(()),
     }
 }
@@ -1340,29 +1340,41 @@ fn canonicalize_keeps_secret_expr_in_interpolation() {
     // A `Secret(_)` inside an `Expr` must NOT be folded into a Literal:
     // doing so would let the secret travel as plain text and bypass
     // redaction in plan display, state serialization, and logging.
+    // Next to an unresolved part, the interpolation stays as written.
     let secret = Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
         ConcreteValue::String("password".into()),
     ))));
     let parts = vec![
         InterpolationPart::Literal("db-".into()),
         InterpolationPart::Expr(secret.clone()),
+        InterpolationPart::Expr(Value::resource_ref("db", "port", vec![])),
     ];
     let v = Value::Deferred(DeferredValue::Interpolation(parts.clone()));
     assert_eq!(
         v.canonicalize(),
         Value::Deferred(DeferredValue::Interpolation(parts))
     );
+}
+
+#[test]
+fn canonicalize_folds_secret_interpolation_into_a_secret_string() {
+    let secret = Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+        ConcreteValue::String("password".into()),
+    ))));
+    let v = Value::Deferred(DeferredValue::Interpolation(vec![
+        InterpolationPart::Literal("db-".into()),
+        InterpolationPart::Expr(secret.clone()),
+    ]));
+    let folded = Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+        ConcreteValue::String("db-password".into()),
+    ))));
+    assert_eq!(v.canonicalize(), folded);
+    assert_eq!(folded.clone().canonicalize(), folded);
 
-    // Even when the Secret is the only Expr part, it must stay wrapped.
     let only_secret = Value::Deferred(DeferredValue::Interpolation(vec![InterpolationPart::Expr(
         secret.clone(),
     )]));
-    assert_eq!(
-        only_secret.canonicalize(),
-        Value::Deferred(DeferredValue::Interpolation(vec![InterpolationPart::Expr(
-            secret
-        )])),
-    );
+    assert_eq!(only_secret.canonicalize(), secret);
 }
 
 #[test]
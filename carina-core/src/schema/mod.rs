@@ -19,10 +19,19 @@ use crate::value::format_value_with_key;
 
 mod resolved_attr_type;
 mod type_identity;
+mod units;
+mod unknown_properties;
+mod validation_error;
 
-pub use carina_provider_protocol::types::DslTransform;
+pub use carina_provider_protocol::cidr::{
+    Cidr, CidrError, cidr_contains, cidr_overlaps, normalize_cidr,
+};
+pub use carina_provider_protocol::types::{DiffSuppressor, DiffSuppressorRule, DslTransform};
 pub use resolved_attr_type::ResolvedAttrType;
 pub use type_identity::TypeIdentity;
+pub use units::{AttributeUnit, BYTE_SIZE_UNITS};
+pub use unknown_properties::UnknownPropertyReport;
+pub use validation_error::ValidationError;
 
 /// Error returned when a bare projection reaches a schema-bound
 /// [`AttrTypeKind::Ref`] without a defs map.
@@ -355,12 +364,26 @@ impl<'a> DslMap<'a> {
                 Some(
                     DslTransform::Identity
                     | DslTransform::StripSuffix(_)
+                    | DslTransform::CanonicalCidr
+                    | DslTransform::CanonicalPolicyDocument
+                    | DslTransform::CanonicalJsonDocument
+                    | DslTransform::Lowercase
                     | DslTransform::Unknown(_),
                 )
                 | None => dsl.to_string(),
             })
     }
 
+    /// Look up `dsl` in the alias table only, returning its
+    /// API-canonical spelling. Unlike [`api_for`], the transform is not
+    /// consulted and a miss returns `None` rather than echoing the input,
+    /// so callers can tell an alias apart from an already-canonical value.
+    pub fn alias_api_for(&self, dsl: &str) -> Option<&'a str> {
+        self.aliases
+            .iter()
+            .find_map(|(a, d)| (d == dsl).then_some(a.as_str()))
+    }
+
     /// True only when the mapping carries no DSL-side rewrite
     /// machinery: no alias-table entries and no transform callback.
     ///
@@ -2710,12 +2733,34 @@ impl AttributeType {
     /// regex-containment proofs through the type system. Loosening here
     /// re-introduces the silent-false-positive class that #2218 closed.
     pub fn is_assignable_to(&self, sink: &AttributeType) -> bool {
+        self.assignable_with(sink, &|a, b| a == b)
+    }
+
+    /// [`is_assignable_to`](Self::is_assignable_to), with the provider
+    /// axis of identified types compared by `registry`'s provider
+    /// families instead of by name. `aws.ec2.Vpc.VpcId` flows into an
+    /// `awscc.ec2.Vpc.VpcId` sink when both providers declare the same
+    /// family; every other axis and rule is unchanged. Use this where a
+    /// reference can cross provider namespaces.
+    pub fn is_assignable_to_in(&self, sink: &AttributeType, registry: &SchemaRegistry) -> bool {
+        self.assignable_with(sink, &|a, b| registry.same_provider_family(a, b))
+    }
+
+    fn assignable_with(
+        &self,
+        sink: &AttributeType,
+        same_provider: &dyn Fn(&str, &str) -> bool,
+    ) -> bool {
         use AttrTypeKind::*;
         if let Union(members) = &sink.kind {
-            return members.iter().any(|m| self.is_assignable_to(m));
+            return members
+                .iter()
+                .any(|m| self.assignable_with(m, same_provider));
         }
         if let Union(members) = &self.kind {
-            return members.iter().all(|m| m.is_assignable_to(sink));
+            return members
+                .iter()
+                .all(|m| m.assignable_with(sink, same_provider));
         }
         match (&self.kind, &sink.kind) {
             (
@@ -2731,9 +2776,12 @@ impl AttributeType {
                     pattern: _,
                     ..
                 },
-            ) => s_id.assignable_to(k_id) && length_contains(s_len.as_ref(), k_len.as_ref()),
+            ) => {
+                s_id.assignable_to_with(k_id, same_provider)
+                    && length_contains(s_len.as_ref(), k_len.as_ref())
+            }
             (Enum { identity: s_id, .. }, Enum { identity: k_id, .. })
-                if !s_id.assignable_to(k_id) =>
+                if !s_id.assignable_to_with(k_id, same_provider) =>
             {
                 false
             }
@@ -2748,7 +2796,10 @@ impl AttributeType {
                     base: k_base,
                     ..
                 },
-            ) => s_id.assignable_to(k_id) && s_base.is_assignable_to(k_base),
+            ) => {
+                s_id.assignable_to_with(k_id, same_provider)
+                    && s_base.assignable_with(k_base, same_provider)
+            }
             // Anonymous source → identified sink has no proof of identity.
             (
                 String { identity: None, .. }
@@ -2795,7 +2846,10 @@ impl AttributeType {
                     range: k_range,
                     ..
                 },
-            ) => s_id.assignable_to(k_id) && i64_range_contains(s_range.as_ref(), k_range.as_ref()),
+            ) => {
+                s_id.assignable_to_with(k_id, same_provider)
+                    && i64_range_contains(s_range.as_ref(), k_range.as_ref())
+            }
             (Int { range: s_range, .. }, Int { range: k_range, .. }) => {
                 i64_range_contains(s_range.as_ref(), k_range.as_ref())
             }
@@ -2810,7 +2864,10 @@ impl AttributeType {
                     range: k_range,
                     ..
                 },
-            ) => s_id.assignable_to(k_id) && f64_range_contains(s_range.as_ref(), k_range.as_ref()),
+            ) => {
+                s_id.assignable_to_with(k_id, same_provider)
+                    && f64_range_contains(s_range.as_ref(), k_range.as_ref())
+            }
             (Float { range: s_range, .. }, Float { range: k_range, .. }) => {
                 f64_range_contains(s_range.as_ref(), k_range.as_ref())
             }
@@ -3301,7 +3358,7 @@ fn format_invalid_enum(
         (None, Some(t)) => format!(" for {}", t),
         (None, None) => String::new(),
     };
-    if qualifier.is_empty() {
+    let message = if qualifier.is_empty() {
         format!(
             "Invalid enum variant '{}', expected one of: {}",
             value, joined
@@ -3311,7 +3368,30 @@ fn format_invalid_enum(
             "Invalid value '{}'{}: expected one of {}",
             value, qualifier, joined
         )
+    };
+    let suggestions = suggest_enum_variants(value, expected);
+    if suggestions.is_empty() {
+        return message;
     }
+    let quoted: Vec<String> = suggestions.iter().map(|v| format!("'{v}'")).collect();
+    format!("{message}; did you mean {}?", quoted.join(" or "))
+}
+
+/// Up to three of `expected` closest to `value` by edit distance on the
+/// variant segment, best first. `value` may be bare (`dedcated`) or
+/// namespaced (`aws.ec2.Instance.Tenancy.dedcated`); only its last
+/// segment is compared. Used for "did you mean" hints on invalid enum
+/// values in CLI errors and LSP diagnostics.
+pub fn suggest_enum_variants<'a>(
+    value: &str,
+    expected: &'a [ExpectedEnumVariant],
+) -> Vec<&'a ExpectedEnumVariant> {
+    let tail = value.rsplit('.').next().unwrap_or(value);
+    let known: Vec<&str> = expected.iter().map(|v| v.value.as_str()).collect();
+    suggest_similar_names(tail, &known, 3)
+        .into_iter()
+        .filter_map(|name| expected.iter().find(|v| v.value.as_str() == name))
+        .collect()
 }
 
 fn format_invalid_value_intro(
@@ -3695,6 +3775,7 @@ impl Value {
             Value::Concrete(ConcreteValue::Float(_)) => "Float".to_string(),
             Value::Concrete(ConcreteValue::Bool(_)) => "Bool".to_string(),
             Value::Concrete(ConcreteValue::Duration(_)) => "Duration".to_string(),
+            Value::Concrete(ConcreteValue::ByteSize(_)) => "ByteSize".to_string(),
             Value::Concrete(ConcreteValue::List(_)) => "List".to_string(),
             Value::Concrete(ConcreteValue::StringList(_)) => "StringList".to_string(),
             Value::Concrete(ConcreteValue::Map(_)) => "Map".to_string(),
@@ -3726,6 +3807,7 @@ impl ConcreteValueRef<'_> {
             ConcreteValueRef::Float(_) => "Float",
             ConcreteValueRef::Bool(_) => "Bool",
             ConcreteValueRef::Duration(_) => "Duration",
+            ConcreteValueRef::ByteSize(_) => "ByteSize",
             ConcreteValueRef::List(_) => "List",
             ConcreteValueRef::StringList(_) => "StringList",
             ConcreteValueRef::Map(_) => "Map",
@@ -3749,6 +3831,7 @@ impl ConcreteValueRef<'_> {
             ConcreteValueRef::Float(f) => Value::Concrete(ConcreteValue::Float(f)),
             ConcreteValueRef::Bool(b) => Value::Concrete(ConcreteValue::Bool(b)),
             ConcreteValueRef::Duration(d) => Value::Concrete(ConcreteValue::Duration(d)),
+            ConcreteValueRef::ByteSize(n) => Value::Concrete(ConcreteValue::ByteSize(n)),
             ConcreteValueRef::List(items) => Value::Concrete(ConcreteValue::List(items.to_vec())),
             ConcreteValueRef::StringList(items) => {
                 Value::Concrete(ConcreteValue::StringList(items.to_vec()))
@@ -3803,6 +3886,63 @@ pub mod validators {
             }]),
         }
     }
+
+    /// Validate that every field in `requires` is present whenever
+    /// `attribute` is present. Vacuously `Ok(())` when `attribute` is absent.
+    pub fn validate_required_with(
+        attributes: &HashMap<String, Value>,
+        attribute: &str,
+        requires: &[&str],
+    ) -> Result<(), Vec<TypeError>> {
+        if !attributes.contains_key(attribute) {
+            return Ok(());
+        }
+        let missing: Vec<&str> = requires
+            .iter()
+            .filter(|&&name| !attributes.contains_key(name))
+            .copied()
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(vec![TypeError::ResourceValidationFailed {
+            message: format!(
+                "'{}' requires [{}] to also be specified, but missing: {}",
+                attribute,
+                requires.join(", "),
+                missing.join(", ")
+            ),
+            attribute: Some(attribute.to_string()),
+        }])
+    }
+
+    /// Validate that none of the fields in `conflicts` is present whenever
+    /// `attribute` is present.
+    pub fn validate_conflicts_with(
+        attributes: &HashMap<String, Value>,
+        attribute: &str,
+        conflicts: &[&str],
+    ) -> Result<(), Vec<TypeError>> {
+        if !attributes.contains_key(attribute) {
+            return Ok(());
+        }
+        let present: Vec<&str> = conflicts
+            .iter()
+            .filter(|&&name| attributes.contains_key(name))
+            .copied()
+            .collect();
+        if present.is_empty() {
+            return Ok(());
+        }
+        Err(vec![TypeError::ResourceValidationFailed {
+            message: format!(
+                "'{}' cannot be specified together with: {}",
+                attribute,
+                present.join(", ")
+            ),
+            attribute: Some(attribute.to_string()),
+        }])
+    }
 }
 
 /// Completion value for LSP completions
@@ -3873,6 +4013,15 @@ pub struct AttributeSchema {
     /// attribute is not necessarily deferred-populate (it may be
     /// populated synchronously, e.g. an ARN echoed back by Create).
     pub deferred_populate: bool,
+    /// Unit of an integer attribute at the provider API. When set, the
+    /// attribute also accepts a duration or byte-size literal, converted
+    /// to this unit before validation.
+    pub unit: Option<AttributeUnit>,
+    /// Field that identifies an element of a `List<Struct>` attribute
+    /// (e.g. a rule's `id`). Plan diffs pair old and new elements by
+    /// this field instead of by position or similarity, so inserting
+    /// one element does not show every later element as changed.
+    pub list_key: Option<String>,
 }
 
 impl AttributeSchema {
@@ -3892,6 +4041,8 @@ impl AttributeSchema {
             write_only: false,
             identity: false,
             deferred_populate: false,
+            unit: None,
+            list_key: None,
         }
     }
 
@@ -3927,6 +4078,32 @@ impl AttributeSchema {
         self
     }
 
+    pub fn with_unit(mut self, unit: AttributeUnit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn with_list_key(mut self, key: impl Into<String>) -> Self {
+        self.list_key = Some(key.into());
+        self
+    }
+
+    /// The value of this attribute's list key in `item`, when the
+    /// attribute declares one and `item` is a map carrying it.
+    pub fn element_key<'a>(&self, item: &'a Value) -> Option<&'a Value> {
+        list_element_key(self.list_key.as_deref(), item)
+    }
+
+    /// Convert a duration or byte-size literal to this attribute's unit.
+    /// Returns the value unchanged when the attribute has no unit or the
+    /// value is not a unit literal.
+    pub fn convert_unit_literal(&self, value: &Value) -> Result<Option<Value>, TypeError> {
+        match self.unit {
+            Some(unit) => unit.convert(value),
+            None => Ok(None),
+        }
+    }
+
     pub fn removable(mut self) -> Self {
         self.removable = Some(true);
         self
@@ -4013,6 +4190,15 @@ pub(crate) fn struct_fields_with_defs<'a>(
     }
 }
 
+/// Look up a list element's `key` field; `None` when no key is given
+/// or the element is not a map carrying it.
+pub(crate) fn list_element_key<'a>(key: Option<&str>, item: &'a Value) -> Option<&'a Value> {
+    match item {
+        Value::Concrete(ConcreteValue::Map(map)) => map.get(key?),
+        _ => None,
+    }
+}
+
 pub(crate) fn union_members_with_defs<'a>(
     attr_type: &'a AttributeType,
     defs: &'a std::collections::BTreeMap<String, AttributeType>,
@@ -4051,6 +4237,89 @@ pub enum UniqueNameSpec {
     Conflicting,
 }
 
+/// Declarative cross-attribute rule on a [`ResourceSchema`].
+///
+/// Plain data rather than a closure (cf. `ResourceSchema::validator`), so
+/// it survives the WASM plugin boundary. The first three variants map
+/// onto CloudFormation schema constructs: `oneOf` of required properties,
+/// `dependencies`, and mutually exclusive properties respectively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeConstraint {
+    /// Exactly one of the attributes must be specified.
+    ExactlyOneOf(Vec<String>),
+    /// When `attribute` is specified, every attribute in `requires` must be too.
+    RequiredWith {
+        attribute: String,
+        requires: Vec<String>,
+    },
+    /// When `attribute` is specified, none of `conflicts` may be.
+    ConflictsWith {
+        attribute: String,
+        conflicts: Vec<String>,
+    },
+    /// The CIDR block in `attribute` should lie within
+    /// `referenced_attribute` of the resource `reference` points at. Spans
+    /// two resources, so `check` passes it; plan time evaluates it through
+    /// [`crate::validation::reference_constraints`] and warns.
+    CidrWithinReference {
+        attribute: String,
+        reference: String,
+        referenced_attribute: String,
+    },
+}
+
+impl AttributeConstraint {
+    /// Evaluate this rule against a resource's attributes. Presence is
+    /// key-based: an attribute bound to a not-yet-known value still
+    /// counts as specified.
+    pub fn check(&self, attributes: &HashMap<String, Value>) -> Result<(), Vec<TypeError>> {
+        fn refs(names: &[String]) -> Vec<&str> {
+            names.iter().map(String::as_str).collect()
+        }
+        match self {
+            AttributeConstraint::ExactlyOneOf(group) => {
+                validators::validate_exclusive_required(attributes, &refs(group))
+            }
+            AttributeConstraint::RequiredWith {
+                attribute,
+                requires,
+            } => validators::validate_required_with(attributes, attribute, &refs(requires)),
+            AttributeConstraint::ConflictsWith {
+                attribute,
+                conflicts,
+            } => validators::validate_conflicts_with(attributes, attribute, &refs(conflicts)),
+            AttributeConstraint::CidrWithinReference { .. } => Ok(()),
+        }
+    }
+
+    /// Whether a schema default for the unset `attribute` must not be
+    /// materialized, given the attributes for which `is_present` returns
+    /// true. A default would either break a group the user satisfied
+    /// with a different member, for an `exactly_one_of` group the user
+    /// left empty make the choice for them and hide the error, or turn
+    /// on a `required_with` trigger whose partners the user never wrote.
+    pub fn excludes(&self, attribute: &str, is_present: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            AttributeConstraint::ExactlyOneOf(group) => group.iter().any(|a| a == attribute),
+            AttributeConstraint::RequiredWith {
+                attribute: trigger,
+                requires,
+            } => trigger == attribute && !requires.iter().all(|r| is_present(r)),
+            AttributeConstraint::CidrWithinReference { .. } => false,
+            AttributeConstraint::ConflictsWith {
+                attribute: trigger,
+                conflicts,
+            } => {
+                if trigger == attribute {
+                    conflicts.iter().any(|c| is_present(c))
+                } else {
+                    conflicts.iter().any(|c| c == attribute) && is_present(trigger)
+                }
+            }
+        }
+    }
+}
+
 /// Resource schema
 #[derive(Debug, Clone)]
 pub struct ResourceSchema {
@@ -4068,10 +4337,14 @@ pub struct ResourceSchema {
     /// Per-resource operational config (timeouts, retries).
     /// When None, provider defaults are used.
     pub operation_config: Option<OperationConfig>,
-    /// Declarative "exactly one of" groups. Each inner vec is a group of
-    /// attribute names where exactly one must be specified. Unlike `validator`
-    /// (a function pointer), this is plain data and survives the WASM plugin
-    /// boundary.
+    /// Declarative cross-attribute rules, evaluated in declaration order
+    /// by `validate()`. Unlike `validator` (a function pointer), these are
+    /// plain data and survive the WASM plugin boundary.
+    pub constraints: Vec<AttributeConstraint>,
+    /// Former home of the exactly-one-of groups. Groups set here are
+    /// still enforced, after `constraints`, as
+    /// [`AttributeConstraint::ExactlyOneOf`].
+    #[deprecated(note = "push `AttributeConstraint::ExactlyOneOf` to `constraints` instead")]
     pub exclusive_required: Vec<Vec<String>>,
     /// Default total timeout for `wait <target> { ... }` polling against
     /// this resource type. `None` falls back to
@@ -4081,6 +4354,10 @@ pub struct ResourceSchema {
     /// against this resource type. `None` falls back to
     /// [`WAIT_DEFAULT_INTERVAL`].
     pub default_wait_interval: Option<std::time::Duration>,
+    /// Attribute-path rules for differences the differ should ignore.
+    /// Plain data, so providers declare them across the WASM boundary;
+    /// applied by the differ before comparing each attribute.
+    pub diff_suppressors: Vec<DiffSuppressorRule>,
     /// Named definitions reachable via [`AttrTypeKind::Ref`] from this
     /// resource's attribute types. Empty for resources whose attribute
     /// graph contains no cycles (the common case).
@@ -4107,6 +4384,7 @@ pub const WAIT_DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_
 pub const WAIT_DEFAULT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
 
 impl ResourceSchema {
+    #[allow(deprecated)]
     pub fn new(resource_type: impl Into<String>) -> Self {
         Self {
             resource_type: resource_type.into(),
@@ -4116,13 +4394,38 @@ impl ResourceSchema {
             kind: SchemaKind::Resource,
             unique_name: UniqueNameSpec::Conflicting,
             operation_config: None,
+            constraints: Vec::new(),
             exclusive_required: Vec::new(),
             default_wait_timeout: None,
             default_wait_interval: None,
+            diff_suppressors: Vec::new(),
             defs: std::collections::BTreeMap::new(),
         }
     }
 
+    /// Register a diff suppressor at `path` (see [`DiffSuppressorRule`]).
+    pub fn with_diff_suppressor(
+        mut self,
+        path: impl Into<String>,
+        suppressor: DiffSuppressor,
+    ) -> Self {
+        self.diff_suppressors.push(DiffSuppressorRule {
+            path: path.into(),
+            suppressor,
+        });
+        self
+    }
+
+    /// Suppressor rules whose path starts at top-level attribute `attr`.
+    pub fn diff_suppressors_for<'a>(
+        &'a self,
+        attr: &'a str,
+    ) -> impl Iterator<Item = &'a DiffSuppressorRule> + 'a {
+        self.diff_suppressors
+            .iter()
+            .filter(move |rule| rule.path.split('.').next() == Some(attr))
+    }
+
     /// Attach a named definition reachable via [`AttrTypeKind::Ref`].
     ///
     /// Used by codegen to register cyclic CFN struct definitions
@@ -4203,6 +4506,49 @@ impl ResourceSchema {
         out
     }
 
+    /// Map a DSL alias of an enum attribute back to its API-canonical
+    /// value using the enum's own alias table (e.g. `ip_protocol = all`
+    /// → `"-1"`). Looks through lists, maps and unions the same way
+    /// [`Self::enum_valid_values_for_attr_alias`] does. Returns `None`
+    /// when `value` is not an alias, including when it is already
+    /// canonical.
+    pub fn enum_alias_reverse(&self, attr_name: &str, value: &str) -> Option<String> {
+        let attr_schema = self.attributes.get(attr_name)?;
+        self.enum_alias_reverse_in_attr_type(&attr_schema.attr_type, value)
+    }
+
+    fn enum_alias_reverse_in_attr_type(
+        &self,
+        attr_type: &AttributeType,
+        value: &str,
+    ) -> Option<String> {
+        match self.shape_of(attr_type) {
+            Shape::Enum {
+                dsl_aliases,
+                to_dsl,
+                ..
+            } => DslMap::new(dsl_aliases, to_dsl)
+                .alias_api_for(value)
+                .map(str::to_string),
+            Shape::List {
+                element_type: inner,
+                ..
+            } => self.enum_alias_reverse_in_attr_type(inner, value),
+            Shape::Map { value: inner, .. } => self.enum_alias_reverse_in_attr_type(inner, value),
+            Shape::Union => self.union_members_of(attr_type).and_then(|members| {
+                members
+                    .iter()
+                    .find_map(|member| self.enum_alias_reverse_in_attr_type(member, value))
+            }),
+            Shape::String { .. }
+            | Shape::Int { .. }
+            | Shape::Float { .. }
+            | Shape::Bool
+            | Shape::Duration
+            | Shape::Struct { .. } => None,
+        }
+    }
+
     fn append_enum_values_from_top_level_attr_type(
         &self,
         attr_type: &AttributeType,
@@ -4271,9 +4617,55 @@ impl ResourceSchema {
     ///
     /// Multiple calls append additional groups; each group is evaluated
     /// independently by `validate()`.
-    pub fn exclusive_required(mut self, fields: &[&str]) -> Self {
-        self.exclusive_required
-            .push(fields.iter().map(|s| s.to_string()).collect());
+    pub fn exactly_one_of(mut self, fields: &[&str]) -> Self {
+        self.constraints.push(AttributeConstraint::ExactlyOneOf(
+            fields.iter().map(|s| s.to_string()).collect(),
+        ));
+        self
+    }
+
+    /// Former name of [`ResourceSchema::exactly_one_of`], kept so
+    /// generated provider schemas keep building.
+    #[deprecated(note = "use `exactly_one_of`")]
+    pub fn exclusive_required(self, fields: &[&str]) -> Self {
+        self.exactly_one_of(fields)
+    }
+
+    /// Declare that specifying `attribute` requires every attribute in
+    /// `requires` to be specified too (CloudFormation `dependencies`).
+    pub fn required_with(mut self, attribute: &str, requires: &[&str]) -> Self {
+        self.constraints.push(AttributeConstraint::RequiredWith {
+            attribute: attribute.to_string(),
+            requires: requires.iter().map(|s| s.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Declare that `attribute` cannot be specified together with any
+    /// attribute in `conflicts`.
+    pub fn conflicts_with(mut self, attribute: &str, conflicts: &[&str]) -> Self {
+        self.constraints.push(AttributeConstraint::ConflictsWith {
+            attribute: attribute.to_string(),
+            conflicts: conflicts.iter().map(|s| s.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Declare that the CIDR block in `attribute` must lie within
+    /// `referenced_attribute` of the resource `reference` points at —
+    /// e.g. a subnet's `cidr_block` inside its VPC's `cidr_block`.
+    pub fn cidr_within_reference(
+        mut self,
+        attribute: &str,
+        reference: &str,
+        referenced_attribute: &str,
+    ) -> Self {
+        self.constraints
+            .push(AttributeConstraint::CidrWithinReference {
+                attribute: attribute.to_string(),
+                reference: reference.to_string(),
+                referenced_attribute: referenced_attribute.to_string(),
+            });
         self
     }
 
@@ -4339,6 +4731,40 @@ impl ResourceSchema {
             .collect()
     }
 
+    /// `constraints` followed by the groups still set through the
+    /// deprecated `exclusive_required` field.
+    #[allow(deprecated)]
+    fn effective_constraints(&self) -> std::borrow::Cow<'_, [AttributeConstraint]> {
+        if self.exclusive_required.is_empty() {
+            return std::borrow::Cow::Borrowed(&self.constraints);
+        }
+        let legacy = self
+            .exclusive_required
+            .iter()
+            .cloned()
+            .map(AttributeConstraint::ExactlyOneOf);
+        std::borrow::Cow::Owned(self.constraints.iter().cloned().chain(legacy).collect())
+    }
+
+    /// Schema defaults to materialize into a desired resource: attributes
+    /// with a default that the user did not specify and that no
+    /// declared constraint excludes, sorted by name.
+    pub fn missing_defaults(&self, is_present: &dyn Fn(&str) -> bool) -> Vec<(&str, &Value)> {
+        let mut defaults: Vec<(&str, &Value)> = self
+            .default_value_attributes()
+            .into_iter()
+            .filter(|(name, _)| !is_present(name))
+            .filter(|(name, _)| {
+                !self
+                    .effective_constraints()
+                    .iter()
+                    .any(|c| c.excludes(name, is_present))
+            })
+            .collect();
+        defaults.sort_by_key(|(name, _)| *name);
+        defaults
+    }
+
     /// Returns default-value attributes not specified by the user, sorted by name.
     /// Each entry is (attribute_name, formatted_default_value).
     pub fn compute_default_attrs(&self, user_keys: &HashSet<&str>) -> Vec<(String, String)> {
@@ -4501,6 +4927,14 @@ impl ResourceSchema {
             let canonical = bn_map.get(name).map(|s| s.as_str()).unwrap_or(name);
 
             if let Some(schema) = self.attributes.get(canonical) {
+                let converted = match schema.convert_unit_literal(value) {
+                    Ok(converted) => converted,
+                    Err(e) => {
+                        errors.push(e.with_attribute(name));
+                        continue;
+                    }
+                };
+                let value = converted.as_ref().unwrap_or(value);
                 if let Err(e) = schema_view.validate_attr(&schema.attr_type, value) {
                     // Tag the error with the attribute name the user actually
                     // wrote (which may be a block-name alias), so diagnostics
@@ -4530,10 +4964,9 @@ impl ResourceSchema {
             }
         }
 
-        // Evaluate declarative exclusive-required groups (WASM-safe).
-        for group in &self.exclusive_required {
-            let refs: Vec<&str> = group.iter().map(|s| s.as_str()).collect();
-            if let Err(mut e) = validators::validate_exclusive_required(attributes, &refs) {
+        // Evaluate declarative cross-attribute constraints (WASM-safe).
+        for constraint in self.effective_constraints().iter() {
+            if let Err(mut e) = constraint.check(attributes) {
                 errors.append(&mut e);
             }
         }
@@ -4837,7 +5270,8 @@ pub fn resolve_block_names(
 }
 
 /// Provider-agnostic types only. AWS-specific types (arn, aws_resource_id,
-/// availability_zone, etc.) belong in provider crates.
+/// availability_zone, etc.) belong in provider crates; the exception is
+/// `iam_policy_document`, whose canonical form the host must know.
 /// See carina-provider-awscc/src/schemas/generated/mod.rs for AWS types.
 pub mod types {
     use super::*;
@@ -4861,7 +5295,8 @@ pub mod types {
         )
     }
 
-    /// IPv4 CIDR block type (e.g., "10.0.0.0/16")
+    /// IPv4 CIDR block type (e.g., "10.0.0.0/16"). Compared in canonical
+    /// form, so a read-back value only differs if it names another block.
     pub fn ipv4_cidr() -> AttributeType {
         AttributeType::refined_string_with_validator(
             Some(TypeIdentity::bare("Ipv4Cidr")),
@@ -4869,12 +5304,12 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_ipv4_cidr(s)
+                    check_ipv4_cidr(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
             }),
-            None,
+            Some(DslTransform::CanonicalCidr),
         )
     }
 
@@ -4886,7 +5321,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_ipv4_address(s)
+                    check_ipv4_address(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -4903,7 +5338,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_ipv6_address(s)
+                    check_ipv6_address(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -4912,7 +5347,8 @@ pub mod types {
         )
     }
 
-    /// IPv6 CIDR block type (e.g., "2001:db8::/32", "::/0")
+    /// IPv6 CIDR block type (e.g., "2001:db8::/32", "::/0"). Compared in
+    /// canonical form, so `2001:DB8:0::/32` matches `2001:db8::/32`.
     pub fn ipv6_cidr() -> AttributeType {
         AttributeType::refined_string_with_validator(
             Some(TypeIdentity::bare("Ipv6Cidr")),
@@ -4920,12 +5356,36 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_ipv6_cidr(s)
+                    check_ipv6_cidr(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
             }),
+            Some(DslTransform::CanonicalCidr),
+        )
+    }
+
+    /// IAM policy document, a JSON string. Compared in canonical form
+    /// (see [`carina_provider_protocol::policy::canonical_policy_document`]),
+    /// so the reformatting AWS applies to stored policies is not drift.
+    /// Shared by the `aws` and `awscc` generated schemas.
+    pub fn iam_policy_document() -> AttributeType {
+        AttributeType::refined_string_with_validator(
+            Some(TypeIdentity::bare("IamPolicyDocument")),
+            None,
             None,
+            legacy_validator(|value| {
+                if let Value::Concrete(ConcreteValue::String(s)) = value {
+                    match serde_json::from_str::<serde_json::Value>(s) {
+                        Ok(serde_json::Value::Object(_)) => Ok(()),
+                        Ok(_) => Err("Policy document must be a JSON object".to_string()),
+                        Err(e) => Err(format!("Policy document is not valid JSON: {e}")),
+                    }
+                } else {
+                    Err("Expected string".to_string())
+                }
+            }),
+            Some(DslTransform::CanonicalPolicyDocument),
         )
     }
 
@@ -4946,7 +5406,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_email(s)
+                    check_email(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -4968,7 +5428,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_http_response_status_code(s)
+                    check_http_response_status_code(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -4980,10 +5440,10 @@ pub mod types {
     /// ELBv2 redirect protocol restricted to `HTTP`, `HTTPS`, or `#{protocol}`.
     ///
     /// ```
-    /// # use carina_core::schema::validate_redirect_protocol;
-    /// assert!(validate_redirect_protocol("HTTPS").is_ok());
-    /// assert!(validate_redirect_protocol("#{protocol}").is_ok());
-    /// assert!(validate_redirect_protocol("#{path}").is_err());
+    /// # use carina_core::schema::check_redirect_protocol;
+    /// assert!(check_redirect_protocol("HTTPS").is_ok());
+    /// assert!(check_redirect_protocol("#{protocol}").is_ok());
+    /// assert!(check_redirect_protocol("#{path}").is_err());
     /// ```
     pub fn redirect_protocol() -> AttributeType {
         AttributeType::refined_string_with_validator(
@@ -4992,7 +5452,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_redirect_protocol(s)
+                    check_redirect_protocol(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -5004,9 +5464,9 @@ pub mod types {
     /// ELBv2 redirect host, including wildcards and `#{host}`.
     ///
     /// ```
-    /// # use carina_core::schema::validate_redirect_host;
-    /// assert!(validate_redirect_host("example.com").is_ok());
-    /// assert!(validate_redirect_host("#{path}").is_err());
+    /// # use carina_core::schema::check_redirect_host;
+    /// assert!(check_redirect_host("example.com").is_ok());
+    /// assert!(check_redirect_host("#{path}").is_err());
     /// ```
     pub fn redirect_host() -> AttributeType {
         AttributeType::refined_string_with_validator(
@@ -5015,7 +5475,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_redirect_host(s)
+                    check_redirect_host(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -5027,9 +5487,9 @@ pub mod types {
     /// ELBv2 redirect port restricted to `1..=65535` or `#{port}`.
     ///
     /// ```
-    /// # use carina_core::schema::validate_redirect_port;
-    /// assert!(validate_redirect_port("443").is_ok());
-    /// assert!(validate_redirect_port("#{path}").is_err());
+    /// # use carina_core::schema::check_redirect_port;
+    /// assert!(check_redirect_port("443").is_ok());
+    /// assert!(check_redirect_port("#{path}").is_err());
     /// ```
     pub fn redirect_port() -> AttributeType {
         AttributeType::refined_string_with_validator(
@@ -5038,7 +5498,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_redirect_port(s)
+                    check_redirect_port(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -5050,9 +5510,9 @@ pub mod types {
     /// ELBv2 redirect path, including wildcards and ELBv2 redirect placeholders.
     ///
     /// ```
-    /// # use carina_core::schema::validate_redirect_path;
-    /// assert!(validate_redirect_path("/#{host}/#{path}").is_ok());
-    /// assert!(validate_redirect_path("relative").is_err());
+    /// # use carina_core::schema::check_redirect_path;
+    /// assert!(check_redirect_path("/#{host}/#{path}").is_ok());
+    /// assert!(check_redirect_path("relative").is_err());
     /// ```
     pub fn redirect_path() -> AttributeType {
         AttributeType::refined_string_with_validator(
@@ -5061,7 +5521,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_redirect_path(s)
+                    check_redirect_path(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -5073,9 +5533,9 @@ pub mod types {
     /// ELBv2 redirect query string, including ELBv2 redirect placeholders.
     ///
     /// ```
-    /// # use carina_core::schema::validate_redirect_query;
-    /// assert!(validate_redirect_query("next=#{path}&q=#{query}").is_ok());
-    /// assert!(validate_redirect_query(&"a".repeat(129)).is_err());
+    /// # use carina_core::schema::check_redirect_query;
+    /// assert!(check_redirect_query("next=#{path}&q=#{query}").is_ok());
+    /// assert!(check_redirect_query(&"a".repeat(129)).is_err());
     /// ```
     pub fn redirect_query() -> AttributeType {
         AttributeType::refined_string_with_validator(
@@ -5084,7 +5544,7 @@ pub mod types {
             None,
             legacy_validator(|value| {
                 if let Value::Concrete(ConcreteValue::String(s)) = value {
-                    validate_redirect_query(s)
+                    check_redirect_query(s).map_err(String::from)
                 } else {
                     Err("Expected string".to_string())
                 }
@@ -5095,21 +5555,23 @@ pub mod types {
 }
 
 /// Validate an IPv4 address (e.g., "10.0.1.5", "192.168.0.1")
-pub fn validate_ipv4_address(ip: &str) -> Result<(), String> {
+pub fn check_ipv4_address(ip: &str) -> Result<(), ValidationError> {
     let octets: Vec<&str> = ip.split('.').collect();
     if octets.len() != 4 {
-        return Err(format!("Invalid IPv4 address '{}': expected 4 octets", ip));
+        return Err(ValidationError::new(
+            "IPv4 address",
+            ip,
+            "expected 4 octets",
+        ));
     }
 
     for octet in &octets {
-        match octet.parse::<u8>() {
-            Ok(_) => {}
-            Err(_) => {
-                return Err(format!(
-                    "Invalid octet '{}' in IPv4 address: must be 0-255",
-                    octet
-                ));
-            }
+        if octet.parse::<u8>().is_err() {
+            return Err(ValidationError::new(
+                "octet",
+                *octet,
+                format!("must be 0-255 in IPv4 address '{ip}'"),
+            ));
         }
     }
 
@@ -5117,12 +5579,13 @@ pub fn validate_ipv4_address(ip: &str) -> Result<(), String> {
 }
 
 /// Validate IPv4 CIDR block format (e.g., "10.0.0.0/16")
-pub fn validate_ipv4_cidr(cidr: &str) -> Result<(), String> {
+pub fn check_ipv4_cidr(cidr: &str) -> Result<(), ValidationError> {
     let parts: Vec<&str> = cidr.split('/').collect();
     if parts.len() != 2 {
-        return Err(format!(
-            "Invalid CIDR format '{}': expected IP/prefix",
-            cidr
+        return Err(ValidationError::new(
+            "CIDR format",
+            cidr,
+            "expected IP/prefix",
         ));
     }
 
@@ -5130,26 +5593,38 @@ pub fn validate_ipv4_cidr(cidr: &str) -> Result<(), String> {
     let prefix = parts[1];
 
     // Validate IP address
-    validate_ipv4_address(ip)?;
+    check_ipv4_address(ip)?;
 
     // Validate prefix length
     match prefix.parse::<u8>() {
-        Ok(p) if p <= 32 => Ok(()),
-        Ok(p) => Err(format!("Invalid prefix length '{}': must be 0-32", p)),
-        Err(_) => Err(format!(
-            "Invalid prefix length '{}': must be a number",
-            prefix
-        )),
+        Ok(p) if p <= 32 => {}
+        Ok(_) => {
+            return Err(ValidationError::new(
+                "prefix length",
+                prefix,
+                "must be 0-32",
+            ));
+        }
+        Err(_) => {
+            return Err(ValidationError::new(
+                "prefix length",
+                prefix,
+                "must be a number",
+            ));
+        }
     }
+
+    reject_host_bits(cidr)
 }
 
 /// Validate IPv6 CIDR block format (e.g., "2001:db8::/32", "::/0")
-pub fn validate_ipv6_cidr(cidr: &str) -> Result<(), String> {
+pub fn check_ipv6_cidr(cidr: &str) -> Result<(), ValidationError> {
     let parts: Vec<&str> = cidr.split('/').collect();
     if parts.len() != 2 {
-        return Err(format!(
-            "Invalid IPv6 CIDR format '{}': expected address/prefix",
-            cidr
+        return Err(ValidationError::new(
+            "IPv6 CIDR format",
+            cidr,
+            "expected address/prefix",
         ));
     }
 
@@ -5157,38 +5632,62 @@ pub fn validate_ipv6_cidr(cidr: &str) -> Result<(), String> {
     let prefix = parts[1];
 
     // Validate IPv6 address
-    validate_ipv6_address(addr)?;
+    check_ipv6_address(addr)?;
 
     // Validate prefix length (0-128)
     match prefix.parse::<u8>() {
-        Ok(p) if p <= 128 => Ok(()),
-        Ok(p) => Err(format!("Invalid IPv6 prefix length '{}': must be 0-128", p)),
-        Err(_) => Err(format!(
-            "Invalid IPv6 prefix length '{}': must be a number",
-            prefix
-        )),
+        Ok(p) if p <= 128 => {}
+        Ok(_) => {
+            return Err(ValidationError::new(
+                "IPv6 prefix length",
+                prefix,
+                "must be 0-128",
+            ));
+        }
+        Err(_) => {
+            return Err(ValidationError::new(
+                "IPv6 prefix length",
+                prefix,
+                "must be a number",
+            ));
+        }
+    }
+
+    reject_host_bits(cidr)
+}
+
+/// Reject a syntactically valid CIDR whose address has bits set past the
+/// prefix (`10.0.0.5/16`), suggesting the network address instead.
+fn reject_host_bits(cidr: &str) -> Result<(), ValidationError> {
+    match Cidr::parse(cidr) {
+        Err(CidrError::HostBitsSet { network }) => {
+            Err(
+                ValidationError::new("CIDR block", cidr, "host bits are set")
+                    .with_suggestion(network),
+            )
+        }
+        _ => Ok(()),
     }
 }
 
 /// Validate an IPv6 address (supports `::` shorthand)
-pub fn validate_ipv6_address(addr: &str) -> Result<(), String> {
+pub fn check_ipv6_address(addr: &str) -> Result<(), ValidationError> {
+    let invalid = |reason: &str| ValidationError::new("IPv6 address", addr, reason);
+
     if addr.is_empty() {
-        return Err("Empty IPv6 address".to_string());
+        return Err(invalid("must not be empty"));
     }
 
     // Handle :: shorthand
     if addr.contains("::") {
         let halves: Vec<&str> = addr.splitn(2, "::").collect();
         if halves.len() != 2 {
-            return Err(format!("Invalid IPv6 address '{}': malformed '::'", addr));
+            return Err(invalid("malformed '::'"));
         }
 
         // Check for multiple ::
         if halves[1].contains("::") {
-            return Err(format!(
-                "Invalid IPv6 address '{}': only one '::' allowed",
-                addr
-            ));
+            return Err(invalid("only one '::' allowed"));
         }
 
         let left_groups: Vec<&str> = if halves[0].is_empty() {
@@ -5204,10 +5703,7 @@ pub fn validate_ipv6_address(addr: &str) -> Result<(), String> {
 
         let total = left_groups.len() + right_groups.len();
         if total > 7 {
-            return Err(format!(
-                "Invalid IPv6 address '{}': too many groups with '::'",
-                addr
-            ));
+            return Err(invalid("too many groups with '::'"));
         }
 
         for group in left_groups.iter().chain(right_groups.iter()) {
@@ -5216,11 +5712,7 @@ pub fn validate_ipv6_address(addr: &str) -> Result<(), String> {
     } else {
         let groups: Vec<&str> = addr.split(':').collect();
         if groups.len() != 8 {
-            return Err(format!(
-                "Invalid IPv6 address '{}': expected 8 groups, got {}",
-                addr,
-                groups.len()
-            ));
+            return Err(invalid(&format!("expected 8 groups, got {}", groups.len())));
         }
         for group in &groups {
             validate_ipv6_group(group, addr)?;
@@ -5241,51 +5733,41 @@ pub fn validate_ipv6_address(addr: &str) -> Result<(), String> {
 ///
 /// This is intentionally not a full RFC 5322 parser; it catches the common
 /// formatting mistakes without rejecting unusual-but-valid addresses.
-pub fn validate_email(email: &str) -> Result<(), String> {
+pub fn check_email(email: &str) -> Result<(), ValidationError> {
+    let invalid = |reason: &str| ValidationError::new("email", email, reason);
+
     if email.is_empty() {
-        return Err("Empty email address".to_string());
+        return Err(invalid("must not be empty"));
     }
 
     let parts: Vec<&str> = email.split('@').collect();
     if parts.len() != 2 {
-        return Err(format!(
-            "Invalid email '{}': expected exactly one '@'",
-            email
-        ));
+        return Err(invalid("expected exactly one '@'"));
     }
 
     let local = parts[0];
     let domain = parts[1];
 
     if local.is_empty() {
-        return Err(format!("Invalid email '{}': empty local part", email));
+        return Err(invalid("empty local part"));
     }
     if local.chars().any(char::is_whitespace) {
-        return Err(format!(
-            "Invalid email '{}': local part contains whitespace",
-            email
-        ));
+        return Err(invalid("local part contains whitespace"));
     }
 
     if domain.is_empty() {
-        return Err(format!("Invalid email '{}': empty domain", email));
+        return Err(invalid("empty domain"));
     }
     if !domain.contains('.') {
-        return Err(format!(
-            "Invalid email '{}': domain must contain at least one dot",
-            email
-        ));
+        return Err(invalid("domain must contain at least one dot"));
     }
 
     for label in domain.split('.') {
         if label.is_empty() {
-            return Err(format!("Invalid email '{}': domain has empty label", email));
+            return Err(invalid("domain has empty label"));
         }
         if label.chars().any(char::is_whitespace) {
-            return Err(format!(
-                "Invalid email '{}': domain label contains whitespace",
-                email
-            ));
+            return Err(invalid("domain label contains whitespace"));
         }
     }
 
@@ -5293,72 +5775,87 @@ pub fn validate_email(email: &str) -> Result<(), String> {
 }
 
 /// Validate a 3-digit HTTP response status code restricted to 2XX/4XX/5XX.
-pub fn validate_http_response_status_code(code: &str) -> Result<(), String> {
+pub fn check_http_response_status_code(code: &str) -> Result<(), ValidationError> {
+    let invalid = |reason: String| ValidationError::new("HTTP response status code", code, reason);
+
     if code.len() != 3 {
-        return Err(format!(
+        return Err(invalid(format!(
             "must be exactly 3 digits, got {} characters",
             code.len()
-        ));
+        )));
     }
     if !code.chars().all(|c| c.is_ascii_digit()) {
-        return Err("must contain only digits".to_string());
+        return Err(invalid("must contain only digits".to_string()));
     }
     let first = code.chars().next().expect("length checked above");
     if !matches!(first, '2' | '4' | '5') {
-        return Err(format!("must start with 2, 4, or 5 (got '{first}')"));
+        return Err(invalid(format!(
+            "must start with 2, 4, or 5 (got '{first}')"
+        )));
     }
     Ok(())
 }
 
 /// Validate an ELBv2 redirect protocol value.
-pub fn validate_redirect_protocol(protocol: &str) -> Result<(), String> {
+pub fn check_redirect_protocol(protocol: &str) -> Result<(), ValidationError> {
     if matches!(protocol, "HTTP" | "HTTPS" | "#{protocol}") {
         return Ok(());
     }
-    Err(format!(
-        "Invalid redirect protocol '{protocol}': expected HTTP, HTTPS, or #{{protocol}}"
-    ))
+    let err = ValidationError::new(
+        "redirect protocol",
+        protocol,
+        "expected HTTP, HTTPS, or #{protocol}",
+    );
+    let upper = protocol.to_ascii_uppercase();
+    Err(if matches!(upper.as_str(), "HTTP" | "HTTPS") {
+        err.with_suggestion(upper)
+    } else {
+        err
+    })
 }
 
 /// Validate an ELBv2 redirect host value.
-pub fn validate_redirect_host(host: &str) -> Result<(), String> {
+pub fn check_redirect_host(host: &str) -> Result<(), ValidationError> {
     validate_char_length(host, 1, 128, "redirect host")?;
     validate_known_placeholders(host, &["#{host}"], "redirect host")
 }
 
 /// Validate an ELBv2 redirect port value.
-pub fn validate_redirect_port(port: &str) -> Result<(), String> {
+pub fn check_redirect_port(port: &str) -> Result<(), ValidationError> {
     if port == "#{port}" {
         return Ok(());
     }
+    let invalid = |reason: String| ValidationError::new("redirect port", port, reason);
     if port.starts_with('+') || (port.len() > 1 && port.starts_with('0')) {
-        return Err(format!(
-            "Invalid redirect port '{port}': expected decimal integer or #{{port}}"
-        ));
+        let err = invalid("expected decimal integer or #{port}".to_string());
+        let trimmed = port.trim_start_matches('+').trim_start_matches('0');
+        return Err(match trimmed.parse::<u32>() {
+            Ok(value) if (1..=65535).contains(&value) => err.with_suggestion(trimmed),
+            _ => err,
+        });
     }
     match port.parse::<u32>() {
         Ok(value) if (1..=65535).contains(&value) => Ok(()),
-        Ok(value) => Err(format!(
-            "Invalid redirect port '{port}': expected 1..=65535, got {value}"
-        )),
-        Err(_) => Err(format!(
-            "Invalid redirect port '{port}': expected decimal integer or #{{port}}"
-        )),
+        Ok(value) => Err(invalid(format!("expected 1..=65535, got {value}"))),
+        Err(_) => Err(invalid("expected decimal integer or #{port}".to_string())),
     }
 }
 
 /// Validate an ELBv2 redirect path value.
-pub fn validate_redirect_path(path: &str) -> Result<(), String> {
+pub fn check_redirect_path(path: &str) -> Result<(), ValidationError> {
     validate_char_length(path, 1, 128, "redirect path")?;
     if !path.starts_with('/') {
-        return Err("Invalid redirect path: must start with '/'".to_string());
+        return Err(
+            ValidationError::new("redirect path", path, "must start with '/'")
+                .with_suggestion(format!("/{path}")),
+        );
     }
     let placeholders = ["#{host}", "#{port}", "#{path}"];
     validate_known_placeholders(path, &placeholders, "redirect path")
 }
 
 /// Validate an ELBv2 redirect query value.
-pub fn validate_redirect_query(query: &str) -> Result<(), String> {
+pub fn check_redirect_query(query: &str) -> Result<(), ValidationError> {
     validate_char_length(query, 0, 128, "redirect query")?;
     validate_known_placeholders(
         query,
@@ -5367,27 +5864,98 @@ pub fn validate_redirect_query(query: &str) -> Result<(), String> {
     )
 }
 
-fn validate_char_length(value: &str, min: usize, max: usize, field: &str) -> Result<(), String> {
+// String-returning forms of the `check_*` validators above, kept so
+// provider crates generated against the old signatures still build.
+
+#[deprecated(note = "use `check_ipv4_address`, which returns a `ValidationError`")]
+pub fn validate_ipv4_address(ip: &str) -> Result<(), String> {
+    check_ipv4_address(ip).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_ipv4_cidr`, which returns a `ValidationError`")]
+pub fn validate_ipv4_cidr(cidr: &str) -> Result<(), String> {
+    check_ipv4_cidr(cidr).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_ipv6_cidr`, which returns a `ValidationError`")]
+pub fn validate_ipv6_cidr(cidr: &str) -> Result<(), String> {
+    check_ipv6_cidr(cidr).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_ipv6_address`, which returns a `ValidationError`")]
+pub fn validate_ipv6_address(addr: &str) -> Result<(), String> {
+    check_ipv6_address(addr).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_email`, which returns a `ValidationError`")]
+pub fn validate_email(email: &str) -> Result<(), String> {
+    check_email(email).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_http_response_status_code`, which returns a `ValidationError`")]
+pub fn validate_http_response_status_code(code: &str) -> Result<(), String> {
+    check_http_response_status_code(code).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_redirect_protocol`, which returns a `ValidationError`")]
+pub fn validate_redirect_protocol(protocol: &str) -> Result<(), String> {
+    check_redirect_protocol(protocol).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_redirect_host`, which returns a `ValidationError`")]
+pub fn validate_redirect_host(host: &str) -> Result<(), String> {
+    check_redirect_host(host).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_redirect_port`, which returns a `ValidationError`")]
+pub fn validate_redirect_port(port: &str) -> Result<(), String> {
+    check_redirect_port(port).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_redirect_path`, which returns a `ValidationError`")]
+pub fn validate_redirect_path(path: &str) -> Result<(), String> {
+    check_redirect_path(path).map_err(String::from)
+}
+
+#[deprecated(note = "use `check_redirect_query`, which returns a `ValidationError`")]
+pub fn validate_redirect_query(query: &str) -> Result<(), String> {
+    check_redirect_query(query).map_err(String::from)
+}
+
+fn validate_char_length(
+    value: &str,
+    min: usize,
+    max: usize,
+    field: &str,
+) -> Result<(), ValidationError> {
     let len = value.chars().count();
     if len < min || len > max {
-        return Err(format!(
-            "Invalid {field}: expected {min}..={max} characters, got {len}"
+        return Err(ValidationError::new(
+            field,
+            value,
+            format!("expected {min}..={max} characters, got {len}"),
         ));
     }
     Ok(())
 }
 
-fn validate_known_placeholders(value: &str, allowed: &[&str], field: &str) -> Result<(), String> {
+fn validate_known_placeholders(
+    value: &str,
+    allowed: &[&str],
+    field: &str,
+) -> Result<(), ValidationError> {
     let mut rest = value;
     while let Some(start) = rest.find("#{") {
         let candidate = &rest[start..];
         let Some(end) = candidate.find('}') else {
-            return Err(format!("Invalid {field}: malformed placeholder"));
+            return Err(ValidationError::new(field, value, "malformed placeholder"));
         };
         let placeholder = &candidate[..=end];
         if !allowed.contains(&placeholder) {
-            return Err(format!(
-                "Invalid {field}: placeholder {placeholder} is not allowed"
+            return Err(ValidationError::new(
+                field,
+                value,
+                format!("placeholder {placeholder} is not allowed"),
             ));
         }
         rest = &candidate[end + 1..];
@@ -5422,13 +5990,18 @@ pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
     prev[b_len]
 }
 
-/// Suggest the most similar field name, if one is close enough
-pub fn suggest_similar_name(unknown: &str, known: &[&str]) -> Option<String> {
-    let max_distance = match unknown.len() {
+/// Maximum edit distance for a name to count as "similar" to `unknown`.
+fn similar_name_max_distance(unknown: &str) -> usize {
+    match unknown.len() {
         0..=2 => 1,
         3..=5 => 2,
         _ => 3,
-    };
+    }
+}
+
+/// Suggest the most similar field name, if one is close enough
+pub fn suggest_similar_name(unknown: &str, known: &[&str]) -> Option<String> {
+    let max_distance = similar_name_max_distance(unknown);
 
     known
         .iter()
@@ -5438,18 +6011,36 @@ pub fn suggest_similar_name(unknown: &str, known: &[&str]) -> Option<String> {
         .map(|(name, _)| name.to_string())
 }
 
-/// Validate a single IPv6 group (1-4 hex digits)
-fn validate_ipv6_group(group: &str, addr: &str) -> Result<(), String> {
-    if group.is_empty() || group.len() > 4 {
-        return Err(format!(
-            "Invalid IPv6 group '{}' in address '{}': must be 1-4 hex digits",
-            group, addr
-        ));
+/// Suggest up to `limit` similar names, closest first. Ties keep the
+/// order of `known`; an exact match is never suggested, and duplicates
+/// in `known` are returned once.
+pub fn suggest_similar_names<'a>(unknown: &str, known: &[&'a str], limit: usize) -> Vec<&'a str> {
+    let max_distance = similar_name_max_distance(unknown);
+    let mut candidates: Vec<(&str, usize)> = Vec::new();
+    for name in known {
+        if candidates.iter().any(|(seen, _)| seen == name) {
+            continue;
+        }
+        let dist = levenshtein_distance(unknown, name);
+        if dist > 0 && dist <= max_distance {
+            candidates.push((name, dist));
+        }
     }
-    if !group.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(format!(
-            "Invalid IPv6 group '{}' in address '{}': must be hex digits",
-            group, addr
+    candidates.sort_by_key(|(_, dist)| *dist);
+    candidates
+        .into_iter()
+        .take(limit)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Validate a single IPv6 group (1-4 hex digits)
+fn validate_ipv6_group(group: &str, addr: &str) -> Result<(), ValidationError> {
+    if group.is_empty() || group.len() > 4 || !group.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ValidationError::new(
+            "IPv6 group",
+            group,
+            format!("must be 1-4 hex digits in address '{addr}'"),
         ));
     }
     Ok(())
@@ -5468,6 +6059,11 @@ fn validate_ipv6_group(group: &str, addr: &str) -> Result<(), String> {
 pub struct SchemaRegistry {
     managed: HashMap<(String, String), ResourceSchema>,
     data_sources: HashMap<(String, String), ResourceSchema>,
+    /// Provider name → declared provider family. Providers absent here
+    /// form a family of their own.
+    families: HashMap<String, String>,
+    /// Provider name → version of the provider that supplied its schemas.
+    versions: HashMap<String, String>,
 }
 
 impl SchemaRegistry {
@@ -5489,6 +6085,40 @@ impl SchemaRegistry {
         }
     }
 
+    /// Record that `provider` belongs to `family`. Providers in the same
+    /// family model the same cloud (`aws` and `awscc` are both `aws`),
+    /// so identified custom types they share are interchangeable in
+    /// cross-provider references.
+    pub fn set_provider_family(&mut self, provider: impl Into<String>, family: impl Into<String>) {
+        self.families.insert(provider.into(), family.into());
+    }
+
+    /// Whether `a` and `b` are the same provider or declare the same
+    /// family.
+    pub fn same_provider_family(&self, a: &str, b: &str) -> bool {
+        a == b || self.family_of(a) == self.family_of(b)
+    }
+
+    fn family_of<'a>(&'a self, provider: &'a str) -> &'a str {
+        self.families.get(provider).map_or(provider, String::as_str)
+    }
+
+    /// Record the version of the provider whose schemas are registered
+    /// under `provider`.
+    pub fn set_provider_version(
+        &mut self,
+        provider: impl Into<String>,
+        version: impl Into<String>,
+    ) {
+        self.versions.insert(provider.into(), version.into());
+    }
+
+    /// Version of the provider that supplied `provider`'s schemas, when
+    /// the provider reported one.
+    pub fn provider_version(&self, provider: &str) -> Option<&str> {
+        self.versions.get(provider).map(String::as_str)
+    }
+
     /// Look up a schema by explicit `(provider, resource_type, kind)`.
     pub fn get(
         &self,
@@ -5524,6 +6154,23 @@ impl SchemaRegistry {
         )
     }
 
+    /// Look up a schema by its fully-qualified type name as written in
+    /// the DSL (`"awscc.ec2.Vpc"`), trying `Managed` before `DataSource`.
+    /// A name whose first segment is not a registered provider falls back
+    /// to the provider-less entry for the whole name.
+    pub fn get_by_type_name(&self, type_name: &str) -> Option<&ResourceSchema> {
+        let lookup = |provider: &str, resource_type: &str| {
+            self.get(provider, resource_type, SchemaKind::Resource)
+                .or_else(|| self.get(provider, resource_type, SchemaKind::DataSource))
+        };
+        match type_name.split_once('.') {
+            Some((provider, resource_type)) => {
+                lookup(provider, resource_type).or_else(|| lookup("", type_name))
+            }
+            None => lookup("", type_name),
+        }
+    }
+
     pub fn has_managed(&self, provider: &str, resource_type: &str) -> bool {
         self.get(provider, resource_type, SchemaKind::Resource)
             .is_some()
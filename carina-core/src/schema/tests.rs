@@ -428,6 +428,53 @@ fn invalid_enum_error_names_the_enum_type_and_fully_qualified_variants() {
     );
 }
 
+#[test]
+fn invalid_enum_error_suggests_nearest_variants() {
+    let t = AttributeType::enum_(
+        crate::schema::enum_identity("Tenancy", Some("aws.ec2.Instance")),
+        Some(vec![
+            "default".to_string(),
+            "dedicated".to_string(),
+            "host".to_string(),
+        ]),
+        vec![],
+        None,
+        None,
+    );
+    let msg = t
+        .validate(&Value::Concrete(ConcreteValue::enum_identifier(
+            "aws.ec2.Instance.Tenancy.dedcated".to_string(),
+        )))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        msg.ends_with("; did you mean 'aws.ec2.Instance.Tenancy.dedicated'?"),
+        "got: {msg}"
+    );
+
+    let msg = t
+        .validate(&Value::Concrete(ConcreteValue::enum_identifier(
+            "aws.ec2.Instance.Tenancy.zzzzzzzz".to_string(),
+        )))
+        .unwrap_err()
+        .to_string();
+    assert!(!msg.contains("did you mean"), "got: {msg}");
+}
+
+#[test]
+fn suggest_similar_names_orders_by_distance_and_limits() {
+    let known = ["dedicate", "default", "dedicated", "host", "dedicate"];
+    assert_eq!(
+        suggest_similar_names("dedcated", &known, 3),
+        vec!["dedicated", "dedicate"]
+    );
+    assert_eq!(
+        suggest_similar_names("dedcated", &known, 1),
+        vec!["dedicated"]
+    );
+    assert!(suggest_similar_names("host", &known, 3).is_empty());
+}
+
 #[test]
 fn with_attribute_adds_attribute_name_to_enum_error() {
     // Regression for #2098, updated for carina#2986. The
@@ -1411,7 +1458,7 @@ fn validate_ipv6_cidr_type() {
     );
     assert!(
         t.validate(&Value::Concrete(ConcreteValue::String(
-            "2001:0db8:85a3:0000:0000:8a2e:0370:7334/64".to_string()
+            "2001:0db8:85a3:0000:0000:0000:0000:0000/64".to_string()
         )))
         .is_ok()
     );
@@ -1462,17 +1509,33 @@ fn validate_ipv6_cidr_type() {
 #[test]
 fn validate_ipv6_cidr_function_directly() {
     // Valid
-    assert!(validate_ipv6_cidr("::/0").is_ok());
-    assert!(validate_ipv6_cidr("2001:db8::/32").is_ok());
-    assert!(validate_ipv6_cidr("fe80::/10").is_ok());
-    assert!(validate_ipv6_cidr("::1/128").is_ok());
-    assert!(validate_ipv6_cidr("2001:0db8:85a3:0000:0000:8a2e:0370:7334/64").is_ok());
+    assert!(check_ipv6_cidr("::/0").is_ok());
+    assert!(check_ipv6_cidr("2001:db8::/32").is_ok());
+    assert!(check_ipv6_cidr("fe80::/10").is_ok());
+    assert!(check_ipv6_cidr("::1/128").is_ok());
+    assert!(check_ipv6_cidr("2001:0db8:85a3:0000:0000:0000:0000:0000/64").is_ok());
 
     // Invalid
-    assert!(validate_ipv6_cidr("2001:db8::/129").is_err());
-    assert!(validate_ipv6_cidr("not-a-cidr").is_err());
-    assert!(validate_ipv6_cidr("2001:db8::").is_err());
-    assert!(validate_ipv6_cidr("/64").is_err());
+    assert!(check_ipv6_cidr("2001:db8::/129").is_err());
+    assert!(check_ipv6_cidr("not-a-cidr").is_err());
+    assert!(check_ipv6_cidr("2001:db8::").is_err());
+    assert!(check_ipv6_cidr("/64").is_err());
+}
+
+#[test]
+fn validate_cidr_rejects_host_bits_with_network_suggestion() {
+    let err = check_ipv4_cidr("10.0.0.5/16").unwrap_err();
+    assert_eq!(err.suggestion.as_deref(), Some("10.0.0.0/16"));
+    assert_eq!(
+        err.to_string(),
+        "Invalid CIDR block '10.0.0.5/16': host bits are set; did you mean '10.0.0.0/16'?"
+    );
+
+    let err = check_ipv6_cidr("2001:db8::1/64").unwrap_err();
+    assert_eq!(err.suggestion.as_deref(), Some("2001:db8::/64"));
+
+    assert!(check_ipv4_cidr("10.0.0.0/16").is_ok());
+    assert!(check_ipv4_cidr("10.0.0.5/32").is_ok());
 }
 
 #[test]
@@ -1852,7 +1915,7 @@ fn exclusive_required_declarative() {
             "ipv4_ipam_pool_id",
             AttributeType::string(),
         ))
-        .exclusive_required(&["cidr_block", "ipv4_ipam_pool_id"]);
+        .exactly_one_of(&["cidr_block", "ipv4_ipam_pool_id"]);
 
     // Valid: exactly one present
     let mut one = HashMap::new();
@@ -1893,6 +1956,45 @@ fn exclusive_required_declarative() {
     );
 }
 
+#[test]
+#[allow(deprecated)]
+fn exclusive_required_builder_is_an_alias_of_exactly_one_of() {
+    let schema =
+        ResourceSchema::new("vpc").exclusive_required(&["cidr_block", "ipv4_ipam_pool_id"]);
+    assert_eq!(
+        schema.constraints,
+        vec![AttributeConstraint::ExactlyOneOf(vec![
+            "cidr_block".to_string(),
+            "ipv4_ipam_pool_id".to_string(),
+        ])]
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn exclusive_required_field_groups_are_still_enforced() {
+    let mut schema = ResourceSchema::new("vpc")
+        .attribute(AttributeSchema::new("cidr_block", AttributeType::string()))
+        .attribute(
+            AttributeSchema::new("ipv4_ipam_pool_id", AttributeType::string())
+                .with_default(Value::Concrete(ConcreteValue::String("pool".to_string()))),
+        );
+    schema.exclusive_required = vec![vec![
+        "cidr_block".to_string(),
+        "ipv4_ipam_pool_id".to_string(),
+    ]];
+
+    let err = schema.validate(&HashMap::new()).unwrap_err();
+    assert!(
+        err.iter().any(|e| e.to_string().contains("cidr_block")),
+        "missing expected error, got: {:?}",
+        err
+    );
+    // Like an `exactly_one_of` constraint, the group keeps its
+    // member's default from being materialized.
+    assert!(schema.missing_defaults(&|_| false).is_empty());
+}
+
 #[test]
 fn exclusive_required_multiple_groups() {
     let schema = ResourceSchema::new("multi")
@@ -1900,8 +2002,8 @@ fn exclusive_required_multiple_groups() {
         .attribute(AttributeSchema::new("b", AttributeType::string()))
         .attribute(AttributeSchema::new("x", AttributeType::string()))
         .attribute(AttributeSchema::new("y", AttributeType::string()))
-        .exclusive_required(&["a", "b"])
-        .exclusive_required(&["x", "y"]);
+        .exactly_one_of(&["a", "b"])
+        .exactly_one_of(&["x", "y"]);
 
     // Neither group satisfied → two errors
     let err = schema.validate(&HashMap::new()).unwrap_err();
@@ -1927,6 +2029,145 @@ fn exclusive_required_multiple_groups() {
     assert!(schema.validate(&ok).is_ok());
 }
 
+#[test]
+fn unit_literals_convert_to_the_attribute_unit() {
+    let schema = ResourceSchema::new("iam.Role")
+        .attribute(
+            AttributeSchema::new("max_session_duration", AttributeType::int())
+                .with_unit(AttributeUnit::Seconds),
+        )
+        .attribute(
+            AttributeSchema::new("expiration_days", AttributeType::int())
+                .with_unit(AttributeUnit::Days),
+        )
+        .attribute(
+            AttributeSchema::new("volume_size", AttributeType::int())
+                .with_unit(AttributeUnit::Gibibytes),
+        );
+    let hours = |h: u64| {
+        Value::Concrete(ConcreteValue::Duration(std::time::Duration::from_secs(
+            h * 3600,
+        )))
+    };
+    let int = |n: i64| Value::Concrete(ConcreteValue::Int(n));
+    let attr = |name: &str| &schema.attributes[name];
+
+    assert_eq!(
+        attr("max_session_duration").convert_unit_literal(&hours(12)),
+        Ok(Some(int(43_200)))
+    );
+    assert_eq!(
+        attr("expiration_days").convert_unit_literal(&hours(720)),
+        Ok(Some(int(30)))
+    );
+    assert_eq!(
+        attr("volume_size")
+            .convert_unit_literal(&Value::Concrete(ConcreteValue::ByteSize(20 << 30))),
+        Ok(Some(int(20)))
+    );
+    // Plain integers are already in the API unit.
+    assert_eq!(attr("volume_size").convert_unit_literal(&int(20)), Ok(None));
+
+    let mut attrs = HashMap::new();
+    attrs.insert("max_session_duration".to_string(), hours(12));
+    assert!(schema.validate(&attrs).is_ok());
+
+    // Not a whole number of days.
+    attrs.insert("expiration_days".to_string(), hours(36));
+    // Wrong quantity.
+    attrs.insert(
+        "volume_size".to_string(),
+        Value::Concrete(ConcreteValue::Duration(std::time::Duration::from_secs(60))),
+    );
+    let errors: Vec<String> = schema
+        .validate(&attrs)
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.contains("36h is not a whole number of days")),
+        "{errors:?}"
+    );
+    assert!(
+        errors.iter().any(|e| e.contains("expected Int (GiB)")),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn required_with_constraint() {
+    let schema = ResourceSchema::new("listener")
+        .attribute(AttributeSchema::new("protocol", AttributeType::string()))
+        .attribute(AttributeSchema::new(
+            "certificate_arn",
+            AttributeType::string(),
+        ))
+        .attribute(AttributeSchema::new("ssl_policy", AttributeType::string()))
+        .required_with("certificate_arn", &["protocol", "ssl_policy"]);
+
+    // Trigger absent → no requirement
+    assert!(schema.validate(&HashMap::new()).is_ok());
+
+    let mut attrs = HashMap::new();
+    attrs.insert(
+        "certificate_arn".to_string(),
+        Value::Concrete(ConcreteValue::String("arn:cert".to_string())),
+    );
+    attrs.insert(
+        "protocol".to_string(),
+        Value::Concrete(ConcreteValue::String("HTTPS".to_string())),
+    );
+    let err = schema.validate(&attrs).unwrap_err();
+    assert!(
+        err.iter().any(|e| e
+            .to_string()
+            .contains("'certificate_arn' requires [protocol, ssl_policy] to also be specified, but missing: ssl_policy")),
+        "missing expected error, got: {:?}",
+        err
+    );
+
+    attrs.insert(
+        "ssl_policy".to_string(),
+        Value::Concrete(ConcreteValue::String("tls13".to_string())),
+    );
+    assert!(schema.validate(&attrs).is_ok());
+}
+
+#[test]
+fn conflicts_with_constraint() {
+    let schema = ResourceSchema::new("instance")
+        .attribute(AttributeSchema::new("subnet_id", AttributeType::string()))
+        .attribute(AttributeSchema::new(
+            "network_interfaces",
+            AttributeType::string(),
+        ))
+        .conflicts_with("network_interfaces", &["subnet_id"]);
+
+    let mut attrs = HashMap::new();
+    attrs.insert(
+        "subnet_id".to_string(),
+        Value::Concrete(ConcreteValue::String("subnet-1".to_string())),
+    );
+    assert!(schema.validate(&attrs).is_ok());
+
+    attrs.insert(
+        "network_interfaces".to_string(),
+        Value::Concrete(ConcreteValue::String("eni-1".to_string())),
+    );
+    let err = schema.validate(&attrs).unwrap_err();
+    assert!(
+        err.iter().any(|e| e
+            .to_string()
+            .contains("'network_interfaces' cannot be specified together with: subnet_id")),
+        "missing expected error, got: {:?}",
+        err
+    );
+}
+
 #[test]
 fn validate_union_type() {
     // Create two Custom types that validate different prefixes
@@ -3937,29 +4178,29 @@ fn custom_type_name_anonymous_pattern_and_length() {
 #[test]
 fn validate_email_function_directly() {
     // Valid
-    assert!(validate_email("user@example.com").is_ok());
-    assert!(validate_email("user.name+tag@sub.example.co.jp").is_ok());
-    assert!(validate_email("a@b.c").is_ok());
+    assert!(check_email("user@example.com").is_ok());
+    assert!(check_email("user.name+tag@sub.example.co.jp").is_ok());
+    assert!(check_email("a@b.c").is_ok());
 
     // Invalid: no '@'
-    assert!(validate_email("no-at-sign.com").is_err());
+    assert!(check_email("no-at-sign.com").is_err());
     // Invalid: no dot in domain
-    assert!(validate_email("noTLD@host").is_err());
+    assert!(check_email("noTLD@host").is_err());
     // Invalid: empty local-part
-    assert!(validate_email("@example.com").is_err());
+    assert!(check_email("@example.com").is_err());
     // Invalid: empty domain
-    assert!(validate_email("user@").is_err());
+    assert!(check_email("user@").is_err());
     // Invalid: empty input
-    assert!(validate_email("").is_err());
+    assert!(check_email("").is_err());
     // Invalid: more than one '@'
-    assert!(validate_email("a@b@c.com").is_err());
+    assert!(check_email("a@b@c.com").is_err());
     // Invalid: empty domain label (consecutive dots)
-    assert!(validate_email("user@example..com").is_err());
+    assert!(check_email("user@example..com").is_err());
     // Invalid: trailing dot in domain creates empty label
-    assert!(validate_email("user@example.com.").is_err());
+    assert!(check_email("user@example.com.").is_err());
     // Invalid: whitespace
-    assert!(validate_email("us er@example.com").is_err());
-    assert!(validate_email("user@exa mple.com").is_err());
+    assert!(check_email("us er@example.com").is_err());
+    assert!(check_email("user@exa mple.com").is_err());
 }
 
 #[test]
@@ -4026,108 +4267,145 @@ fn validate_email_type() {
 #[test]
 fn validate_http_response_status_code_function_directly() {
     // Valid: 2XX/4XX/5XX
-    assert!(validate_http_response_status_code("200").is_ok());
-    assert!(validate_http_response_status_code("299").is_ok());
-    assert!(validate_http_response_status_code("400").is_ok());
-    assert!(validate_http_response_status_code("499").is_ok());
-    assert!(validate_http_response_status_code("500").is_ok());
-    assert!(validate_http_response_status_code("599").is_ok());
+    assert!(check_http_response_status_code("200").is_ok());
+    assert!(check_http_response_status_code("299").is_ok());
+    assert!(check_http_response_status_code("400").is_ok());
+    assert!(check_http_response_status_code("499").is_ok());
+    assert!(check_http_response_status_code("500").is_ok());
+    assert!(check_http_response_status_code("599").is_ok());
 
     // Invalid: disallowed leading digit (fence-post boundaries pin the
     // excluded 1XX/3XX/6XX ranges explicitly).
-    assert!(validate_http_response_status_code("100").is_err());
-    assert!(validate_http_response_status_code("199").is_err());
-    assert!(validate_http_response_status_code("300").is_err());
-    assert!(validate_http_response_status_code("301").is_err());
-    assert!(validate_http_response_status_code("399").is_err());
-    assert!(validate_http_response_status_code("600").is_err());
+    assert!(check_http_response_status_code("100").is_err());
+    assert!(check_http_response_status_code("199").is_err());
+    assert!(check_http_response_status_code("300").is_err());
+    assert!(check_http_response_status_code("301").is_err());
+    assert!(check_http_response_status_code("399").is_err());
+    assert!(check_http_response_status_code("600").is_err());
     // Invalid: wrong length
-    assert!(validate_http_response_status_code("20").is_err());
-    assert!(validate_http_response_status_code("2000").is_err());
-    assert!(validate_http_response_status_code("").is_err());
+    assert!(check_http_response_status_code("20").is_err());
+    assert!(check_http_response_status_code("2000").is_err());
+    assert!(check_http_response_status_code("").is_err());
     // Invalid: non-digit
-    assert!(validate_http_response_status_code("nonsense").is_err());
-    assert!(validate_http_response_status_code("12a").is_err());
+    assert!(check_http_response_status_code("nonsense").is_err());
+    assert!(check_http_response_status_code("12a").is_err());
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_validate_wrappers_return_the_rendered_message() {
+    assert_eq!(validate_ipv4_cidr("10.0.0.0/16"), Ok(()));
+    assert_eq!(
+        validate_ipv4_address("10.0.1"),
+        Err(check_ipv4_address("10.0.1").unwrap_err().to_string())
+    );
+    assert_eq!(
+        validate_email("no-at-sign.com"),
+        Err(check_email("no-at-sign.com").unwrap_err().to_string())
+    );
+}
+
+#[test]
+fn validation_error_renders_input_reason_and_suggestion() {
+    let err = check_ipv4_cidr("10.0.0.0/33").unwrap_err();
+    assert_eq!(err.input, "33");
+    assert_eq!(err.to_string(), "Invalid prefix length '33': must be 0-32");
+
+    let err = check_redirect_protocol("http").unwrap_err();
+    assert_eq!(err.suggestion.as_deref(), Some("HTTP"));
+    assert_eq!(
+        err.to_string(),
+        "Invalid redirect protocol 'http': expected HTTP, HTTPS, or #{protocol}; did you mean 'HTTP'?"
+    );
+
+    let err = check_email("a b@example.com")
+        .unwrap_err()
+        .with_original_input(" a b@example.com ");
+    assert_eq!(err.normalized.as_deref(), Some("a b@example.com"));
+    assert_eq!(
+        err.to_string(),
+        "Invalid email ' a b@example.com ' (normalized to 'a b@example.com'): local part contains whitespace"
+    );
 }
 
 #[test]
 fn validate_redirect_protocol_function_directly() {
-    assert!(validate_redirect_protocol("HTTP").is_ok());
-    assert!(validate_redirect_protocol("HTTPS").is_ok());
-    assert!(validate_redirect_protocol("#{protocol}").is_ok());
+    assert!(check_redirect_protocol("HTTP").is_ok());
+    assert!(check_redirect_protocol("HTTPS").is_ok());
+    assert!(check_redirect_protocol("#{protocol}").is_ok());
 
-    assert!(validate_redirect_protocol("").is_err());
-    assert!(validate_redirect_protocol("http").is_err());
-    assert!(validate_redirect_protocol("#{host}").is_err());
-    assert!(validate_redirect_protocol("#{port}").is_err());
-    assert!(validate_redirect_protocol("#{path}").is_err());
-    assert!(validate_redirect_protocol("#{query}").is_err());
+    assert!(check_redirect_protocol("").is_err());
+    assert!(check_redirect_protocol("http").is_err());
+    assert!(check_redirect_protocol("#{host}").is_err());
+    assert!(check_redirect_protocol("#{port}").is_err());
+    assert!(check_redirect_protocol("#{path}").is_err());
+    assert!(check_redirect_protocol("#{query}").is_err());
 }
 
 #[test]
 fn validate_redirect_host_function_directly() {
-    assert!(validate_redirect_host("example").is_ok());
-    assert!(validate_redirect_host("bad.example.com").is_ok());
-    assert!(validate_redirect_host("www-#{host}").is_ok());
-    assert!(validate_redirect_host("*-api?").is_ok());
-    assert!(validate_redirect_host("https://example.com:443").is_ok());
+    assert!(check_redirect_host("example").is_ok());
+    assert!(check_redirect_host("bad.example.com").is_ok());
+    assert!(check_redirect_host("www-#{host}").is_ok());
+    assert!(check_redirect_host("*-api?").is_ok());
+    assert!(check_redirect_host("https://example.com:443").is_ok());
 
-    assert!(validate_redirect_host("").is_err());
-    assert!(validate_redirect_host(&"a".repeat(128)).is_ok());
-    assert!(validate_redirect_host(&"a".repeat(129)).is_err());
-    assert!(validate_redirect_host("#{protocol}").is_err());
-    assert!(validate_redirect_host("#{path}").is_err());
+    assert!(check_redirect_host("").is_err());
+    assert!(check_redirect_host(&"a".repeat(128)).is_ok());
+    assert!(check_redirect_host(&"a".repeat(129)).is_err());
+    assert!(check_redirect_host("#{protocol}").is_err());
+    assert!(check_redirect_host("#{path}").is_err());
 }
 
 #[test]
 fn validate_redirect_port_function_directly() {
-    assert!(validate_redirect_port("1").is_ok());
-    assert!(validate_redirect_port("443").is_ok());
-    assert!(validate_redirect_port("65535").is_ok());
-    assert!(validate_redirect_port("#{port}").is_ok());
-
-    assert!(validate_redirect_port("").is_err());
-    assert!(validate_redirect_port("0").is_err());
-    assert!(validate_redirect_port("65536").is_err());
+    assert!(check_redirect_port("1").is_ok());
+    assert!(check_redirect_port("443").is_ok());
+    assert!(check_redirect_port("65535").is_ok());
+    assert!(check_redirect_port("#{port}").is_ok());
+
+    assert!(check_redirect_port("").is_err());
+    assert!(check_redirect_port("0").is_err());
+    assert!(check_redirect_port("65536").is_err());
     // Non-decimal forms must be rejected even though u32::from_str accepts them.
-    assert!(validate_redirect_port("+443").is_err());
-    assert!(validate_redirect_port("0443").is_err());
-    assert!(validate_redirect_port("#{path}").is_err());
-    assert!(validate_redirect_port("443?").is_err());
+    assert!(check_redirect_port("+443").is_err());
+    assert!(check_redirect_port("0443").is_err());
+    assert!(check_redirect_port("#{path}").is_err());
+    assert!(check_redirect_port("443?").is_err());
 }
 
 #[test]
 fn validate_redirect_path_function_directly() {
-    assert!(validate_redirect_path("/").is_ok());
-    assert!(validate_redirect_path("/#{host}:#{port}/#{path}").is_ok());
-    assert!(validate_redirect_path("/a*b?&-._$~\"'@:+/").is_ok());
-    assert!(validate_redirect_path("/encoded/%2F?x=1").is_ok());
-    assert!(validate_redirect_path("/bad#path").is_ok());
+    assert!(check_redirect_path("/").is_ok());
+    assert!(check_redirect_path("/#{host}:#{port}/#{path}").is_ok());
+    assert!(check_redirect_path("/a*b?&-._$~\"'@:+/").is_ok());
+    assert!(check_redirect_path("/encoded/%2F?x=1").is_ok());
+    assert!(check_redirect_path("/bad#path").is_ok());
 
-    assert!(validate_redirect_path("").is_err());
-    assert!(validate_redirect_path(&format!("/{}", "a".repeat(127))).is_ok());
-    assert!(validate_redirect_path(&format!("/{}", "a".repeat(128))).is_err());
-    assert!(validate_redirect_path("#{path}").is_err());
-    assert!(validate_redirect_path("/#{protocol}").is_err());
-    assert!(validate_redirect_path("/#{query}").is_err());
+    assert!(check_redirect_path("").is_err());
+    assert!(check_redirect_path(&format!("/{}", "a".repeat(127))).is_ok());
+    assert!(check_redirect_path(&format!("/{}", "a".repeat(128))).is_err());
+    assert!(check_redirect_path("#{path}").is_err());
+    assert!(check_redirect_path("/#{protocol}").is_err());
+    assert!(check_redirect_path("/#{query}").is_err());
 }
 
 #[test]
 fn validate_redirect_query_function_directly() {
-    assert!(validate_redirect_query("").is_ok());
-    assert!(validate_redirect_query("next=#{path}&q=#{query}").is_ok());
-    assert!(validate_redirect_query("proto=#{protocol}&host=#{host}&port=#{port}").is_ok());
-    assert!(validate_redirect_query("spaces and arbitrary chars !%[]").is_ok());
-    assert!(validate_redirect_query(&"a".repeat(128)).is_ok());
+    assert!(check_redirect_query("").is_ok());
+    assert!(check_redirect_query("next=#{path}&q=#{query}").is_ok());
+    assert!(check_redirect_query("proto=#{protocol}&host=#{host}&port=#{port}").is_ok());
+    assert!(check_redirect_query("spaces and arbitrary chars !%[]").is_ok());
+    assert!(check_redirect_query(&"a".repeat(128)).is_ok());
     // Confirm every documented placeholder is accepted.
-    assert!(validate_redirect_query("#{protocol}").is_ok());
-    assert!(validate_redirect_query("#{host}").is_ok());
-    assert!(validate_redirect_query("#{port}").is_ok());
-    assert!(validate_redirect_query("#{path}").is_ok());
-    assert!(validate_redirect_query("#{query}").is_ok());
+    assert!(check_redirect_query("#{protocol}").is_ok());
+    assert!(check_redirect_query("#{host}").is_ok());
+    assert!(check_redirect_query("#{port}").is_ok());
+    assert!(check_redirect_query("#{path}").is_ok());
+    assert!(check_redirect_query("#{query}").is_ok());
 
-    assert!(validate_redirect_query(&"a".repeat(129)).is_err());
-    assert!(validate_redirect_query("#{bogus}").is_err());
+    assert!(check_redirect_query(&"a".repeat(129)).is_err());
+    assert!(check_redirect_query("#{bogus}").is_err());
 }
 
 #[test]
@@ -4935,6 +5213,34 @@ fn schema_registry_has_managed_only_does_not_imply_data_source() {
     assert!(!registry.has_data_source("aws", "s3.Bucket"));
 }
 
+#[test]
+fn schema_registry_looks_up_fully_qualified_type_names() {
+    let mut registry = SchemaRegistry::new();
+    registry.insert("awscc", ResourceSchema::new("ec2.Vpc"));
+    registry.insert(
+        "awscc",
+        ResourceSchema::new("sts.CallerIdentity").as_data_source(),
+    );
+    registry.insert("", ResourceSchema::new("local.File"));
+
+    let vpc = registry.get_by_type_name("awscc.ec2.Vpc").unwrap();
+    assert_eq!(vpc.resource_type, "ec2.Vpc");
+    let caller = registry
+        .get_by_type_name("awscc.sts.CallerIdentity")
+        .unwrap();
+    assert_eq!(caller.kind, SchemaKind::DataSource);
+    assert!(registry.get_by_type_name("local.File").is_some());
+    assert!(registry.get_by_type_name("aws.ec2.Vpc").is_none());
+}
+
+#[test]
+fn schema_registry_records_provider_versions() {
+    let mut registry = SchemaRegistry::new();
+    registry.set_provider_version("awscc", "1.4.0");
+    assert_eq!(registry.provider_version("awscc"), Some("1.4.0"));
+    assert_eq!(registry.provider_version("aws"), None);
+}
+
 #[test]
 fn validate_skips_value_unknown_for_primitive_types() {
     // `Value::Deferred(DeferredValue::Unknown)` carries no concrete type at plan time, so it
@@ -5103,12 +5409,12 @@ fn union_walk_custom_lookup_cidr_accepts_ipv4_when_lookup_routes_correctly() {
             return Ok(());
         };
         match id.kind.as_str() {
-            "Ipv4Cidr" => {
-                validate_ipv4_cidr(s).map_err(|message| TypeError::ValidationFailed { message })
-            }
-            "Ipv6Cidr" => {
-                validate_ipv6_cidr(s).map_err(|message| TypeError::ValidationFailed { message })
-            }
+            "Ipv4Cidr" => check_ipv4_cidr(s).map_err(|message| TypeError::ValidationFailed {
+                message: message.into(),
+            }),
+            "Ipv6Cidr" => check_ipv6_cidr(s).map_err(|message| TypeError::ValidationFailed {
+                message: message.into(),
+            }),
             _ => Ok(()),
         }
     };
@@ -5515,6 +5821,16 @@ mod dsl_map_api_for {
         assert_eq!(map.api_for("Enabled"), "Enabled");
     }
 
+    #[test]
+    fn alias_api_for_only_matches_alias_entries() {
+        let aliases = alias_table();
+        let transform = crate::schema::DslTransform::HyphenToUnderscore;
+        let map = DslMap::new(&aliases, Some(&transform));
+        assert_eq!(map.alias_api_for("enabled"), Some("Enabled"));
+        assert_eq!(map.alias_api_for("Enabled"), None);
+        assert_eq!(map.alias_api_for("ap_northeast_1"), None);
+    }
+
     #[test]
     fn aliases_empty_table_is_identity() {
         let aliases: Vec<(String, String)> = vec![];
@@ -6192,3 +6508,37 @@ fn raw_shape_passes_through_non_ref_variants() {
         other => panic!("expected RawShape::List(unordered), got {other:?}"),
     }
 }
+
+#[test]
+fn unknown_property_report_collects_undeclared_read_back_attributes() {
+    use crate::resource::{ResourceId, State};
+
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert(
+        "awscc",
+        ResourceSchema::new("ec2.Vpc")
+            .attribute(AttributeSchema::new("cidr_block", AttributeType::string()))
+            .attribute(AttributeSchema::new("vpc_id", AttributeType::string()).read_only()),
+    );
+    let string = |s: &str| Value::Concrete(ConcreteValue::String(s.to_string()));
+
+    let vpc_id = ResourceId::with_provider_identity("awscc", "ec2.Vpc", "main", None);
+    let mut attrs = HashMap::new();
+    attrs.insert("cidr_block".to_string(), string("10.0.0.0/16"));
+    attrs.insert("vpc_id".to_string(), string("vpc-1"));
+    attrs.insert("ipv6_native".to_string(), string("false"));
+    attrs.insert("_internal".to_string(), string("x"));
+    let unschematized = ResourceId::with_provider_identity("awscc", "ec2.Unknown", "other", None);
+    let mut states = HashMap::new();
+    states.insert(vpc_id.clone(), State::existing(vpc_id, attrs.clone()));
+    states.insert(unschematized.clone(), State::existing(unschematized, attrs));
+
+    let report = UnknownPropertyReport::from_states(&schemas, &states);
+
+    let entries: Vec<(&str, Vec<&str>)> = report
+        .iter()
+        .map(|(ty, names)| (ty, names.iter().map(String::as_str).collect()))
+        .collect();
+    assert_eq!(entries, vec![("awscc.ec2.Vpc", vec!["ipv6_native"])]);
+    assert_eq!(report.to_string(), "awscc.ec2.Vpc: ipv6_native");
+}
@@ -185,11 +185,27 @@ impl TypeIdentity {
     /// - `aws.Region` ↔ `gcp.Region`: rejected both ways (populated
     ///   providers differ).
     pub fn assignable_to(&self, sink: &TypeIdentity) -> bool {
+        self.assignable_to_with(sink, &|a, b| a == b)
+    }
+
+    /// [`assignable_to`](Self::assignable_to) with the provider axis
+    /// decided by `same_provider` instead of string equality. Lets a
+    /// caller that knows two providers model the same cloud (`aws` and
+    /// `awscc`) treat their identities as one namespace. The "sink
+    /// populates, source must too" rule still applies.
+    pub fn assignable_to_with(
+        &self,
+        sink: &TypeIdentity,
+        same_provider: &dyn Fn(&str, &str) -> bool,
+    ) -> bool {
         if self.kind != sink.kind {
             return false;
         }
         if let Some(sink_provider) = &sink.provider
-            && self.provider.as_deref() != Some(sink_provider.as_str())
+            && !self
+                .provider
+                .as_deref()
+                .is_some_and(|source| same_provider(source, sink_provider))
         {
             return false;
         }
@@ -382,6 +398,20 @@ mod tests {
         assert!(!vpc_id.assignable_to(&arn));
     }
 
+    #[test]
+    fn assignable_to_with_compares_provider_axis_by_predicate() {
+        let aws = TypeIdentity::new(Some("aws"), ["ec2", "Vpc"], "VpcId");
+        let awscc = TypeIdentity::new(Some("awscc"), ["ec2", "Vpc"], "VpcId");
+        let same_cloud =
+            |a: &str, b: &str| a == b || matches!((a, b), ("aws", "awscc") | ("awscc", "aws"));
+        assert!(aws.assignable_to_with(&awscc, &same_cloud));
+        assert!(awscc.assignable_to_with(&aws, &same_cloud));
+        assert!(!aws.assignable_to(&awscc));
+
+        let bare = TypeIdentity::new(None::<String>, ["ec2", "Vpc"], "VpcId");
+        assert!(!bare.assignable_to_with(&aws, &|_, _| true));
+    }
+
     #[test]
     fn dotted_prefix_drops_kind_and_handles_bare_identity() {
         // Provider + segments + kind → `provider.segments...`.
@@ -0,0 +1,146 @@
+//! Unit metadata for integer attributes.
+//!
+//! Many cloud APIs take a bare integer whose unit is implied by the
+//! property name: IAM `MaxSessionDuration` is seconds, S3 lifecycle
+//! expiration is days, EBS volume size is GiB. An [`AttributeUnit`] on
+//! the attribute schema records that unit so the DSL can accept a
+//! duration (`12h`) or byte-size (`20GiB`) literal and convert it to the
+//! API's integer before validation and diffing.
+
+use std::fmt;
+
+use super::TypeError;
+use crate::resource::{ConcreteValue, Value};
+
+/// Byte-size literal suffixes and their factors. Binary units come
+/// first so [`crate::value::render_byte_size`] prefers them.
+pub const BYTE_SIZE_UNITS: [(&str, u64); 9] = [
+    ("TiB", 1 << 40),
+    ("GiB", 1 << 30),
+    ("MiB", 1 << 20),
+    ("KiB", 1 << 10),
+    ("TB", 1_000_000_000_000),
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+/// Unit an integer attribute is expressed in at the provider API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeUnit {
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Bytes,
+    Kibibytes,
+    Mebibytes,
+    Gibibytes,
+    Megabytes,
+    Gigabytes,
+}
+
+impl AttributeUnit {
+    const ALL: [AttributeUnit; 10] = [
+        AttributeUnit::Seconds,
+        AttributeUnit::Minutes,
+        AttributeUnit::Hours,
+        AttributeUnit::Days,
+        AttributeUnit::Bytes,
+        AttributeUnit::Kibibytes,
+        AttributeUnit::Mebibytes,
+        AttributeUnit::Gibibytes,
+        AttributeUnit::Megabytes,
+        AttributeUnit::Gigabytes,
+    ];
+
+    /// Name used in schemas, plan annotations and hover text.
+    pub fn name(self) -> &'static str {
+        match self {
+            AttributeUnit::Seconds => "seconds",
+            AttributeUnit::Minutes => "minutes",
+            AttributeUnit::Hours => "hours",
+            AttributeUnit::Days => "days",
+            AttributeUnit::Bytes => "bytes",
+            AttributeUnit::Kibibytes => "KiB",
+            AttributeUnit::Mebibytes => "MiB",
+            AttributeUnit::Gibibytes => "GiB",
+            AttributeUnit::Megabytes => "MB",
+            AttributeUnit::Gigabytes => "GB",
+        }
+    }
+
+    /// Inverse of [`Self::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|unit| unit.name() == name)
+    }
+
+    /// Whether this unit measures time (as opposed to data size).
+    pub fn is_time(self) -> bool {
+        matches!(
+            self,
+            AttributeUnit::Seconds
+                | AttributeUnit::Minutes
+                | AttributeUnit::Hours
+                | AttributeUnit::Days
+        )
+    }
+
+    /// Seconds (time units) or bytes (size units) in one of this unit.
+    fn factor(self) -> u64 {
+        match self {
+            AttributeUnit::Seconds | AttributeUnit::Bytes => 1,
+            AttributeUnit::Minutes => 60,
+            AttributeUnit::Hours => 3_600,
+            AttributeUnit::Days => 86_400,
+            AttributeUnit::Kibibytes => 1 << 10,
+            AttributeUnit::Mebibytes => 1 << 20,
+            AttributeUnit::Gibibytes => 1 << 30,
+            AttributeUnit::Megabytes => 1_000_000,
+            AttributeUnit::Gigabytes => 1_000_000_000,
+        }
+    }
+
+    /// Convert a duration or byte-size literal to an integer in this
+    /// unit. Returns `Ok(None)` for any other value, which is left for
+    /// the attribute's type to validate.
+    ///
+    /// Fails when the literal measures the wrong quantity (`5GB` on a
+    /// seconds attribute) or is not a whole number of this unit (`90min`
+    /// on an hours attribute), rather than silently rounding.
+    pub fn convert(self, value: &Value) -> Result<Option<Value>, TypeError> {
+        let (amount, literal, is_time) = match value {
+            Value::Concrete(ConcreteValue::Duration(d)) => {
+                (d.as_secs(), crate::value::render_duration(*d), true)
+            }
+            Value::Concrete(ConcreteValue::ByteSize(n)) => {
+                (*n, crate::value::render_byte_size(*n), false)
+            }
+            _ => return Ok(None),
+        };
+        if is_time != self.is_time() {
+            return Err(TypeError::TypeMismatch {
+                expected: format!("Int ({})", self.name()),
+                got: if is_time { "Duration" } else { "ByteSize" }.to_string(),
+            });
+        }
+        let factor = self.factor();
+        if !amount.is_multiple_of(factor) {
+            return Err(TypeError::ValidationFailed {
+                message: format!("{literal} is not a whole number of {}", self.name()),
+            });
+        }
+        let converted =
+            i64::try_from(amount / factor).map_err(|_| TypeError::ValidationFailed {
+                message: format!("{literal} is too large"),
+            })?;
+        Ok(Some(Value::Concrete(ConcreteValue::Int(converted))))
+    }
+}
+
+impl fmt::Display for AttributeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,96 @@
+//! Read-back properties the compiled schema does not declare.
+//!
+//! A provider that returns attributes absent from its schema (typically a
+//! cloud API that grew new properties after the schema was generated)
+//! gets them ignored by the differ. [`UnknownPropertyReport`] collects
+//! those names per resource type so they can be surfaced — as an error in
+//! strict mode, or as input to schema regeneration.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{SchemaKind, SchemaRegistry};
+use crate::resource::{ResourceId, State};
+
+/// Unknown attribute names grouped by `provider.resource_type`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct UnknownPropertyReport {
+    by_type: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl UnknownPropertyReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every name in `attributes` that the schema for
+    /// `provider`/`resource_type` does not declare. Types without a
+    /// registered schema are skipped — there is nothing to compare
+    /// against — as are internal `_`-prefixed attributes.
+    pub fn record<'a>(
+        &mut self,
+        schemas: &SchemaRegistry,
+        provider: &str,
+        resource_type: &str,
+        attributes: impl IntoIterator<Item = &'a str>,
+    ) {
+        let Some(schema) = schemas.get(provider, resource_type, SchemaKind::Resource) else {
+            return;
+        };
+        let unknown: BTreeSet<String> = attributes
+            .into_iter()
+            .filter(|name| !name.starts_with('_') && !schema.attributes.contains_key(*name))
+            .map(str::to_string)
+            .collect();
+        if unknown.is_empty() {
+            return;
+        }
+        let key = if provider.is_empty() {
+            resource_type.to_string()
+        } else {
+            format!("{provider}.{resource_type}")
+        };
+        self.by_type.entry(key).or_default().extend(unknown);
+    }
+
+    /// Collect unknown properties from refreshed states.
+    pub fn from_states(schemas: &SchemaRegistry, states: &HashMap<ResourceId, State>) -> Self {
+        let mut report = Self::new();
+        for (id, state) in states {
+            if state.exists {
+                report.record(
+                    schemas,
+                    &id.provider,
+                    &id.resource_type,
+                    state.attributes.keys().map(String::as_str),
+                );
+            }
+        }
+        report
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_type.is_empty()
+    }
+
+    /// `(resource type, unknown attribute names)` in type order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BTreeSet<String>)> {
+        self.by_type.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl fmt::Display for UnknownPropertyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (resource_type, names)) in self.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            write!(f, "{}: {}", resource_type, names.join(", "))?;
+        }
+        Ok(())
+    }
+}
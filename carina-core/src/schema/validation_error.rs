@@ -0,0 +1,79 @@
+//! Structured errors for the string-format `check_*` functions.
+//!
+//! Every validator reports the same three things — which value was
+//! rejected, why, and (when it can tell) what the user probably meant —
+//! so [`ValidationError`] carries them as fields and renders them in one
+//! shape: `Invalid <subject> '<input>': <reason>`.
+
+use std::fmt;
+
+/// A value rejected by a `check_*` function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// What kind of value was being validated (`"IPv4 address"`).
+    pub subject: String,
+    /// The rejected value as the user wrote it.
+    pub input: String,
+    /// Why it was rejected (`"expected 4 octets"`).
+    pub reason: String,
+    /// Normalized form of `input` the validator actually checked, when it
+    /// differs from what the user wrote.
+    pub normalized: Option<String>,
+    /// A corrected value to offer the user.
+    pub suggestion: Option<String>,
+}
+
+impl ValidationError {
+    pub fn new(
+        subject: impl Into<String>,
+        input: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            subject: subject.into(),
+            input: input.into(),
+            reason: reason.into(),
+            normalized: None,
+            suggestion: None,
+        }
+    }
+
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Record that the validator checked a normalized form of `original`.
+    /// The current `input` becomes [`Self::normalized`] and `original`
+    /// becomes the reported input, so the message quotes what the user
+    /// typed. A no-op when the two are equal.
+    pub fn with_original_input(mut self, original: impl Into<String>) -> Self {
+        let original = original.into();
+        if original != self.input {
+            self.normalized = Some(std::mem::replace(&mut self.input, original));
+        }
+        self
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid {} '{}'", self.subject, self.input)?;
+        if let Some(normalized) = &self.normalized {
+            write!(f, " (normalized to '{normalized}')")?;
+        }
+        write!(f, ": {}", self.reason)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "; did you mean '{suggestion}'?")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for String {
+    fn from(err: ValidationError) -> Self {
+        err.to_string()
+    }
+}
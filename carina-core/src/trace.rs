@@ -0,0 +1,459 @@
+//! Trace spans for plan and apply, exported as OpenTelemetry traces.
+//!
+//! A run produces one root span (`carina plan` or `carina apply`) with a
+//! child span per effect. Apply spans are recorded by [`TraceRecorder`]
+//! from execution events; wait heartbeats become span events, so a hung
+//! wait shows up as a long span with its last observed attributes.
+//!
+//! Plan span ids are derived from the trace id and the effect's identity
+//! rather than generated, so an `apply --plan` run that only knows the
+//! saved plan's trace id can still link each resource's apply span to its
+//! plan span. [`to_otlp_json`] encodes spans as an OTLP
+//! `ExportTraceServiceRequest` in its JSON form.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value as Json, json};
+
+use crate::effect::Effect;
+use crate::executor::{ExecutionEvent, ExecutionObserver, OperationOutcome};
+use crate::plan::Plan;
+use crate::resource::ResourceId;
+
+/// 16-byte OpenTelemetry trace id, shown and serialized as 32 hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceId([u8; 16]);
+
+impl TraceId {
+    pub fn random() -> Self {
+        Self(*uuid::Uuid::new_v4().as_bytes())
+    }
+
+    pub fn parse(hex: &str) -> Option<Self> {
+        parse_hex(hex).map(Self)
+    }
+}
+
+/// 8-byte OpenTelemetry span id, shown as 16 hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpanId([u8; 8]);
+
+impl SpanId {
+    pub fn random() -> Self {
+        let bytes = uuid::Uuid::new_v4().into_bytes();
+        Self(bytes[..8].try_into().expect("uuid has 16 bytes"))
+    }
+
+    /// Span id of `phase` for the effect with `key` in `trace`. Stable
+    /// across processes, so a later run can link to it.
+    pub fn derive(trace: TraceId, phase: &str, key: &str) -> Self {
+        // FNV-1a: fixed output for a given input, unlike `DefaultHasher`.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in trace
+            .0
+            .iter()
+            .chain(phase.as_bytes())
+            .chain(&[0])
+            .chain(key.as_bytes())
+        {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Self(hash.to_be_bytes())
+    }
+}
+
+fn parse_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+macro_rules! hex_id {
+    ($name:ident) => {
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                parse_hex(&s)
+                    .map(Self)
+                    .ok_or_else(|| serde::de::Error::custom(format!("invalid id `{s}`")))
+            }
+        }
+    };
+}
+
+hex_id!(TraceId);
+hex_id!(SpanId);
+
+/// A timestamped annotation on a span, e.g. a wait heartbeat.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanEvent {
+    pub name: String,
+    pub time: SystemTime,
+    pub attributes: BTreeMap<String, String>,
+}
+
+/// One finished span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanRecord {
+    pub trace_id: TraceId,
+    pub span_id: SpanId,
+    pub parent: Option<SpanId>,
+    pub name: String,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    pub attributes: BTreeMap<String, String>,
+    pub events: Vec<SpanEvent>,
+    /// Spans in this or another trace that this one follows from.
+    pub links: Vec<(TraceId, SpanId)>,
+    /// Set when the span's operation failed.
+    pub error: Option<String>,
+    /// Resource the span operated on; not exported.
+    pub resource: Option<ResourceId>,
+}
+
+impl SpanRecord {
+    pub fn new(trace_id: TraceId, span_id: SpanId, name: impl Into<String>) -> Self {
+        let now = SystemTime::now();
+        Self {
+            trace_id,
+            span_id,
+            parent: None,
+            name: name.into(),
+            start: now,
+            end: now,
+            attributes: BTreeMap::new(),
+            events: Vec::new(),
+            links: Vec::new(),
+            error: None,
+            resource: None,
+        }
+    }
+
+    fn for_effect(trace_id: TraceId, span_id: SpanId, parent: SpanId, effect: &Effect) -> Self {
+        let id = effect.resource_id();
+        let mut span = Self::new(trace_id, span_id, format!("{} {}", effect.kind(), id));
+        span.parent = Some(parent);
+        span.attributes
+            .insert("carina.operation".to_string(), effect.kind().to_string());
+        span.attributes
+            .insert("carina.resource".to_string(), id.to_string());
+        span.attributes
+            .insert("carina.resource_type".to_string(), id.display_type());
+        span.resource = Some(id.clone());
+        span
+    }
+}
+
+/// Span id of the plan root span in `trace`.
+pub fn plan_root_span_id(trace: TraceId) -> SpanId {
+    SpanId::derive(trace, "plan", "")
+}
+
+fn plan_span_id(trace: TraceId, effect: &Effect) -> SpanId {
+    SpanId::derive(trace, "plan", effect.identity().as_str())
+}
+
+/// Spans for a plan computed between `start` and `end`: the root span
+/// and one child per effect, all covering the whole plan because the
+/// differ plans resources together.
+pub fn plan_spans(
+    plan: &Plan,
+    trace: TraceId,
+    start: SystemTime,
+    end: SystemTime,
+) -> Vec<SpanRecord> {
+    let root_id = plan_root_span_id(trace);
+    let mut root = SpanRecord::new(trace, root_id, "carina plan");
+    root.start = start;
+    root.end = end;
+    let mut spans = vec![root];
+    for effect in plan.effects() {
+        let mut span = SpanRecord::for_effect(trace, plan_span_id(trace, effect), root_id, effect);
+        span.name = format!("plan {}", span.name);
+        span.start = start;
+        span.end = end;
+        spans.push(span);
+    }
+    spans
+}
+
+/// Observer wrapper that records an apply span per effect as a child of
+/// `root`. When `plan_trace` is given, each span links to the plan span
+/// of the same effect in that trace.
+pub struct TraceRecorder {
+    inner: Box<dyn ExecutionObserver>,
+    trace: TraceId,
+    root: SpanId,
+    plan_trace: Option<TraceId>,
+    open: Mutex<HashMap<String, SpanRecord>>,
+    spans: Arc<Mutex<Vec<SpanRecord>>>,
+}
+
+impl TraceRecorder {
+    /// Wrap `inner`, returning the recorder and a handle to the spans it
+    /// finishes.
+    pub fn wrap(
+        inner: Box<dyn ExecutionObserver>,
+        trace: TraceId,
+        root: SpanId,
+        plan_trace: Option<TraceId>,
+    ) -> (Self, Arc<Mutex<Vec<SpanRecord>>>) {
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        (
+            Self {
+                inner,
+                trace,
+                root,
+                plan_trace,
+                open: Mutex::new(HashMap::new()),
+                spans: Arc::clone(&spans),
+            },
+            spans,
+        )
+    }
+
+    fn start(&self, effect: &Effect) {
+        let mut span = SpanRecord::for_effect(self.trace, SpanId::random(), self.root, effect);
+        if let Some(plan_trace) = self.plan_trace {
+            span.links
+                .push((plan_trace, plan_span_id(plan_trace, effect)));
+        }
+        self.open
+            .lock()
+            .unwrap()
+            .insert(effect.identity().to_string(), span);
+    }
+
+    fn finish(&self, effect: &Effect, error: Option<&str>) {
+        let Some(mut span) = self.open.lock().unwrap().remove(effect.identity().as_str()) else {
+            return;
+        };
+        span.end = SystemTime::now();
+        span.error = error.map(str::to_string);
+        self.spans.lock().unwrap().push(span);
+    }
+}
+
+impl ExecutionObserver for TraceRecorder {
+    fn on_event(&self, event: &ExecutionEvent) {
+        match event {
+            ExecutionEvent::EffectStarted { effect } => self.start(effect),
+            ExecutionEvent::EffectSucceeded { effect, .. }
+            | ExecutionEvent::EffectPartiallySucceeded { effect, .. } => self.finish(effect, None),
+            ExecutionEvent::EffectFailed { effect, error, .. } => self.finish(effect, Some(error)),
+            ExecutionEvent::WaitPolling {
+                observation,
+                elapsed,
+            } => {
+                if let Some(span) = self.open.lock().unwrap().get_mut(observation.binding()) {
+                    let mut attributes = BTreeMap::from([(
+                        "carina.wait.elapsed_secs".to_string(),
+                        elapsed.as_secs().to_string(),
+                    )]);
+                    if let Some((attr, value)) = observation.primary() {
+                        attributes.insert(
+                            format!("carina.wait.{}", attr.segments().join(".")),
+                            crate::value::format_value_user_facing(value),
+                        );
+                    }
+                    span.events.push(SpanEvent {
+                        name: "wait.poll".to_string(),
+                        time: SystemTime::now(),
+                        attributes,
+                    });
+                }
+            }
+            _ => {}
+        }
+        self.inner.on_event(event);
+    }
+}
+
+/// Add the provider-reported counters of each resource's operation to
+/// its apply span.
+pub fn annotate_operation_metrics(
+    spans: &mut [SpanRecord],
+    outcomes: &HashMap<ResourceId, OperationOutcome>,
+) {
+    for span in spans {
+        let Some(metrics) = span
+            .resource
+            .as_ref()
+            .and_then(|id| outcomes.get(id))
+            .and_then(|o| o.metrics)
+        else {
+            continue;
+        };
+        for (key, value) in [
+            ("carina.api.calls", metrics.api_calls.to_string()),
+            (
+                "carina.api.latency_ms",
+                metrics.api_latency.as_millis().to_string(),
+            ),
+            ("carina.polls", metrics.polls.to_string()),
+            ("carina.retries", metrics.retries.to_string()),
+        ] {
+            span.attributes.insert(key.to_string(), value);
+        }
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+fn otlp_attributes(attributes: &BTreeMap<String, String>) -> Json {
+    attributes
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+        .collect()
+}
+
+/// `spans` as an OTLP `ExportTraceServiceRequest` in its JSON encoding.
+pub fn to_otlp_json(spans: &[SpanRecord]) -> Json {
+    let spans: Vec<Json> = spans
+        .iter()
+        .map(|span| {
+            let mut out = json!({
+                "traceId": span.trace_id.to_string(),
+                "spanId": span.span_id.to_string(),
+                "name": span.name,
+                "kind": 1,
+                "startTimeUnixNano": unix_nanos(span.start),
+                "endTimeUnixNano": unix_nanos(span.end),
+                "attributes": otlp_attributes(&span.attributes),
+                "events": span.events.iter().map(|event| json!({
+                    "name": event.name,
+                    "timeUnixNano": unix_nanos(event.time),
+                    "attributes": otlp_attributes(&event.attributes),
+                })).collect::<Vec<_>>(),
+                "links": span.links.iter().map(|(trace_id, span_id)| json!({
+                    "traceId": trace_id.to_string(),
+                    "spanId": span_id.to_string(),
+                })).collect::<Vec<_>>(),
+            });
+            if let Some(parent) = span.parent {
+                out["parentSpanId"] = json!(parent.to_string());
+            }
+            if let Some(error) = &span.error {
+                out["status"] = json!({ "code": 2, "message": error });
+            }
+            out
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "carina" } }
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "carina", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::ProgressInfo;
+    use crate::resource::{ResolvedResource, Resource};
+    use std::time::Duration;
+
+    struct Silent;
+
+    impl ExecutionObserver for Silent {
+        fn on_event(&self, _event: &ExecutionEvent) {}
+    }
+
+    fn create(name: &str) -> Effect {
+        Effect::Create(ResolvedResource::new(Resource::new("s3.Bucket", name)))
+    }
+
+    #[test]
+    fn ids_round_trip_and_derived_ids_are_stable() {
+        let trace = TraceId::random();
+        assert_eq!(TraceId::parse(&trace.to_string()), Some(trace));
+        assert_eq!(trace.to_string().len(), 32);
+        assert_eq!(
+            SpanId::derive(trace, "plan", "bucket"),
+            SpanId::derive(trace, "plan", "bucket")
+        );
+        assert_ne!(
+            SpanId::derive(trace, "plan", "bucket"),
+            SpanId::derive(trace, "plan", "logs")
+        );
+        assert_eq!(TraceId::parse("zz"), None);
+    }
+
+    #[test]
+    fn apply_spans_link_to_plan_spans() {
+        let plan_trace = TraceId::random();
+        let mut plan = Plan::new();
+        plan.add(create("bucket"));
+        let now = SystemTime::now();
+        let plan_spans = plan_spans(&plan, plan_trace, now, now);
+        assert_eq!(plan_spans.len(), 2);
+
+        let apply_trace = TraceId::random();
+        let root = SpanId::random();
+        let (recorder, spans) =
+            TraceRecorder::wrap(Box::new(Silent), apply_trace, root, Some(plan_trace));
+        let effect = &plan.effects()[0];
+        recorder.on_event(&ExecutionEvent::EffectStarted { effect });
+        recorder.on_event(&ExecutionEvent::EffectFailed {
+            effect,
+            error: "access denied",
+            cause: None,
+            duration: Duration::from_secs(1),
+            progress: ProgressInfo {
+                completed: 1,
+                total: 1,
+            },
+        });
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.parent, Some(root));
+        assert_eq!(span.links, [(plan_trace, plan_spans[1].span_id)]);
+        assert_eq!(span.error.as_deref(), Some("access denied"));
+
+        let doc = to_otlp_json(&spans);
+        let exported = &doc["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(exported["parentSpanId"], root.to_string());
+        assert_eq!(exported["status"]["code"], 2);
+        assert_eq!(
+            exported["links"][0]["spanId"],
+            plan_spans[1].span_id.to_string()
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! Source rewrites for breaking schema changes between releases.
+//!
+//! A regenerated provider schema can rename an attribute or change the
+//! casing of an enum value, which breaks every `.crn` file that used the
+//! old spelling. [`UpgradeRules`] records those changes per resource type,
+//! and [`UpgradeRules::rewrite_source`] applies them to a file's text:
+//!
+//! - attribute renames inside blocks of the affected resource type, both
+//!   `old = value` and `old { ... }` block syntax;
+//! - `binding.old` references to a `let`-bound resource of that type,
+//!   anywhere in the same file;
+//! - enum value mappings on the (renamed) attribute, for bare
+//!   (`Old`), namespaced (`awscc.ec2.Vpc.Tenancy.Old`) and quoted
+//!   (`'Old'`) values.
+//!
+//! The rewrite works on text rather than the AST so comments and layout
+//! survive untouched. State-side renames live with the state types.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A set of upgrade rules, as shipped in a release's rules file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeRules {
+    #[serde(default)]
+    pub rules: Vec<UpgradeRule>,
+}
+
+/// Changes to one resource type introduced by one release.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeRule {
+    /// Release that introduced the change, for reporting.
+    pub version: String,
+    /// Fully-qualified resource type, e.g. `awscc.ec2.Vpc`.
+    pub resource_type: String,
+    /// Old attribute name → new attribute name.
+    #[serde(default)]
+    pub rename_attributes: BTreeMap<String, String>,
+    /// Attribute name (after renames) → old enum value → new enum value.
+    #[serde(default)]
+    pub enum_values: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl UpgradeRules {
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Invalid upgrade rules: {}", e))
+    }
+
+    /// Attribute renames for `resource_type` (`provider.type`), across all
+    /// rules, in rule order.
+    pub fn attribute_renames(&self, resource_type: &str) -> Vec<(&str, &str)> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.resource_type == resource_type)
+            .flat_map(|rule| {
+                rule.rename_attributes
+                    .iter()
+                    .map(|(old, new)| (old.as_str(), new.as_str()))
+            })
+            .collect()
+    }
+
+    fn rename(&self, resource_type: &str, attribute: &str) -> Option<&str> {
+        self.attribute_renames(resource_type)
+            .into_iter()
+            .find(|(old, _)| *old == attribute)
+            .map(|(_, new)| new)
+    }
+
+    fn enum_value(&self, resource_type: &str, attribute: &str, value: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.resource_type == resource_type)
+            .find_map(|rule| rule.enum_values.get(attribute)?.get(value))
+            .map(String::as_str)
+    }
+
+    /// Apply the rules to one `.crn` file's text. Returns the text
+    /// unchanged when nothing matches.
+    pub fn rewrite_source(&self, text: &str) -> String {
+        if self.rules.is_empty() {
+            return text.to_string();
+        }
+        // Resource type of each open block, innermost last; `None` for
+        // blocks that are not resources covered by a rule.
+        let mut blocks: Vec<Option<&str>> = Vec::new();
+        // `let` bindings of covered resources, for reference rewrites.
+        let mut bindings: Vec<(String, &str)> = Vec::new();
+        let mut lines: Vec<String> = Vec::new();
+
+        for line in text.split('\n') {
+            let (code, _) = split_comment(line);
+            let mut rewritten = line.to_string();
+            if let Some(Some(resource_type)) = blocks.last() {
+                rewritten = self.rewrite_attribute_line(line, resource_type);
+            }
+
+            let header = header_resource_type(code).and_then(|(binding, ty)| {
+                let rule = self.rules.iter().find(|rule| rule.resource_type == ty)?;
+                if let Some(binding) = binding {
+                    bindings.push((binding.to_string(), rule.resource_type.as_str()));
+                }
+                Some(rule.resource_type.as_str())
+            });
+            let opens_header = code.trim_end().ends_with('{');
+            let braces: Vec<char> = code_chars(code)
+                .filter(|c| *c == '{' || *c == '}')
+                .collect();
+            for (i, brace) in braces.iter().enumerate() {
+                if *brace == '}' {
+                    blocks.pop();
+                } else if opens_header && i == braces.len() - 1 {
+                    blocks.push(header);
+                } else {
+                    blocks.push(None);
+                }
+            }
+            lines.push(rewritten);
+        }
+
+        let mut result = lines.join("\n");
+        for (binding, resource_type) in &bindings {
+            for (old, new) in self.attribute_renames(resource_type) {
+                result = replace_reference(&result, binding, old, new);
+            }
+        }
+        result
+    }
+
+    /// Rewrite `name = value` / `name {` directly inside a block of
+    /// `resource_type`.
+    fn rewrite_attribute_line(&self, line: &str, resource_type: &str) -> String {
+        let indent = line.len() - line.trim_start().len();
+        let rest = &line[indent..];
+        let name_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if name_len == 0 {
+            return line.to_string();
+        }
+        let name = &rest[..name_len];
+        let after = &rest[name_len..];
+        let after_trimmed = after.trim_start();
+        let is_assignment = after_trimmed.starts_with('=') && !after_trimmed.starts_with("==");
+        if !is_assignment && !after_trimmed.starts_with('{') {
+            return line.to_string();
+        }
+
+        let new_name = self.rename(resource_type, name).unwrap_or(name);
+        let mut after = after.to_string();
+        if is_assignment {
+            after = self.rewrite_enum_value(&after, resource_type, new_name);
+        }
+        format!("{}{}{}", &line[..indent], new_name, after)
+    }
+
+    /// Map the value in `" = Value  # comment"` through the enum rules of
+    /// `attribute`, keeping everything around it.
+    fn rewrite_enum_value(&self, after_name: &str, resource_type: &str, attribute: &str) -> String {
+        let (code, comment) = split_comment(after_name);
+        let eq = code.find('=').expect("assignment");
+        let value_start = eq + 1 + (code[eq + 1..].len() - code[eq + 1..].trim_start().len());
+        let value = code[value_start..].trim_end();
+        let trailing = &code[value_start + value.len()..];
+
+        let replaced = if let Some(inner) = value
+            .strip_prefix('\'')
+            .and_then(|v| v.strip_suffix('\''))
+            .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
+        {
+            self.enum_value(resource_type, attribute, inner)
+                .map(|new| format!("{}{}{}", &value[..1], new, &value[..1]))
+        } else if !value.is_empty()
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            let (prefix, last) = match value.rfind('.') {
+                Some(dot) => (&value[..=dot], &value[dot + 1..]),
+                None => ("", value),
+            };
+            self.enum_value(resource_type, attribute, last)
+                .map(|new| format!("{}{}", prefix, new))
+        } else {
+            None
+        };
+
+        match replaced {
+            Some(new_value) => format!(
+                "{}{}{}{}",
+                &code[..value_start],
+                new_value,
+                trailing,
+                comment
+            ),
+            None => after_name.to_string(),
+        }
+    }
+}
+
+/// `(binding, resource_type)` for a block header such as
+/// `let vpc = awscc.ec2.Vpc {` or `read awscc.ec2.Vpc {`.
+fn header_resource_type(code: &str) -> Option<(Option<&str>, &str)> {
+    let header = code.trim();
+    let header = header.strip_suffix('{')?.trim_end();
+    let (binding, rhs) = match header.strip_prefix("let ") {
+        Some(rest) => {
+            let (name, rhs) = rest.split_once('=')?;
+            (Some(name.trim()), rhs.trim())
+        }
+        None => (None, header),
+    };
+    let rhs = rhs.strip_prefix("read ").unwrap_or(rhs).trim();
+    (!rhs.is_empty() && !rhs.contains(char::is_whitespace)).then_some((binding, rhs))
+}
+
+/// Split a line at its `#` / `//` comment, ignoring markers in strings.
+fn split_comment(line: &str) -> (&str, &str) {
+    let mut quote: Option<char> = None;
+    let mut prev = '\0';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q && prev != '\\' => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if c == '#' => return (&line[..i], &line[i..]),
+            None if c == '/' && line[i + 1..].starts_with('/') => return (&line[..i], &line[i..]),
+            None => {}
+        }
+        prev = c;
+    }
+    (line, "")
+}
+
+/// Characters of `code` outside string literals.
+fn code_chars(code: &str) -> impl Iterator<Item = char> + '_ {
+    let mut quote: Option<char> = None;
+    let mut prev = '\0';
+    code.chars().filter(move |&c| {
+        let outside = match quote {
+            Some(q) => {
+                if c == q && prev != '\\' {
+                    quote = None;
+                }
+                false
+            }
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                false
+            }
+            None => true,
+        };
+        prev = c;
+        outside
+    })
+}
+
+/// Replace `binding.old` with `binding.new` where both ends fall on
+/// identifier boundaries.
+fn replace_reference(text: &str, binding: &str, old: &str, new: &str) -> String {
+    let needle = format!("{}.{}", binding, old);
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(&needle) {
+        let before = rest[..pos].chars().next_back();
+        let after = rest[pos + needle.len()..].chars().next();
+        let bounded =
+            !before.is_some_and(|c| is_ident(c) || c == '.') && !after.is_some_and(is_ident);
+        result.push_str(&rest[..pos]);
+        if bounded {
+            result.push_str(binding);
+            result.push('.');
+            result.push_str(new);
+        } else {
+            result.push_str(&needle);
+        }
+        rest = &rest[pos + needle.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> UpgradeRules {
+        UpgradeRules::from_json(
+            r#"{
+              "rules": [
+                {
+                  "version": "0.9.0",
+                  "resource_type": "awscc.ec2.Vpc",
+                  "rename_attributes": { "cidr": "cidr_block", "dns": "dns_settings" },
+                  "enum_values": { "instance_tenancy": { "Default": "default" } }
+                }
+              ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn renames_attributes_and_references() {
+        let source = "\
+let vpc = awscc.ec2.Vpc {
+  cidr = '10.0.0.0/16'  # main range
+  dns {
+    cidr = 'kept'
+  }
+}
+
+awscc.ec2.Subnet {
+  cidr       = '10.0.1.0/24'
+  vpc_cidr   = vpc.cidr
+  other      = vpc.cidr_extra
+}
+";
+        let expected = "\
+let vpc = awscc.ec2.Vpc {
+  cidr_block = '10.0.0.0/16'  # main range
+  dns_settings {
+    cidr = 'kept'
+  }
+}
+
+awscc.ec2.Subnet {
+  cidr       = '10.0.1.0/24'
+  vpc_cidr   = vpc.cidr_block
+  other      = vpc.cidr_extra
+}
+";
+        assert_eq!(rules().rewrite_source(source), expected);
+    }
+
+    #[test]
+    fn maps_enum_values_in_every_spelling() {
+        let source = "\
+awscc.ec2.Vpc {
+  instance_tenancy = awscc.ec2.Vpc.InstanceTenancy.Default
+}
+
+awscc.ec2.Vpc {
+  instance_tenancy = Default
+}
+
+awscc.ec2.Vpc {
+  instance_tenancy = 'Default' # quoted
+}
+";
+        let rewritten = rules().rewrite_source(source);
+        assert!(rewritten.contains("= awscc.ec2.Vpc.InstanceTenancy.default\n"));
+        assert!(rewritten.contains("= default\n"));
+        assert!(rewritten.contains("= 'default' # quoted\n"));
+    }
+
+    #[test]
+    fn leaves_other_resource_types_and_unmatched_text_alone() {
+        let source = "\
+let vpc = awscc.ec2.VpcEndpoint {
+  cidr = '10.0.0.0/16'
+}
+
+let subnets = for az in azs {
+  awscc.ec2.Vpc {
+    cidr = 'x'  // nested resources are covered too
+  }
+}
+";
+        let rewritten = rules().rewrite_source(source);
+        assert!(
+            rewritten.starts_with("let vpc = awscc.ec2.VpcEndpoint {\n  cidr = '10.0.0.0/16'\n")
+        );
+        assert!(rewritten.contains("    cidr_block = 'x'  // nested resources are covered too\n"));
+        assert_eq!(rewritten.lines().count(), source.lines().count());
+    }
+
+    #[test]
+    fn rewrite_is_idempotent_and_empty_rules_are_a_no_op() {
+        let source = "let vpc = awscc.ec2.Vpc {\n  cidr = '10.0.0.0/16'\n}\n";
+        let once = rules().rewrite_source(source);
+        assert_eq!(rules().rewrite_source(&once), once);
+        assert_eq!(UpgradeRules::default().rewrite_source(source), source);
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        let err = UpgradeRules::from_json(r#"{"rules": [{"resource_type": "x"}]}"#).unwrap_err();
+        assert!(err.contains("Invalid upgrade rules"), "{err}");
+    }
+}
@@ -712,6 +712,7 @@ fn walk_value_against_type(
         | Value::Concrete(ConcreteValue::Float(_))
         | Value::Concrete(ConcreteValue::Bool(_))
         | Value::Concrete(ConcreteValue::Duration(_))
+        | Value::Concrete(ConcreteValue::ByteSize(_))
         | Value::Concrete(ConcreteValue::StringList(_))
         | Value::Deferred(DeferredValue::Unknown(_)) => {}
         // `BindingRef` carries no attribute, so there is nothing to
@@ -208,6 +208,7 @@ fn collect_unsynchronized_refs(
             | ConcreteValue::Float(_)
             | ConcreteValue::Bool(_)
             | ConcreteValue::Duration(_)
+            | ConcreteValue::ByteSize(_)
             | ConcreteValue::EnumIdentifier(_)
             | ConcreteValue::CanonicalEnum(_)
             | ConcreteValue::StringList(_),
@@ -341,6 +341,9 @@ fn infer_type_from_value_with_visiting(
         Value::Concrete(ConcreteValue::Float(_)) => Ok(TypeExpr::Float),
         Value::Concrete(ConcreteValue::Bool(_)) => Ok(TypeExpr::Bool),
         Value::Concrete(ConcreteValue::Duration(_)) => Ok(TypeExpr::Duration),
+        // Byte sizes have no type-expression of their own; they only
+        // appear as literals for unit-annotated `Int` attributes.
+        Value::Concrete(ConcreteValue::ByteSize(_)) => Ok(TypeExpr::Int),
         Value::Deferred(DeferredValue::Interpolation(_)) => Ok(TypeExpr::String),
         Value::Deferred(DeferredValue::Secret(_)) => Ok(TypeExpr::String),
         Value::Concrete(ConcreteValue::List(items)) => {
@@ -2,6 +2,8 @@
 
 pub mod deferred_populate;
 pub mod depends_on;
+pub mod naming;
+pub mod reference_constraints;
 pub mod wait;
 
 use std::collections::{HashMap, HashSet};
@@ -288,8 +290,10 @@ pub fn validate_resource_ref_types<E>(
 
                 // Directional check: source (the referenced attribute, post
                 // path narrowing) must be assignable to the sink (the
-                // current resource's attribute).
-                if narrowed.is_assignable_to(&attr_schema.attr_type) {
+                // current resource's attribute). Identities are compared
+                // across provider families so `aws` and `awscc` resources
+                // can reference each other.
+                if narrowed.is_assignable_to_in(&attr_schema.attr_type, registry) {
                     continue;
                 }
 
@@ -879,6 +883,9 @@ pub fn validate_argument_custom_types<E: crate::parser::ExportParamLike>(
     for arg in &parsed.arguments {
         collect_unknown_simple_types_in(&arg.type_expr, config, "argument", &arg.name, &mut errors);
     }
+    for var in &parsed.variable_params {
+        collect_unknown_simple_types_in(&var.type_expr, config, "variable", &var.name, &mut errors);
+    }
     for ap in &parsed.attribute_params {
         if let Some(ty) = &ap.type_expr {
             collect_unknown_simple_types_in(ty, config, "attribute", &ap.name, &mut errors);
@@ -977,6 +984,12 @@ pub fn resolve_file_type_exprs<E: crate::parser::ExportParamLike>(
             Err(e) => errors.push(format!("argument '{}': {e}", arg.name)),
         }
     }
+    for var in &mut parsed.variable_params {
+        match resolve_type_expr(&var.type_expr, config) {
+            Ok(resolved) => var.type_expr = resolved,
+            Err(e) => errors.push(format!("variable '{}': {e}", var.name)),
+        }
+    }
     for ap in &mut parsed.attribute_params {
         if let Some(ty) = &ap.type_expr {
             match resolve_type_expr(ty, config) {
@@ -0,0 +1,398 @@
+//! Plan-time enforcement of the project `naming { ... }` policy.
+//!
+//! The policy applies to the name a resource is known by in the cloud:
+//! the schema's unique-name attribute (`bucket_name`, `role_name`, ...)
+//! and, when present, the `Name` tag. Values not yet known at plan time
+//! are skipped.
+//!
+//! Both policy forms are compiled once, when the `naming` block is
+//! parsed. A `pattern` is a regex. A `template` is matched part by part:
+//! placeholders bound in the block (`env = "prd"`) match their value
+//! literally, `{type}` matches the resource type in kebab case
+//! (`s3.Bucket` -> `bucket`), and every other placeholder matches one or
+//! more lowercase kebab-case words.
+
+use std::fmt;
+
+use indexmap::IndexMap;
+use regex::Regex;
+
+use crate::parser::NamingPolicy;
+use crate::parser::pascal_to_snake;
+use crate::resource::{ConcreteValue, Resource, ResourceId, Value};
+use crate::schema::{SchemaRegistry, UniqueNameSpec};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Placeholder(String),
+}
+
+/// A compiled `pattern = "<regex>"` policy.
+#[derive(Debug, Clone)]
+pub struct NamingPattern(Regex);
+
+impl NamingPattern {
+    /// Compile `pattern`, rejecting invalid regexes.
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|e| format!("invalid pattern: {e}"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl PartialEq for NamingPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// A compiled `template = "{env}-{app}-{name}"` policy with the literal
+/// values bound to some of its placeholders (`env = "prd"`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamingTemplate {
+    template: String,
+    parts: Vec<Part>,
+    values: IndexMap<String, String>,
+}
+
+impl NamingTemplate {
+    /// Compile `template`, rejecting malformed placeholders and values
+    /// for placeholders the template does not have.
+    pub fn new(template: &str, values: IndexMap<String, String>) -> Result<Self, String> {
+        let parts = parse_template(template)?;
+        if let Some(unused) = values.keys().find(|key| {
+            !parts
+                .iter()
+                .any(|p| matches!(p, Part::Placeholder(name) if name == *key))
+        }) {
+            return Err(format!(
+                "'{unused}' is not a placeholder in template '{template}'"
+            ));
+        }
+        Ok(Self {
+            template: template.to_string(),
+            parts,
+            values,
+        })
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    pub fn values(&self) -> &IndexMap<String, String> {
+        &self.values
+    }
+
+    /// The fixed text a part stands for, or `None` for a free placeholder.
+    fn fixed<'a>(&'a self, part: &'a Part, type_segment: &'a str) -> Option<&'a str> {
+        match part {
+            Part::Literal(text) => Some(text),
+            Part::Placeholder(key) => match self.values.get(key) {
+                Some(value) => Some(value),
+                None if key == "type" => Some(type_segment),
+                None => None,
+            },
+        }
+    }
+
+    fn matches_parts(&self, parts: &[Part], type_segment: &str, name: &str) -> bool {
+        let Some((part, rest)) = parts.split_first() else {
+            return name.is_empty();
+        };
+        match self.fixed(part, type_segment) {
+            Some(text) => name
+                .strip_prefix(text)
+                .is_some_and(|tail| self.matches_parts(rest, type_segment, tail)),
+            None => (1..=name.len()).any(|end| {
+                name.is_char_boundary(end)
+                    && is_segment(&name[..end])
+                    && self.matches_parts(rest, type_segment, &name[end..])
+            }),
+        }
+    }
+}
+
+impl NamingPolicy {
+    /// Whether `name` satisfies the policy for a resource of
+    /// `resource_type`.
+    pub fn matches(&self, resource_type: &str, name: &str) -> bool {
+        match self {
+            NamingPolicy::Pattern(pattern) => pattern.0.is_match(name),
+            NamingPolicy::Template(template) => {
+                template.matches_parts(&template.parts, &type_segment(resource_type), name)
+            }
+        }
+    }
+
+    /// A compliant name derived from `name`, when the template leaves
+    /// nothing but `{name}` to fill in. `None` for regex policies.
+    pub fn suggest(&self, resource_type: &str, name: &str) -> Option<String> {
+        let NamingPolicy::Template(template) = self else {
+            return None;
+        };
+        let slug = slug(name);
+        if slug.is_empty() {
+            return None;
+        }
+        let type_segment = type_segment(resource_type);
+        let mut out = String::new();
+        for part in &template.parts {
+            match (template.fixed(part, &type_segment), part) {
+                (Some(text), _) => out.push_str(text),
+                (None, Part::Placeholder(key)) if key == "name" => out.push_str(&slug),
+                (None, _) => return None,
+            }
+        }
+        self.matches(resource_type, &out).then_some(out)
+    }
+}
+
+/// A resource name that does not satisfy the naming policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamingViolation {
+    pub resource: ResourceId,
+    /// The offending attribute: the unique-name attribute or `tags.Name`.
+    pub attribute: String,
+    pub value: String,
+    /// A compliant replacement, when one can be derived.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for NamingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} '{}' does not match the naming policy",
+            self.resource, self.attribute, self.value
+        )?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean '{suggestion}'?)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Check every resource's unique-name attribute and `Name` tag against
+/// `policy`. Resources without a schema in `registry` are checked on their
+/// `Name` tag only.
+pub fn check_naming(
+    resources: &[Resource],
+    registry: &SchemaRegistry,
+    policy: &NamingPolicy,
+) -> Vec<NamingViolation> {
+    let mut violations = Vec::new();
+    for resource in resources {
+        let resource_type = &resource.id.resource_type;
+        let mut check = |attribute: String, value: &str| {
+            if !policy.matches(resource_type, value) {
+                violations.push(NamingViolation {
+                    resource: resource.id.clone(),
+                    attribute,
+                    value: value.to_string(),
+                    suggestion: policy.suggest(resource_type, value),
+                });
+            }
+        };
+        if let Some(schema) = registry.get_for(resource)
+            && let UniqueNameSpec::Attribute(attr) = &schema.unique_name
+            && let Some(Value::Concrete(ConcreteValue::String(name))) = resource.get_attr(attr)
+        {
+            check(attr.clone(), name);
+        }
+        if let Some(Value::Concrete(ConcreteValue::Map(tags))) = resource.get_attr("tags")
+            && let Some(Value::Concrete(ConcreteValue::String(name))) = tags.get("Name")
+        {
+            check("tags.Name".to_string(), name);
+        }
+    }
+    violations
+}
+
+fn parse_template(template: &str) -> Result<Vec<Part>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            parts.push(Part::Literal(rest[..open].to_string()));
+        }
+        let after = &rest[open + 1..];
+        let close = after
+            .find('}')
+            .ok_or_else(|| format!("unclosed '{{' in template '{template}'"))?;
+        let key = &after[..close];
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(format!(
+                "invalid placeholder '{{{key}}}' in template '{template}'"
+            ));
+        }
+        parts.push(Part::Placeholder(key.to_string()));
+        rest = &after[close + 1..];
+    }
+    if rest.contains('}') {
+        return Err(format!("unmatched '}}' in template '{template}'"));
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Literal(rest.to_string()));
+    }
+    Ok(parts)
+}
+
+/// `s3.Bucket` -> `bucket`, `ec2.SecurityGroup` -> `security-group`.
+fn type_segment(resource_type: &str) -> String {
+    let last = resource_type.rsplit('.').next().unwrap_or(resource_type);
+    pascal_to_snake(last).replace('_', "-")
+}
+
+/// Whether `s` is one or more lowercase kebab-case words.
+fn is_segment(s: &str) -> bool {
+    s.split('-').all(|word| {
+        !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    })
+}
+
+/// Lowercase kebab-case form of an arbitrary name.
+fn slug(name: &str) -> String {
+    name.to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{AttributeSchema, AttributeType, ResourceSchema};
+    use indexmap::IndexMap;
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    fn template(template: &str, values: &[(&str, &str)]) -> Result<NamingPolicy, String> {
+        NamingTemplate::new(
+            template,
+            values
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+        .map(NamingPolicy::Template)
+    }
+
+    fn registry() -> SchemaRegistry {
+        let mut registry = SchemaRegistry::new();
+        registry.insert(
+            "awscc",
+            ResourceSchema::new("s3.Bucket")
+                .attribute(AttributeSchema::new("bucket_name", AttributeType::string()))
+                .attribute(AttributeSchema::new(
+                    "tags",
+                    AttributeType::map(AttributeType::string()),
+                ))
+                .with_unique_name_attribute("bucket_name"),
+        );
+        registry
+    }
+
+    fn bucket(name: &str, name_tag: Option<&str>) -> Resource {
+        let mut bucket = Resource::with_provider("awscc", "s3.Bucket", "logs", None);
+        bucket.set_attr("bucket_name", string(name));
+        if let Some(tag) = name_tag {
+            bucket.set_attr(
+                "tags",
+                Value::Concrete(ConcreteValue::Map(IndexMap::from([(
+                    "Name".to_string(),
+                    string(tag),
+                )]))),
+            );
+        }
+        bucket
+    }
+
+    #[test]
+    fn template_binds_values_and_type() {
+        let matcher = template("{env}-{type}-{name}", &[("env", "prd")]).unwrap();
+        assert!(matcher.matches("s3.Bucket", "prd-bucket-access-logs"));
+        assert!(matcher.matches("ec2.SecurityGroup", "prd-security-group-web"));
+        assert!(!matcher.matches("s3.Bucket", "dev-bucket-access-logs"));
+        assert!(!matcher.matches("s3.Bucket", "prd-bucket-"));
+        assert!(!matcher.matches("s3.Bucket", "prd-bucket-Logs"));
+        // A free placeholder may span several words on either side of
+        // a fixed part.
+        let matcher = template("{app}-{type}-{name}", &[]).unwrap();
+        assert!(matcher.matches("s3.Bucket", "web-shop-bucket-access-logs"));
+        assert!(!matcher.matches("s3.Bucket", "web-shop-access-logs"));
+    }
+
+    #[test]
+    fn malformed_policies_are_rejected() {
+        assert!(NamingPattern::new("(").is_err());
+        assert!(template("{env-{name}", &[]).is_err());
+        assert!(template("{Env}-{name}", &[]).is_err());
+        assert!(template("{name}}", &[]).is_err());
+        let err = template("{name}", &[("env", "prd")]).unwrap_err();
+        assert!(err.contains("'env' is not a placeholder"), "{err}");
+    }
+
+    #[test]
+    fn violations_cover_unique_name_and_name_tag() {
+        let policy = template("{env}-{type}-{name}", &[("env", "prd")]).unwrap();
+        let ok = check_naming(
+            &[bucket("prd-bucket-logs", Some("prd-bucket-logs"))],
+            &registry(),
+            &policy,
+        );
+        assert!(ok.is_empty(), "{ok:?}");
+
+        let violations = check_naming(
+            &[bucket("AccessLogs", Some("prd-bucket-logs"))],
+            &registry(),
+            &policy,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].attribute, "bucket_name");
+        assert_eq!(
+            violations[0].suggestion.as_deref(),
+            Some("prd-bucket-accesslogs")
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "awscc.s3.Bucket.logs: bucket_name 'AccessLogs' does not match the naming policy \
+             (did you mean 'prd-bucket-accesslogs'?)"
+        );
+
+        let violations = check_naming(
+            &[bucket("prd-bucket-logs", Some("Logs"))],
+            &registry(),
+            &policy,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].attribute, "tags.Name");
+    }
+
+    #[test]
+    fn pattern_policy_has_no_suggestion_and_skips_unknown_values() {
+        let policy = NamingPolicy::Pattern(NamingPattern::new("^prd-").unwrap());
+        let violations = check_naming(&[bucket("dev-logs", None)], &registry(), &policy);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].suggestion, None);
+
+        let mut deferred = bucket("dev-logs", None);
+        deferred.set_attr("bucket_name", Value::resource_ref("other", "name", vec![]));
+        assert!(check_naming(&[deferred], &registry(), &policy).is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+//! Plan-time evaluation of schema constraints that span two resources.
+//!
+//! [`AttributeConstraint::CidrWithinReference`] compares a resource's
+//! CIDR block with an attribute of the resource one of its references
+//! points at, so it cannot run inside `ResourceSchema::validate`. The plan
+//! path evaluates it here once the bindings view is built, which lets the
+//! referenced value come from the same configuration or, when the DSL
+//! leaves it out (IPAM-allocated VPCs), from state.
+//!
+//! Values not yet known at plan time are skipped: the check only reports
+//! blocks it can prove disjoint, so a pass here is not a guarantee.
+//!
+//! Findings are warnings, not errors. The check only sees the one
+//! referenced attribute, while the referenced resource may hold more
+//! ranges than that: a subnet can sit in a secondary CIDR block
+//! associated with its VPC through a separate resource or outside
+//! Carina.
+
+use crate::binding_index::ResolvedBindings;
+use crate::resolver::resolve_ref_value;
+use crate::resource::{ConcreteValue, DeferredValue, Resource, Value};
+use crate::schema::{AttributeConstraint, SchemaRegistry, cidr_contains};
+
+/// Evaluate every cross-resource constraint declared on the schemas of
+/// `resources` against `bindings` and return a warning per violation.
+/// `resources` must still hold their `ResourceRef` values (the unresolved
+/// set), since the reference is what names the other resource.
+pub fn check_reference_constraints(
+    resources: &[Resource],
+    bindings: &ResolvedBindings,
+    registry: &SchemaRegistry,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for resource in resources {
+        let Some(schema) = registry.get_for(resource) else {
+            continue;
+        };
+        for constraint in &schema.constraints {
+            let AttributeConstraint::CidrWithinReference {
+                attribute,
+                reference,
+                referenced_attribute,
+            } = constraint
+            else {
+                continue;
+            };
+            let Some(Value::Deferred(DeferredValue::ResourceRef { path })) =
+                resource.attributes.get(reference)
+            else {
+                continue;
+            };
+            let Some(inner) = resource
+                .attributes
+                .get(attribute)
+                .and_then(|v| known_string(v, bindings))
+            else {
+                continue;
+            };
+            let Some(outer) = bindings
+                .get(path.binding())
+                .and_then(|attrs| attrs.get(referenced_attribute))
+                .and_then(|v| known_string(v, bindings))
+            else {
+                continue;
+            };
+            if !cidr_contains(&outer, &inner) {
+                warnings.push(format!(
+                    "{}: {} '{}' is not within {}.{} '{}'; the create fails unless the range is added to {} in another way",
+                    resource.id,
+                    attribute,
+                    inner,
+                    path.binding(),
+                    referenced_attribute,
+                    outer,
+                    path.binding(),
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+fn known_string(value: &Value, bindings: &ResolvedBindings) -> Option<String> {
+    match resolve_ref_value(value, bindings).ok()? {
+        Value::Concrete(ConcreteValue::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding_index::PreApplyInputs;
+    use crate::resource::{PlanInputState, ResourceId, State};
+    use crate::schema::{AttributeSchema, AttributeType, ResourceSchema};
+    use std::collections::HashMap;
+
+    fn string(s: &str) -> Value {
+        Value::Concrete(ConcreteValue::String(s.to_string()))
+    }
+
+    fn registry() -> SchemaRegistry {
+        let mut registry = SchemaRegistry::new();
+        registry.insert(
+            "awscc",
+            ResourceSchema::new("ec2.Vpc")
+                .attribute(AttributeSchema::new("cidr_block", AttributeType::string())),
+        );
+        registry.insert(
+            "awscc",
+            ResourceSchema::new("ec2.Subnet")
+                .attribute(AttributeSchema::new("cidr_block", AttributeType::string()))
+                .attribute(AttributeSchema::new("vpc_id", AttributeType::string()))
+                .cidr_within_reference("cidr_block", "vpc_id", "cidr_block"),
+        );
+        registry
+    }
+
+    fn vpc(cidr_block: Option<&str>) -> Resource {
+        let mut vpc = Resource::with_provider("awscc", "ec2.Vpc", "vpc", None);
+        vpc.binding = Some("vpc".to_string());
+        if let Some(cidr) = cidr_block {
+            vpc.set_attr("cidr_block", string(cidr));
+        }
+        vpc
+    }
+
+    fn subnet(cidr_block: &str) -> Resource {
+        let mut subnet = Resource::with_provider("awscc", "ec2.Subnet", "subnet", None);
+        subnet.set_attr("cidr_block", string(cidr_block));
+        subnet.set_attr("vpc_id", Value::resource_ref("vpc", "vpc_id", vec![]));
+        subnet
+    }
+
+    fn check(resources: &[Resource], states: &HashMap<ResourceId, PlanInputState>) -> Vec<String> {
+        let bindings = ResolvedBindings::pre_apply(PreApplyInputs {
+            managed: resources,
+            compositions: &[],
+            data_sources: &[],
+            current_states: states,
+            remote_bindings: &HashMap::new(),
+            wait_aliases: &[],
+        });
+        check_reference_constraints(resources, &bindings, &registry())
+    }
+
+    #[test]
+    fn subnet_outside_vpc_in_same_config_is_reported() {
+        let inside = check(
+            &[vpc(Some("10.0.0.0/16")), subnet("10.0.1.0/24")],
+            &HashMap::new(),
+        );
+        assert!(inside.is_empty(), "{inside:?}");
+
+        let outside = check(
+            &[vpc(Some("10.0.0.0/16")), subnet("10.1.0.0/24")],
+            &HashMap::new(),
+        );
+        assert_eq!(outside.len(), 1);
+        assert!(
+            outside[0]
+                .contains("cidr_block '10.1.0.0/24' is not within vpc.cidr_block '10.0.0.0/16'"),
+            "{}",
+            outside[0]
+        );
+    }
+
+    #[test]
+    fn vpc_cidr_falls_back_to_state() {
+        let vpc = vpc(None);
+        let state = State::existing(
+            vpc.id.clone(),
+            HashMap::from([("cidr_block".to_string(), string("172.16.0.0/16"))]),
+        );
+        let states = HashMap::from([(vpc.id.clone(), state.into_plan_input())]);
+
+        let errors = check(&[vpc, subnet("10.0.1.0/24")], &states);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+    }
+
+    #[test]
+    fn unknown_vpc_cidr_is_skipped() {
+        assert!(check(&[vpc(None), subnet("10.0.1.0/24")], &HashMap::new()).is_empty());
+    }
+}
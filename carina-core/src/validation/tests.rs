@@ -14,8 +14,10 @@ fn empty_parsed() -> ParsedFile {
         module_calls: Vec::new(),
         arguments: Vec::new(),
         attribute_params: Vec::new(),
+        variable_params: vec![],
         export_params: vec![],
         backend: None,
+        naming: None,
         state_blocks: Vec::new(),
         user_functions: HashMap::new(),
         upstream_states: Vec::new(),
@@ -526,6 +528,7 @@ fn binding_used_inside_for_body_is_not_flagged_as_unused() {
 }
 
 /// Helper to create a simple ResourceSchema with given attributes.
+#[allow(deprecated)]
 fn make_schema(resource_type: &str, attrs: Vec<(&str, AttributeType)>) -> ResourceSchema {
     let mut attributes = HashMap::new();
     for (name, attr_type) in attrs {
@@ -546,6 +549,8 @@ fn make_schema(resource_type: &str, attrs: Vec<(&str, AttributeType)>) -> Resour
                 write_only: false,
                 identity: false,
                 deferred_populate: false,
+                unit: None,
+                list_key: None,
             },
         );
     }
@@ -557,9 +562,11 @@ fn make_schema(resource_type: &str, attrs: Vec<(&str, AttributeType)>) -> Resour
         kind: crate::schema::SchemaKind::Resource,
         unique_name: crate::schema::UniqueNameSpec::Conflicting,
         operation_config: None,
+        constraints: Vec::new(),
         exclusive_required: Vec::new(),
         default_wait_timeout: None,
         default_wait_interval: None,
+        diff_suppressors: Vec::new(),
         defs: std::collections::BTreeMap::new(),
     }
 }
@@ -1043,6 +1050,7 @@ fn provider_in_module_with_arguments_errors() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1073,6 +1081,7 @@ fn provider_in_module_with_attributes_errors() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1099,6 +1108,7 @@ fn provider_without_module_markers_ok() {
         name: "awscc".to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -1143,6 +1153,7 @@ fn provider(name: &str) -> crate::parser::ProviderConfig {
         name: name.to_string(),
         attributes: IndexMap::new(),
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -3003,7 +3014,7 @@ fn validate_resources_accepts_resource_ref_in_map_position() {
 }
 
 /// `validate_resources` must reject a resource whose schema declares an
-/// `exclusive_required` group that is not satisfied — mirrors
+/// `exactly_one_of` constraint that is not satisfied — mirrors
 /// `awscc.ec2.Vpc {}` with no cidr_block / ipam pool.
 #[test]
 fn validate_resources_rejects_missing_exclusive_required() {
@@ -3014,7 +3025,7 @@ fn validate_resources_rejects_missing_exclusive_required() {
             ("ipv4_ipam_pool_id", AttributeType::string()),
         ],
     )
-    .exclusive_required(&["cidr_block", "ipv4_ipam_pool_id"]);
+    .exactly_one_of(&["cidr_block", "ipv4_ipam_pool_id"]);
 
     let mut schemas = SchemaRegistry::new();
     schemas.insert("awscc", schema);
@@ -3622,6 +3633,7 @@ fn validate_provider_config_skips_attributes_with_deferred_refs() {
         name: "aws".to_string(),
         attributes: attrs,
         default_tags: IndexMap::new(),
+        provenance_tags: false,
         source: None,
         version: None,
         revision: None,
@@ -3911,3 +3923,67 @@ fn resolve_type_expr_accepts_registered_resource_ref() {
         "registered resource refs such as aws.vpc must keep resolving as Ref"
     );
 }
+
+fn vpc_id_type(provider: &str) -> AttributeType {
+    AttributeType::refined_string(
+        Some(TypeIdentity::new(Some(provider), ["ec2", "Vpc"], "VpcId")),
+        None,
+        None,
+        None,
+    )
+}
+
+/// An `aws` subnet referencing an `awscc` VPC and an `awscc` subnet
+/// referencing an `aws` VPC.
+fn cross_provider_vpc_refs() -> (SchemaRegistry, ParsedFile) {
+    let mut schemas = SchemaRegistry::new();
+    for provider in ["aws", "awscc"] {
+        schemas.insert(
+            provider,
+            make_schema("ec2.Vpc", vec![("vpc_id", vpc_id_type(provider))]),
+        );
+        schemas.insert(
+            provider,
+            make_schema("ec2.Subnet", vec![("vpc_id", vpc_id_type(provider))]),
+        );
+    }
+
+    let mut parsed = empty_parsed();
+    for (provider, other) in [("aws", "awscc"), ("awscc", "aws")] {
+        let vpc_binding = format!("{provider}_vpc");
+        let vpc = Resource::with_provider(provider, "ec2.Vpc", &vpc_binding, None)
+            .with_binding(&vpc_binding);
+        let subnet = Resource::with_provider(other, "ec2.Subnet", format!("{other}_subnet"), None)
+            .with_attribute(
+                "vpc_id",
+                Value::resource_ref(vpc_binding, "vpc_id".to_string(), vec![]),
+            );
+        parsed.resources.push(vpc); // allow: direct — fixture test inspection
+        parsed.resources.push(subnet); // allow: direct — fixture test inspection
+    }
+    (schemas, parsed)
+}
+
+#[test]
+fn cross_provider_ref_requires_shared_provider_family() {
+    let (schemas, parsed) = cross_provider_vpc_refs();
+    let err = validate_resource_ref_types_for_test(&parsed, &schemas, &HashSet::new())
+        .expect_err("unrelated providers keep their types apart");
+    assert!(
+        err.contains("aws.ec2.Subnet.aws_subnet: cannot assign awscc.ec2.Vpc.VpcId"),
+        "{err}"
+    );
+    assert!(
+        err.contains("awscc.ec2.Subnet.awscc_subnet: cannot assign aws.ec2.Vpc.VpcId"),
+        "{err}"
+    );
+}
+
+#[test]
+fn cross_provider_ref_within_provider_family_is_accepted() {
+    let (mut schemas, parsed) = cross_provider_vpc_refs();
+    schemas.set_provider_family("aws", "aws");
+    schemas.set_provider_family("awscc", "aws");
+    let result = validate_resource_ref_types_for_test(&parsed, &schemas, &HashSet::new());
+    assert!(result.is_ok(), "{result:?}");
+}
@@ -124,6 +124,7 @@ impl std::fmt::Display for UnknownReason {
             UnknownReason::PostCreateReadIncomplete { detail } => {
                 write!(f, "post-create read failed: {detail}")
             }
+            UnknownReason::ForGroup { binding } => write!(f, "deferred for-expression {binding}"),
         }
     }
 }
@@ -149,6 +150,7 @@ pub fn render_unknown(reason: &UnknownReason) -> String {
         UnknownReason::PostCreateReadIncomplete { detail } => {
             format!("(known after next apply: post-create read failed — {detail})")
         }
+        UnknownReason::ForGroup { binding } => format!("(known after upstream apply: {binding})"),
     }
 }
 
@@ -248,6 +250,7 @@ pub fn value_to_json_with_context(
         Value::Concrete(ConcreteValue::Duration(d)) => {
             Ok(serde_json::Value::Number((d.as_secs() as i64).into()))
         }
+        Value::Concrete(ConcreteValue::ByteSize(n)) => Ok(serde_json::Value::Number((*n).into())),
         Value::Concrete(ConcreteValue::Float(f)) => {
             let num =
                 serde_json::Number::from_f64(*f).ok_or(SerializationError::NonFiniteFloat {
@@ -533,6 +536,24 @@ pub fn render_duration(d: std::time::Duration) -> String {
     format!("{secs}s")
 }
 
+/// Render a byte size to its canonical surface form.
+///
+/// Picks the largest binary unit (`TiB`, `GiB`, `MiB`, `KiB`) that
+/// divides the size cleanly, falling back to the decimal units and
+/// then to bytes. Like [`render_duration`], the authoring unit is not
+/// preserved.
+pub fn render_byte_size(bytes: u64) -> String {
+    if bytes == 0 {
+        return "0B".into();
+    }
+    for (unit, factor) in crate::schema::BYTE_SIZE_UNITS {
+        if factor > 1 && bytes.is_multiple_of(factor) {
+            return format!("{}{unit}", bytes / factor);
+        }
+    }
+    format!("{bytes}B")
+}
+
 /// Render `value` into `sink` using the same code path that produces
 /// the public `format_value_with_key` output. The single source of
 /// truth for plan-display value formatting; sinks downstream of this
@@ -569,6 +590,7 @@ pub(crate) fn format_value_into<S: FormatSink>(
         Value::Concrete(ConcreteValue::CanonicalEnum(c)) => sink.write_str(c.api_value()),
         Value::Concrete(ConcreteValue::Int(n)) => sink.write_str(&n.to_string()),
         Value::Concrete(ConcreteValue::Duration(d)) => sink.write_str(&render_duration(*d)),
+        Value::Concrete(ConcreteValue::ByteSize(n)) => sink.write_str(&render_byte_size(*n)),
         Value::Concrete(ConcreteValue::Float(f)) => {
             let s = f.to_string();
             sink.write_str(&s)?;
@@ -663,6 +685,47 @@ pub fn contains_secret(value: &Value) -> bool {
     }
 }
 
+/// Replace the plaintext of every secret in `values` with `(secret)` in
+/// `message`.
+///
+/// Provider errors often echo the request back ("invalid password
+/// 'hunter2'"), and the executor receives them after secrets have been
+/// unwrapped for the provider. Longer plaintexts are replaced first so a
+/// secret that contains another is not left half redacted.
+pub fn redact_secret_text<'a>(
+    message: &str,
+    values: impl IntoIterator<Item = &'a Value>,
+) -> String {
+    fn collect<'v>(value: &'v Value, in_secret: bool, out: &mut Vec<&'v str>) {
+        match value {
+            Value::Deferred(DeferredValue::Secret(inner)) => collect(inner, true, out),
+            Value::Concrete(ConcreteValue::String(s)) if in_secret && !s.is_empty() => out.push(s),
+            Value::Concrete(ConcreteValue::List(items)) => {
+                for item in items {
+                    collect(item, in_secret, out);
+                }
+            }
+            Value::Concrete(ConcreteValue::Map(map)) => {
+                for v in map.values() {
+                    collect(v, in_secret, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut plaintexts = Vec::new();
+    for value in values {
+        collect(value, false, &mut plaintexts);
+    }
+    plaintexts.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    plaintexts
+        .into_iter()
+        .fold(message.to_string(), |acc, secret| {
+            acc.replace(secret, "(secret)")
+        })
+}
+
 /// Merge secret hashes from the desired value into the provider-returned JSON.
 ///
 /// For attributes containing secrets nested inside Maps or Lists, we cannot simply
@@ -1711,6 +1774,13 @@ pub fn canonicalize_resources_with_schemas<'a>(
         for (key, value) in std::mem::take(&mut resource.attributes) {
             let canon = match schema.attributes.get(&key) {
                 Some(attr_schema) => {
+                    // Unit literals (`12h`, `20GiB`) become the API's
+                    // integer here; a literal the unit rejects is left
+                    // as-is for schema validation to report.
+                    let value = match attr_schema.convert_unit_literal(&value) {
+                        Ok(Some(converted)) => converted,
+                        _ => value,
+                    };
                     canonicalize_with_type(value, &attr_schema.attr_type, &schema.defs)
                 }
                 None => value,
@@ -1892,9 +1962,15 @@ pub fn resolve_value_alias_with_schemas(
             if !valid_values.is_empty() {
                 let valid_refs: Vec<&str> = valid_values.iter().map(String::as_str).collect();
                 let raw = extract_enum_value_with_values(s, &valid_refs);
-                if let Some(canonical) =
-                    factory.get_enum_alias_reverse(resource_type, attr_name, raw)
-                {
+                // The schema's own alias table is authoritative; the
+                // factory hook covers providers whose aliases are not
+                // carried on the enum type.
+                let canonical = schemas
+                    .iter()
+                    .filter(|schema| schema.resource_type == resource_type)
+                    .find_map(|schema| schema.enum_alias_reverse(attr_name, raw))
+                    .or_else(|| factory.get_enum_alias_reverse(resource_type, attr_name, raw));
+                if let Some(canonical) = canonical {
                     *s = canonical;
                 }
             }
@@ -2072,6 +2148,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_value_alias_uses_schema_alias_table_without_factory_hook() {
+        use crate::schema::enum_identity;
+
+        let schema = schema_with_attr(
+            "ec2.SecurityGroupIngress",
+            "ip_protocol",
+            AttributeType::enum_(
+                enum_identity("IpProtocol", Some("aws.ec2.SecurityGroupIngress")),
+                Some(vec!["-1".to_string(), "tcp".to_string()]),
+                vec![("-1".to_string(), "all".to_string())],
+                None,
+                None,
+            ),
+        );
+        let factory = SchemaAliasFactory {
+            schemas: vec![schema],
+            resource_type: "ec2.SecurityGroupIngress",
+            attr_name: "ip_protocol",
+            alias_value: "all",
+            canonical: None,
+        };
+        let mut value = Value::Concrete(ConcreteValue::String(
+            "aws.ec2.SecurityGroupIngress.IpProtocol.all".to_string(),
+        ));
+        resolve_value_alias(
+            &mut value,
+            "ec2.SecurityGroupIngress",
+            "ip_protocol",
+            &factory,
+        );
+
+        assert_eq!(
+            value,
+            Value::Concrete(ConcreteValue::String("-1".to_string()))
+        );
+    }
+
     #[test]
     fn resolve_value_alias_extracts_top_level_list_enum() {
         let schema = schema_with_attr(
@@ -2282,6 +2396,15 @@ mod tests {
         assert_eq!(render_duration(Duration::from_secs(90061)), "90061s");
     }
 
+    #[test]
+    fn render_byte_size_prefers_binary_units() {
+        assert_eq!(render_byte_size(0), "0B");
+        assert_eq!(render_byte_size(512 << 20), "512MiB");
+        assert_eq!(render_byte_size(5_000_000_000), "5GB");
+        assert_eq!(render_byte_size(1 << 40), "1TiB");
+        assert_eq!(render_byte_size(1_500), "1500B");
+    }
+
     #[test]
     fn value_to_json_duration_emits_integer_seconds() {
         let v = Value::Concrete(ConcreteValue::Duration(std::time::Duration::from_secs(
@@ -2894,6 +3017,7 @@ mod tests {
                     )),
                 },
                 default_tags: indexmap::IndexMap::new(),
+                provenance_tags: false,
                 source: None,
                 version: None,
                 revision: None,
@@ -2909,6 +3033,7 @@ mod tests {
                     )),
                 },
                 default_tags: indexmap::IndexMap::new(),
+                provenance_tags: false,
                 source: None,
                 version: None,
                 revision: None,
@@ -3308,6 +3433,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_redact_secret_text_masks_plaintext_in_messages() {
+        let secret = |s: &str| {
+            Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
+                ConcreteValue::String(s.to_string()),
+            ))))
+        };
+        let attrs = [
+            secret("hunter2"),
+            Value::Concrete(ConcreteValue::Map(IndexMap::from([(
+                "token".to_string(),
+                secret("hunter2-extra"),
+            )]))),
+            Value::Concrete(ConcreteValue::String("admin".to_string())),
+        ];
+        let message = "invalid password 'hunter2' for admin (token hunter2-extra)";
+        assert_eq!(
+            redact_secret_text(message, attrs.iter()),
+            "invalid password '(secret)' for admin (token (secret))"
+        );
+    }
+
     #[test]
     fn test_redact_secrets_in_value_replaces_secret() {
         let v = Value::Deferred(DeferredValue::Secret(Box::new(Value::Concrete(
@@ -11,7 +11,7 @@
 //! acceptance condition the original issue called out.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use carina_core::formatter::{FormatConfig, format};
 
@@ -260,3 +260,60 @@ fn fmt_preserves_comments_in_carina_state_fixture() {
         "fmt must be idempotent on the carina-state fixture"
     );
 }
+
+fn collect_crn_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            // Negative fixtures are deliberately malformed.
+            if path.file_name().is_some_and(|n| n == "negative") {
+                continue;
+            }
+            collect_crn_files(&path, out);
+        } else if path.extension().is_some_and(|e| e == "crn") {
+            out.push(path);
+        }
+    }
+}
+
+// Every example configuration checked into the workspace — the fixtures
+// here and the CLI's plan/apply fixtures — must format, and formatting must
+// be a fixed point. `carina fmt` and the LSP's Format Document share this
+// engine, so a regression here shows up in both.
+#[test]
+fn fmt_idempotent_on_all_workspace_fixtures() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut files = Vec::new();
+    collect_crn_files(&manifest_dir.join("tests/fixtures"), &mut files);
+    collect_crn_files(
+        &manifest_dir.join("../carina-cli/tests/fixtures"),
+        &mut files,
+    );
+    assert!(!files.is_empty(), "no .crn fixtures found");
+
+    let config = FormatConfig::default();
+    let mut failures = Vec::new();
+    for path in &files {
+        let source = fs::read_to_string(path).expect("read file");
+        let first = match format(&source, &config) {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                failures.push(format!("{}: format failed: {}", path.display(), e));
+                continue;
+            }
+        };
+        match format(&first, &config) {
+            Ok(second) if second == first => {}
+            Ok(_) => failures.push(format!("{}: not idempotent", path.display())),
+            Err(e) => failures.push(format!("{}: second format failed: {}", path.display(), e)),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "formatter must accept and be idempotent on every fixture:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,205 @@
+//! Property tests for the Value ↔ state-JSON marshalling path and the
+//! string validators that guard it.
+//!
+//! Every resource's attributes go through `value_to_json` on save and
+//! `json_to_dsl_value` + `lift_state_enum_leaves` on load. A value that is
+//! valid against its schema must come back from that trip unchanged and
+//! still valid, or the next plan diffs against a phantom change.
+//!
+//! Validators get arbitrary input: they must never panic, and for
+//! generated well-formed CIDRs they must agree with `Cidr::parse` on the
+//! host-bits rule.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use indexmap::IndexMap;
+use proptest::prelude::*;
+
+use carina_core::resource::{ConcreteValue, EnumValueResolver, Value};
+use carina_core::schema::{
+    AttributeSchema, AttributeType, Cidr, ResourceSchema, StructField, TypeIdentity, check_email,
+    check_ipv4_address, check_ipv4_cidr, check_ipv6_address, check_ipv6_cidr,
+};
+use carina_core::utils::lift_state_enum_leaves;
+use carina_core::value::{json_to_dsl_value, value_to_json};
+
+fn status_type() -> AttributeType {
+    AttributeType::enum_(
+        TypeIdentity::bare("Status"),
+        Some(vec!["Enabled".to_string(), "Suspended".to_string()]),
+        vec![],
+        None,
+        None,
+    )
+}
+
+fn schema() -> ResourceSchema {
+    let rule = AttributeType::struct_(
+        "Rule",
+        vec![
+            StructField::new("prefix", AttributeType::string()),
+            StructField::new("status", status_type()),
+        ],
+    );
+    ResourceSchema::new("test.Bucket")
+        .attribute(AttributeSchema::new("name", AttributeType::string()))
+        .attribute(AttributeSchema::new("count", AttributeType::int()))
+        .attribute(AttributeSchema::new("ratio", AttributeType::float()))
+        .attribute(AttributeSchema::new("enabled", AttributeType::bool()))
+        .attribute(AttributeSchema::new("status", status_type()))
+        .attribute(AttributeSchema::new(
+            "tags",
+            AttributeType::map(AttributeType::string()),
+        ))
+        .attribute(AttributeSchema::new("rules", AttributeType::list(rule)))
+}
+
+fn string() -> impl Strategy<Value = Value> {
+    ".{0,24}".prop_map(|s| Value::Concrete(ConcreteValue::String(s)))
+}
+
+/// Enum leaves in their resolved (canonical) form, which is what the
+/// differ compares and what state stores.
+fn status() -> impl Strategy<Value = Value> {
+    prop_oneof![Just("Enabled"), Just("Suspended")].prop_map(|s| {
+        let canonical = EnumValueResolver::new(&status_type())
+            .resolve_state_text(s)
+            .unwrap();
+        Value::Concrete(ConcreteValue::CanonicalEnum(canonical))
+    })
+}
+
+fn rule() -> impl Strategy<Value = Value> {
+    (string(), status()).prop_map(|(prefix, status)| {
+        Value::Concrete(ConcreteValue::Map(IndexMap::from([
+            ("prefix".to_string(), prefix),
+            ("status".to_string(), status),
+        ])))
+    })
+}
+
+/// A random subset of the schema's attributes, each holding a value of
+/// its declared type.
+fn attributes() -> impl Strategy<Value = HashMap<String, Value>> {
+    let entries = (
+        proptest::option::of(string()),
+        proptest::option::of(any::<i64>().prop_map(|n| Value::Concrete(ConcreteValue::Int(n)))),
+        // Integral floats serialize as JSON floats (`1.0`) and load back
+        // as `Float`, so the full finite range is in play.
+        proptest::option::of(
+            any::<f64>()
+                .prop_filter("JSON has no NaN or infinity", |f| f.is_finite())
+                .prop_map(|f| Value::Concrete(ConcreteValue::Float(f))),
+        ),
+        proptest::option::of(any::<bool>().prop_map(|b| Value::Concrete(ConcreteValue::Bool(b)))),
+        proptest::option::of(status()),
+        proptest::option::of(
+            proptest::collection::btree_map("[a-z][a-z0-9_]{0,8}", string(), 0..4)
+                .prop_map(|m| Value::Concrete(ConcreteValue::Map(m.into_iter().collect()))),
+        ),
+        proptest::option::of(
+            proptest::collection::vec(rule(), 0..4)
+                .prop_map(|rules| Value::Concrete(ConcreteValue::List(rules))),
+        ),
+    );
+    entries.prop_map(|(name, count, ratio, enabled, status, tags, rules)| {
+        [
+            ("name", name),
+            ("count", count),
+            ("ratio", ratio),
+            ("enabled", enabled),
+            ("status", status),
+            ("tags", tags),
+            ("rules", rules),
+        ]
+        .into_iter()
+        .filter_map(|(k, v)| v.map(|v| (k.to_string(), v)))
+        .collect()
+    })
+}
+
+fn ipv4_network() -> impl Strategy<Value = (u32, u8)> {
+    (any::<u32>(), 0u8..=32).prop_map(|(addr, prefix)| {
+        let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+        (addr & mask, prefix)
+    })
+}
+
+proptest! {
+    #[test]
+    fn valid_attributes_survive_state_round_trip(attrs in attributes()) {
+        let schema = schema();
+        prop_assert!(schema.validate(&attrs).is_ok(), "generator produced invalid input");
+
+        let mut loaded: HashMap<String, Value> = attrs
+            .iter()
+            .map(|(k, v)| {
+                let json = value_to_json(v).expect("concrete values serialize");
+                (k.clone(), json_to_dsl_value(&json).expect("no nulls are written"))
+            })
+            .collect();
+        lift_state_enum_leaves(&mut loaded, &schema);
+
+        prop_assert_eq!(&loaded, &attrs);
+        prop_assert!(schema.validate(&loaded).is_ok());
+    }
+
+    #[test]
+    fn validators_never_panic(s in "\\PC{0,48}") {
+        let _ = check_ipv4_address(&s);
+        let _ = check_ipv4_cidr(&s);
+        let _ = check_ipv6_address(&s);
+        let _ = check_ipv6_cidr(&s);
+        let _ = check_email(&s);
+        let _ = Cidr::parse(&s);
+    }
+
+    #[test]
+    fn validators_never_panic_on_cidr_shaped_input(s in "[0-9a-fA-F:./]{0,48}") {
+        let _ = check_ipv4_cidr(&s);
+        let _ = check_ipv6_cidr(&s);
+        let _ = Cidr::parse(&s);
+    }
+
+    #[test]
+    fn ipv4_network_addresses_validate((addr, prefix) in ipv4_network()) {
+        let cidr = format!("{}/{}", Ipv4Addr::from(addr), prefix);
+        prop_assert!(check_ipv4_cidr(&cidr).is_ok(), "{}", cidr);
+        let parsed = Cidr::parse(&cidr).unwrap();
+        prop_assert_eq!(parsed.to_string(), cidr);
+        prop_assert!(parsed.contains(&parsed));
+    }
+
+    #[test]
+    fn ipv4_host_bits_are_rejected_with_network_suggestion(addr in any::<u32>(), prefix in 0u8..32) {
+        let host_mask = u32::MAX >> prefix;
+        prop_assume!(addr & host_mask != 0);
+        let cidr = format!("{}/{}", Ipv4Addr::from(addr), prefix);
+        let network = format!("{}/{}", Ipv4Addr::from(addr & !host_mask), prefix);
+
+        prop_assert!(check_ipv4_cidr(&cidr).is_err(), "{}", cidr);
+        let lenient = Cidr::parse_lenient(&cidr).unwrap();
+        prop_assert_eq!(lenient.network().to_string(), network);
+    }
+
+    #[test]
+    fn ipv4_subnets_are_contained((addr, prefix) in ipv4_network(), extra in 0u8..=32) {
+        let sub_prefix = prefix.saturating_add(extra).min(32);
+        let outer = Cidr::parse(&format!("{}/{}", Ipv4Addr::from(addr), prefix)).unwrap();
+        let inner = Cidr::parse_lenient(&format!("{}/{}", Ipv4Addr::from(addr), sub_prefix))
+            .unwrap()
+            .network();
+        prop_assert!(outer.contains(&inner));
+        prop_assert!(outer.overlaps(&inner));
+        prop_assert_eq!(inner.contains(&outer), sub_prefix == prefix);
+    }
+
+    #[test]
+    fn ipv6_network_addresses_validate(addr in any::<u128>(), prefix in 0u8..=128) {
+        let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+        let cidr = format!("{}/{}", Ipv6Addr::from(addr & mask), prefix);
+        prop_assert!(check_ipv6_cidr(&cidr).is_ok(), "{}", cidr);
+        prop_assert!(Cidr::parse(&cidr).is_ok(), "{}", cidr);
+    }
+}
@@ -15,7 +15,10 @@ use crate::completion::CompletionProvider;
 use crate::diagnostics::DiagnosticEngine;
 use crate::document::Document;
 use crate::hover::HoverProvider;
+use crate::inlay_hints::{InlayHintOptions, InlayHintProvider};
+use crate::rename;
 use crate::semantic_tokens::{self, SemanticTokensProvider};
+use crate::symbols;
 use crate::workspace;
 
 /// Calculate the end position (line, character) of a text document.
@@ -31,11 +34,36 @@ pub fn document_end_position(text: &str) -> (u32, u32) {
     (last_line, last_char)
 }
 
+/// Whole-document edit that rewrites `text` into `carina fmt`'s canonical
+/// form. `None` when the text is already formatted or does not parse, so a
+/// half-typed file is never mangled.
+pub fn formatting_edits(text: &str) -> Option<Vec<TextEdit>> {
+    let formatted = formatter::format(text, &FormatConfig::default()).ok()?;
+    if formatted == text {
+        return None;
+    }
+    let (last_line, last_char) = document_end_position(text);
+    Some(vec![TextEdit {
+        range: Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: last_line,
+                character: last_char,
+            },
+        },
+        new_text: formatted,
+    }])
+}
+
 /// Schema-dependent providers that are rebuilt when provider configs change.
 struct ProviderState {
     diagnostic_engine: DiagnosticEngine,
     completion_provider: CompletionProvider,
     hover_provider: HoverProvider,
+    inlay_hint_provider: InlayHintProvider,
     semantic_tokens_provider: SemanticTokensProvider,
     /// Configs and their source directory, retained so a background poller can
     /// re-probe provider installation without re-scanning the workspace.
@@ -87,7 +115,9 @@ impl ProviderState {
                 region_completions.clone(),
                 custom_type_names,
             ),
-            semantic_tokens_provider: SemanticTokensProvider::new(&region_completions),
+            semantic_tokens_provider: SemanticTokensProvider::new(&region_completions)
+                .with_schemas(Arc::clone(&schemas)),
+            inlay_hint_provider: InlayHintProvider::new(Arc::clone(&schemas)),
             hover_provider: HoverProvider::new(schemas, region_completions),
             configs,
             prober,
@@ -138,6 +168,27 @@ fn probe_install_fingerprint(
 /// negligible.
 const PROVIDER_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
+/// How often the background refresher copies each project's state into its
+/// `.carina/` replica when the client does not set
+/// `stateRefreshIntervalSecs`. Hover and completion only ever read the
+/// replica, so this bounds how stale a live value can be without putting a
+/// backend read on any request path.
+const STATE_REPLICA_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Replica refresh interval from the client's `initializationOptions`.
+/// `stateRefreshIntervalSecs: 0` turns the refresher off; readers then
+/// use whatever replica is already on disk.
+fn state_refresh_interval(options: Option<&serde_json::Value>) -> Option<std::time::Duration> {
+    match options
+        .and_then(|o| o.get("stateRefreshIntervalSecs"))
+        .and_then(serde_json::Value::as_u64)
+    {
+        Some(0) => None,
+        Some(secs) => Some(std::time::Duration::from_secs(secs)),
+        None => Some(STATE_REPLICA_REFRESH_INTERVAL),
+    }
+}
+
 /// Per-directory provider states keyed by configuration directory.
 struct ProviderStates {
     /// Directory → ProviderState. Each directory with provider declarations
@@ -231,6 +282,13 @@ pub struct Backend {
     /// Set once `initialized` spawns the background `.carina/` drift poller,
     /// to keep it from double-spawning on clients that re-send `initialized`.
     poller_spawned: std::sync::atomic::AtomicBool,
+    /// Same guard for the state replica refresher.
+    refresher_spawned: std::sync::atomic::AtomicBool,
+    /// Replica refresh interval read from `initializationOptions`; `None`
+    /// inside disables the refresher.
+    state_refresh_interval: std::sync::OnceLock<Option<std::time::Duration>>,
+    /// Inlay hint kinds the client enabled in `initializationOptions`.
+    inlay_hint_options: std::sync::OnceLock<InlayHintOptions>,
 }
 
 impl Backend {
@@ -262,6 +320,30 @@ impl Backend {
             factory_builder,
             install_prober,
             poller_spawned: std::sync::atomic::AtomicBool::new(false),
+            refresher_spawned: std::sync::atomic::AtomicBool::new(false),
+            state_refresh_interval: std::sync::OnceLock::new(),
+            inlay_hint_options: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Text of every open document.
+    fn open_texts(&self) -> HashMap<Url, String> {
+        self.documents
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().text()))
+            .collect()
+    }
+
+    /// Sources of the project `uri` belongs to, and its directory. A
+    /// document without a file path is a project of its own.
+    fn rename_project(&self, uri: &Url, text: &str) -> (Vec<(Url, String)>, Option<PathBuf>) {
+        match uri
+            .to_file_path()
+            .ok()
+            .and_then(|p| p.parent().map(Path::to_path_buf))
+        {
+            Some(dir) => (rename::project_sources(&dir, &self.open_texts()), Some(dir)),
+            None => (vec![(uri.clone(), text.to_string())], None),
         }
     }
 
@@ -320,6 +402,57 @@ impl Backend {
         });
     }
 
+    /// Spawn a background task that copies every project's state into its
+    /// `.carina/` replica (see [`carina_state::StateReplica`]) on a timer.
+    /// Hover reads only the replica, so editor requests never wait on a
+    /// remote backend or on a CLI run that holds the state lock.
+    ///
+    /// Called once from `initialized`. Subsequent calls are no-ops.
+    fn spawn_state_replica_refresher(&self) {
+        use std::sync::atomic::Ordering;
+        if self.refresher_spawned.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let Some(interval) = self
+            .state_refresh_interval
+            .get()
+            .copied()
+            .unwrap_or(Some(STATE_REPLICA_REFRESH_INTERVAL))
+        else {
+            return;
+        };
+        let workspace_root = Arc::clone(&self.workspace_root);
+        let client = self.client.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Some(root) = workspace_root.get().cloned().flatten() else {
+                    continue;
+                };
+                let projects =
+                    tokio::task::spawn_blocking(move || workspace::discover_state_backends(&root))
+                        .await
+                        .unwrap_or_default();
+                for (dir, backend_config) in projects {
+                    if let Err(e) = refresh_state_replica(&dir, backend_config.as_ref()).await {
+                        client
+                            .log_message(
+                                MessageType::LOG,
+                                format!(
+                                    "State replica refresh failed for {}: {}",
+                                    dir.display(),
+                                    e
+                                ),
+                            )
+                            .await;
+                    }
+                }
+            }
+        });
+    }
+
     /// Load or reload provider schemas from workspace .crn files.
     async fn load_schemas(&self) {
         load_schemas_impl(
@@ -349,6 +482,14 @@ impl LanguageServer for Backend {
                     .and_then(|f| f.uri.to_file_path().ok())
             });
         let _ = self.workspace_root.set(root);
+        let _ = self.state_refresh_interval.set(state_refresh_interval(
+            params.initialization_options.as_ref(),
+        ));
+        let _ = self
+            .inlay_hint_options
+            .set(InlayHintOptions::from_initialization_options(
+                params.initialization_options.as_ref(),
+            ));
 
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
@@ -376,6 +517,11 @@ impl LanguageServer for Backend {
                     ..Default::default()
                 }),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                    retrigger_characters: None,
+                    work_done_progress_options: Default::default(),
+                }),
                 semantic_tokens_provider: Some(
                     SemanticTokensServerCapabilities::SemanticTokensOptions(
                         SemanticTokensOptions {
@@ -387,14 +533,23 @@ impl LanguageServer for Backend {
                     ),
                 ),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
                 // Declare `code_action_kinds` explicitly so editors that
                 // gate on `only` (`CodeActionContext.only`) can avoid
-                // probing this server for kinds it never emits. Carina
-                // only ships QUICKFIX actions for #2309 today; add more
-                // kinds here when other features start emitting actions.
+                // probing this server for kinds it never emits: quick
+                // fixes for diagnostics and the extract-to-`let` refactor.
                 code_action_provider: Some(CodeActionProviderCapability::Options(
                     CodeActionOptions {
-                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                        ]),
                         resolve_provider: None,
                         work_done_progress_options: Default::default(),
                     },
@@ -446,6 +601,8 @@ impl LanguageServer for Backend {
         // Start polling for `.carina/` drift so a user deleting it mid-session
         // is noticed without any editor interaction.
         self.spawn_provider_drift_poller();
+        // Keep each project's state replica current for hover.
+        self.spawn_state_replica_refresher();
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -540,6 +697,15 @@ impl LanguageServer for Backend {
         Ok(None)
     }
 
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        Ok(self
+            .documents
+            .get(uri)
+            .and_then(|doc| crate::signature_help::signature_help(&doc.text(), position)))
+    }
+
     async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
@@ -565,59 +731,139 @@ impl LanguageServer for Backend {
         Ok(None)
     }
 
-    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+    async fn inlay_hint(&self, params: InlayHintParams) -> Result<Option<Vec<InlayHint>>> {
         let uri = &params.text_document.uri;
+        let Some(doc) = self.documents.get(uri) else {
+            return Ok(None);
+        };
+        let base_path = uri
+            .to_file_path()
+            .ok()
+            .and_then(|p| p.parent().map(|p| p.to_path_buf()));
+        let providers = self.providers.read().await;
+        let state = base_path
+            .as_ref()
+            .map(|p| providers.state_for_path(p))
+            .unwrap_or(&providers.empty);
+        let options = self.inlay_hint_options.get().copied().unwrap_or_default();
+        Ok(Some(state.inlay_hint_provider.inlay_hints(
+            &doc.text(),
+            params.range,
+            options,
+        )))
+    }
 
-        if let Some(doc) = self.documents.get(uri) {
-            let text = doc.text();
-            let config = FormatConfig::default();
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        Ok(self
+            .documents
+            .get(&params.text_document.uri)
+            .and_then(|doc| formatting_edits(&doc.text())))
+    }
 
-            match formatter::format(&text, &config) {
-                Ok(formatted) => {
-                    if formatted == text {
-                        return Ok(None);
-                    }
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        Ok(self
+            .documents
+            .get(&params.text_document.uri)
+            .map(|doc| DocumentSymbolResponse::Nested(symbols::document_symbols(&doc.text()))))
+    }
 
-                    let (last_line, last_char) = document_end_position(&text);
-
-                    let edit = TextEdit {
-                        range: Range {
-                            start: Position {
-                                line: 0,
-                                character: 0,
-                            },
-                            end: Position {
-                                line: last_line,
-                                character: last_char,
-                            },
-                        },
-                        new_text: formatted,
-                    };
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        // Every `.crn` file under the root is re-scanned per query; the
+        // scan is a token pass with no parsing, and editors debounce
+        // symbol queries as the user types.
+        let open = self.open_texts();
+        let sources = match self.workspace_root() {
+            Some(root) => rename::workspace_sources(root, None, &open),
+            None => open.into_iter().collect(),
+        };
+        Ok(Some(symbols::workspace_symbols(&params.query, &sources)))
+    }
 
-                    return Ok(Some(vec![edit]));
-                }
-                Err(_) => {
-                    return Ok(None);
-                }
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let Some(text) = self
+            .documents
+            .get(&params.text_document.uri)
+            .map(|d| d.text())
+        else {
+            return Ok(None);
+        };
+        let Some((target, range)) = rename::target_at(&text, params.position) else {
+            return Ok(None);
+        };
+        let (project, _) = self.rename_project(&params.text_document.uri, &text);
+        if !rename::is_defined(&target, &project) {
+            return Ok(None);
+        }
+        Ok(Some(PrepareRenameResponse::Range(range)))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let Some(text) = self.documents.get(uri).map(|d| d.text()) else {
+            return Ok(None);
+        };
+        let Some((target, _)) = rename::target_at(&text, params.text_document_position.position)
+        else {
+            return Ok(None);
+        };
+        let (project, project_dir) = self.rename_project(uri, &text);
+        // Only an export has readers outside its own project.
+        let consumers = match (&target, &project_dir, self.workspace_root()) {
+            (rename::RenameTarget::Export(_), Some(dir), Some(root)) => {
+                rename::workspace_sources(root, Some(dir), &self.open_texts())
             }
+            _ => Vec::new(),
+        };
+        let project_dir = project_dir.unwrap_or_default();
+        match rename::rename_edits(
+            &target,
+            &params.new_name,
+            &project,
+            &project_dir,
+            &consumers,
+        ) {
+            Ok(changes) => Ok(Some(WorkspaceEdit {
+                changes: Some(changes),
+                ..Default::default()
+            })),
+            Err(message) => Err(tower_lsp::jsonrpc::Error::invalid_params(message)),
         }
-        Ok(None)
     }
 
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
-        // The handler is purely diagnostic-driven: the editor passes the
+        // Quick fixes are diagnostic-driven: the editor passes the
         // diagnostics it currently shows in `params.context.diagnostics`,
-        // each one carrying its `Diagnostic.data` payload unchanged. We
-        // read the structured `EnumDiagnosticData` off matching
-        // diagnostics and emit one `CodeAction` per remaining candidate.
-        // Diagnostics without an enum payload (or from other features)
-        // produce no actions here.
+        // each one carrying its `Diagnostic.data` payload unchanged, and
+        // we turn each payload into its fixes. The extract-to-`let`
+        // refactor is the exception; it only looks at the cursor line.
         let uri = &params.text_document.uri;
-        let mut actions: Vec<CodeActionOrCommand> = Vec::new();
-        for diag in &params.context.diagnostics {
-            for action in crate::code_action::code_actions_for_diagnostic(uri, diag) {
-                actions.push(CodeActionOrCommand::CodeAction(action));
-            }
+        let mut actions: Vec<CodeActionOrCommand> =
+            crate::code_action::code_actions_for_diagnostics(uri, &params.context.diagnostics)
+                .into_iter()
+                .map(CodeActionOrCommand::CodeAction)
+                .collect();
+        let wants_refactor = params.context.only.as_ref().is_none_or(|only| {
+            only.iter().any(|kind| {
+                CodeActionKind::REFACTOR_EXTRACT
+                    .as_str()
+                    .starts_with(kind.as_str())
+            })
+        });
+        if wants_refactor
+            && let Some(doc) = self.documents.get(uri)
+            && let Some(action) =
+                crate::code_action::extract_to_let_action(uri, &doc.text(), params.range)
+        {
+            actions.push(CodeActionOrCommand::CodeAction(action));
         }
         if actions.is_empty() {
             Ok(None)
@@ -627,6 +873,20 @@ impl LanguageServer for Backend {
     }
 }
 
+/// Copy the state of the project in `dir` into its replica. Local backends
+/// are anchored at `dir`, as `carina` does for a directory argument.
+async fn refresh_state_replica(
+    dir: &Path,
+    backend_config: Option<&carina_core::parser::BackendConfig>,
+) -> std::result::Result<(), carina_state::BackendError> {
+    let config = backend_config.map(carina_state::BackendConfig::from);
+    let backend = carina_state::resolve_backend_anchored(config.as_ref(), dir).await?;
+    carina_state::StateReplica::new(dir)
+        .refresh(backend.as_ref())
+        .await
+        .map(|_| ())
+}
+
 /// Publish diagnostics for a single document. Free function so both the
 /// `Backend` methods and the provider-drift poller can call it without going
 /// through `&self`.
@@ -984,6 +1244,7 @@ mod tests {
             is_default: true,
             attributes,
             default_tags: IndexMap::new(),
+            provenance_tags: false,
         };
         let configs = vec![(tmp.path().to_path_buf(), config)];
         let prober = test_prober();
@@ -1019,6 +1280,7 @@ mod tests {
             is_default: true,
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
         };
         let configs = vec![(tmp.path().to_path_buf(), config)];
         let prober = test_prober();
@@ -1028,6 +1290,40 @@ mod tests {
             "two back-to-back probes with no fs change must agree"
         );
     }
+    #[test]
+    fn state_refresh_interval_reads_initialization_options() {
+        use std::time::Duration;
+        assert_eq!(
+            state_refresh_interval(None),
+            Some(STATE_REPLICA_REFRESH_INTERVAL)
+        );
+        let options = serde_json::json!({ "stateRefreshIntervalSecs": 15 });
+        assert_eq!(
+            state_refresh_interval(Some(&options)),
+            Some(Duration::from_secs(15))
+        );
+        let off = serde_json::json!({ "stateRefreshIntervalSecs": 0 });
+        assert_eq!(state_refresh_interval(Some(&off)), None);
+    }
+
+    #[tokio::test]
+    async fn refresh_state_replica_copies_local_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = carina_state::StateFile::new();
+        let mut bucket = carina_state::ResourceState::new("s3.Bucket", "logs", "aws");
+        bucket.identifier = Some("logs".to_string());
+        state.resources.push(bucket);
+        std::fs::write(
+            dir.path()
+                .join(carina_state::LocalBackend::DEFAULT_STATE_FILE),
+            serde_json::to_string(&state).unwrap(),
+        )
+        .unwrap();
+
+        refresh_state_replica(dir.path(), None).await.unwrap();
+        let replica = carina_state::StateReplica::new(dir.path()).load().unwrap();
+        assert_eq!(replica.resources.len(), 1);
+    }
 }
 
 #[cfg(test)]
@@ -1042,6 +1338,7 @@ mod reload_skip_tests {
             name: name.into(),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             source: source.map(String::from),
             version: None,
             revision: None,
@@ -1145,4 +1442,25 @@ mod reload_skip_tests {
         let b = vec![(dir, cfg_b)];
         assert!(configs_match(&a, &b));
     }
+
+    #[test]
+    fn formatting_edits_replace_whole_document_with_canonical_form() {
+        let text = "provider aws {\nregion=aws.Region.ap_northeast_1\nname = \"x\"\n}";
+        let edits = formatting_edits(text).expect("unformatted text yields an edit");
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range.start, Position::new(0, 0));
+        assert_eq!(edits[0].range.end, Position::new(3, 1));
+        assert!(
+            edits[0]
+                .new_text
+                .contains("  region = aws.Region.ap_northeast_1")
+        );
+        assert!(edits[0].new_text.contains("name   = 'x'"));
+        assert!(formatting_edits(&edits[0].new_text).is_none());
+    }
+
+    #[test]
+    fn formatting_edits_decline_unparseable_text() {
+        assert!(formatting_edits("provider aws {\n  region = \n").is_none());
+    }
 }
@@ -1,4 +1,5 @@
-//! Code-action support for enum-mismatch diagnostics. See #2309.
+//! Code-action support for enum-mismatch and naming-policy diagnostics.
+//! See #2309.
 //!
 //! Diagnostics produced for `TypeError::InvalidEnumVariant` and
 //! `TypeError::StringLiteralExpectedEnum` carry a serialized
@@ -19,10 +20,24 @@
 //!   covers the literal *including* both quote characters; the
 //!   replacement is the canonical identifier form (no quotes), so
 //!   applying the action drops the quotes too.
+//!
+//! Naming-policy violations carry a [`NamingDiagnosticData`] payload
+//! instead: a single compliant name that replaces the quoted literal.
+//!
+//! Missing required attributes carry a [`RequiredAttributeDiagnosticData`]
+//! payload: the line to insert (attribute name plus a typed placeholder)
+//! and where to insert it. When several such diagnostics point at the
+//! same block, an extra action inserts all of them at once.
+//!
+//! [`extract_to_let_action`] is the one action not driven by a
+//! diagnostic: it lifts the inline value under the cursor into a
+//! top-level `let` binding.
 
 use carina_core::schema::ExpectedEnumVariant;
 use serde::{Deserialize, Serialize};
-use tower_lsp::lsp_types::{CodeAction, CodeActionKind, Diagnostic, TextEdit, Url, WorkspaceEdit};
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, Diagnostic, Position, Range, TextEdit, Url, WorkspaceEdit,
+};
 
 /// Whether the diagnostic was emitted for a bare-identifier mismatch
 /// or a quoted string literal in enum position. Determines how the
@@ -78,9 +93,178 @@ impl EnumDiagnosticData {
     }
 }
 
+/// Payload attached to naming-policy `Diagnostic.data`: the compliant
+/// name derived from the `naming` template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamingDiagnosticData {
+    pub tag: NamingDiagnosticTag,
+    pub suggestion: String,
+}
+
+/// Structural marker for [`NamingDiagnosticData`]; see
+/// [`EnumDiagnosticTag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NamingDiagnosticTag {
+    #[serde(rename = "carina_naming_violation")]
+    NamingViolation,
+}
+
+impl NamingDiagnosticData {
+    pub fn new(suggestion: String) -> Self {
+        Self {
+            tag: NamingDiagnosticTag::NamingViolation,
+            suggestion,
+        }
+    }
+
+    /// Try to read a naming payload off a `Diagnostic`.
+    pub fn from_diagnostic(diag: &Diagnostic) -> Option<Self> {
+        let data = diag.data.as_ref()?;
+        serde_json::from_value(data.clone()).ok()
+    }
+}
+
+/// Payload attached to `MissingRequired` `Diagnostic.data`: the full
+/// line that declares the attribute with a placeholder value, and the
+/// position (start of the line after the block header) to insert it at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequiredAttributeDiagnosticData {
+    pub tag: RequiredAttributeDiagnosticTag,
+    pub attribute: String,
+    pub insert_at: Position,
+    pub new_text: String,
+}
+
+/// Structural marker for [`RequiredAttributeDiagnosticData`]; see
+/// [`EnumDiagnosticTag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RequiredAttributeDiagnosticTag {
+    #[serde(rename = "carina_missing_required")]
+    MissingRequired,
+}
+
+impl RequiredAttributeDiagnosticData {
+    pub fn new(attribute: String, insert_at: Position, new_text: String) -> Self {
+        Self {
+            tag: RequiredAttributeDiagnosticTag::MissingRequired,
+            attribute,
+            insert_at,
+            new_text,
+        }
+    }
+
+    /// Try to read a missing-required payload off a `Diagnostic`.
+    pub fn from_diagnostic(diag: &Diagnostic) -> Option<Self> {
+        let data = diag.data.as_ref()?;
+        serde_json::from_value(data.clone()).ok()
+    }
+
+    fn edit(&self) -> TextEdit {
+        TextEdit {
+            range: Range {
+                start: self.insert_at,
+                end: self.insert_at,
+            },
+            new_text: self.new_text.clone(),
+        }
+    }
+}
+
+fn code_action(
+    uri: &Url,
+    kind: CodeActionKind,
+    diagnostics: Option<Vec<Diagnostic>>,
+    title: String,
+    edits: Vec<TextEdit>,
+) -> CodeAction {
+    let mut changes = std::collections::HashMap::new();
+    changes.insert(uri.clone(), edits);
+    CodeAction {
+        title,
+        kind: Some(kind),
+        diagnostics,
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn quick_fix(uri: &Url, diag: &Diagnostic, title: String, new_text: String) -> CodeAction {
+    let edit = TextEdit {
+        range: diag.range,
+        new_text,
+    };
+    code_action(
+        uri,
+        CodeActionKind::QUICKFIX,
+        Some(vec![diag.clone()]),
+        title,
+        vec![edit],
+    )
+}
+
+/// Build the `CodeAction` list for every diagnostic in a request: the
+/// per-diagnostic actions, plus one "insert all" action for each block
+/// with more than one missing required attribute.
+pub fn code_actions_for_diagnostics(uri: &Url, diags: &[Diagnostic]) -> Vec<CodeAction> {
+    let mut actions: Vec<CodeAction> = diags
+        .iter()
+        .flat_map(|diag| code_actions_for_diagnostic(uri, diag))
+        .collect();
+
+    let mut groups: Vec<(
+        Position,
+        Vec<(&Diagnostic, RequiredAttributeDiagnosticData)>,
+    )> = Vec::new();
+    for diag in diags {
+        let Some(payload) = RequiredAttributeDiagnosticData::from_diagnostic(diag) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(at, _)| *at == payload.insert_at) {
+            Some((_, members)) => members.push((diag, payload)),
+            None => groups.push((payload.insert_at, vec![(diag, payload)])),
+        }
+    }
+    for (_, members) in groups.into_iter().filter(|(_, m)| m.len() > 1) {
+        let title = format!("Insert all {} missing required attributes", members.len());
+        let edits = members.iter().map(|(_, p)| p.edit()).collect();
+        let diagnostics = members.iter().map(|(d, _)| (*d).clone()).collect();
+        actions.push(code_action(
+            uri,
+            CodeActionKind::QUICKFIX,
+            Some(diagnostics),
+            title,
+            edits,
+        ));
+    }
+    actions
+}
+
 /// Build the `CodeAction` list for one diagnostic. Returns an empty
 /// vec when the payload is absent or no candidates apply.
 pub fn code_actions_for_diagnostic(uri: &Url, diag: &Diagnostic) -> Vec<CodeAction> {
+    if let Some(naming) = NamingDiagnosticData::from_diagnostic(diag) {
+        // The diagnostic range covers the quoted literal, quotes included.
+        let mut action = quick_fix(
+            uri,
+            diag,
+            format!("Rename to `{}`", naming.suggestion),
+            format!("\"{}\"", naming.suggestion),
+        );
+        action.is_preferred = Some(true);
+        return vec![action];
+    }
+    if let Some(required) = RequiredAttributeDiagnosticData::from_diagnostic(diag) {
+        return vec![code_action(
+            uri,
+            CodeActionKind::QUICKFIX,
+            Some(vec![diag.clone()]),
+            format!("Insert required attribute `{}`", required.attribute),
+            vec![required.edit()],
+        )];
+    }
     let Some(payload) = EnumDiagnosticData::from_diagnostic(diag) else {
         return Vec::new();
     };
@@ -101,27 +285,190 @@ pub fn code_actions_for_diagnostic(uri: &Url, diag: &Diagnostic) -> Vec<CodeActi
         .map(|variant| {
             let new_text = variant.to_string();
             let title = format!("Replace with `{}`", new_text);
-            let edit = TextEdit {
-                range: diag.range,
-                new_text,
-            };
-            let mut changes = std::collections::HashMap::new();
-            changes.insert(uri.clone(), vec![edit]);
-            CodeAction {
-                title,
-                kind: Some(CodeActionKind::QUICKFIX),
-                diagnostics: Some(vec![diag.clone()]),
-                edit: Some(WorkspaceEdit {
-                    changes: Some(changes),
-                    ..Default::default()
-                }),
-                is_preferred: Some(!variant.is_alias),
-                ..Default::default()
-            }
+            let mut action = quick_fix(uri, diag, title, new_text);
+            action.is_preferred = Some(!variant.is_alias);
+            action
         })
         .collect()
 }
 
+/// Offer "Extract value to `let`" when the cursor sits on a single-line
+/// `name = value` attribute inside a top-level resource or module block.
+/// The value moves into a `let` above the block, named after the
+/// attribute, and the attribute refers to the new binding instead.
+pub fn extract_to_let_action(uri: &Url, text: &str, range: Range) -> Option<CodeAction> {
+    let lines: Vec<&str> = text.lines().collect();
+    let line_idx = range.start.line as usize;
+    let line = *lines.get(line_idx)?;
+    if !line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (name, value_start, value_end) = inline_attribute(line)?;
+    let value = &line[value_start..value_end];
+    if is_identifier(value) {
+        return None;
+    }
+
+    // The enclosing top-level statement is the nearest line above that
+    // starts at column zero and is not a closing brace or comment.
+    let header_idx = (0..line_idx).rev().find(|&i| {
+        let l = lines[i];
+        !l.is_empty()
+            && !l.starts_with(char::is_whitespace)
+            && !l.starts_with('}')
+            && !l.starts_with('#')
+            && !l.starts_with("//")
+    })?;
+    if !extractable_header(lines[header_idx]) {
+        return None;
+    }
+
+    let binding = fresh_binding(name, &lines);
+    let column = |byte: usize| line[..byte].chars().count() as u32;
+    let edits = vec![
+        TextEdit {
+            range: Range {
+                start: Position {
+                    line: header_idx as u32,
+                    character: 0,
+                },
+                end: Position {
+                    line: header_idx as u32,
+                    character: 0,
+                },
+            },
+            new_text: format!("let {binding} = {value}\n\n"),
+        },
+        TextEdit {
+            range: Range {
+                start: Position {
+                    line: line_idx as u32,
+                    character: column(value_start),
+                },
+                end: Position {
+                    line: line_idx as u32,
+                    character: column(value_end),
+                },
+            },
+            new_text: binding.clone(),
+        },
+    ];
+    Some(code_action(
+        uri,
+        CodeActionKind::REFACTOR_EXTRACT,
+        None,
+        format!("Extract value to `let {binding}`"),
+        edits,
+    ))
+}
+
+/// Split `  name = value  # comment` into the attribute name and the
+/// byte range of the value. Returns `None` unless the value is complete
+/// on this line (balanced brackets, closed quotes).
+fn inline_attribute(line: &str) -> Option<(&str, usize, usize)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    if !is_identifier(name) {
+        return None;
+    }
+    let after_name = &rest[name_len..];
+    let eq = after_name.trim_start();
+    if !eq.starts_with('=') || eq.starts_with("==") {
+        return None;
+    }
+    let after_eq = &eq[1..];
+    let value_start = line.len() - after_eq.trim_start().len();
+
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut end = line.len();
+    let mut chars = line[value_start..].char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                '#' => {
+                    end = value_start + i;
+                    break;
+                }
+                '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                    end = value_start + i;
+                    break;
+                }
+                _ => {}
+            },
+        }
+    }
+    let value_end = value_start + line[value_start..end].trim_end().len();
+    if depth != 0 || quote.is_some() || value_end == value_start {
+        return None;
+    }
+    Some((name, value_start, value_end))
+}
+
+/// Resource (`let x = provider.type {` / `provider.type {`) and module
+/// call headers. `for` and `if` bodies are excluded because their values
+/// may refer to loop variables that do not exist at the top level.
+fn extractable_header(header: &str) -> bool {
+    let header = header.trim_end();
+    if !header.ends_with('{') {
+        return false;
+    }
+    let rhs = match header.strip_prefix("let ") {
+        Some(rest) => match rest.split_once('=') {
+            Some((_, rhs)) => rhs.trim_start(),
+            None => return false,
+        },
+        None => header,
+    };
+    if rhs.starts_with("for ") || rhs.starts_with("if ") {
+        return false;
+    }
+    header.starts_with("let ")
+        || rhs
+            .split_whitespace()
+            .next()
+            .is_some_and(|first| first.contains('.'))
+}
+
+/// The attribute name, or the attribute name with the first free
+/// numeric suffix when a top-level `let` already uses it.
+fn fresh_binding(name: &str, lines: &[&str]) -> String {
+    let taken: std::collections::HashSet<&str> = lines
+        .iter()
+        .filter_map(|l| l.strip_prefix("let "))
+        .filter_map(|rest| rest.split(|c: char| c.is_whitespace() || c == '=').next())
+        .collect();
+    if !taken.contains(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{name}_{n}"))
+        .find(|candidate| !taken.contains(candidate.as_str()))
+        .expect("unbounded suffix search")
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,6 +608,29 @@ mod tests {
         assert_eq!(edits[0].new_text, "aws.s3.Bucket.VersioningStatus.Enabled");
     }
 
+    #[test]
+    fn naming_payload_offers_rename_to_suggestion() {
+        let diag = Diagnostic {
+            data: Some(
+                serde_json::to_value(NamingDiagnosticData::new("prd-bucket-logs".to_string()))
+                    .unwrap(),
+            ),
+            ..diag_with_payload(EnumDiagnosticData::new(
+                EnumDiagnosticKind::StringLiteral,
+                vec![],
+            ))
+        };
+        assert!(EnumDiagnosticData::from_diagnostic(&diag).is_none());
+
+        let actions = code_actions_for_diagnostic(&dummy_uri(), &diag);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Rename to `prd-bucket-logs`");
+        assert_eq!(actions[0].is_preferred, Some(true));
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&dummy_uri()];
+        assert_eq!(edits[0].range, diag.range);
+        assert_eq!(edits[0].new_text, "\"prd-bucket-logs\"");
+    }
+
     #[test]
     fn non_namespaced_variant_renders_bare_value() {
         let payload = EnumDiagnosticData::new(
@@ -346,4 +716,161 @@ mod tests {
             .expect("2012_10_17 quickfix");
         assert_eq!(preferred.is_preferred, Some(true));
     }
+
+    fn required_diag(attribute: &str, line: u32, new_text: &str) -> Diagnostic {
+        Diagnostic {
+            data: Some(
+                serde_json::to_value(RequiredAttributeDiagnosticData::new(
+                    attribute.to_string(),
+                    Position { line, character: 0 },
+                    new_text.to_string(),
+                ))
+                .unwrap(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn edits_of(action: &CodeAction) -> &Vec<TextEdit> {
+        &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&dummy_uri()]
+    }
+
+    /// Apply non-overlapping edits to `text`, last position first.
+    fn apply(text: &str, edits: &[TextEdit]) -> String {
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        let mut edits = edits.to_vec();
+        edits.sort_by_key(|e| std::cmp::Reverse((e.range.start.line, e.range.start.character)));
+        for edit in edits {
+            let line = &mut lines[edit.range.start.line as usize];
+            let start = edit.range.start.character as usize;
+            let end = edit.range.end.character as usize;
+            line.replace_range(start..end, &edit.new_text);
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn required_payload_inserts_attribute_line() {
+        let diag = required_diag("cidr_block", 3, "  cidr_block = ''\n");
+        assert!(EnumDiagnosticData::from_diagnostic(&diag).is_none());
+        let actions = code_actions_for_diagnostic(&dummy_uri(), &diag);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Insert required attribute `cidr_block`");
+        let edits = edits_of(&actions[0]);
+        assert_eq!(edits[0].range.start, Position::new(3, 0));
+        assert_eq!(edits[0].range.end, Position::new(3, 0));
+        assert_eq!(edits[0].new_text, "  cidr_block = ''\n");
+    }
+
+    #[test]
+    fn insert_all_is_offered_per_block_with_several_missing_attributes() {
+        let diags = vec![
+            required_diag("name", 1, "  name = ''\n"),
+            required_diag("mode", 1, "  mode = test.r.Mode.fast\n"),
+            required_diag("count", 8, "  count = 0\n"),
+        ];
+        let actions = code_actions_for_diagnostics(&dummy_uri(), &diags);
+        let titles: Vec<&str> = actions.iter().map(|a| a.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Insert required attribute `name`",
+                "Insert required attribute `mode`",
+                "Insert required attribute `count`",
+                "Insert all 2 missing required attributes",
+            ]
+        );
+        let all = actions.last().unwrap();
+        let inserted: Vec<&str> = edits_of(all).iter().map(|e| e.new_text.as_str()).collect();
+        assert_eq!(
+            inserted,
+            vec!["  name = ''\n", "  mode = test.r.Mode.fast\n"]
+        );
+        assert_eq!(all.diagnostics.as_ref().unwrap().len(), 2);
+    }
+
+    const EXTRACT_SOURCE: &str = "\
+let vpc = awscc.ec2.Vpc {
+  cidr_block = '10.0.0.0/16'  # primary
+  tags = { Name = 'main' }
+}
+
+let cidr_block = '10.1.0.0/16'
+
+awscc.ec2.Subnet {
+  vpc_id     = vpc.vpc_id
+  cidr_block = cidr_block
+}";
+
+    fn cursor(line: u32) -> Range {
+        Range::new(Position::new(line, 4), Position::new(line, 4))
+    }
+
+    #[test]
+    fn extract_moves_value_into_let_above_the_block() {
+        let action = extract_to_let_action(&dummy_uri(), EXTRACT_SOURCE, cursor(2)).unwrap();
+        assert_eq!(action.title, "Extract value to `let tags`");
+        assert_eq!(action.kind, Some(CodeActionKind::REFACTOR_EXTRACT));
+        let result = apply(EXTRACT_SOURCE, edits_of(&action));
+        assert!(
+            result.starts_with(
+                "let tags = { Name = 'main' }\n\nlet vpc = awscc.ec2.Vpc {\n  cidr_block = '10.0.0.0/16'  # primary\n  tags = tags\n}"
+            ),
+            "{result}"
+        );
+    }
+
+    #[test]
+    fn extract_keeps_trailing_comment_and_avoids_taken_names() {
+        let action = extract_to_let_action(&dummy_uri(), EXTRACT_SOURCE, cursor(1)).unwrap();
+        assert_eq!(action.title, "Extract value to `let cidr_block_2`");
+        let result = apply(EXTRACT_SOURCE, edits_of(&action));
+        assert!(
+            result.starts_with("let cidr_block_2 = '10.0.0.0/16'\n\nlet vpc"),
+            "{result}"
+        );
+        assert!(result.contains("  cidr_block = cidr_block_2  # primary\n"));
+    }
+
+    #[test]
+    fn extract_applies_to_anonymous_resources_but_not_to_references() {
+        let action = extract_to_let_action(&dummy_uri(), EXTRACT_SOURCE, cursor(8)).unwrap();
+        let edits = edits_of(&action);
+        assert_eq!(edits[0].range.start, Position::new(7, 0));
+        assert_eq!(edits[0].new_text, "let vpc_id = vpc.vpc_id\n\n");
+
+        // Already a binding, a top-level line, and a closing brace.
+        for line in [9, 5, 3] {
+            assert!(extract_to_let_action(&dummy_uri(), EXTRACT_SOURCE, cursor(line)).is_none());
+        }
+    }
+
+    #[test]
+    fn extract_is_not_offered_inside_for_bodies_or_provider_blocks() {
+        let text = "\
+provider awscc {
+  region = awscc.Region.ap_northeast_1
+}
+
+let subnets = for az in azs {
+  awscc.ec2.Subnet {
+    availability_zone = az
+    cidr_block = '10.0.1.0/24'
+  }
+}";
+        for line in [1, 7] {
+            assert!(extract_to_let_action(&dummy_uri(), text, cursor(line)).is_none());
+        }
+    }
+
+    #[test]
+    fn extract_requires_the_value_to_end_on_the_same_line() {
+        let text = "\
+awscc.s3.Bucket {
+  tags = {
+    Name = 'logs'
+  }
+}";
+        assert!(extract_to_let_action(&dummy_uri(), text, cursor(1)).is_none());
+    }
 }
@@ -12,11 +12,13 @@ pub(crate) use dsl_source::DslSource;
 use std::path::Path;
 use std::sync::Arc;
 
-use tower_lsp::lsp_types::{Command, CompletionItem, CompletionItemKind, Position, Range};
+use tower_lsp::lsp_types::{
+    Command, CompletionItem, CompletionItemKind, Documentation, Position, Range,
+};
 
 use crate::document::Document;
 use carina_core::schema::{
-    AttributeType, CompletionValue, ResourceSchema, SchemaKind, SchemaRegistry, Shape, StructField,
+    AttributeType, CompletionValue, ResourceSchema, SchemaRegistry, Shape, StructField,
 };
 
 pub struct CompletionProvider {
@@ -63,16 +65,7 @@ impl CompletionProvider {
 
     /// Look up a schema by `"<provider>.<resource_type>"` key, trying Managed first.
     pub(super) fn lookup_schema(&self, key: &str) -> Option<&ResourceSchema> {
-        let (provider, resource_type) = match key.split_once('.') {
-            Some(parts) => parts,
-            None => return self.schemas.get("", key, SchemaKind::Resource),
-        };
-        self.schemas
-            .get(provider, resource_type, SchemaKind::Resource)
-            .or_else(|| {
-                self.schemas
-                    .get(provider, resource_type, SchemaKind::DataSource)
-            })
+        self.schemas.get_by_type_name(key)
     }
 
     pub fn complete(
@@ -118,6 +111,13 @@ impl CompletionProvider {
             );
         }
 
+        // `"${binding.<partial>` — same string-literal suppression as above.
+        if let Some((binding, partial)) = detect_interpolation_binding_dot(&text, position) {
+            return self.interpolation_binding_dot_completions(
+                &text, &binding, &partial, position, base_path,
+            );
+        }
+
         let context = self.get_completion_context(&text, position);
 
         match context {
@@ -756,26 +756,14 @@ impl CompletionProvider {
             if let Some(fields) = self.resolve_struct_fields_for_path(schema, attr_path) {
                 return fields
                     .iter()
-                    .map(|field| {
-                        let required_marker = if field.required { " (required)" } else { "" };
-                        CompletionItem {
-                            label: field.name.clone(),
-                            kind: Some(CompletionItemKind::FIELD),
-                            detail: field
-                                .description
-                                .as_ref()
-                                .map(|d| format!("{}{}", d, required_marker))
-                                .or_else(|| {
-                                    if field.required {
-                                        Some("(required)".to_string())
-                                    } else {
-                                        None
-                                    }
-                                }),
-                            insert_text: Some(format!("{} = ", field.name)),
-                            command: Some(trigger_suggest.clone()),
-                            ..Default::default()
-                        }
+                    .map(|field| CompletionItem {
+                        label: field.name.clone(),
+                        kind: Some(CompletionItemKind::FIELD),
+                        detail: Some(member_detail(&field.field_type, field.required)),
+                        documentation: member_documentation(field.description.as_deref()),
+                        insert_text: Some(format!("{} = ", field.name)),
+                        command: Some(trigger_suggest.clone()),
+                        ..Default::default()
                     })
                     .collect();
             }
@@ -948,6 +936,22 @@ enum CompletionContext {
     None,
 }
 
+/// Completion `detail` for a schema attribute or struct field: its type,
+/// marked when the block must set it. Always present so the popup shows
+/// what the value should look like even for undocumented attributes.
+pub(super) fn member_detail(attr_type: &AttributeType, required: bool) -> String {
+    if required {
+        format!("{} (required)", attr_type.type_name())
+    } else {
+        attr_type.type_name()
+    }
+}
+
+/// Completion `documentation` carrying the schema description, if any.
+pub(super) fn member_documentation(description: Option<&str>) -> Option<Documentation> {
+    description.map(|d| Documentation::String(d.to_string()))
+}
+
 /// Detect a `let <binding> = upstream_state {` opening line, where `<binding>`
 /// is a bare identifier. Used to enter the upstream_state block context.
 /// Extract the type annotation from a line that opens an `exports`
@@ -1159,6 +1163,29 @@ fn detect_interpolation_bare_partial(text: &str, position: Position) -> Option<S
     Some(partial.to_string())
 }
 
+/// `<binding>.<partial>` typed directly after an unescaped `${` inside a
+/// double-quoted string. Whether `<binding>` names a resource is left to
+/// the caller.
+fn detect_interpolation_binding_dot(text: &str, position: Position) -> Option<(String, String)> {
+    let current_line = text.lines().nth(position.line as usize)?;
+    if !current_line.as_bytes().contains(&b'$') {
+        return None;
+    }
+    let prefix: String = current_line
+        .chars()
+        .take(position.character as usize)
+        .collect();
+    let segments = parse_trailing_dotted_segments(&prefix, 2)?;
+    let typed = segments[0].len() + 1 + segments[1].len();
+    let before = prefix[..prefix.len() - typed]
+        .strip_suffix('{')?
+        .strip_suffix('$')?;
+    if before.ends_with('\\') || !is_inside_double_quoted_string(before) {
+        return None;
+    }
+    Some((segments[0].to_string(), segments[1].to_string()))
+}
+
 /// Returns true when an unmatched (odd-count) double quote opens a
 /// string literal somewhere in `prefix`. Backslash-escaped quotes
 /// (`\"`) don't open or close the literal and are skipped.
@@ -2414,6 +2414,7 @@ fn directives_block_completion_includes_all_five_keys() {
         "create_before_destroy",
         "prevent_destroy",
         "depends_on",
+        "enabled",
         "provider",
     ] {
         assert!(
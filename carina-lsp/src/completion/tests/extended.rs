@@ -712,11 +712,17 @@ fn builtin_function_completions_cover_all_functions() {
     let labels: Vec<&str> = completions.iter().map(|c| c.label.as_str()).collect();
 
     let expected = [
+        "base64_encode",
+        "cidr_host",
         "cidr_subnet",
+        "coalesce",
         "concat",
         "env",
         "flatten",
+        "format",
         "join",
+        "json_encode",
+        "keyed",
         "keys",
         "length",
         "lookup",
@@ -4379,3 +4385,110 @@ attributes {
         labels
     );
 }
+
+#[test]
+fn schema_attribute_completions_show_type_and_required() {
+    let versioning = AttributeType::struct_(
+        "Versioning".to_string(),
+        vec![
+            StructField::new("status", AttributeType::string())
+                .required()
+                .with_description("Versioning state of the bucket"),
+            StructField::new("mfa_delete", AttributeType::bool()),
+        ],
+    );
+    let schema = ResourceSchema::new("s3.Bucket")
+        .attribute(AttributeSchema::new("bucket_name", AttributeType::string()).required())
+        .attribute(
+            AttributeSchema::new("versioning", versioning)
+                .with_description("Versioning configuration"),
+        );
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("test", schema);
+    let provider =
+        CompletionProvider::new(Arc::new(schemas), vec!["test".to_string()], vec![], vec![]);
+
+    // Required attributes without a description still carry their type.
+    let attrs = provider.attribute_completions_for_type("test.s3.Bucket");
+    let bucket_name = find_completion(&attrs, "bucket_name");
+    assert_eq!(bucket_name.detail.as_deref(), Some("String (required)"));
+    assert!(bucket_name.documentation.is_none());
+
+    let versioning = find_completion(&attrs, "versioning");
+    assert_eq!(versioning.detail.as_deref(), Some("Struct(Versioning)"));
+    assert_eq!(
+        versioning.documentation,
+        Some(Documentation::String(
+            "Versioning configuration".to_string()
+        ))
+    );
+
+    let fields = provider.struct_field_completions("test.s3.Bucket", &["versioning".to_string()]);
+    let status = find_completion(&fields, "status");
+    assert_eq!(status.detail.as_deref(), Some("String (required)"));
+    assert_eq!(
+        status.documentation,
+        Some(Documentation::String(
+            "Versioning state of the bucket".to_string()
+        ))
+    );
+    assert_eq!(
+        find_completion(&fields, "mfa_delete").detail.as_deref(),
+        Some("Bool")
+    );
+}
+
+fn test_provider_with_read_only_vpc_id() -> CompletionProvider {
+    let vpc = ResourceSchema::new("ec2.Vpc")
+        .attribute(AttributeSchema::new("cidr_block", AttributeType::string()))
+        .attribute(AttributeSchema::new("vpc_id", AttributeType::string()).read_only());
+    let subnet = ResourceSchema::new("ec2.Subnet")
+        .attribute(AttributeSchema::new("vpc_id", AttributeType::string()))
+        .attribute(AttributeSchema::new(
+            "tags",
+            AttributeType::map(AttributeType::string()),
+        ));
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("awscc", vpc);
+    schemas.insert("awscc", subnet);
+    CompletionProvider::new(Arc::new(schemas), vec!["awscc".to_string()], vec![], vec![])
+}
+
+/// Completion after `<binding>.` fires wherever the reference is typed,
+/// not only directly after `=`, and replaces exactly `<binding>.<partial>`.
+#[test]
+fn binding_dot_completion_inside_list_call_and_interpolation() {
+    let provider = test_provider_with_read_only_vpc_id();
+    let cases = [
+        ("  vpc_id = [vpc.", 12),
+        ("  vpc_id = join(\",\", [vpc.vp", 22),
+        ("  vpc_id = \"${vpc.", 14),
+    ];
+    for (line, binding_start) in cases {
+        let text = format!("let vpc = awscc.ec2.Vpc {{\n}}\n\nawscc.ec2.Subnet {{\n{line}\n}}");
+        let position = Position::new(4, line.chars().count() as u32);
+        let completions = provider.complete(&create_document(&text), position, None);
+        let item = find_completion(&completions, "vpc.vpc_id");
+        let Some(tower_lsp::lsp_types::CompletionTextEdit::Edit(edit)) = &item.text_edit else {
+            panic!("expected a text edit for {line:?}");
+        };
+        assert_eq!(edit.range.start.character, binding_start, "{line:?}");
+        assert_eq!(edit.range.end, position, "{line:?}");
+    }
+}
+
+#[test]
+fn binding_dot_completion_ranks_read_only_attributes_first() {
+    let provider = test_provider_with_read_only_vpc_id();
+    let text = "let vpc = awscc.ec2.Vpc {\n}\n\nawscc.ec2.Subnet {\n  vpc_id = vpc.\n}";
+    let completions = provider.complete(&create_document(text), Position::new(4, 15), None);
+    let mut labels: Vec<(&str, &str)> = completions
+        .iter()
+        .map(|c| (c.sort_text.as_deref().unwrap(), c.label.as_str()))
+        .collect();
+    labels.sort();
+    assert_eq!(
+        labels.iter().map(|(_, l)| *l).collect::<Vec<_>>(),
+        vec!["vpc.vpc_id", "vpc.cidr_block"]
+    );
+}
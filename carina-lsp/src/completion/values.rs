@@ -8,7 +8,7 @@ use tower_lsp::lsp_types::{
 
 use carina_core::builtins;
 use carina_core::parser::snake_to_pascal;
-use carina_core::schema::{AttributeType, Shape, TypeIdentity, legacy_validator};
+use carina_core::schema::{AttributeType, DslMap, Shape, TypeIdentity, legacy_validator};
 
 use super::{CompletionProvider, DslSource};
 
@@ -21,6 +21,8 @@ const UPSTREAM_SOURCE_MAX_ITEMS: usize = 100;
 struct BindingDotContext {
     binding_name: String,
     resource_type: String,
+    /// Attribute characters typed after the dot.
+    partial: String,
 }
 
 /// Caller mode for [`CompletionProvider::in_scope_binding_completions`].
@@ -72,13 +74,11 @@ impl CompletionProvider {
         // Get schema for specific resource type
         if let Some(schema) = self.lookup_schema(resource_type) {
             for attr in schema.attributes.values().filter(|a| !a.read_only) {
-                let detail = attr.description.clone();
-                let required_marker = if attr.required { " (required)" } else { "" };
-
                 completions.push(CompletionItem {
                     label: attr.name.clone(),
                     kind: Some(CompletionItemKind::PROPERTY),
-                    detail: detail.map(|d| format!("{}{}", d, required_marker)),
+                    detail: Some(super::member_detail(&attr.attr_type, attr.required)),
+                    documentation: super::member_documentation(attr.description.as_deref()),
                     insert_text: Some(format!("{} = ", attr.name)),
                     command: Some(trigger_suggest.clone()),
                     ..Default::default()
@@ -147,15 +147,16 @@ impl CompletionProvider {
         let mut src_buf = String::new();
         let src = DslSource::resolve_directory(text, base_path, &mut src_buf);
 
-        // Check if the user has typed "binding." after "=" — if so, show only
+        // Check if the user has typed "binding." in the value — if so, show only
         // that binding's resource attributes, not built-in functions or generic completions.
         if let Some(dot_binding) =
             self.detect_binding_dot_context(text, position, current_binding, src)
         {
+            let typed = format!("{}.{}", dot_binding.binding_name, dot_binding.partial);
             return self.binding_attribute_completions(
                 &dot_binding.binding_name,
                 &dot_binding.resource_type,
-                ref_edit_range,
+                super::range_for_partial(position, &typed),
             );
         }
 
@@ -432,8 +433,10 @@ impl CompletionProvider {
         completions
     }
 
-    /// Detect if the user has typed `binding_name.` after `=` on the current
-    /// line. Returns the binding name and its resource type if detected.
+    /// Detect if the value being typed ends in `binding_name.` or
+    /// `binding_name.partial` — directly after `=`, or nested inside a
+    /// list, a function call or a `${...}` interpolation. Returns the
+    /// binding name and its resource type if detected.
     ///
     /// `src` must be [`DslSource::DirectoryScoped`] in normal use so that a
     /// binding declared in a sibling `.crn` can be resolved. `BufferOnly` is
@@ -454,21 +457,8 @@ impl CompletionProvider {
         let col = position.character as usize;
         let prefix: String = lines[line_idx].chars().take(col).collect();
 
-        // Extract the value part after "="
-        let after_eq = prefix.rsplit('=').next()?.trim();
-
-        // Check if it looks like "identifier." (ends with dot or has dot followed by partial text)
-        let dot_pos = after_eq.find('.')?;
-        let candidate_binding = &after_eq[..dot_pos];
-
-        // Validate binding name: alphanumeric + underscore
-        if candidate_binding.is_empty()
-            || !candidate_binding
-                .chars()
-                .all(|c| c.is_alphanumeric() || c == '_')
-        {
-            return None;
-        }
+        let segments = super::parse_trailing_dotted_segments(&prefix, 2)?;
+        let (candidate_binding, partial) = (segments[0], segments[1]);
 
         for (binding_name, binding_resource_type) in &self.extract_resource_bindings(src) {
             if binding_name == candidate_binding && !binding_resource_type.is_empty() {
@@ -479,6 +469,7 @@ impl CompletionProvider {
                 return Some(BindingDotContext {
                     binding_name: binding_name.clone(),
                     resource_type: binding_resource_type.clone(),
+                    partial: partial.to_string(),
                 });
             }
         }
@@ -486,8 +477,36 @@ impl CompletionProvider {
         None
     }
 
+    /// Completions for `"${binding.<partial>`: the binding's attributes
+    /// when it names a resource, nothing otherwise.
+    pub(super) fn interpolation_binding_dot_completions(
+        &self,
+        text: &str,
+        binding: &str,
+        partial: &str,
+        position: Position,
+        base_path: Option<&Path>,
+    ) -> Vec<CompletionItem> {
+        let mut src_buf = String::new();
+        let src = DslSource::resolve_directory(text, base_path, &mut src_buf);
+        let Some((_, resource_type)) = self
+            .extract_resource_bindings(src)
+            .into_iter()
+            .find(|(name, resource_type)| name == binding && !resource_type.is_empty())
+        else {
+            return Vec::new();
+        };
+        self.binding_attribute_completions(
+            binding,
+            &resource_type,
+            super::range_for_partial(position, &format!("{binding}.{partial}")),
+        )
+    }
+
     /// Provide completions for a binding's resource attributes.
-    /// Shows all attributes of the binding's resource type as `binding.attribute` completions.
+    /// Shows all attributes of the binding's resource type as `binding.attribute` completions,
+    /// read-only attributes (ids, ARNs and other provider-computed values) first since
+    /// those are what references usually point at.
     fn binding_attribute_completions(
         &self,
         binding_name: &str,
@@ -502,6 +521,11 @@ impl CompletionProvider {
                     label: full_ref.clone(),
                     kind: Some(CompletionItemKind::REFERENCE),
                     detail: attr.description.clone(),
+                    sort_text: Some(format!(
+                        "{}{}",
+                        if attr.read_only { '0' } else { '1' },
+                        attr.name
+                    )),
                     text_edit: Some(tower_lsp::lsp_types::CompletionTextEdit::Edit(TextEdit {
                         range: edit_range,
                         new_text: full_ref,
@@ -762,6 +786,16 @@ impl CompletionProvider {
                 command: Some(trigger_suggest.clone()),
                 ..Default::default()
             },
+            CompletionItem {
+                label: "enabled".to_string(),
+                kind: Some(CompletionItemKind::PROPERTY),
+                detail: Some(
+                    "Keep the block but plan the resource as absent when false".to_string(),
+                ),
+                insert_text: Some("enabled = ".to_string()),
+                command: Some(trigger_suggest.clone()),
+                ..Default::default()
+            },
             CompletionItem {
                 label: "force_delete".to_string(),
                 kind: Some(CompletionItemKind::PROPERTY),
@@ -1837,10 +1871,7 @@ impl CompletionProvider {
                 values
                     .iter()
                     .map(|value| {
-                        let dsl_value = dsl_aliases
-                            .iter()
-                            .find_map(|(api, dsl)| (api == value).then(|| dsl.clone()))
-                            .unwrap_or_else(|| value.clone());
+                        let dsl_value = DslMap::new(dsl_aliases, None).dsl_for(value);
                         let full = format!("{}.{}.{}", ns, type_name, dsl_value);
                         CompletionItem {
                             label: full.clone(),
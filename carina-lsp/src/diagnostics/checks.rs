@@ -406,6 +406,9 @@ impl DiagnosticEngine {
             &overrides,
         )
         .ok()?;
+        // There is no var file here; defaults stand in for the values
+        // `carina plan --var-file` would bind.
+        carina_core::module_resolver::bind_root_variable_defaults(&mut merged);
         // Module expansion is a no-op for configs without `module_call`, and
         // safe to ignore-if-fails for module-loading errors (sibling LSP
         // checks like `check_module_calls` already report those). We only
@@ -445,6 +448,7 @@ impl DiagnosticEngine {
             &overrides,
         )
         .ok()?;
+        carina_core::module_resolver::bind_root_variable_defaults(&mut merged);
         let _ = carina_core::module_resolver::resolve_modules_with_config(
             &mut merged,
             base_path,
@@ -1155,16 +1159,7 @@ impl DiagnosticEngine {
         sibling_bindings: &HashMap<String, String>,
     ) -> Option<String> {
         let resource_type = sibling_bindings.get(binding)?;
-        let (provider, rt) = resource_type
-            .split_once('.')
-            .unwrap_or(("", resource_type.as_str()));
-        let schema = self
-            .schemas
-            .get(provider, rt, carina_core::schema::SchemaKind::Resource)
-            .or_else(|| {
-                self.schemas
-                    .get(provider, rt, carina_core::schema::SchemaKind::DataSource)
-            })?;
+        let schema = self.schemas.get_by_type_name(resource_type)?;
         let attr_schema = schema.attributes.get(attr)?;
         let ref_type = &attr_schema.attr_type;
         if carina_core::validation::is_type_expr_compatible_with_schema(
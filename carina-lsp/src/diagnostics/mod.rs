@@ -449,6 +449,43 @@ impl DiagnosticEngine {
 
                 // Semantic validation using schema
                 let schema = resource_schema().cloned();
+
+                // Compile policy document blocks first, as the CLI
+                // pipeline does, so a block is type-checked as the JSON
+                // string it becomes. A block that does not compile is
+                // reported once here and skipped by the checks below.
+                let mut resource_attributes = resource_attributes;
+                let mut failed_documents = HashSet::new();
+                if let Some(schema) = &schema {
+                    for (attr_name, compiled) in
+                        carina_core::policy_document::compile_attribute_documents(
+                            &resource_attributes,
+                            schema,
+                        )
+                    {
+                        match compiled {
+                            Ok(value) => {
+                                resource_attributes.to_mut().insert(attr_name, value);
+                            }
+                            Err(errors) => {
+                                if let Some((line, col)) =
+                                    self.find_attribute_position(doc, &attr_name, scope)
+                                {
+                                    for error in errors {
+                                        diagnostics.push(carina_diagnostic(
+                                            line,
+                                            col,
+                                            col + attr_name.len() as u32,
+                                            DiagnosticSeverity::ERROR,
+                                            format!("{}: {}", attr_name, error),
+                                        ));
+                                    }
+                                }
+                                failed_documents.insert(attr_name);
+                            }
+                        }
+                    }
+                }
                 if let Some(schema) = &schema {
                     // Check data source without `read` keyword
                     if schema.is_data_source()
@@ -483,6 +520,9 @@ impl DiagnosticEngine {
                         if attr_name.starts_with('_') {
                             continue; // Skip internal attributes
                         }
+                        if failed_documents.contains(attr_name) {
+                            continue;
+                        }
 
                         // Resolve block_name to canonical attribute name
                         let canonical_name = bn_map
@@ -518,14 +558,24 @@ impl DiagnosticEngine {
                             if let Some((line, col)) =
                                 self.find_attribute_position(doc, attr_name, scope)
                             {
-                                // Check if there's a similar attribute (e.g., vpc -> vpc_id)
-                                let suggestion =
-                                    if schema.attributes.contains_key(&format!("{}_id", attr_name))
-                                    {
-                                        format!(". Did you mean '{}_id'?", attr_name)
-                                    } else {
-                                        String::new()
-                                    };
+                                // Reference-shaped names (vpc -> vpc_id) first, then
+                                // the shared edit-distance engine over the schema's
+                                // attribute and block names.
+                                let id_form = format!("{}_id", attr_name);
+                                let suggestion = if schema.attributes.contains_key(&id_form) {
+                                    Some(id_form)
+                                } else {
+                                    let known: Vec<&str> = schema
+                                        .attributes
+                                        .keys()
+                                        .map(String::as_str)
+                                        .chain(bn_map.keys().map(String::as_str))
+                                        .collect();
+                                    carina_core::schema::suggest_similar_name(attr_name, &known)
+                                };
+                                let suggestion = suggestion
+                                    .map(|s| format!(". Did you mean '{}'?", s))
+                                    .unwrap_or_default();
 
                                 diagnostics.push(carina_diagnostic(
                                     line,
@@ -642,6 +692,7 @@ impl DiagnosticEngine {
                                     },
                                     Value::Deferred(DeferredValue::ResourceRef { path }),
                                 ) => check_resource_ref_type_mismatch(
+                                    &self.schemas,
                                     &binding_schema_map,
                                     &attr_schema.attr_type,
                                     path.binding(),
@@ -847,7 +898,8 @@ impl DiagnosticEngine {
                     }
 
                     // Run resource-level validator (e.g., mutually exclusive required fields)
-                    let resolved_attrs = rref.resolved_attributes();
+                    let resolved_attrs =
+                        carina_core::resource::attrs_to_hashmap(&resource_attributes);
                     let lookup =
                         carina_core::parser::provider_context_lookup(&self.provider_context);
                     let is_string_literal =
@@ -870,6 +922,7 @@ impl DiagnosticEngine {
                                     | carina_core::schema::TypeError::StructFieldError { .. }
                                     | carina_core::schema::TypeError::ListItemError { .. }
                                     | carina_core::schema::TypeError::MapValueError { .. }
+                                    | carina_core::schema::TypeError::UnknownAttribute { .. }
                             ) {
                                 continue;
                             }
@@ -892,7 +945,7 @@ impl DiagnosticEngine {
                                 )
                             });
                             if let Some((line, col)) = position {
-                                diagnostics.push(carina_diagnostic_range(
+                                let mut diagnostic = carina_diagnostic_range(
                                     Range {
                                         start: Position {
                                             line,
@@ -910,7 +963,19 @@ impl DiagnosticEngine {
                                     },
                                     DiagnosticSeverity::ERROR,
                                     error.to_string(),
-                                ));
+                                );
+                                if let carina_core::schema::TypeError::MissingRequired { name } =
+                                    &error
+                                {
+                                    diagnostic.data = required_attribute_data(
+                                        doc,
+                                        scope.map_or(line, |(start, _)| start),
+                                        &full_resource_type,
+                                        &schema,
+                                        name,
+                                    );
+                                }
+                                diagnostics.push(diagnostic);
                             }
                         }
                     }
@@ -993,6 +1058,16 @@ impl DiagnosticEngine {
                 parsed,
                 &binding_schema_map,
             ));
+
+            // The `naming` block usually lives in a sibling file, so
+            // prefer the merged parse's policy.
+            let naming = merged
+                .as_ref()
+                .and_then(|merged| merged.naming.as_ref())
+                .or(parsed.naming.as_ref());
+            if let Some(policy) = naming {
+                diagnostics.extend(self.check_naming_policy(doc, parsed, policy));
+            }
         }
 
         // Check for duplicate attribute keys (text-based, works without parsed file)
@@ -1007,6 +1082,78 @@ impl DiagnosticEngine {
         diagnostics
     }
 
+    /// Flag resource names and `Name` tags that violate the project
+    /// naming policy. When the policy is a template, the diagnostic
+    /// carries a [`crate::code_action::NamingDiagnosticData`] payload so
+    /// the quick fix can rename the literal.
+    fn check_naming_policy(
+        &self,
+        doc: &Document,
+        parsed: &ParsedFile,
+        policy: &carina_core::parser::NamingPolicy,
+    ) -> Vec<Diagnostic> {
+        use crate::code_action::NamingDiagnosticData;
+        use tower_lsp::lsp_types::{Position, Range};
+
+        carina_core::validation::naming::check_naming(&parsed.resources, &self.schemas, policy)
+            .into_iter()
+            .filter_map(|violation| {
+                let scope = resource_source_range(
+                    doc,
+                    carina_core::parser::ResourceContext::Direct,
+                    &violation.resource.provider,
+                    &violation.resource.resource_type,
+                );
+                let key = violation
+                    .attribute
+                    .strip_prefix("tags.")
+                    .unwrap_or(&violation.attribute);
+                let value_range = self.find_attribute_value_range(doc, key, scope);
+                let range = match value_range {
+                    Some(range) => range,
+                    None => {
+                        let (line, col) = self.find_attribute_position(doc, key, scope)?;
+                        Range {
+                            start: Position {
+                                line,
+                                character: col,
+                            },
+                            end: Position {
+                                line,
+                                character: col + key.len() as u32,
+                            },
+                        }
+                    }
+                };
+                // Only offer the rename when the range is the quoted
+                // literal itself, not a binding that evaluates to it.
+                let is_literal = value_range.is_some_and(|r| {
+                    r.end.character - r.start.character
+                        == violation.value.chars().count() as u32 + 2
+                });
+                let data = violation
+                    .suggestion
+                    .clone()
+                    .filter(|_| is_literal)
+                    .map(|suggestion| {
+                        serde_json::to_value(NamingDiagnosticData::new(suggestion))
+                            .expect("NamingDiagnosticData serialize")
+                    });
+                Some(Diagnostic {
+                    range,
+                    severity: Some(DiagnosticSeverity::WARNING),
+                    source: Some("carina".to_string()),
+                    message: format!(
+                        "{} '{}' does not match the naming policy",
+                        violation.attribute, violation.value
+                    ),
+                    data,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
     /// Check for duplicate attribute keys within the same block.
     fn check_duplicate_attrs(&self, doc: &Document) -> Vec<Diagnostic> {
         let text = doc.text();
@@ -1338,6 +1485,75 @@ impl DiagnosticEngine {
 /// parses). Callers that fall back to `None` get document-wide search —
 /// the historic behavior — which is still correct when there's no
 /// ambiguity.
+/// Quick-fix payload for a missing required attribute: a line declaring
+/// it with a typed placeholder, inserted right after the block header.
+/// `None` when the header line does not open the block (e.g. a `for`
+/// body whose resource header is further down).
+fn required_attribute_data(
+    doc: &Document,
+    header_line: u32,
+    resource_type: &str,
+    schema: &ResourceSchema,
+    name: &str,
+) -> Option<serde_json::Value> {
+    use crate::code_action::RequiredAttributeDiagnosticData;
+    let text = doc.text();
+    let header = text.lines().nth(header_line as usize)?;
+    if !header.contains(resource_type) || !strip_line_comment(header).trim_end().ends_with('{') {
+        return None;
+    }
+    let attr = schema.attributes.get(name)?;
+    let indent = &header[..header.len() - header.trim_start().len()];
+    let payload = RequiredAttributeDiagnosticData::new(
+        name.to_string(),
+        Position {
+            line: header_line + 1,
+            character: 0,
+        },
+        format!(
+            "{indent}  {name} = {}\n",
+            attribute_placeholder(schema, &attr.attr_type, resource_type)
+        ),
+    );
+    Some(serde_json::to_value(payload).expect("RequiredAttributeDiagnosticData serialize"))
+}
+
+/// Placeholder value of the right shape for a scaffolded attribute. Enums
+/// get their first variant so the result already type-checks.
+fn attribute_placeholder(
+    schema: &ResourceSchema,
+    ty: &carina_core::schema::AttributeType,
+    resource_type: &str,
+) -> String {
+    use carina_core::schema::{DslMap, Shape};
+    match schema.shape_of(ty) {
+        Shape::Int { .. } => "0".to_string(),
+        Shape::Float { .. } => "0.0".to_string(),
+        Shape::Bool => "false".to_string(),
+        Shape::Duration => "0s".to_string(),
+        Shape::List { .. } => "[]".to_string(),
+        Shape::Map { .. } | Shape::Struct { .. } => "{}".to_string(),
+        Shape::Enum {
+            identity,
+            values: Some(values),
+            dsl_aliases,
+            to_dsl,
+            ..
+        } if !values.is_empty() => {
+            let namespace = identity
+                .dotted_prefix()
+                .unwrap_or_else(|| resource_type.to_string());
+            format!(
+                "{}.{}.{}",
+                namespace,
+                identity.kind,
+                DslMap::new(dsl_aliases, to_dsl).dsl_for(&values[0])
+            )
+        }
+        Shape::String { .. } | Shape::Enum { .. } | Shape::Union => "''".to_string(),
+    }
+}
+
 fn resource_source_range(
     doc: &Document,
     ctx: carina_core::parser::ResourceContext<'_>,
@@ -1472,6 +1688,7 @@ fn build_enum_diagnostic(
 /// Returns `Some(message)` on mismatch, `None` when compatible or when the binding/attribute
 /// cannot be resolved (unknown bindings are not flagged here).
 fn check_resource_ref_type_mismatch(
+    registry: &SchemaRegistry,
     binding_schema_map: &HashMap<&str, &ResourceSchema>,
     expected_type: &carina_core::schema::AttributeType,
     ref_binding: &str,
@@ -1481,7 +1698,11 @@ fn check_resource_ref_type_mismatch(
     let ref_attr_schema = ref_schema.attributes.get(ref_attr)?;
 
     // Directional: the ref (source) must be assignable to the expected (sink).
-    if ref_attr_schema.attr_type.is_assignable_to(expected_type) {
+    // Provider families let an `aws` resource reference an `awscc` one.
+    if ref_attr_schema
+        .attr_type
+        .is_assignable_to_in(expected_type, registry)
+    {
         None
     } else {
         Some(format!(
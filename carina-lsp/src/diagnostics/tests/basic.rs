@@ -971,3 +971,17 @@ fn arguments_single_string_literal_type_does_not_surface_parser_error() {
         diagnostics.iter().map(|d| &d.message).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn unterminated_heredoc_is_reported_at_its_opener() {
+    let engine = test_engine();
+    let doc = create_document("let a = 'x'\n\nlet script = <<EOT\necho hi\n");
+
+    let diagnostics = engine.analyze(&doc, None);
+
+    let diag = diagnostics
+        .iter()
+        .find(|d| d.message.contains("Unterminated heredoc"))
+        .expect("unterminated heredoc diagnostic");
+    assert_eq!(diag.range.start.line, 2);
+}
@@ -4080,3 +4080,215 @@ let bucket = awscc.s3.Bucket {
         diags.iter().map(|d| &d.message).collect::<Vec<_>>(),
     );
 }
+
+#[test]
+fn naming_policy_violation_carries_rename_payload() {
+    use carina_core::schema::{AttributeSchema, AttributeType, ResourceSchema};
+    let schema = ResourceSchema::new("s3.Bucket")
+        .attribute(AttributeSchema::new("bucket_name", AttributeType::string()))
+        .with_unique_name_attribute("bucket_name");
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("aws", schema);
+    let engine =
+        DiagnosticEngine::new(Arc::new(schemas), vec!["aws".to_string()], Arc::new(vec![]));
+
+    let src = r#"
+naming {
+    template = "{env}-{type}-{name}"
+    env      = "prd"
+}
+
+let logs = aws.s3.Bucket {
+    bucket_name = "AccessLogs"
+}
+"#;
+    let doc = create_document(src);
+    let diags = engine.analyze(&doc, None);
+    let naming: Vec<_> = diags
+        .iter()
+        .filter(|d| d.message.contains("naming policy"))
+        .collect();
+    assert_eq!(naming.len(), 1, "{diags:?}");
+    assert_eq!(
+        naming[0].message,
+        "bucket_name 'AccessLogs' does not match the naming policy"
+    );
+    assert_eq!(naming[0].range.start.line, 7);
+    let data = crate::code_action::NamingDiagnosticData::from_diagnostic(naming[0])
+        .expect("naming payload");
+    assert_eq!(data.suggestion, "prd-bucket-accesslogs");
+}
+
+#[test]
+fn unknown_attribute_suggests_closest_schema_attribute_once() {
+    use carina_core::schema::{AttributeSchema, AttributeType, ResourceSchema};
+
+    let schema = ResourceSchema::new("test.resource")
+        .attribute(AttributeSchema::new("bucket_name", AttributeType::string()))
+        .attribute(AttributeSchema::new("versioning", AttributeType::bool()));
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("test", schema);
+    let engine = custom_engine(schemas);
+    let doc = create_document(
+        r#"provider test {
+  region = "ap-northeast-1"
+}
+
+test.test.resource {
+  bucket_nme = "logs"
+}"#,
+    );
+
+    let diagnostics = engine.analyze(&doc, None);
+    let unknown: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.message.contains("bucket_nme"))
+        .collect();
+    assert_eq!(
+        unknown.len(),
+        1,
+        "unknown attribute must be reported once. Got: {:?}",
+        diagnostics.iter().map(|d| &d.message).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        unknown[0].message,
+        "Unknown attribute 'bucket_nme' for resource type 'test.resource'. Did you mean 'bucket_name'?"
+    );
+    assert_eq!(unknown[0].range.start.line, 5);
+}
+
+#[test]
+fn missing_required_attribute_is_reported() {
+    use carina_core::schema::{AttributeSchema, AttributeType, ResourceSchema};
+
+    let schema = ResourceSchema::new("test.resource")
+        .attribute(AttributeSchema::new("name", AttributeType::string()).required())
+        .attribute(AttributeSchema::new("size", AttributeType::int()));
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("test", schema);
+    let engine = custom_engine(schemas);
+    let doc = create_document(
+        r#"provider test {
+  region = "ap-northeast-1"
+}
+
+test.test.resource {
+  size = 3
+}"#,
+    );
+
+    let diagnostics = engine.analyze(&doc, None);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|d| d.message == "Required attribute 'name' is missing" && d.range.start.line == 4),
+        "Got: {:?}",
+        diagnostics.iter().map(|d| &d.message).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn missing_required_attributes_carry_scaffold_payload() {
+    use crate::code_action::RequiredAttributeDiagnosticData;
+    use carina_core::schema::{AttributeSchema, AttributeType, ResourceSchema, enum_identity};
+
+    let schema = ResourceSchema::new("test.resource")
+        .attribute(AttributeSchema::new("name", AttributeType::string()).required())
+        .attribute(AttributeSchema::new("count", AttributeType::int()).required())
+        .attribute(
+            AttributeSchema::new(
+                "mode",
+                AttributeType::enum_(
+                    enum_identity("Mode", Some("test.test.resource")),
+                    Some(vec!["fast".to_string(), "slow".to_string()]),
+                    vec![],
+                    None,
+                    None,
+                ),
+            )
+            .required(),
+        )
+        .attribute(AttributeSchema::new("size", AttributeType::int()));
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("test", schema);
+    let engine = custom_engine(schemas);
+    let doc = create_document(
+        r#"provider test {
+  region = "ap-northeast-1"
+}
+
+let r = test.test.resource {
+  size = 3
+}"#,
+    );
+
+    let diagnostics = engine.analyze(&doc, None);
+    let mut payloads: Vec<RequiredAttributeDiagnosticData> = diagnostics
+        .iter()
+        .filter_map(RequiredAttributeDiagnosticData::from_diagnostic)
+        .collect();
+    payloads.sort_by(|a, b| a.attribute.cmp(&b.attribute));
+    let inserted: Vec<(&str, Position)> = payloads
+        .iter()
+        .map(|p| (p.new_text.as_str(), p.insert_at))
+        .collect();
+    assert_eq!(
+        inserted,
+        vec![
+            ("  count = 0\n", Position::new(5, 0)),
+            (
+                "  mode = test.test.resource.Mode.fast\n",
+                Position::new(5, 0)
+            ),
+            ("  name = ''\n", Position::new(5, 0)),
+        ]
+    );
+}
+
+#[test]
+fn policy_document_blocks_are_compiled_before_type_checking() {
+    use carina_core::schema::{AttributeSchema, ResourceSchema, types};
+    let schema = ResourceSchema::new("iam.role_policy").attribute(AttributeSchema::new(
+        "policy_document",
+        types::iam_policy_document(),
+    ));
+    let mut schemas = SchemaRegistry::new();
+    schemas.insert("test", schema);
+    let engine = custom_engine(schemas);
+    let doc = |statement: &str| {
+        create_document(&format!(
+            r#"provider test {{
+  region = "ap-northeast-1"
+}}
+
+let p = test.iam.role_policy {{
+  policy_document {{
+    statement {{
+      {statement}
+    }}
+  }}
+}}"#
+        ))
+    };
+
+    let diagnostics = engine.analyze(&doc(r#"actions = ["s3:GetObject"]"#), None);
+    let errors: Vec<&str> = diagnostics
+        .iter()
+        .filter(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+        .map(|d| d.message.as_str())
+        .collect();
+    assert!(errors.is_empty(), "{errors:?}");
+
+    let diagnostics = engine.analyze(&doc(r#"action = ["s3:GetObject"]"#), None);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.starts_with("policy_document: statement 1: unknown key 'action'")),
+        "{messages:?}"
+    );
+    assert!(
+        !messages.iter().any(|m| m.contains("Type mismatch")),
+        "{messages:?}"
+    );
+}
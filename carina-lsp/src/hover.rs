@@ -47,6 +47,50 @@ fn find_use_import_path(
     None
 }
 
+/// Longest live value rendered in a hover before it is cut short.
+const LIVE_VALUE_MAX_CHARS: usize = 120;
+
+/// The applied value of `attribute` on the resource bound by the
+/// enclosing top-level `let`, read from the project's state replica.
+/// `None` when the cursor is not in a bound block, the resource has not
+/// been applied, or no replica has been written yet.
+fn live_attribute_value(
+    doc: &Document,
+    position: Position,
+    base_path: &Path,
+    attribute: &str,
+) -> Option<String> {
+    let text = doc.text();
+    let lines: Vec<&str> = text.lines().collect();
+    let current = *lines.get(position.line as usize)?;
+    if !current.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let header = lines[..position.line as usize]
+        .iter()
+        .rev()
+        .find(|l| !l.is_empty() && !l.starts_with(char::is_whitespace))?;
+    let binding = header
+        .strip_prefix("let ")?
+        .split(|c: char| c.is_whitespace() || c == '=')
+        .next()?;
+
+    let state = carina_state::StateReplica::new(base_path).load()?;
+    let value = state
+        .resources
+        .iter()
+        .find(|r| r.binding.as_deref() == Some(binding))?
+        .attributes
+        .get(attribute)?;
+    let rendered = serde_json::to_string(value).ok()?;
+    if rendered.chars().count() > LIVE_VALUE_MAX_CHARS {
+        let truncated: String = rendered.chars().take(LIVE_VALUE_MAX_CHARS).collect();
+        Some(format!("{truncated}…"))
+    } else {
+        Some(rendered)
+    }
+}
+
 /// Format a Value for hover display
 fn format_value_for_hover(value: &Value) -> String {
     match value {
@@ -55,6 +99,7 @@ fn format_value_for_hover(value: &Value) -> String {
         Value::Concrete(ConcreteValue::Float(f)) => f.to_string(),
         Value::Concrete(ConcreteValue::Bool(b)) => b.to_string(),
         Value::Concrete(ConcreteValue::Duration(d)) => carina_core::value::render_duration(*d),
+        Value::Concrete(ConcreteValue::ByteSize(n)) => carina_core::value::render_byte_size(*n),
         Value::Concrete(ConcreteValue::List(_)) | Value::Concrete(ConcreteValue::StringList(_)) => {
             "[...]".to_string()
         }
@@ -196,7 +241,15 @@ impl HoverProvider {
         // Check for attribute hover (but not in module call context)
         if !self.is_in_module_call(doc, position) {
             let enclosing_resource = self.find_enclosing_resource_type(doc, position);
-            if let Some(hover) = self.attribute_hover(&word, enclosing_resource.as_deref()) {
+            if let Some(mut hover) = self.attribute_hover(&word, enclosing_resource.as_deref()) {
+                if let Some(base) = base_path
+                    && let Some(value) = live_attribute_value(doc, position, base, &word)
+                    && let HoverContents::Markup(markup) = &mut hover.contents
+                {
+                    markup
+                        .value
+                        .push_str(&format!("\n- **Current value**: `{value}`"));
+                }
                 return Some(hover);
             }
         }
@@ -499,13 +552,18 @@ impl HoverProvider {
                 if word.contains(&format!("{}_", key)) || word.contains(&format!("_{}", key)) {
                     continue;
                 }
-                return self.schema_hover(&key, schema);
+                return self.schema_hover(provider, &key, schema);
             }
         }
         None
     }
 
-    fn schema_hover(&self, resource_name: &str, schema: &ResourceSchema) -> Option<Hover> {
+    fn schema_hover(
+        &self,
+        provider: &str,
+        resource_name: &str,
+        schema: &ResourceSchema,
+    ) -> Option<Hover> {
         let description = convert_markdown_links_to_plain_text(
             schema
                 .description
@@ -514,6 +572,9 @@ impl HoverProvider {
         );
 
         let mut content = format!("## {}\n\n{}\n", resource_name, description);
+        if let Some(version) = self.schemas.provider_version(provider) {
+            content.push_str(&format!("\n_Schema from `{}` {}_\n", provider, version));
+        }
 
         // Split attributes into arguments (writable) and attributes (read-only)
         let mut arguments: Vec<&carina_core::schema::AttributeSchema> = schema
@@ -622,29 +683,7 @@ impl HoverProvider {
         // an unrelated schema (nondeterministic via HashMap iteration order) —
         // see #1988.
         let key = enclosing_resource?;
-        // Try splitting on the first dot first (`provider.resource_type`); if that
-        // doesn't resolve, fall back to treating the whole key as the resource type
-        // under the empty provider — some test fixtures register schemas that way.
-        let lookup = |provider: &str, resource_type: &str| {
-            self.schemas
-                .get(
-                    provider,
-                    resource_type,
-                    carina_core::schema::SchemaKind::Resource,
-                )
-                .or_else(|| {
-                    self.schemas.get(
-                        provider,
-                        resource_type,
-                        carina_core::schema::SchemaKind::DataSource,
-                    )
-                })
-        };
-        let schema = if let Some((provider, rest)) = key.split_once('.') {
-            lookup(provider, rest).or_else(|| lookup("", key))
-        } else {
-            lookup("", key)
-        }?;
+        let schema = self.schemas.get_by_type_name(key)?;
         schema
             .attributes
             .get(word)
@@ -662,10 +701,20 @@ impl HoverProvider {
         };
         let type_name = format!("{}", attr.attr_type);
 
-        let content = format!(
+        let mut content = format!(
             "## {}\n\n{}\n\n- **Type**: {}\n- **Required**: {}",
             attr.name, description, type_name, required
         );
+        if let Some(unit) = attr.unit {
+            let literals = if unit.is_time() {
+                "`30s`, `12h`, `7d`"
+            } else {
+                "`512MiB`, `20GiB`, `5GB`"
+            };
+            content.push_str(&format!(
+                "\n- **Unit**: {unit} (accepts literals such as {literals})"
+            ));
+        }
 
         Some(Hover {
             contents: HoverContents::Markup(MarkupContent {
@@ -875,7 +924,7 @@ mod tests {
             .schemas
             .get("", "ec2.Vpc", carina_core::schema::SchemaKind::Resource)
             .unwrap();
-        let hover = provider.schema_hover("ec2.Vpc", schema).unwrap();
+        let hover = provider.schema_hover("", "ec2.Vpc", schema).unwrap();
 
         let content = match &hover.contents {
             HoverContents::Markup(m) => &m.value,
@@ -896,6 +945,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resource_hover_names_provider_version() {
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert("awscc", ResourceSchema::new("ec2.Vpc"));
+        schemas.set_provider_version("awscc", "1.4.0");
+        let provider = HoverProvider::new(Arc::new(schemas), vec![]);
+
+        let hover = provider.resource_type_hover("awscc.ec2.Vpc").unwrap();
+        let HoverContents::Markup(content) = hover.contents else {
+            panic!("Expected markup content");
+        };
+        assert!(content.value.contains("_Schema from `awscc` 1.4.0_"));
+    }
+
     #[test]
     fn test_attribute_hover_converts_markdown_links() {
         // Attribute description with a markdown link (full, not truncated)
@@ -1061,6 +1124,54 @@ awscc.ec2.vpc_gateway_attachment {
         );
     }
 
+    #[test]
+    fn attribute_hover_shows_live_value_from_state_replica() {
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert(
+            "aws",
+            ResourceSchema::new("s3.Bucket")
+                .attribute(AttributeSchema::new("bucket_name", AttributeType::string()))
+                .attribute(AttributeSchema::new("arn", AttributeType::string())),
+        );
+        let provider = HoverProvider::new(Arc::new(schemas), vec![]);
+        let doc = Document::new(
+            "let logs = aws.s3.Bucket {\n  bucket_name = 'logs'\n}\n\naws.s3.Bucket {\n  bucket_name = 'other'\n}\n"
+                .to_string(),
+            Arc::new(ProviderContext::default()),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let hover_text = |line: u32| {
+            let hover = provider
+                .hover_with_base_path(&doc, Position::new(line, 4), Some(dir.path()))
+                .expect("attribute hover");
+            match hover.contents {
+                HoverContents::Markup(m) => m.value,
+                _ => panic!("Expected markup content"),
+            }
+        };
+        assert!(!hover_text(1).contains("Current value"));
+
+        let mut state = carina_state::StateFile::new();
+        let mut bucket = carina_state::ResourceState::new("s3.Bucket", "logs", "aws");
+        bucket.identifier = Some("logs-1a2b".to_string());
+        bucket.binding = Some("logs".to_string());
+        bucket
+            .attributes
+            .insert("bucket_name".to_string(), serde_json::json!("logs-1a2b"));
+        state.resources.push(bucket);
+        let replica = carina_state::StateReplica::new(dir.path());
+        std::fs::create_dir_all(replica.path().parent().unwrap()).unwrap();
+        std::fs::write(replica.path(), serde_json::to_string(&state).unwrap()).unwrap();
+
+        assert!(
+            hover_text(1).contains("- **Current value**: `\"logs-1a2b\"`"),
+            "{}",
+            hover_text(1)
+        );
+        // The anonymous block has no binding to look up.
+        assert!(!hover_text(5).contains("Current value"));
+    }
+
     #[test]
     fn test_attribute_hover_unknown_in_enclosing_resource_returns_none() {
         // Regression for #1988: when a word is NOT an attribute of the
@@ -1179,12 +1290,18 @@ awscc.ec2.vpc_gateway_attachment {
     fn test_all_builtin_functions_have_hover() {
         let provider = HoverProvider::new(Arc::new(SchemaRegistry::new()), vec![]);
         let names = [
+            "base64_encode",
+            "cidr_host",
             "cidr_subnet",
+            "coalesce",
             "concat",
             "decrypt",
             "env",
             "flatten",
+            "format",
             "join",
+            "json_encode",
+            "keyed",
             "keys",
             "length",
             "lookup",
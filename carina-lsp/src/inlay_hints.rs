@@ -0,0 +1,314 @@
+//! Inlay hints for resource attributes.
+//!
+//! Each single-line attribute assignment inside a resource or data source
+//! block gets its schema type (`cidr_block = '10.0.0.0/16'  : Ipv4Cidr`),
+//! and an enum written in namespaced or aliased form gets the canonical
+//! API value it resolves to. Both are computed from the token scan, so
+//! hints survive a file the parser currently rejects.
+
+use std::sync::Arc;
+
+use tower_lsp::lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position, Range};
+
+use carina_core::schema::{AttributeSchema, ResourceSchema, SchemaRegistry};
+use carina_core::utils::canonicalize_enum_to_api;
+
+use crate::scan::{Token, TokenKind, block_schema, is_sym, scan};
+
+/// Which hints to show, read from the client's `initializationOptions`:
+///
+/// ```json
+/// { "inlayHints": { "types": true, "enumValues": false } }
+/// ```
+///
+/// Both kinds default to on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InlayHintOptions {
+    pub types: bool,
+    pub enum_values: bool,
+}
+
+impl Default for InlayHintOptions {
+    fn default() -> Self {
+        Self {
+            types: true,
+            enum_values: true,
+        }
+    }
+}
+
+impl InlayHintOptions {
+    pub fn from_initialization_options(options: Option<&serde_json::Value>) -> Self {
+        let hints = options.and_then(|o| o.get("inlayHints"));
+        let flag = |key: &str| {
+            hints
+                .and_then(|h| h.get(key))
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(true)
+        };
+        Self {
+            types: flag("types"),
+            enum_values: flag("enumValues"),
+        }
+    }
+}
+
+pub struct InlayHintProvider {
+    schemas: Arc<SchemaRegistry>,
+}
+
+impl InlayHintProvider {
+    pub fn new(schemas: Arc<SchemaRegistry>) -> Self {
+        Self { schemas }
+    }
+
+    /// Hints for the attribute assignments on lines within `range`.
+    pub fn inlay_hints(
+        &self,
+        text: &str,
+        range: Range,
+        options: InlayHintOptions,
+    ) -> Vec<InlayHint> {
+        if !options.types && !options.enum_values {
+            return Vec::new();
+        }
+        let tokens = scan(text);
+        let lines: Vec<&str> = text.lines().collect();
+        let mut schema: Option<&ResourceSchema> = None;
+        let mut hints = Vec::new();
+
+        for (i, token) in tokens.iter().enumerate() {
+            match token.kind {
+                TokenKind::Sym('{') if token.depth == 1 => {
+                    schema = block_schema(&self.schemas, &tokens[..i]);
+                    continue;
+                }
+                TokenKind::Sym('}') if token.depth == 0 => {
+                    schema = None;
+                    continue;
+                }
+                _ => {}
+            }
+            let Some(schema) = schema else {
+                continue;
+            };
+            let line = token.range.start.line;
+            if token.kind != TokenKind::Ident
+                || token.depth != 1
+                || line < range.start.line
+                || line > range.end.line
+                || !is_sym(tokens.get(i + 1), '=')
+                || i.checked_sub(1)
+                    .is_some_and(|p| tokens[p].range.end.line == line)
+            {
+                continue;
+            }
+            let Some(attr) = schema.attributes.get(&token.text) else {
+                continue;
+            };
+            let Some(end) = lines.get(line as usize).and_then(|l| value_end(l)) else {
+                continue;
+            };
+            let position = Position::new(line, end);
+            if options.types {
+                hints.push(hint(
+                    position,
+                    format!(": {}", attr.attr_type),
+                    Some(InlayHintKind::TYPE),
+                ));
+            }
+            if options.enum_values {
+                let value: Vec<&Token> = tokens[i + 2..]
+                    .iter()
+                    .take_while(|t| t.range.start.line == line)
+                    .collect();
+                if let Some(api) = canonical_enum_value(attr, &value) {
+                    hints.push(hint(position, format!("→ \"{}\"", api), None));
+                }
+            }
+        }
+        hints
+    }
+}
+
+fn hint(position: Position, label: String, kind: Option<InlayHintKind>) -> InlayHint {
+    InlayHint {
+        position,
+        label: InlayHintLabel::String(label),
+        kind,
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(true),
+        padding_right: None,
+        data: None,
+    }
+}
+
+/// Column just past a single-line value, before any trailing comment.
+/// `None` when the value continues on later lines (an open `{`, `[`, or
+/// `(`), where a hint would land in the middle of the construct.
+fn value_end(line: &str) -> Option<u32> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut quote: Option<char> = None;
+    let mut end = chars.len();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' || (c == '/' && chars.get(i + 1) == Some(&'/')) => {
+                end = i;
+                break;
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    let code: String = chars[..end].iter().collect();
+    let code = code.trim_end();
+    if code.ends_with(['{', '[', '(', '=']) {
+        return None;
+    }
+    Some(code.chars().count() as u32)
+}
+
+/// The API value an enum attribute's written value resolves to, when it
+/// differs from what is written (`aws.ec2.Vpc.InstanceTenancy.dedicated`,
+/// or a DSL alias of the API spelling).
+fn canonical_enum_value(attr: &AttributeSchema, value: &[&Token]) -> Option<String> {
+    let (_, values, _, _, dsl_map) = attr.attr_type.enum_parts()?;
+    let written = match value {
+        [token] if token.kind == TokenKind::Str => token.text.clone(),
+        _ => {
+            let dotted = value.iter().enumerate().all(|(i, t)| {
+                if i.is_multiple_of(2) {
+                    t.kind == TokenKind::Ident
+                } else {
+                    t.kind == TokenKind::Sym('.')
+                }
+            });
+            if value.is_empty() || !dotted || value.len().is_multiple_of(2) {
+                return None;
+            }
+            value.iter().map(|t| t.text.as_str()).collect()
+        }
+    };
+    let api = match values {
+        Some(values) => {
+            let valid: Vec<&str> = values.iter().map(String::as_str).collect();
+            canonicalize_enum_to_api(&written, &valid, &dsl_map)
+        }
+        None => dsl_map.api_for(written.rsplit('.').next().unwrap_or(&written)),
+    };
+    (api != written).then_some(api)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::schema::{AttributeType, TypeIdentity, types};
+
+    fn provider() -> InlayHintProvider {
+        let tenancy = AttributeType::enum_(
+            TypeIdentity::new(Some("awscc"), ["ec2", "Vpc"], "InstanceTenancy"),
+            Some(vec!["default".to_string(), "dedicated".to_string()]),
+            vec![],
+            None,
+            None,
+        );
+        let schema = ResourceSchema::new("ec2.Vpc")
+            .attribute(AttributeSchema::new("cidr_block", types::ipv4_cidr()))
+            .attribute(AttributeSchema::new("instance_tenancy", tenancy))
+            .attribute(AttributeSchema::new(
+                "tags",
+                AttributeType::map(AttributeType::string()),
+            ));
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert("awscc", schema);
+        InlayHintProvider::new(Arc::new(schemas))
+    }
+
+    fn everything() -> Range {
+        Range::new(Position::new(0, 0), Position::new(u32::MAX, 0))
+    }
+
+    fn labels(hints: &[InlayHint]) -> Vec<(u32, u32, String)> {
+        hints
+            .iter()
+            .map(|h| {
+                let InlayHintLabel::String(label) = &h.label else {
+                    panic!("expected a string label");
+                };
+                (h.position.line, h.position.character, label.clone())
+            })
+            .collect()
+    }
+
+    const VPC: &str = "let vpc = awscc.ec2.Vpc {\n  cidr_block = '10.0.0.0/16' # main\n  instance_tenancy = awscc.ec2.Vpc.InstanceTenancy.dedicated\n  tags = {\n    Name = 'main'\n  }\n}\n";
+
+    #[test]
+    fn hints_show_types_and_canonical_enum_values() {
+        let hints = provider().inlay_hints(VPC, everything(), InlayHintOptions::default());
+        let ipv4 = format!(": {}", types::ipv4_cidr());
+        assert_eq!(
+            labels(&hints),
+            vec![
+                (1, 28, ipv4),
+                (2, 60, ": awscc.ec2.Vpc.InstanceTenancy".to_string()),
+                (2, 60, "→ \"dedicated\"".to_string()),
+            ]
+        );
+        assert_eq!(hints[0].kind, Some(InlayHintKind::TYPE));
+    }
+
+    #[test]
+    fn bare_enum_value_needs_no_canonical_hint() {
+        let text = "awscc.ec2.Vpc {\n  instance_tenancy = dedicated\n}\n";
+        let options = InlayHintOptions {
+            types: false,
+            enum_values: true,
+        };
+        assert!(
+            provider()
+                .inlay_hints(text, everything(), options)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn options_toggle_each_kind_and_range_limits_lines() {
+        let options = InlayHintOptions::from_initialization_options(Some(
+            &serde_json::json!({ "inlayHints": { "types": false } }),
+        ));
+        assert_eq!(
+            options,
+            InlayHintOptions {
+                types: false,
+                enum_values: true
+            }
+        );
+        let hints = provider().inlay_hints(VPC, everything(), options);
+        assert_eq!(labels(&hints).len(), 1);
+
+        let first_line = Range::new(Position::new(1, 0), Position::new(1, 40));
+        let hints = provider().inlay_hints(VPC, first_line, InlayHintOptions::default());
+        assert_eq!(labels(&hints).len(), 1);
+    }
+
+    #[test]
+    fn blocks_without_a_schema_get_no_hints() {
+        let text = "provider awscc {\n  region = awscc.Region.ap_northeast_1\n}\n";
+        assert!(
+            provider()
+                .inlay_hints(text, everything(), InlayHintOptions::default())
+                .is_empty()
+        );
+    }
+}
@@ -4,9 +4,14 @@ pub mod completion;
 pub mod diagnostics;
 pub mod document;
 pub mod hover;
+pub mod inlay_hints;
 pub(crate) mod let_parse;
 pub mod position;
+pub mod rename;
+pub(crate) mod scan;
 pub mod semantic_tokens;
+pub mod signature_help;
+pub mod symbols;
 pub mod workspace;
 
 pub use backend::Backend;
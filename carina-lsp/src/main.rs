@@ -26,9 +26,6 @@ fn resolve_install(source_dir: &Path, config: &ProviderConfig) -> Option<PathBuf
     } else {
         return None;
     };
-    if !carina_provider_resolver::is_wasm_provider(&binary_path) {
-        return None;
-    }
     if !binary_path.exists() {
         return None;
     }
@@ -79,11 +76,6 @@ fn build_factories(providers: &[(PathBuf, ProviderConfig)]) -> FactoryBuildResul
                     let p = PathBuf::from(stripped);
                     if !p.exists() {
                         errors.insert(config.name.clone(), format!("file not found: {}", stripped));
-                    } else if !carina_provider_resolver::is_wasm_provider(&p) {
-                        errors.insert(
-                            config.name.clone(),
-                            format!("not a WASM component: {}", p.display()),
-                        );
                     }
                 } else if source.starts_with("github.com/") {
                     if let Err(e) =
@@ -102,22 +94,33 @@ fn build_factories(providers: &[(PathBuf, ProviderConfig)]) -> FactoryBuildResul
             }
         };
 
-        match tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(
-                carina_plugin_host::WasmProviderFactory::new(binary_path.clone()),
-            )
-        }) {
+        let loaded: Result<Box<dyn ProviderFactory>, String> = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                if carina_provider_resolver::is_wasm_provider(&binary_path) {
+                    carina_plugin_host::WasmProviderFactory::new(binary_path.clone())
+                        .await
+                        .map(|f| Box::new(f) as Box<dyn ProviderFactory>)
+                        .map_err(|e| format!("failed to load WASM: {e}"))
+                } else {
+                    carina_plugin_host::ProcessProviderFactory::new(binary_path.clone())
+                        .await
+                        .map(|f| Box::new(f) as Box<dyn ProviderFactory>)
+                        .map_err(|e| format!("failed to start provider process: {e}"))
+                }
+            })
+        });
+        match loaded {
             Ok(factory) => {
                 log::info!(
                     "LSP: loaded provider '{}' from {}",
                     config.name,
                     binary_path.display()
                 );
-                factories.push(Box::new(factory));
+                factories.push(factory);
                 fingerprint.push((config.name.clone(), true));
             }
             Err(e) => {
-                errors.insert(config.name.clone(), format!("failed to load WASM: {}", e));
+                errors.insert(config.name.clone(), e);
                 // Factory failed to load even though the path resolved; treat
                 // as "not installed" from the LSP's perspective so the next
                 // poll can notice if the user replaces the WASM.
@@ -190,6 +193,7 @@ mod tests {
                 name: name.to_string(),
                 attributes: IndexMap::new(),
                 default_tags: IndexMap::new(),
+                provenance_tags: false,
                 source: source.map(String::from),
                 version: None,
                 revision: None,
@@ -0,0 +1,717 @@
+//! `textDocument/rename` for top-level `let` bindings and `exports` keys.
+//!
+//! A Carina project is one directory, so a rename covers every `.crn`
+//! file beside the one being edited, with open buffers taking precedence
+//! over disk. Renaming an export also rewrites `<upstream>.<key>` in
+//! workspace projects whose `upstream_state` points at this directory.
+//!
+//! Occurrences come from a small token scan rather than the parser, so a
+//! rename still works while some file in the project fails to parse.
+//! Comments and string literals are skipped; `${...}` interpolations are
+//! scanned as code.
+//!
+//! Renaming a resource binding changes its address, so the rename also
+//! appends a `moved` block after the definition's file; without it the
+//! next plan would destroy the resource under its old name and create it
+//! under the new one.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tower_lsp::lsp_types::{Position, Range, TextEdit, Url};
+
+use crate::scan::{Token, TokenKind, is_ident, is_sym, scan};
+
+/// The symbol a rename request points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameTarget {
+    /// A top-level `let` binding: a resource, module, upstream state, or
+    /// plain value.
+    Binding(String),
+    /// A key of the project's `exports` block.
+    Export(String),
+}
+
+impl RenameTarget {
+    fn name(&self) -> &str {
+        match self {
+            RenameTarget::Binding(name) | RenameTarget::Export(name) => name,
+        }
+    }
+}
+
+/// Find the renameable symbol under `position` and the range it spans.
+/// Only the syntactic shape is checked here; [`is_defined`] confirms the
+/// project actually declares it.
+pub fn target_at(text: &str, position: Position) -> Option<(RenameTarget, Range)> {
+    let tokens = scan(text);
+    let index = tokens.iter().position(|t| {
+        t.kind == TokenKind::Ident
+            && t.range.start.line == position.line
+            && t.range.start.character <= position.character
+            && position.character <= t.range.end.character
+    })?;
+    let token = &tokens[index];
+    if is_export_key(&tokens, index) {
+        Some((RenameTarget::Export(token.text.clone()), token.range))
+    } else if is_binding_occurrence(&tokens, index) {
+        Some((RenameTarget::Binding(token.text.clone()), token.range))
+    } else {
+        None
+    }
+}
+
+/// Whether `project` declares `target`: a top-level `let` for a binding,
+/// an `exports` key for an export.
+pub fn is_defined(target: &RenameTarget, project: &[(Url, String)]) -> bool {
+    project.iter().any(|(_, text)| {
+        let tokens = scan(text);
+        match target {
+            RenameTarget::Binding(name) => {
+                binding_definitions(&tokens).any(|i| tokens[i].text == *name)
+            }
+            RenameTarget::Export(name) => export_keys(&tokens).any(|i| tokens[i].text == *name),
+        }
+    })
+}
+
+/// Compute the edits renaming `target` to `new_name`.
+///
+/// `project` holds the sources of the project declaring `target`, which
+/// lives in `project_dir`; `consumers` holds sources of other workspace
+/// projects, searched for `upstream_state` reads of a renamed export.
+/// Returns a user-facing message when the rename is refused.
+pub fn rename_edits(
+    target: &RenameTarget,
+    new_name: &str,
+    project: &[(Url, String)],
+    project_dir: &Path,
+    consumers: &[(Url, String)],
+) -> Result<HashMap<Url, Vec<TextEdit>>, String> {
+    if !is_identifier(new_name) || carina_core::keywords::is_keyword(new_name) {
+        return Err(format!("'{}' is not a valid name", new_name));
+    }
+    if !is_defined(target, project) {
+        return Err(match target {
+            RenameTarget::Binding(name) => format!("'{}' is not a top-level binding", name),
+            RenameTarget::Export(name) => format!("'{}' is not an export of this project", name),
+        });
+    }
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    if target.name() == new_name {
+        return Ok(changes);
+    }
+
+    let scanned: Vec<(&Url, Vec<Token>)> = project
+        .iter()
+        .map(|(uri, text)| (uri, scan(text)))
+        .collect();
+    match target {
+        RenameTarget::Binding(old) => {
+            check_binding_conflicts(old, new_name, &scanned)?;
+            let resource_type = scanned
+                .iter()
+                .find_map(|(_, tokens)| resource_type_of(tokens, old));
+            for (uri, tokens) in &scanned {
+                let mut edits: Vec<TextEdit> = (0..tokens.len())
+                    .filter(|&i| tokens[i].text == *old && is_binding_occurrence(tokens, i))
+                    .map(|i| edit(&tokens[i], new_name))
+                    .collect();
+                // `import`/`moved` targets name the resource by address;
+                // `from` sides and `removed` refer to state and stay put.
+                if let Some(resource_type) = &resource_type {
+                    edits.extend(
+                        addresses(tokens)
+                            .filter(|a| {
+                                matches!(a.block.as_deref(), Some("import" | "moved"))
+                                    && a.key == "to"
+                                    && a.resource_type == *resource_type
+                                    && tokens[a.name].text == *old
+                            })
+                            .map(|a| edit(&tokens[a.name], new_name)),
+                    );
+                }
+                if let Some(resource_type) = &resource_type
+                    && binding_definitions(tokens).any(|i| tokens[i].text == *old)
+                {
+                    let text = project
+                        .iter()
+                        .find(|(u, _)| u == *uri)
+                        .map_or("", |(_, text)| text.as_str());
+                    edits.push(moved_block(text, resource_type, old, new_name));
+                }
+                if !edits.is_empty() {
+                    changes.insert((*uri).clone(), edits);
+                }
+            }
+        }
+        RenameTarget::Export(old) => {
+            if scanned
+                .iter()
+                .any(|(_, tokens)| export_keys(tokens).any(|i| tokens[i].text == new_name))
+            {
+                return Err(format!(
+                    "'{}' is already exported by this project",
+                    new_name
+                ));
+            }
+            for (uri, tokens) in &scanned {
+                let edits: Vec<TextEdit> = export_keys(tokens)
+                    .filter(|&i| tokens[i].text == *old)
+                    .map(|i| edit(&tokens[i], new_name))
+                    .collect();
+                if !edits.is_empty() {
+                    changes.insert((*uri).clone(), edits);
+                }
+            }
+            for (uri, text) in consumers {
+                let tokens = scan(text);
+                let upstreams = upstreams_of(&tokens, uri, project_dir);
+                let edits: Vec<TextEdit> = (2..tokens.len())
+                    .filter(|&i| {
+                        tokens[i].text == *old
+                            && tokens[i].kind == TokenKind::Ident
+                            && tokens[i - 1].kind == TokenKind::Sym('.')
+                            && upstreams.contains(&tokens[i - 2].text)
+                            && is_binding_occurrence(&tokens, i - 2)
+                    })
+                    .map(|i| edit(&tokens[i], new_name))
+                    .collect();
+                if !edits.is_empty() {
+                    changes.insert(uri.clone(), edits);
+                }
+            }
+        }
+    }
+    Ok(changes)
+}
+
+/// Sources of the project in `dir`: every `.crn` file directly inside
+/// it, with open buffers (`open`) replacing the on-disk text.
+pub fn project_sources(dir: &Path, open: &HashMap<Url, String>) -> Vec<(Url, String)> {
+    let mut sources: HashMap<Url, String> = HashMap::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for path in entries.flatten().map(|e| e.path()) {
+            if is_crn(&path)
+                && let Ok(uri) = Url::from_file_path(&path)
+                && let Ok(text) = fs::read_to_string(&path)
+            {
+                sources.insert(uri, text);
+            }
+        }
+    }
+    for (uri, text) in open {
+        if let Ok(path) = uri.to_file_path()
+            && path.parent() == Some(dir)
+        {
+            sources.insert(uri.clone(), text.clone());
+        }
+    }
+    sources.into_iter().collect()
+}
+
+/// Sources of every workspace project other than `skip_dir`, for
+/// finding `upstream_state` consumers of an export, or of every project
+/// when `skip_dir` is `None`.
+pub fn workspace_sources(
+    root: &Path,
+    skip_dir: Option<&Path>,
+    open: &HashMap<Url, String>,
+) -> Vec<(Url, String)> {
+    let mut dirs = Vec::new();
+    collect_dirs(root, &mut dirs);
+    dirs.into_iter()
+        .filter(|dir| Some(dir.as_path()) != skip_dir)
+        .flat_map(|dir| project_sources(&dir, open))
+        .collect()
+}
+
+fn collect_dirs(dir: &Path, dirs: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut has_crn = false;
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            // `.carina/` holds installed providers and caches, never sources.
+            if !path
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            {
+                collect_dirs(&path, dirs);
+            }
+        } else if is_crn(&path) {
+            has_crn = true;
+        }
+    }
+    if has_crn {
+        dirs.push(dir.to_path_buf());
+    }
+}
+
+fn is_crn(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "crn")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn edit(token: &Token, new_name: &str) -> TextEdit {
+    TextEdit {
+        range: token.range,
+        new_text: new_name.to_string(),
+    }
+}
+
+/// An insertion at the end of `text` of a `moved` block carrying the
+/// resource's state from its old address to the renamed one.
+fn moved_block(text: &str, resource_type: &str, old: &str, new_name: &str) -> TextEdit {
+    let last = text.split('\n').next_back().unwrap_or("");
+    let end = Position {
+        line: text.matches('\n').count() as u32,
+        character: last.chars().count() as u32,
+    };
+    let separator = if text.ends_with('\n') { "\n" } else { "\n\n" };
+    TextEdit {
+        range: Range { start: end, end },
+        new_text: format!(
+            "{separator}moved {{\n  from = {resource_type} \"{old}\"\n  to = {resource_type} \"{new_name}\"\n}}\n"
+        ),
+    }
+}
+
+/// Refuse a binding rename whose new name is already taken in the
+/// project, either by another binding or argument (both are referenced
+/// as bare identifiers) or by a resource address that state blocks name.
+fn check_binding_conflicts(
+    old: &str,
+    new_name: &str,
+    scanned: &[(&Url, Vec<Token>)],
+) -> Result<(), String> {
+    for (_, tokens) in scanned {
+        if binding_definitions(tokens).any(|i| tokens[i].text == new_name) {
+            return Err(format!("'{}' is already bound in this project", new_name));
+        }
+        if argument_names(tokens).any(|i| tokens[i].text == new_name) {
+            return Err(format!(
+                "'{}' is already an argument of this module",
+                new_name
+            ));
+        }
+    }
+    let Some(resource_type) = scanned
+        .iter()
+        .find_map(|(_, tokens)| resource_type_of(tokens, old))
+    else {
+        return Ok(());
+    };
+    for (_, tokens) in scanned {
+        if addresses(tokens)
+            .any(|a| a.resource_type == resource_type && tokens[a.name].text == new_name)
+        {
+            return Err(format!(
+                "resource address {} \"{}\" is already used in this project",
+                resource_type, new_name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Indices of the names in top-level `let <name> =` definitions.
+fn binding_definitions(tokens: &[Token]) -> impl Iterator<Item = usize> + '_ {
+    (1..tokens.len()).filter(|&i| {
+        tokens[i].kind == TokenKind::Ident
+            && tokens[i].depth == 0
+            && is_ident(tokens.get(i - 1), "let")
+            && is_sym(tokens.get(i + 1), '=')
+    })
+}
+
+/// Indices of the keys of `exports { ... }` parameters.
+fn export_keys(tokens: &[Token]) -> impl Iterator<Item = usize> + '_ {
+    (0..tokens.len()).filter(|&i| is_export_key(tokens, i))
+}
+
+fn is_export_key(tokens: &[Token], i: usize) -> bool {
+    block_key(tokens, i, "exports")
+}
+
+/// Indices of the names declared in `arguments { ... }`.
+fn argument_names(tokens: &[Token]) -> impl Iterator<Item = usize> + '_ {
+    (0..tokens.len()).filter(|&i| block_key(tokens, i, "arguments"))
+}
+
+/// Whether token `i` is a `name: type` or `name =` key directly inside a
+/// top-level `<block> { ... }`.
+fn block_key(tokens: &[Token], i: usize, block: &str) -> bool {
+    let token = &tokens[i];
+    token.kind == TokenKind::Ident
+        && token.depth == 1
+        && token.block.as_deref() == Some(block)
+        && !(i > 0 && is_sym(tokens.get(i - 1), '.'))
+        && (is_sym(tokens.get(i + 1), ':')
+            || (is_sym(tokens.get(i + 1), '=') && !is_sym(tokens.get(i + 2), '=')))
+}
+
+/// Whether identifier `i` reads a binding by name, as opposed to an
+/// attribute key, a member access, or some other declaration.
+fn is_binding_occurrence(tokens: &[Token], i: usize) -> bool {
+    let token = &tokens[i];
+    if token.kind != TokenKind::Ident || carina_core::keywords::is_keyword(&token.text) {
+        return false;
+    }
+    let prev = i.checked_sub(1).and_then(|p| tokens.get(p));
+    let next = tokens.get(i + 1);
+    if is_sym(prev, '.') {
+        return false;
+    }
+    if is_ident(prev, "let") {
+        // Block-local `let`s are separate symbols scoped to their block.
+        return token.depth == 0;
+    }
+    if ["provider", "backend", "fn", "for"]
+        .iter()
+        .any(|kw| is_ident(prev, kw))
+    {
+        return false;
+    }
+    // `name: type` declarations and `key = value` attributes.
+    if is_sym(next, ':') || (is_sym(next, '=') && !is_sym(tokens.get(i + 2), '=')) {
+        return false;
+    }
+    // A nested block (`tags { ... }`) inside a resource body, as opposed
+    // to a module call on the right-hand side of a `let`.
+    if is_sym(next, '{') && token.depth > 0 && !is_sym(prev, '=') {
+        return false;
+    }
+    true
+}
+
+/// The `provider.service.Type` of the resource bound by `let <name> =`,
+/// if `name` binds a resource in these tokens.
+fn resource_type_of(tokens: &[Token], name: &str) -> Option<String> {
+    let def = binding_definitions(tokens).find(|&i| tokens[i].text == name)?;
+    let mut segments = Vec::new();
+    let mut i = def + 2;
+    while let Some(token) = tokens.get(i)
+        && token.kind == TokenKind::Ident
+    {
+        segments.push(token.text.as_str());
+        if !is_sym(tokens.get(i + 1), '.') {
+            break;
+        }
+        i += 2;
+    }
+    (segments.len() >= 2 && is_sym(tokens.get(i + 1), '{')).then(|| segments.join("."))
+}
+
+/// A `<key> = provider.service.Type "name"` resource address inside an
+/// `import`, `moved`, or `removed` block.
+struct Address {
+    block: Option<String>,
+    key: String,
+    resource_type: String,
+    /// Index of the string token holding the resource name.
+    name: usize,
+}
+
+fn addresses(tokens: &[Token]) -> impl Iterator<Item = Address> + '_ {
+    (0..tokens.len()).filter_map(|i| {
+        if tokens[i].kind != TokenKind::Str
+            || !matches!(
+                tokens[i].block.as_deref(),
+                Some("import" | "moved" | "removed")
+            )
+        {
+            return None;
+        }
+        let mut segments = Vec::new();
+        let mut j = i;
+        loop {
+            let token = tokens.get(j.checked_sub(1)?)?;
+            if token.kind != TokenKind::Ident {
+                return None;
+            }
+            segments.push(token.text.as_str());
+            j -= 1;
+            if !is_sym(j.checked_sub(1).and_then(|p| tokens.get(p)), '.') {
+                break;
+            }
+            j -= 1;
+        }
+        if segments.len() < 2 || !is_sym(j.checked_sub(1).and_then(|p| tokens.get(p)), '=') {
+            return None;
+        }
+        let key = tokens.get(j.checked_sub(2)?)?;
+        segments.reverse();
+        Some(Address {
+            block: tokens[i].block.clone(),
+            key: key.text.clone(),
+            resource_type: segments.join("."),
+            name: i,
+        })
+    })
+}
+
+/// Bindings of `let <name> = upstream_state { source = '...' }` whose
+/// source resolves to `project_dir`, relative to the file at `uri`.
+fn upstreams_of(tokens: &[Token], uri: &Url, project_dir: &Path) -> Vec<String> {
+    let Some(base) = uri
+        .to_file_path()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+    else {
+        return Vec::new();
+    };
+    let target = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+    binding_definitions(tokens)
+        .filter(|&def| is_ident(tokens.get(def + 2), "upstream_state"))
+        .filter(|&def| {
+            let source = tokens[def + 3..]
+                .iter()
+                .take_while(|t| t.depth > 0 || t.kind == TokenKind::Sym('{'))
+                .collect::<Vec<_>>()
+                .windows(3)
+                .find_map(|w| {
+                    (w[0].kind == TokenKind::Ident
+                        && w[0].text == "source"
+                        && w[0].depth == 1
+                        && w[1].kind == TokenKind::Sym('=')
+                        && w[2].kind == TokenKind::Str)
+                        .then(|| w[2].text.clone())
+                });
+            source.is_some_and(|source| {
+                let dir = base.join(source);
+                dir.canonicalize().unwrap_or(dir) == target
+            })
+        })
+        .map(|def| tokens[def].text.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn uri(name: &str) -> Url {
+        Url::parse(&format!("file:///project/{}", name)).unwrap()
+    }
+
+    fn apply(text: &str, edits: &[TextEdit]) -> String {
+        let mut lines: Vec<String> = text.split('\n').map(String::from).collect();
+        let mut edits = edits.to_vec();
+        // Right to left, so earlier edits keep their columns.
+        edits.sort_by_key(|e| std::cmp::Reverse((e.range.start.line, e.range.start.character)));
+        for e in &edits {
+            assert_eq!(e.range.start.line, e.range.end.line);
+            let line = &mut lines[e.range.start.line as usize];
+            let chars: Vec<char> = line.chars().collect();
+            let start = e.range.start.character as usize;
+            let end = e.range.end.character as usize;
+            *line = chars[..start]
+                .iter()
+                .chain(e.new_text.chars().collect::<Vec<_>>().iter())
+                .chain(chars[end..].iter())
+                .collect();
+        }
+        lines.join("\n")
+    }
+
+    fn rename_binding(
+        files: &[(Url, String)],
+        old: &str,
+        new_name: &str,
+    ) -> Result<HashMap<Url, Vec<TextEdit>>, String> {
+        rename_edits(
+            &RenameTarget::Binding(old.to_string()),
+            new_name,
+            files,
+            Path::new("/project"),
+            &[],
+        )
+    }
+
+    const MAIN: &str = r#"let vpc = awscc.ec2.Vpc {
+  cidr_block = '10.0.0.0/16'
+  tags = {
+    Name = "vpc-${vpc_name}"
+  }
+}
+
+# vpc is referenced below
+let subnet = awscc.ec2.Subnet {
+  vpc_id = vpc.vpc_id
+  tags {
+    vpc = 'vpc'
+  }
+}
+
+exports {
+  vpc_id = vpc.vpc_id
+}"#;
+
+    #[test]
+    fn target_at_distinguishes_bindings_from_keys_and_members() {
+        let at = |line, character| target_at(MAIN, Position { line, character }).map(|t| t.0);
+        assert_eq!(at(0, 5), Some(RenameTarget::Binding("vpc".into())));
+        assert_eq!(at(9, 12), Some(RenameTarget::Binding("vpc".into())));
+        // `.vpc_id` member, `cidr_block` attribute key, nested `vpc =` key.
+        assert_eq!(at(9, 16), None);
+        assert_eq!(at(1, 4), None);
+        assert_eq!(at(11, 4), None);
+        assert_eq!(at(16, 3), Some(RenameTarget::Export("vpc_id".into())));
+    }
+
+    #[test]
+    fn renames_definition_and_references_across_project_files() {
+        let other = "let route = awscc.ec2.Route {\n  vpc_id = \"${vpc.vpc_id}\"\n}";
+        let files = vec![
+            (uri("main.crn"), MAIN.to_string()),
+            (uri("route.crn"), other.to_string()),
+        ];
+        let changes = rename_binding(&files, "vpc", "network").unwrap();
+
+        let main = apply(MAIN, &changes[&uri("main.crn")]);
+        assert!(main.starts_with("let network = awscc.ec2.Vpc {"));
+        assert!(main.contains("  vpc_id = network.vpc_id\n"));
+        assert!(main.contains("# vpc is referenced below"));
+        assert!(main.contains("    vpc = 'vpc'"));
+        assert!(main.ends_with(
+            "exports {\n  vpc_id = network.vpc_id\n}\n\nmoved {\n  from = awscc.ec2.Vpc \"vpc\"\n  to = awscc.ec2.Vpc \"network\"\n}\n"
+        ));
+        assert_eq!(
+            apply(other, &changes[&uri("route.crn")]),
+            "let route = awscc.ec2.Route {\n  vpc_id = \"${network.vpc_id}\"\n}"
+        );
+    }
+
+    #[test]
+    fn renames_import_and_moved_targets_but_not_state_sides() {
+        let text = r#"let vpc = awscc.ec2.Vpc {
+  cidr_block = '10.0.0.0/16'
+}
+
+import {
+  to = awscc.ec2.Vpc "vpc"
+  id = "vpc-123"
+}
+
+moved {
+  from = awscc.ec2.Vpc "old"
+  to = awscc.ec2.Vpc "vpc"
+}"#;
+        let files = vec![(uri("main.crn"), text.to_string())];
+        let changes = rename_binding(&files, "vpc", "main").unwrap();
+        let renamed = apply(text, &changes[&uri("main.crn")]);
+        assert!(renamed.contains("to = awscc.ec2.Vpc \"main\"\n  id"));
+        assert!(renamed.contains("from = awscc.ec2.Vpc \"old\""));
+        assert!(renamed.contains("to = awscc.ec2.Vpc \"main\"\n}"));
+    }
+
+    #[test]
+    fn renaming_a_resource_moves_its_state_but_plain_values_do_not() {
+        let text = "let vpc = awscc.ec2.Vpc {\n  cidr_block = '10.0.0.0/16'\n}\n";
+        let files = vec![
+            (uri("main.crn"), text.to_string()),
+            (uri("other.crn"), "let x = vpc.vpc_id".to_string()),
+        ];
+        let changes = rename_binding(&files, "vpc", "main").unwrap();
+        assert_eq!(
+            apply(text, &changes[&uri("main.crn")]),
+            "let main = awscc.ec2.Vpc {\n  cidr_block = '10.0.0.0/16'\n}\n\nmoved {\n  from = awscc.ec2.Vpc \"vpc\"\n  to = awscc.ec2.Vpc \"main\"\n}\n"
+        );
+        assert_eq!(
+            apply("let x = vpc.vpc_id", &changes[&uri("other.crn")]),
+            "let x = main.vpc_id"
+        );
+
+        let text = "let region = 'ap-northeast-1'\nlet y = region";
+        let files = vec![(uri("main.crn"), text.to_string())];
+        let changes = rename_binding(&files, "region", "home").unwrap();
+        assert_eq!(
+            apply(text, &changes[&uri("main.crn")]),
+            "let home = 'ap-northeast-1'\nlet y = home"
+        );
+    }
+
+    #[test]
+    fn refuses_conflicting_names() {
+        let files = vec![(uri("main.crn"), MAIN.to_string())];
+        assert_eq!(
+            rename_binding(&files, "vpc", "subnet").unwrap_err(),
+            "'subnet' is already bound in this project"
+        );
+        assert_eq!(
+            rename_binding(&files, "vpc", "let").unwrap_err(),
+            "'let' is not a valid name"
+        );
+        assert_eq!(
+            rename_binding(&files, "vpc", "2vpc").unwrap_err(),
+            "'2vpc' is not a valid name"
+        );
+        assert_eq!(
+            rename_binding(&files, "missing", "other").unwrap_err(),
+            "'missing' is not a top-level binding"
+        );
+
+        let text = format!("{MAIN}\n\nremoved {{\n  from = awscc.ec2.Vpc \"legacy\"\n}}");
+        let files = vec![(uri("main.crn"), text)];
+        assert_eq!(
+            rename_binding(&files, "vpc", "legacy").unwrap_err(),
+            "resource address awscc.ec2.Vpc \"legacy\" is already used in this project"
+        );
+    }
+
+    #[test]
+    fn renaming_an_export_updates_upstream_state_consumers() {
+        let workspace = TempDir::new().unwrap();
+        let network = workspace.path().join("network");
+        let app = workspace.path().join("app");
+        fs::create_dir_all(&network).unwrap();
+        fs::create_dir_all(&app).unwrap();
+        fs::write(network.join("main.crn"), MAIN).unwrap();
+        let consumer = "let net = upstream_state {\n  source = '../network'\n}\n\nlet sg = awscc.ec2.SecurityGroup {\n  vpc_id = net.vpc_id\n}";
+        fs::write(app.join("main.crn"), consumer).unwrap();
+
+        let open = HashMap::new();
+        let project = project_sources(&network, &open);
+        let consumers = workspace_sources(workspace.path(), Some(&network), &open);
+        let changes = rename_edits(
+            &RenameTarget::Export("vpc_id".into()),
+            "network_vpc_id",
+            &project,
+            &network,
+            &consumers,
+        )
+        .unwrap();
+
+        let main_uri = Url::from_file_path(network.join("main.crn")).unwrap();
+        let renamed = apply(MAIN, &changes[&main_uri]);
+        assert!(renamed.ends_with("exports {\n  network_vpc_id = vpc.vpc_id\n}"));
+        assert!(renamed.contains("  vpc_id = vpc.vpc_id\n  tags {"));
+
+        let app_uri = Url::from_file_path(app.join("main.crn")).unwrap();
+        assert!(apply(consumer, &changes[&app_uri]).ends_with("vpc_id = net.network_vpc_id\n}"));
+    }
+
+    #[test]
+    fn open_buffers_replace_disk_contents() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("main.crn");
+        fs::write(&path, "let a = 1").unwrap();
+        let uri = Url::from_file_path(&path).unwrap();
+        let open = HashMap::from([(uri.clone(), "let b = 2".to_string())]);
+        assert_eq!(
+            project_sources(dir.path(), &open),
+            vec![(uri, "let b = 2".to_string())]
+        );
+    }
+}
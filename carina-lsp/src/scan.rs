@@ -0,0 +1,266 @@
+//! Comment- and string-aware token scan of `.crn` source.
+//!
+//! Rename and the symbol outline need token positions while the user is
+//! mid-edit, when the pest parser rejects the file. This scanner never
+//! fails: it yields identifiers, plain string literals and punctuation
+//! with their ranges and brace depth, skipping comments and scanning
+//! `${...}` interpolations as code.
+
+use carina_core::schema::{ResourceSchema, SchemaRegistry};
+use tower_lsp::lsp_types::{Position, Range};
+
+pub(crate) fn is_sym(token: Option<&Token>, c: char) -> bool {
+    token.is_some_and(|t| t.kind == TokenKind::Sym(c))
+}
+
+pub(crate) fn is_ident(token: Option<&Token>, text: &str) -> bool {
+    token.is_some_and(|t| t.kind == TokenKind::Ident && t.text == text)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Ident,
+    /// A string literal without interpolation; `range` and `text` cover
+    /// its content, without quotes.
+    Str,
+    Sym(char),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) text: String,
+    pub(crate) range: Range,
+    /// Brace depth the token sits at; 0 is top level.
+    pub(crate) depth: usize,
+    /// Last identifier before the `{` that opened the enclosing top-level
+    /// block (`exports`, `upstream_state`, `moved`, `Vpc`, ...).
+    pub(crate) block: Option<String>,
+}
+
+pub(crate) fn scan(text: &str) -> Vec<Token> {
+    let mut scanner = Scanner {
+        chars: text.chars().collect(),
+        i: 0,
+        line: 0,
+        col: 0,
+        depth: 0,
+        block: None,
+        tokens: Vec::new(),
+    };
+    scanner.code(false);
+    scanner.tokens
+}
+
+/// The schema of the block whose `{` follows `before`: the dotted type
+/// right before the brace (`aws.ec2.Vpc {`, `let vpc = read aws.ec2.Vpc {`).
+pub(crate) fn block_schema<'a>(
+    schemas: &'a SchemaRegistry,
+    before: &[Token],
+) -> Option<&'a ResourceSchema> {
+    let start = dotted_start(before);
+    let key: String = before[start..].iter().map(|t| t.text.as_str()).collect();
+    schemas.get_by_type_name(&key)
+}
+
+/// Index of the first token of the `ident(.ident)*` run that ends `tokens`.
+pub(crate) fn dotted_start(tokens: &[Token]) -> usize {
+    let mut start = tokens.len();
+    while start > 0 {
+        let expect_ident = (tokens.len() - start).is_multiple_of(2);
+        let token = &tokens[start - 1];
+        let matches = if expect_ident {
+            token.kind == TokenKind::Ident
+        } else {
+            token.kind == TokenKind::Sym('.')
+        };
+        if !matches {
+            break;
+        }
+        start -= 1;
+    }
+    start
+}
+
+struct Scanner {
+    chars: Vec<char>,
+    i: usize,
+    line: u32,
+    col: u32,
+    depth: usize,
+    block: Option<String>,
+    tokens: Vec<Token>,
+}
+
+impl Scanner {
+    fn peek(&self, n: usize) -> Option<char> {
+        self.chars.get(self.i + n).copied()
+    }
+
+    fn pos(&self) -> Position {
+        Position {
+            line: self.line,
+            character: self.col,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.i += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    fn push(&mut self, kind: TokenKind, text: String, start: Position) {
+        self.tokens.push(Token {
+            kind,
+            text,
+            range: Range {
+                start,
+                end: self.pos(),
+            },
+            depth: self.depth,
+            block: self.block.clone(),
+        });
+    }
+
+    /// Scan code until end of input, or, inside an interpolation, until
+    /// the `}` that closes it.
+    fn code(&mut self, interpolation: bool) {
+        let mut braces = 0usize;
+        while let Some(c) = self.peek(0) {
+            match c {
+                '#' => self.skip_line(),
+                '/' if self.peek(1) == Some('/') => self.skip_line(),
+                '/' if self.peek(1) == Some('*') => self.skip_block_comment(),
+                '\'' => self.string('\''),
+                '"' => self.string('"'),
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let start = self.pos();
+                    let mut word = String::new();
+                    while let Some(c) = self.peek(0)
+                        && (c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        word.push(c);
+                        self.bump();
+                    }
+                    self.push(TokenKind::Ident, word, start);
+                }
+                c if c.is_ascii_digit() => {
+                    while self
+                        .peek(0)
+                        .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                    {
+                        self.bump();
+                    }
+                }
+                c if c.is_whitespace() => {
+                    self.bump();
+                }
+                '{' => {
+                    if interpolation {
+                        braces += 1;
+                    } else {
+                        if self.depth == 0 {
+                            self.block = self
+                                .tokens
+                                .last()
+                                .filter(|t| t.kind == TokenKind::Ident)
+                                .map(|t| t.text.clone());
+                        }
+                        self.depth += 1;
+                    }
+                    self.symbol(c);
+                }
+                '}' => {
+                    if interpolation {
+                        if braces == 0 {
+                            self.bump();
+                            return;
+                        }
+                        braces -= 1;
+                    } else {
+                        self.depth = self.depth.saturating_sub(1);
+                    }
+                    self.symbol(c);
+                    if !interpolation && self.depth == 0 {
+                        self.block = None;
+                    }
+                }
+                c => self.symbol(c),
+            }
+        }
+    }
+
+    fn symbol(&mut self, c: char) {
+        let start = self.pos();
+        self.bump();
+        self.push(TokenKind::Sym(c), c.to_string(), start);
+    }
+
+    fn skip_line(&mut self) {
+        while self.peek(0).is_some_and(|c| c != '\n') {
+            self.bump();
+        }
+    }
+
+    fn skip_block_comment(&mut self) {
+        let mut nesting = 0usize;
+        while let Some(c) = self.peek(0) {
+            if c == '/' && self.peek(1) == Some('*') {
+                nesting += 1;
+                self.bump();
+            } else if c == '*' && self.peek(1) == Some('/') {
+                self.bump();
+                self.bump();
+                nesting -= 1;
+                if nesting == 0 {
+                    return;
+                }
+                continue;
+            }
+            self.bump();
+        }
+    }
+
+    /// Scan a string literal. Only double-quoted strings interpolate;
+    /// a string with an interpolation emits the interpolated code's
+    /// tokens instead of a `Str` token.
+    fn string(&mut self, quote: char) {
+        self.bump();
+        let start = self.pos();
+        let mut text = String::new();
+        let mut plain = true;
+        while let Some(c) = self.peek(0) {
+            match c {
+                '\\' => {
+                    self.bump();
+                    plain = false;
+                    self.bump();
+                }
+                c if c == quote => {
+                    if plain {
+                        self.push(TokenKind::Str, text, start);
+                    }
+                    self.bump();
+                    return;
+                }
+                '$' if quote == '"' && self.peek(1) == Some('{') => {
+                    plain = false;
+                    self.bump();
+                    self.bump();
+                    self.code(true);
+                }
+                c => {
+                    text.push(c);
+                    self.bump();
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,12 @@
+use std::sync::Arc;
+
+use carina_core::schema::SchemaRegistry;
+use tower_lsp::lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend,
+};
+
 use crate::position;
-use tower_lsp::lsp_types::{SemanticToken, SemanticTokenType, SemanticTokensLegend};
+use crate::scan::{Token, TokenKind, block_schema, dotted_start, is_sym, scan};
 
 /// Token types supported by this language server.
 ///
@@ -8,29 +15,52 @@ use tower_lsp::lsp_types::{SemanticToken, SemanticTokenType, SemanticTokensLegen
 /// each category independently (#1948). The `KEYWORD` entry stays in the
 /// legend only to keep the indices of later entries stable.
 pub const TOKEN_TYPES: &[SemanticTokenType] = &[
-    SemanticTokenType::KEYWORD,  // 0: (unused — kept for index stability)
-    SemanticTokenType::TYPE,     // 1: aws.s3.Bucket, aws.ec2.Vpc, aws.Region.*
-    SemanticTokenType::VARIABLE, // 2: variable names
-    SemanticTokenType::PROPERTY, // 3: attribute names (name, region, etc.)
-    SemanticTokenType::STRING,   // 4: string literals
-    SemanticTokenType::NUMBER,   // 5: number literals
-    SemanticTokenType::OPERATOR, // 6: =
-    SemanticTokenType::COMMENT,  // 7: comments
-    SemanticTokenType::FUNCTION, // 8: function names
-    SemanticTokenType::MACRO,    // 9: ${...} interpolation spans inside double-quoted strings
+    SemanticTokenType::KEYWORD,     // 0: (unused — kept for index stability)
+    SemanticTokenType::TYPE,        // 1: aws.s3.Bucket, aws.ec2.Vpc, aws.Region.*
+    SemanticTokenType::VARIABLE,    // 2: variable names
+    SemanticTokenType::PROPERTY,    // 3: attribute names (name, region, etc.)
+    SemanticTokenType::STRING,      // 4: string literals
+    SemanticTokenType::NUMBER,      // 5: number literals
+    SemanticTokenType::OPERATOR,    // 6: =
+    SemanticTokenType::COMMENT,     // 7: comments
+    SemanticTokenType::FUNCTION,    // 8: function names
+    SemanticTokenType::MACRO,       // 9: ${...} interpolation spans inside double-quoted strings
+    SemanticTokenType::ENUM,        // 10: enum namespace (awscc.ec2.Vpc.InstanceTenancy)
+    SemanticTokenType::ENUM_MEMBER, // 11: enum value (dedicated)
 ];
 
+/// Token modifiers, set only on tokens classified against a provider
+/// schema. A read-only attribute written in a block carries both
+/// `deprecated` and `readonly`, so themes that strike through deprecated
+/// symbols flag it without extra configuration.
+pub const TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+    SemanticTokenModifier::DEPRECATED, // 0: read-only attribute assigned in a block
+    SemanticTokenModifier::READONLY,   // 1: read-only attribute assigned in a block
+    SemanticTokenModifier::DEFAULT_LIBRARY, // 2: resource type known to a provider schema
+    SemanticTokenModifier::new("required"), // 3: required attribute
+    SemanticTokenModifier::new("optional"), // 4: optional attribute
+];
+
+const MOD_DEPRECATED: u32 = 1 << 0;
+const MOD_READONLY: u32 = 1 << 1;
+const MOD_DEFAULT_LIBRARY: u32 = 1 << 2;
+const MOD_REQUIRED: u32 = 1 << 3;
+const MOD_OPTIONAL: u32 = 1 << 4;
+
 /// Create the semantic tokens legend for capability registration
 pub fn legend() -> SemanticTokensLegend {
     SemanticTokensLegend {
         token_types: TOKEN_TYPES.to_vec(),
-        token_modifiers: vec![],
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
     }
 }
 
 pub struct SemanticTokensProvider {
     /// Precomputed region patterns like "aws.Region.us_east_1", "awscc.Region.ap_northeast_1"
     region_patterns: Vec<String>,
+    /// Provider schemas used to refine the lexical tokens; see
+    /// [`Self::with_schemas`].
+    schemas: Option<Arc<SchemaRegistry>>,
 }
 
 impl SemanticTokensProvider {
@@ -38,10 +68,29 @@ impl SemanticTokensProvider {
         // Extract region patterns directly from completion data
         let region_patterns: Vec<String> =
             region_completions.iter().map(|c| c.value.clone()).collect();
-        Self { region_patterns }
+        Self {
+            region_patterns,
+            schemas: None,
+        }
+    }
+
+    /// Classify identifiers inside resource and data source blocks against
+    /// `schemas`: known resource types, required / optional / read-only
+    /// attributes, and enum namespaces and values.
+    pub fn with_schemas(mut self, schemas: Arc<SchemaRegistry>) -> Self {
+        self.schemas = Some(schemas);
+        self
     }
 
     pub fn tokenize(&self, text: &str) -> Vec<SemanticToken> {
+        let tokens = self.tokenize_lexical(text);
+        match &self.schemas {
+            Some(schemas) => apply_schema_classes(&tokens, schema_classes(schemas, text)),
+            None => tokens,
+        }
+    }
+
+    fn tokenize_lexical(&self, text: &str) -> Vec<SemanticToken> {
         let mut tokens = Vec::new();
         let mut prev_line = 0u32;
         let mut prev_start = 0u32;
@@ -972,6 +1021,178 @@ fn skip_string_literal(chars: &[char], start: usize) -> usize {
     chars.len()
 }
 
+/// A token classified against a provider schema, in absolute
+/// coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SchemaClass {
+    line: u32,
+    start: u32,
+    length: u32,
+    token_type: u32,
+    modifiers: u32,
+}
+
+impl SchemaClass {
+    /// A class spanning `first..=last`, or `None` when the span crosses a
+    /// line break (semantic tokens are single-line).
+    fn span(first: &Token, last: &Token, token_type: u32, modifiers: u32) -> Option<Self> {
+        let (start, end) = (first.range.start, last.range.end);
+        (start.line == end.line).then_some(Self {
+            line: start.line,
+            start: start.character,
+            length: end.character - start.character,
+            token_type,
+            modifiers,
+        })
+    }
+}
+
+/// Classify the resource types, attribute names and enum values of every
+/// block whose type resolves in `schemas`. Works from the token scan, so
+/// a file the parser currently rejects still gets classified.
+fn schema_classes(schemas: &SchemaRegistry, text: &str) -> Vec<SchemaClass> {
+    let tokens = scan(text);
+    let mut schema = None;
+    let mut classes = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            TokenKind::Sym('{') if token.depth == 1 => {
+                schema = block_schema(schemas, &tokens[..i]);
+                let start = dotted_start(&tokens[..i]);
+                if schema.is_some() && start < i {
+                    classes.extend(SchemaClass::span(
+                        &tokens[start],
+                        &tokens[i - 1],
+                        1, // TYPE
+                        MOD_DEFAULT_LIBRARY,
+                    ));
+                }
+                continue;
+            }
+            TokenKind::Sym('}') if token.depth == 0 => {
+                schema = None;
+                continue;
+            }
+            _ => {}
+        }
+        let Some(schema) = schema else {
+            continue;
+        };
+        let line = token.range.start.line;
+        if token.kind != TokenKind::Ident
+            || token.depth != 1
+            || !is_sym(tokens.get(i + 1), '=')
+            || i.checked_sub(1)
+                .is_some_and(|p| tokens[p].range.end.line == line)
+        {
+            continue;
+        }
+        let Some(attr) = schema.attributes.get(&token.text) else {
+            continue;
+        };
+        let modifiers = if attr.read_only {
+            MOD_DEPRECATED | MOD_READONLY
+        } else if attr.required {
+            MOD_REQUIRED
+        } else {
+            MOD_OPTIONAL
+        };
+        classes.extend(SchemaClass::span(token, token, 3, modifiers)); // PROPERTY
+
+        if attr.attr_type.enum_parts().is_some() {
+            let value: Vec<&Token> = tokens[i + 2..]
+                .iter()
+                .take_while(|t| t.range.start.line == line)
+                .collect();
+            classes.extend(enum_value_classes(&value));
+        }
+    }
+    classes
+}
+
+/// ENUM over the namespace of a dotted enum value and ENUM_MEMBER over
+/// its last segment; a bare identifier is just the member.
+fn enum_value_classes(value: &[&Token]) -> Vec<SchemaClass> {
+    let dotted = value.iter().enumerate().all(|(i, t)| {
+        if i.is_multiple_of(2) {
+            t.kind == TokenKind::Ident
+        } else {
+            t.kind == TokenKind::Sym('.')
+        }
+    });
+    if value.is_empty() || !dotted || value.len().is_multiple_of(2) {
+        return Vec::new();
+    }
+    let member = value[value.len() - 1];
+    let mut classes = Vec::new();
+    if value.len() >= 3 {
+        classes.extend(SchemaClass::span(value[0], value[value.len() - 3], 10, 0)); // ENUM
+    }
+    classes.extend(SchemaClass::span(member, member, 11, 0)); // ENUM_MEMBER
+    classes
+}
+
+/// Overlay `classes` on the lexical tokens. A class replaces every
+/// lexical token it fully covers; a class that only partly overlaps a
+/// lexical token is dropped, since the lexer saw something the scan did
+/// not.
+fn apply_schema_classes(tokens: &[SemanticToken], classes: Vec<SchemaClass>) -> Vec<SemanticToken> {
+    let mut absolute: Vec<SchemaClass> = Vec::with_capacity(tokens.len());
+    let (mut line, mut start) = (0u32, 0u32);
+    for t in tokens {
+        if t.delta_line == 0 {
+            start += t.delta_start;
+        } else {
+            line += t.delta_line;
+            start = t.delta_start;
+        }
+        absolute.push(SchemaClass {
+            line,
+            start,
+            length: t.length,
+            token_type: t.token_type,
+            modifiers: t.token_modifiers_bitset,
+        });
+    }
+
+    for class in classes {
+        let end = class.start + class.length;
+        let overlaps = |t: &SchemaClass| {
+            t.line == class.line && t.start < end && class.start < t.start + t.length
+        };
+        let covered = |t: &SchemaClass| t.start >= class.start && t.start + t.length <= end;
+        if absolute.iter().any(|t| overlaps(t) && !covered(t)) {
+            continue;
+        }
+        absolute.retain(|t| !overlaps(t));
+        absolute.push(class);
+    }
+    absolute.sort_by_key(|t| (t.line, t.start));
+
+    let (mut prev_line, mut prev_start) = (0u32, 0u32);
+    absolute
+        .into_iter()
+        .map(|t| {
+            let delta_line = t.line - prev_line;
+            let delta_start = if delta_line == 0 {
+                t.start - prev_start
+            } else {
+                t.start
+            };
+            prev_line = t.line;
+            prev_start = t.start;
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: t.length,
+                token_type: t.token_type,
+                token_modifiers_bitset: t.modifiers,
+            }
+        })
+        .collect()
+}
+
 /// Convert raw `(start, length, token_type)` tuples for a single line
 /// into LSP-encoded `SemanticToken`s appended to `tokens`. Updates
 /// `prev_line` / `prev_start` to track the running delta cursor across
@@ -2474,4 +2695,67 @@ mod tests {
              tokens: {tokens:?}"
         );
     }
+
+    fn schema_provider() -> SemanticTokensProvider {
+        use carina_core::schema::{
+            AttributeSchema, AttributeType, ResourceSchema, TypeIdentity, types,
+        };
+        let tenancy = AttributeType::enum_(
+            TypeIdentity::new(Some("awscc"), ["ec2", "Vpc"], "InstanceTenancy"),
+            Some(vec!["default".to_string(), "dedicated".to_string()]),
+            vec![],
+            None,
+            None,
+        );
+        let schema = ResourceSchema::new("ec2.Vpc")
+            .attribute(AttributeSchema::new("cidr_block", types::ipv4_cidr()).required())
+            .attribute(AttributeSchema::new("instance_tenancy", tenancy))
+            .attribute(AttributeSchema::new("vpc_id", AttributeType::string()).read_only());
+        let mut schemas = SchemaRegistry::new();
+        schemas.insert("awscc", schema);
+        SemanticTokensProvider::new(&[]).with_schemas(Arc::new(schemas))
+    }
+
+    /// `(line, start, length, kind, modifiers)` for every token.
+    fn classified(text: &str) -> Vec<(u32, u32, u32, u32, u32)> {
+        let tokens = schema_provider().tokenize(text);
+        absolute_tokens(&tokens)
+            .into_iter()
+            .zip(&tokens)
+            .map(|((line, start, len, kind), t)| (line, start, len, kind, t.token_modifiers_bitset))
+            .collect()
+    }
+
+    #[test]
+    fn schema_classifies_resource_type_and_attribute_names() {
+        let text = "let vpc = awscc.ec2.Vpc {\n  cidr_block = '10.0.0.0/16'\n  instance_tenancy = dedicated\n  vpc_id = 'vpc-1'\n  unknown = 1\n}\n";
+        let tokens = classified(text);
+        assert!(tokens.contains(&(0, 10, 13, 1, MOD_DEFAULT_LIBRARY)));
+        assert!(tokens.contains(&(1, 2, 10, 3, MOD_REQUIRED)));
+        assert!(tokens.contains(&(2, 2, 16, 3, MOD_OPTIONAL)));
+        assert!(tokens.contains(&(3, 2, 6, 3, MOD_DEPRECATED | MOD_READONLY)));
+        // Not in the schema: plain lexical PROPERTY.
+        assert!(tokens.contains(&(4, 2, 7, 3, 0)));
+    }
+
+    #[test]
+    fn schema_classifies_enum_namespace_and_member() {
+        let text =
+            "awscc.ec2.Vpc {\n  instance_tenancy = awscc.ec2.Vpc.InstanceTenancy.dedicated\n}\n";
+        let tokens = classified(text);
+        assert!(tokens.contains(&(1, 21, 29, 10, 0)));
+        assert!(tokens.contains(&(1, 51, 9, 11, 0)));
+
+        let bare = classified("awscc.ec2.Vpc {\n  instance_tenancy = dedicated\n}\n");
+        assert!(bare.contains(&(1, 21, 9, 11, 0)));
+    }
+
+    #[test]
+    fn unknown_resource_type_keeps_lexical_tokens() {
+        let text = "aws.s3.Bucket {\n  cidr_block = 'x'\n}\n";
+        assert_eq!(
+            schema_provider().tokenize(text),
+            SemanticTokensProvider::new(&[]).tokenize(text)
+        );
+    }
 }
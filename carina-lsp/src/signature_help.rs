@@ -0,0 +1,205 @@
+//! Signature help for built-in function calls.
+//!
+//! Works on raw text so it keeps answering while the call is half
+//! typed: from the cursor, walk back to the innermost unclosed `(`,
+//! take the identifier in front of it as the function name, and count
+//! the top-level commas in between to find the active parameter.
+//! Parameter labels come from the `signature` string each built-in
+//! registers, so this never drifts from hover or completion.
+
+use tower_lsp::lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, Position, SignatureHelp,
+    SignatureInformation,
+};
+
+use carina_core::builtins;
+
+/// Signature help at `position` in `text`, or `None` when the cursor is
+/// not inside the argument list of a known built-in.
+pub fn signature_help(text: &str, position: Position) -> Option<SignatureHelp> {
+    let before = text_before(text, position)?;
+    let (name, active_parameter) = enclosing_call(&before)?;
+    let func = builtins::builtin_functions()
+        .iter()
+        .find(|f| f.name == name)?;
+
+    let parameters = parameter_labels(func.signature)
+        .into_iter()
+        .map(|label| ParameterInformation {
+            label: ParameterLabel::Simple(label.to_string()),
+            documentation: None,
+        })
+        .collect();
+
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label: func.signature.to_string(),
+            documentation: Some(Documentation::String(func.description.to_string())),
+            parameters: Some(parameters),
+            active_parameter: None,
+        }],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+/// Everything in `text` up to `position`.
+fn text_before(text: &str, position: Position) -> Option<String> {
+    let mut out = String::new();
+    for (i, line) in text.split('\n').enumerate() {
+        if i == position.line as usize {
+            out.extend(line.chars().take(position.character as usize));
+            return Some(out);
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    None
+}
+
+/// The function name and zero-based argument index of the innermost
+/// call whose `(` is still open at the end of `before`.
+fn enclosing_call(before: &str) -> Option<(String, u32)> {
+    // Quoted text can hold parentheses and commas; blank it out first.
+    let mut code = Vec::with_capacity(before.len());
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for c in before.chars() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+                code.push(' ');
+            }
+            None => {
+                if c == '\'' || c == '"' {
+                    quote = Some(c);
+                    code.push(' ');
+                } else if c == '#' {
+                    // Comments run to the end of the line.
+                    quote = Some('\n');
+                    code.push(' ');
+                } else {
+                    code.push(c);
+                }
+            }
+        }
+    }
+
+    let mut depth = 0usize;
+    let mut commas = 0u32;
+    for i in (0..code.len()).rev() {
+        match code[i] {
+            ')' | ']' | '}' => depth += 1,
+            '[' | '{' if depth > 0 => depth -= 1,
+            // Inside a list literal argument: the commas seen so far
+            // separate list elements, not arguments.
+            '[' => commas = 0,
+            '{' => return None,
+            '(' if depth > 0 => depth -= 1,
+            '(' => {
+                let name: String = code[..i]
+                    .iter()
+                    .rev()
+                    .skip_while(|c| **c == ' ')
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev()
+                    .collect();
+                return (!name.is_empty()).then_some((name, commas));
+            }
+            ',' if depth == 0 => commas += 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `cidr_subnet(prefix: String, newbits: Int, netnum: Int) -> String`
+/// → `["prefix: String", "newbits: Int", "netnum: Int"]`.
+fn parameter_labels(signature: &str) -> Vec<&str> {
+    let Some(open) = signature.find('(') else {
+        return Vec::new();
+    };
+    let Some(close) = signature.rfind(") ->").or_else(|| signature.rfind(')')) else {
+        return Vec::new();
+    };
+    let inner = &signature[open + 1..close];
+    let mut labels = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                labels.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() {
+        labels.push(last);
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn help_at(text: &str, line: u32, character: u32) -> Option<SignatureHelp> {
+        signature_help(text, Position { line, character })
+    }
+
+    #[test]
+    fn active_parameter_follows_top_level_commas() {
+        let text = "cidr = cidr_subnet('10.0.0.0/16', 8, ";
+        let help = help_at(text, 0, text.len() as u32).unwrap();
+        assert_eq!(help.active_parameter, Some(2));
+        let signature = &help.signatures[0];
+        assert!(signature.label.starts_with("cidr_subnet("));
+        let labels: Vec<_> = signature
+            .parameters
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|p| match &p.label {
+                ParameterLabel::Simple(s) => s.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["prefix: String", "newbits: Int", "netnum: Int"]
+        );
+    }
+
+    #[test]
+    fn nested_calls_and_quoted_commas_are_skipped() {
+        let text = "name = format('%s, %s', [join('-', ['a', 'b']), ";
+        let help = help_at(text, 0, text.len() as u32).unwrap();
+        assert!(help.signatures[0].label.starts_with("format("));
+        assert_eq!(help.active_parameter, Some(1));
+
+        let text = "x = cidr_host(\n  '10.0.1.0/24',\n  ";
+        let help = help_at(text, 2, 2).unwrap();
+        assert!(help.signatures[0].label.starts_with("cidr_host("));
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn no_help_outside_a_known_call() {
+        assert!(help_at("cidr_block = '10.0.0.0/16'", 0, 20).is_none());
+        assert!(help_at("x = my_fn(1, ", 0, 13).is_none());
+        assert!(help_at("x = upper('a')", 0, 14).is_none());
+    }
+}
@@ -0,0 +1,512 @@
+//! `textDocument/documentSymbol` and `workspace/symbol`.
+//!
+//! The outline lists a file's top-level declarations: `let` bindings
+//! (resources, module calls, upstream states, plain values), anonymous
+//! resources and module calls, providers, the backend, functions, and the
+//! keys of `arguments`, `attributes` and `exports`. Like rename, it works
+//! from the token scan so the outline survives a file that does not parse.
+
+use std::path::Path;
+
+use tower_lsp::lsp_types::{DocumentSymbol, Location, Range, SymbolInformation, SymbolKind, Url};
+
+use crate::scan::{Token, TokenKind, is_sym, scan};
+
+/// Top-level keywords that open a statement.
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "let",
+    "provider",
+    "backend",
+    "naming",
+    "arguments",
+    "variables",
+    "attributes",
+    "exports",
+    "import",
+    "removed",
+    "moved",
+    "require",
+    "if",
+    "for",
+    "fn",
+];
+
+/// Outline of one file, in source order.
+pub fn document_symbols(text: &str) -> Vec<DocumentSymbol> {
+    let tokens = scan(text);
+    let starts = statement_starts(&tokens);
+    starts
+        .iter()
+        .enumerate()
+        .filter_map(|(n, &start)| {
+            let end = starts.get(n + 1).map_or(tokens.len(), |&next| next) - 1;
+            let range = Range {
+                start: tokens[start].range.start,
+                end: tokens[end].range.end,
+            };
+            statement_symbol(&tokens[start..=end], range)
+        })
+        .collect()
+}
+
+/// Symbols across `sources` whose name fuzzily matches `query`: every
+/// query character appears in the name, in order, ignoring case and
+/// whitespace. Resources are named `<type> <binding>` so either half can
+/// be searched for.
+pub fn workspace_symbols(query: &str, sources: &[(Url, String)]) -> Vec<SymbolInformation> {
+    let mut symbols = Vec::new();
+    for (uri, text) in sources {
+        let container = uri.to_file_path().ok().and_then(|p| {
+            p.parent()
+                .and_then(Path::file_name)
+                .map(|n| n.to_string_lossy().into_owned())
+        });
+        for symbol in document_symbols(text) {
+            let name = match &symbol.detail {
+                Some(resource_type) if symbol.kind == SymbolKind::OBJECT => {
+                    format!("{} {}", resource_type, symbol.name)
+                }
+                _ => symbol.name.clone(),
+            };
+            push_symbol(
+                &mut symbols,
+                query,
+                name,
+                symbol.kind,
+                uri,
+                symbol.range,
+                container.clone(),
+            );
+            for child in symbol.children.iter().flatten() {
+                push_symbol(
+                    &mut symbols,
+                    query,
+                    child.name.clone(),
+                    child.kind,
+                    uri,
+                    child.range,
+                    Some(symbol.name.clone()),
+                );
+            }
+        }
+    }
+    symbols
+}
+
+#[allow(deprecated)] // `SymbolInformation::deprecated` must still be set
+fn push_symbol(
+    symbols: &mut Vec<SymbolInformation>,
+    query: &str,
+    name: String,
+    kind: SymbolKind,
+    uri: &Url,
+    range: Range,
+    container_name: Option<String>,
+) {
+    if !fuzzy_matches(query, &name) {
+        return;
+    }
+    symbols.push(SymbolInformation {
+        name,
+        kind,
+        tags: None,
+        deprecated: None,
+        location: Location::new(uri.clone(), range),
+        container_name,
+    });
+}
+
+fn fuzzy_matches(query: &str, name: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| name_chars.any(|c| c == q))
+}
+
+/// Indices of the tokens that open a top-level statement: a statement
+/// keyword, or the head of an anonymous resource or module call
+/// (`a.b.C {`, `module {`), first on its line.
+fn statement_starts(tokens: &[Token]) -> Vec<usize> {
+    (0..tokens.len())
+        .filter(|&i| {
+            let token = &tokens[i];
+            if token.kind != TokenKind::Ident || token.depth != 0 {
+                return false;
+            }
+            if i > 0 && tokens[i - 1].range.end.line == token.range.start.line {
+                return false;
+            }
+            // `else { ... }` on its own line continues an `if`.
+            if token.text == "else" {
+                return false;
+            }
+            STATEMENT_KEYWORDS.contains(&token.text.as_str())
+                || is_sym(tokens.get(type_end(tokens, i) + 1), '{')
+        })
+        .collect()
+}
+
+/// Index of the last identifier of the dotted path starting at `i`.
+fn type_end(tokens: &[Token], i: usize) -> usize {
+    let mut end = i;
+    while is_sym(tokens.get(end + 1), '.')
+        && tokens
+            .get(end + 2)
+            .is_some_and(|t| t.kind == TokenKind::Ident)
+    {
+        end += 2;
+    }
+    end
+}
+
+fn dotted(tokens: &[Token], start: usize, end: usize) -> String {
+    tokens[start..=end]
+        .iter()
+        .filter(|t| t.kind == TokenKind::Ident)
+        .map(|t| t.text.as_str())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn statement_symbol(tokens: &[Token], range: Range) -> Option<DocumentSymbol> {
+    let head = &tokens[0];
+    match head.text.as_str() {
+        "let" => {
+            let name = tokens.get(1).filter(|t| t.kind == TokenKind::Ident)?;
+            if name.text == "_" || !is_sym(tokens.get(2), '=') {
+                return None;
+            }
+            let (kind, detail) = binding_kind(tokens, 3);
+            Some(symbol(name, kind, detail, range, None))
+        }
+        "provider" => {
+            let name = tokens.get(1).filter(|t| t.kind == TokenKind::Ident)?;
+            Some(symbol(
+                name,
+                SymbolKind::PACKAGE,
+                Some("provider".to_string()),
+                range,
+                None,
+            ))
+        }
+        "backend" => {
+            let detail = tokens.get(1).map(|t| t.text.clone());
+            Some(symbol(head, SymbolKind::PACKAGE, detail, range, None))
+        }
+        "fn" => {
+            let name = tokens.get(1).filter(|t| t.kind == TokenKind::Ident)?;
+            Some(symbol(name, SymbolKind::FUNCTION, None, range, None))
+        }
+        block @ ("arguments" | "attributes" | "exports") => {
+            let kind = if block == "arguments" {
+                SymbolKind::FIELD
+            } else {
+                SymbolKind::PROPERTY
+            };
+            let children = (0..tokens.len())
+                .filter(|&i| is_block_key(tokens, i))
+                .map(|i| {
+                    let end = (i + 1..tokens.len())
+                        .find(|&j| is_block_key(tokens, j))
+                        .map_or(tokens.len() - 1, |next| next - 1);
+                    // The block's own closing brace belongs to the block.
+                    let end = if is_sym(tokens.get(end), '}') && tokens[end].depth == 0 {
+                        end.saturating_sub(1).max(i)
+                    } else {
+                        end
+                    };
+                    let child_range = Range {
+                        start: tokens[i].range.start,
+                        end: tokens[end].range.end,
+                    };
+                    symbol(&tokens[i], kind, None, child_range, None)
+                })
+                .collect();
+            Some(symbol(
+                head,
+                SymbolKind::NAMESPACE,
+                None,
+                range,
+                Some(children),
+            ))
+        }
+        keyword if STATEMENT_KEYWORDS.contains(&keyword) => None,
+        _ => {
+            let end = type_end(tokens, 0);
+            if end == 0 {
+                Some(symbol(
+                    head,
+                    SymbolKind::MODULE,
+                    Some("module".to_string()),
+                    range,
+                    None,
+                ))
+            } else {
+                let resource_type = dotted(tokens, 0, end);
+                let selection = Range {
+                    start: head.range.start,
+                    end: tokens[end].range.end,
+                };
+                Some(with_selection(
+                    DocumentSymbolParts {
+                        name: resource_type,
+                        kind: SymbolKind::OBJECT,
+                        detail: None,
+                        children: None,
+                    },
+                    range,
+                    selection,
+                ))
+            }
+        }
+    }
+}
+
+/// Kind and detail of the value bound by a `let`, from the tokens
+/// starting at `rhs`.
+fn binding_kind(tokens: &[Token], rhs: usize) -> (SymbolKind, Option<String>) {
+    let Some(first) = tokens.get(rhs).filter(|t| t.kind == TokenKind::Ident) else {
+        return (SymbolKind::VARIABLE, None);
+    };
+    match first.text.as_str() {
+        "upstream_state" => (SymbolKind::NAMESPACE, Some("upstream_state".to_string())),
+        "use" => (SymbolKind::MODULE, Some("use".to_string())),
+        "wait" => (
+            SymbolKind::EVENT,
+            tokens.get(rhs + 1).map(|t| format!("wait {}", t.text)),
+        ),
+        "read"
+            if tokens
+                .get(rhs + 1)
+                .is_some_and(|t| t.kind == TokenKind::Ident) =>
+        {
+            let end = type_end(tokens, rhs + 1);
+            (SymbolKind::OBJECT, Some(dotted(tokens, rhs + 1, end)))
+        }
+        _ => {
+            let end = type_end(tokens, rhs);
+            if !is_sym(tokens.get(end + 1), '{') {
+                (SymbolKind::VARIABLE, None)
+            } else if end == rhs {
+                (SymbolKind::MODULE, Some(first.text.clone()))
+            } else {
+                (SymbolKind::OBJECT, Some(dotted(tokens, rhs, end)))
+            }
+        }
+    }
+}
+
+/// Whether token `i` is a `name: type` or `name =` key directly inside
+/// the block the statement opens.
+fn is_block_key(tokens: &[Token], i: usize) -> bool {
+    let token = &tokens[i];
+    token.kind == TokenKind::Ident
+        && token.depth == 1
+        && !(i > 0 && (is_sym(tokens.get(i - 1), '.') || is_sym(tokens.get(i - 1), ':')))
+        && (is_sym(tokens.get(i + 1), ':')
+            || (is_sym(tokens.get(i + 1), '=') && !is_sym(tokens.get(i + 2), '=')))
+}
+
+struct DocumentSymbolParts {
+    name: String,
+    kind: SymbolKind,
+    detail: Option<String>,
+    children: Option<Vec<DocumentSymbol>>,
+}
+
+fn symbol(
+    name: &Token,
+    kind: SymbolKind,
+    detail: Option<String>,
+    range: Range,
+    children: Option<Vec<DocumentSymbol>>,
+) -> DocumentSymbol {
+    with_selection(
+        DocumentSymbolParts {
+            name: name.text.clone(),
+            kind,
+            detail,
+            children,
+        },
+        range,
+        name.range,
+    )
+}
+
+#[allow(deprecated)] // `DocumentSymbol::deprecated` must still be set
+fn with_selection(
+    parts: DocumentSymbolParts,
+    range: Range,
+    selection_range: Range,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: parts.name,
+        detail: parts.detail,
+        kind: parts.kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: parts.children,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::Position;
+
+    const MAIN: &str = r#"provider aws {
+  region = aws.Region.ap_northeast_1
+}
+
+let vpc = aws.ec2.Vpc {
+  cidr_block = '10.0.0.0/16'
+  tags = {
+    Name = 'main'
+  }
+}
+
+# let commented = aws.ec2.Vpc {}
+let private_a = aws.ec2.Subnet {
+  vpc_id     = vpc.vpc_id
+  cidr_block = '10.0.1.0/24'
+}
+
+let cidrs = [
+  '10.0.0.0/16',
+]
+
+aws.s3.Bucket {
+  bucket = 'logs'
+}
+
+exports {
+  vpc_id: String = vpc.vpc_id
+  subnet_ids: list(String) = [
+    private_a.subnet_id,
+  ]
+}
+"#;
+
+    fn names(symbols: &[DocumentSymbol]) -> Vec<(&str, SymbolKind, Option<&str>)> {
+        symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.detail.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn outline_lists_top_level_declarations_in_order() {
+        let symbols = document_symbols(MAIN);
+        assert_eq!(
+            names(&symbols),
+            vec![
+                ("aws", SymbolKind::PACKAGE, Some("provider")),
+                ("vpc", SymbolKind::OBJECT, Some("aws.ec2.Vpc")),
+                ("private_a", SymbolKind::OBJECT, Some("aws.ec2.Subnet")),
+                ("cidrs", SymbolKind::VARIABLE, None),
+                ("aws.s3.Bucket", SymbolKind::OBJECT, None),
+                ("exports", SymbolKind::NAMESPACE, None),
+            ]
+        );
+        let exports = symbols.last().unwrap().children.as_ref().unwrap();
+        assert_eq!(
+            names(exports),
+            vec![
+                ("vpc_id", SymbolKind::PROPERTY, None),
+                ("subnet_ids", SymbolKind::PROPERTY, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn symbol_ranges_cover_the_statement_and_select_the_name() {
+        let symbols = document_symbols(MAIN);
+        let vpc = &symbols[1];
+        assert_eq!(vpc.range.start, Position::new(4, 0));
+        assert_eq!(vpc.range.end, Position::new(9, 1));
+        assert_eq!(vpc.selection_range.start, Position::new(4, 4));
+        assert_eq!(vpc.selection_range.end, Position::new(4, 7));
+
+        let cidrs = &symbols[3];
+        assert_eq!(cidrs.range.start, Position::new(17, 0));
+        assert_eq!(cidrs.range.end, Position::new(19, 1));
+
+        let subnet_ids = &symbols[5].children.as_ref().unwrap()[1];
+        assert_eq!(subnet_ids.range.start, Position::new(27, 2));
+        assert_eq!(subnet_ids.range.end, Position::new(29, 3));
+    }
+
+    #[test]
+    fn binding_kinds_follow_the_right_hand_side() {
+        let text = "let net = network {\n  cidr = '10.0.0.0/16'\n}\n\
+                    let orgs = upstream_state {\n  source = '../orgs'\n}\n\
+                    let ami = read aws.ec2.Image {\n  name = 'x'\n}\n\
+                    let lib = use { source = './lib' }\n\
+                    fn double(x: Int): Int {\n  x * 2\n}\n";
+        assert_eq!(
+            names(&document_symbols(text)),
+            vec![
+                ("net", SymbolKind::MODULE, Some("network")),
+                ("orgs", SymbolKind::NAMESPACE, Some("upstream_state")),
+                ("ami", SymbolKind::OBJECT, Some("aws.ec2.Image")),
+                ("lib", SymbolKind::MODULE, Some("use")),
+                ("double", SymbolKind::FUNCTION, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn outline_survives_unparseable_text() {
+        let text = "let vpc = aws.ec2.Vpc {\n  cidr_block = \n}\n\nlet subnet = aws.ec2.Subnet {\n";
+        assert_eq!(
+            names(&document_symbols(text)),
+            vec![
+                ("vpc", SymbolKind::OBJECT, Some("aws.ec2.Vpc")),
+                ("subnet", SymbolKind::OBJECT, Some("aws.ec2.Subnet")),
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_symbols_fuzzy_match_type_and_binding() {
+        let network = Url::parse("file:///infra/network/main.crn").unwrap();
+        let app = Url::parse("file:///infra/app/main.crn").unwrap();
+        let sources = vec![
+            (network.clone(), MAIN.to_string()),
+            (
+                app.clone(),
+                "let private_app = aws.ec2.Instance {\n  ami = 'x'\n}\n".to_string(),
+            ),
+        ];
+
+        let found = workspace_symbols("ec2.Subnet private_a", &sources);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "aws.ec2.Subnet private_a");
+        assert_eq!(found[0].location.uri, network);
+        assert_eq!(found[0].container_name.as_deref(), Some("network"));
+
+        let found: Vec<_> = workspace_symbols("privat", &sources)
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(
+            found,
+            vec!["aws.ec2.Subnet private_a", "aws.ec2.Instance private_app"]
+        );
+
+        let exported = workspace_symbols("subnet_ids", &sources);
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].container_name.as_deref(), Some("exports"));
+    }
+
+    #[test]
+    fn empty_query_returns_every_symbol() {
+        let uri = Url::parse("file:///infra/network/main.crn").unwrap();
+        let sources = vec![(uri, MAIN.to_string())];
+        assert_eq!(workspace_symbols("", &sources).len(), 8);
+    }
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use carina_core::parser::{self, ProviderConfig, ProviderContext};
+use carina_core::parser::{self, BackendConfig, ProviderConfig, ProviderContext};
 
 /// Discover all provider configurations from .crn files in a workspace directory.
 ///
@@ -93,6 +93,44 @@ fn discover_by_dir_recursive(dir: &Path, result: &mut HashMap<PathBuf, Vec<Provi
     }
 }
 
+/// Discover configuration directories that have state, with the `backend`
+/// block each one declares (`None` for the default local backend).
+///
+/// A directory counts when one of its `.crn` files declares a provider or
+/// a backend; module directories declare neither and keep no state.
+pub fn discover_state_backends(workspace_root: &Path) -> HashMap<PathBuf, Option<BackendConfig>> {
+    let mut result: HashMap<PathBuf, Option<BackendConfig>> = HashMap::new();
+    discover_backends_recursive(workspace_root, &mut result);
+    result
+}
+
+fn discover_backends_recursive(dir: &Path, result: &mut HashMap<PathBuf, Option<BackendConfig>>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            discover_backends_recursive(&path, result);
+        } else if path.extension().is_some_and(|ext| ext == "crn")
+            && let Ok(content) = fs::read_to_string(&path)
+        {
+            let ctx = ProviderContext::default();
+            if let Ok(parsed) = parser::parse(&content, &ctx)
+                && (parsed.backend.is_some() || !parsed.providers.is_empty())
+            {
+                let source_dir = path.parent().unwrap_or(dir).to_path_buf();
+                let backend = result.entry(source_dir).or_default();
+                if backend.is_none() {
+                    *backend = parsed.backend;
+                }
+            }
+        }
+    }
+}
+
 /// Build a reverse import map: module directory → set of caller directories.
 ///
 /// Scans all `.crn` files in the workspace for `import` statements, resolves
@@ -464,4 +502,40 @@ mod tests {
         let import_map = discover_import_map(dir.path());
         assert!(import_map.is_empty());
     }
+
+    #[test]
+    fn discover_state_backends_finds_configuration_directories() {
+        let dir = TempDir::new().unwrap();
+        let remote = dir.path().join("remote");
+        let local = dir.path().join("local");
+        let module = dir.path().join("modules").join("vpc");
+        for d in [&remote, &local, &module] {
+            fs::create_dir_all(d).unwrap();
+        }
+
+        fs::write(
+            remote.join("backend.crn"),
+            "backend s3 {\n  bucket = 'state'\n  key = 'remote.json'\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            remote.join("providers.crn"),
+            "provider aws {\n  region = 'us-east-1'\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            local.join("providers.crn"),
+            "provider aws {\n  region = 'us-east-1'\n}\n",
+        )
+        .unwrap();
+        fs::write(module.join("main.crn"), "arguments {\n  cidr: String\n}\n").unwrap();
+
+        let backends = discover_state_backends(dir.path());
+        assert_eq!(backends.len(), 2);
+        assert_eq!(
+            backends[&remote].as_ref().map(|b| b.backend_type.as_str()),
+            Some("s3")
+        );
+        assert!(backends[&local].is_none());
+    }
 }
@@ -0,0 +1,155 @@
+//! Whole-request deadline for plugin HTTP calls.
+//!
+//! wasi:http's per-phase timeouts (connect, first byte, between bytes)
+//! leave two holes: the TLS handshake runs without any timeout, and a
+//! response body that trickles one chunk just inside the between-bytes
+//! limit never ends. Either one blocks the guest in `pollable.block()` /
+//! `blocking_read` indefinitely while it holds the store mutex. A single
+//! deadline covering the whole exchange, from connect to the last body
+//! frame, closes both holes; when it expires the guest gets a wasi:http
+//! timeout error code it can report (or retry) like any other timeout.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::body::{Body, Frame, SizeHint};
+use tokio::time::{Instant, Sleep};
+
+/// Wraps a response body so that streaming fails with `on_timeout()` once
+/// `deadline` passes, however steadily the server keeps sending bytes.
+pub(crate) struct DeadlineBody<B: Body> {
+    inner: B,
+    sleep: Pin<Box<Sleep>>,
+    on_timeout: fn() -> B::Error,
+    expired: bool,
+}
+
+impl<B: Body> DeadlineBody<B> {
+    pub(crate) fn new(inner: B, deadline: Instant, on_timeout: fn() -> B::Error) -> Self {
+        Self {
+            inner,
+            sleep: Box::pin(tokio::time::sleep_until(deadline)),
+            on_timeout,
+            expired: false,
+        }
+    }
+}
+
+impl<B: Body + Unpin> Body for DeadlineBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        if this.expired {
+            return Poll::Ready(None);
+        }
+        if let Poll::Ready(frame) = Pin::new(&mut this.inner).poll_frame(cx) {
+            return Poll::Ready(frame);
+        }
+        match this.sleep.as_mut().poll(cx) {
+            Poll::Ready(()) => {
+                this.expired = true;
+                Poll::Ready(Some(Err((this.on_timeout)())))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.expired || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Run `send` (connect, TLS, request, response headers) under `deadline`,
+/// mapping expiry to `on_timeout()`.
+pub(crate) async fn until_deadline<T, E>(
+    deadline: Instant,
+    send: impl Future<Output = Result<T, E>>,
+    on_timeout: fn() -> E,
+) -> Result<T, E> {
+    match tokio::time::timeout_at(deadline, send).await {
+        Ok(result) => result,
+        Err(_elapsed) => Err(on_timeout()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// A body that yields one frame per `poll` after `gap`, forever.
+    struct Trickle {
+        gap: Pin<Box<Sleep>>,
+        interval: Duration,
+    }
+
+    impl Body for Trickle {
+        type Data = &'static [u8];
+        type Error = &'static str;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            let interval = self.interval;
+            match self.gap.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    self.gap = Box::pin(tokio::time::sleep(interval));
+                    Poll::Ready(Some(Ok(Frame::data(b"x".as_slice()))))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    async fn next_frame<B: Body + Unpin>(body: &mut B) -> Option<Result<Frame<B::Data>, B::Error>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx)).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn trickling_body_fails_at_the_deadline() {
+        let interval = Duration::from_secs(10);
+        let trickle = Trickle {
+            gap: Box::pin(tokio::time::sleep(interval)),
+            interval,
+        };
+        let deadline = Instant::now() + Duration::from_secs(35);
+        let mut body = DeadlineBody::new(trickle, deadline, || "deadline");
+
+        let mut frames = 0;
+        let err = loop {
+            match next_frame(&mut body).await {
+                Some(Ok(_)) => frames += 1,
+                Some(Err(e)) => break e,
+                None => panic!("trickle never ends on its own"),
+            }
+        };
+        assert_eq!(err, "deadline");
+        assert_eq!(frames, 3);
+        assert!(next_frame(&mut body).await.is_none());
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_handshake_fails_at_the_deadline() {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let hung = std::future::pending::<Result<(), &str>>();
+        assert_eq!(
+            until_deadline(deadline, hung, || "deadline").await,
+            Err("deadline")
+        );
+
+        let quick = async { Ok::<_, &str>(7) };
+        assert_eq!(until_deadline(deadline, quick, || "deadline").await, Ok(7));
+    }
+}
@@ -1,3 +1,6 @@
+mod http_deadline;
+pub mod process_convert;
+pub mod process_factory;
 pub mod wasm_convert;
 pub mod wasm_factory;
 
@@ -25,4 +28,5 @@ pub mod wasm_bindings_http {
     });
 }
 
+pub use process_factory::{ProcessProviderFactory, ProviderClient};
 pub use wasm_factory::WasmProviderFactory;
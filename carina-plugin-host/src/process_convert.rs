@@ -0,0 +1,343 @@
+//! Conversions between carina-core types and the JSON-RPC protocol types
+//! spoken by out-of-process providers.
+//!
+//! Values travel as untagged JSON on this boundary, so the value
+//! conversions reuse the JSON helpers of [`crate::wasm_convert`] and
+//! reject the same unresolved `Value` variants.
+
+use std::collections::HashMap;
+
+use carina_core::provider::{
+    CreateOutcome as CoreCreateOutcome, CreateRequest as CoreCreateRequest,
+    DeleteBlocker as CoreDeleteBlocker, DeleteRequest as CoreDeleteRequest,
+    DiagnosticSeverity as CoreDiagnosticSeverity, ErrorDetail as CoreErrorDetail,
+    OperationMetrics as CoreOperationMetrics, PatchOp as CorePatchOp,
+    PatchOpKind as CorePatchOpKind, ProviderCapabilities as CoreProviderCapabilities,
+    ProviderError as CoreProviderError, ResourceDiagnostic as CoreResourceDiagnostic,
+    UpdateOutcome as CoreUpdateOutcome, UpdateRequest as CoreUpdateRequest,
+};
+use carina_core::resource::{
+    DataSource as CoreDataSource, Directives, Resource as CoreResource,
+    ResourceId as CoreResourceId, State as CoreState, Value as CoreValue,
+};
+use carina_core::value::SerializationError;
+
+use carina_provider_protocol::types as proto;
+
+use crate::wasm_convert::{FlattenedCause, core_value_to_json, json_to_core_value};
+
+// -- Value --
+
+pub fn core_to_proto_value(v: &CoreValue) -> Result<proto::Value, SerializationError> {
+    let json = core_value_to_json(v)?;
+    // Every JSON shape decodes into the untagged protocol `Value`.
+    Ok(serde_json::from_value(json).expect("protocol Value accepts any JSON"))
+}
+
+pub fn proto_to_core_value(v: &proto::Value) -> CoreValue {
+    let json = serde_json::to_value(v).unwrap_or(serde_json::Value::Null);
+    json_to_core_value(&json)
+}
+
+pub fn core_to_proto_value_map<'a, M>(
+    map: M,
+) -> Result<HashMap<String, proto::Value>, SerializationError>
+where
+    M: IntoIterator<Item = (&'a String, &'a CoreValue)>,
+{
+    map.into_iter()
+        .map(|(k, v)| core_to_proto_value(v).map(|pv| (k.clone(), pv)))
+        .collect()
+}
+
+pub fn proto_to_core_value_map(map: &HashMap<String, proto::Value>) -> HashMap<String, CoreValue> {
+    map.iter()
+        .map(|(k, v)| (k.clone(), proto_to_core_value(v)))
+        .collect()
+}
+
+// -- ResourceId --
+
+pub fn core_to_proto_resource_id(id: &CoreResourceId) -> proto::ResourceId {
+    proto::ResourceId {
+        provider: id.provider.clone(),
+        resource_type: id.resource_type.clone(),
+        identity: id.identity_or_empty().to_string(),
+    }
+}
+
+pub fn proto_to_core_resource_id(id: &proto::ResourceId) -> CoreResourceId {
+    // Like the WIT record, the protocol `ResourceId` has no
+    // provider-instance field, so a named instance does not round-trip.
+    CoreResourceId::with_provider_name_compat(&id.provider, &id.resource_type, &id.identity, None)
+}
+
+// -- State --
+
+pub fn core_to_proto_state(state: &CoreState) -> Result<proto::State, SerializationError> {
+    Ok(proto::State {
+        id: core_to_proto_resource_id(&state.id),
+        identifier: state.identifier.clone(),
+        attributes: core_to_proto_value_map(&state.attributes)?,
+        exists: state.exists,
+    })
+}
+
+/// Convert a protocol state back to core, keyed on the host's `id` rather
+/// than the one echoed by the provider.
+pub fn proto_to_core_state(state: &proto::State, id: &CoreResourceId) -> CoreState {
+    if !state.exists {
+        return CoreState::not_found(id.clone());
+    }
+    let mut core_state =
+        CoreState::existing(id.clone(), proto_to_core_value_map(&state.attributes));
+    if let Some(ref ident) = state.identifier {
+        core_state = core_state.with_identifier(ident);
+    }
+    core_state
+}
+
+pub fn proto_to_core_create_outcome(
+    outcome: &proto::CreateOutcome,
+    id: &CoreResourceId,
+) -> CoreCreateOutcome {
+    match outcome {
+        proto::CreateOutcome::Success { state } => CoreCreateOutcome::Success {
+            state: proto_to_core_state(state, id),
+        },
+        proto::CreateOutcome::PartialSuccess { state, diagnostic } => {
+            CoreCreateOutcome::partial_success(
+                proto_to_core_state(state, id),
+                diagnostic.reason.clone(),
+                diagnostic.missing_attributes.clone(),
+            )
+        }
+    }
+}
+
+pub fn proto_to_core_update_outcome(
+    outcome: &proto::UpdateOutcome,
+    id: &CoreResourceId,
+) -> CoreUpdateOutcome {
+    match outcome {
+        proto::UpdateOutcome::Success { state } => CoreUpdateOutcome::Success {
+            state: proto_to_core_state(state, id),
+        },
+        proto::UpdateOutcome::PartialSuccess { state, diagnostic } => {
+            CoreUpdateOutcome::partial_success(
+                proto_to_core_state(state, id),
+                diagnostic.reason.clone(),
+                diagnostic.missing_attributes.clone(),
+            )
+        }
+    }
+}
+
+// -- Resource --
+
+pub fn core_to_proto_directives(directives: &Directives) -> proto::Directives {
+    proto::Directives {
+        force_delete: directives.force_delete,
+        create_before_destroy: directives.create_before_destroy,
+        prevent_destroy: directives.prevent_destroy,
+    }
+}
+
+pub fn core_to_proto_resource(
+    resource: &CoreResource,
+) -> Result<proto::Resource, SerializationError> {
+    Ok(proto::Resource {
+        id: core_to_proto_resource_id(&resource.id),
+        attributes: core_to_proto_value_map(&resource.resolved_attributes())?,
+        directives: core_to_proto_directives(&resource.directives),
+    })
+}
+
+pub fn core_data_source_to_proto_resource(
+    data_source: &CoreDataSource,
+) -> Result<proto::Resource, SerializationError> {
+    Ok(proto::Resource {
+        id: core_to_proto_resource_id(&data_source.id),
+        attributes: core_to_proto_value_map(&data_source.attributes)?,
+        directives: core_to_proto_directives(&data_source.directives),
+    })
+}
+
+// -- Requests --
+
+pub fn core_to_proto_create_request(
+    request: &CoreCreateRequest,
+) -> Result<proto::CreateRequest, SerializationError> {
+    Ok(proto::CreateRequest {
+        resource: core_to_proto_resource(request.resource.as_resource())?,
+        timeout_secs: request.timeout.map(|t| t.as_secs()),
+        client_token: request.client_token.clone(),
+    })
+}
+
+pub fn core_to_proto_update_request(
+    request: &CoreUpdateRequest,
+) -> Result<proto::UpdateRequest, SerializationError> {
+    let ops = request
+        .patch
+        .ops
+        .iter()
+        .map(core_to_proto_patch_op)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(proto::UpdateRequest {
+        from: core_to_proto_state(&request.from)?,
+        patch: proto::UpdatePatch { ops },
+        timeout_secs: request.timeout.map(|t| t.as_secs()),
+    })
+}
+
+pub fn core_to_proto_patch_op(op: &CorePatchOp) -> Result<proto::PatchOp, SerializationError> {
+    let value = match &op.value {
+        Some(v) => Some(core_to_proto_value(v)?),
+        None => None,
+    };
+    Ok(proto::PatchOp {
+        kind: match op.kind {
+            CorePatchOpKind::Add => proto::PatchOpKind::Add,
+            CorePatchOpKind::Replace => proto::PatchOpKind::Replace,
+            CorePatchOpKind::Remove => proto::PatchOpKind::Remove,
+        },
+        key: op.key.clone(),
+        value,
+    })
+}
+
+pub fn core_to_proto_delete_request(request: &CoreDeleteRequest) -> proto::DeleteRequest {
+    proto::DeleteRequest {
+        directives: core_to_proto_directives(&request.directives),
+        timeout_secs: request.timeout.map(|t| t.as_secs()),
+        force_dependencies: request.force_dependencies,
+    }
+}
+
+pub fn proto_to_core_delete_blocker(blocker: proto::DeleteBlocker) -> CoreDeleteBlocker {
+    CoreDeleteBlocker {
+        kind: blocker.kind,
+        identifier: blocker.identifier,
+        description: blocker.description,
+        removable: blocker.removable,
+    }
+}
+
+pub fn proto_to_core_resource_diagnostic(
+    diagnostic: proto::ResourceDiagnostic,
+) -> CoreResourceDiagnostic {
+    CoreResourceDiagnostic {
+        severity: match diagnostic.severity {
+            proto::DiagnosticSeverity::Error => CoreDiagnosticSeverity::Error,
+            proto::DiagnosticSeverity::Warning => CoreDiagnosticSeverity::Warning,
+        },
+        message: diagnostic.message,
+        attribute: diagnostic.attribute,
+    }
+}
+
+pub fn proto_to_core_operation_metrics(metrics: proto::OperationMetrics) -> CoreOperationMetrics {
+    CoreOperationMetrics {
+        api_calls: metrics.api_calls,
+        api_latency: std::time::Duration::from_millis(metrics.api_latency_ms),
+        polls: metrics.polls,
+        retries: metrics.retries,
+    }
+}
+
+pub fn proto_to_core_capabilities(
+    capabilities: proto::ProviderCapabilities,
+) -> CoreProviderCapabilities {
+    CoreProviderCapabilities {
+        replace_only_types: capabilities.replace_only_types.into_iter().collect(),
+        supports_import: capabilities.supports_import,
+        supports_tagging: capabilities.supports_tagging,
+        // A cap of 0 would stall apply; treat it as no cap.
+        max_parallel_ops: capabilities
+            .max_parallel_ops
+            .and_then(|n| std::num::NonZeroUsize::new(n as usize)),
+    }
+}
+
+// -- ProviderError --
+
+/// Convert a protocol [`proto::ProviderError`] (carried in a JSON-RPC
+/// error's `data`) into the host-side core error, preserving its kind.
+pub fn proto_to_core_provider_error(err: proto::ProviderError) -> CoreProviderError {
+    let ctor: fn(Box<CoreErrorDetail>) -> CoreProviderError = match err.kind {
+        proto::ProviderErrorKind::InvalidInput => CoreProviderError::InvalidInput,
+        proto::ProviderErrorKind::ApiError => CoreProviderError::ApiError,
+        proto::ProviderErrorKind::NotFound => CoreProviderError::NotFound,
+        proto::ProviderErrorKind::Timeout => CoreProviderError::Timeout,
+        proto::ProviderErrorKind::AlreadyExists => CoreProviderError::AlreadyExists,
+        proto::ProviderErrorKind::AmbiguousMatch => CoreProviderError::AmbiguousMatch,
+        proto::ProviderErrorKind::Credentials => CoreProviderError::Credentials,
+        proto::ProviderErrorKind::Internal => CoreProviderError::Internal,
+    };
+    ctor(Box::new(CoreErrorDetail {
+        message: err.message,
+        resource_id: err
+            .resource_id
+            .map(|id| Box::new(proto_to_core_resource_id(&id))),
+        cause: err
+            .cause
+            .map(|s| Box::new(FlattenedCause(s)) as Box<dyn std::error::Error + Send + Sync>),
+        provider_name: err.provider_name,
+        operation: err.operation,
+        status: err.status,
+        code: err.code,
+        request_id: err.request_id,
+        candidates: err.candidates,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use carina_core::resource::{ConcreteValue, DeferredValue};
+
+    #[test]
+    fn value_round_trips_through_protocol() {
+        let value = CoreValue::Concrete(ConcreteValue::Map(
+            [(
+                "tags".to_string(),
+                CoreValue::Concrete(ConcreteValue::List(vec![
+                    CoreValue::Concrete(ConcreteValue::String("a".to_string())),
+                    CoreValue::Concrete(ConcreteValue::Int(2)),
+                ])),
+            )]
+            .into_iter()
+            .collect(),
+        ));
+        let wire = core_to_proto_value(&value).unwrap();
+        assert_eq!(proto_to_core_value(&wire), value);
+    }
+
+    #[test]
+    fn unresolved_values_are_rejected() {
+        let unknown = CoreValue::Deferred(DeferredValue::BindingRef {
+            binding: "vpc".to_string(),
+        });
+        assert!(core_to_proto_value(&unknown).is_err());
+    }
+
+    #[test]
+    fn provider_error_keeps_kind_and_metadata() {
+        let err = proto_to_core_provider_error(proto::ProviderError {
+            kind: proto::ProviderErrorKind::NotFound,
+            message: "no such bucket".to_string(),
+            resource_id: None,
+            cause: Some("404".to_string()),
+            provider_name: Some("native".to_string()),
+            operation: Some("s3.HeadBucket".to_string()),
+            status: Some(404),
+            code: None,
+            request_id: None,
+            candidates: Vec::new(),
+        });
+        assert!(matches!(err, CoreProviderError::NotFound(_)));
+        assert_eq!(err.detail().status, Some(404));
+        assert_eq!(err.detail().operation.as_deref(), Some("s3.HeadBucket"));
+    }
+}
@@ -0,0 +1,1075 @@
+//! ProcessProviderFactory runs a native provider binary out of process and
+//! implements ProviderFactory over JSON-RPC on its stdin/stdout.
+//!
+//! This is the path for providers that cannot compile to a WASM component
+//! (e.g. heavy native SDK dependencies). The binary is built with
+//! `carina_plugin_sdk::run`, which announces itself with a `ready`
+//! notification carrying its protocol version and then serves one
+//! request per line. Each provider instance is its own child process, so
+//! a provider binary can be versioned and released independently of the
+//! host as long as its protocol version stays within
+//! [`MIN_SUPPORTED_PROTOCOL_VERSION`]..=[`PROTOCOL_VERSION`].
+//!
+//! Plan-time normalization hooks are not forwarded yet; process providers
+//! get the [`NoopNormalizer`](carina_core::provider::NoopNormalizer).
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use carina_core::cost::MonthlyCost;
+use carina_core::effect::PlanOp;
+use carina_core::provider::{
+    BoxFuture, CreateOutcome, CreateRequest, DeleteBlocker, DeleteRequest, ListRequest,
+    OperationMetrics, Provider, ProviderCapabilities, ProviderError, ProviderFactory,
+    ProviderResult, ReadRequest, ResourceDiagnostic, UpdateOutcome, UpdateRequest,
+};
+use carina_core::resource::{DataSource, ResolvedResource, Resource, ResourceId, State, Value};
+use carina_core::schema::ResourceSchema;
+use carina_core::value::SerializationError;
+use carina_provider_protocol::jsonrpc::{Notification, Request, Response, RpcError};
+use carina_provider_protocol::types as proto;
+use carina_provider_protocol::{MIN_SUPPORTED_PROTOCOL_VERSION, PROTOCOL_VERSION, methods};
+
+use crate::process_convert;
+use crate::wasm_convert;
+
+/// How long a freshly spawned binary has to print its `ready` line.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Budget for a health-check round trip.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Budget for a single provider call. Sized like the WASM host's hard
+/// backstop: long enough for the slowest legitimate single-call waiter,
+/// short enough that a wedged binary becomes a bounded error.
+const PROCESS_OPERATION_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+
+/// Headroom past a user-configured operation timeout, so the provider's
+/// own timeout error reaches the user before the host stops the process.
+const USER_TIMEOUT_GRACE: Duration = Duration::from_secs(60);
+
+/// Budget for a call that carries a user-configured `timeouts` value.
+fn operation_budget(requested: Option<Duration>) -> Duration {
+    requested.map_or(PROCESS_OPERATION_TIMEOUT, |t| {
+        PROCESS_OPERATION_TIMEOUT.max(t + USER_TIMEOUT_GRACE)
+    })
+}
+
+/// JSON-RPC "method not found"; an older SDK answers this for methods it
+/// predates.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Failure of one JSON-RPC call to a provider process.
+#[derive(Debug)]
+enum CallError {
+    /// The provider answered with a JSON-RPC error.
+    Rpc(RpcError),
+    /// The pipe broke, the process exited, or a message was malformed.
+    Transport(String),
+    /// No answer within the call's budget; the process has been killed.
+    Timeout(Duration),
+}
+
+impl CallError {
+    fn into_provider_error(self, operation: &str) -> ProviderError {
+        match self {
+            CallError::Rpc(err) => match err
+                .data
+                .and_then(|data| serde_json::from_value::<proto::ProviderError>(data).ok())
+            {
+                Some(detail) => process_convert::proto_to_core_provider_error(detail),
+                None => ProviderError::api_error(err.message),
+            },
+            CallError::Transport(msg) => {
+                ProviderError::internal(format!("provider process failed in {operation}: {msg}"))
+            }
+            CallError::Timeout(budget) => ProviderError::timeout(format!(
+                "provider process did not answer {operation} within {}s; the process was stopped",
+                budget.as_secs()
+            )),
+        }
+    }
+
+    fn into_message(self, operation: &str) -> String {
+        match self {
+            CallError::Rpc(err) => err.message,
+            other => other.into_provider_error(operation).to_string(),
+        }
+    }
+}
+
+/// Pipe ends of a provider process. Held by whichever call is in flight;
+/// the SDK server answers one request at a time.
+struct Channel {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Channel {
+    fn call(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, CallError> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let line = serde_json::to_string(&Request::new(id, method, params))
+            .map_err(|e| CallError::Transport(format!("failed to encode request: {e}")))?;
+        writeln!(self.stdin, "{line}")
+            .and_then(|()| self.stdin.flush())
+            .map_err(|e| CallError::Transport(format!("failed to write request: {e}")))?;
+
+        loop {
+            let line = read_line(&mut self.stdout)?;
+            let response: Response = match serde_json::from_str(&line) {
+                Ok(r) => r,
+                Err(_) => {
+                    // Anything else on stdout (a stray print from the
+                    // provider) is not part of the protocol.
+                    log::warn!("provider process wrote a non-JSON-RPC line: {line}");
+                    continue;
+                }
+            };
+            if response.id != id {
+                log::warn!(
+                    "provider process answered request {} while {id} was pending",
+                    response.id
+                );
+                continue;
+            }
+            if let Some(err) = response.error {
+                return Err(CallError::Rpc(err));
+            }
+            return Ok(response.result.unwrap_or(serde_json::Value::Null));
+        }
+    }
+}
+
+/// Read the next non-blank line, treating EOF as the process going away.
+fn read_line(stdout: &mut impl BufRead) -> Result<String, CallError> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match stdout.read_line(&mut line) {
+            Ok(0) => {
+                return Err(CallError::Transport(
+                    "provider process closed its output (exited?)".to_string(),
+                ));
+            }
+            Ok(_) if line.trim().is_empty() => continue,
+            Ok(_) => return Ok(line.trim().to_string()),
+            Err(e) => {
+                return Err(CallError::Transport(format!(
+                    "failed to read response: {e}"
+                )));
+            }
+        }
+    }
+}
+
+/// Parse the `ready` notification and return the protocol version it
+/// announces. A provider predating the version field announces 0.
+fn parse_ready(line: &str) -> Result<u32, String> {
+    let notification: Notification = serde_json::from_str(line)
+        .map_err(|e| format!("expected a `ready` notification, got '{line}': {e}"))?;
+    if notification.method != "ready" {
+        return Err(format!(
+            "expected a `ready` notification, got method '{}'",
+            notification.method
+        ));
+    }
+    let version = notification
+        .params
+        .as_ref()
+        .and_then(|p| p.get("protocol_version"))
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    u32::try_from(version).map_err(|_| format!("invalid protocol version {version}"))
+}
+
+/// Accept `version` if this host can speak it. Mirrors
+/// [`wasm_convert::check_protocol_version`] for process providers.
+fn negotiate_protocol_version(binary: &Path, version: u32) -> Result<u32, String> {
+    if version < MIN_SUPPORTED_PROTOCOL_VERSION {
+        return Err(format!(
+            "provider '{}' speaks protocol version {version} but this host requires at \
+             least version {MIN_SUPPORTED_PROTOCOL_VERSION}; rebuild the provider against \
+             the current carina protocol",
+            binary.display()
+        ));
+    }
+    if version > PROTOCOL_VERSION {
+        return Err(format!(
+            "provider '{}' speaks protocol version {version} but this host supports up \
+             to version {PROTOCOL_VERSION}; upgrade carina to load this provider",
+            binary.display()
+        ));
+    }
+    Ok(version)
+}
+
+/// A connection to one running provider process.
+///
+/// Calls take turns on the channel and run on a blocking thread. A call's
+/// budget starts once it holds the channel, so waiting behind other calls
+/// under parallel apply does not count against it; when a call overruns
+/// its budget the process is killed, which unblocks the reader and makes
+/// every later call fail fast instead of hanging behind it.
+pub struct ProviderClient {
+    binary_path: PathBuf,
+    child: Arc<Mutex<Child>>,
+    channel: Arc<tokio::sync::Mutex<Channel>>,
+    protocol_version: u32,
+}
+
+impl ProviderClient {
+    /// Spawn `binary_path` and complete the version handshake.
+    pub async fn spawn(binary_path: &Path) -> Result<Self, String> {
+        let mut child = Command::new(binary_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| format!("failed to start '{}': {e}", binary_path.display()))?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let child = Arc::new(Mutex::new(child));
+
+        let handshake = tokio::task::spawn_blocking(move || {
+            read_line(&mut stdout)
+                .map_err(|e| e.into_message("handshake"))
+                .map(|line| (line, stdout))
+        });
+        let (line, stdout) = match tokio::time::timeout(HANDSHAKE_TIMEOUT, handshake).await {
+            Ok(joined) => joined.map_err(|e| format!("handshake task failed: {e}"))??,
+            Err(_elapsed) => {
+                kill(&child);
+                return Err(format!(
+                    "'{}' did not announce itself within {}s",
+                    binary_path.display(),
+                    HANDSHAKE_TIMEOUT.as_secs()
+                ));
+            }
+        };
+        let protocol_version =
+            match parse_ready(&line).and_then(|v| negotiate_protocol_version(binary_path, v)) {
+                Ok(v) => v,
+                Err(e) => {
+                    kill(&child);
+                    return Err(e);
+                }
+            };
+
+        Ok(Self {
+            binary_path: binary_path.to_path_buf(),
+            child,
+            channel: Arc::new(tokio::sync::Mutex::new(Channel {
+                stdin,
+                stdout,
+                next_id: 0,
+            })),
+            protocol_version,
+        })
+    }
+
+    /// Protocol version agreed on during the handshake.
+    pub fn protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
+
+    /// Check that the process is still running and answering requests.
+    pub async fn health_check(&self) -> Result<(), String> {
+        let exited = self
+            .child
+            .lock()
+            .expect("child lock poisoned")
+            .try_wait()
+            .map_err(|e| format!("failed to query provider process: {e}"))?;
+        if let Some(status) = exited {
+            return Err(format!(
+                "provider process '{}' has exited ({status})",
+                self.binary_path.display()
+            ));
+        }
+        self.call::<_, methods::ProviderInfoResult>(
+            "provider_info",
+            serde_json::json!({}),
+            HEALTH_CHECK_TIMEOUT,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| e.into_message("health check"))
+    }
+
+    async fn call<P: Serialize, R: DeserializeOwned + Send + 'static>(
+        &self,
+        method: &'static str,
+        params: P,
+        budget: Duration,
+    ) -> Result<R, CallError> {
+        let params = serde_json::to_value(params)
+            .map_err(|e| CallError::Transport(format!("failed to encode {method} params: {e}")))?;
+        let mut channel = Arc::clone(&self.channel).lock_owned().await;
+        let task = tokio::task::spawn_blocking(move || {
+            let result = channel.call(method, params)?;
+            serde_json::from_value(result)
+                .map_err(|e| CallError::Transport(format!("malformed {method} result: {e}")))
+        });
+        match tokio::time::timeout(budget, task).await {
+            Ok(joined) => {
+                joined.map_err(|e| CallError::Transport(format!("{method} task failed: {e}")))?
+            }
+            Err(_elapsed) => {
+                kill(&self.child);
+                Err(CallError::Timeout(budget))
+            }
+        }
+    }
+
+    /// Blocking variant of [`call`](Self::call) for the synchronous
+    /// `ProviderFactory` methods.
+    fn call_blocking<P: Serialize, R: DeserializeOwned + Send + 'static>(
+        &self,
+        method: &'static str,
+        params: P,
+    ) -> Result<R, CallError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(self.call(
+                method,
+                params,
+                PROCESS_OPERATION_TIMEOUT,
+            ))
+        })
+    }
+}
+
+impl Drop for ProviderClient {
+    fn drop(&mut self) {
+        // The SDK loop also exits on stdin EOF, but a provider stuck in a
+        // call would never read it; kill so no child outlives the host.
+        kill(&self.child);
+    }
+}
+
+fn kill(child: &Mutex<Child>) {
+    let mut child = child.lock().expect("child lock poisoned");
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn early_provider_err<T: 'static>(e: SerializationError) -> BoxFuture<'static, ProviderResult<T>> {
+    let msg = e.to_string();
+    Box::pin(async move { Err(ProviderError::internal(msg)) })
+}
+
+// -- ProcessProviderFactory --
+
+pub struct ProcessProviderFactory {
+    binary_path: PathBuf,
+    name: String,
+    display_name: String,
+    version: String,
+    type_family: Option<String>,
+    schemas: Vec<ResourceSchema>,
+    schemas_json: String,
+    provider_config_types: HashMap<String, carina_core::schema::AttributeType>,
+    /// Unconfigured instance that answers metadata and `validate_config`.
+    metadata: ProviderClient,
+}
+
+impl ProcessProviderFactory {
+    /// Start the provider binary at `binary_path` and load its metadata.
+    pub async fn new(binary_path: PathBuf) -> Result<Self, String> {
+        let metadata = ProviderClient::spawn(&binary_path).await?;
+
+        let info: methods::ProviderInfoResult = metadata
+            .call("provider_info", serde_json::json!({}), HANDSHAKE_TIMEOUT)
+            .await
+            .map_err(|e| e.into_message("provider_info"))?;
+
+        let schemas: serde_json::Value = metadata
+            .call("schemas", serde_json::json!({}), HANDSHAKE_TIMEOUT)
+            .await
+            .map_err(|e| e.into_message("schemas"))?;
+        let schemas_json = schemas
+            .get("schemas")
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "[]".to_string());
+        let decoded = wasm_convert::json_to_schemas(&schemas_json)
+            .map_err(|e| format!("provider '{}': {e}", info.info.name))?;
+
+        let provider_config_types = match metadata
+            .call::<_, serde_json::Value>(
+                "provider_config_attribute_types",
+                serde_json::json!({}),
+                HANDSHAKE_TIMEOUT,
+            )
+            .await
+        {
+            Ok(result) => {
+                let types = result.get("types").map(|t| t.to_string());
+                wasm_convert::json_to_attribute_types(types.as_deref().unwrap_or("{}"))
+                    .map_err(|e| format!("provider '{}': {e}", info.info.name))?
+            }
+            // Built against an SDK that predates the method: the host
+            // skips type-level config validation, as it would for a
+            // provider that declares no config attributes.
+            Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => HashMap::new(),
+            Err(e) => return Err(e.into_message("provider_config_attribute_types")),
+        };
+
+        Ok(Self {
+            binary_path,
+            name: info.info.name,
+            display_name: info.info.display_name,
+            version: info.info.version,
+            type_family: info.info.type_family,
+            schemas: decoded,
+            schemas_json,
+            provider_config_types,
+            metadata,
+        })
+    }
+
+    /// Path of the provider binary.
+    pub fn binary_path(&self) -> &Path {
+        &self.binary_path
+    }
+
+    /// Provider version reported by the binary.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Raw schemas JSON, for lock-file schema verification.
+    pub fn schemas_json(&self) -> &str {
+        &self.schemas_json
+    }
+
+    /// Check that the metadata process is still healthy.
+    pub async fn health_check(&self) -> Result<(), String> {
+        self.metadata.health_check().await
+    }
+
+    /// Verify that this provider's version satisfies the given constraint.
+    pub fn verify_version(&self, constraint_raw: &str) -> Result<(), String> {
+        let req = semver::VersionReq::parse(constraint_raw)
+            .map_err(|e| format!("Invalid version constraint '{}': {}", constraint_raw, e))?;
+        let actual = semver::Version::parse(&self.version).map_err(|e| {
+            format!(
+                "Provider '{}' reports invalid version '{}': {}",
+                self.name, self.version, e
+            )
+        })?;
+        if !req.matches(&actual) {
+            return Err(format!(
+                "Provider '{}' version {} does not satisfy constraint '{}'",
+                self.name, actual, constraint_raw
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ProviderFactory for ProcessProviderFactory {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn type_family(&self) -> Option<&str> {
+        self.type_family.as_deref()
+    }
+
+    fn version(&self) -> Option<&str> {
+        Some(&self.version)
+    }
+
+    fn provider_config_attribute_types(
+        &self,
+    ) -> HashMap<String, carina_core::schema::AttributeType> {
+        self.provider_config_types.clone()
+    }
+
+    fn validate_config(&self, attributes: &IndexMap<String, Value>) -> Result<(), String> {
+        let attributes =
+            process_convert::core_to_proto_value_map(attributes).map_err(|e| e.to_string())?;
+        let result: methods::ValidateConfigResult = self
+            .metadata
+            .call_blocking(
+                "validate_config",
+                methods::ValidateConfigParams { attributes },
+            )
+            .map_err(|e| e.into_message("validate_config"))?;
+        match result.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn extract_region(&self, attributes: &IndexMap<String, Value>) -> String {
+        carina_core::utils::extract_region_from_attrs(attributes, "ap-northeast-1")
+    }
+
+    fn create_provider(
+        &self,
+        _binding: Option<&str>,
+        attributes: &IndexMap<String, Value>,
+    ) -> BoxFuture<'_, ProviderResult<Box<dyn Provider>>> {
+        let attributes = match process_convert::core_to_proto_value_map(attributes) {
+            Ok(v) => v,
+            Err(e) => return early_provider_err(e),
+        };
+        Box::pin(async move {
+            // Every instance is its own process, so named instances never
+            // share configuration.
+            let client = ProviderClient::spawn(&self.binary_path)
+                .await
+                .map_err(ProviderError::internal)?;
+            // Surface the provider's initialize error verbatim; it is the
+            // user-facing message (see #2407).
+            client
+                .call::<_, methods::InitializeResult>(
+                    "initialize",
+                    methods::InitializeParams { attributes },
+                    PROCESS_OPERATION_TIMEOUT,
+                )
+                .await
+                .map_err(|e| ProviderError::invalid_input(e.into_message("initialize")))?;
+            let capabilities = match client
+                .call::<_, methods::CapabilitiesResult>(
+                    "capabilities",
+                    serde_json::json!({}),
+                    HANDSHAKE_TIMEOUT,
+                )
+                .await
+            {
+                Ok(result) => process_convert::proto_to_core_capabilities(result.capabilities),
+                // Binaries built before the method existed declare no limits.
+                Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => {
+                    ProviderCapabilities::default()
+                }
+                Err(e) => return Err(e.into_provider_error("capabilities")),
+            };
+            Ok(Box::new(ProcessProvider {
+                client,
+                name: self.name.clone(),
+                capabilities,
+                operation_metrics: Mutex::new(HashMap::new()),
+            }) as Box<dyn Provider>)
+        })
+    }
+
+    fn schemas(&self) -> Vec<ResourceSchema> {
+        self.schemas.clone()
+    }
+}
+
+// -- ProcessProvider --
+
+pub struct ProcessProvider {
+    client: ProviderClient,
+    name: String,
+    /// Fetched once after `initialize`; they do not change while the
+    /// process runs.
+    capabilities: ProviderCapabilities,
+    /// Counters reported with each resource's last create, update or
+    /// delete result.
+    operation_metrics: Mutex<HashMap<ResourceId, OperationMetrics>>,
+}
+
+impl ProcessProvider {
+    /// Check that the provider process is still healthy.
+    pub async fn health_check(&self) -> Result<(), String> {
+        self.client.health_check().await
+    }
+
+    fn record_metrics(&self, id: &ResourceId, metrics: Option<proto::OperationMetrics>) {
+        let mut recorded = self.operation_metrics.lock().unwrap();
+        match metrics {
+            Some(m) => {
+                recorded.insert(
+                    id.clone(),
+                    process_convert::proto_to_core_operation_metrics(m),
+                );
+            }
+            None => {
+                recorded.remove(id);
+            }
+        }
+    }
+}
+
+impl Provider for ProcessProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn read(
+        &self,
+        id: &ResourceId,
+        identifier: Option<&str>,
+        _request: ReadRequest,
+    ) -> BoxFuture<'_, ProviderResult<State>> {
+        let params = methods::ReadParams {
+            id: process_convert::core_to_proto_resource_id(id),
+            identifier: identifier.map(|s| s.to_string()),
+            request: proto::ReadRequest,
+        };
+        let id = id.clone();
+        Box::pin(async move {
+            let result: methods::ReadResult = self
+                .client
+                .call("read", params, PROCESS_OPERATION_TIMEOUT)
+                .await
+                .map_err(|e| e.into_provider_error("read"))?;
+            Ok(process_convert::proto_to_core_state(&result.state, &id))
+        })
+    }
+
+    fn read_data_source(&self, resource: &DataSource) -> BoxFuture<'_, ProviderResult<State>> {
+        let params = match process_convert::core_data_source_to_proto_resource(resource) {
+            Ok(resource) => methods::ReadDataSourceParams { resource },
+            Err(e) => return early_provider_err(e),
+        };
+        let id = resource.id.clone();
+        Box::pin(async move {
+            let result: methods::ReadDataSourceResult = self
+                .client
+                .call("read_data_source", params, PROCESS_OPERATION_TIMEOUT)
+                .await
+                .map_err(|e| e.into_provider_error("read_data_source"))?;
+            Ok(process_convert::proto_to_core_state(&result.state, &id))
+        })
+    }
+
+    fn create(
+        &self,
+        id: &ResourceId,
+        request: CreateRequest,
+    ) -> BoxFuture<'_, ProviderResult<CreateOutcome>> {
+        let budget = operation_budget(request.timeout);
+        let params = match process_convert::core_to_proto_create_request(&request) {
+            Ok(request) => methods::CreateParams {
+                id: process_convert::core_to_proto_resource_id(id),
+                request,
+            },
+            Err(e) => return early_provider_err(e),
+        };
+        let id = id.clone();
+        Box::pin(async move {
+            let result: methods::CreateResult = self
+                .client
+                .call("create", params, budget)
+                .await
+                .map_err(|e| e.into_provider_error("create"))?;
+            self.record_metrics(&id, result.metrics);
+            Ok(process_convert::proto_to_core_create_outcome(
+                &result.outcome,
+                &id,
+            ))
+        })
+    }
+
+    fn update(
+        &self,
+        id: &ResourceId,
+        identifier: &str,
+        request: UpdateRequest,
+    ) -> BoxFuture<'_, ProviderResult<UpdateOutcome>> {
+        let budget = operation_budget(request.timeout);
+        let params = match process_convert::core_to_proto_update_request(&request) {
+            Ok(request) => methods::UpdateParams {
+                id: process_convert::core_to_proto_resource_id(id),
+                identifier: identifier.to_string(),
+                request,
+            },
+            Err(e) => return early_provider_err(e),
+        };
+        let id = id.clone();
+        Box::pin(async move {
+            let result: methods::UpdateResult = self
+                .client
+                .call("update", params, budget)
+                .await
+                .map_err(|e| e.into_provider_error("update"))?;
+            self.record_metrics(&id, result.metrics);
+            Ok(process_convert::proto_to_core_update_outcome(
+                &result.outcome,
+                &id,
+            ))
+        })
+    }
+
+    fn delete(
+        &self,
+        id: &ResourceId,
+        identifier: &str,
+        request: DeleteRequest,
+    ) -> BoxFuture<'_, ProviderResult<()>> {
+        let budget = operation_budget(request.timeout);
+        let params = methods::DeleteParams {
+            id: process_convert::core_to_proto_resource_id(id),
+            identifier: identifier.to_string(),
+            request: process_convert::core_to_proto_delete_request(&request),
+        };
+        let id = id.clone();
+        Box::pin(async move {
+            let result: methods::DeleteResult = self
+                .client
+                .call("delete", params, budget)
+                .await
+                .map_err(|e| e.into_provider_error("delete"))?;
+            self.record_metrics(&id, result.metrics);
+            Ok(())
+        })
+    }
+
+    fn required_permissions(&self, _id: &ResourceId, _op: PlanOp) -> Vec<String> {
+        // Not part of the JSON-RPC protocol; process providers declare none.
+        Vec::new()
+    }
+
+    fn validate_resource(&self, _resource: &Resource) -> Vec<ResourceDiagnostic> {
+        // Not part of the JSON-RPC protocol; process providers check
+        // resources through `simulate`.
+        Vec::new()
+    }
+
+    fn operation_metrics(&self, id: &ResourceId) -> Option<OperationMetrics> {
+        self.operation_metrics.lock().unwrap().get(id).copied()
+    }
+
+    fn capabilities(&self, _id: &ResourceId) -> ProviderCapabilities {
+        self.capabilities.clone()
+    }
+
+    fn credential_expiry(&self) -> BoxFuture<'_, Option<SystemTime>> {
+        Box::pin(async move {
+            // Only a pre-apply warning hangs on this, so any failure,
+            // including binaries built before the method existed, reads
+            // as "does not expire".
+            let result: methods::CredentialExpiryResult = self
+                .client
+                .call(
+                    "credential_expiry",
+                    serde_json::json!({}),
+                    PROCESS_OPERATION_TIMEOUT,
+                )
+                .await
+                .ok()?;
+            result
+                .expires_at
+                .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+        })
+    }
+
+    fn delete_blockers(
+        &self,
+        id: &ResourceId,
+        identifier: &str,
+    ) -> BoxFuture<'_, ProviderResult<Vec<DeleteBlocker>>> {
+        let params = methods::DeleteBlockersParams {
+            id: process_convert::core_to_proto_resource_id(id),
+            identifier: identifier.to_string(),
+        };
+        Box::pin(async move {
+            let result: methods::DeleteBlockersResult = match self
+                .client
+                .call("delete_blockers", params, PROCESS_OPERATION_TIMEOUT)
+                .await
+            {
+                Ok(result) => result,
+                // Binaries built before the method existed know of none.
+                Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => return Ok(Vec::new()),
+                Err(e) => return Err(e.into_provider_error("delete_blockers")),
+            };
+            Ok(result
+                .blockers
+                .into_iter()
+                .map(process_convert::proto_to_core_delete_blocker)
+                .collect())
+        })
+    }
+
+    fn find_existing(
+        &self,
+        id: &ResourceId,
+        resource: &ResolvedResource,
+    ) -> BoxFuture<'_, ProviderResult<Option<String>>> {
+        let params = match process_convert::core_to_proto_resource(resource.as_resource()) {
+            Ok(resource) => methods::FindExistingParams {
+                id: process_convert::core_to_proto_resource_id(id),
+                resource,
+            },
+            Err(e) => return early_provider_err(e),
+        };
+        Box::pin(async move {
+            match self
+                .client
+                .call::<_, methods::FindExistingResult>(
+                    "find_existing",
+                    params,
+                    PROCESS_OPERATION_TIMEOUT,
+                )
+                .await
+            {
+                Ok(result) => Ok(result.identifier),
+                // Binaries built before the method existed cannot look up.
+                Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => Ok(None),
+                Err(e) => Err(e.into_provider_error("find_existing")),
+            }
+        })
+    }
+
+    fn simulate(
+        &self,
+        resource: &Resource,
+    ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+        let params = match process_convert::core_to_proto_resource(resource) {
+            Ok(resource) => methods::SimulateParams { resource },
+            Err(e) => return early_provider_err(e),
+        };
+        Box::pin(async move {
+            match self
+                .client
+                .call::<_, methods::SimulateResult>("simulate", params, PROCESS_OPERATION_TIMEOUT)
+                .await
+            {
+                Ok(result) => Ok(result
+                    .diagnostics
+                    .into_iter()
+                    .map(process_convert::proto_to_core_resource_diagnostic)
+                    .collect()),
+                // Binaries built before the method existed have nothing to check.
+                Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => Ok(Vec::new()),
+                Err(e) => Err(e.into_provider_error("simulate")),
+            }
+        })
+    }
+
+    fn list_project_resources(
+        &self,
+        request: ListRequest,
+    ) -> BoxFuture<'_, ProviderResult<Option<Vec<State>>>> {
+        let params = methods::ListProjectResourcesParams {
+            project: request.project,
+        };
+        Box::pin(async move {
+            let result: methods::ListProjectResourcesResult = match self
+                .client
+                .call("list_project_resources", params, PROCESS_OPERATION_TIMEOUT)
+                .await
+            {
+                Ok(result) => result,
+                // Binaries built before the method existed cannot list.
+                Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => return Ok(None),
+                Err(e) => return Err(e.into_provider_error("list_project_resources")),
+            };
+            Ok(result.states.map(|states| {
+                states
+                    .iter()
+                    .map(|state| {
+                        let id = process_convert::proto_to_core_resource_id(&state.id);
+                        process_convert::proto_to_core_state(state, &id)
+                    })
+                    .collect()
+            }))
+        })
+    }
+
+    /// An estimate is advisory: a provider that cannot answer leaves the
+    /// resource unpriced rather than failing the plan.
+    fn monthly_cost<'a>(
+        &'a self,
+        id: &'a ResourceId,
+        attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>> {
+        Box::pin(async move {
+            let params = methods::MonthlyCostParams {
+                resource: proto::Resource {
+                    id: process_convert::core_to_proto_resource_id(id),
+                    attributes: process_convert::core_to_proto_value_map(attributes).ok()?,
+                    directives: Default::default(),
+                },
+            };
+            let result: methods::MonthlyCostResult = match self
+                .client
+                .call("monthly_cost", params, PROCESS_OPERATION_TIMEOUT)
+                .await
+            {
+                Ok(result) => result,
+                Err(CallError::Rpc(err)) if err.code == METHOD_NOT_FOUND => return None,
+                Err(e) => {
+                    log::warn!(
+                        "{id}: cost estimate failed: {}",
+                        e.into_provider_error("monthly_cost")
+                    );
+                    return None;
+                }
+            };
+            result.cost.map(|cost| MonthlyCost {
+                usd: cost.usd,
+                basis: cost.basis,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ready_line_announces_protocol_version() {
+        let line = serde_json::to_string(&Notification::ready()).unwrap();
+        assert_eq!(parse_ready(&line), Ok(PROTOCOL_VERSION));
+
+        let legacy = r#"{"jsonrpc":"2.0","method":"ready"}"#;
+        assert_eq!(parse_ready(legacy), Ok(0));
+
+        assert!(parse_ready(r#"{"jsonrpc":"2.0","method":"log"}"#).is_err());
+        assert!(parse_ready("starting up...").is_err());
+    }
+
+    #[test]
+    fn negotiation_accepts_only_the_supported_range() {
+        let bin = Path::new("carina-provider-native");
+        assert_eq!(
+            negotiate_protocol_version(bin, PROTOCOL_VERSION),
+            Ok(PROTOCOL_VERSION)
+        );
+        let too_old = negotiate_protocol_version(bin, 0).unwrap_err();
+        assert!(too_old.contains("rebuild the provider"), "{too_old}");
+        let too_new = negotiate_protocol_version(bin, PROTOCOL_VERSION + 1).unwrap_err();
+        assert!(too_new.contains("upgrade carina"), "{too_new}");
+    }
+
+    /// Write a shell script standing in for a provider binary: it
+    /// announces `protocol_version`, then answers every request with a
+    /// provider_info result after a line of stray output.
+    #[cfg(unix)]
+    fn fake_provider(dir: &Path, protocol_version: u32) -> PathBuf {
+        fake_provider_with_delay(dir, protocol_version, 0)
+    }
+
+    /// [`fake_provider`] that sleeps `delay_secs` before each answer.
+    #[cfg(unix)]
+    fn fake_provider_with_delay(dir: &Path, protocol_version: u32, delay_secs: u32) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join("carina-provider-fake");
+        let script = format!(
+            r#"#!/bin/sh
+echo '{{"jsonrpc":"2.0","method":"ready","params":{{"protocol_version":{protocol_version}}}}}'
+while read -r line; do
+  id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+  echo "debug: handling $id"
+  sleep {delay_secs}
+  echo '{{"jsonrpc":"2.0","id":'"$id"',"result":{{"info":{{"name":"fake","display_name":"Fake","version":"0.1.0"}}}}}}'
+done
+"#
+        );
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn client_handshakes_and_passes_health_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = ProviderClient::spawn(&fake_provider(dir.path(), PROTOCOL_VERSION))
+            .await
+            .unwrap();
+        assert_eq!(client.protocol_version(), PROTOCOL_VERSION);
+        client.health_check().await.unwrap();
+        client.health_check().await.unwrap();
+
+        kill(&client.child);
+        let err = client.health_check().await.unwrap_err();
+        assert!(err.contains("has exited"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn time_spent_waiting_for_the_channel_does_not_count_against_the_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let client =
+            ProviderClient::spawn(&fake_provider_with_delay(dir.path(), PROTOCOL_VERSION, 1))
+                .await
+                .unwrap();
+        // Each answer takes about a second, so the second call queues for
+        // about a second before its own round trip starts.
+        let budget = Duration::from_millis(1800);
+        let info = || {
+            client.call::<_, methods::ProviderInfoResult>(
+                "provider_info",
+                serde_json::json!({}),
+                budget,
+            )
+        };
+        let (first, second) = tokio::join!(info(), info());
+        first.unwrap();
+        second.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn client_rejects_an_unsupported_protocol_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = ProviderClient::spawn(&fake_provider(dir.path(), PROTOCOL_VERSION + 1))
+            .await
+            .err()
+            .unwrap();
+        assert!(err.contains("upgrade carina"), "{err}");
+    }
+
+    #[test]
+    fn user_timeouts_extend_the_call_budget() {
+        assert_eq!(operation_budget(None), PROCESS_OPERATION_TIMEOUT);
+        assert_eq!(
+            operation_budget(Some(Duration::from_secs(60))),
+            PROCESS_OPERATION_TIMEOUT
+        );
+        let forty_minutes = Duration::from_secs(40 * 60);
+        assert_eq!(
+            operation_budget(Some(forty_minutes)),
+            forty_minutes + USER_TIMEOUT_GRACE
+        );
+    }
+
+    #[test]
+    fn rpc_error_data_keeps_the_provider_error_kind() {
+        let err = CallError::Rpc(RpcError {
+            code: -1,
+            message: "throttled".to_string(),
+            data: Some(serde_json::json!({"kind": "timeout", "message": "throttled"})),
+        });
+        assert!(matches!(
+            err.into_provider_error("read"),
+            ProviderError::Timeout(_)
+        ));
+
+        let bare = CallError::Rpc(RpcError {
+            code: -1,
+            message: "boom".to_string(),
+            data: None,
+        });
+        assert!(matches!(
+            bare.into_provider_error("read"),
+            ProviderError::ApiError(_)
+        ));
+    }
+}
@@ -17,8 +17,8 @@ use carina_core::resource::{
 };
 use carina_core::schema::{
     AttributeSchema as CoreAttributeSchema, AttributeType as CoreAttributeType,
-    ResourceSchema as CoreResourceSchema, StructField as CoreStructField,
-    UniqueNameSpec as CoreUniqueNameSpec, legacy_validator,
+    AttributeUnit as CoreAttributeUnit, ResourceSchema as CoreResourceSchema,
+    StructField as CoreStructField, UniqueNameSpec as CoreUniqueNameSpec, legacy_validator,
 };
 use carina_core::value::{SerializationContext, SerializationError};
 use carina_core::wait::BindingPattern as CoreBindingPattern;
@@ -120,6 +120,10 @@ pub fn core_to_wit_value(v: &CoreValue) -> Result<wit::Value, SerializationError
         CoreValue::Concrete(ConcreteValue::Duration(d)) => {
             Ok(wit::Value::IntVal(d.as_secs() as i64))
         }
+        // Byte sizes cross as integer bytes, like Duration above. Unit-
+        // annotated attributes are converted to the API's unit before
+        // reaching the provider, so this arm only sees unannotated uses.
+        CoreValue::Concrete(ConcreteValue::ByteSize(n)) => Ok(wit::Value::IntVal(*n as i64)),
         CoreValue::Concrete(ConcreteValue::List(items)) => {
             let json_items: Result<Vec<serde_json::Value>, _> =
                 items.iter().map(core_value_to_json).collect();
@@ -267,7 +271,7 @@ pub fn wit_to_core_binding_pattern(
 /// `Err` for the same set of variants as `core_to_wit_value` — see
 /// that function's doc for the rationale and the strip-and-restore
 /// pass that keeps these arms unreachable in legitimate flows.
-fn core_value_to_json(v: &CoreValue) -> Result<serde_json::Value, SerializationError> {
+pub(crate) fn core_value_to_json(v: &CoreValue) -> Result<serde_json::Value, SerializationError> {
     match v {
         CoreValue::Concrete(ConcreteValue::String(s)) => Ok(serde_json::Value::String(s.clone())),
         CoreValue::Concrete(ConcreteValue::EnumIdentifier(s)) => {
@@ -284,6 +288,9 @@ fn core_value_to_json(v: &CoreValue) -> Result<serde_json::Value, SerializationE
         CoreValue::Concrete(ConcreteValue::Duration(d)) => {
             Ok(serde_json::Value::Number((d.as_secs() as i64).into()))
         }
+        CoreValue::Concrete(ConcreteValue::ByteSize(n)) => {
+            Ok(serde_json::Value::Number((*n).into()))
+        }
         CoreValue::Concrete(ConcreteValue::List(items)) => {
             let arr: Result<Vec<_>, _> = items.iter().map(core_value_to_json).collect();
             Ok(serde_json::Value::Array(arr?))
@@ -343,7 +350,7 @@ fn core_value_to_json(v: &CoreValue) -> Result<serde_json::Value, SerializationE
 }
 
 /// Helper: convert a serde_json::Value to a core Value.
-fn json_to_core_value(v: &serde_json::Value) -> CoreValue {
+pub(crate) fn json_to_core_value(v: &serde_json::Value) -> CoreValue {
     match v {
         serde_json::Value::String(s) => CoreValue::Concrete(ConcreteValue::String(s.clone())),
         serde_json::Value::Number(n) => {
@@ -536,10 +543,49 @@ pub fn core_to_wit_provider_error(err: &CoreProviderError) -> wit::ProviderError
         CoreProviderError::ApiError(_) => wit::ProviderError::ApiError(wit_detail),
         CoreProviderError::NotFound(_) => wit::ProviderError::NotFound(wit_detail),
         CoreProviderError::Timeout(_) => wit::ProviderError::Timeout(wit_detail),
+        // The WIT variant has neither case; they travel as api-errors
+        // tagged with their protocol code.
+        CoreProviderError::AlreadyExists(_) => {
+            kind_coded_api_error(wit_detail, proto::ALREADY_EXISTS_CODE)
+        }
+        CoreProviderError::AmbiguousMatch(_) => {
+            kind_coded_api_error(wit_detail, proto::AMBIGUOUS_MATCH_CODE)
+        }
+        // The provider's own code (e.g. `ExpiredToken`) is kept inside
+        // the wire code rather than replaced.
+        CoreProviderError::Credentials(_) => wit::ProviderError::ApiError(wit::ErrorDetail {
+            code: Some(proto::credentials_wire_code(wit_detail.code.as_deref())),
+            ..wit_detail
+        }),
         CoreProviderError::Internal(_) => wit::ProviderError::Internal(wit_detail),
     }
 }
 
+/// An api-error carrying the provider's own code inside the wire code
+/// for `kind_code`; see [`proto::kind_wire_code`].
+fn kind_coded_api_error(detail: wit::ErrorDetail, kind_code: &str) -> wit::ProviderError {
+    wit::ProviderError::ApiError(wit::ErrorDetail {
+        code: Some(proto::kind_wire_code(kind_code, detail.code.as_deref())),
+        ..detail
+    })
+}
+
+fn has_kind_wire_code(detail: &wit::ErrorDetail, kind_code: &str) -> bool {
+    detail
+        .code
+        .as_deref()
+        .is_some_and(|wire| proto::parse_kind_wire_code(kind_code, wire).is_some())
+}
+
+/// Replace the wire code for `kind_code` with the provider's own code.
+fn take_kind_wire_code(detail: &mut wit::ErrorDetail, kind_code: &str) {
+    detail.code = detail
+        .code
+        .as_deref()
+        .and_then(|wire| proto::parse_kind_wire_code(kind_code, wire))
+        .flatten();
+}
+
 /// Convert a WIT [`wit::ProviderError`] into the host-side core
 /// [`CoreProviderError`]. The variant is preserved exactly; the
 /// `cause` string is rehydrated as an `Option<String>` inside
@@ -550,6 +596,32 @@ pub fn wit_to_core_provider_error(err: wit::ProviderError) -> CoreProviderError
         fn(Box<CoreErrorDetail>) -> CoreProviderError,
     ) = match err {
         wit::ProviderError::InvalidInput(d) => (d, CoreProviderError::InvalidInput),
+        // Kinds the WIT variant lacks arrive as api-errors carrying
+        // their protocol code.
+        wit::ProviderError::ApiError(mut d)
+            if has_kind_wire_code(&d, proto::ALREADY_EXISTS_CODE) =>
+        {
+            take_kind_wire_code(&mut d, proto::ALREADY_EXISTS_CODE);
+            (d, CoreProviderError::AlreadyExists)
+        }
+        wit::ProviderError::ApiError(mut d)
+            if has_kind_wire_code(&d, proto::AMBIGUOUS_MATCH_CODE) =>
+        {
+            take_kind_wire_code(&mut d, proto::AMBIGUOUS_MATCH_CODE);
+            (d, CoreProviderError::AmbiguousMatch)
+        }
+        wit::ProviderError::ApiError(mut d)
+            if d.code
+                .as_deref()
+                .is_some_and(|code| proto::parse_credentials_wire_code(code).is_some()) =>
+        {
+            d.code = d
+                .code
+                .as_deref()
+                .and_then(proto::parse_credentials_wire_code)
+                .flatten();
+            (d, CoreProviderError::Credentials)
+        }
         wit::ProviderError::ApiError(d) => (d, CoreProviderError::ApiError),
         wit::ProviderError::NotFound(d) => (d, CoreProviderError::NotFound),
         wit::ProviderError::Timeout(d) => (d, CoreProviderError::Timeout),
@@ -568,6 +640,8 @@ pub fn wit_to_core_provider_error(err: wit::ProviderError) -> CoreProviderError
         status: detail.status,
         code: detail.code,
         request_id: detail.request_id,
+        // Candidates only survive the boundary in the message.
+        candidates: Vec::new(),
     };
     ctor(Box::new(core_detail))
 }
@@ -578,7 +652,7 @@ pub fn wit_to_core_provider_error(err: wit::ProviderError) -> CoreProviderError
 /// in a minimal `Error` shape so callers reading
 /// `ProviderError::source()` still see a non-empty message.
 #[derive(Debug)]
-struct FlattenedCause(String);
+pub(crate) struct FlattenedCause(pub(crate) String);
 
 impl std::fmt::Display for FlattenedCause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -598,16 +672,19 @@ pub fn core_to_wit_update_request(
     Ok(wit::UpdateRequest {
         current: core_to_wit_state(&request.from)?,
         patch: core_to_wit_update_patch(&request.patch)?,
+        timeout_secs: request.timeout.map(|t| t.as_secs()),
     })
 }
 
 /// Build a [`wit::CreateRequest`] from the host-side core
-/// [`CoreCreateRequest`].
+/// [`CoreCreateRequest`]. `create-request` has no idempotency-token
+/// field, so `client_token` is dropped at the boundary.
 pub fn core_to_wit_create_request(
     request: &CoreCreateRequest,
 ) -> Result<wit::CreateRequest, SerializationError> {
     Ok(wit::CreateRequest {
         res: core_to_wit_resource(request.resource.as_resource())?,
+        timeout_secs: request.timeout.map(|t| t.as_secs()),
     })
 }
 
@@ -625,6 +702,7 @@ pub fn core_to_wit_read_request(_request: &CoreReadRequest) -> wit::ReadRequest
 pub fn core_to_wit_delete_request(request: &CoreDeleteRequest) -> wit::DeleteRequest {
     wit::DeleteRequest {
         directives: core_to_wit_directives(&request.directives),
+        timeout_secs: request.timeout.map(|t| t.as_secs()),
     }
 }
 
@@ -717,6 +795,14 @@ pub fn json_to_provider_info(json: &str) -> (String, String, String) {
     }
 }
 
+/// Extract the provider family a provider declares in its info() JSON,
+/// if any.
+pub fn json_to_type_family(json: &str) -> Option<String> {
+    serde_json::from_str::<proto::ProviderInfo>(json)
+        .ok()
+        .and_then(|info| info.type_family)
+}
+
 /// Reject a provider whose protocol is outside this host's supported range.
 /// The SDK stamps the version it was compiled against into the info() envelope,
 /// so this is a compile-time fact of the provider's protocol crate, not a value
@@ -767,6 +853,41 @@ pub fn json_to_attribute_types(
 
 // -- Protocol schema to core schema conversion --
 
+fn proto_constraint_to_core(
+    c: &proto::AttributeConstraint,
+) -> carina_core::schema::AttributeConstraint {
+    use carina_core::schema::AttributeConstraint as Core;
+    match c {
+        proto::AttributeConstraint::ExactlyOneOf { attributes } => {
+            Core::ExactlyOneOf(attributes.clone())
+        }
+        proto::AttributeConstraint::RequiredWith {
+            attribute,
+            requires,
+        } => Core::RequiredWith {
+            attribute: attribute.clone(),
+            requires: requires.clone(),
+        },
+        proto::AttributeConstraint::ConflictsWith {
+            attribute,
+            conflicts,
+        } => Core::ConflictsWith {
+            attribute: attribute.clone(),
+            conflicts: conflicts.clone(),
+        },
+        proto::AttributeConstraint::CidrWithinReference {
+            attribute,
+            reference,
+            referenced_attribute,
+        } => Core::CidrWithinReference {
+            attribute: attribute.clone(),
+            reference: reference.clone(),
+            referenced_attribute: referenced_attribute.clone(),
+        },
+    }
+}
+
+#[allow(deprecated)]
 fn proto_schema_to_core(
     s: &proto::ResourceSchema,
 ) -> Result<CoreResourceSchema, SchemaDecodeError> {
@@ -792,13 +913,20 @@ fn proto_schema_to_core(
                 create_max_retries: c.create_max_retries,
             }
         }),
-        exclusive_required: s.exclusive_required.clone(),
+        constraints: s
+            .exclusive_required
+            .iter()
+            .map(|group| carina_core::schema::AttributeConstraint::ExactlyOneOf(group.clone()))
+            .chain(s.constraints.iter().map(proto_constraint_to_core))
+            .collect(),
+        exclusive_required: Vec::new(),
         // Wait defaults are not (yet) carried across the WASM plugin
         // boundary — providers fall back to the carina-core constants
         // (`WAIT_DEFAULT_TIMEOUT` / `WAIT_DEFAULT_INTERVAL`) until the
         // protocol gains explicit fields. See carina-rs/carina#2825.
         default_wait_timeout: None,
         default_wait_interval: None,
+        diff_suppressors: s.diff_suppressors.clone(),
         // Cyclic CFN struct definitions reachable via
         // `AttributeType::Ref`. Mirrors the wire `defs` map onto the
         // core schema so walk-sites that traverse a `Ref` can resolve
@@ -866,7 +994,7 @@ fn proto_attr_schema_to_core(
         name: a.name.clone(),
         attr_type: proto_attr_type_to_core(&a.attr_type)?,
         required: a.required,
-        default: None,
+        default: a.default.as_ref().map(proto_value_to_core),
         description: a.description.clone(),
         completions: None,
         provider_name: a.provider_name.clone(),
@@ -876,6 +1004,10 @@ fn proto_attr_schema_to_core(
         block_name: a.block_name.clone(),
         write_only: a.write_only,
         identity: a.identity,
+        // Unknown unit names (a newer provider than this host) degrade
+        // to a plain integer attribute.
+        unit: a.unit.as_deref().and_then(CoreAttributeUnit::from_name),
+        list_key: a.list_key.clone(),
         // The WIT contract does not transmit `deferred_populate` —
         // the annotation lives entirely in the host-side schema; see
         // `proto_struct_field_to_core` for the rationale.
@@ -883,6 +1015,29 @@ fn proto_attr_schema_to_core(
     })
 }
 
+fn proto_value_to_core(v: &proto::Value) -> CoreValue {
+    CoreValue::Concrete(match v {
+        proto::Value::Bool(b) => ConcreteValue::Bool(*b),
+        proto::Value::Int(i) => ConcreteValue::Int(*i),
+        proto::Value::Float(f) => ConcreteValue::Float(*f),
+        proto::Value::String(s) => ConcreteValue::String(s.clone()),
+        proto::Value::StringList(items) => ConcreteValue::List(
+            items
+                .iter()
+                .map(|s| CoreValue::Concrete(ConcreteValue::String(s.clone())))
+                .collect(),
+        ),
+        proto::Value::List(items) => {
+            ConcreteValue::List(items.iter().map(proto_value_to_core).collect())
+        }
+        proto::Value::Map(map) => ConcreteValue::Map(
+            map.iter()
+                .map(|(k, v)| (k.clone(), proto_value_to_core(v)))
+                .collect(),
+        ),
+    })
+}
+
 fn proto_attr_type_to_core(
     t: &proto::AttributeType,
 ) -> Result<CoreAttributeType, SchemaDecodeError> {
@@ -1677,6 +1832,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_already_exists_error_round_trip_keeps_provider_code() {
+        let err = CoreProviderError::already_exists("role exists").with_code("EntityAlreadyExists");
+        let back = wit_to_core_provider_error(core_to_wit_provider_error(&err));
+        assert!(matches!(back, CoreProviderError::AlreadyExists(_)));
+        assert_eq!(back.detail().code.as_deref(), Some("EntityAlreadyExists"));
+
+        let back = wit_to_core_provider_error(core_to_wit_provider_error(
+            &CoreProviderError::already_exists("bucket exists"),
+        ));
+        assert!(matches!(back, CoreProviderError::AlreadyExists(_)));
+        assert_eq!(back.detail().code, None);
+    }
+
+    #[test]
+    fn test_ambiguous_match_error_round_trip_keeps_provider_code() {
+        let err = CoreProviderError::ambiguous_match(
+            "two roles match",
+            vec!["role-a".to_string(), "role-b".to_string()],
+        )
+        .with_code("MultipleMatches");
+        let back = wit_to_core_provider_error(core_to_wit_provider_error(&err));
+        assert!(matches!(back, CoreProviderError::AmbiguousMatch(_)));
+        assert_eq!(back.detail().code.as_deref(), Some("MultipleMatches"));
+
+        let back = wit_to_core_provider_error(core_to_wit_provider_error(
+            &CoreProviderError::ambiguous_match("two roles match", Vec::new()),
+        ));
+        assert!(matches!(back, CoreProviderError::AmbiguousMatch(_)));
+        assert_eq!(back.detail().code, None);
+    }
+
+    #[test]
+    fn test_credentials_error_round_trip_keeps_provider_code() {
+        let err = CoreProviderError::credentials("token expired").with_code("ExpiredToken");
+        let back = wit_to_core_provider_error(core_to_wit_provider_error(&err));
+        assert!(back.is_fatal());
+        assert_eq!(back.detail().code.as_deref(), Some("ExpiredToken"));
+
+        let back = wit_to_core_provider_error(core_to_wit_provider_error(
+            &CoreProviderError::credentials("no credentials"),
+        ));
+        assert!(matches!(back, CoreProviderError::Credentials(_)));
+        assert_eq!(back.detail().code, None);
+    }
+
     #[test]
     fn test_provider_error_detail_fields_round_trip() {
         // Resource id, cause string, and provider name must all
@@ -1776,6 +1977,14 @@ mod tests {
         assert_eq!(version, "1.0.0");
     }
 
+    #[test]
+    fn test_json_to_type_family() {
+        let json = r#"{"name":"awscc","display_name":"AWS Cloud Control","version":"1.0.0","type_family":"aws"}"#;
+        assert_eq!(json_to_type_family(json).as_deref(), Some("aws"));
+        let json = r#"{"name":"mock","display_name":"Mock","version":"1.0.0"}"#;
+        assert_eq!(json_to_type_family(json), None);
+    }
+
     #[test]
     fn test_json_to_provider_info_missing_version_falls_back() {
         // When version is missing entirely (complete parse failure), fall back to "0.0.0"
@@ -2180,6 +2389,8 @@ mod tests {
             operation_config: None,
             validators: vec![proto::ValidatorType::TagsKeyValueCheck],
             exclusive_required: vec![],
+            constraints: vec![],
+            diff_suppressors: vec![],
             defs: Default::default(),
         };
         let core_schema = proto_schema_to_core(&proto_schema).unwrap();
@@ -2197,6 +2408,8 @@ mod tests {
             operation_config: None,
             validators: vec![],
             exclusive_required: vec![],
+            constraints: vec![],
+            diff_suppressors: vec![],
             defs: Default::default(),
         };
         let core_schema = proto_schema_to_core(&proto_schema).unwrap();
@@ -2219,15 +2432,16 @@ mod tests {
                 "cidr_block".to_string(),
                 "ipv4_ipam_pool_id".to_string(),
             ]],
+            constraints: vec![],
+            diff_suppressors: vec![],
             defs: Default::default(),
         };
         let core_schema = proto_schema_to_core(&proto_schema).unwrap();
         assert_eq!(
-            core_schema.exclusive_required,
-            vec![vec![
-                "cidr_block".to_string(),
-                "ipv4_ipam_pool_id".to_string(),
-            ]]
+            core_schema.constraints,
+            vec![carina_core::schema::AttributeConstraint::ExactlyOneOf(
+                vec!["cidr_block".to_string(), "ipv4_ipam_pool_id".to_string(),]
+            )]
         );
 
         // And the resulting core schema rejects empty attributes.
@@ -2254,14 +2468,54 @@ mod tests {
             operation_config: None,
             validators: vec![],
             exclusive_required: vec![vec!["a".to_string(), "b".to_string()]],
+            constraints: vec![],
+            diff_suppressors: vec![],
             defs: Default::default(),
         };
         let json = serde_json::to_string(&vec![proto_schema]).unwrap();
         let schemas = json_to_schemas(&json).unwrap();
         assert_eq!(schemas.len(), 1);
         assert_eq!(
-            schemas[0].exclusive_required,
-            vec![vec!["a".to_string(), "b".to_string()]]
+            schemas[0].constraints,
+            vec![carina_core::schema::AttributeConstraint::ExactlyOneOf(
+                vec!["a".to_string(), "b".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_attribute_default_survives_proto_boundary() {
+        // Schema defaults are materialized into desired state during
+        // planning; dropping them here would reintroduce the spurious
+        // diffs they exist to prevent.
+        let json = r#"[{
+            "resource_type": "ec2.Vpc",
+            "attributes": {
+                "instance_tenancy": {
+                    "name": "instance_tenancy",
+                    "attr_type": { "type": "String" },
+                    "required": false,
+                    "default": "default"
+                },
+                "enable_dns_support": {
+                    "name": "enable_dns_support",
+                    "attr_type": { "type": "Bool" },
+                    "required": false,
+                    "default": true
+                }
+            }
+        }]"#;
+        let schemas = json_to_schemas(json).unwrap();
+        let attrs = &schemas[0].attributes;
+        assert_eq!(
+            attrs["instance_tenancy"].default,
+            Some(CoreValue::Concrete(ConcreteValue::String(
+                "default".to_string()
+            )))
+        );
+        assert_eq!(
+            attrs["enable_dns_support"].default,
+            Some(CoreValue::Concrete(ConcreteValue::Bool(true)))
         );
     }
 
@@ -2909,6 +3163,8 @@ mod tests {
             operation_config: None,
             validators: vec![proto::ValidatorType::TagsKeyValueCheck],
             exclusive_required: vec![],
+            constraints: vec![],
+            diff_suppressors: vec![],
             defs: Default::default(),
         };
         let core_schema = proto_schema_to_core(&proto_schema).unwrap();
@@ -2950,4 +3206,22 @@ mod tests {
         );
         assert!(validator(&attrs).is_ok());
     }
+
+    #[test]
+    fn user_timeouts_cross_the_wit_boundary() {
+        let request = CoreDeleteRequest {
+            directives: Directives::default(),
+            timeout: Some(std::time::Duration::from_secs(45 * 60)),
+            force_dependencies: false,
+        };
+        assert_eq!(
+            core_to_wit_delete_request(&request).timeout_secs,
+            Some(45 * 60)
+        );
+        let request = CoreDeleteRequest {
+            timeout: None,
+            ..request
+        };
+        assert_eq!(core_to_wit_delete_request(&request).timeout_secs, None);
+    }
 }
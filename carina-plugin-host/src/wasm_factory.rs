@@ -1,4 +1,16 @@
 //! WasmProviderFactory loads a WASM component and implements ProviderFactory.
+//!
+//! The WIT `provider` world only carries the CRUD calls, permissions,
+//! satisfier hints and the normalization hooks. The optional `Provider`
+//! hooks added since — `validate_resource`, `simulate`,
+//! `list_project_resources`, `monthly_cost`, `delete_blockers`,
+//! `find_existing`, `operation_metrics`, `credential_expiry` and
+//! `capabilities` — have no export in it, so [`WasmProvider`] answers
+//! each with the trait default, or for `capabilities` a conservative
+//! value, and says so on the method. The two that
+//! only run when the user asks for them (`carina plan --simulate`,
+//! `carina orphans`) log a warning the first time they are used, so an
+//! empty result is not mistaken for a clean one.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -6,6 +18,7 @@ use std::path::{Path, PathBuf};
 use indexmap::IndexMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
 
 use sha2::{Digest, Sha256};
 
@@ -20,13 +33,15 @@ use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 use wasmtime_wasi_http::WasiHttpCtx;
 use wasmtime_wasi_http::p2::{WasiHttpCtxView, WasiHttpView};
 
+use carina_core::cost::MonthlyCost;
 use carina_core::effect::PlanOp;
 use carina_core::provider::{
-    BoxFuture, CreateOutcome, CreateRequest, DeleteRequest, Provider, ProviderError,
-    ProviderFactory, ProviderNormalizer, ProviderResult, ReadRequest, SavedAttrs, UpdateOutcome,
+    BoxFuture, CreateOutcome, CreateRequest, DeleteBlocker, DeleteRequest, ListRequest,
+    OperationMetrics, Provider, ProviderCapabilities, ProviderError, ProviderFactory,
+    ProviderNormalizer, ProviderResult, ReadRequest, ResourceDiagnostic, SavedAttrs, UpdateOutcome,
     UpdateRequest,
 };
-use carina_core::resource::{DataSource, Resource, ResourceId, State, Value};
+use carina_core::resource::{DataSource, ResolvedResource, Resource, ResourceId, State, Value};
 use carina_core::schema::{CompletionValue, ResourceSchema, TypeIdentity};
 use carina_core::value::SerializationError;
 use carina_core::wait::BindingPattern;
@@ -97,6 +112,11 @@ const WASM_OPERATION_TIMEOUT_SECS: u64 = 30;
 const HTTP_API_REQUEST_TIMEOUT: std::time::Duration =
     std::time::Duration::from_secs(WASM_OPERATION_TIMEOUT_SECS);
 
+/// Number of per-phase timeout budgets (connect, TLS, first byte, body)
+/// one plugin HTTP request may spend in total before it is cut off. See
+/// [`crate::http_deadline`].
+const HTTP_REQUEST_DEADLINE_PHASES: u32 = 4;
+
 /// Build the standard wasmtime Config used for all WASM plugin engines.
 ///
 /// Enables the component model and epoch-based interruption so that
@@ -383,6 +403,9 @@ impl wasmtime_wasi_http::p2::WasiHttpHooks for AllowListHttpHooks {
         config.connect_timeout = config.connect_timeout.min(cap);
         config.first_byte_timeout = config.first_byte_timeout.min(cap);
         config.between_bytes_timeout = config.between_bytes_timeout.min(cap);
+        // The per-phase caps above do not bound the TLS handshake or the
+        // total body read; this deadline bounds the whole exchange.
+        let deadline = tokio::time::Instant::now() + cap * HTTP_REQUEST_DEADLINE_PHASES;
 
         if trace_http_enabled() {
             let method = request.method().to_string();
@@ -391,7 +414,9 @@ impl wasmtime_wasi_http::p2::WasiHttpHooks for AllowListHttpHooks {
             let handle = wasmtime_wasi::runtime::spawn(async move {
                 let queue_ms = spawn_start.elapsed().as_millis();
                 let handler_start = std::time::Instant::now();
-                let result = traced_send_request_handler(request, config).await;
+                let result =
+                    send_with_deadline(deadline, traced_send_request_handler(request, config))
+                        .await;
                 let handler_ms = handler_start.elapsed().as_millis();
                 eprintln!(
                     "carina-host-http-trace method={} uri={} queue_ms={} handler_ms={} status={}",
@@ -409,12 +434,45 @@ impl wasmtime_wasi_http::p2::WasiHttpHooks for AllowListHttpHooks {
             return Ok(wasmtime_wasi_http::p2::types::HostFutureIncomingResponse::pending(handle));
         }
 
-        Ok(wasmtime_wasi_http::p2::default_send_request(
-            request, config,
-        ))
+        let handle = wasmtime_wasi::runtime::spawn(async move {
+            Ok(send_with_deadline(
+                deadline,
+                wasmtime_wasi_http::p2::default_send_request_handler(request, config),
+            )
+            .await)
+        });
+        Ok(wasmtime_wasi_http::p2::types::HostFutureIncomingResponse::pending(handle))
     }
 }
 
+/// Run an outgoing-request handler under `deadline`: the handler itself
+/// (connect, TLS, response headers) times out with `HttpResponseTimeout`,
+/// and the response body it returns with `ConnectionReadTimeout`.
+async fn send_with_deadline(
+    deadline: tokio::time::Instant,
+    send: impl std::future::Future<
+        Output = Result<
+            wasmtime_wasi_http::p2::types::IncomingResponse,
+            wasmtime_wasi_http::p2::bindings::http::types::ErrorCode,
+        >,
+    >,
+) -> Result<
+    wasmtime_wasi_http::p2::types::IncomingResponse,
+    wasmtime_wasi_http::p2::bindings::http::types::ErrorCode,
+> {
+    use http_body_util::BodyExt;
+    use wasmtime_wasi_http::p2::bindings::http::types::ErrorCode;
+
+    let mut incoming =
+        crate::http_deadline::until_deadline(deadline, send, || ErrorCode::HttpResponseTimeout)
+            .await?;
+    incoming.resp = incoming.resp.map(|body| {
+        crate::http_deadline::DeadlineBody::new(body, deadline, || ErrorCode::ConnectionReadTimeout)
+            .boxed_unsync()
+    });
+    Ok(incoming)
+}
+
 /// Companion to carina-plugin-sdk's `CARINA_WASI_HTTP_TRACE` switch.
 ///
 /// When set to "1", the host-side `WasiHttpHooks::send_request` spawns the
@@ -1333,7 +1391,12 @@ pub struct WasmProviderFactory {
     name: String,
     display_name: String,
     version: String,
+    /// Provider family declared in `info()` (see
+    /// `ProviderFactory::type_family`).
+    type_family: Option<String>,
     schemas: Vec<ResourceSchema>,
+    /// Raw `schemas()` output, kept for signed-manifest verification.
+    schemas_json: String,
     cached_config_completions: HashMap<String, Vec<CompletionValue>>,
     cached_identity_attributes: Vec<String>,
     cached_enum_aliases: HashMap<String, HashMap<String, HashMap<String, String>>>,
@@ -1533,6 +1596,7 @@ impl WasmProviderFactory {
             .map_err(|e| format!("Failed to call schemas(): {e}"))?;
 
         let (name, display_name, version) = wasm_convert::json_to_provider_info(&info_json);
+        let type_family = wasm_convert::json_to_type_family(&info_json);
         let schemas: Vec<ResourceSchema> = wasm_convert::json_to_schemas(&schemas_json)
             .map_err(|e| provider_schema_decode_error(&name, &version, e))?;
 
@@ -1550,7 +1614,9 @@ impl WasmProviderFactory {
             name,
             display_name,
             version,
+            type_family,
             schemas,
+            schemas_json,
             cached_config_completions,
             cached_identity_attributes,
             cached_enum_aliases,
@@ -1644,6 +1710,7 @@ impl WasmProviderFactory {
             .map_err(|e| format!("Failed to call schemas(): {e}"))?;
 
         let (name, display_name, version) = wasm_convert::json_to_provider_info(&info_json);
+        let type_family = wasm_convert::json_to_type_family(&info_json);
         let schemas: Vec<ResourceSchema> = wasm_convert::json_to_schemas(&schemas_json)
             .map_err(|e| provider_schema_decode_error(&name, &version, e))?;
 
@@ -1661,7 +1728,9 @@ impl WasmProviderFactory {
             name,
             display_name,
             version,
+            type_family,
             schemas,
+            schemas_json,
             cached_config_completions,
             cached_identity_attributes,
             cached_enum_aliases,
@@ -1747,6 +1816,11 @@ impl WasmProviderFactory {
         &self.version
     }
 
+    /// The JSON the component's `schemas()` export returned at load.
+    pub fn schemas_json(&self) -> &str {
+        &self.schemas_json
+    }
+
     /// Verify that this provider's version satisfies the given constraint.
     pub fn verify_version(&self, constraint_raw: &str) -> Result<(), String> {
         let req = semver::VersionReq::parse(constraint_raw)
@@ -1776,6 +1850,14 @@ impl ProviderFactory for WasmProviderFactory {
         &self.display_name
     }
 
+    fn type_family(&self) -> Option<&str> {
+        self.type_family.as_deref()
+    }
+
+    fn version(&self) -> Option<&str> {
+        Some(&self.version)
+    }
+
     fn provider_config_attribute_types(
         &self,
     ) -> HashMap<String, carina_core::schema::AttributeType> {
@@ -1876,6 +1958,8 @@ impl ProviderFactory for WasmProviderFactory {
             Ok(Box::new(WasmProvider {
                 instance,
                 name: self.name.clone(),
+                warned_simulate: AtomicBool::new(false),
+                warned_list: AtomicBool::new(false),
             }) as Box<dyn Provider>)
         })
     }
@@ -1913,6 +1997,26 @@ impl ProviderFactory for WasmProviderFactory {
 pub struct WasmProvider {
     instance: Arc<SharedWasmInstance>,
     name: String,
+    /// Set once the "not forwarded" warning for `simulate` was logged.
+    warned_simulate: AtomicBool,
+    /// Set once the "not forwarded" warning for `list_project_resources`
+    /// was logged.
+    warned_list: AtomicBool,
+}
+
+impl WasmProvider {
+    /// Log, once per provider instance, that `feature` was requested but
+    /// the plugin interface cannot carry it.
+    fn warn_not_forwarded(&self, flag: &AtomicBool, feature: &str) {
+        if !flag.swap(true, Ordering::Relaxed) {
+            log::warn!(
+                target: "carina_plugin_host::wasm_factory",
+                "provider '{}' is a WASM plugin, whose interface does not carry {}; skipped",
+                self.name,
+                feature
+            );
+        }
+    }
 }
 
 // Safety: SharedWasmInstance.store is behind a Mutex, so concurrent access is
@@ -2201,6 +2305,86 @@ impl Provider for WasmProvider {
             })
         })
     }
+
+    /// Not forwarded over WIT: WASM providers have no plan-time
+    /// validation beyond their schema.
+    fn validate_resource(&self, _resource: &Resource) -> Vec<ResourceDiagnostic> {
+        Vec::new()
+    }
+
+    /// Not forwarded over WIT. Warns on first use, since the user asked
+    /// for the dry run explicitly.
+    fn simulate(
+        &self,
+        _resource: &Resource,
+    ) -> BoxFuture<'_, ProviderResult<Vec<ResourceDiagnostic>>> {
+        self.warn_not_forwarded(&self.warned_simulate, "plan-time simulation");
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// Not forwarded over WIT: `carina orphans` reports this provider as
+    /// unchecked. Warns on first use.
+    fn list_project_resources(
+        &self,
+        _request: ListRequest,
+    ) -> BoxFuture<'_, ProviderResult<Option<Vec<State>>>> {
+        self.warn_not_forwarded(&self.warned_list, "project resource listing");
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Not forwarded over WIT: the plan's cost summary prices this
+    /// provider's `aws` / `awscc` resources from the router's bundled
+    /// list prices instead.
+    fn monthly_cost<'a>(
+        &'a self,
+        _id: &'a ResourceId,
+        _attributes: &'a HashMap<String, Value>,
+    ) -> BoxFuture<'a, Option<MonthlyCost>> {
+        Box::pin(async { None })
+    }
+
+    /// Not forwarded over WIT: delete blockers are only discovered when
+    /// the delete itself fails.
+    fn delete_blockers(
+        &self,
+        _id: &ResourceId,
+        _identifier: &str,
+    ) -> BoxFuture<'_, ProviderResult<Vec<DeleteBlocker>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// Not forwarded over WIT: creates are not checked for an existing
+    /// remote resource of the same name.
+    fn find_existing(
+        &self,
+        _id: &ResourceId,
+        _resource: &ResolvedResource,
+    ) -> BoxFuture<'_, ProviderResult<Option<String>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Not forwarded over WIT: the apply summary has no provider metrics
+    /// for WASM providers.
+    fn operation_metrics(&self, _id: &ResourceId) -> Option<OperationMetrics> {
+        None
+    }
+
+    /// Not forwarded over WIT: the pre-apply credential expiry check is
+    /// skipped for WASM providers.
+    fn credential_expiry(&self) -> BoxFuture<'_, Option<SystemTime>> {
+        Box::pin(async { None })
+    }
+
+    /// Not forwarded over WIT, so only what the interface itself
+    /// guarantees is claimed. Import only needs `read`, which every
+    /// component exports. Whether the provider accepts Carina's
+    /// provenance tags is unknown, so tagging is off.
+    fn capabilities(&self, _id: &ResourceId) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supports_tagging: false,
+            ..ProviderCapabilities::default()
+        }
+    }
 }
 
 // -- WasmProviderNormalizer --
@@ -120,6 +120,8 @@ async fn test_wasm_mock_provider_create_and_read() {
             &id,
             CreateRequest {
                 resource: normalized_for_test(resource.clone()).await,
+                timeout: None,
+                client_token: None,
             },
         )
         .await
@@ -189,6 +191,8 @@ async fn test_wasm_mock_provider_update_and_delete() {
             &id,
             CreateRequest {
                 resource: normalized_for_test(resource.clone()).await,
+                timeout: None,
+                client_token: None,
             },
         )
         .await
@@ -224,6 +228,7 @@ async fn test_wasm_mock_provider_update_and_delete() {
             UpdateRequest {
                 from: created.clone(),
                 patch,
+                timeout: None,
             },
         )
         .await
@@ -501,3 +506,34 @@ async fn test_wasm_mock_provider_required_permissions_dispatches_through_wit() {
         Vec::<String>::new()
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wasm_provider_resources_are_priced_from_list_prices() {
+    use carina_core::cost::{AwsListPrices, CostEstimator};
+    use carina_core::provider::ProviderRouter;
+
+    let path = skip_if_no_wasm!();
+    let (factory, _cache) = load_factory(&path).await;
+    let provider = factory
+        .create_provider(None, &indexmap::IndexMap::new())
+        .await
+        .expect("provider should init");
+    let nat = ResourceId::with_provider_identity("awscc", "ec2.NatGateway", "nat", None);
+    let attributes = HashMap::new();
+    // The plugin interface has no monthly-cost export.
+    assert!(provider.monthly_cost(&nat, &attributes).await.is_none());
+
+    let parsed = carina_core::parser::parse(
+        "provider awscc {\n  region = \"us-east-1\"\n}\n",
+        &carina_core::parser::ProviderContext::default(),
+    )
+    .expect("provider block parses");
+    let mut router = ProviderRouter::new();
+    router.add_provider("awscc".to_string(), provider);
+    router.set_list_prices(AwsListPrices::from_configs(&parsed.providers).unwrap());
+
+    let cost = CostEstimator::monthly_cost(&router, &nat, &attributes)
+        .await
+        .expect("NAT gateway is priced from the bundled list prices");
+    assert!((cost.usd - 32.85).abs() < 1e-9);
+}
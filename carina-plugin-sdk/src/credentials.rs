@@ -0,0 +1,154 @@
+//! Cached credentials that are refreshed before they expire.
+//!
+//! Assumed-role and SSO credentials typically last an hour; an apply that
+//! creates a database or a cluster can take longer, and fails halfway
+//! with `ExpiredToken` when the provider keeps using the credentials it
+//! fetched at `initialize`. A provider keeps them in a
+//! [`CredentialCache`] and asks [`CredentialCache::get`] before each
+//! request instead: the cached value is returned until it is within the
+//! refresh window of its expiry, then the provider's fetch (e.g. an STS
+//! `AssumeRole`) runs again. [`CredentialCache::expires_at`] is what the
+//! provider reports from `credential_expiry`.
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// How long before expiry credentials are refreshed by default. Leaves
+/// room for a request signed just before the refresh to still complete.
+pub const DEFAULT_REFRESH_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// A credential value and when it stops working.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expiring<T> {
+    pub value: T,
+    /// `None` for credentials that do not expire, e.g. static keys.
+    pub expires_at: Option<SystemTime>,
+}
+
+impl<T> Expiring<T> {
+    pub fn new(value: T, expires_at: Option<SystemTime>) -> Self {
+        Self { value, expires_at }
+    }
+}
+
+/// Credentials shared by a provider's clients, refreshed on demand.
+#[derive(Debug)]
+pub struct CredentialCache<T> {
+    cached: Mutex<Option<Expiring<T>>>,
+    refresh_window: Duration,
+}
+
+impl<T: Clone> CredentialCache<T> {
+    /// An empty cache that refreshes `refresh_window` before expiry.
+    pub fn new(refresh_window: Duration) -> Self {
+        Self {
+            cached: Mutex::new(None),
+            refresh_window,
+        }
+    }
+
+    /// The credentials to use at `now`, calling `fetch` when there are
+    /// none yet or they are about to expire. Concurrent callers wait for
+    /// a single fetch. When a refresh fails but the cached credentials
+    /// have not actually expired yet, they are returned and the refresh
+    /// is retried on the next call.
+    pub fn get<E>(
+        &self,
+        now: SystemTime,
+        fetch: impl FnOnce() -> Result<Expiring<T>, E>,
+    ) -> Result<T, E> {
+        let mut cached = self.cached.lock().unwrap();
+        if let Some(current) = cached.as_ref()
+            && !expires_within(current, now, self.refresh_window)
+        {
+            return Ok(current.value.clone());
+        }
+        match fetch() {
+            Ok(fresh) => {
+                let value = fresh.value.clone();
+                *cached = Some(fresh);
+                Ok(value)
+            }
+            Err(e) => match cached.as_ref() {
+                Some(current) if !expires_within(current, now, Duration::ZERO) => {
+                    Ok(current.value.clone())
+                }
+                _ => Err(e),
+            },
+        }
+    }
+
+    /// Expiry of the cached credentials; `None` when nothing is cached
+    /// or the credentials do not expire.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.cached.lock().unwrap().as_ref()?.expires_at
+    }
+
+    /// Drop the cached credentials so the next [`CredentialCache::get`]
+    /// fetches, e.g. after a request was rejected as expired.
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap() = None;
+    }
+}
+
+impl<T: Clone> Default for CredentialCache<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_REFRESH_WINDOW)
+    }
+}
+
+fn expires_within<T>(credentials: &Expiring<T>, now: SystemTime, window: Duration) -> bool {
+    credentials
+        .expires_at
+        .is_some_and(|expires_at| now + window >= expires_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn refreshes_only_inside_the_window() {
+        let cache = CredentialCache::new(Duration::from_secs(300));
+        let fetches = std::cell::Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok::<_, String>(Expiring::new(fetches.get(), Some(at(3600 * fetches.get()))))
+        };
+
+        assert_eq!(cache.get(at(0), fetch), Ok(1));
+        assert_eq!(cache.get(at(3000), fetch), Ok(1));
+        assert_eq!(cache.expires_at(), Some(at(3600)));
+        // 3300s is exactly five minutes before expiry.
+        assert_eq!(cache.get(at(3300), fetch), Ok(2));
+        assert_eq!(cache.expires_at(), Some(at(7200)));
+
+        cache.invalidate();
+        assert_eq!(cache.get(at(3400), fetch), Ok(3));
+    }
+
+    #[test]
+    fn failed_refresh_keeps_unexpired_credentials() {
+        let cache = CredentialCache::new(Duration::from_secs(300));
+        cache
+            .get(at(0), || {
+                Ok::<_, String>(Expiring::new("first", Some(at(600))))
+            })
+            .unwrap();
+        let fail = || Err::<Expiring<&str>, _>("sts unavailable".to_string());
+
+        assert_eq!(cache.get(at(400), fail), Ok("first"));
+        assert_eq!(cache.get(at(600), fail), Err("sts unavailable".to_string()));
+
+        let static_keys = CredentialCache::default();
+        static_keys
+            .get(at(0), || Ok::<_, String>(Expiring::new("static", None)))
+            .unwrap();
+        assert_eq!(static_keys.get(at(u32::MAX.into()), fail), Ok("static"));
+        assert_eq!(static_keys.expires_at(), None);
+    }
+}
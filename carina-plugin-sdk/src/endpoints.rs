@@ -0,0 +1,323 @@
+//! Per-service endpoint overrides from provider configuration.
+//!
+//! Tests point a provider at LocalStack or moto, and locked-down networks
+//! reach AWS through interface VPC endpoints; both need the provider's
+//! clients to use a URL other than the regional default. A provider reads
+//!
+//! ```text
+//! provider awscc {
+//!   endpoints = {
+//!     cloudcontrol = "http://localhost:4566"
+//!     sts          = "http://localhost:4566"
+//!   }
+//!   skip_tls_verify = true
+//! }
+//! ```
+//!
+//! with [`EndpointOverrides::from_config`] in `validate_config` /
+//! `initialize`, and asks [`EndpointOverrides::resolve`] for each client's
+//! URL. TLS verification can only be turned off when every override
+//! points at the local machine, and [`EndpointOverrides::skip_tls_verify`]
+//! answers per service: a service left on its default endpoint keeps
+//! verifying, so the toggle cannot leak into a client that talks to a
+//! real endpoint.
+//!
+//! `use_fips_endpoints` and `use_dualstack_endpoints` select the FIPS and
+//! dual-stack variants of the default endpoints. A provider built on the
+//! AWS SDK passes [`EndpointOverrides::variant`] on to its config loader
+//! (`use_fips` / `use_dual_stack`); an explicit override always wins.
+
+use std::collections::{BTreeMap, HashMap};
+
+use carina_provider_protocol::types::Value;
+
+use crate::partition::{EndpointVariant, Partition};
+
+/// Provider configuration attribute holding the service → URL map.
+pub const ENDPOINTS_ATTRIBUTE: &str = "endpoints";
+/// Provider configuration attribute that disables TLS verification.
+pub const SKIP_TLS_VERIFY_ATTRIBUTE: &str = "skip_tls_verify";
+/// Provider configuration attribute selecting FIPS endpoints.
+pub const USE_FIPS_ATTRIBUTE: &str = "use_fips_endpoints";
+/// Provider configuration attribute selecting dual-stack endpoints.
+pub const USE_DUALSTACK_ATTRIBUTE: &str = "use_dualstack_endpoints";
+
+/// Endpoint URLs that replace the regional defaults, keyed by service.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointOverrides {
+    endpoints: BTreeMap<String, String>,
+    skip_tls_verify: bool,
+    variant: EndpointVariant,
+}
+
+impl EndpointOverrides {
+    /// Read the overrides from provider configuration. `services` lists
+    /// the keys the provider accepts in `endpoints`, e.g.
+    /// `["cloudcontrol", "ec2", "sts"]`.
+    pub fn from_config(attrs: &HashMap<String, Value>, services: &[&str]) -> Result<Self, String> {
+        let mut endpoints = BTreeMap::new();
+        match attrs.get(ENDPOINTS_ATTRIBUTE) {
+            None => {}
+            Some(Value::Map(map)) => {
+                for (service, url) in map {
+                    if !services.contains(&service.as_str()) {
+                        return Err(format!(
+                            "{ENDPOINTS_ATTRIBUTE}: unknown service '{service}'; expected one of: {}",
+                            services.join(", ")
+                        ));
+                    }
+                    let Value::String(url) = url else {
+                        return Err(format!(
+                            "{ENDPOINTS_ATTRIBUTE}.{service}: expected a URL string"
+                        ));
+                    };
+                    if host(url).is_none() {
+                        return Err(format!(
+                            "{ENDPOINTS_ATTRIBUTE}.{service}: '{url}' is not an http:// or https:// URL"
+                        ));
+                    }
+                    endpoints.insert(service.clone(), url.trim_end_matches('/').to_string());
+                }
+            }
+            Some(_) => {
+                return Err(format!(
+                    "{ENDPOINTS_ATTRIBUTE}: expected a map of service name to URL"
+                ));
+            }
+        }
+
+        let skip_tls_verify = bool_attribute(attrs, SKIP_TLS_VERIFY_ATTRIBUTE)?;
+        if skip_tls_verify {
+            if endpoints.is_empty() {
+                return Err(format!(
+                    "{SKIP_TLS_VERIFY_ATTRIBUTE} requires {ENDPOINTS_ATTRIBUTE}: \
+                     TLS verification can only be disabled for local test endpoints"
+                ));
+            }
+            if let Some((service, url)) = endpoints
+                .iter()
+                .find(|(_, url)| !host(url).is_some_and(is_local_host))
+            {
+                return Err(format!(
+                    "{SKIP_TLS_VERIFY_ATTRIBUTE} is only allowed for local test endpoints, \
+                     but {ENDPOINTS_ATTRIBUTE}.{service} is '{url}'"
+                ));
+            }
+        }
+
+        Ok(Self {
+            endpoints,
+            skip_tls_verify,
+            variant: EndpointVariant {
+                fips: bool_attribute(attrs, USE_FIPS_ATTRIBUTE)?,
+                dualstack: bool_attribute(attrs, USE_DUALSTACK_ATTRIBUTE)?,
+            },
+        })
+    }
+
+    /// The override for `service`, if configured.
+    pub fn get(&self, service: &str) -> Option<&str> {
+        self.endpoints.get(service).map(String::as_str)
+    }
+
+    /// URL for `service` in `region`: the override when configured,
+    /// otherwise the partition's default endpoint for `endpoint_prefix`
+    /// (e.g. `cloudcontrolapi` for the `cloudcontrol` service) in the
+    /// selected variant. Errors when the region's partition has no such
+    /// variant, e.g. FIPS in China.
+    pub fn resolve(
+        &self,
+        service: &str,
+        endpoint_prefix: &str,
+        region: &str,
+    ) -> Result<String, String> {
+        match self.get(service) {
+            Some(url) => Ok(url.to_string()),
+            None => Partition::for_region(region).endpoint_variant(
+                endpoint_prefix,
+                region,
+                self.variant,
+            ),
+        }
+    }
+
+    /// The FIPS / dual-stack selection for default endpoints.
+    pub fn variant(&self) -> EndpointVariant {
+        self.variant
+    }
+
+    /// Whether the client for `service` should skip TLS certificate
+    /// verification: only when the toggle is set and `service` is
+    /// overridden, which `from_config` guarantees is a local endpoint.
+    /// Services on their default endpoint always verify.
+    pub fn skip_tls_verify(&self, service: &str) -> bool {
+        self.skip_tls_verify && self.endpoints.contains_key(service)
+    }
+}
+
+fn bool_attribute(attrs: &HashMap<String, Value>, name: &str) -> Result<bool, String> {
+    match attrs.get(name) {
+        None => Ok(false),
+        Some(Value::Bool(value)) => Ok(*value),
+        Some(_) => Err(format!("{name}: expected a bool")),
+    }
+}
+
+/// Host of an `http://` or `https://` URL, without port or brackets.
+fn host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit('@').next()?;
+    let host = match authority.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next()?,
+        None => authority.split(':').next()?,
+    };
+    (!host.is_empty()).then_some(host)
+}
+
+fn is_local_host(host: &str) -> bool {
+    host == "localhost"
+        || host.ends_with(".localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVICES: [&str; 3] = ["cloudcontrol", "ec2", "sts"];
+
+    fn config(endpoints: &[(&str, &str)], skip_tls_verify: Option<bool>) -> HashMap<String, Value> {
+        let mut attrs = HashMap::from([(
+            ENDPOINTS_ATTRIBUTE.to_string(),
+            Value::Map(
+                endpoints
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+                    .collect(),
+            ),
+        )]);
+        if let Some(skip) = skip_tls_verify {
+            attrs.insert(SKIP_TLS_VERIFY_ATTRIBUTE.to_string(), Value::Bool(skip));
+        }
+        attrs
+    }
+
+    #[test]
+    fn overrides_replace_the_regional_default() {
+        let overrides = EndpointOverrides::from_config(
+            &config(&[("cloudcontrol", "http://localhost:4566/")], None),
+            &SERVICES,
+        )
+        .unwrap();
+        assert_eq!(
+            overrides.resolve("cloudcontrol", "cloudcontrolapi", "us-east-1"),
+            Ok("http://localhost:4566".to_string())
+        );
+        assert_eq!(
+            overrides.resolve("ec2", "ec2", "cn-north-1"),
+            Ok("https://ec2.cn-north-1.amazonaws.com.cn".to_string())
+        );
+        assert!(!overrides.skip_tls_verify("cloudcontrol"));
+        assert_eq!(
+            EndpointOverrides::from_config(&HashMap::new(), &SERVICES).unwrap(),
+            EndpointOverrides::default()
+        );
+    }
+
+    #[test]
+    fn fips_and_dualstack_flags_select_the_default_endpoint_variant() {
+        let mut attrs = config(&[("sts", "http://localhost:4566")], None);
+        attrs.insert(USE_FIPS_ATTRIBUTE.to_string(), Value::Bool(true));
+        attrs.insert(USE_DUALSTACK_ATTRIBUTE.to_string(), Value::Bool(true));
+        let overrides = EndpointOverrides::from_config(&attrs, &SERVICES).unwrap();
+        assert_eq!(
+            overrides.variant(),
+            EndpointVariant {
+                fips: true,
+                dualstack: true,
+            }
+        );
+        assert_eq!(
+            overrides.resolve("ec2", "ec2", "us-east-1"),
+            Ok("https://ec2-fips.us-east-1.api.aws".to_string())
+        );
+        assert_eq!(
+            overrides.resolve("sts", "sts", "us-east-1"),
+            Ok("http://localhost:4566".to_string()),
+            "an explicit override wins"
+        );
+        assert!(overrides.resolve("ec2", "ec2", "cn-north-1").is_err());
+
+        attrs.insert(USE_FIPS_ATTRIBUTE.to_string(), Value::String("yes".into()));
+        let err = EndpointOverrides::from_config(&attrs, &SERVICES).unwrap_err();
+        assert!(err.contains("use_fips_endpoints: expected a bool"), "{err}");
+    }
+
+    #[test]
+    fn unknown_services_and_bad_urls_are_rejected() {
+        let err = EndpointOverrides::from_config(
+            &config(&[("s3", "http://localhost:4566")], None),
+            &SERVICES,
+        )
+        .unwrap_err();
+        assert!(err.contains("unknown service 's3'"), "{err}");
+
+        let err =
+            EndpointOverrides::from_config(&config(&[("sts", "localhost:4566")], None), &SERVICES)
+                .unwrap_err();
+        assert!(err.contains("not an http:// or https:// URL"), "{err}");
+    }
+
+    #[test]
+    fn tls_verification_can_only_be_skipped_for_local_endpoints() {
+        for url in [
+            "https://localhost:4566",
+            "https://127.0.0.1:5000",
+            "https://[::1]:4566",
+            "https://localstack.localhost",
+        ] {
+            let overrides =
+                EndpointOverrides::from_config(&config(&[("sts", url)], Some(true)), &SERVICES)
+                    .unwrap();
+            assert!(overrides.skip_tls_verify("sts"), "{url}");
+        }
+
+        let err = EndpointOverrides::from_config(
+            &config(
+                &[
+                    ("sts", "https://localhost:4566"),
+                    ("ec2", "https://vpce-0abc.ec2.us-east-1.vpce.amazonaws.com"),
+                ],
+                Some(true),
+            ),
+            &SERVICES,
+        )
+        .unwrap_err();
+        assert!(err.contains("endpoints.ec2"), "{err}");
+
+        let err = EndpointOverrides::from_config(&config(&[], Some(true)), &SERVICES).unwrap_err();
+        assert!(err.contains("requires endpoints"), "{err}");
+    }
+
+    #[test]
+    fn services_left_on_real_endpoints_keep_verifying_tls() {
+        let overrides = EndpointOverrides::from_config(
+            &config(&[("cloudcontrol", "http://localhost:4566")], Some(true)),
+            &SERVICES,
+        )
+        .unwrap();
+        assert!(overrides.skip_tls_verify("cloudcontrol"));
+        for service in ["ec2", "sts"] {
+            assert_eq!(
+                overrides.resolve(service, service, "us-east-1"),
+                Ok(format!("https://{service}.us-east-1.amazonaws.com"))
+            );
+            assert!(!overrides.skip_tls_verify(service), "{service}");
+        }
+    }
+}
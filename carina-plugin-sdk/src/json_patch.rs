@@ -0,0 +1,263 @@
+//! Minimal JSON Patch (RFC 6902) documents for update APIs.
+//!
+//! Update APIs such as Cloud Control's `UpdateResource` take a JSON Patch
+//! against the resource's current properties. Replacing a whole attribute
+//! for every change (the entire `Tags` array when one tag's value moves)
+//! makes the patch touch properties the user never changed, which some
+//! handlers reject and which reads poorly in audit logs. [`diff`] computes
+//! the smallest set of `add` / `remove` / `replace` operations that turns
+//! the previously read properties into the desired ones, removing only
+//! properties the configuration used to set.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// One JSON Patch operation. Serializes to the RFC 6902 wire form,
+/// e.g. `{"op":"replace","path":"/Tags/0/Value","value":"prod"}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// Operations that turn `previous` into `desired`.
+///
+/// `previous` is what the provider read back, so it also holds
+/// properties the user never set: server defaults, computed values,
+/// keys added by other tools. `previous_desired` is the desired document
+/// of the last apply, and decides which of those the user manages: an
+/// object key absent from `desired` is removed only when
+/// `previous_desired` has it at the same path, i.e. the user dropped it
+/// from the configuration. Every other key missing from `desired` is
+/// left alone. Pass [`Value::Null`] when there was no earlier apply
+/// (e.g. right after an import), which removes nothing.
+///
+/// Objects are diffed key by key and arrays element by element, with
+/// trailing elements added or removed. An array whose element-wise
+/// operations would touch every element (an insertion near the front
+/// shifts all later ones) is replaced as a whole, which is both shorter
+/// and easier to read. Equal documents produce no operations.
+pub fn diff(previous: &Value, desired: &Value, previous_desired: &Value) -> Vec<PatchOperation> {
+    let mut ops = Vec::new();
+    diff_at(
+        &mut String::new(),
+        previous,
+        desired,
+        previous_desired,
+        &mut ops,
+    );
+    ops
+}
+
+fn diff_at(
+    path: &mut String,
+    previous: &Value,
+    desired: &Value,
+    managed: &Value,
+    ops: &mut Vec<PatchOperation>,
+) {
+    if previous == desired {
+        return;
+    }
+    match (previous, desired) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old
+                .keys()
+                .filter(|k| !new.contains_key(*k) && managed.get(k).is_some())
+            {
+                ops.push(PatchOperation::Remove {
+                    path: child_path(path, key),
+                });
+            }
+            for (key, value) in new {
+                match old.get(key) {
+                    Some(old_value) => {
+                        let len = path.len();
+                        path.push_str(&child_path("", key));
+                        let managed = managed.get(key).unwrap_or(&Value::Null);
+                        diff_at(path, old_value, value, managed, ops);
+                        path.truncate(len);
+                    }
+                    None => ops.push(PatchOperation::Add {
+                        path: child_path(path, key),
+                        value: value.clone(),
+                    }),
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if !path.is_empty() => {
+            let mut element_ops = Vec::new();
+            for (i, (old_item, new_item)) in old.iter().zip(new).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{i}"));
+                let managed = managed.get(i).unwrap_or(&Value::Null);
+                diff_at(path, old_item, new_item, managed, &mut element_ops);
+                path.truncate(len);
+            }
+            for (i, item) in new.iter().enumerate().skip(old.len()) {
+                element_ops.push(PatchOperation::Add {
+                    path: format!("{path}/{i}"),
+                    value: item.clone(),
+                });
+            }
+            // Remove from the end so each index is still valid when
+            // its operation is applied.
+            for i in (new.len()..old.len()).rev() {
+                element_ops.push(PatchOperation::Remove {
+                    path: format!("{path}/{i}"),
+                });
+            }
+            if element_ops.len() > 1 && element_ops.len() >= old.len().max(new.len()) {
+                ops.push(replace(path, desired));
+            } else {
+                ops.extend(element_ops);
+            }
+        }
+        _ => ops.push(replace(path, desired)),
+    }
+}
+
+fn replace(path: &str, value: &Value) -> PatchOperation {
+    PatchOperation::Replace {
+        path: path.to_string(),
+        value: value.clone(),
+    }
+}
+
+/// `parent` extended by `key`, escaped as a JSON Pointer token.
+fn child_path(parent: &str, key: &str) -> String {
+    format!("{parent}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn single_tag_change_patches_only_that_value() {
+        let previous = json!({
+            "CidrBlock": "10.0.0.0/16",
+            "Tags": [
+                { "Key": "Name", "Value": "main" },
+                { "Key": "Env", "Value": "dev" },
+            ],
+        });
+        let mut desired = previous.clone();
+        desired["Tags"][1]["Value"] = json!("prod");
+
+        assert_eq!(
+            diff(&previous, &desired, &previous),
+            [PatchOperation::Replace {
+                path: "/Tags/1/Value".to_string(),
+                value: json!("prod"),
+            }]
+        );
+        assert!(diff(&previous, &previous, &previous).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_properties() {
+        let previous = json!({ "EnableDnsSupport": true, "InstanceTenancy": "default" });
+        let desired = json!({ "EnableDnsSupport": true, "EnableDnsHostnames": true });
+
+        assert_eq!(
+            diff(&previous, &desired, &previous),
+            [
+                PatchOperation::Remove {
+                    path: "/InstanceTenancy".to_string(),
+                },
+                PatchOperation::Add {
+                    path: "/EnableDnsHostnames".to_string(),
+                    value: json!(true),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn only_keys_the_last_apply_set_are_removed() {
+        // `InstanceTenancy` is a server default the user never wrote;
+        // `EnableDnsHostnames` was in the configuration and was dropped.
+        let previous = json!({
+            "EnableDnsHostnames": true,
+            "InstanceTenancy": "default",
+            "Labels": { "team": "infra", "aws:created-by": "console" },
+        });
+        let previous_desired = json!({
+            "EnableDnsHostnames": true,
+            "Labels": { "team": "infra" },
+        });
+        let desired = json!({ "Labels": {} });
+
+        assert_eq!(
+            diff(&previous, &desired, &previous_desired),
+            [
+                PatchOperation::Remove {
+                    path: "/EnableDnsHostnames".to_string(),
+                },
+                PatchOperation::Remove {
+                    path: "/Labels/team".to_string(),
+                },
+            ]
+        );
+        assert!(diff(&previous, &json!({}), &Value::Null).is_empty());
+    }
+
+    #[test]
+    fn arrays_grow_and_shrink_at_the_end() {
+        let previous = json!({ "Ports": [22, 80, 443, 8080] });
+        let desired = json!({ "Ports": [22, 80] });
+        assert_eq!(
+            diff(&previous, &desired, &previous),
+            [
+                PatchOperation::Remove {
+                    path: "/Ports/3".to_string(),
+                },
+                PatchOperation::Remove {
+                    path: "/Ports/2".to_string(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            diff(&desired, &previous, &desired),
+            [
+                PatchOperation::Add {
+                    path: "/Ports/2".to_string(),
+                    value: json!(443),
+                },
+                PatchOperation::Add {
+                    path: "/Ports/3".to_string(),
+                    value: json!(8080),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shifted_array_is_replaced_whole() {
+        let previous = json!({ "Ports": [80, 443] });
+        let desired = json!({ "Ports": [22, 80, 443] });
+        assert_eq!(
+            diff(&previous, &desired, &previous),
+            [PatchOperation::Replace {
+                path: "/Ports".to_string(),
+                value: json!([22, 80, 443]),
+            }]
+        );
+    }
+
+    #[test]
+    fn keys_are_escaped_and_ops_serialize_to_rfc_6902() {
+        let previous = json!({ "Labels": { "a/b": "1", "c~d": "2" } });
+        let desired = json!({ "Labels": { "a/b": "3", "c~d": "2" } });
+        let ops = diff(&previous, &desired, &previous);
+        assert_eq!(
+            serde_json::to_value(&ops).unwrap(),
+            json!([{ "op": "replace", "path": "/Labels/a~1b", "value": "3" }])
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! in your `main()` to start the JSON-RPC server loop.
 
 pub use carina_provider_protocol as protocol;
+pub use carina_provider_protocol::pricing;
 pub use carina_provider_protocol::types;
 
 #[cfg(target_arch = "wasm32")]
@@ -13,6 +14,14 @@ pub mod wasm_guest;
 #[cfg(target_arch = "wasm32")]
 pub mod wasi_http;
 
+pub mod credentials;
+pub mod endpoints;
+pub mod json_patch;
+pub mod lookup;
+pub mod partition;
+pub mod rate_limit;
+pub mod time;
+
 // `wasi_http_body` is compiled for every target so its pure-Rust
 // classification helpers can be unit-tested on the host. The only
 // non-test caller (`wasi_http::make_request`) is wasm32-only, so on
@@ -58,6 +67,7 @@ use carina_provider_protocol::methods;
 use carina_provider_protocol::types::*;
 use std::collections::HashMap;
 use std::io::{self, BufRead, Write};
+use std::time::SystemTime;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlanOp {
@@ -183,6 +193,74 @@ pub trait CarinaProvider {
         Vec::new()
     }
 
+    /// List every remote resource tagged `carina:project = project`,
+    /// whether or not it is in state. `Ok(None)` (the default) means the
+    /// provider cannot enumerate its resources.
+    fn list_project_resources(&self, _project: &str) -> Result<Option<Vec<State>>, ProviderError> {
+        Ok(None)
+    }
+
+    /// Estimated monthly cost of `resource`, for the cost summary after
+    /// `carina plan`. Price it for this provider's configuration, e.g.
+    /// with [`pricing::aws_monthly_cost`] for its region. `None` (the
+    /// default) leaves the type unpriced.
+    fn monthly_cost(&self, _resource: &Resource) -> Option<MonthlyCost> {
+        None
+    }
+
+    /// Remote objects that would make deleting `id` fail. With
+    /// `DeleteRequest::force_dependencies` set, `delete` should remove
+    /// the ones marked `removable` first.
+    fn delete_blockers(
+        &self,
+        _id: &ResourceId,
+        _identifier: &str,
+    ) -> Result<Vec<DeleteBlocker>, ProviderError> {
+        Ok(Vec::new())
+    }
+
+    /// Best-effort dry run of applying `resource`, run at plan time when
+    /// the user asks for simulation: check it against the provider's own
+    /// resource schema and confirm the identifiers it references exist.
+    /// Findings with [`DiagnosticSeverity::Error`] fail the plan.
+    fn simulate(&self, _resource: &Resource) -> Result<Vec<ResourceDiagnostic>, ProviderError> {
+        Ok(Vec::new())
+    }
+
+    /// Timing counters for the create, update or delete of `id` that just
+    /// returned, sent back with its result for the end-of-apply summary.
+    fn operation_metrics(&self, _id: &ResourceId) -> Option<OperationMetrics> {
+        None
+    }
+
+    /// When the credentials the provider holds expire, so the host can
+    /// warn before an apply that would outlast them. `None` when they do
+    /// not expire, or when the provider refreshes them itself (see
+    /// [`credentials::CredentialCache`]).
+    fn credential_expiry(&self) -> Option<SystemTime> {
+        None
+    }
+
+    /// Cloud identifier of an existing remote resource that creating
+    /// `resource` would duplicate, looked up by its unique name. Called
+    /// before each create; `None` means no match.
+    fn find_existing(
+        &self,
+        _id: &ResourceId,
+        _resource: &Resource,
+    ) -> Result<Option<String>, ProviderError> {
+        Ok(None)
+    }
+
+    /// What this provider can and cannot do, asked once after
+    /// `initialize`: resource types with no in-place update, import and
+    /// tagging support, and a cap on concurrent operations. Distinct
+    /// from [`capabilities`](Self::capabilities), which lists the
+    /// optional methods implemented.
+    fn provider_capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::default()
+    }
+
     /// Return provider config attribute completions.
     /// Key is attribute name (e.g., "region"), value is list of completion candidates.
     fn config_completions(&self) -> HashMap<String, Vec<CompletionValue>> {
@@ -325,6 +403,11 @@ fn dispatch(provider: &mut impl CarinaProvider, request: &Request) -> Response {
             Response::success(id, methods::SchemasResult { schemas })
         }
 
+        "provider_config_attribute_types" => {
+            let types = provider.provider_config_attribute_types();
+            Response::success(id, methods::ProviderConfigAttributeTypesResult { types })
+        }
+
         "initialize" => {
             let params: methods::InitializeParams = match parse_params(&request.params) {
                 Ok(p) => p,
@@ -343,18 +426,109 @@ fn dispatch(provider: &mut impl CarinaProvider, request: &Request) -> Response {
             };
             match provider.read(&params.id, params.identifier.as_deref(), params.request) {
                 Ok(state) => Response::success(id, methods::ReadResult { state }),
-                Err(e) => Response::error(id, -1, e.message),
+                Err(e) => provider_error_response(id, e),
             }
         }
 
+        "read_data_source" => {
+            let params: methods::ReadDataSourceParams = match parse_params(&request.params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, -32602, e),
+            };
+            match provider.read_data_source(&params.resource) {
+                Ok(state) => Response::success(id, methods::ReadDataSourceResult { state }),
+                Err(e) => provider_error_response(id, e),
+            }
+        }
+
+        "delete_blockers" => {
+            let params: methods::DeleteBlockersParams = match parse_params(&request.params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, -32602, e),
+            };
+            match provider.delete_blockers(&params.id, &params.identifier) {
+                Ok(blockers) => Response::success(id, methods::DeleteBlockersResult { blockers }),
+                Err(e) => provider_error_response(id, e),
+            }
+        }
+
+        "find_existing" => {
+            let params: methods::FindExistingParams = match parse_params(&request.params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, -32602, e),
+            };
+            match provider.find_existing(&params.id, &params.resource) {
+                Ok(identifier) => Response::success(id, methods::FindExistingResult { identifier }),
+                Err(e) => provider_error_response(id, e),
+            }
+        }
+
+        "simulate" => {
+            let params: methods::SimulateParams = match parse_params(&request.params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, -32602, e),
+            };
+            match provider.simulate(&params.resource) {
+                Ok(diagnostics) => Response::success(id, methods::SimulateResult { diagnostics }),
+                Err(e) => provider_error_response(id, e),
+            }
+        }
+
+        "credential_expiry" => Response::success(
+            id,
+            methods::CredentialExpiryResult {
+                expires_at: provider
+                    .credential_expiry()
+                    .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs()),
+            },
+        ),
+
+        "capabilities" => Response::success(
+            id,
+            methods::CapabilitiesResult {
+                capabilities: provider.provider_capabilities(),
+            },
+        ),
+
+        "list_project_resources" => {
+            let params: methods::ListProjectResourcesParams = match parse_params(&request.params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, -32602, e),
+            };
+            match provider.list_project_resources(&params.project) {
+                Ok(states) => Response::success(id, methods::ListProjectResourcesResult { states }),
+                Err(e) => provider_error_response(id, e),
+            }
+        }
+
+        "monthly_cost" => {
+            let params: methods::MonthlyCostParams = match parse_params(&request.params) {
+                Ok(p) => p,
+                Err(e) => return Response::error(id, -32602, e),
+            };
+            Response::success(
+                id,
+                methods::MonthlyCostResult {
+                    cost: provider.monthly_cost(&params.resource),
+                },
+            )
+        }
+
         "create" => {
             let params: methods::CreateParams = match parse_params(&request.params) {
                 Ok(p) => p,
                 Err(e) => return Response::error(id, -32602, e),
             };
             match provider.create(&params.id, params.request) {
-                Ok(outcome) => Response::success(id, methods::CreateResult { outcome }),
-                Err(e) => Response::error(id, -1, e.message),
+                Ok(outcome) => Response::success(
+                    id,
+                    methods::CreateResult {
+                        outcome,
+                        metrics: provider.operation_metrics(&params.id),
+                    },
+                ),
+                Err(e) => provider_error_response(id, e),
             }
         }
 
@@ -364,8 +538,14 @@ fn dispatch(provider: &mut impl CarinaProvider, request: &Request) -> Response {
                 Err(e) => return Response::error(id, -32602, e),
             };
             match provider.update(&params.id, &params.identifier, params.request) {
-                Ok(outcome) => Response::success(id, methods::UpdateResult { outcome }),
-                Err(e) => Response::error(id, -1, e.message),
+                Ok(outcome) => Response::success(
+                    id,
+                    methods::UpdateResult {
+                        outcome,
+                        metrics: provider.operation_metrics(&params.id),
+                    },
+                ),
+                Err(e) => provider_error_response(id, e),
             }
         }
 
@@ -375,8 +555,14 @@ fn dispatch(provider: &mut impl CarinaProvider, request: &Request) -> Response {
                 Err(e) => return Response::error(id, -32602, e),
             };
             match provider.delete(&params.id, &params.identifier, params.request) {
-                Ok(()) => Response::success(id, methods::DeleteResult { ok: true }),
-                Err(e) => Response::error(id, -1, e.message),
+                Ok(()) => Response::success(
+                    id,
+                    methods::DeleteResult {
+                        ok: true,
+                        metrics: provider.operation_metrics(&params.id),
+                    },
+                ),
+                Err(e) => provider_error_response(id, e),
             }
         }
 
@@ -424,6 +610,16 @@ fn dispatch(provider: &mut impl CarinaProvider, request: &Request) -> Response {
     }
 }
 
+/// Carry the full [`ProviderError`] in the JSON-RPC error's `data` so the
+/// host keeps the error kind and cloud-API metadata, not just the message.
+fn provider_error_response(id: u64, error: ProviderError) -> Response {
+    let mut response = Response::error(id, -1, error.message.clone());
+    if let Some(rpc_error) = response.error.as_mut() {
+        rpc_error.data = serde_json::to_value(&error).ok();
+    }
+    response
+}
+
 fn parse_params<T: serde::de::DeserializeOwned>(
     params: &Option<serde_json::Value>,
 ) -> Result<T, String> {
@@ -0,0 +1,118 @@
+//! Resolving a remote resource referred to by name to its identifier.
+//!
+//! Providers often let a configuration point at an existing resource by
+//! name, most commonly a `Name` tag (a VPC, a subnet, a security group).
+//! Names are not unique: several VPCs can share a `Name` tag, and picking
+//! the first one the API lists risks updating or deleting the wrong
+//! resource. [`NameLookup::resolve`] refuses to guess, returning a
+//! [`ProviderErrorKind::AmbiguousMatch`] error that lists every
+//! candidate, and honors an explicit identifier attribute (`vpc_id`)
+//! that the configuration sets to pick one.
+
+use carina_provider_protocol::types::{ProviderError, ProviderErrorKind};
+
+/// A lookup of one remote resource by name.
+#[derive(Debug, Clone, Copy)]
+pub struct NameLookup<'a> {
+    /// What is looked up, for messages, e.g. `"VPC"`.
+    pub kind: &'a str,
+    /// The name searched for.
+    pub name: &'a str,
+    /// Attribute that takes the identifier directly, e.g. `vpc_id`.
+    /// Named in the ambiguity error as the way to pick one.
+    pub id_attribute: &'a str,
+    /// Value of `id_attribute` in the configuration, if set.
+    pub explicit_id: Option<&'a str>,
+}
+
+impl NameLookup<'_> {
+    /// Pick the identifier the lookup refers to among `candidates`, the
+    /// identifiers of every resource carrying the name.
+    ///
+    /// An explicit identifier wins outright, so callers may skip the
+    /// name search when one is set. Otherwise no candidate is `None`,
+    /// one is that candidate, and more than one is an `AmbiguousMatch`
+    /// error listing them all.
+    #[allow(clippy::result_large_err)]
+    pub fn resolve(&self, mut candidates: Vec<String>) -> Result<Option<String>, ProviderError> {
+        if let Some(id) = self.explicit_id {
+            return Ok(Some(id.to_string()));
+        }
+        candidates.sort();
+        candidates.dedup();
+        if candidates.len() <= 1 {
+            return Ok(candidates.pop());
+        }
+        Err(ProviderError {
+            kind: ProviderErrorKind::AmbiguousMatch,
+            message: format!(
+                "{} {}s are named '{}' ({}); set {} to choose one",
+                candidates.len(),
+                self.kind,
+                self.name,
+                candidates.join(", "),
+                self.id_attribute
+            ),
+            resource_id: None,
+            cause: None,
+            provider_name: None,
+            operation: None,
+            status: None,
+            code: None,
+            request_id: None,
+            candidates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vpc_lookup(explicit_id: Option<&str>) -> NameLookup<'_> {
+        NameLookup {
+            kind: "VPC",
+            name: "main",
+            id_attribute: "vpc_id",
+            explicit_id,
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn unique_or_missing_names_resolve() {
+        let lookup = vpc_lookup(None);
+        assert_eq!(lookup.resolve(Vec::new()).unwrap(), None);
+        assert_eq!(
+            lookup.resolve(ids(&["vpc-1"])).unwrap().as_deref(),
+            Some("vpc-1")
+        );
+    }
+
+    #[test]
+    fn shared_names_are_ambiguous_and_list_every_candidate() {
+        let err = vpc_lookup(None)
+            .resolve(ids(&["vpc-2", "vpc-1"]))
+            .unwrap_err();
+        assert_eq!(err.kind, ProviderErrorKind::AmbiguousMatch);
+        assert_eq!(err.candidates, ["vpc-1", "vpc-2"]);
+        assert_eq!(
+            err.message,
+            "2 VPCs are named 'main' (vpc-1, vpc-2); set vpc_id to choose one"
+        );
+    }
+
+    #[test]
+    fn explicit_id_disambiguates() {
+        assert_eq!(
+            vpc_lookup(Some("vpc-2"))
+                .resolve(ids(&["vpc-1", "vpc-2"]))
+                .unwrap()
+                .as_deref(),
+            Some("vpc-2")
+        );
+    }
+}
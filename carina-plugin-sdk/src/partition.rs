@@ -0,0 +1,221 @@
+//! AWS partitions: which region belongs where, and what that changes.
+//!
+//! Regions are grouped into partitions with their own endpoints and ARN
+//! prefix. A provider that hardcodes `amazonaws.com` and `arn:aws:` works
+//! in the standard partition but builds endpoints that do not resolve in
+//! China (`amazonaws.com.cn`) and ARNs that GovCloud rejects
+//! (`arn:aws-us-gov:`). [`Partition::for_region`] picks the partition,
+//! [`Partition::endpoint`], [`Partition::endpoint_variant`] (FIPS and
+//! dual-stack) and [`Partition::arn`] build names inside it,
+//! and [`Partition::check_region`] / [`Partition::check_arn`] turn a
+//! cross-partition mix-up into a clear validation error instead of an
+//! opaque API failure.
+
+/// One AWS partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    /// Partition id as it appears in ARNs, e.g. `aws-us-gov`.
+    pub id: &'static str,
+    /// Domain suffix of service endpoints, e.g. `amazonaws.com.cn`.
+    pub dns_suffix: &'static str,
+    /// Domain suffix of dual-stack (IPv4 and IPv6) endpoints.
+    pub dualstack_dns_suffix: &'static str,
+    /// Whether services offer FIPS 140 validated endpoints here.
+    pub supports_fips: bool,
+    /// Region name prefix of the partition's regions; empty for the
+    /// standard partition, which takes every other region.
+    region_prefix: &'static str,
+}
+
+/// The standard partition.
+pub const AWS: Partition = Partition {
+    id: "aws",
+    dns_suffix: "amazonaws.com",
+    dualstack_dns_suffix: "api.aws",
+    supports_fips: true,
+    region_prefix: "",
+};
+
+/// AWS GovCloud (US).
+pub const AWS_US_GOV: Partition = Partition {
+    id: "aws-us-gov",
+    dns_suffix: "amazonaws.com",
+    dualstack_dns_suffix: "api.aws",
+    supports_fips: true,
+    region_prefix: "us-gov-",
+};
+
+/// AWS China.
+pub const AWS_CN: Partition = Partition {
+    id: "aws-cn",
+    dns_suffix: "amazonaws.com.cn",
+    dualstack_dns_suffix: "api.amazonwebservices.com.cn",
+    supports_fips: false,
+    region_prefix: "cn-",
+};
+
+const PARTITIONS: [Partition; 3] = [AWS, AWS_US_GOV, AWS_CN];
+
+/// Which endpoint variant to use. The default is the plain regional
+/// endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndpointVariant {
+    /// FIPS 140 validated endpoints, for regulated environments.
+    pub fips: bool,
+    /// Endpoints reachable over IPv6 as well as IPv4.
+    pub dualstack: bool,
+}
+
+impl Partition {
+    /// Partition of `region`. Regions outside GovCloud and China are in
+    /// the standard partition, including ones newer than this table.
+    pub fn for_region(region: &str) -> Partition {
+        PARTITIONS
+            .into_iter()
+            .find(|p| !p.region_prefix.is_empty() && region.starts_with(p.region_prefix))
+            .unwrap_or(AWS)
+    }
+
+    /// Partition with the ARN id `id`.
+    pub fn by_id(id: &str) -> Option<Partition> {
+        PARTITIONS.into_iter().find(|p| p.id == id)
+    }
+
+    /// HTTPS endpoint of `service` (its endpoint prefix, e.g.
+    /// `cloudcontrolapi`) in `region`.
+    pub fn endpoint(&self, service: &str, region: &str) -> String {
+        format!("https://{service}.{region}.{}", self.dns_suffix)
+    }
+
+    /// Like [`Partition::endpoint`], for the FIPS and/or dual-stack
+    /// variant: `service-fips` as the host label for FIPS, the
+    /// partition's dual-stack domain for dual-stack.
+    pub fn endpoint_variant(
+        &self,
+        service: &str,
+        region: &str,
+        variant: EndpointVariant,
+    ) -> Result<String, String> {
+        if variant.fips && !self.supports_fips {
+            return Err(format!(
+                "FIPS endpoints are not available in partition '{}' (region '{region}')",
+                self.id
+            ));
+        }
+        let label = if variant.fips {
+            format!("{service}-fips")
+        } else {
+            service.to_string()
+        };
+        let suffix = if variant.dualstack {
+            self.dualstack_dns_suffix
+        } else {
+            self.dns_suffix
+        };
+        Ok(format!("https://{label}.{region}.{suffix}"))
+    }
+
+    /// ARN in this partition. `region` and `account` are empty for
+    /// global resources, e.g. `arn:aws-cn:s3:::bucket`.
+    pub fn arn(&self, service: &str, region: &str, account: &str, resource: &str) -> String {
+        format!("arn:{}:{service}:{region}:{account}:{resource}", self.id)
+    }
+
+    /// Error unless `region` belongs to this partition, for a provider
+    /// configured with an explicit partition.
+    pub fn check_region(&self, region: &str) -> Result<(), String> {
+        let actual = Partition::for_region(region);
+        if actual == *self {
+            Ok(())
+        } else {
+            Err(format!(
+                "region '{region}' is in partition '{}', not '{}'",
+                actual.id, self.id
+            ))
+        }
+    }
+
+    /// Error unless `arn` is in this partition. A malformed ARN is left
+    /// to the attribute's own format validation.
+    pub fn check_arn(&self, arn: &str) -> Result<(), String> {
+        let mut parts = arn.splitn(3, ':');
+        match (parts.next(), parts.next()) {
+            (Some("arn"), Some(id)) if id != self.id => Err(format!(
+                "ARN '{arn}' is in partition '{id}', but the provider is in '{}'; \
+                 use 'arn:{}:...'",
+                self.id, self.id
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_map_to_partitions() {
+        assert_eq!(Partition::for_region("us-east-1"), AWS);
+        assert_eq!(Partition::for_region("ap-southeast-7"), AWS);
+        assert_eq!(Partition::for_region("us-gov-west-1"), AWS_US_GOV);
+        assert_eq!(Partition::for_region("cn-northwest-1"), AWS_CN);
+        assert_eq!(Partition::by_id("aws-cn"), Some(AWS_CN));
+        assert_eq!(Partition::by_id("aws-iso"), None);
+    }
+
+    #[test]
+    fn endpoints_and_arns_use_the_partition() {
+        let cn = Partition::for_region("cn-north-1");
+        assert_eq!(
+            cn.endpoint("cloudcontrolapi", "cn-north-1"),
+            "https://cloudcontrolapi.cn-north-1.amazonaws.com.cn"
+        );
+        assert_eq!(
+            AWS_US_GOV.arn("iam", "", "123456789012", "role/deploy"),
+            "arn:aws-us-gov:iam::123456789012:role/deploy"
+        );
+    }
+
+    #[test]
+    fn fips_and_dualstack_variants() {
+        let both = EndpointVariant {
+            fips: true,
+            dualstack: true,
+        };
+        assert_eq!(
+            AWS.endpoint_variant("ec2", "us-east-1", EndpointVariant::default()),
+            Ok(AWS.endpoint("ec2", "us-east-1"))
+        );
+        assert_eq!(
+            AWS_US_GOV.endpoint_variant("cloudcontrolapi", "us-gov-west-1", both),
+            Ok("https://cloudcontrolapi-fips.us-gov-west-1.api.aws".to_string())
+        );
+        let dualstack = EndpointVariant {
+            dualstack: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            AWS_CN.endpoint_variant("sts", "cn-north-1", dualstack),
+            Ok("https://sts.cn-north-1.api.amazonwebservices.com.cn".to_string())
+        );
+        let err = AWS_CN
+            .endpoint_variant("sts", "cn-north-1", both)
+            .unwrap_err();
+        assert!(err.contains("not available in partition 'aws-cn'"), "{err}");
+    }
+
+    #[test]
+    fn cross_partition_regions_and_arns_are_rejected() {
+        assert!(AWS_US_GOV.check_region("us-gov-east-1").is_ok());
+        let err = AWS_US_GOV.check_region("us-east-1").unwrap_err();
+        assert!(err.contains("partition 'aws'"), "{err}");
+
+        assert!(AWS_CN.check_arn("arn:aws-cn:s3:::logs").is_ok());
+        let err = AWS_CN
+            .check_arn("arn:aws:iam::aws:policy/ReadOnlyAccess")
+            .unwrap_err();
+        assert!(err.contains("use 'arn:aws-cn:...'"), "{err}");
+        assert!(AWS_CN.check_arn("not-an-arn").is_ok());
+    }
+}
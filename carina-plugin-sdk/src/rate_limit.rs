@@ -0,0 +1,203 @@
+//! Client-side rate limiting shared by a provider's API clients.
+//!
+//! Cloud APIs throttle per account, not per client: a large parallel
+//! apply that drives Cloud Control and EC2 at full speed gets throttled
+//! and then spends its time in backoff, slowing down everyone else using
+//! the account too. A provider builds one [`RateLimiter`], hands a clone
+//! to each client, and calls [`RateLimiter::acquire`] before every
+//! request. Reads and writes are separate token buckets
+//! ([`ApiFamily`]), since APIs usually give describe calls a much higher
+//! limit than mutating ones.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::time::{Clock, Sleeper};
+
+/// Which bucket a request draws from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiFamily {
+    /// Read-only calls: `Describe*`, `Get*`, `List*`.
+    Describe,
+    /// Everything else: creates, updates, deletes, tagging.
+    Mutate,
+}
+
+impl ApiFamily {
+    /// Family of an API operation, by its name's verb prefix
+    /// (`DescribeVpcs`, `GetResource`, `ListResources` are reads).
+    pub fn of(operation: &str) -> Self {
+        const READ_PREFIXES: [&str; 4] = ["Describe", "Get", "List", "Read"];
+        if READ_PREFIXES.iter().any(|p| operation.starts_with(p)) {
+            ApiFamily::Describe
+        } else {
+            ApiFamily::Mutate
+        }
+    }
+}
+
+/// Sustained rate and burst size of one token bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub per_second: f64,
+    /// Requests allowed back to back after an idle period.
+    pub burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+}
+
+/// Limits per [`ApiFamily`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimits {
+    pub describe: RateLimit,
+    pub mutate: RateLimit,
+}
+
+impl Default for RateLimits {
+    /// Conservative enough to leave headroom under typical AWS account
+    /// limits when several tools share the account.
+    fn default() -> Self {
+        Self {
+            describe: RateLimit::new(10.0, 20),
+            mutate: RateLimit::new(2.0, 5),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: RateLimit,
+    /// Tokens available at `updated`. Negative when waiters have
+    /// reserved tokens that have not been refilled yet.
+    tokens: f64,
+    updated: Option<Duration>,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            updated: None,
+        }
+    }
+
+    /// Take one token at `now`, returning how long the caller must wait
+    /// before its request may go out.
+    fn reserve(&mut self, now: Duration) -> Duration {
+        if let Some(updated) = self.updated {
+            let refill = now.saturating_sub(updated).as_secs_f64() * self.limit.per_second;
+            self.tokens = (self.tokens + refill).min(f64::from(self.limit.burst));
+        }
+        self.updated = Some(now);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 || self.limit.per_second <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.limit.per_second)
+        }
+    }
+}
+
+/// Token buckets shared by every clone, so all clients of a provider
+/// draw from the same budget.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<[Bucket; 2]>>,
+}
+
+impl RateLimiter {
+    /// A limiter with `limits`. A `per_second` of zero or less disables
+    /// limiting for that family.
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            buckets: Arc::new(Mutex::new([
+                Bucket::new(limits.describe),
+                Bucket::new(limits.mutate),
+            ])),
+        }
+    }
+
+    /// Block until a request of `family` may be sent. Waiters are served
+    /// in call order: each reserves its token before sleeping, so a
+    /// burst of callers spreads out at the configured rate.
+    pub fn acquire(&self, family: ApiFamily, clock: &dyn Clock, sleeper: &dyn Sleeper) {
+        let wait = {
+            let mut buckets = self.buckets.lock().unwrap();
+            let bucket = match family {
+                ApiFamily::Describe => &mut buckets[0],
+                ApiFamily::Mutate => &mut buckets[1],
+            };
+            bucket.reserve(clock.now())
+        };
+        if !wait.is_zero() {
+            sleeper.sleep(wait);
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimits::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::ManualClock;
+
+    #[test]
+    fn families_by_operation_name() {
+        assert_eq!(ApiFamily::of("DescribeVpcs"), ApiFamily::Describe);
+        assert_eq!(
+            ApiFamily::of("GetResourceRequestStatus"),
+            ApiFamily::Describe
+        );
+        assert_eq!(ApiFamily::of("ListResources"), ApiFamily::Describe);
+        assert_eq!(ApiFamily::of("CreateResource"), ApiFamily::Mutate);
+        assert_eq!(ApiFamily::of("CreateTags"), ApiFamily::Mutate);
+    }
+
+    #[test]
+    fn burst_passes_then_callers_are_spaced_at_the_rate() {
+        let clock = ManualClock::new();
+        let limiter = RateLimiter::new(RateLimits {
+            describe: RateLimit::new(100.0, 100),
+            mutate: RateLimit::new(2.0, 2),
+        });
+        for _ in 0..4 {
+            limiter.acquire(ApiFamily::Mutate, &clock, &clock);
+        }
+        assert_eq!(
+            clock.sleeps(),
+            [Duration::from_millis(500), Duration::from_millis(500)]
+        );
+
+        // Describe calls draw from their own bucket.
+        limiter.acquire(ApiFamily::Describe, &clock, &clock);
+        assert_eq!(clock.sleeps().len(), 2);
+    }
+
+    #[test]
+    fn clones_share_one_budget_and_idle_time_refills() {
+        let clock = ManualClock::new();
+        let cloud_control = RateLimiter::new(RateLimits {
+            describe: RateLimit::new(1.0, 1),
+            mutate: RateLimit::new(1.0, 1),
+        });
+        let ec2 = cloud_control.clone();
+
+        cloud_control.acquire(ApiFamily::Mutate, &clock, &clock);
+        ec2.acquire(ApiFamily::Mutate, &clock, &clock);
+        assert_eq!(clock.sleeps(), [Duration::from_secs(1)]);
+
+        clock.advance(Duration::from_secs(10));
+        ec2.acquire(ApiFamily::Mutate, &clock, &clock);
+        assert_eq!(clock.sleeps().len(), 1, "burst is capped, then refilled");
+    }
+}
@@ -0,0 +1,295 @@
+//! Clock and sleep abstraction for provider polling loops.
+//!
+//! Provider operations run synchronously inside the WASM guest, where
+//! `tokio::time::sleep` is not available. Polling loops (e.g. a Cloud
+//! Control `wait_for_operation`) and throttling backoff take a [`Clock`]
+//! and a [`Sleeper`] instead of calling into a runtime directly, so the
+//! same loop runs:
+//!
+//! - natively, on `std::time::Instant` / `std::thread::sleep`;
+//! - under WASI, on `wasi:clocks/monotonic-clock`;
+//! - in tests, against [`ManualClock`], which advances virtual time on
+//!   every sleep so a ten-minute timeout is exercised without waiting.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Monotonic time source.
+pub trait Clock: Send + Sync {
+    /// Time elapsed since an arbitrary, fixed origin. Only differences
+    /// between two readings are meaningful.
+    fn now(&self) -> Duration;
+}
+
+/// Blocking sleep.
+pub trait Sleeper: Send + Sync {
+    /// Suspend the current operation for `duration`.
+    fn sleep(&self, duration: Duration);
+}
+
+/// Real clock and sleeper for the current target.
+///
+/// Uses `wasi:clocks/monotonic-clock` on `wasm32` and the standard
+/// library elsewhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        static ORIGIN: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        ORIGIN.get_or_init(std::time::Instant::now).elapsed()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Sleeper for SystemClock {
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(wasi::clocks::monotonic_clock::now())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Sleeper for SystemClock {
+    fn sleep(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        wasi::clocks::monotonic_clock::subscribe_duration(nanos).block();
+    }
+}
+
+/// Deterministic clock for tests.
+///
+/// Time only moves when [`ManualClock::advance`] or [`Sleeper::sleep`]
+/// is called; a sleep returns immediately after advancing the clock by
+/// the requested duration and is recorded in [`ManualClock::sleeps`].
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Mutex<Duration>,
+    sleeps: Mutex<Vec<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move virtual time forward without recording a sleep.
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+
+    /// Every duration passed to [`Sleeper::sleep`], in call order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.lock().unwrap().clone()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+}
+
+impl Sleeper for ManualClock {
+    fn sleep(&self, duration: Duration) {
+        self.sleeps.lock().unwrap().push(duration);
+        self.advance(duration);
+    }
+}
+
+/// Exponential backoff schedule: `initial * 2^attempt`, capped at `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    /// Retries after the first attempt. `0` disables retrying.
+    pub max_retries: u32,
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration, max_retries: u32) -> Self {
+        Self {
+            initial,
+            max,
+            max_retries,
+        }
+    }
+
+    /// Delay before retry number `attempt` (0-based).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        self.initial.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Run `op` until it succeeds, fails with a non-retryable error, or the
+/// backoff's retry budget is exhausted. `op` receives the 0-based
+/// attempt number. The last error is returned on exhaustion.
+pub fn retry_with_backoff<T, E>(
+    sleeper: &dyn Sleeper,
+    backoff: &Backoff,
+    is_retryable: impl Fn(&E) -> bool,
+    mut op: impl FnMut(u32) -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt = 0;
+    loop {
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < backoff.max_retries && is_retryable(&e) => {
+                sleeper.sleep(backoff.delay(attempt));
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Returned by [`poll_until`] when the deadline passes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollTimedOut {
+    pub elapsed: Duration,
+}
+
+/// Call `probe` every `interval` until it returns `Some`, or until
+/// `timeout` has elapsed on `clock`.
+///
+/// `probe` runs at least once. A probe that needs to abort the loop on
+/// error returns `Some(Err(..))`.
+pub fn poll_until<T>(
+    clock: &dyn Clock,
+    sleeper: &dyn Sleeper,
+    timeout: Duration,
+    interval: Duration,
+    mut probe: impl FnMut() -> Option<T>,
+) -> Result<T, PollTimedOut> {
+    let start = clock.now();
+    loop {
+        if let Some(value) = probe() {
+            return Ok(value);
+        }
+        let elapsed = clock.now().saturating_sub(start);
+        if elapsed >= timeout {
+            return Err(PollTimedOut { elapsed });
+        }
+        sleeper.sleep(interval.min(timeout - elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_clock_sleep_advances_virtual_time() {
+        let clock = ManualClock::new();
+        clock.sleep(Duration::from_secs(5));
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(clock.now(), Duration::from_secs(6));
+        assert_eq!(clock.sleeps(), vec![Duration::from_secs(5)]);
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5), 10);
+        assert_eq!(backoff.delay(0), Duration::from_secs(1));
+        assert_eq!(backoff.delay(2), Duration::from_secs(4));
+        assert_eq!(backoff.delay(3), Duration::from_secs(5));
+        assert_eq!(backoff.delay(40), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn retry_with_backoff_stops_on_success() {
+        let clock = ManualClock::new();
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(30), 5);
+        let result: Result<u32, &str> = retry_with_backoff(
+            &clock,
+            &backoff,
+            |_| true,
+            |attempt| {
+                if attempt < 2 {
+                    Err("throttled")
+                } else {
+                    Ok(attempt)
+                }
+            },
+        );
+        assert_eq!(result, Ok(2));
+        assert_eq!(
+            clock.sleeps(),
+            vec![Duration::from_secs(1), Duration::from_secs(2)]
+        );
+    }
+
+    #[test]
+    fn retry_with_backoff_returns_non_retryable_error_immediately() {
+        let clock = ManualClock::new();
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(30), 5);
+        let result: Result<(), &str> =
+            retry_with_backoff(&clock, &backoff, |e| *e == "throttled", |_| Err("denied"));
+        assert_eq!(result, Err("denied"));
+        assert!(clock.sleeps().is_empty());
+    }
+
+    #[test]
+    fn retry_with_backoff_exhausts_budget() {
+        let clock = ManualClock::new();
+        let backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(30), 2);
+        let mut calls = 0;
+        let result: Result<(), &str> = retry_with_backoff(
+            &clock,
+            &backoff,
+            |_| true,
+            |_| {
+                calls += 1;
+                Err("throttled")
+            },
+        );
+        assert_eq!(result, Err("throttled"));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn poll_until_returns_first_ready_value() {
+        let clock = ManualClock::new();
+        let mut polls = 0;
+        let result = poll_until(
+            &clock,
+            &clock,
+            Duration::from_secs(60),
+            Duration::from_secs(5),
+            || {
+                polls += 1;
+                (polls == 3).then_some("SUCCESS")
+            },
+        );
+        assert_eq!(result, Ok("SUCCESS"));
+        assert_eq!(clock.now(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn poll_until_times_out_on_virtual_clock() {
+        let clock = ManualClock::new();
+        let result: Result<(), _> = poll_until(
+            &clock,
+            &clock,
+            Duration::from_secs(600),
+            Duration::from_secs(7),
+            || None,
+        );
+        assert_eq!(
+            result,
+            Err(PollTimedOut {
+                elapsed: Duration::from_secs(600)
+            })
+        );
+        // The last sleep is clamped so the loop never overshoots the deadline.
+        assert_eq!(clock.sleeps().last(), Some(&Duration::from_secs(5)));
+    }
+}
@@ -9,6 +9,8 @@
 //!   use to issue a plain HTTP request with only the `http` crate's
 //!   types (no AWS SDK dependency). This is how non-AWS providers (e.g.
 //!   the GitHub provider) talk to their REST APIs.
+//!   [`send_request_with_deadline`] is the same call with a per-request
+//!   timeout and a typed [`HttpError`].
 //! - [`WasiHttpClient`] — implements the AWS SDK's `HttpClient` trait by
 //!   translating its `HttpRequest`/`SdkBody` into the same neutral core,
 //!   so AWS SDK operations work inside a WASM component too.
@@ -37,7 +39,8 @@ use aws_smithy_types::body::SdkBody;
 
 use wasi::http::outgoing_handler;
 use wasi::http::types::{
-    Fields, IncomingBody, Method, OutgoingBody, OutgoingRequest, RequestOptions, Scheme,
+    ErrorCode, Fields, IncomingBody, Method, OutgoingBody, OutgoingRequest, RequestOptions, Scheme,
+    http_error_code,
 };
 use wasi::io::streams::StreamError;
 
@@ -46,6 +49,58 @@ use crate::wasi_http_body::{
     inject_content_length_header,
 };
 
+/// A failed wasi:http request.
+///
+/// Timeouts are kept apart from other failures: the host enforces
+/// per-phase timeouts plus a whole-request deadline, and a provider will
+/// usually want to retry a timed-out call rather than report it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpError {
+    /// A connect, response or body-read timeout expired.
+    Timeout(String),
+    /// Any other transport failure.
+    Other(String),
+}
+
+impl HttpError {
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, HttpError::Timeout(_))
+    }
+
+    fn from_code(context: &str, code: ErrorCode) -> Self {
+        let message = format!("{context}: {code:?}");
+        match code {
+            ErrorCode::ConnectionTimeout
+            | ErrorCode::ConnectionReadTimeout
+            | ErrorCode::ConnectionWriteTimeout
+            | ErrorCode::HttpResponseTimeout => HttpError::Timeout(message),
+            _ => HttpError::Other(message),
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::Timeout(message) | HttpError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+impl From<String> for HttpError {
+    fn from(message: String) -> Self {
+        HttpError::Other(message)
+    }
+}
+
+impl From<HttpError> for String {
+    fn from(error: HttpError) -> Self {
+        error.to_string()
+    }
+}
+
 /// When `CARINA_WASI_HTTP_TRACE=1` is set in the host environment, emit a
 /// per-phase wall-clock breakdown of each request to stderr.
 ///
@@ -153,9 +208,9 @@ fn build_request_options(
 
 /// A provider-agnostic HTTP request for the wasi:http transport core.
 ///
-/// Internal to this module — callers go through [`send_request`] (generic)
-/// or [`WasiHttpClient`] (AWS SDK), never construct this directly, so they
-/// cannot bypass the body-framing fixes in [`execute`].
+/// Internal to this module — callers go through [`send_request`] /
+/// [`send_request_with_deadline`] (generic) or [`WasiHttpClient`] (AWS
+/// SDK), never construct this directly, so they cannot bypass the body-framing fixes in [`execute`].
 struct WasiRequest {
     /// HTTP method, uppercase (`GET`, `PUT`, ...).
     method: String,
@@ -181,7 +236,10 @@ struct WasiResponse {
 /// [`send_request`] and the AWS SDK [`WasiHttpClient`] funnel through
 /// here, so the body-framing fixes (carina#3254 / #3320 / #3318) apply
 /// to every caller.
-fn execute(request: WasiRequest, options: Option<RequestOptions>) -> Result<WasiResponse, String> {
+fn execute(
+    request: WasiRequest,
+    options: Option<RequestOptions>,
+) -> Result<WasiResponse, HttpError> {
     let trace = trace_enabled();
     let req_start = if trace { Some(Instant::now()) } else { None };
     let trace_method = if trace {
@@ -303,7 +361,7 @@ fn execute(request: WasiRequest, options: Option<RequestOptions>) -> Result<Wasi
     // impl would block forever — the carina-rs/carina#3320 20-minute
     // host I/O hang.
     let future_response = outgoing_handler::handle(outgoing_req, options)
-        .map_err(|e| format!("outgoing-handler error: {e:?}"))?;
+        .map_err(|e| HttpError::from_code("outgoing-handler error", e))?;
     let t_handle_done = req_start.map(|s| s.elapsed());
 
     // Now ship the body. The hyper consumer is live, so each
@@ -324,7 +382,7 @@ fn execute(request: WasiRequest, options: Option<RequestOptions>) -> Result<Wasi
         .get()
         .ok_or_else(|| "Response not ready after block".to_string())?
         .map_err(|()| "Response already taken".to_string())?
-        .map_err(|e| format!("HTTP error: {e:?}"))?;
+        .map_err(|e| HttpError::from_code("HTTP error", e))?;
     let t_get_response_done = req_start.map(|s| s.elapsed());
 
     // Read response status
@@ -389,9 +447,24 @@ fn execute(request: WasiRequest, options: Option<RequestOptions>) -> Result<Wasi
 /// `http::Response<Vec<u8>>`.
 ///
 /// Errors are returned as `String` (the provider maps them to its own
-/// error type). The body-framing fixes in [`execute`] (carina#3254 /
+/// error type). Use [`send_request_with_deadline`] to tell timeouts apart
+/// from other failures. The body-framing fixes in [`execute`] (carina#3254 /
 /// #3320 / #3318) apply here exactly as they do for the AWS SDK path.
 pub fn send_request(request: http::Request<Vec<u8>>) -> Result<http::Response<Vec<u8>>, String> {
+    send_request_with_deadline(request, None).map_err(String::from)
+}
+
+/// [`send_request`] with an optional per-phase timeout and a typed error.
+///
+/// `timeout` bounds the connect, first-byte and between-bytes phases of
+/// this request; the host still caps each phase and the whole exchange,
+/// so a longer value than the host allows has no effect. Errors are
+/// returned as [`HttpError`]: [`HttpError::is_timeout`] picks out calls
+/// worth retrying.
+pub fn send_request_with_deadline(
+    request: http::Request<Vec<u8>>,
+    timeout: Option<Duration>,
+) -> Result<http::Response<Vec<u8>>, HttpError> {
     let (parts, body) = request.into_parts();
     let headers = parts
         .headers
@@ -405,7 +478,7 @@ pub fn send_request(request: http::Request<Vec<u8>>) -> Result<http::Response<Ve
         body,
     };
 
-    let wasi_resp = execute(wasi_req, None)?;
+    let wasi_resp = execute(wasi_req, build_request_options(timeout, timeout))?;
 
     let mut builder = http::Response::builder().status(wasi_resp.status);
     for (key, value) in wasi_resp.headers {
@@ -413,7 +486,7 @@ pub fn send_request(request: http::Request<Vec<u8>>) -> Result<http::Response<Ve
     }
     builder
         .body(wasi_resp.body)
-        .map_err(|e| format!("Failed to build response: {e}"))
+        .map_err(|e| HttpError::Other(format!("Failed to build response: {e}")))
 }
 
 /// Convert an AWS SDK HttpRequest to a [`WasiRequest`], run it through the
@@ -435,8 +508,15 @@ fn make_request(
         body: request.body().bytes().unwrap_or(&[]).to_vec(),
     };
 
-    let wasi_resp =
-        execute(wasi_req, options).map_err(|e| ConnectorError::other(e.into(), None))?;
+    // Timeouts go through `ConnectorError::timeout` so the SDK's retry
+    // classifier treats them as transient.
+    let wasi_resp = execute(wasi_req, options).map_err(|e| {
+        if e.is_timeout() {
+            ConnectorError::timeout(e.into())
+        } else {
+            ConnectorError::other(e.into(), None)
+        }
+    })?;
 
     // Build the AWS SDK Response
     let mut sdk_response = Response::new(
@@ -481,7 +561,10 @@ fn write_body(body: &OutgoingBody, data: &[u8]) -> Result<(), String> {
 }
 
 /// Read all bytes from an incoming body.
-fn read_body(body: &IncomingBody) -> Result<Vec<u8>, String> {
+///
+/// A stream failure carrying a wasi:http error code (the host's body
+/// deadline surfaces as `ConnectionReadTimeout`) keeps that code.
+fn read_body(body: &IncomingBody) -> Result<Vec<u8>, HttpError> {
     let stream = body
         .stream()
         .map_err(|()| "Failed to get input stream".to_string())?;
@@ -490,8 +573,11 @@ fn read_body(body: &IncomingBody) -> Result<Vec<u8>, String> {
         match stream.blocking_read(64 * 1024) {
             Ok(chunk) => buf.extend_from_slice(&chunk),
             Err(StreamError::Closed) => break,
-            Err(e) => {
-                return Err(format!("Read error: {e:?}"));
+            Err(StreamError::LastOperationFailed(e)) => {
+                return Err(match http_error_code(&e) {
+                    Some(code) => HttpError::from_code("Read error", code),
+                    None => HttpError::Other(format!("Read error: {}", e.to_debug_string())),
+                });
             }
         }
     }
@@ -323,6 +323,32 @@ macro_rules! export_provider {
                     proto::ProviderErrorKind::Timeout => {
                         wit_types::ProviderError::Timeout(detail)
                     }
+                    // Kinds the WIT variant lacks travel as api-errors;
+                    // the host recognises them by the error code.
+                    proto::ProviderErrorKind::AlreadyExists => {
+                        wit_types::ProviderError::ApiError(wit_types::ErrorDetail {
+                            code: Some(proto::kind_wire_code(
+                                proto::ALREADY_EXISTS_CODE,
+                                detail.code.as_deref(),
+                            )),
+                            ..detail
+                        })
+                    }
+                    proto::ProviderErrorKind::AmbiguousMatch => {
+                        wit_types::ProviderError::ApiError(wit_types::ErrorDetail {
+                            code: Some(proto::kind_wire_code(
+                                proto::AMBIGUOUS_MATCH_CODE,
+                                detail.code.as_deref(),
+                            )),
+                            ..detail
+                        })
+                    }
+                    proto::ProviderErrorKind::Credentials => {
+                        wit_types::ProviderError::ApiError(wit_types::ErrorDetail {
+                            code: Some(proto::credentials_wire_code(detail.code.as_deref())),
+                            ..detail
+                        })
+                    }
                     proto::ProviderErrorKind::Internal => {
                         wit_types::ProviderError::Internal(detail)
                     }
@@ -370,6 +396,7 @@ macro_rules! export_provider {
                 proto::UpdateRequest {
                     from,
                     patch: proto::UpdatePatch { ops },
+                    timeout_secs: req.timeout_secs,
                 }
             }
 
@@ -378,6 +405,9 @@ macro_rules! export_provider {
             ) -> proto::CreateRequest {
                 proto::CreateRequest {
                     resource: wit_to_proto_resource(&req.res),
+                    timeout_secs: req.timeout_secs,
+                    // The WIT records carry no client token yet.
+                    client_token: None,
                 }
             }
 
@@ -390,6 +420,8 @@ macro_rules! export_provider {
                         create_before_destroy: req.directives.create_before_destroy,
                         prevent_destroy: req.directives.prevent_destroy,
                     },
+                    timeout_secs: req.timeout_secs,
+                    force_dependencies: false,
                 }
             }
 
@@ -927,6 +959,32 @@ macro_rules! export_provider {
                     proto::ProviderErrorKind::Timeout => {
                         wit_types::ProviderError::Timeout(detail)
                     }
+                    // Kinds the WIT variant lacks travel as api-errors;
+                    // the host recognises them by the error code.
+                    proto::ProviderErrorKind::AlreadyExists => {
+                        wit_types::ProviderError::ApiError(wit_types::ErrorDetail {
+                            code: Some(proto::kind_wire_code(
+                                proto::ALREADY_EXISTS_CODE,
+                                detail.code.as_deref(),
+                            )),
+                            ..detail
+                        })
+                    }
+                    proto::ProviderErrorKind::AmbiguousMatch => {
+                        wit_types::ProviderError::ApiError(wit_types::ErrorDetail {
+                            code: Some(proto::kind_wire_code(
+                                proto::AMBIGUOUS_MATCH_CODE,
+                                detail.code.as_deref(),
+                            )),
+                            ..detail
+                        })
+                    }
+                    proto::ProviderErrorKind::Credentials => {
+                        wit_types::ProviderError::ApiError(wit_types::ErrorDetail {
+                            code: Some(proto::credentials_wire_code(detail.code.as_deref())),
+                            ..detail
+                        })
+                    }
                     proto::ProviderErrorKind::Internal => {
                         wit_types::ProviderError::Internal(detail)
                     }
@@ -974,6 +1032,7 @@ macro_rules! export_provider {
                 proto::UpdateRequest {
                     from,
                     patch: proto::UpdatePatch { ops },
+                    timeout_secs: req.timeout_secs,
                 }
             }
 
@@ -982,6 +1041,9 @@ macro_rules! export_provider {
             ) -> proto::CreateRequest {
                 proto::CreateRequest {
                     resource: wit_to_proto_resource(&req.res),
+                    timeout_secs: req.timeout_secs,
+                    // The WIT records carry no client token yet.
+                    client_token: None,
                 }
             }
 
@@ -994,6 +1056,8 @@ macro_rules! export_provider {
                         create_before_destroy: req.directives.create_before_destroy,
                         prevent_destroy: req.directives.prevent_destroy,
                     },
+                    timeout_secs: req.timeout_secs,
+                    force_dependencies: false,
                 }
             }
 
@@ -622,6 +622,7 @@ mod tests {
                                 value: Some(string_value("v2")),
                             }],
                         },
+                        timeout: None,
                     },
                 )
                 .await
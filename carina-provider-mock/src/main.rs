@@ -28,6 +28,7 @@ impl CarinaProvider for MockProcessProvider {
             display_name: "Mock Provider (Process)".into(),
             capabilities: vec![],
             version: env!("CARGO_PKG_VERSION").to_string(),
+            type_family: None,
         }
     }
 
@@ -0,0 +1,258 @@
+//! CIDR block parsing, normalization and containment checks.
+//!
+//! Shared by the host (validation, drift comparison via
+//! [`DslTransform::CanonicalCidr`](crate::types::DslTransform::CanonicalCidr))
+//! and providers, so both sides agree on what a CIDR block means.
+
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// A parsed IPv4 or IPv6 CIDR block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cidr {
+    V4 { addr: Ipv4Addr, prefix: u8 },
+    V6 { addr: Ipv6Addr, prefix: u8 },
+}
+
+/// Why a string is not a valid CIDR block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CidrError {
+    /// Not of the form `address/prefix`.
+    Format,
+    /// The address part does not parse.
+    Address,
+    /// The prefix is not a number or exceeds `max`.
+    PrefixLength { max: u8 },
+    /// Bits after the prefix are set; `network` is the block the user
+    /// most likely meant.
+    HostBitsSet { network: String },
+}
+
+impl fmt::Display for CidrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format => write!(f, "expected address/prefix"),
+            Self::Address => write!(f, "invalid address"),
+            Self::PrefixLength { max } => write!(f, "prefix length must be 0-{max}"),
+            Self::HostBitsSet { network } => {
+                write!(f, "host bits are set (network address is {network})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CidrError {}
+
+impl Cidr {
+    /// Parse `s`, rejecting blocks with host bits set.
+    pub fn parse(s: &str) -> Result<Self, CidrError> {
+        let cidr = Self::parse_lenient(s)?;
+        let network = cidr.network();
+        if network != cidr {
+            return Err(CidrError::HostBitsSet {
+                network: network.to_string(),
+            });
+        }
+        Ok(cidr)
+    }
+
+    /// Parse `s`, keeping any host bits. Use [`Self::network`] to clear
+    /// them.
+    pub fn parse_lenient(s: &str) -> Result<Self, CidrError> {
+        let (addr, prefix) = s.split_once('/').ok_or(CidrError::Format)?;
+        if addr.contains(':') {
+            let addr: Ipv6Addr = addr.parse().map_err(|_| CidrError::Address)?;
+            let prefix = parse_prefix(prefix, 128)?;
+            Ok(Self::V6 { addr, prefix })
+        } else {
+            let addr = parse_ipv4(addr).ok_or(CidrError::Address)?;
+            let prefix = parse_prefix(prefix, 32)?;
+            Ok(Self::V4 { addr, prefix })
+        }
+    }
+
+    pub fn prefix(&self) -> u8 {
+        match self {
+            Self::V4 { prefix, .. } | Self::V6 { prefix, .. } => *prefix,
+        }
+    }
+
+    /// The block with all host bits cleared.
+    pub fn network(&self) -> Self {
+        match *self {
+            Self::V4 { addr, prefix } => Self::V4 {
+                addr: Ipv4Addr::from(u32::from(addr) & v4_mask(prefix)),
+                prefix,
+            },
+            Self::V6 { addr, prefix } => Self::V6 {
+                addr: Ipv6Addr::from(u128::from(addr) & v6_mask(prefix)),
+                prefix,
+            },
+        }
+    }
+
+    /// Whether every address in `other` is also in `self`. Blocks of
+    /// different address families never contain each other.
+    pub fn contains(&self, other: &Cidr) -> bool {
+        match (*self, *other) {
+            (
+                Self::V4 {
+                    addr: a,
+                    prefix: pa,
+                },
+                Self::V4 {
+                    addr: b,
+                    prefix: pb,
+                },
+            ) => pa <= pb && (u32::from(a) ^ u32::from(b)) & v4_mask(pa) == 0,
+            (
+                Self::V6 {
+                    addr: a,
+                    prefix: pa,
+                },
+                Self::V6 {
+                    addr: b,
+                    prefix: pb,
+                },
+            ) => pa <= pb && (u128::from(a) ^ u128::from(b)) & v6_mask(pa) == 0,
+            _ => false,
+        }
+    }
+
+    /// Whether `self` and `other` share at least one address.
+    pub fn overlaps(&self, other: &Cidr) -> bool {
+        self.contains(other) || other.contains(self)
+    }
+}
+
+impl fmt::Display for Cidr {
+    /// Canonical form: dotted-quad for IPv4, RFC 5952 (lowercase,
+    /// longest zero run compressed) for IPv6.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V4 { addr, prefix } => write!(f, "{addr}/{prefix}"),
+            Self::V6 { addr, prefix } => write!(f, "{addr}/{prefix}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Cidr {
+    type Err = CidrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Canonical spelling of `cidr` with host bits cleared, or `None` when it
+/// does not parse. Cloud APIs return CIDRs in this form, so comparing
+/// normalized values keeps `2001:DB8:0::/32` and `2001:db8::/32` from
+/// showing up as drift.
+pub fn normalize_cidr(cidr: &str) -> Option<String> {
+    Cidr::parse_lenient(cidr)
+        .ok()
+        .map(|c| c.network().to_string())
+}
+
+/// Whether `outer` contains `inner`. False when either does not parse or
+/// the address families differ.
+pub fn cidr_contains(outer: &str, inner: &str) -> bool {
+    match (Cidr::parse_lenient(outer), Cidr::parse_lenient(inner)) {
+        (Ok(outer), Ok(inner)) => outer.contains(&inner),
+        _ => false,
+    }
+}
+
+/// Whether `a` and `b` share any address. False when either does not
+/// parse or the address families differ.
+pub fn cidr_overlaps(a: &str, b: &str) -> bool {
+    match (Cidr::parse_lenient(a), Cidr::parse_lenient(b)) {
+        (Ok(a), Ok(b)) => a.overlaps(&b),
+        _ => false,
+    }
+}
+
+/// Dotted-quad parse that, unlike `Ipv4Addr::from_str`, accepts leading
+/// zeros in an octet (`010.0.0.0`), matching the schema validators.
+fn parse_ipv4(s: &str) -> Option<Ipv4Addr> {
+    let mut octets = [0u8; 4];
+    let mut parts = s.split('.');
+    for octet in &mut octets {
+        *octet = parts.next()?.parse().ok()?;
+    }
+    parts.next().is_none().then(|| Ipv4Addr::from(octets))
+}
+
+fn parse_prefix(s: &str, max: u8) -> Result<u8, CidrError> {
+    match s.parse::<u8>() {
+        Ok(p) if p <= max => Ok(p),
+        _ => Err(CidrError::PrefixLength { max }),
+    }
+}
+
+fn v4_mask(prefix: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0)
+}
+
+fn v6_mask(prefix: u8) -> u128 {
+    u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_host_bits_and_bad_prefixes() {
+        assert!(Cidr::parse("10.0.0.0/16").is_ok());
+        assert!(Cidr::parse("0.0.0.0/0").is_ok());
+        assert!(Cidr::parse("::/0").is_ok());
+        assert_eq!(
+            Cidr::parse("10.0.0.5/16"),
+            Err(CidrError::HostBitsSet {
+                network: "10.0.0.0/16".to_string()
+            })
+        );
+        assert_eq!(
+            Cidr::parse("2001:db8::1/32"),
+            Err(CidrError::HostBitsSet {
+                network: "2001:db8::/32".to_string()
+            })
+        );
+        assert_eq!(
+            Cidr::parse("10.0.0.0/33"),
+            Err(CidrError::PrefixLength { max: 32 })
+        );
+        assert_eq!(Cidr::parse("10.0.0.0"), Err(CidrError::Format));
+        assert_eq!(Cidr::parse("10.0.0/8"), Err(CidrError::Address));
+    }
+
+    #[test]
+    fn normalize_produces_canonical_network() {
+        assert_eq!(
+            normalize_cidr("2001:DB8:0:0::/32").as_deref(),
+            Some("2001:db8::/32")
+        );
+        assert_eq!(normalize_cidr("010.0.0.0/8").as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(
+            normalize_cidr("10.1.2.3/16").as_deref(),
+            Some("10.1.0.0/16")
+        );
+        assert_eq!(normalize_cidr("not-a-cidr"), None);
+    }
+
+    #[test]
+    fn contains_and_overlaps() {
+        assert!(cidr_contains("10.0.0.0/16", "10.0.1.0/24"));
+        assert!(cidr_contains("10.0.0.0/16", "10.0.0.0/16"));
+        assert!(!cidr_contains("10.0.1.0/24", "10.0.0.0/16"));
+        assert!(!cidr_contains("10.0.0.0/16", "10.1.0.0/24"));
+        assert!(cidr_contains("0.0.0.0/0", "192.168.0.0/16"));
+        assert!(cidr_contains("2001:db8::/32", "2001:db8:1::/48"));
+        assert!(!cidr_contains("10.0.0.0/8", "2001:db8::/32"));
+
+        assert!(cidr_overlaps("10.0.1.0/24", "10.0.0.0/16"));
+        assert!(!cidr_overlaps("10.0.0.0/24", "10.0.1.0/24"));
+        assert!(!cidr_overlaps("garbage", "10.0.0.0/8"));
+    }
+}
@@ -1,5 +1,8 @@
+pub mod cidr;
 pub mod jsonrpc;
 pub mod methods;
+pub mod policy;
+pub mod pricing;
 pub mod types;
 
 /// Protocol version for host-plugin communication.
@@ -31,6 +31,13 @@ pub struct SchemasResult {
     pub schemas: Vec<ResourceSchema>,
 }
 
+// -- provider_config_attribute_types --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProviderConfigAttributeTypesResult {
+    pub types: HashMap<String, AttributeType>,
+}
+
 // -- initialize --
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -65,6 +72,44 @@ pub struct ReadResult {
     pub state: State,
 }
 
+// -- read_data_source --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadDataSourceParams {
+    pub resource: Resource,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadDataSourceResult {
+    pub state: State,
+}
+
+// -- list_project_resources --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListProjectResourcesParams {
+    pub project: String,
+}
+
+/// `states` is `None` when the provider cannot enumerate its resources.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListProjectResourcesResult {
+    pub states: Option<Vec<State>>,
+}
+
+// -- monthly_cost --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonthlyCostParams {
+    pub resource: Resource,
+}
+
+/// `cost` is `None` when the provider does not price the type.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MonthlyCostResult {
+    pub cost: Option<MonthlyCost>,
+}
+
 // -- create --
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,6 +121,8 @@ pub struct CreateParams {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateResult {
     pub outcome: CreateOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<OperationMetrics>,
 }
 
 // -- update --
@@ -90,6 +137,8 @@ pub struct UpdateParams {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateResult {
     pub outcome: UpdateOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<OperationMetrics>,
 }
 
 // -- delete --
@@ -104,6 +153,63 @@ pub struct DeleteParams {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteResult {
     pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<OperationMetrics>,
+}
+
+// -- delete_blockers --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteBlockersParams {
+    pub id: ResourceId,
+    pub identifier: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteBlockersResult {
+    pub blockers: Vec<DeleteBlocker>,
+}
+
+// -- find_existing --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindExistingParams {
+    pub id: ResourceId,
+    pub resource: Resource,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindExistingResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+}
+
+// -- simulate --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimulateParams {
+    pub resource: Resource,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimulateResult {
+    pub diagnostics: Vec<ResourceDiagnostic>,
+}
+
+// -- capabilities --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CapabilitiesResult {
+    pub capabilities: ProviderCapabilities,
+}
+
+// -- credential_expiry --
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialExpiryResult {
+    /// Unix time in seconds; absent when the credentials do not expire.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 // -- normalize_desired --
@@ -0,0 +1,192 @@
+//! IAM policy document canonicalization.
+//!
+//! Shared by the host (drift comparison via
+//! [`DslTransform::CanonicalPolicyDocument`](crate::types::DslTransform::CanonicalPolicyDocument))
+//! and providers, so both sides agree on when two policy documents say
+//! the same thing. AWS hands policies back reformatted: keys reordered,
+//! a one-element `Action` list collapsed to a string, condition values
+//! turned into strings. Compared as text, each of those is a perpetual
+//! diff.
+
+use serde_json::{Map, Value};
+
+/// Canonical spelling of the JSON document `doc`, or `None` when it is
+/// not JSON: object keys sorted and whitespace removed. The policy form
+/// below builds on this one.
+pub fn canonical_json_document(doc: &str) -> Option<String> {
+    Some(sorted(serde_json::from_str(doc).ok()?).to_string())
+}
+
+/// Canonical spelling of the policy document `doc`, or `None` when it
+/// is not a JSON object.
+///
+/// In the canonical form:
+/// - object keys are sorted and whitespace is removed;
+/// - `Statement` is always a list;
+/// - `Action`, `Resource`, principal and condition values are sets:
+///   sorted, deduplicated, and a single element is a bare string;
+/// - action names are lowercase, since IAM matches them case-insensitively;
+/// - condition values are strings (`true` and `"true"` are the same);
+/// - the principal `{"AWS": "*"}` is written `"*"`.
+///
+/// Statement order is kept.
+pub fn canonical_policy_document(doc: &str) -> Option<String> {
+    let Value::Object(mut policy) = serde_json::from_str(doc).ok()? else {
+        return None;
+    };
+    if let Some(statements) = policy.get_mut("Statement") {
+        let list = match statements.take() {
+            Value::Array(items) => items,
+            single => vec![single],
+        };
+        *statements = Value::Array(list.into_iter().map(canonical_statement).collect());
+    }
+    Some(sorted(Value::Object(policy)).to_string())
+}
+
+fn canonical_statement(mut statement: Value) -> Value {
+    let Value::Object(fields) = &mut statement else {
+        return statement;
+    };
+    for (key, value) in fields.iter_mut() {
+        match key.as_str() {
+            "Action" | "NotAction" => string_set(value, true),
+            "Resource" | "NotResource" => string_set(value, false),
+            "Principal" | "NotPrincipal" => principal(value),
+            "Condition" => condition(value),
+            _ => {}
+        }
+    }
+    statement
+}
+
+fn principal(value: &mut Value) {
+    let Value::Object(kinds) = value else {
+        return;
+    };
+    for ids in kinds.values_mut() {
+        string_set(ids, false);
+    }
+    if kinds.len() == 1 && kinds.get("AWS").and_then(Value::as_str) == Some("*") {
+        *value = Value::String("*".to_string());
+    }
+}
+
+fn condition(value: &mut Value) {
+    let Value::Object(operators) = value else {
+        return;
+    };
+    for keys in operators.values_mut() {
+        if let Value::Object(keys) = keys {
+            for values in keys.values_mut() {
+                string_set(values, false);
+            }
+        }
+    }
+}
+
+/// Rewrite a string or list of scalars as a set. Left alone when any
+/// element is not a scalar.
+fn string_set(value: &mut Value, lowercase: bool) {
+    let items: Vec<&Value> = match &*value {
+        Value::Array(items) => items.iter().collect(),
+        single => vec![single],
+    };
+    let Some(mut strings) = items
+        .into_iter()
+        .map(scalar_string)
+        .collect::<Option<Vec<String>>>()
+    else {
+        return;
+    };
+    if lowercase {
+        for s in &mut strings {
+            s.make_ascii_lowercase();
+        }
+    }
+    strings.sort();
+    strings.dedup();
+    *value = match <[String; 1]>::try_from(strings) {
+        Ok([single]) => Value::String(single),
+        Err(strings) => Value::Array(strings.into_iter().map(Value::String).collect()),
+    };
+}
+
+fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// `value` with every object's keys in sorted order, whether or not
+/// `serde_json` preserves insertion order.
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sorted(v)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sorted).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(a: &str, b: &str) -> bool {
+        canonical_policy_document(a).unwrap() == canonical_policy_document(b).unwrap()
+    }
+
+    #[test]
+    fn formatting_and_list_spellings_do_not_matter() {
+        let written = r#"{
+            "Version": "2012-10-17",
+            "Statement": [{
+                "Effect": "Allow",
+                "Action": ["s3:GetObject"],
+                "Resource": ["arn:aws:s3:::b/*", "arn:aws:s3:::b"],
+                "Principal": {"AWS": "*"},
+                "Condition": {"Bool": {"aws:SecureTransport": true}}
+            }]
+        }"#;
+        let returned = r#"{"Statement":{"Condition":{"Bool":{"aws:SecureTransport":"true"}},
+            "Principal":"*","Resource":["arn:aws:s3:::b","arn:aws:s3:::b/*"],
+            "Action":"S3:GetObject","Effect":"Allow"},"Version":"2012-10-17"}"#;
+        assert!(same(written, returned));
+    }
+
+    #[test]
+    fn real_changes_still_differ() {
+        let base = r#"{"Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"*"}]}"#;
+        assert!(!same(
+            base,
+            r#"{"Statement":[{"Effect":"Deny","Action":"s3:GetObject","Resource":"*"}]}"#
+        ));
+        assert!(!same(
+            base,
+            r#"{"Statement":[{"Effect":"Allow","Action":"s3:GetObject","Resource":"arn:aws:s3:::B"}]}"#
+        ));
+        // Resource ARNs are case-sensitive, unlike action names.
+        assert!(!same(
+            r#"{"Statement":[{"Resource":"arn:aws:s3:::b"}]}"#,
+            r#"{"Statement":[{"Resource":"arn:aws:s3:::B"}]}"#
+        ));
+    }
+
+    #[test]
+    fn non_objects_have_no_canonical_form() {
+        assert_eq!(canonical_policy_document("not json"), None);
+        assert_eq!(canonical_policy_document("[1]"), None);
+    }
+}
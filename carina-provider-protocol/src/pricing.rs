@@ -0,0 +1,172 @@
+//! AWS list prices.
+//!
+//! Providers built on the plugin SDK price their resources with
+//! [`aws_monthly_cost`]; the host falls back to [`aws_list_price`] for
+//! providers that do not answer `monthly_cost` themselves. Prices come from a bundled on-demand snapshot (`aws_pricing.json`)
+//! per region. It covers the fixed hourly charges that most often
+//! surprise: NAT gateways, public IPv4 addresses (Elastic IPs) and
+//! interface / Gateway Load Balancer VPC endpoints, billed per
+//! Availability Zone. Gateway endpoints are free and so go unpriced, as
+//! does every type in a region the snapshot lacks.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::types::{MonthlyCost, Resource, Value};
+
+const BUNDLED_SNAPSHOT: &str = include_str!("aws_pricing.json");
+
+/// List prices captured on `snapshot_date`.
+#[derive(Debug, Clone, Deserialize)]
+struct PricingSnapshot {
+    snapshot_date: String,
+    hours_per_month: f64,
+    /// Hourly price per billed unit, keyed by region, then by charge.
+    regions: HashMap<String, HashMap<String, f64>>,
+}
+
+fn snapshot() -> &'static PricingSnapshot {
+    static SNAPSHOT: OnceLock<PricingSnapshot> = OnceLock::new();
+    SNAPSHOT.get_or_init(|| {
+        serde_json::from_str(BUNDLED_SNAPSHOT).expect("bundled AWS pricing snapshot is valid")
+    })
+}
+
+/// Monthly list price of `resource` in `region`, or `None` when the type
+/// or the region is not priced.
+pub fn aws_monthly_cost(region: &str, resource: &Resource) -> Option<MonthlyCost> {
+    let attributes = &resource.attributes;
+    aws_list_price(
+        region,
+        &resource.id.resource_type,
+        enum_text(attributes.get("vpc_endpoint_type")),
+        subnet_count(attributes.get("subnet_ids")),
+    )
+}
+
+/// Monthly list price of a `resource_type` resource in `region`, or
+/// `None` when the type or the region is not priced.
+///
+/// `vpc_endpoint_type` is the bare variant name of a VPC endpoint's type
+/// and `subnets` the number of subnets it is placed in; other types
+/// ignore both.
+pub fn aws_list_price(
+    region: &str,
+    resource_type: &str,
+    vpc_endpoint_type: Option<&str>,
+    subnets: usize,
+) -> Option<MonthlyCost> {
+    let snapshot = snapshot();
+    let prices = snapshot.regions.get(region)?;
+    let hourly = |charge: &str, units: usize, unit: &str| {
+        let rate = *prices.get(charge)?;
+        let hours = snapshot.hours_per_month;
+        let basis = if units == 1 {
+            format!("{hours} h × ${rate}/h")
+        } else {
+            format!("{units} {unit} × {hours} h × ${rate}/h")
+        };
+        Some(MonthlyCost {
+            usd: units as f64 * hours * rate,
+            basis: format!("{basis}, {region} list price of {}", snapshot.snapshot_date),
+        })
+    };
+    match resource_type {
+        "ec2.NatGateway" => hourly("nat_gateway", 1, ""),
+        "ec2.Eip" => hourly("public_ipv4_address", 1, ""),
+        "ec2.VpcEndpoint" => {
+            let charge = match vpc_endpoint_type?.to_ascii_lowercase().as_str() {
+                "interface" => "interface_endpoint_per_az",
+                "gatewayloadbalancer" => "gateway_load_balancer_endpoint_per_az",
+                _ => return None,
+            };
+            hourly(charge, subnets.max(1), "AZ")
+        }
+        _ => None,
+    }
+}
+
+/// The variant name of an enum-typed attribute, whether sent bare or
+/// namespaced (`awscc.ec2.VpcEndpoint.VpcEndpointType.Interface`).
+fn enum_text(value: Option<&Value>) -> Option<&str> {
+    match value? {
+        Value::String(s) => Some(s.rsplit('.').next().unwrap_or(s)),
+        _ => None,
+    }
+}
+
+/// Endpoints are billed per subnet (one per AZ). A list not yet known at
+/// plan time counts as one.
+fn subnet_count(value: Option<&Value>) -> usize {
+    let count = match value {
+        Some(Value::List(items)) => items.len(),
+        Some(Value::StringList(items)) => items.len(),
+        _ => 1,
+    };
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResourceId;
+
+    fn resource(resource_type: &str, attributes: HashMap<String, Value>) -> Resource {
+        Resource {
+            id: ResourceId {
+                provider: "awscc".to_string(),
+                resource_type: resource_type.to_string(),
+                identity: "r".to_string(),
+            },
+            attributes,
+            directives: Default::default(),
+        }
+    }
+
+    #[test]
+    fn nat_gateway_is_priced_for_the_region() {
+        let nat = resource("ec2.NatGateway", HashMap::new());
+        let virginia = aws_monthly_cost("us-east-1", &nat).unwrap();
+        assert!((virginia.usd - 32.85).abs() < 1e-9);
+        assert!(virginia.basis.starts_with("730 h × $0.045/h, us-east-1"));
+        let tokyo = aws_monthly_cost("ap-northeast-1", &nat).unwrap();
+        assert!((tokyo.usd - 45.26).abs() < 1e-9);
+        assert!(aws_monthly_cost("mars-north-1", &nat).is_none());
+    }
+
+    #[test]
+    fn interface_endpoints_are_priced_per_subnet() {
+        let endpoint = resource(
+            "ec2.VpcEndpoint",
+            HashMap::from([
+                (
+                    "vpc_endpoint_type".to_string(),
+                    Value::String("awscc.ec2.VpcEndpoint.VpcEndpointType.Interface".to_string()),
+                ),
+                (
+                    "subnet_ids".to_string(),
+                    Value::StringList(vec!["subnet-a".to_string(), "subnet-b".to_string()]),
+                ),
+            ]),
+        );
+        let cost = aws_monthly_cost("us-east-1", &endpoint).unwrap();
+        assert!((cost.usd - 14.6).abs() < 1e-9);
+        assert!(cost.basis.starts_with("2 AZ × 730 h × $0.01/h"));
+    }
+
+    #[test]
+    fn gateway_endpoints_are_unpriced() {
+        let gateway = resource(
+            "ec2.VpcEndpoint",
+            HashMap::from([(
+                "vpc_endpoint_type".to_string(),
+                Value::String("Gateway".to_string()),
+            )]),
+        );
+        assert!(aws_monthly_cost("us-east-1", &gateway).is_none());
+        let untyped = resource("ec2.VpcEndpoint", HashMap::new());
+        assert!(aws_monthly_cost("us-east-1", &untyped).is_none());
+    }
+}
@@ -202,6 +202,14 @@ pub struct ReadRequest;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateRequest {
     pub resource: Resource,
+    /// User-configured create timeout in seconds; `None` keeps the
+    /// provider's own wait window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Idempotency token to pass to the cloud API's create call, stable
+    /// across retries of the same configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_token: Option<String>,
 }
 
 /// Per-operation request record for `update`. Mirrors
@@ -210,6 +218,9 @@ pub struct CreateRequest {
 pub struct UpdateRequest {
     pub from: State,
     pub patch: UpdatePatch,
+    /// User-configured update timeout in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
 }
 
 /// Per-operation request record for `delete`. Mirrors
@@ -218,6 +229,88 @@ pub struct UpdateRequest {
 pub struct DeleteRequest {
     #[serde(default)]
     pub directives: Directives,
+    /// User-configured delete timeout in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Remove the removable blockers reported by `delete_blockers` first.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub force_dependencies: bool,
+}
+
+/// A remote object that keeps a resource from being deleted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteBlocker {
+    pub kind: String,
+    pub identifier: String,
+    pub description: String,
+    /// Safe for the provider to remove when `force_dependencies` is set.
+    #[serde(default)]
+    pub removable: bool,
+}
+
+/// A resource's estimated recurring cost, as a provider prices it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyCost {
+    pub usd: f64,
+    /// How the figure was derived, e.g. `730 h × $0.045/h`.
+    pub basis: String,
+}
+
+/// What a provider can and cannot do. Fields a provider omits take the
+/// unrestricted default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderCapabilities {
+    /// Resource types with no in-place update.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replace_only_types: Vec<String>,
+    #[serde(default = "default_true")]
+    pub supports_import: bool,
+    #[serde(default = "default_true")]
+    pub supports_tagging: bool,
+    /// Concurrent operations the provider accepts; `None` for no cap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel_ops: Option<u32>,
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            replace_only_types: Vec::new(),
+            supports_import: true,
+            supports_tagging: true,
+            max_parallel_ops: None,
+        }
+    }
+}
+
+/// Timing counters for the last create, update or delete of a resource,
+/// reported alongside the operation's result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OperationMetrics {
+    pub api_calls: u32,
+    /// Summed latency of the API calls, in milliseconds.
+    pub api_latency_ms: u64,
+    pub polls: u32,
+    pub retries: u32,
+}
+
+/// Severity of a [`ResourceDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    /// Fails the plan.
+    Error,
+    Warning,
+}
+
+/// A plan-time finding about a desired resource, e.g. from `simulate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<String>,
 }
 
 /// Carina-side directives for a resource. Mirrors `directives` in
@@ -250,6 +343,11 @@ pub struct ProviderInfo {
     #[serde(default)]
     pub capabilities: Vec<String>,
     pub version: String,
+    /// Provider family shared with other providers of the same cloud
+    /// (e.g. `"aws"` for both `aws` and `awscc`). Custom types are
+    /// assignable across providers of one family.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_family: Option<String>,
 }
 
 /// Wire envelope for info(): carries the provider's metadata plus the
@@ -285,10 +383,67 @@ pub enum ProviderErrorKind {
     ApiError,
     NotFound,
     Timeout,
+    AlreadyExists,
+    AmbiguousMatch,
+    /// The cloud API rejected the credentials themselves (expired
+    /// session token, unknown access key), so every later call in the
+    /// run fails the same way. The host stops dispatching operations.
+    Credentials,
     #[default]
     Internal,
 }
 
+/// Error code a [`ProviderErrorKind::AlreadyExists`] error carries
+/// across the WASM boundary, whose `provider-error` variant has no
+/// already-exists case: it travels as an `api-error` with this code,
+/// keeping the provider's own code as `AlreadyExists:EntityAlreadyExists`;
+/// see [`kind_wire_code`].
+pub const ALREADY_EXISTS_CODE: &str = "AlreadyExists";
+
+/// Error code a [`ProviderErrorKind::AmbiguousMatch`] error carries
+/// across the WASM boundary, the same way as [`ALREADY_EXISTS_CODE`],
+/// including the provider's own code. Its candidates travel only in the
+/// message there.
+pub const AMBIGUOUS_MATCH_CODE: &str = "AmbiguousMatch";
+
+/// Error code a [`ProviderErrorKind::Credentials`] error carries across
+/// the WASM boundary, the same way as [`ALREADY_EXISTS_CODE`]: the
+/// provider's own code (e.g. `ExpiredToken`) rides along as
+/// `Credentials:ExpiredToken`; see [`credentials_wire_code`].
+pub const CREDENTIALS_CODE: &str = "Credentials";
+
+/// Wire code for an error of a kind the WIT `provider-error` variant
+/// lacks, tagged `kind_code`, whose provider-set code is `code`.
+pub fn kind_wire_code(kind_code: &str, code: Option<&str>) -> String {
+    match code {
+        Some(code) => format!("{kind_code}:{code}"),
+        None => kind_code.to_string(),
+    }
+}
+
+/// Inverse of [`kind_wire_code`]: `Some(original code)` when `wire`
+/// marks an error of kind `kind_code`, `None` otherwise.
+pub fn parse_kind_wire_code(kind_code: &str, wire: &str) -> Option<Option<String>> {
+    if wire == kind_code {
+        return Some(None);
+    }
+    wire.strip_prefix(kind_code)?
+        .strip_prefix(':')
+        .map(|code| Some(code.to_string()))
+}
+
+/// Wire code for a [`ProviderErrorKind::Credentials`] error whose
+/// provider-set code is `code`.
+pub fn credentials_wire_code(code: Option<&str>) -> String {
+    kind_wire_code(CREDENTIALS_CODE, code)
+}
+
+/// Inverse of [`credentials_wire_code`]: `Some(original code)` when
+/// `wire` marks a credentials error, `None` otherwise.
+pub fn parse_credentials_wire_code(wire: &str) -> Option<Option<String>> {
+    parse_kind_wire_code(CREDENTIALS_CODE, wire)
+}
+
 /// Provider error returned from operations.
 ///
 /// Mirrors `(provider-error, error-detail)` in `wit/types.wit`. The
@@ -328,6 +483,10 @@ pub struct ProviderError {
     /// Correlation id from the cloud-API response.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub request_id: Option<String>,
+    /// Identifiers every [`ProviderErrorKind::AmbiguousMatch`] lookup
+    /// matched.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<String>,
 }
 
 /// Serializable validator types that can cross the WASM boundary.
@@ -461,8 +620,21 @@ pub struct ResourceSchema {
     /// Declarative "exactly one of" groups. Each inner vec is a group of
     /// attribute names where exactly one must be specified. Survives
     /// serialization across the WASM plugin boundary.
+    ///
+    /// Kept for plugins built before `constraints` existed; new providers
+    /// declare [`AttributeConstraint::ExactlyOneOf`] instead. The host
+    /// evaluates both.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub exclusive_required: Vec<Vec<String>>,
+    /// Declarative cross-attribute rules. Mirror of
+    /// `carina_core::schema::AttributeConstraint`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<AttributeConstraint>,
+    /// Attribute-scoped rules for ignoring differences the cloud API
+    /// introduces on read-back (reordering, reformatting, server-assigned
+    /// fields). Mirror of `carina_core::schema::ResourceSchema::diff_suppressors`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diff_suppressors: Vec<DiffSuppressorRule>,
     /// Named definitions reachable via [`AttributeType::Ref`] from
     /// this resource's attribute types. Empty for resources whose
     /// attribute graph contains no cycles (the common case). Mirror
@@ -474,6 +646,70 @@ pub struct ResourceSchema {
     pub defs: std::collections::BTreeMap<String, AttributeType>,
 }
 
+/// Declarative cross-attribute rule on a [`ResourceSchema`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttributeConstraint {
+    /// Exactly one of `attributes` must be specified (CFN `oneOf`).
+    ExactlyOneOf { attributes: Vec<String> },
+    /// When `attribute` is specified, every attribute in `requires` must
+    /// be too (CFN `dependencies`).
+    RequiredWith {
+        attribute: String,
+        requires: Vec<String>,
+    },
+    /// When `attribute` is specified, none of `conflicts` may be.
+    ConflictsWith {
+        attribute: String,
+        conflicts: Vec<String>,
+    },
+    /// The CIDR block in `attribute` must lie within
+    /// `referenced_attribute` of the resource `reference` points at
+    /// (a subnet's `cidr_block` inside its VPC's). Checked at plan time,
+    /// once the reference resolves.
+    CidrWithinReference {
+        attribute: String,
+        reference: String,
+        referenced_attribute: String,
+    },
+}
+
+/// A [`DiffSuppressor`] applied at one attribute path of a resource.
+///
+/// `path` is a dot-separated walk from a top-level attribute through
+/// struct fields and map keys (`lifecycle_configuration.rules`). Lists
+/// along the way are transparent: the rest of the path applies to every
+/// element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffSuppressorRule {
+    pub path: String,
+    pub suppressor: DiffSuppressor,
+}
+
+/// A class of difference between desired and read-back values that does
+/// not represent a real change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DiffSuppressor {
+    /// A string holding a JSON document compares by parsed value, so
+    /// whitespace and object key order are ignored (policy documents).
+    JsonDocument,
+    /// A list compares without regard to element order.
+    IgnoreOrder,
+    /// A string compares case-insensitively.
+    IgnoreCase,
+    /// The named fields of the struct at the path are not compared
+    /// (server-assigned rule IDs).
+    IgnoreFields { fields: Vec<String> },
+    /// The value is not compared at all once the resource has one, for
+    /// attributes that legitimately change outside Carina (auto-scaled
+    /// counts, last-modified timestamps).
+    Ignore,
+    /// A suppressor this host does not know; suppresses nothing.
+    #[serde(other)]
+    Unknown,
+}
+
 /// Per-resource operational configuration for timeouts and retries.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct OperationConfig {
@@ -514,6 +750,12 @@ pub struct AttributeSchema {
     /// Whether this attribute contributes to anonymous resource identity hashing.
     #[serde(default)]
     pub identity: bool,
+    /// API unit of an integer attribute (`"seconds"`, `"days"`, `"GiB"`, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Field identifying an element of a list-of-structs attribute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -667,6 +909,20 @@ pub enum DslTransform {
     HyphenToUnderscore,
     StripSuffix(String),
     ReplaceTable(Vec<(String, String)>),
+    /// Canonical CIDR spelling with host bits cleared; see
+    /// [`crate::cidr::normalize_cidr`]. Values that do not parse pass
+    /// through unchanged.
+    CanonicalCidr,
+    /// Canonical IAM policy document; see
+    /// [`crate::policy::canonical_policy_document`]. Values that do not
+    /// parse pass through unchanged.
+    CanonicalPolicyDocument,
+    /// Canonical JSON document: object keys sorted, whitespace removed;
+    /// see [`crate::policy::canonical_json_document`]. Values that do not
+    /// parse pass through unchanged.
+    CanonicalJsonDocument,
+    /// Lowercase spelling, for values the API matches case-insensitively.
+    Lowercase,
     Unknown(serde_json::Value),
 }
 
@@ -683,6 +939,20 @@ impl DslTransform {
                 Some((_, v)) => Cow::Owned(v.clone()),
                 None => Cow::Borrowed(s),
             },
+            Self::CanonicalCidr => match crate::cidr::normalize_cidr(s) {
+                Some(normalized) if normalized != s => Cow::Owned(normalized),
+                _ => Cow::Borrowed(s),
+            },
+            Self::CanonicalPolicyDocument => match crate::policy::canonical_policy_document(s) {
+                Some(canonical) if canonical != s => Cow::Owned(canonical),
+                _ => Cow::Borrowed(s),
+            },
+            Self::CanonicalJsonDocument => match crate::policy::canonical_json_document(s) {
+                Some(canonical) if canonical != s => Cow::Owned(canonical),
+                _ => Cow::Borrowed(s),
+            },
+            Self::Lowercase if s.chars().any(char::is_uppercase) => Cow::Owned(s.to_lowercase()),
+            Self::Lowercase => Cow::Borrowed(s),
         }
     }
 }
@@ -692,6 +962,12 @@ impl Serialize for DslTransform {
         let value = match self {
             Self::Identity => serde_json::json!({ "type": "Identity" }),
             Self::HyphenToUnderscore => serde_json::json!({ "type": "HyphenToUnderscore" }),
+            Self::CanonicalCidr => serde_json::json!({ "type": "CanonicalCidr" }),
+            Self::CanonicalPolicyDocument => {
+                serde_json::json!({ "type": "CanonicalPolicyDocument" })
+            }
+            Self::CanonicalJsonDocument => serde_json::json!({ "type": "CanonicalJsonDocument" }),
+            Self::Lowercase => serde_json::json!({ "type": "Lowercase" }),
             Self::StripSuffix(suffix) => {
                 serde_json::json!({ "type": "StripSuffix", "value": suffix })
             }
@@ -715,6 +991,10 @@ impl<'de> Deserialize<'de> for DslTransform {
         match tag {
             "Identity" => Ok(Self::Identity),
             "HyphenToUnderscore" => Ok(Self::HyphenToUnderscore),
+            "CanonicalCidr" => Ok(Self::CanonicalCidr),
+            "CanonicalPolicyDocument" => Ok(Self::CanonicalPolicyDocument),
+            "CanonicalJsonDocument" => Ok(Self::CanonicalJsonDocument),
+            "Lowercase" => Ok(Self::Lowercase),
             "StripSuffix" => {
                 let value = raw
                     .get("value")
@@ -766,6 +1046,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn credentials_wire_code_keeps_the_provider_code() {
+        for code in [None, Some("ExpiredToken")] {
+            let wire = credentials_wire_code(code);
+            assert_eq!(
+                parse_credentials_wire_code(&wire),
+                Some(code.map(str::to_string))
+            );
+        }
+        assert_eq!(parse_credentials_wire_code("CredentialsMissing"), None);
+        assert_eq!(parse_credentials_wire_code("AccessDenied"), None);
+    }
+
+    #[test]
+    fn already_exists_wire_code_keeps_the_provider_code() {
+        let wire = kind_wire_code(ALREADY_EXISTS_CODE, Some("EntityAlreadyExists"));
+        assert_eq!(wire, "AlreadyExists:EntityAlreadyExists");
+        assert_eq!(
+            parse_kind_wire_code(ALREADY_EXISTS_CODE, &wire),
+            Some(Some("EntityAlreadyExists".to_string()))
+        );
+        assert_eq!(
+            parse_kind_wire_code(ALREADY_EXISTS_CODE, "AlreadyExists"),
+            Some(None)
+        );
+        assert_eq!(
+            parse_kind_wire_code(ALREADY_EXISTS_CODE, "EntityAlreadyExists"),
+            None
+        );
+    }
+
     #[test]
     fn test_value_roundtrip() {
         let values = vec![
@@ -1081,6 +1392,8 @@ mod tests {
                 operation_config: None,
                 validators: vec![],
                 exclusive_required: vec![],
+                constraints: vec![],
+                diff_suppressors: vec![],
                 defs: std::collections::BTreeMap::new(),
             };
 
@@ -1129,6 +1442,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resource_schema_constraints_roundtrip() {
+        let json = r#"{
+            "resource_type":"ec2.Vpc",
+            "attributes":{},
+            "constraints":[
+                {"type":"exactly_one_of","attributes":["cidr_block","ipv4_ipam_pool_id"]},
+                {"type":"required_with","attribute":"ipv4_ipam_pool_id","requires":["ipv4_netmask_length"]},
+                {"type":"conflicts_with","attribute":"cidr_block","conflicts":["ipv4_netmask_length"]},
+                {"type":"cidr_within_reference","attribute":"cidr_block","reference":"vpc_id","referenced_attribute":"cidr_block"}
+            ]
+        }"#;
+        let schema: ResourceSchema = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            schema.constraints,
+            vec![
+                AttributeConstraint::ExactlyOneOf {
+                    attributes: vec!["cidr_block".into(), "ipv4_ipam_pool_id".into()],
+                },
+                AttributeConstraint::RequiredWith {
+                    attribute: "ipv4_ipam_pool_id".into(),
+                    requires: vec!["ipv4_netmask_length".into()],
+                },
+                AttributeConstraint::ConflictsWith {
+                    attribute: "cidr_block".into(),
+                    conflicts: vec!["ipv4_netmask_length".into()],
+                },
+                AttributeConstraint::CidrWithinReference {
+                    attribute: "cidr_block".into(),
+                    reference: "vpc_id".into(),
+                    referenced_attribute: "cidr_block".into(),
+                },
+            ]
+        );
+        let back: ResourceSchema =
+            serde_json::from_str(&serde_json::to_string(&schema).unwrap()).unwrap();
+        assert_eq!(back.constraints, schema.constraints);
+    }
+
+    #[test]
+    fn diff_suppressor_wire_shape_and_unknown_fallback() {
+        let rule = DiffSuppressorRule {
+            path: "rules".into(),
+            suppressor: DiffSuppressor::IgnoreFields {
+                fields: vec!["id".into()],
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&rule).unwrap(),
+            serde_json::json!({
+                "path": "rules",
+                "suppressor": {"type": "ignore_fields", "fields": ["id"]}
+            })
+        );
+        let future: DiffSuppressor =
+            serde_json::from_str(r#"{"type":"future_suppressor"}"#).unwrap();
+        assert_eq!(future, DiffSuppressor::Unknown);
+    }
+
     #[test]
     fn resource_schema_defs_roundtrip() {
         // carina#3340: the `defs` map carries cyclic struct definitions
@@ -1144,6 +1516,8 @@ mod tests {
             operation_config: None,
             validators: vec![],
             exclusive_required: vec![],
+            constraints: vec![],
+            diff_suppressors: vec![],
             defs: std::collections::BTreeMap::from([(
                 "Statement".to_string(),
                 AttributeType::Struct {
@@ -1193,6 +1567,7 @@ mod tests {
             display_name: "Test Provider".into(),
             capabilities: vec!["normalize_desired".into(), "normalize_state".into()],
             version: "1.2.3".into(),
+            type_family: None,
         };
         let json = serde_json::to_string(&info).unwrap();
         let back: ProviderInfo = serde_json::from_str(&json).unwrap();
@@ -1208,6 +1583,33 @@ mod tests {
         let json = r#"{"name":"old","display_name":"Old Provider","version":"1.0.0"}"#;
         let info: ProviderInfo = serde_json::from_str(json).unwrap();
         assert!(info.capabilities.is_empty());
+        assert!(info.type_family.is_none());
+    }
+
+    #[test]
+    fn test_provider_capabilities_omitted_fields_are_unrestricted() {
+        let caps: ProviderCapabilities = serde_json::from_str(r#"{"max_parallel_ops":2}"#).unwrap();
+        assert_eq!(
+            caps,
+            ProviderCapabilities {
+                max_parallel_ops: Some(2),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_provider_info_type_family_round_trips() {
+        let info = ProviderInfo {
+            name: "awscc".into(),
+            display_name: "AWS Cloud Control provider".into(),
+            capabilities: vec![],
+            version: "1.0.0".into(),
+            type_family: Some("aws".into()),
+        };
+        let json = serde_json::to_string(&info).unwrap();
+        let back: ProviderInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.type_family.as_deref(), Some("aws"));
     }
 
     #[test]
@@ -1218,6 +1620,7 @@ mod tests {
                 display_name: "Test Provider".into(),
                 capabilities: vec!["normalize_desired".into()],
                 version: "1.2.3".into(),
+                type_family: None,
             },
             protocol_version: crate::PROTOCOL_VERSION,
         };
@@ -1390,6 +1793,10 @@ mod tests {
             DslTransform::HyphenToUnderscore,
             DslTransform::StripSuffix(".".to_string()),
             DslTransform::ReplaceTable(vec![("all".to_string(), "-1".to_string())]),
+            DslTransform::CanonicalCidr,
+            DslTransform::CanonicalPolicyDocument,
+            DslTransform::CanonicalJsonDocument,
+            DslTransform::Lowercase,
         ];
 
         for transform in transforms {
@@ -1437,6 +1844,29 @@ mod tests {
         let replace_table = DslTransform::ReplaceTable(vec![("-1".to_string(), "all".to_string())]);
         let result = replace_table.apply("udp");
         assert!(matches!(result, Cow::Borrowed("udp")));
+
+        let canonical_cidr = DslTransform::CanonicalCidr;
+        let result = canonical_cidr.apply("10.0.0.0/16");
+        assert!(matches!(result, Cow::Borrowed("10.0.0.0/16")));
+        assert_eq!(canonical_cidr.apply("2001:DB8:0::/32"), "2001:db8::/32");
+
+        let policy = DslTransform::CanonicalPolicyDocument;
+        assert!(matches!(
+            policy.apply("not json"),
+            Cow::Borrowed("not json")
+        ));
+        assert_eq!(
+            policy.apply(r#"{ "Statement": { "Action": ["s3:*"] } }"#),
+            r#"{"Statement":[{"Action":"s3:*"}]}"#
+        );
+
+        let json = DslTransform::CanonicalJsonDocument;
+        assert!(matches!(json.apply(r#"{"a":1}"#), Cow::Borrowed(_)));
+        assert_eq!(json.apply(r#"{ "b": [1], "a": 2 }"#), r#"{"a":2,"b":[1]}"#);
+
+        let lowercase = DslTransform::Lowercase;
+        assert!(matches!(lowercase.apply("abc"), Cow::Borrowed("abc")));
+        assert_eq!(lowercase.apply("AbC"), "abc");
     }
 
     #[test]
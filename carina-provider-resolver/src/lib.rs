@@ -2,9 +2,13 @@
 
 pub mod provider_resolver;
 pub mod revision_resolver;
+pub mod schema_manifest;
 pub mod version_resolver;
 
 pub use provider_resolver::*;
+pub use schema_manifest::{
+    SchemaDigest, SchemaManifest, SchemaSigner, SignedSchemaManifest, verify_provider_schemas,
+};
 pub use version_resolver::{
     ResolvedVersion, fetch_latest_tag, fetch_release_tags, resolve_from_tags,
 };
@@ -13,6 +13,10 @@ use time::format_description::well_known::Rfc3339;
 
 use carina_core::parser::ProviderConfig;
 
+use crate::schema_manifest::{
+    SchemaDigest, SchemaSigner, record_schema_digest, schema_manifest_path, schema_manifest_url,
+};
+
 /// Distinguishes the three shapes a lock entry can take. Encoded as a tagged
 /// enum so that invalid field combinations (e.g. `version = ""` *and*
 /// `revision = "main"`, the root cause of #2028) can't be constructed at
@@ -95,6 +99,12 @@ pub struct RegistryLock {
 pub struct LockFile {
     #[serde(default)]
     pub provider: Vec<LockEntry>,
+    /// Trusted signing keys for provider schema manifests, one per source.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema_signer: Vec<SchemaSigner>,
+    /// Digests of the verified schema manifests, one per source.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema_digest: Vec<SchemaDigest>,
 }
 
 impl LockFile {
@@ -144,6 +154,33 @@ impl LockFile {
             .find(|e| Self::sources_match(&e.source, source))
     }
 
+    /// The schema manifest signer pinned for `source`, if any.
+    pub fn find_schema_signer(&self, source: &str) -> Option<&SchemaSigner> {
+        self.schema_signer
+            .iter()
+            .find(|s| Self::sources_match(&s.source, source))
+    }
+
+    /// The schema manifest digest recorded for `source`, if any.
+    pub fn find_schema_digest(&self, source: &str) -> Option<&SchemaDigest> {
+        self.schema_digest
+            .iter()
+            .find(|d| Self::sources_match(&d.source, source))
+    }
+
+    /// Record `digest`, replacing any digest recorded for its source.
+    pub fn record_schema_digest(&mut self, digest: SchemaDigest) {
+        if let Some(existing) = self
+            .schema_digest
+            .iter_mut()
+            .find(|d| Self::sources_match(&d.source, &digest.source))
+        {
+            *existing = digest;
+        } else {
+            self.schema_digest.push(digest);
+        }
+    }
+
     /// Find a revision-mode entry whose `resolved_sha` matches. Version and
     /// file entries can't have a resolved SHA, so they never match.
     pub fn find_by_source_and_sha(&self, source: &str, sha: &str) -> Option<&LockEntry> {
@@ -726,6 +763,31 @@ fn download_to_file(url: &str, dest: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Download the schema manifest published next to `binary_url`, if the
+/// release ships one. A missing manifest is not an error here:
+/// [`crate::verify_provider_schemas`] decides at load whether one was
+/// required.
+fn fetch_schema_manifest<H: RegistryHttp>(http: &H, binary_url: &str, binary_path: &Path) {
+    let manifest_path = schema_manifest_path(binary_path);
+    let Some(url) = schema_manifest_url(binary_url) else {
+        return;
+    };
+    if manifest_path.exists() {
+        return;
+    }
+    if http.download_to_file(&url, &manifest_path).is_err() {
+        let _ = fs::remove_file(&manifest_path);
+    }
+}
+
+/// Link or copy the schema manifest of `from` next to `to`, when there is one.
+fn copy_schema_manifest(from: &Path, to: &Path) {
+    let (from, to) = (schema_manifest_path(from), schema_manifest_path(to));
+    if from.exists() && !to.exists() {
+        let _ = fs::hard_link(&from, &to).or_else(|_| fs::copy(&from, &to).map(|_| ()));
+    }
+}
+
 /// Extract a tar.gz archive. Returns the path to the extracted binary.
 fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<PathBuf, String> {
     let file = fs::File::open(archive_path)
@@ -940,6 +1002,9 @@ fn resolve_registry_provider_with_http<H: RegistryHttp>(
         }
     }
 
+    fetch_schema_manifest(http, &download.download_url, &wasm_path);
+    record_schema_digest(lock_file, &source.source_key(), &wasm_path)?;
+
     lock_file.upsert(LockEntry {
         name: name.to_string(),
         source: source.source_key(),
@@ -1025,6 +1090,7 @@ fn resolve_provider_with_http<H: RegistryHttp>(
     let wasm_path = cache_path_wasm(base_dir, source, version);
     if wasm_path.exists() {
         verify_or_record_version_cache(&wasm_path, source, version, name, lock_file)?;
+        record_schema_digest(lock_file, source, &wasm_path)?;
         return Ok(wasm_path);
     }
 
@@ -1046,6 +1112,7 @@ fn resolve_provider_with_http<H: RegistryHttp>(
         fs::hard_link(&global_wasm, &wasm_path)
             .or_else(|_| fs::copy(&global_wasm, &wasm_path).map(|_| ()))
             .map_err(|e| format!("Failed to link/copy from global cache: {e}"))?;
+        copy_schema_manifest(&global_wasm, &wasm_path);
         let hash =
             sha256_file(&wasm_path).map_err(|e| format!("Failed to hash WASM binary: {e}"))?;
         lock_file.upsert(LockEntry {
@@ -1058,6 +1125,7 @@ fn resolve_provider_with_http<H: RegistryHttp>(
             sha256: hash,
             registry: None,
         });
+        record_schema_digest(lock_file, source, &wasm_path)?;
         eprintln!(
             "Installed WASM provider '{}' from global cache ({}@{})",
             name, source, version
@@ -1068,8 +1136,9 @@ fn resolve_provider_with_http<H: RegistryHttp>(
     // 4. Try downloading WASM first (platform-independent).
     let wasm_url = download_url_wasm(source, version)?;
     eprintln!("Downloading WASM provider '{}' from {}", name, wasm_url);
-    match download_to_file(&wasm_url, &wasm_path) {
+    match http.download_to_file(&wasm_url, &wasm_path) {
         Ok(()) => {
+            fetch_schema_manifest(http, &wasm_url, &wasm_path);
             let hash =
                 sha256_file(&wasm_path).map_err(|e| format!("Failed to hash WASM binary: {e}"))?;
             lock_file.upsert(LockEntry {
@@ -1089,7 +1158,9 @@ fn resolve_provider_with_http<H: RegistryHttp>(
                 }
                 let _ = fs::hard_link(&wasm_path, &global_wasm)
                     .or_else(|_| fs::copy(&wasm_path, &global_wasm).map(|_| ()));
+                copy_schema_manifest(&wasm_path, &global_wasm);
             }
+            record_schema_digest(lock_file, source, &wasm_path)?;
             eprintln!(
                 "Installed WASM provider '{}' ({}@{})",
                 name, source, version
@@ -1886,6 +1957,7 @@ mod tests {
             revision: revision.map(|r| r.into()),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             unresolved_attributes: IndexMap::new(),
             binding: None,
             is_default: true,
@@ -2099,6 +2171,8 @@ mod tests {
     fn version_mode_toml_roundtrip() {
         let source = "github.com/carina-rs/carina-provider-aws";
         let lock = LockFile {
+            schema_signer: vec![],
+            schema_digest: vec![],
             provider: vec![LockEntry {
                 name: "aws".into(),
                 source: source.into(),
@@ -2125,6 +2199,8 @@ mod tests {
     #[test]
     fn registry_revision_mode_toml_roundtrip() {
         let lock = LockFile {
+            schema_signer: vec![],
+            schema_digest: vec![],
             provider: vec![LockEntry {
                 name: "aws".into(),
                 source: "carina-rs/aws".into(),
@@ -2155,6 +2231,8 @@ mod tests {
     #[test]
     fn revision_mode_toml_roundtrip() {
         let lock = LockFile {
+            schema_signer: vec![],
+            schema_digest: vec![],
             provider: vec![revision_entry(
                 "github.com/carina-rs/carina-provider-awscc",
                 "main",
@@ -2178,6 +2256,8 @@ mod tests {
     #[test]
     fn file_mode_toml_roundtrip() {
         let lock = LockFile {
+            schema_signer: vec![],
+            schema_digest: vec![],
             provider: vec![LockEntry {
                 name: "test".into(),
                 source: "file:///tmp/my-provider.wasm".into(),
@@ -2400,6 +2480,7 @@ sha256 = "abc"
             is_default: true,
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
         }];
 
         let result = resolve_all(tmp.path(), &providers, LockMode::Normal).unwrap();
@@ -2428,6 +2509,7 @@ sha256 = "abc"
             is_default: true,
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
         }];
         let err = resolve_all(tmp.path(), &providers, LockMode::Normal).unwrap_err();
         assert!(err.contains("not found"));
@@ -2574,6 +2656,64 @@ sha256 = "abc"
         assert!(registry.signature_present);
     }
 
+    #[test]
+    fn resolve_installs_schema_manifest_and_records_its_digest() {
+        use crate::schema_manifest::test_support::signed;
+        use crate::schema_manifest::{SchemaManifest, SchemaSigner};
+
+        let dir = tempfile::tempdir().unwrap();
+        let body = b"registry wasm bytes";
+        let manifest = SchemaManifest::from_schemas_json(
+            "aws",
+            "0.5.0",
+            r#"[{"resource_type": "s3.Bucket", "attributes": {}}]"#,
+        )
+        .unwrap();
+        let (signed_manifest, public_key) = signed(manifest.clone());
+        let http = registry_http(body, &sha256_bytes(body)).downloadable_bytes(
+            "https://downloads.example.test/aws.manifest.json",
+            serde_json::to_string(&signed_manifest).unwrap().as_bytes(),
+        );
+        let mut lock_file = LockFile {
+            schema_signer: vec![SchemaSigner {
+                source: "carina-rs/aws".to_string(),
+                public_key,
+            }],
+            ..Default::default()
+        };
+
+        let path = resolve_provider_with_http(
+            dir.path(),
+            "carina-rs/aws",
+            "0.5.0",
+            "aws",
+            &mut lock_file,
+            &http,
+        )
+        .unwrap();
+
+        assert!(schema_manifest_path(&path).exists());
+        let recorded = lock_file.find_schema_digest("carina-rs/aws").unwrap();
+        assert_eq!(recorded.version, "0.5.0");
+        assert_eq!(recorded.digest, manifest.digest());
+
+        // A manifest republished under the same version no longer matches.
+        lock_file.record_schema_digest(SchemaDigest {
+            digest: "0".repeat(64),
+            ..recorded.clone()
+        });
+        let err = resolve_provider_with_http(
+            dir.path(),
+            "carina-rs/aws",
+            "0.5.0",
+            "aws",
+            &mut lock_file,
+            &http,
+        )
+        .unwrap_err();
+        assert!(err.contains("carina-providers.lock records"), "{err}");
+    }
+
     #[test]
     fn registry_revision_resolves_to_version_download_and_records_revision_lock() {
         let dir = tempfile::tempdir().unwrap();
@@ -2609,6 +2749,7 @@ sha256 = "abc"
             revision: Some("main".into()),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             unresolved_attributes: IndexMap::new(),
             binding: None,
             is_default: true,
@@ -2690,6 +2831,7 @@ sha256 = "abc"
             revision: Some("main".into()),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             unresolved_attributes: IndexMap::new(),
             binding: None,
             is_default: true,
@@ -2764,6 +2906,7 @@ sha256 = "abc"
             revision: Some("main".into()),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             unresolved_attributes: IndexMap::new(),
             binding: None,
             is_default: true,
@@ -2786,6 +2929,7 @@ sha256 = "abc"
             revision: Some("main".into()),
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
             unresolved_attributes: IndexMap::new(),
             binding: None,
             is_default: true,
@@ -3635,6 +3779,7 @@ sha256 = "abc"
             is_default: true,
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
         }
     }
 
@@ -3885,6 +4030,7 @@ sha256 = "abc"
             is_default: true,
             attributes: IndexMap::new(),
             default_tags: IndexMap::new(),
+            provenance_tags: false,
         };
         assert!(check_lock_mismatch(&[cfg], &lock, LockMode::Normal).is_ok());
     }
@@ -0,0 +1,485 @@
+//! Signed provider schema manifests.
+//!
+//! A provider release may ship a manifest next to its WASM component that
+//! lists a SHA-256 hash for every resource schema it exports, together with
+//! the provider name and version, signed with the publisher's Ed25519 key.
+//! At load time the host recomputes the hashes from the schemas the
+//! component actually returned and checks them against the signed list, so
+//! an apply can prove which schema definitions were in effect.
+//!
+//! The trusted key is pinned per provider source in `carina-providers.lock`
+//! (`[[schema_signer]]`). Providers with neither a manifest nor a pinned key
+//! load unverified, as before.
+//!
+//! The resolver downloads the manifest with the binary and, once its
+//! signature checks out, records the manifest digest in the lock file
+//! (`[[schema_digest]]`). A later load must then see that exact manifest.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ring::signature::{ED25519, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use carina_core::parser::ProviderConfig;
+
+use crate::provider_resolver::LockFile;
+
+/// Hashes of every resource schema one provider version exports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaManifest {
+    pub provider: String,
+    pub version: String,
+    /// Resource type → SHA-256 of the schema's canonical JSON.
+    pub schemas: BTreeMap<String, String>,
+}
+
+impl SchemaManifest {
+    /// Build a manifest from the JSON array a provider's `schemas()` export
+    /// returns. Each schema is hashed over its canonical JSON (object keys
+    /// sorted, no whitespace), so field order on the wire does not matter.
+    pub fn from_schemas_json(provider: &str, version: &str, json: &str) -> Result<Self, String> {
+        let schemas: Vec<serde_json::Value> = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse schemas of provider '{provider}': {e}"))?;
+        let mut hashes = BTreeMap::new();
+        for schema in &schemas {
+            let resource_type = schema
+                .get("resource_type")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| {
+                    format!("Provider '{provider}' returned a schema without resource_type")
+                })?;
+            hashes.insert(
+                resource_type.to_string(),
+                sha256_hex(canonical_json(schema).as_bytes()),
+            );
+        }
+        Ok(Self {
+            provider: provider.to_string(),
+            version: version.to_string(),
+            schemas: hashes,
+        })
+    }
+
+    /// The bytes a publisher signs: the manifest's canonical JSON.
+    fn signing_payload(&self) -> String {
+        let value = serde_json::to_value(self).expect("SchemaManifest serializes to JSON");
+        canonical_json(&value)
+    }
+
+    /// SHA-256 of the signed payload, identifying this exact set of schemas.
+    pub fn digest(&self) -> String {
+        sha256_hex(self.signing_payload().as_bytes())
+    }
+
+    /// Resource types whose hash differs between `self` and `loaded`,
+    /// including types present on only one side.
+    fn mismatched_types(&self, loaded: &SchemaManifest) -> Vec<String> {
+        let mut types: Vec<String> = self
+            .schemas
+            .iter()
+            .filter(|(t, hash)| loaded.schemas.get(*t) != Some(*hash))
+            .map(|(t, _)| t.clone())
+            .collect();
+        types.extend(
+            loaded
+                .schemas
+                .keys()
+                .filter(|t| !self.schemas.contains_key(*t))
+                .cloned(),
+        );
+        types.sort();
+        types
+    }
+}
+
+/// A [`SchemaManifest`] with the publisher's detached Ed25519 signature.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedSchemaManifest {
+    pub manifest: SchemaManifest,
+    /// Hex-encoded Ed25519 signature over the manifest's canonical JSON.
+    pub signature: String,
+}
+
+impl SignedSchemaManifest {
+    /// Load the manifest shipped next to `binary_path`, or `None` when the
+    /// provider does not ship one.
+    pub fn load_for_binary(binary_path: &Path) -> Result<Option<Self>, String> {
+        let path = schema_manifest_path(binary_path);
+        let content = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    /// Check the signature against a hex-encoded Ed25519 public key.
+    pub fn verify_signature(&self, public_key: &str) -> Result<(), String> {
+        let key = decode_hex(public_key)
+            .ok_or_else(|| format!("Invalid schema signing key '{public_key}'"))?;
+        let signature = decode_hex(&self.signature)
+            .ok_or_else(|| "Schema manifest signature is not valid hex".to_string())?;
+        UnparsedPublicKey::new(&ED25519, key)
+            .verify(self.manifest.signing_payload().as_bytes(), &signature)
+            .map_err(|_| {
+                format!(
+                    "Schema manifest for provider '{}' {} has an invalid signature",
+                    self.manifest.provider, self.manifest.version
+                )
+            })
+    }
+
+    /// Check that the signed manifest describes exactly the schemas the
+    /// provider returned at load.
+    pub fn verify_loaded(&self, loaded: &SchemaManifest) -> Result<(), String> {
+        let signed = &self.manifest;
+        if signed.provider != loaded.provider || signed.version != loaded.version {
+            return Err(format!(
+                "Schema manifest is for provider '{}' {}, but the loaded provider is '{}' {}",
+                signed.provider, signed.version, loaded.provider, loaded.version
+            ));
+        }
+        let mismatched = signed.mismatched_types(loaded);
+        if !mismatched.is_empty() {
+            return Err(format!(
+                "Provider '{}' {} returned schemas that differ from its signed manifest: {}",
+                loaded.provider,
+                loaded.version,
+                mismatched.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A trusted schema signing key pinned for one provider source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaSigner {
+    pub source: String,
+    /// Hex-encoded Ed25519 public key.
+    pub public_key: String,
+}
+
+/// The digest of a provider's verified schema manifest, as recorded in
+/// `carina-providers.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDigest {
+    pub source: String,
+    /// Provider version the signed manifest names.
+    pub version: String,
+    /// [`SchemaManifest::digest`] of that manifest.
+    pub digest: String,
+}
+
+/// URL of the manifest published next to a WASM release asset
+/// (`…/carina-provider-aws-v1.0.0.wasm` → `…/carina-provider-aws-v1.0.0.manifest.json`).
+pub(crate) fn schema_manifest_url(binary_url: &str) -> Option<String> {
+    binary_url
+        .strip_suffix(".wasm")
+        .map(|stem| format!("{stem}.manifest.json"))
+}
+
+/// Check the manifest installed next to `binary_path` against the signer
+/// pinned for `source` and record its digest in `lock_file`.
+///
+/// Does nothing when no signer is pinned or no manifest was installed;
+/// [`verify_provider_schemas`] reports those at load. Fails when the lock
+/// already records a different digest for the same provider version.
+pub(crate) fn record_schema_digest(
+    lock_file: &mut LockFile,
+    source: &str,
+    binary_path: &Path,
+) -> Result<(), String> {
+    let Some(signer) = lock_file.find_schema_signer(source).cloned() else {
+        return Ok(());
+    };
+    let Some(signed) = SignedSchemaManifest::load_for_binary(binary_path)? else {
+        return Ok(());
+    };
+    signed.verify_signature(&signer.public_key)?;
+    let digest = SchemaDigest {
+        source: source.to_string(),
+        version: signed.manifest.version.clone(),
+        digest: signed.manifest.digest(),
+    };
+    check_recorded_digest(lock_file, &digest)?;
+    lock_file.record_schema_digest(digest);
+    Ok(())
+}
+
+/// Fail when `lock_file` records a different digest for the same source
+/// and version: the publisher's manifest changed after it was locked.
+fn check_recorded_digest(lock_file: &LockFile, digest: &SchemaDigest) -> Result<(), String> {
+    match lock_file.find_schema_digest(&digest.source) {
+        Some(recorded)
+            if recorded.version == digest.version && recorded.digest != digest.digest =>
+        {
+            Err(format!(
+                "Schema manifest digest for '{}' {} is {}, but carina-providers.lock records {}",
+                digest.source, digest.version, digest.digest, recorded.digest
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Path of the signed schema manifest shipped alongside a provider binary
+/// (`carina-provider-aws.wasm` → `carina-provider-aws.manifest.json`).
+pub fn schema_manifest_path(binary_path: &Path) -> PathBuf {
+    binary_path.with_extension("manifest.json")
+}
+
+/// Verify a freshly loaded provider's schemas against its signed manifest.
+///
+/// `schemas_json` is the raw output of the component's `schemas()` export.
+/// Returns the verified manifest, or `None` when the provider ships no
+/// manifest and no signer is pinned for its source. A manifest without a
+/// pinned signer, or a pinned signer without a manifest, is an error: the
+/// first cannot be trusted and the second means the manifest went missing.
+pub fn verify_provider_schemas(
+    base_dir: &Path,
+    config: &ProviderConfig,
+    binary_path: &Path,
+    version: &str,
+    schemas_json: &str,
+) -> Result<Option<SchemaManifest>, String> {
+    let lock = match config.source.as_deref() {
+        Some(_) => LockFile::load(&base_dir.join("carina-providers.lock"))?,
+        None => None,
+    };
+    let signer = lock
+        .as_ref()
+        .zip(config.source.as_deref())
+        .and_then(|(lock, source)| lock.find_schema_signer(source).cloned());
+    let signed = SignedSchemaManifest::load_for_binary(binary_path)?;
+
+    let (signed, signer) = match (signed, signer) {
+        (None, None) => return Ok(None),
+        (Some(_), None) => {
+            return Err(format!(
+                "Provider '{}' ships a signed schema manifest, but no schema_signer is pinned \
+                 for its source in carina-providers.lock",
+                config.name
+            ));
+        }
+        (None, Some(_)) => {
+            return Err(format!(
+                "Provider '{}' has a pinned schema_signer, but {} is missing",
+                config.name,
+                schema_manifest_path(binary_path).display()
+            ));
+        }
+        (Some(signed), Some(signer)) => (signed, signer),
+    };
+
+    signed.verify_signature(&signer.public_key)?;
+    let loaded = SchemaManifest::from_schemas_json(&config.name, version, schemas_json)?;
+    signed.verify_loaded(&loaded)?;
+    if let Some(lock) = &lock {
+        check_recorded_digest(
+            lock,
+            &SchemaDigest {
+                source: signer.source.clone(),
+                version: signed.manifest.version.clone(),
+                digest: signed.manifest.digest(),
+            },
+        )?;
+    }
+    Ok(Some(signed.manifest))
+}
+
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(k, v)| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::String(k.clone()),
+                        canonical_json(v)
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Sign `manifest` with a fixed test key; returns it with the
+    /// hex-encoded public key to pin.
+    pub(crate) fn signed(manifest: SchemaManifest) -> (SignedSchemaManifest, String) {
+        let key = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let signature = hex(key.sign(manifest.signing_payload().as_bytes()).as_ref());
+        let public_key = hex(key.public_key().as_ref());
+        (
+            SignedSchemaManifest {
+                manifest,
+                signature,
+            },
+            public_key,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indexmap::IndexMap;
+
+    const SCHEMAS: &str = r#"[
+        {"resource_type": "s3.Bucket", "attributes": {"bucket": {"required": true}}},
+        {"resource_type": "ec2.Vpc", "attributes": {}}
+    ]"#;
+
+    use super::test_support::signed;
+
+    #[test]
+    fn hashes_ignore_field_order() {
+        let reordered = r#"[
+            {"attributes": {}, "resource_type": "ec2.Vpc"},
+            {"attributes": {"bucket": {"required": true}}, "resource_type": "s3.Bucket"}
+        ]"#;
+        let a = SchemaManifest::from_schemas_json("aws", "1.0.0", SCHEMAS).unwrap();
+        let b = SchemaManifest::from_schemas_json("aws", "1.0.0", reordered).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.digest(), b.digest());
+        assert_eq!(a.schemas.len(), 2);
+    }
+
+    #[test]
+    fn valid_signature_and_matching_schemas_verify() {
+        let loaded = SchemaManifest::from_schemas_json("aws", "1.0.0", SCHEMAS).unwrap();
+        let (signed, key) = signed(loaded.clone());
+        signed.verify_signature(&key).unwrap();
+        signed.verify_loaded(&loaded).unwrap();
+    }
+
+    #[test]
+    fn tampered_manifest_fails_signature() {
+        let loaded = SchemaManifest::from_schemas_json("aws", "1.0.0", SCHEMAS).unwrap();
+        let (mut signed, key) = signed(loaded);
+        signed.manifest.version = "1.0.1".to_string();
+        let err = signed.verify_signature(&key).unwrap_err();
+        assert!(err.contains("invalid signature"), "{err}");
+    }
+
+    #[test]
+    fn changed_schema_is_named() {
+        let (signed, _) =
+            signed(SchemaManifest::from_schemas_json("aws", "1.0.0", SCHEMAS).unwrap());
+        let changed = r#"[
+            {"resource_type": "s3.Bucket", "attributes": {"bucket": {"required": false}}},
+            {"resource_type": "ec2.Vpc", "attributes": {}}
+        ]"#;
+        let loaded = SchemaManifest::from_schemas_json("aws", "1.0.0", changed).unwrap();
+        let err = signed.verify_loaded(&loaded).unwrap_err();
+        assert!(err.ends_with(": s3.Bucket"), "{err}");
+    }
+
+    #[test]
+    fn verify_requires_pinned_signer_and_manifest_together() {
+        let tmp = tempfile::tempdir().unwrap();
+        let binary = tmp.path().join("carina-provider-aws.wasm");
+        let config = ProviderConfig {
+            name: "aws".to_string(),
+            source: Some("github.com/carina-rs/carina-provider-aws".to_string()),
+            version: None,
+            revision: None,
+            attributes: IndexMap::new(),
+            default_tags: IndexMap::new(),
+            provenance_tags: false,
+            unresolved_attributes: IndexMap::new(),
+            binding: None,
+            is_default: true,
+        };
+
+        // Neither: loads unverified.
+        assert_eq!(
+            verify_provider_schemas(tmp.path(), &config, &binary, "1.0.0", SCHEMAS).unwrap(),
+            None
+        );
+
+        // Manifest without a pinned signer is rejected.
+        let manifest = SchemaManifest::from_schemas_json("aws", "1.0.0", SCHEMAS).unwrap();
+        let (signed, key) = signed(manifest.clone());
+        fs::write(
+            schema_manifest_path(&binary),
+            serde_json::to_string(&signed).unwrap(),
+        )
+        .unwrap();
+        let err =
+            verify_provider_schemas(tmp.path(), &config, &binary, "1.0.0", SCHEMAS).unwrap_err();
+        assert!(err.contains("no schema_signer is pinned"), "{err}");
+
+        // Pinning the key makes it verify.
+        let mut lock = LockFile {
+            schema_signer: vec![SchemaSigner {
+                source: "github.com/carina-rs/carina-provider-aws".to_string(),
+                public_key: key,
+            }],
+            ..Default::default()
+        };
+        lock.save(&tmp.path().join("carina-providers.lock"))
+            .unwrap();
+        assert_eq!(
+            verify_provider_schemas(tmp.path(), &config, &binary, "1.0.0", SCHEMAS).unwrap(),
+            Some(manifest.clone())
+        );
+
+        // A manifest whose digest differs from the one locked at install
+        // is rejected.
+        lock.record_schema_digest(SchemaDigest {
+            source: "github.com/carina-rs/carina-provider-aws".to_string(),
+            version: "1.0.0".to_string(),
+            digest: "0".repeat(64),
+        });
+        lock.save(&tmp.path().join("carina-providers.lock"))
+            .unwrap();
+        let err =
+            verify_provider_schemas(tmp.path(), &config, &binary, "1.0.0", SCHEMAS).unwrap_err();
+        assert!(err.contains("carina-providers.lock records"), "{err}");
+
+        // A pinned signer with the manifest gone is rejected.
+        fs::remove_file(schema_manifest_path(&binary)).unwrap();
+        let err =
+            verify_provider_schemas(tmp.path(), &config, &binary, "1.0.0", SCHEMAS).unwrap_err();
+        assert!(err.contains("is missing"), "{err}");
+    }
+}
@@ -488,6 +488,24 @@ impl BackendConfig {
     pub fn is_local(&self) -> bool {
         self.backend_type == LOCAL_BACKEND_TYPE
     }
+
+    /// Human-readable location of the state this backend stores, e.g.
+    /// `s3://bucket/key` or the local file path. Recorded in the
+    /// `carina:state` provenance tag.
+    pub fn state_location(&self) -> String {
+        match self.backend_type.as_str() {
+            "s3" => format!(
+                "s3://{}/{}",
+                self.get_string("bucket").unwrap_or_default(),
+                self.get_string("key").unwrap_or_default()
+            ),
+            LOCAL_BACKEND_TYPE => self
+                .get_string("path")
+                .unwrap_or(crate::backends::LocalBackend::DEFAULT_STATE_FILE)
+                .to_string(),
+            other => other.to_string(),
+        }
+    }
 }
 
 impl From<&carina_core::parser::BackendConfig> for BackendConfig {
@@ -556,6 +574,7 @@ mod tests {
         assert_eq!(state_config.backend_type, "s3");
         assert_eq!(state_config.get_string("bucket"), Some("my-bucket"));
         assert_eq!(state_config.get_string("key"), Some("state.json"));
+        assert_eq!(state_config.state_location(), "s3://my-bucket/state.json");
     }
 
     // carina-rs/carina#2603: BackendError::Aws must surface the
@@ -0,0 +1,311 @@
+//! CloudFormation resource import files built from Carina state.
+//!
+//! `aws cloudformation create-change-set --change-set-type IMPORT` takes a
+//! list of `{ResourceType, LogicalResourceId, ResourceIdentifier}`
+//! entries. [`build_import_entries`] produces that list from report rows so
+//! Carina-managed resources can be adopted by a CloudFormation stack.
+//!
+//! State does not record CloudFormation type names or primary identifier
+//! properties. The AWS provider types with a single-property identifier
+//! are listed in [`BUILTIN_CFN_TYPES`]; a [`CfnTypeMapping`] adds or
+//! corrects types. Resources with no mapping are skipped rather than
+//! given a guessed name CloudFormation would reject.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::ReportRow;
+
+/// Providers whose resource types are CloudFormation types, and so are
+/// looked up in [`BUILTIN_CFN_TYPES`].
+const AWS_PROVIDERS: &[&str] = &["aws", "awscc"];
+
+/// Resource type, CloudFormation type name and primary identifier
+/// property for the built-in AWS resource types.
+pub const BUILTIN_CFN_TYPES: &[(&str, &str, &str)] = &[
+    ("dynamodb.Table", "AWS::DynamoDB::Table", "TableName"),
+    ("ec2.FlowLog", "AWS::EC2::FlowLog", "Id"),
+    (
+        "ec2.InternetGateway",
+        "AWS::EC2::InternetGateway",
+        "InternetGatewayId",
+    ),
+    ("ec2.NatGateway", "AWS::EC2::NatGateway", "NatGatewayId"),
+    ("ec2.RouteTable", "AWS::EC2::RouteTable", "RouteTableId"),
+    ("ec2.SecurityGroup", "AWS::EC2::SecurityGroup", "Id"),
+    ("ec2.Subnet", "AWS::EC2::Subnet", "SubnetId"),
+    ("ec2.Vpc", "AWS::EC2::VPC", "VpcId"),
+    ("ec2.VpcEndpoint", "AWS::EC2::VPCEndpoint", "Id"),
+    ("ecr.Repository", "AWS::ECR::Repository", "RepositoryName"),
+    ("iam.Role", "AWS::IAM::Role", "RoleName"),
+    ("kms.Key", "AWS::KMS::Key", "KeyId"),
+    ("lambda.Function", "AWS::Lambda::Function", "FunctionName"),
+    ("logs.LogGroup", "AWS::Logs::LogGroup", "LogGroupName"),
+    ("s3.Bucket", "AWS::S3::Bucket", "BucketName"),
+    ("sns.Topic", "AWS::SNS::Topic", "TopicArn"),
+    ("sqs.Queue", "AWS::SQS::Queue", "QueueUrl"),
+];
+
+/// One entry of a CloudFormation resource import file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CfnImportEntry {
+    pub resource_type: String,
+    pub logical_resource_id: String,
+    pub resource_identifier: BTreeMap<String, String>,
+}
+
+/// CloudFormation type name and primary identifier property for one
+/// Carina resource type.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CfnTypeOverride {
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub identifier: String,
+}
+
+/// User-supplied CloudFormation names, keyed by bare resource type
+/// (`ec2.Vpc`), that take precedence over [`BUILTIN_CFN_TYPES`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct CfnTypeMapping {
+    overrides: HashMap<String, CfnTypeOverride>,
+}
+
+impl CfnTypeMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, resource_type: impl Into<String>, mapping: CfnTypeOverride) {
+        self.overrides.insert(resource_type.into(), mapping);
+    }
+
+    /// CloudFormation type name and primary identifier property for a
+    /// resource of `provider`: the override when one exists, otherwise the
+    /// built-in entry for AWS providers. `None` when neither knows the type.
+    pub fn lookup(&self, provider: &str, resource_type: &str) -> Option<(&str, &str)> {
+        if let Some(o) = self.overrides.get(resource_type) {
+            return Some((&o.type_name, &o.identifier));
+        }
+        if !AWS_PROVIDERS.contains(&provider) {
+            return None;
+        }
+        BUILTIN_CFN_TYPES
+            .iter()
+            .find(|(ty, _, _)| *ty == resource_type)
+            .map(|(_, type_name, identifier)| (*type_name, *identifier))
+    }
+}
+
+/// A resource left out of the import file, with the reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfnImportSkip {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Build import entries for `rows`. Rows without a cloud identifier or a
+/// known CloudFormation type cannot be imported and are returned as skips.
+///
+/// Logical IDs are the row name converted to PascalCase and stripped to
+/// the alphanumerics CloudFormation allows; collisions get a numeric
+/// suffix.
+pub fn build_import_entries(
+    rows: &[ReportRow],
+    mapping: &CfnTypeMapping,
+) -> (Vec<CfnImportEntry>, Vec<CfnImportSkip>) {
+    let mut entries = Vec::new();
+    let mut skipped = Vec::new();
+    let mut used_ids = HashSet::new();
+    for row in rows {
+        let Some(identifier) = &row.identifier else {
+            skipped.push(CfnImportSkip {
+                name: row.name.clone(),
+                reason: "resource has no cloud identifier".to_string(),
+            });
+            continue;
+        };
+        let Some((type_name, identifier_property)) =
+            mapping.lookup(&row.provider, &row.resource_type)
+        else {
+            skipped.push(CfnImportSkip {
+                name: row.name.clone(),
+                reason: format!(
+                    "no CloudFormation type known for {}; add one with --cfn-mapping",
+                    row.qualified_type()
+                ),
+            });
+            continue;
+        };
+        let logical_resource_id = unique_logical_id(&row.name, &mut used_ids);
+        entries.push(CfnImportEntry {
+            resource_type: type_name.to_string(),
+            logical_resource_id,
+            resource_identifier: BTreeMap::from([(
+                identifier_property.to_string(),
+                identifier.clone(),
+            )]),
+        });
+    }
+    (entries, skipped)
+}
+
+/// Render entries as the JSON array `--resources-to-import` accepts.
+pub fn to_import_json(entries: &[CfnImportEntry]) -> String {
+    serde_json::to_string_pretty(entries).expect("import entries are always serializable")
+}
+
+/// `my_vpc` / `my-vpc` / `my.vpc` → `MyVpc`. Falls back to `Resource` when
+/// nothing alphanumeric remains.
+fn logical_id(name: &str) -> String {
+    let id: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect();
+    if id.is_empty() {
+        "Resource".to_string()
+    } else {
+        id
+    }
+}
+
+fn unique_logical_id(name: &str, used: &mut HashSet<String>) -> String {
+    let base = logical_id(name);
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{base}{n}");
+        n += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(resource_type: &str, name: &str, identifier: Option<&str>) -> ReportRow {
+        provider_row("awscc", resource_type, name, identifier)
+    }
+
+    fn provider_row(
+        provider: &str,
+        resource_type: &str,
+        name: &str,
+        identifier: Option<&str>,
+    ) -> ReportRow {
+        ReportRow {
+            provider: provider.to_string(),
+            resource_type: resource_type.to_string(),
+            name: name.to_string(),
+            identifier: identifier.map(str::to_string),
+            tags: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn logical_ids_are_pascal_case_and_unique() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_logical_id("main_vpc", &mut used), "MainVpc");
+        assert_eq!(unique_logical_id("main-vpc", &mut used), "MainVpc2");
+        assert_eq!(unique_logical_id("__", &mut used), "Resource");
+    }
+
+    #[test]
+    fn builtin_types_use_cloudformation_names() {
+        let mapping = CfnTypeMapping::new();
+        assert_eq!(
+            mapping.lookup("awscc", "ec2.Vpc"),
+            Some(("AWS::EC2::VPC", "VpcId"))
+        );
+        assert_eq!(
+            mapping.lookup("aws", "s3.Bucket"),
+            Some(("AWS::S3::Bucket", "BucketName"))
+        );
+        assert_eq!(
+            mapping.lookup("awscc", "ec2.VpcEndpoint"),
+            Some(("AWS::EC2::VPCEndpoint", "Id"))
+        );
+    }
+
+    #[test]
+    fn builtin_table_is_sorted_and_has_aws_type_names() {
+        for pair in BUILTIN_CFN_TYPES.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{} before {}", pair[0].0, pair[1].0);
+        }
+        for (ty, type_name, identifier) in BUILTIN_CFN_TYPES {
+            assert!(type_name.starts_with("AWS::"), "{ty}: {type_name}");
+            assert!(!identifier.is_empty(), "{ty}");
+        }
+    }
+
+    #[test]
+    fn non_aws_providers_have_no_builtin_types() {
+        let mapping = CfnTypeMapping::new();
+        assert_eq!(mapping.lookup("gcp", "s3.Bucket"), None);
+        let (entries, skipped) = build_import_entries(
+            &[provider_row("gcp", "storage.Bucket", "logs", Some("logs"))],
+            &mapping,
+        );
+        assert!(entries.is_empty());
+        assert_eq!(
+            skipped[0].reason,
+            "no CloudFormation type known for gcp.storage.Bucket; add one with --cfn-mapping"
+        );
+    }
+
+    #[test]
+    fn builds_entries_with_builtin_and_overridden_names() {
+        let mut mapping = CfnTypeMapping::new();
+        mapping.insert(
+            "ec2.Eip",
+            CfnTypeOverride {
+                type_name: "AWS::EC2::EIP".to_string(),
+                identifier: "AllocationId".to_string(),
+            },
+        );
+        let rows = vec![
+            row("ec2.Vpc", "main_vpc", Some("vpc-123")),
+            row("ec2.Eip", "nat_ip", Some("eipalloc-1")),
+            row("ec2.Subnet", "pending", None),
+            row("ec2.TransitGateway", "tgw", Some("tgw-1")),
+        ];
+        let (entries, skipped) = build_import_entries(&rows, &mapping);
+
+        assert_eq!(
+            to_import_json(&entries[..1]),
+            r#"[
+  {
+    "ResourceType": "AWS::EC2::VPC",
+    "LogicalResourceId": "MainVpc",
+    "ResourceIdentifier": {
+      "VpcId": "vpc-123"
+    }
+  }
+]"#
+        );
+        assert_eq!(entries[1].resource_type, "AWS::EC2::EIP");
+        assert_eq!(entries[1].resource_identifier["AllocationId"], "eipalloc-1");
+        let skipped: Vec<&str> = skipped.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(skipped, vec!["pending", "tgw"]);
+    }
+
+    #[test]
+    fn mapping_overrides_builtin_types() {
+        let mapping: CfnTypeMapping = serde_json::from_str(
+            r#"{"s3.Bucket": {"type": "AWS::S3::Bucket", "identifier": "Arn"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            mapping.lookup("awscc", "s3.Bucket"),
+            Some(("AWS::S3::Bucket", "Arn"))
+        );
+    }
+}
@@ -46,7 +46,10 @@
 pub mod backend;
 pub mod backend_lock;
 pub mod backends;
+pub mod cfn_import;
 pub mod lock;
+pub mod replica;
+pub mod report;
 pub mod state;
 
 // Re-export main types for convenience
@@ -57,8 +60,9 @@ pub use backends::{
     load_state_from_url, resolve_backend_anchored, resolve_backend_for_read,
 };
 pub use lock::LockInfo;
+pub use replica::StateReplica;
 pub use state::{
-    ApplyDecision, LoadedState, MigratedStateFile, MigrationInfo, NameOverride, ResourceState,
-    StateFile, check_and_migrate, check_and_migrate_bytes, log_state_migration_once,
+    ApplyDecision, LastOperation, LoadedState, MigratedStateFile, MigrationInfo, NameOverride,
+    ResourceState, StateFile, check_and_migrate, check_and_migrate_bytes, log_state_migration_once,
     should_apply_override,
 };
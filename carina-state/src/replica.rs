@@ -0,0 +1,163 @@
+//! Read-only local replica of a project's state.
+//!
+//! Editor features (hover, completion, reference checks) want the current
+//! state on every keystroke, but reading a remote backend on that path is
+//! slow and competes with a CLI run holding the lock. The replica is a
+//! plain copy of the last state read, kept in `.carina/` next to the
+//! project's `.crn` files. A background task calls [`StateReplica::refresh`]
+//! on a timer; every reader calls [`StateReplica::load`], which only
+//! touches the local file.
+//!
+//! Refreshing goes through [`StateBackend::read_state`], which never takes
+//! the lock, and the cache file is replaced atomically, so a reader sees
+//! either the previous copy or the new one.
+
+use std::path::{Path, PathBuf};
+
+use crate::backend::{BackendError, BackendResult, StateBackend};
+use crate::state::{StateFile, check_and_migrate};
+
+/// A project's state replica file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateReplica {
+    path: PathBuf,
+}
+
+impl StateReplica {
+    /// Replica location relative to the project directory.
+    pub const DEFAULT_CACHE_FILE: &'static str = ".carina/state-replica.json";
+
+    /// The replica for the project in `project_dir`.
+    pub fn new(project_dir: &Path) -> Self {
+        Self::with_path(project_dir.join(Self::DEFAULT_CACHE_FILE))
+    }
+
+    pub fn with_path(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The last replicated state. `None` when the replica has not been
+    /// written yet or cannot be parsed; callers treat that as "state
+    /// unknown" rather than an error.
+    pub fn load(&self) -> Option<StateFile> {
+        let content = std::fs::read_to_string(&self.path).ok()?;
+        check_and_migrate(&content).ok().map(|m| m.into_state())
+    }
+
+    /// Read the state from `backend` without locking and replace the
+    /// replica with it. A backend with no state removes the replica, so
+    /// readers stop seeing resources that were destroyed.
+    pub async fn refresh(&self, backend: &dyn StateBackend) -> BackendResult<Option<StateFile>> {
+        let Some(state) = backend.read_state().await?.map(|s| s.into_state()) else {
+            match tokio::fs::remove_file(&self.path).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(BackendError::Io(format!(
+                        "Failed to remove state replica: {}",
+                        err
+                    )));
+                }
+            }
+            return Ok(None);
+        };
+
+        let content = carina_core::utils::pretty_with_newline(&state).map_err(|e| {
+            BackendError::Serialization(format!("Failed to serialize state replica: {}", e))
+        })?;
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                BackendError::Io(format!("Failed to create state replica directory: {}", e))
+            })?;
+        }
+        let tmp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, content.as_bytes())
+            .await
+            .map_err(|e| BackendError::Io(format!("Failed to write state replica: {}", e)))?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|e| BackendError::Io(format!("Failed to replace state replica: {}", e)))?;
+        Ok(Some(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::LocalBackend;
+    use crate::state::ResourceState;
+
+    fn state_with_bucket() -> StateFile {
+        let mut state = StateFile::new();
+        let mut bucket = ResourceState::new("s3.Bucket", "logs", "aws");
+        bucket.identifier = Some("logs-bucket".to_string());
+        bucket.binding = Some("logs".to_string());
+        bucket
+            .attributes
+            .insert("bucket_name".to_string(), serde_json::json!("logs-bucket"));
+        state.resources.push(bucket);
+        state
+    }
+
+    #[tokio::test]
+    async fn refresh_copies_backend_state_into_replica() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LocalBackend::with_path(dir.path().join("carina.state.json"));
+        backend.write_state(&state_with_bucket()).await.unwrap();
+        let replica = StateReplica::new(dir.path());
+        assert!(replica.load().is_none());
+
+        let refreshed = replica.refresh(&backend).await.unwrap().unwrap();
+        assert_eq!(refreshed.resources.len(), 1);
+        let loaded = replica.load().expect("replica written");
+        assert_eq!(
+            loaded.resources[0].attributes["bucket_name"],
+            serde_json::json!("logs-bucket")
+        );
+        assert!(
+            dir.path()
+                .join(".carina")
+                .join("state-replica.json")
+                .exists()
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_does_not_wait_for_a_held_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = LocalBackend::with_path(dir.path().join("carina.state.json"));
+        backend.write_state(&state_with_bucket()).await.unwrap();
+        let lock = backend.acquire_lock("apply").await.unwrap();
+
+        let replica = StateReplica::new(dir.path());
+        assert!(replica.refresh(&backend).await.unwrap().is_some());
+        backend.release_lock(&lock).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn refresh_without_backend_state_removes_replica() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("carina.state.json");
+        let backend = LocalBackend::with_path(state_path.clone());
+        backend.write_state(&state_with_bucket()).await.unwrap();
+        let replica = StateReplica::new(dir.path());
+        replica.refresh(&backend).await.unwrap();
+
+        std::fs::remove_file(&state_path).unwrap();
+        assert!(replica.refresh(&backend).await.unwrap().is_none());
+        assert!(replica.load().is_none());
+        assert!(!replica.path().exists());
+    }
+
+    #[test]
+    fn unreadable_replica_loads_as_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let replica = StateReplica::with_path(dir.path().join("replica.json"));
+        std::fs::write(replica.path(), "not json").unwrap();
+        assert!(replica.load().is_none());
+    }
+}
@@ -0,0 +1,375 @@
+//! Filterable, paginated resource reports with CSV/JSON export.
+//!
+//! A report is a flat list of [`ReportRow`]s — one per resource — built
+//! from the resources a provider discovers ([`ReportRow::from_state`]) or
+//! from a [`StateFile`]. [`ReportFilter`] narrows discovery results by
+//! type glob, tag and tagged-ness, [`paginate`] splits large result sets
+//! into pages, and [`to_csv`] / [`to_json`] render rows for governance
+//! tooling that consumes them directly rather than parsing CLI text.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+use carina_core::provider::PROJECT_TAG;
+use carina_core::resource::{ConcreteValue, State, Value};
+use serde::Serialize;
+
+use crate::state::{ResourceState, StateFile};
+
+/// One resource in a report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportRow {
+    pub provider: String,
+    pub resource_type: String,
+    /// Binding when the resource has one, otherwise its identity.
+    pub name: String,
+    /// Cloud-side identifier, if the resource exists.
+    pub identifier: Option<String>,
+    /// String-valued entries of the resource's `tags` attribute.
+    pub tags: BTreeMap<String, String>,
+}
+
+impl ReportRow {
+    pub fn from_resource_state(rs: &ResourceState) -> Self {
+        let tags = rs
+            .attributes
+            .get("tags")
+            .and_then(serde_json::Value::as_object)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            provider: rs.provider.clone(),
+            resource_type: rs.resource_type.clone(),
+            name: rs.binding.clone().unwrap_or_else(|| rs.identity.clone()),
+            identifier: rs.identifier.clone(),
+            tags,
+        }
+    }
+
+    /// Row for a resource a provider discovered. Such a resource may have
+    /// no DSL name, in which case its identifier stands in. The
+    /// [`PROJECT_TAG`] it was discovered by is left out of `tags`, so an
+    /// otherwise untagged resource counts as untagged.
+    pub fn from_state(state: &State) -> Self {
+        let tags = match state.attributes.get("tags") {
+            Some(Value::Concrete(ConcreteValue::Map(tags))) => tags
+                .iter()
+                .filter(|(k, _)| k.as_str() != PROJECT_TAG)
+                .filter_map(|(k, v)| match v {
+                    Value::Concrete(ConcreteValue::String(v)) => Some((k.clone(), v.clone())),
+                    _ => None,
+                })
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+        Self {
+            provider: state.id.provider.clone(),
+            resource_type: state.id.resource_type.clone(),
+            name: state
+                .id
+                .identity_str()
+                .or(state.identifier.as_deref())
+                .unwrap_or_default()
+                .to_string(),
+            identifier: state.identifier.clone(),
+            tags,
+        }
+    }
+
+    /// `provider.resource_type`, the form `--type` patterns match against.
+    pub fn qualified_type(&self) -> String {
+        format!("{}.{}", self.provider, self.resource_type)
+    }
+}
+
+/// Build report rows for every resource in `state`, in state order.
+pub fn rows_from_state(state: &StateFile) -> Vec<ReportRow> {
+    state
+        .resources
+        .iter()
+        .map(ReportRow::from_resource_state)
+        .collect()
+}
+
+/// A `key=value` tag filter. A bare `key` matches any value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagFilter {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+impl std::str::FromStr for TagFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = match s.split_once('=') {
+            Some((key, value)) => (key, Some(value.to_string())),
+            None => (s, None),
+        };
+        if key.is_empty() {
+            return Err(format!(
+                "invalid tag filter '{s}': expected KEY or KEY=VALUE"
+            ));
+        }
+        Ok(Self {
+            key: key.to_string(),
+            value,
+        })
+    }
+}
+
+impl TagFilter {
+    fn matches(&self, tags: &BTreeMap<String, String>) -> bool {
+        match (tags.get(&self.key), &self.value) {
+            (Some(actual), Some(expected)) => actual == expected,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+/// Criteria a row must satisfy to appear in a report. All criteria are
+/// ANDed; an empty filter keeps every row.
+#[derive(Debug, Clone, Default)]
+pub struct ReportFilter {
+    /// Glob patterns (`*` wildcard) matched against either the bare
+    /// resource type (`ec2.*`) or `provider.resource_type` (`aws.ec2.*`).
+    /// A row matches if any pattern does.
+    pub types: Vec<String>,
+    /// Tags the row must carry. A row must match every filter.
+    pub tags: Vec<TagFilter>,
+    /// Keep only rows with no tags at all.
+    pub untagged_only: bool,
+}
+
+impl ReportFilter {
+    pub fn matches(&self, row: &ReportRow) -> bool {
+        let type_ok = self.types.is_empty()
+            || self.types.iter().any(|pattern| {
+                glob_match(pattern, &row.resource_type)
+                    || glob_match(pattern, &row.qualified_type())
+            });
+        type_ok
+            && self.tags.iter().all(|tag| tag.matches(&row.tags))
+            && (!self.untagged_only || row.tags.is_empty())
+    }
+
+    pub fn apply(&self, rows: Vec<ReportRow>) -> Vec<ReportRow> {
+        rows.into_iter().filter(|row| self.matches(row)).collect()
+    }
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of
+/// characters (including none). No other metacharacters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` in the pattern: exact match.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// One page of a paginated report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportPage<'a, T> {
+    /// 1-based page number.
+    pub page: usize,
+    pub total_pages: usize,
+    pub total_rows: usize,
+    pub rows: &'a [T],
+}
+
+/// Slice `rows` into the 1-based `page` of `page_size` rows. A page past
+/// the end yields an empty slice rather than an error so scripts can loop
+/// until `rows` is empty.
+pub fn paginate<T>(rows: &[T], page_size: NonZeroUsize, page: NonZeroUsize) -> ReportPage<'_, T> {
+    let (page_size, page) = (page_size.get(), page.get());
+    let start = (page - 1).saturating_mul(page_size).min(rows.len());
+    let end = start.saturating_add(page_size).min(rows.len());
+    ReportPage {
+        page,
+        total_pages: rows.len().div_ceil(page_size),
+        total_rows: rows.len(),
+        rows: &rows[start..end],
+    }
+}
+
+/// Render rows as CSV with a header line. Tags are flattened to
+/// `key=value` pairs joined by `;`.
+pub fn to_csv(rows: &[ReportRow]) -> String {
+    let mut out = String::from("provider,resource_type,name,identifier,tags\n");
+    for row in rows {
+        let tags = row
+            .tags
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join(";");
+        let fields = [
+            row.provider.as_str(),
+            row.resource_type.as_str(),
+            row.name.as_str(),
+            row.identifier.as_deref().unwrap_or(""),
+            tags.as_str(),
+        ];
+        let line = fields.map(csv_field).join(",");
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a delimiter, quote or
+/// line break.
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render rows, or a page of them with its pagination metadata, as
+/// pretty-printed JSON.
+pub fn to_json<T: Serialize + ?Sized>(report: &T) -> String {
+    serde_json::to_string_pretty(report).expect("report rows are always serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(resource_type: &str, name: &str, tags: &[(&str, &str)]) -> ReportRow {
+        ReportRow {
+            provider: "aws".to_string(),
+            resource_type: resource_type.to_string(),
+            name: name.to_string(),
+            identifier: Some(format!("id-{name}")),
+            tags: tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn names(rows: &[ReportRow]) -> Vec<&str> {
+        rows.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("ec2.*", "ec2.Vpc"));
+        assert!(glob_match("*.Vpc", "ec2.Vpc"));
+        assert!(glob_match("ec2.*net*", "ec2.Subnet"));
+        assert!(glob_match("ec2.Vpc", "ec2.Vpc"));
+        assert!(!glob_match("ec2.Vpc", "ec2.VpcEndpoint"));
+        assert!(!glob_match("s3.*", "ec2.Vpc"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn filter_by_type_tag_and_untagged() {
+        let rows = vec![
+            row("ec2.Vpc", "vpc", &[("team", "platform")]),
+            row("ec2.Subnet", "subnet", &[]),
+            row("s3.Bucket", "bucket", &[("team", "data")]),
+        ];
+
+        let by_type = ReportFilter {
+            types: vec!["aws.ec2.*".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(&by_type.apply(rows.clone())), ["vpc", "subnet"]);
+
+        let by_tag = ReportFilter {
+            tags: vec!["team=platform".parse().unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(names(&by_tag.apply(rows.clone())), ["vpc"]);
+
+        let by_key = ReportFilter {
+            tags: vec!["team".parse().unwrap()],
+            ..Default::default()
+        };
+        assert_eq!(names(&by_key.apply(rows.clone())), ["vpc", "bucket"]);
+
+        let untagged = ReportFilter {
+            types: vec!["ec2.*".to_string()],
+            untagged_only: true,
+            ..Default::default()
+        };
+        assert_eq!(names(&untagged.apply(rows)), ["subnet"]);
+    }
+
+    #[test]
+    fn tag_filter_rejects_empty_key() {
+        assert!("=platform".parse::<TagFilter>().is_err());
+    }
+
+    #[test]
+    fn paginate_splits_into_pages() {
+        let rows: Vec<_> = (0..5)
+            .map(|i| row("ec2.Vpc", &format!("v{i}"), &[]))
+            .collect();
+        let n = |n| NonZeroUsize::new(n).unwrap();
+        let page = paginate(&rows, n(2), n(3));
+        assert_eq!(names(page.rows), ["v4"]);
+        assert_eq!((page.total_pages, page.total_rows), (3, 5));
+        assert!(paginate(&rows, n(2), n(4)).rows.is_empty());
+    }
+
+    #[test]
+    fn discovered_resource_without_a_name_is_named_by_identifier() {
+        let state = State::existing(
+            carina_core::resource::ResourceId::with_provider("aws", "ec2.Vpc", None, None),
+            std::collections::HashMap::from([(
+                "tags".to_string(),
+                Value::Concrete(ConcreteValue::Map(
+                    [("team", "platform"), (PROJECT_TAG, "lineage-1")]
+                        .into_iter()
+                        .map(|(k, v)| {
+                            (
+                                k.to_string(),
+                                Value::Concrete(ConcreteValue::String(v.to_string())),
+                            )
+                        })
+                        .collect(),
+                )),
+            )]),
+        )
+        .with_identifier("vpc-0abc");
+        let row = ReportRow::from_state(&state);
+        assert_eq!(row.name, "vpc-0abc");
+        assert_eq!(row.qualified_type(), "aws.ec2.Vpc");
+        assert_eq!(
+            row.tags,
+            BTreeMap::from([("team".to_string(), "platform".to_string())])
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_flattens_tags() {
+        let rows = vec![row("s3.Bucket", "a,b", &[("team", "x"), ("env", "prod")])];
+        assert_eq!(
+            to_csv(&rows),
+            "provider,resource_type,name,identifier,tags\n\
+             aws,s3.Bucket,\"a,b\",\"id-a,b\",env=prod;team=x\n"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! State file structures for persisting infrastructure state
 
 use carina_core::deps::get_resource_dependencies;
+use carina_core::executor::{OperationOutcome, OperationStatus};
 use carina_core::explicit::{self, ExplicitFields};
 pub use carina_core::name_override::{ApplyDecision, NameOverride, should_apply_override};
 use carina_core::override_aware::NameOverrideSource;
@@ -11,8 +12,9 @@ use carina_core::value::{
     SecretHashContext, contains_secret, json_to_dsl_value, merge_secrets_into_provider_json,
     value_to_json,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use crate::backend::BackendError;
 
@@ -32,6 +34,12 @@ pub struct StateFile {
     /// Published exports for remote_state consumers
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub exports: HashMap<String, serde_json::Value>,
+    /// Last failed or skipped operation per resource address, kept until
+    /// an operation on that resource succeeds. Keyed by address rather
+    /// than stored on [`ResourceState`] because a failed create has no
+    /// resource row.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub last_operations: BTreeMap<String, LastOperation>,
 }
 
 impl StateFile {
@@ -59,6 +67,7 @@ impl StateFile {
             carina_version: env!("CARGO_PKG_VERSION").to_string(),
             resources: Vec::new(),
             exports: HashMap::new(),
+            last_operations: BTreeMap::new(),
         }
     }
 
@@ -114,6 +123,7 @@ impl StateFile {
             carina_version: env!("CARGO_PKG_VERSION").to_string(),
             resources: Vec::new(),
             exports: HashMap::new(),
+            last_operations: BTreeMap::new(),
         }
     }
 
@@ -403,13 +413,67 @@ impl StateFile {
         }
     }
 
-    /// Remove a resource from the state
+    /// Record the outcome of each operation from an apply. Failures and
+    /// skips replace the resource's previous entry; a success clears it.
+    pub fn record_operation_outcomes(
+        &mut self,
+        outcomes: &HashMap<ResourceId, OperationOutcome>,
+        at: DateTime<Utc>,
+    ) {
+        for (id, outcome) in outcomes {
+            let address = id.to_string();
+            if outcome.status == OperationStatus::Succeeded {
+                self.last_operations.remove(&address);
+            } else {
+                self.last_operations.insert(
+                    address,
+                    LastOperation {
+                        status: outcome.status,
+                        operation: outcome.operation.to_string(),
+                        error_kind: outcome.error_kind.map(str::to_string),
+                        message: outcome.message.clone(),
+                        request_id: outcome.request_id.clone(),
+                        timestamp: at,
+                    },
+                );
+            }
+        }
+    }
+
+    /// The recorded failure or skip for `id`, if its last operation did
+    /// not succeed.
+    pub fn last_operation(&self, id: &ResourceId) -> Option<&LastOperation> {
+        self.last_operations.get(&id.to_string())
+    }
+
+    /// Drop recorded failures for addresses that have neither a state row
+    /// nor a desired resource in `desired`. A failed create leaves an
+    /// entry with no row, which would otherwise outlive the resource's
+    /// removal from the configuration.
+    pub fn prune_last_operations(&mut self, desired: &HashSet<String>) {
+        let in_state: HashSet<String> = self
+            .resources
+            .iter()
+            .map(|r| {
+                ResourceId::with_provider_identity(&r.provider, &r.resource_type, &r.identity, None)
+                    .to_string()
+            })
+            .collect();
+        self.last_operations
+            .retain(|address, _| in_state.contains(address) || desired.contains(address));
+    }
+
+    /// Remove a resource from the state, along with its recorded last
+    /// operation.
     pub fn remove_resource(
         &mut self,
         provider: &str,
         resource_type: &str,
         identity: &str,
     ) -> Option<ResourceState> {
+        let address =
+            ResourceId::with_provider_identity(provider, resource_type, identity, None).to_string();
+        self.last_operations.remove(&address);
         if let Some(pos) = self.resources.iter().position(|r| {
             r.provider == provider && r.resource_type == resource_type && r.identity == identity
         }) {
@@ -687,6 +751,56 @@ pub fn check_and_migrate_bytes(bytes: &[u8]) -> Result<MigratedStateFile, Backen
     check_and_migrate(content)
 }
 
+/// A resource's last operation that did not succeed, as recorded in
+/// [`StateFile::last_operations`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastOperation {
+    pub status: OperationStatus,
+    /// Effect kind: `create`, `update`, or `delete`.
+    pub operation: String,
+    /// Provider error kind (`api_error`, `timeout`, ...), when the
+    /// failure came from the provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    /// Error message, or the reason the operation was skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Cloud-API request id to quote in a support ticket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl std::fmt::Display for LastOperation {
+    /// `create failed at 2026-01-02T03:04:05Z (api_error): AccessDenied [request id: req-1]`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self.status {
+            OperationStatus::Succeeded => "succeeded",
+            OperationStatus::Failed => "failed",
+            OperationStatus::Skipped => "skipped",
+        };
+        write!(
+            f,
+            "{} {} at {}",
+            self.operation,
+            status,
+            self.timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        )?;
+        if let Some(kind) = &self.error_kind {
+            write!(f, " ({kind})")?;
+        }
+        if let Some(message) = &self.message {
+            // Provider errors can span several lines; keep the summary to one.
+            write!(f, ": {}", message.lines().next().unwrap_or_default())?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, " [request id: {request_id}]")?;
+        }
+        Ok(())
+    }
+}
+
 /// State of a single managed resource
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceState {
@@ -809,6 +923,35 @@ impl ResourceState {
         self
     }
 
+    /// Move everything recorded under attribute `old` to `new`: the value,
+    /// its name prefix and override, its write-only marker, and its
+    /// authoring record. Used by `carina upgrade` when a schema renames
+    /// an attribute. Returns whether anything was moved.
+    pub fn rename_attribute(&mut self, old: &str, new: &str) -> bool {
+        fn move_key<V>(map: &mut HashMap<String, V>, old: &str, new: &str) -> bool {
+            match map.remove(old) {
+                Some(value) => {
+                    map.insert(new.to_string(), value);
+                    true
+                }
+                None => false,
+            }
+        }
+        let mut moved = move_key(&mut self.attributes, old, new);
+        moved |= move_key(&mut self.prefixes, old, new);
+        moved |= move_key(&mut self.name_overrides, old, new);
+        if let ExplicitFields::Struct { children } = &mut self.explicit {
+            moved |= move_key(children, old, new);
+        }
+        for attr in &mut self.write_only_attributes {
+            if attr == old {
+                *attr = new.to_string();
+                moved = true;
+            }
+        }
+        moved
+    }
+
     /// Build the seed `ResourceState` for a backend-owned state bucket.
     ///
     /// This is the canonical shape the state backend records when it
@@ -489,6 +489,21 @@ fn test_from_provider_state_without_existing() {
     assert_eq!(rs.identifier, Some("test-id".to_string()));
 }
 
+#[test]
+fn test_from_provider_state_does_not_inherit_recorded_identifier() {
+    use carina_core::resource::{Resource, State as ProviderState};
+
+    // The create half of a replace hands in the replaced row as
+    // `existing`; its identifier names a deleted object.
+    let resource = Resource::with_provider("awscc", "ec2.Vpc", "main", None);
+    let provider_state = ProviderState::existing(resource.id.clone(), HashMap::new());
+    let existing = ResourceState::new("ec2.Vpc", "main", "awscc").with_identifier("vpc-0abc");
+
+    let rs =
+        ResourceState::from_provider_state(&resource, &provider_state, Some(&existing)).unwrap();
+    assert_eq!(rs.identifier, None);
+}
+
 #[test]
 fn test_from_provider_state_repairs_unrecorded_from_state_attrs() {
     // carina#3280: when the prior on-disk row carries `Unrecorded` (the
@@ -1994,3 +2009,129 @@ fn missing_partial_read_field_defaults_to_none() {
     assert_eq!(state.resources.len(), 1);
     assert_eq!(state.resources[0].partial_read, None);
 }
+
+#[test]
+fn operation_outcomes_record_failures_and_clear_on_success() {
+    let id = ResourceId::with_provider_identity("aws", "s3.Bucket", "logs", None);
+    let failed = OperationOutcome {
+        status: OperationStatus::Failed,
+        operation: "create",
+        error_kind: Some("api_error"),
+        message: Some("AccessDenied".to_string()),
+        request_id: Some("req-123".to_string()),
+        duration: None,
+        metrics: None,
+    };
+    let at = DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+        .unwrap()
+        .with_timezone(&Utc);
+
+    let mut state = StateFile::new();
+    state.record_operation_outcomes(&HashMap::from([(id.clone(), failed)]), at);
+    let json = serde_json::to_string(&state).unwrap();
+    let state: StateFile = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        state.last_operation(&id),
+        Some(&LastOperation {
+            status: OperationStatus::Failed,
+            operation: "create".to_string(),
+            error_kind: Some("api_error".to_string()),
+            message: Some("AccessDenied".to_string()),
+            request_id: Some("req-123".to_string()),
+            timestamp: at,
+        })
+    );
+    assert_eq!(
+        state.last_operation(&id).unwrap().to_string(),
+        "create failed at 2026-01-02T03:04:05Z (api_error): AccessDenied [request id: req-123]"
+    );
+
+    let mut state = state;
+    let succeeded = OperationOutcome {
+        status: OperationStatus::Succeeded,
+        operation: "create",
+        error_kind: None,
+        message: None,
+        request_id: None,
+        duration: None,
+        metrics: None,
+    };
+    state.record_operation_outcomes(&HashMap::from([(id.clone(), succeeded)]), at);
+    assert_eq!(state.last_operation(&id), None);
+    assert!(
+        !serde_json::to_string(&state)
+            .unwrap()
+            .contains("last_operations")
+    );
+}
+
+#[test]
+fn last_operations_are_pruned_with_their_resources() {
+    let failed = |operation| OperationOutcome {
+        status: OperationStatus::Failed,
+        operation,
+        error_kind: None,
+        message: None,
+        request_id: None,
+        duration: None,
+        metrics: None,
+    };
+    let vpc = ResourceId::with_provider_identity("aws", "ec2.Vpc", "main", None);
+    let bucket = ResourceId::with_provider_identity("aws", "s3.Bucket", "logs", None);
+    let queue = ResourceId::with_provider_identity("aws", "sqs.Queue", "jobs", None);
+    let mut state = StateFile::new();
+    state.upsert_resource(ResourceState::new("ec2.Vpc", "main", "aws"));
+    state.record_operation_outcomes(
+        &HashMap::from([
+            (vpc.clone(), failed("update")),
+            (bucket.clone(), failed("create")),
+            (queue.clone(), failed("create")),
+        ]),
+        Utc::now(),
+    );
+
+    // `bucket` is still desired; `queue` left the configuration and never
+    // got a state row.
+    state.prune_last_operations(&HashSet::from([bucket.to_string()]));
+    assert!(state.last_operation(&vpc).is_some());
+    assert!(state.last_operation(&bucket).is_some());
+    assert!(state.last_operation(&queue).is_none());
+
+    state.remove_resource("aws", "ec2.Vpc", "main");
+    assert!(state.last_operation(&vpc).is_none());
+}
+
+#[test]
+fn rename_attribute_moves_every_record_of_the_attribute() {
+    let mut resource = ResourceState::new("ec2.Vpc", "main", "awscc")
+        .with_attribute("cidr", serde_json::json!("10.0.0.0/16"))
+        .with_attribute("tags", serde_json::json!({}));
+    resource
+        .prefixes
+        .insert("cidr".to_string(), "p-".to_string());
+    resource.write_only_attributes.push("cidr".to_string());
+    resource.explicit = ExplicitFields::Struct {
+        children: HashMap::from([("cidr".to_string(), ExplicitFields::Leaf)]),
+    };
+
+    assert!(resource.rename_attribute("cidr", "cidr_block"));
+    assert_eq!(
+        resource.attributes.get("cidr_block"),
+        Some(&serde_json::json!("10.0.0.0/16"))
+    );
+    assert!(!resource.attributes.contains_key("cidr"));
+    assert_eq!(
+        resource.prefixes.get("cidr_block").map(String::as_str),
+        Some("p-")
+    );
+    assert_eq!(
+        resource.write_only_attributes,
+        vec!["cidr_block".to_string()]
+    );
+    let ExplicitFields::Struct { children } = &resource.explicit else {
+        panic!("explicit tree replaced");
+    };
+    assert!(children.contains_key("cidr_block"));
+
+    assert!(!resource.rename_attribute("cidr", "cidr_block"));
+}
@@ -223,6 +223,25 @@ pub(super) fn render_list_of_maps_diff(
                         lines.push(Line::from(indented));
                     }
                 }
+                ListOfMapsDiffField::NestedListOfMapsChanged { key, block } => {
+                    // The nested renderer draws its own `key: [` header.
+                    lines.push(Line::from(std::mem::take(&mut spans)));
+                    let mut nested_lines = Vec::new();
+                    render_list_of_maps_diff(
+                        &mut nested_lines,
+                        key,
+                        block.unchanged(),
+                        block.modified(),
+                        block.added(),
+                        block.removed(),
+                        false,
+                    );
+                    for line in nested_lines {
+                        let mut indented = vec![Span::raw("    ")];
+                        indented.extend(line.spans);
+                        lines.push(Line::from(indented));
+                    }
+                }
             }
         }
         // #2881: surface unchanged-fields summary inside the brace block.